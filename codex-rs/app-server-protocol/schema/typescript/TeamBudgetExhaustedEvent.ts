@@ -0,0 +1,25 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ThreadId } from "./ThreadId";
+
+/**
+ * Emitted when a teammate exceeds its token or turn budget and is shut down.
+ */
+export type TeamBudgetExhaustedEvent = { 
+/**
+ * Name of the team.
+ */
+team_name: string, 
+/**
+ * Name of the teammate.
+ */
+member_name: string, 
+/**
+ * Thread ID of the teammate's session.
+ */
+thread_id: ThreadId, 
+/**
+ * Which limit was exceeded, e.g. "used 120000 of 100000 tokens".
+ */
+reason: string, };
@@ -0,0 +1,17 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ThreadId } from "./ThreadId";
+
+/**
+ * Emitted when the team is cleaned up and resources released.
+ */
+export type TeamCleanupEvent = { 
+/**
+ * Name of the team being cleaned up.
+ */
+team_name: string, 
+/**
+ * Thread ID of the leader that initiated cleanup.
+ */
+leader_thread_id: ThreadId, };
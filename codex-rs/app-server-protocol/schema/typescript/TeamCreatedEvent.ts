@@ -0,0 +1,26 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TeamDisplayMode } from "./TeamDisplayMode";
+import type { ThreadId } from "./ThreadId";
+
+/**
+ * Emitted when a new agent team is created.
+ */
+export type TeamCreatedEvent = { 
+/**
+ * Unique name identifying this team.
+ */
+team_name: string, 
+/**
+ * Thread ID of the team leader.
+ */
+leader_thread_id: ThreadId, 
+/**
+ * Short description of the team's goal, if one was given.
+ */
+description?: string, 
+/**
+ * How clients should render teammate activity.
+ */
+display_mode: TeamDisplayMode, };
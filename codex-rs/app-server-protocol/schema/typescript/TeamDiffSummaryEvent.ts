@@ -0,0 +1,22 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TeamFileDiff } from "./TeamFileDiff";
+
+/**
+ * Emitted when a teammate finishes a turn, summarizing the changes it has
+ * made so far.
+ */
+export type TeamDiffSummaryEvent = { 
+/**
+ * Name of the team.
+ */
+team_name: string, 
+/**
+ * Name of the teammate.
+ */
+member_name: string, 
+/**
+ * Changed files, sorted by path.
+ */
+files: Array<TeamFileDiff>, };
@@ -0,0 +1,8 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How clients render teammate activity for a team.
+ */
+export type TeamDisplayMode = "inline" | "split-pane" | "quiet" | "compact";
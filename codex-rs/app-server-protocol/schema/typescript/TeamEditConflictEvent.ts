@@ -0,0 +1,17 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TeamFileConflict } from "./TeamFileConflict";
+
+/**
+ * Emitted when teammates edit files another teammate has already edited.
+ */
+export type TeamEditConflictEvent = { 
+/**
+ * Name of the team.
+ */
+team_name: string, 
+/**
+ * Newly shared files.
+ */
+conflicts: Array<TeamFileConflict>, };
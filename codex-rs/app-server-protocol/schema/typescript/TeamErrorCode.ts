@@ -0,0 +1,8 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Kind of failure of a team tool.
+ */
+export type TeamErrorCode = "invalid_arguments" | "team_not_found" | "member_not_found" | "task_not_found" | "patch_not_found" | "not_leader" | "budget_exceeded" | "limit_reached" | "spawn_failed" | "storage_corrupt" | "storage_error" | "other";
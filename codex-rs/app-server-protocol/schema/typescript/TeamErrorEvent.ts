@@ -0,0 +1,21 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TeamErrorCode } from "./TeamErrorCode";
+
+/**
+ * Emitted alongside the error a team tool reports to the model.
+ */
+export type TeamErrorEvent = { 
+/**
+ * Name of the team tool that failed.
+ */
+tool_name: string, 
+/**
+ * Team named in the call's arguments, if any.
+ */
+team_name?: string, code: TeamErrorCode, 
+/**
+ * The error as reported to the model.
+ */
+message: string, };
@@ -0,0 +1,28 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Emitted when a teammate calls `escalate_to_user` to ask the user a question.
+ */
+export type TeamEscalationEvent = { 
+/**
+ * Name of the team.
+ */
+team_name: string, 
+/**
+ * Id to answer the escalation with, e.g. "escalation-1".
+ */
+escalation_id: string, 
+/**
+ * Name of the teammate that asked.
+ */
+from: string, 
+/**
+ * The question for the user.
+ */
+question: string, 
+/**
+ * Suggested answers, if the teammate offered any.
+ */
+options: Array<string>, };
@@ -0,0 +1,16 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A file edited by more than one teammate.
+ */
+export type TeamFileConflict = { 
+/**
+ * Path relative to the repository root, or absolute outside a repository.
+ */
+path: string, 
+/**
+ * Teammates that edited the file, sorted by name.
+ */
+members: Array<string>, };
@@ -0,0 +1,20 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Line counts of one file changed by a teammate.
+ */
+export type TeamFileDiff = { 
+/**
+ * Path relative to the repository root.
+ */
+path: string, 
+/**
+ * Lines added; 0 for binary files.
+ */
+added: number, 
+/**
+ * Lines removed; 0 for binary files.
+ */
+removed: number, };
@@ -0,0 +1,21 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ThreadId } from "./ThreadId";
+
+/**
+ * Emitted when leadership of a team is handed to another thread.
+ */
+export type TeamLeaderChangedEvent = { 
+/**
+ * Name of the team.
+ */
+team_name: string, 
+/**
+ * Thread ID of the outgoing leader.
+ */
+previous_leader_thread_id: ThreadId, 
+/**
+ * Thread ID of the new leader.
+ */
+leader_thread_id: ThreadId, };
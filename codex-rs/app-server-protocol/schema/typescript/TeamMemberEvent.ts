@@ -0,0 +1,23 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AgentStatus } from "./AgentStatus";
+import type { TeamMemberInfo } from "./TeamMemberInfo";
+
+/**
+ * Emitted when a member is added to or removed from a team, or its status
+ * changes.
+ */
+export type TeamMemberEvent = { 
+/**
+ * Name of the team.
+ */
+team_name: string, 
+/**
+ * Information about the member.
+ */
+member: TeamMemberInfo, 
+/**
+ * Status the member had before, for `TeamMemberStatusChanged`.
+ */
+previous_status?: AgentStatus, };
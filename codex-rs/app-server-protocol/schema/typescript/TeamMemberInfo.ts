@@ -0,0 +1,26 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AgentStatus } from "./AgentStatus";
+import type { ThreadId } from "./ThreadId";
+
+/**
+ * Information about a team member.
+ */
+export type TeamMemberInfo = { 
+/**
+ * Human-readable name of the teammate (e.g. "security-reviewer").
+ */
+name: string, 
+/**
+ * Thread ID of the teammate's session.
+ */
+thread_id: ThreadId, 
+/**
+ * Assigned role or focus area.
+ */
+role?: string, 
+/**
+ * Current status of the agent.
+ */
+status: AgentStatus, };
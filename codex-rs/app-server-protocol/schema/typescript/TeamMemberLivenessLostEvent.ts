@@ -0,0 +1,27 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ThreadId } from "./ThreadId";
+
+/**
+ * Emitted in the leader's session when a teammate working on a turn has
+ * emitted no events for longer than `teams.heartbeat_timeout_secs`. The
+ * teammate is marked stale in the team config until it is heard from again.
+ */
+export type TeamMemberLivenessLostEvent = { 
+/**
+ * Name of the team.
+ */
+team_name: string, 
+/**
+ * Name of the teammate.
+ */
+member_name: string, 
+/**
+ * Thread ID of the teammate's session.
+ */
+thread_id: ThreadId, 
+/**
+ * Seconds since the teammate's last event.
+ */
+silent_secs: number, };
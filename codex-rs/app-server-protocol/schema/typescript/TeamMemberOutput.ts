@@ -0,0 +1,8 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A piece of teammate output.
+ */
+export type TeamMemberOutput = { "type": "agent_message", message: string, } | { "type": "agent_message_delta", delta: string, } | { "type": "command_begin", call_id: string, command: Array<string>, } | { "type": "command_output_delta", call_id: string, chunk: string, } | { "type": "command_end", call_id: string, exit_code: number, };
@@ -0,0 +1,23 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TeamMemberOutput } from "./TeamMemberOutput";
+import type { ThreadId } from "./ThreadId";
+
+/**
+ * Emitted in the leader's session for output of one of its teammates,
+ * as configured by `teams.teammate_output`.
+ */
+export type TeamMemberOutputEvent = { 
+/**
+ * Name of the team.
+ */
+team_name: string, 
+/**
+ * Name of the teammate.
+ */
+member_name: string, 
+/**
+ * Thread ID of the teammate's session.
+ */
+thread_id: ThreadId, output: TeamMemberOutput, };
@@ -0,0 +1,37 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ThreadId } from "./ThreadId";
+
+/**
+ * Emitted when an errored teammate is respawned by its restart policy.
+ */
+export type TeamMemberRestartedEvent = { 
+/**
+ * Name of the team.
+ */
+team_name: string, 
+/**
+ * Name of the teammate.
+ */
+member_name: string, 
+/**
+ * Thread ID of the errored session.
+ */
+previous_thread_id: ThreadId, 
+/**
+ * Thread ID of the new session.
+ */
+thread_id: ThreadId, 
+/**
+ * Which restart this was, starting at 1.
+ */
+attempt: number, 
+/**
+ * Restarts allowed by the policy.
+ */
+max_restarts: number, 
+/**
+ * Error the previous session stopped with.
+ */
+error: string, };
@@ -0,0 +1,31 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ThreadId } from "./ThreadId";
+import type { TokenUsage } from "./TokenUsage";
+
+/**
+ * Emitted in the leader's session each time the token usage of one of its
+ * teammates is updated.
+ */
+export type TeamMemberTokenUsageEvent = { 
+/**
+ * Name of the team.
+ */
+team_name: string, 
+/**
+ * Name of the teammate.
+ */
+member_name: string, 
+/**
+ * Thread ID of the teammate's session.
+ */
+thread_id: ThreadId, 
+/**
+ * Usage of the teammate's session so far.
+ */
+total_token_usage: TokenUsage, 
+/**
+ * Usage of the teammate's latest model request.
+ */
+last_token_usage: TokenUsage, };
@@ -0,0 +1,24 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Emitted when a message is sent between teammates.
+ */
+export type TeamMessageEvent = { 
+/**
+ * Name of the team.
+ */
+team_name: string, 
+/**
+ * Name of the sender.
+ */
+from: string, 
+/**
+ * Name of the recipient, or "all" for broadcasts.
+ */
+to: string, 
+/**
+ * Message content.
+ */
+content: string, };
@@ -0,0 +1,37 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Emitted in the leader's session whenever the task or teammate counts of a
+ * team change.
+ */
+export type TeamProgressEvent = { 
+/**
+ * Name of the team.
+ */
+team_name: string, 
+/**
+ * Tasks completed.
+ */
+tasks_completed: number, 
+/**
+ * Tasks in the task list, completed or not.
+ */
+tasks_total: number, 
+/**
+ * Teammates starting up or working on a turn.
+ */
+members_running: number, 
+/**
+ * Teammates that finished their turn or were shut down.
+ */
+members_idle: number, 
+/**
+ * Teammates that errored or whose session is gone.
+ */
+members_errored: number, 
+/**
+ * Seconds since the team was created.
+ */
+elapsed_secs: number, };
@@ -0,0 +1,16 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Emitted when a team is renamed.
+ */
+export type TeamRenamedEvent = { 
+/**
+ * Previous name of the team.
+ */
+old_name: string, 
+/**
+ * New name of the team.
+ */
+new_name: string, };
@@ -0,0 +1,17 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TeamTaskInfo } from "./TeamTaskInfo";
+
+/**
+ * Emitted when a task is created or its status changes.
+ */
+export type TeamTaskEvent = { 
+/**
+ * Name of the team.
+ */
+team_name: string, 
+/**
+ * The task that was created or updated.
+ */
+task: TeamTaskInfo, };
@@ -0,0 +1,42 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TeamTaskStatus } from "./TeamTaskStatus";
+
+/**
+ * A single task in the shared task list.
+ */
+export type TeamTaskInfo = { 
+/**
+ * Unique task identifier.
+ */
+id: string, 
+/**
+ * Short description of the task.
+ */
+title: string, 
+/**
+ * Longer description of the work, if one was given.
+ */
+description?: string, 
+/**
+ * Current status.
+ */
+status: TeamTaskStatus, 
+/**
+ * Name of the teammate assigned to this task, if any.
+ */
+assigned_to?: string, 
+/**
+ * Task IDs that must be completed before this task can be accepted.
+ */
+depends_on: Array<string>, 
+/**
+ * Final message of the assignee's latest turn while it worked on this
+ * task.
+ */
+result?: string, 
+/**
+ * When the task was created, RFC 3339.
+ */
+created_at?: string, };
@@ -0,0 +1,22 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TeamTaskInfo } from "./TeamTaskInfo";
+
+/**
+ * Emitted for each pending task that a completed task unblocked, i.e. whose
+ * dependencies are now all completed.
+ */
+export type TeamTaskUnblockedEvent = { 
+/**
+ * Name of the team.
+ */
+team_name: string, 
+/**
+ * The task that is now ready to be worked on.
+ */
+task: TeamTaskInfo, 
+/**
+ * Id of the completed task that unblocked it.
+ */
+unblocked_by: string, };
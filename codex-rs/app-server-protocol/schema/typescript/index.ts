@@ -199,6 +199,32 @@ export type { SkillsListEntry } from "./SkillsListEntry";
 export type { StepStatus } from "./StepStatus";
 export type { StreamErrorEvent } from "./StreamErrorEvent";
 export type { SubAgentSource } from "./SubAgentSource";
+export type { TeamBudgetExhaustedEvent } from "./TeamBudgetExhaustedEvent";
+export type { TeamCleanupEvent } from "./TeamCleanupEvent";
+export type { TeamCreatedEvent } from "./TeamCreatedEvent";
+export type { TeamDiffSummaryEvent } from "./TeamDiffSummaryEvent";
+export type { TeamDisplayMode } from "./TeamDisplayMode";
+export type { TeamEditConflictEvent } from "./TeamEditConflictEvent";
+export type { TeamErrorCode } from "./TeamErrorCode";
+export type { TeamErrorEvent } from "./TeamErrorEvent";
+export type { TeamEscalationEvent } from "./TeamEscalationEvent";
+export type { TeamFileConflict } from "./TeamFileConflict";
+export type { TeamFileDiff } from "./TeamFileDiff";
+export type { TeamLeaderChangedEvent } from "./TeamLeaderChangedEvent";
+export type { TeamMemberEvent } from "./TeamMemberEvent";
+export type { TeamMemberInfo } from "./TeamMemberInfo";
+export type { TeamMemberLivenessLostEvent } from "./TeamMemberLivenessLostEvent";
+export type { TeamMemberOutput } from "./TeamMemberOutput";
+export type { TeamMemberOutputEvent } from "./TeamMemberOutputEvent";
+export type { TeamMemberRestartedEvent } from "./TeamMemberRestartedEvent";
+export type { TeamMemberTokenUsageEvent } from "./TeamMemberTokenUsageEvent";
+export type { TeamMessageEvent } from "./TeamMessageEvent";
+export type { TeamProgressEvent } from "./TeamProgressEvent";
+export type { TeamRenamedEvent } from "./TeamRenamedEvent";
+export type { TeamTaskEvent } from "./TeamTaskEvent";
+export type { TeamTaskInfo } from "./TeamTaskInfo";
+export type { TeamTaskStatus } from "./TeamTaskStatus";
+export type { TeamTaskUnblockedEvent } from "./TeamTaskUnblockedEvent";
 export type { TerminalInteractionEvent } from "./TerminalInteractionEvent";
 export type { TextElement } from "./TextElement";
 export type { ThreadId } from "./ThreadId";
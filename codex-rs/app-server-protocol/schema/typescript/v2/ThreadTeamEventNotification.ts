@@ -0,0 +1,10 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { EventMsg } from "../EventMsg";
+
+/**
+ * EXPERIMENTAL - a `Team*` event of a team led by the thread, including the
+ * `TeamProgress` summaries and the teammate events forwarded to the leader.
+ */
+export type ThreadTeamEventNotification = { threadId: string, turnId: string, event: EventMsg, };
@@ -1689,6 +1689,66 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn generated_types_include_team_events() -> Result<()> {
+        let output_dir = std::env::temp_dir().join(format!("codex_team_types_{}", Uuid::now_v7()));
+        fs::create_dir(&output_dir)?;
+
+        struct TempDirGuard(PathBuf);
+
+        impl Drop for TempDirGuard {
+            fn drop(&mut self) {
+                let _ = fs::remove_dir_all(&self.0);
+            }
+        }
+
+        let _guard = TempDirGuard(output_dir.clone());
+
+        let options = GenerateTsOptions {
+            generate_indices: false,
+            ensure_headers: false,
+            run_prettier: false,
+            experimental_api: false,
+        };
+        generate_ts_with_options(&output_dir, None, options)?;
+
+        let event_msg_ts = fs::read_to_string(output_dir.join("EventMsg.ts"))?;
+        for (tag, event) in [
+            ("team_created", "TeamCreatedEvent"),
+            ("team_task_updated", "TeamTaskEvent"),
+            ("team_member_status_changed", "TeamMemberEvent"),
+            ("team_progress", "TeamProgressEvent"),
+            ("team_error", "TeamErrorEvent"),
+        ] {
+            assert_eq!(
+                event_msg_ts.contains(&format!("{{ \"type\": \"{tag}\" }} & {event}")),
+                true,
+                "EventMsg.ts should include {tag}"
+            );
+        }
+        for name in [
+            "TeamCreatedEvent",
+            "TeamMemberInfo",
+            "TeamTaskInfo",
+            "TeamTaskStatus",
+            "TeamDisplayMode",
+            "TeamMemberOutput",
+            "TeamErrorCode",
+        ] {
+            assert_eq!(
+                output_dir.join(format!("{name}.ts")).exists(),
+                true,
+                "{name}.ts should be generated"
+            );
+        }
+
+        let schema = write_json_schema_with_return::<EventMsg>(&output_dir, "EventMsg")?;
+        let schema_str = serde_json::to_string(&schema.value)?;
+        assert_eq!(schema_str.contains("\"team_created\""), true);
+        assert_eq!(schema_str.contains("TeamTaskInfo"), true);
+        Ok(())
+    }
+
     #[test]
     fn stable_schema_filter_removes_mock_thread_start_field() -> Result<()> {
         let output_dir = std::env::temp_dir().join(format!("codex_schema_{}", Uuid::now_v7()));
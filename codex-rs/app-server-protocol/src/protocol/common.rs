@@ -804,6 +804,8 @@ server_notification_definitions! {
     /// Deprecated: Use `ContextCompaction` item type instead.
     ContextCompacted => "thread/compacted" (v2::ContextCompactedNotification),
     ModelRerouted => "model/rerouted" (v2::ModelReroutedNotification),
+    /// EXPERIMENTAL - team events of a thread that leads an agent team.
+    ThreadTeamEvent => "thread/team/event" (v2::ThreadTeamEventNotification),
     DeprecationNotice => "deprecationNotice" (v2::DeprecationNoticeNotification),
     ConfigWarning => "configWarning" (v2::ConfigWarningNotification),
     FuzzyFileSearchSessionUpdated => "fuzzyFileSearch/sessionUpdated" (FuzzyFileSearchSessionUpdatedNotification),
@@ -838,6 +840,8 @@ mod tests {
     use codex_protocol::account::PlanType;
     use codex_protocol::parse_command::ParsedCommand;
     use codex_protocol::protocol::AskForApproval;
+    use codex_protocol::protocol::EventMsg;
+    use codex_protocol::protocol::TeamProgressEvent;
     use pretty_assertions::assert_eq;
     use serde_json::json;
     use std::path::PathBuf;
@@ -1357,6 +1361,44 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn serialize_thread_team_event_notification() -> Result<()> {
+        let notification = ServerNotification::ThreadTeamEvent(v2::ThreadTeamEventNotification {
+            thread_id: "thr_123".to_string(),
+            turn_id: "turn_1".to_string(),
+            event: EventMsg::TeamProgress(TeamProgressEvent {
+                team_name: "zeta".to_string(),
+                tasks_completed: 1,
+                tasks_total: 3,
+                members_running: 2,
+                members_idle: 0,
+                members_errored: 0,
+                elapsed_secs: 200,
+            }),
+        });
+        assert_eq!(
+            json!({
+                "method": "thread/team/event",
+                "params": {
+                    "threadId": "thr_123",
+                    "turnId": "turn_1",
+                    "event": {
+                        "type": "team_progress",
+                        "team_name": "zeta",
+                        "tasks_completed": 1,
+                        "tasks_total": 3,
+                        "members_running": 2,
+                        "members_idle": 0,
+                        "members_errored": 0,
+                        "elapsed_secs": 200,
+                    },
+                }
+            }),
+            serde_json::to_value(&notification)?,
+        );
+        Ok(())
+    }
+
     #[test]
     fn mock_experimental_method_is_marked_experimental() {
         let request = ClientRequest::MockExperimentalMethod {
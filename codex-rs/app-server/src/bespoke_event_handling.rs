@@ -57,6 +57,7 @@ use codex_app_server_protocol::TerminalInteractionNotification;
 use codex_app_server_protocol::ThreadItem;
 use codex_app_server_protocol::ThreadNameUpdatedNotification;
 use codex_app_server_protocol::ThreadRollbackResponse;
+use codex_app_server_protocol::ThreadTeamEventNotification;
 use codex_app_server_protocol::ThreadTokenUsage;
 use codex_app_server_protocol::ThreadTokenUsageUpdatedNotification;
 use codex_app_server_protocol::ToolRequestUserInputOption;
@@ -1252,6 +1253,36 @@ pub(crate) async fn apply_bespoke_event_handling(
             )
             .await;
         }
+        event @ (EventMsg::TeamCreated(_)
+        | EventMsg::TeamMemberAdded(_)
+        | EventMsg::TeamMemberRemoved(_)
+        | EventMsg::TeamTaskCreated(_)
+        | EventMsg::TeamTaskUpdated(_)
+        | EventMsg::TeamMessageSent(_)
+        | EventMsg::TeamCleanup(_)
+        | EventMsg::TeamRenamed(_)
+        | EventMsg::TeamLeaderChanged(_)
+        | EventMsg::TeamMemberStatusChanged(_)
+        | EventMsg::TeamBudgetExhausted(_)
+        | EventMsg::TeamMemberRestarted(_)
+        | EventMsg::TeamEditConflict(_)
+        | EventMsg::TeamDiffSummary(_)
+        | EventMsg::TeamMemberOutput(_)
+        | EventMsg::TeamMemberTokenUsage(_)
+        | EventMsg::TeamProgress(_)
+        | EventMsg::TeamMemberLivenessLost(_)
+        | EventMsg::TeamError(_)
+        | EventMsg::TeamTaskUnblocked(_)
+        | EventMsg::TeamEscalationRequested(_)) => {
+            handle_team_event(
+                conversation_id,
+                &event_turn_id,
+                event,
+                api_version,
+                &outgoing,
+            )
+            .await;
+        }
         EventMsg::ShutdownComplete => {
             thread_watch_manager
                 .note_thread_shutdown(&conversation_id.to_string())
@@ -1281,6 +1312,25 @@ async fn handle_turn_diff(
     }
 }
 
+async fn handle_team_event(
+    conversation_id: ThreadId,
+    event_turn_id: &str,
+    event: EventMsg,
+    api_version: ApiVersion,
+    outgoing: &ThreadScopedOutgoingMessageSender,
+) {
+    if let ApiVersion::V2 = api_version {
+        let notification = ThreadTeamEventNotification {
+            thread_id: conversation_id.to_string(),
+            turn_id: event_turn_id.to_string(),
+            event,
+        };
+        outgoing
+            .send_server_notification(ServerNotification::ThreadTeamEvent(notification))
+            .await;
+    }
+}
+
 async fn handle_turn_plan_update(
     conversation_id: ThreadId,
     event_turn_id: &str,
@@ -2057,6 +2107,7 @@ mod tests {
     use codex_core::protocol::McpInvocation;
     use codex_core::protocol::RateLimitSnapshot;
     use codex_core::protocol::RateLimitWindow;
+    use codex_core::protocol::TeamProgressEvent;
     use codex_core::protocol::TokenUsage;
     use codex_core::protocol::TokenUsageInfo;
     use codex_protocol::mcp::CallToolResult;
@@ -2336,6 +2387,60 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_handle_team_event_emits_notification_for_v2() -> Result<()> {
+        let (tx, mut rx) = mpsc::channel(CHANNEL_CAPACITY);
+        let outgoing = Arc::new(OutgoingMessageSender::new(tx));
+        let outgoing = ThreadScopedOutgoingMessageSender::new(outgoing, vec![ConnectionId(1)]);
+        let progress = TeamProgressEvent {
+            team_name: "zeta".to_string(),
+            tasks_completed: 1,
+            tasks_total: 3,
+            members_running: 2,
+            members_idle: 1,
+            members_errored: 0,
+            elapsed_secs: 200,
+        };
+        let conversation_id = ThreadId::new();
+
+        handle_team_event(
+            conversation_id,
+            "turn-123",
+            EventMsg::TeamProgress(progress.clone()),
+            ApiVersion::V1,
+            &outgoing,
+        )
+        .await;
+        assert!(
+            rx.try_recv().is_err(),
+            "v1 clients get no team notification"
+        );
+
+        handle_team_event(
+            conversation_id,
+            "turn-123",
+            EventMsg::TeamProgress(progress.clone()),
+            ApiVersion::V2,
+            &outgoing,
+        )
+        .await;
+
+        let msg = recv_broadcast_message(&mut rx).await?;
+        match msg {
+            OutgoingMessage::AppServerNotification(ServerNotification::ThreadTeamEvent(n)) => {
+                assert_eq!(n.thread_id, conversation_id.to_string());
+                assert_eq!(n.turn_id, "turn-123");
+                match n.event {
+                    EventMsg::TeamProgress(event) => assert_eq!(event, progress),
+                    other => bail!("unexpected event: {other:?}"),
+                }
+            }
+            other => bail!("unexpected message: {other:?}"),
+        }
+        assert!(rx.try_recv().is_err(), "no extra messages expected");
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_handle_token_count_event_emits_usage_and_rate_limits() -> Result<()> {
         let conversation_id = ThreadId::new();
@@ -20,6 +20,7 @@ use std::sync::atomic::AtomicBool;
 
 use crate::message_processor::MessageProcessor;
 use crate::message_processor::MessageProcessorArgs;
+use crate::metrics::start_metrics_endpoint;
 use crate::outgoing_message::ConnectionId;
 use crate::outgoing_message::OutgoingEnvelope;
 use crate::outgoing_message::OutgoingMessageSender;
@@ -59,6 +60,7 @@ mod error_code;
 mod filters;
 mod fuzzy_file_search;
 mod message_processor;
+mod metrics;
 mod models;
 mod outgoing_message;
 mod thread_state;
@@ -369,6 +371,11 @@ pub async fn run_main_with_transport(
         }
     }
 
+    let metrics_handle = match config.teams.metrics_listen {
+        Some(bind_address) => Some(start_metrics_endpoint(bind_address).await?),
+        None => None,
+    };
+
     let transport_event_tx_for_outbound = transport_event_tx.clone();
     let outbound_handle = tokio::spawn(async move {
         let mut outbound_connections = HashMap::<ConnectionId, OutboundConnectionState>::new();
@@ -585,6 +592,9 @@ pub async fn run_main_with_transport(
     if let Some(handle) = websocket_accept_handle {
         handle.abort();
     }
+    if let Some(handle) = metrics_handle {
+        handle.abort();
+    }
 
     for handle in stdio_handles {
         let _ = handle.await;
@@ -0,0 +1,151 @@
+//! Prometheus endpoint for team metrics, served when `teams.metrics_listen`
+//! is set.
+//!
+//! Only `GET /metrics` is answered; every other request gets a 404. The
+//! endpoint is plain HTTP/1.1 with one response per connection, which is all
+//! a Prometheus scraper needs. Connections that are slow to send their
+//! request or read the response are dropped, and only a few are served at a
+//! time.
+
+use codex_core::teams::metrics::render_team_metrics;
+use std::io::Result as IoResult;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::net::TcpListener;
+use tokio::net::TcpStream;
+use tokio::sync::Semaphore;
+use tokio::task::JoinHandle;
+use tokio::time::timeout;
+use tracing::debug;
+use tracing::info;
+use tracing::warn;
+
+/// Longest request, headers included, the endpoint reads.
+const MAX_REQUEST_BYTES: u64 = 8 * 1024;
+
+/// How long a connection has to send its request and read the response.
+const SCRAPE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Connections served at once; further ones wait to be accepted.
+const MAX_CONNECTIONS: usize = 16;
+
+pub(crate) async fn start_metrics_endpoint(bind_address: SocketAddr) -> IoResult<JoinHandle<()>> {
+    let listener = TcpListener::bind(bind_address).await?;
+    info!(
+        "app-server team metrics on http://{}/metrics",
+        listener.local_addr()?
+    );
+    Ok(tokio::spawn(accept_scrapes(listener)))
+}
+
+async fn accept_scrapes(listener: TcpListener) {
+    let connections = Arc::new(Semaphore::new(MAX_CONNECTIONS));
+    loop {
+        let Ok(permit) = Arc::clone(&connections).acquire_owned().await else {
+            return;
+        };
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tokio::spawn(async move {
+                    match timeout(SCRAPE_TIMEOUT, answer_scrape(stream)).await {
+                        Ok(Ok(())) => {}
+                        Ok(Err(err)) => debug!("failed to answer metrics request: {err}"),
+                        Err(_) => debug!("metrics request timed out"),
+                    }
+                    drop(permit);
+                });
+            }
+            Err(err) => {
+                warn!("failed to accept metrics connection: {err}");
+            }
+        }
+    }
+}
+
+async fn answer_scrape(stream: TcpStream) -> IoResult<()> {
+    let mut reader = BufReader::new(stream).take(MAX_REQUEST_BYTES);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await?;
+    // Skip the headers, which end with an empty line.
+    let mut header = String::new();
+    while reader.read_line(&mut header).await? > 0 && !header.trim().is_empty() {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (status, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => ("200 OK", render_team_metrics()),
+        _ => ("404 Not Found", String::new()),
+    };
+    let response = format!(
+        "HTTP/1.1 {status}\r\n\
+         Content-Type: text/plain; version=0.0.4; charset=utf-8\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\r\n{body}",
+        body.len()
+    );
+    let mut stream = reader.into_inner().into_inner();
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    async fn get(address: SocketAddr, path: &str) -> String {
+        let mut stream = TcpStream::connect(address).await.expect("connect");
+        let request = format!("GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n");
+        stream
+            .write_all(request.as_bytes())
+            .await
+            .expect("send request");
+        let mut response = String::new();
+        stream
+            .read_to_string(&mut response)
+            .await
+            .expect("read response");
+        response
+    }
+
+    #[tokio::test]
+    async fn serves_team_metrics_at_metrics_only() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let address = listener.local_addr().expect("local address");
+        let handle = tokio::spawn(accept_scrapes(listener));
+
+        let metrics = get(address, "/metrics").await;
+        let (head, body) = metrics.split_once("\r\n\r\n").expect("response head");
+        assert_eq!(head.lines().next(), Some("HTTP/1.1 200 OK"));
+        assert!(body.contains("# TYPE codex_team_active_teams gauge"));
+
+        let other = get(address, "/").await;
+        assert_eq!(other.lines().next(), Some("HTTP/1.1 404 Not Found"));
+        handle.abort();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn drops_connections_that_never_send_a_request() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let address = listener.local_addr().expect("local address");
+        let handle = tokio::spawn(accept_scrapes(listener));
+
+        let mut silent = TcpStream::connect(address).await.expect("connect");
+        let mut response = String::new();
+        silent
+            .read_to_string(&mut response)
+            .await
+            .expect("read until closed");
+        assert_eq!(response, "");
+
+        let metrics = get(address, "/metrics").await;
+        assert_eq!(metrics.lines().next(), Some("HTTP/1.1 200 OK"));
+        handle.abort();
+    }
+}
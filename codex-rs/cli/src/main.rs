@@ -36,10 +36,15 @@ mod app_cmd;
 #[cfg(target_os = "macos")]
 mod desktop_app;
 mod mcp_cmd;
+mod team_cmd;
 #[cfg(not(windows))]
 mod wsl_paths;
 
 use crate::mcp_cmd::McpCli;
+use crate::team_cmd::AttachArgs;
+use crate::team_cmd::JoinArgs;
+use crate::team_cmd::TeamCli;
+use crate::team_cmd::TeamSubcommand;
 
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
@@ -99,6 +104,9 @@ enum Subcommand {
     /// Start Codex as an MCP server (stdio).
     McpServer,
 
+    /// Run, inspect and clean up agent teams.
+    Team(TeamCli),
+
     /// [experimental] Run the app server or related tooling.
     AppServer(AppServerCommand),
 
@@ -597,6 +605,34 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             prepend_config_flags(&mut mcp_cli.config_overrides, root_config_overrides.clone());
             mcp_cli.run().await?;
         }
+        Some(Subcommand::Team(mut team_cli)) => {
+            prepend_config_flags(
+                &mut team_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            match team_cli.subcommand {
+                TeamSubcommand::Attach(AttachArgs { name }) => {
+                    interactive.attach_team = Some(name);
+                }
+                TeamSubcommand::Join(JoinArgs { name, member, role }) => {
+                    let quoted = |value: String| toml::Value::String(value).to_string();
+                    let overrides = &mut team_cli.config_overrides.raw_overrides;
+                    overrides.push("teams.enabled=true".to_string());
+                    overrides.push(format!("teams.join.team={}", quoted(name)));
+                    overrides.push(format!("teams.join.name={}", quoted(member)));
+                    if let Some(role) = role {
+                        overrides.push(format!("teams.join.role={}", quoted(role)));
+                    }
+                }
+                TeamSubcommand::Replay(ref args) if args.tui => {
+                    interactive.replay_trace = Some(args.trace_path());
+                }
+                _ => return team_cli.run(codex_linux_sandbox_exe).await,
+            }
+            prepend_config_flags(&mut interactive.config_overrides, team_cli.config_overrides);
+            let exit_info = run_interactive_tui(interactive, codex_linux_sandbox_exe).await?;
+            handle_app_exit(exit_info)?;
+        }
         Some(Subcommand::AppServer(app_server_cli)) => match app_server_cli.subcommand {
             None => {
                 let transport = app_server_cli.listen;
@@ -0,0 +1,1087 @@
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use chrono::DateTime;
+use chrono::Utc;
+use clap::Parser;
+use codex_core::config::Config;
+use codex_core::config::find_codex_home;
+use codex_core::find_thread_path_by_id_str;
+use codex_core::protocol::TeamTaskInfo;
+use codex_core::protocol::TeamTaskStatus;
+use codex_core::teams::default_tasks_root;
+use codex_core::teams::default_teams_root;
+use codex_core::teams::doctor::TeamIssue;
+use codex_core::teams::event_log::TeamEventLog;
+use codex_core::teams::github_import::GitHubPlanImporter;
+use codex_core::teams::github_import::GitHubPlanMapping;
+use codex_core::teams::github_import::GitHubPlanSource;
+use codex_core::teams::replay::TeamReplayState;
+use codex_core::teams::replay::describe;
+use codex_core::teams::replay::status_name;
+use codex_core::teams::report::load_team_run_report;
+use codex_core::teams::task_list::TaskList;
+use codex_core::teams::team_manager::GcAction;
+use codex_core::teams::team_manager::LeaderLock;
+use codex_core::teams::team_manager::TeamConfig;
+use codex_core::teams::team_manager::TeamManager;
+use codex_exec::Cli as ExecCli;
+use codex_exec::Command as ExecCommand;
+use codex_exec::TeamResumeMemberArgs;
+use codex_exec::TeamRunArgs;
+use codex_protocol::ThreadId;
+use codex_utils_cli::CliConfigOverrides;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Run agent teams from plan files, and inspect and clean up teams without
+/// starting a session by reading the on-disk stores under `~/.codex/teams`
+/// and `~/.codex/tasks` directly.
+///
+/// Subcommands:
+/// - `run`     — create a team from a plan file and lead it headlessly
+/// - `import`  — write a plan file from a GitHub milestone or project
+/// - `attach`  — follow a team led from another terminal (read-only)
+/// - `join`    — work in a team led from another terminal as one of its members
+/// - `resume-member` — bring back a single teammate whose thread stopped
+/// - `list`    — list all teams (with `--json`)
+/// - `show`    — show a team's config and task summary (with `--json`)
+/// - `members` — list a team's members (with `--json`)
+/// - `tasks`   — list a team's tasks (with `--json`)
+/// - `doctor`  — check a team's stored state and repair it (with `--fix`)
+/// - `replay`  — step through a team's recorded run (with `--tui`)
+/// - `report`  — write a Markdown or HTML report of a team's run
+/// - `cleanup` — delete a team and its task list
+/// - `gc`      — delete or archive teams left behind by crashed sessions
+#[derive(Debug, clap::Parser)]
+pub struct TeamCli {
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub subcommand: TeamSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum TeamSubcommand {
+    /// Create a team from a plan file and lead it until its tasks are done.
+    Run(TeamRunArgs),
+    /// Write a plan file from the open items of a GitHub milestone or
+    /// project, one task per item.
+    Import(ImportArgs),
+    /// Follow a team led from another terminal, without joining it.
+    Attach(AttachArgs),
+    /// Join a team led from another terminal as one of its members, taking
+    /// its messages and tasks in this session.
+    Join(JoinArgs),
+    /// Bring back a single teammate whose thread stopped, reattaching to its
+    /// rollout or respawning it with its stored prompt, and run it until its
+    /// turn ends.
+    ResumeMember(TeamResumeMemberArgs),
+    List(ListArgs),
+    Show(ShowArgs),
+    Members(MembersArgs),
+    Tasks(TasksArgs),
+    /// Check a team's stored state for problems, and optionally fix them.
+    Doctor(DoctorArgs),
+    /// Replay a team's recorded events and tool calls, rebuilding its state
+    /// step by step.
+    Replay(ReplayArgs),
+    /// Write a report of a team's run: a timeline, the outcome of each task
+    /// and the tokens and estimated cost of each participant.
+    Report(ReportArgs),
+    Cleanup(CleanupArgs),
+    /// Delete or archive teams that no session is leading and that have not
+    /// been touched for a while, along with their task lists.
+    Gc(GcArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ImportArgs {
+    /// URL of the milestone, e.g. `https://github.com/acme/widgets/milestone/3`,
+    /// or project, e.g. `https://github.com/orgs/acme/projects/5`.
+    pub url: String,
+
+    /// Name of the team the plan creates.
+    #[arg(long)]
+    pub name: String,
+
+    /// Hand the items assigned to LOGIN to TEAMMATE. May be repeated.
+    #[arg(long = "assignee", value_name = "LOGIN=TEAMMATE", value_parser = parse_mapping)]
+    pub assignees: Vec<(String, String)>,
+
+    /// Hand the items labeled LABEL to TEAMMATE. May be repeated.
+    #[arg(long = "label", value_name = "LABEL=TEAMMATE", value_parser = parse_mapping)]
+    pub labels: Vec<(String, String)>,
+
+    /// Write the plan to FILE instead of stdout.
+    #[arg(long, short = 'o', value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct AttachArgs {
+    /// Name of the team to follow.
+    pub name: String,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct JoinArgs {
+    /// Name of the team to join.
+    pub name: String,
+
+    /// Member name to join under.
+    #[arg(long = "as", value_name = "MEMBER")]
+    pub member: String,
+
+    /// Role shown to the rest of the team.
+    #[arg(long)]
+    pub role: Option<String>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ListArgs {
+    /// Output the teams as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ShowArgs {
+    /// Name of the team to display.
+    pub name: String,
+
+    /// Output the team and its tasks as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct MembersArgs {
+    /// Name of the team whose members to list.
+    pub name: String,
+
+    /// Output the members as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct TasksArgs {
+    /// Name of the team whose tasks to list.
+    pub name: String,
+
+    /// Output the tasks as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct DoctorArgs {
+    /// Name of the team to check.
+    pub name: String,
+
+    /// Repair the problems that can be repaired.
+    #[arg(long)]
+    pub fix: bool,
+
+    /// Repair the team even while a session is leading it.
+    #[arg(long, requires = "fix")]
+    pub force: bool,
+
+    /// Output the problems found and fixed as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ReplayArgs {
+    /// Name of the team whose run to replay.
+    #[arg(required_unless_present = "trace")]
+    pub name: Option<String>,
+
+    /// Replay a copy of a team's `events.jsonl` instead.
+    #[arg(long, value_name = "FILE", conflicts_with = "name")]
+    pub trace: Option<PathBuf>,
+
+    /// Stop after step N and show the team as it was then.
+    #[arg(long, value_name = "N")]
+    pub until: Option<usize>,
+
+    /// Output the steps and the team's final state as JSON.
+    #[arg(long, conflicts_with = "tui")]
+    pub json: bool,
+
+    /// Step through the run in the team dashboard instead.
+    #[arg(long)]
+    pub tui: bool,
+}
+
+impl ReplayArgs {
+    /// The trace to replay: `--trace`, or the named team's event log.
+    pub fn trace_path(&self) -> PathBuf {
+        match (&self.trace, &self.name) {
+            (Some(trace), _) => trace.clone(),
+            (None, name) => TeamManager::new(default_teams_root())
+                .events_path(name.as_deref().unwrap_or_default()),
+        }
+    }
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ReportArgs {
+    /// Name of the team to report on.
+    pub name: String,
+
+    /// Write a standalone HTML page instead of Markdown.
+    #[arg(long, conflicts_with = "json")]
+    pub html: bool,
+
+    /// Output the report's data as JSON.
+    #[arg(long)]
+    pub json: bool,
+
+    /// Write the report to FILE instead of stdout.
+    #[arg(long, short = 'o', value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct CleanupArgs {
+    /// Name of the team to remove.
+    pub name: String,
+
+    /// Remove the team even while a session is leading it. Its teammates
+    /// are not shut down.
+    #[arg(long)]
+    pub force: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct GcArgs {
+    /// Only collect teams untouched for this long, e.g. `30m`, `12h` or
+    /// `7d`.
+    #[arg(long, value_name = "AGE", default_value = "7d", value_parser = parse_age)]
+    pub older_than: Duration,
+
+    /// List the teams that would be collected without touching them.
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Move collected teams and task lists into `.archive` instead of
+    /// deleting them.
+    #[arg(long)]
+    pub archive: bool,
+}
+
+impl TeamCli {
+    pub async fn run(self, codex_linux_sandbox_exe: Option<PathBuf>) -> Result<()> {
+        let teams = TeamManager::new(default_teams_root());
+        let tasks = TaskList::new(default_tasks_root());
+
+        match self.subcommand {
+            TeamSubcommand::Run(run_args) => {
+                let mut exec_cli = ExecCli::try_parse_from(["codex", "exec"])?;
+                exec_cli.command = Some(ExecCommand::RunTeam(run_args));
+                exec_cli.config_overrides = self.config_overrides;
+                codex_exec::run_main(exec_cli, codex_linux_sandbox_exe).await?;
+            }
+            TeamSubcommand::Import(args) => {
+                run_import(&self.config_overrides, args).await?;
+            }
+            TeamSubcommand::ResumeMember(resume_args) => {
+                let mut exec_cli = ExecCli::try_parse_from(["codex", "exec"])?;
+                exec_cli.command = Some(ExecCommand::ResumeMember(resume_args));
+                exec_cli.config_overrides = self.config_overrides;
+                codex_exec::run_main(exec_cli, codex_linux_sandbox_exe).await?;
+            }
+            TeamSubcommand::Attach(_) | TeamSubcommand::Join(_) => {
+                // `main` starts the TUI for these instead of calling `run`.
+                bail!("`codex team attach` and `codex team join` need the interactive TUI");
+            }
+            TeamSubcommand::List(args) => {
+                run_list(&teams, &tasks, args).await?;
+            }
+            TeamSubcommand::Show(args) => {
+                run_show(&teams, &tasks, args).await?;
+            }
+            TeamSubcommand::Members(args) => {
+                run_members(&teams, args).await?;
+            }
+            TeamSubcommand::Tasks(args) => {
+                run_tasks(&teams, &tasks, args).await?;
+            }
+            TeamSubcommand::Doctor(args) => {
+                run_doctor(&teams, &tasks, args).await?;
+            }
+            TeamSubcommand::Replay(args) => {
+                run_replay(&teams, args).await?;
+            }
+            TeamSubcommand::Report(args) => {
+                run_report(&self.config_overrides, &teams, args).await?;
+            }
+            TeamSubcommand::Cleanup(args) => {
+                run_cleanup(&teams, &tasks, args).await?;
+            }
+            TeamSubcommand::Gc(args) => {
+                run_gc(&teams, &tasks, args).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+async fn run_import(config_overrides: &CliConfigOverrides, import_args: ImportArgs) -> Result<()> {
+    let ImportArgs {
+        url,
+        name,
+        assignees,
+        labels,
+        output,
+    } = import_args;
+    let source = GitHubPlanSource::parse(&url).map_err(anyhow::Error::msg)?;
+    // The team's `github_issues` entry supplies the API, the token and the
+    // teammates' logins.
+    let overrides = config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides(overrides)
+        .await
+        .context("failed to load configuration")?;
+    let settings = config.teams.github_issues_for(&name);
+    let mut mapping = GitHubPlanMapping {
+        assignees: settings
+            .map(|settings| {
+                settings
+                    .assignees
+                    .iter()
+                    .map(|(teammate, login)| (login.clone(), teammate.clone()))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        labels: labels.into_iter().collect(),
+    };
+    mapping.assignees.extend(assignees);
+
+    let importer = GitHubPlanImporter::new(settings).map_err(anyhow::Error::msg)?;
+    let plan = importer
+        .import(&name, &source, &mapping)
+        .await
+        .map_err(anyhow::Error::msg)
+        .with_context(|| format!("failed to import {source}"))?;
+    let rendered = toml::to_string_pretty(&plan)?;
+    match output {
+        Some(path) => {
+            std::fs::write(&path, rendered)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+            println!(
+                "Wrote a plan of {} tasks for {} teammates to {}. Start the team with `codex team run {}`.",
+                plan.tasks.len(),
+                plan.members.len(),
+                path.display(),
+                path.display()
+            );
+        }
+        None => print!("{rendered}"),
+    }
+
+    Ok(())
+}
+
+async fn run_list(teams: &TeamManager, tasks: &TaskList, list_args: ListArgs) -> Result<()> {
+    let configs = teams.list_teams().await.context("failed to list teams")?;
+
+    let mut entries = Vec::new();
+    for config in configs {
+        let leader = teams.leader_lock(&config.name).await.ok().flatten();
+        let team_tasks = tasks.get_all_tasks(&config.name).await.unwrap_or_default();
+        entries.push((config, leader, team_tasks));
+    }
+
+    if list_args.json {
+        let json_entries: Vec<_> = entries
+            .iter()
+            .map(|(config, leader, team_tasks)| {
+                serde_json::json!({
+                    "name": config.name,
+                    "description": config.description,
+                    "created_at": config.created_at,
+                    "leader_thread_id": config.leader_thread_id.to_string(),
+                    "leader_running": is_running(leader.as_ref()),
+                    "parent_team": config.parent_team,
+                    "members": config.members.len(),
+                    "tasks": team_tasks.len(),
+                    "tasks_completed": count_tasks(team_tasks, TeamTaskStatus::Completed),
+                })
+            })
+            .collect();
+        let output = serde_json::to_string_pretty(&json_entries)?;
+        println!("{output}");
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!("No teams found.");
+        return Ok(());
+    }
+
+    let rows: Vec<[String; 5]> = entries
+        .iter()
+        .map(|(config, leader, team_tasks)| {
+            [
+                config.name.clone(),
+                config.members.len().to_string(),
+                format!(
+                    "{}/{}",
+                    count_tasks(team_tasks, TeamTaskStatus::Completed),
+                    team_tasks.len()
+                ),
+                leader_display(leader.as_ref()),
+                config.created_at.clone(),
+            ]
+        })
+        .collect();
+    let mut widths = [
+        "Name".len(),
+        "Members".len(),
+        "Tasks".len(),
+        "Leader".len(),
+        "Created".len(),
+    ];
+    for row in &rows {
+        for (i, cell) in row.iter().enumerate() {
+            widths[i] = widths[i].max(cell.len());
+        }
+    }
+
+    println!(
+        "{name:<name_w$}  {members:<members_w$}  {tasks:<tasks_w$}  {leader:<leader_w$}  {created}",
+        name = "Name",
+        members = "Members",
+        tasks = "Tasks",
+        leader = "Leader",
+        created = "Created",
+        name_w = widths[0],
+        members_w = widths[1],
+        tasks_w = widths[2],
+        leader_w = widths[3],
+    );
+    for row in &rows {
+        println!(
+            "{name:<name_w$}  {members:<members_w$}  {tasks:<tasks_w$}  {leader:<leader_w$}  {created}",
+            name = row[0].as_str(),
+            members = row[1].as_str(),
+            tasks = row[2].as_str(),
+            leader = row[3].as_str(),
+            created = row[4].as_str(),
+            name_w = widths[0],
+            members_w = widths[1],
+            tasks_w = widths[2],
+            leader_w = widths[3],
+        );
+    }
+
+    Ok(())
+}
+
+async fn run_show(teams: &TeamManager, tasks: &TaskList, show_args: ShowArgs) -> Result<()> {
+    let ShowArgs { name, json } = show_args;
+    let config = load_team(teams, &name).await?;
+    let leader = teams.leader_lock(&name).await.ok().flatten();
+    let team_tasks = tasks
+        .get_all_tasks(&name)
+        .await
+        .with_context(|| format!("failed to load the tasks of team '{name}'"))?;
+
+    if json {
+        let output = serde_json::to_string_pretty(&serde_json::json!({
+            "config": config,
+            "leader_running": is_running(leader.as_ref()),
+            "tasks": team_tasks,
+        }))?;
+        println!("{output}");
+        return Ok(());
+    }
+
+    println!("{}", config.name);
+    if let Some(description) = &config.description {
+        println!("  description: {description}");
+    }
+    println!("  created_at: {}", config.created_at);
+    println!(
+        "  leader: {} ({})",
+        config.leader_thread_id,
+        leader_display(leader.as_ref())
+    );
+    println!(
+        "  display_mode: {}",
+        serde_name(serde_json::json!(config.display_mode))
+    );
+    println!("  delegation_mode: {}", config.delegation_mode);
+    println!("  work_stealing: {}", config.work_stealing);
+    if let Some(parent) = &config.parent_team {
+        println!("  parent_team: {parent}");
+    }
+    if !config.sub_teams.is_empty() {
+        println!("  sub_teams: {}", config.sub_teams.join(", "));
+    }
+    let members_display = if config.members.is_empty() {
+        "-".to_string()
+    } else {
+        config
+            .members
+            .iter()
+            .map(|member| format!("{} ({})", member.name, member.status))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    println!("  members: {members_display}");
+    println!(
+        "  tasks: {} completed, {} in progress, {} pending",
+        count_tasks(&team_tasks, TeamTaskStatus::Completed),
+        count_tasks(&team_tasks, TeamTaskStatus::InProgress),
+        count_tasks(&team_tasks, TeamTaskStatus::Pending),
+    );
+    for task in &team_tasks {
+        let assignee = task.assigned_to.as_deref().unwrap_or("unassigned");
+        println!(
+            "    [{}] {} ({}, {assignee})",
+            task.id,
+            task.title,
+            serde_name(serde_json::json!(task.status))
+        );
+    }
+
+    Ok(())
+}
+
+async fn run_members(teams: &TeamManager, members_args: MembersArgs) -> Result<()> {
+    let MembersArgs { name, json } = members_args;
+    let config = load_team(teams, &name).await?;
+
+    if json {
+        let output = serde_json::to_string_pretty(&config.members)?;
+        println!("{output}");
+        return Ok(());
+    }
+
+    if config.members.is_empty() {
+        println!("Team '{name}' has no members.");
+        return Ok(());
+    }
+
+    let rows: Vec<[String; 4]> = config
+        .members
+        .iter()
+        .map(|member| {
+            [
+                member.name.clone(),
+                member.role.clone().unwrap_or_else(|| "-".to_string()),
+                member.status.clone(),
+                member.thread_id.to_string(),
+            ]
+        })
+        .collect();
+    let mut widths = ["Name".len(), "Role".len(), "Status".len()];
+    for row in &rows {
+        for (i, cell) in row.iter().take(widths.len()).enumerate() {
+            widths[i] = widths[i].max(cell.len());
+        }
+    }
+
+    println!(
+        "{name:<name_w$}  {role:<role_w$}  {status:<status_w$}  {thread}",
+        name = "Name",
+        role = "Role",
+        status = "Status",
+        thread = "Thread",
+        name_w = widths[0],
+        role_w = widths[1],
+        status_w = widths[2],
+    );
+    for row in &rows {
+        println!(
+            "{name:<name_w$}  {role:<role_w$}  {status:<status_w$}  {thread}",
+            name = row[0].as_str(),
+            role = row[1].as_str(),
+            status = row[2].as_str(),
+            thread = row[3].as_str(),
+            name_w = widths[0],
+            role_w = widths[1],
+            status_w = widths[2],
+        );
+    }
+
+    Ok(())
+}
+
+async fn run_tasks(teams: &TeamManager, tasks: &TaskList, tasks_args: TasksArgs) -> Result<()> {
+    let TasksArgs { name, json } = tasks_args;
+    load_team(teams, &name).await?;
+    let team_tasks = tasks
+        .get_all_tasks(&name)
+        .await
+        .with_context(|| format!("failed to load the tasks of team '{name}'"))?;
+
+    if json {
+        let output = serde_json::to_string_pretty(&team_tasks)?;
+        println!("{output}");
+        return Ok(());
+    }
+
+    if team_tasks.is_empty() {
+        println!("Team '{name}' has no tasks.");
+        return Ok(());
+    }
+
+    let now = Utc::now();
+    let rows: Vec<[String; 6]> = team_tasks
+        .iter()
+        .map(|task| {
+            let age = task
+                .created_at
+                .as_deref()
+                .and_then(|created_at| DateTime::parse_from_rfc3339(created_at).ok())
+                .map(|created_at| {
+                    let secs = (now - created_at.with_timezone(&Utc)).num_seconds().max(0);
+                    match secs {
+                        0..60 => format!("{secs}s"),
+                        60..3600 => format!("{}m", secs / 60),
+                        3600..86400 => format!("{}h", secs / 3600),
+                        _ => format!("{}d", secs / 86400),
+                    }
+                })
+                .unwrap_or_else(|| "-".to_string());
+            let deps = if task.depends_on.is_empty() {
+                "-".to_string()
+            } else {
+                task.depends_on.join(",")
+            };
+            [
+                task.id.clone(),
+                task.title.clone(),
+                serde_name(serde_json::json!(task.status)),
+                task.assigned_to.clone().unwrap_or_else(|| "-".to_string()),
+                deps,
+                age,
+            ]
+        })
+        .collect();
+    let mut widths = [
+        "ID".len(),
+        "Title".len(),
+        "Status".len(),
+        "Assignee".len(),
+        "Deps".len(),
+    ];
+    for row in &rows {
+        for (i, cell) in row.iter().take(widths.len()).enumerate() {
+            widths[i] = widths[i].max(cell.len());
+        }
+    }
+
+    println!(
+        "{id:<id_w$}  {title:<title_w$}  {status:<status_w$}  {assignee:<assignee_w$}  {deps:<deps_w$}  {age}",
+        id = "ID",
+        title = "Title",
+        status = "Status",
+        assignee = "Assignee",
+        deps = "Deps",
+        age = "Age",
+        id_w = widths[0],
+        title_w = widths[1],
+        status_w = widths[2],
+        assignee_w = widths[3],
+        deps_w = widths[4],
+    );
+    for row in &rows {
+        println!(
+            "{id:<id_w$}  {title:<title_w$}  {status:<status_w$}  {assignee:<assignee_w$}  {deps:<deps_w$}  {age}",
+            id = row[0].as_str(),
+            title = row[1].as_str(),
+            status = row[2].as_str(),
+            assignee = row[3].as_str(),
+            deps = row[4].as_str(),
+            age = row[5].as_str(),
+            id_w = widths[0],
+            title_w = widths[1],
+            status_w = widths[2],
+            assignee_w = widths[3],
+            deps_w = widths[4],
+        );
+    }
+
+    Ok(())
+}
+
+async fn run_doctor(teams: &TeamManager, tasks: &TaskList, doctor_args: DoctorArgs) -> Result<()> {
+    let DoctorArgs {
+        name,
+        fix,
+        force,
+        json,
+    } = doctor_args;
+
+    if !teams.team_exists(&name).await {
+        bail!("No team named '{name}' found.");
+    }
+    if fix
+        && !force
+        && let Some(leader) = teams.leader_lock(&name).await.ok().flatten()
+        && !leader.is_stale()
+    {
+        bail!(
+            "Team '{name}' is led by a running session (pid {}). Stop it first, or pass --force.",
+            leader.pid
+        );
+    }
+
+    // A member's thread is alive as long as its rollout is on disk.
+    let codex_home = find_codex_home().context("failed to locate the codex home")?;
+    let codex_home = codex_home.as_path();
+    let is_alive = |thread_id: ThreadId| async move {
+        find_thread_path_by_id_str(codex_home, &thread_id.to_string())
+            .await
+            .ok()
+            .flatten()
+            .is_some()
+    };
+    let mut issues = teams
+        .validate(&name, is_alive)
+        .await
+        .with_context(|| format!("failed to check team '{name}'"))?;
+    let mut fixed = Vec::new();
+    if fix {
+        fixed = teams
+            .repair(&name, &issues)
+            .await
+            .with_context(|| format!("failed to repair team '{name}'"))?;
+    }
+    // Checked after the team's repair, so tasks of removed members show up.
+    let members: Option<Vec<String>> = teams
+        .load_config(&name)
+        .await
+        .ok()
+        .map(|config| config.members.into_iter().map(|m| m.name).collect());
+    let task_issues = tasks
+        .validate(&name, members.as_deref())
+        .await
+        .with_context(|| format!("failed to check the tasks of team '{name}'"))?;
+    if fix {
+        fixed.extend(
+            tasks
+                .repair(&name, &task_issues)
+                .await
+                .with_context(|| format!("failed to repair the tasks of team '{name}'"))?,
+        );
+    }
+    issues.extend(task_issues);
+    let remaining: Vec<&TeamIssue> = issues.iter().filter(|i| !fixed.contains(i)).collect();
+
+    if json {
+        let output = serde_json::to_string_pretty(&serde_json::json!({
+            "team_name": name,
+            "issues": issues,
+            "fixed": fixed,
+        }))?;
+        println!("{output}");
+        return Ok(());
+    }
+
+    if issues.is_empty() {
+        println!("Team '{name}' looks healthy.");
+        return Ok(());
+    }
+    for issue in &fixed {
+        println!("Fixed: {issue}");
+    }
+    if remaining.is_empty() {
+        return Ok(());
+    }
+    println!("Team '{name}' has {} problem(s):", remaining.len());
+    for issue in &remaining {
+        println!("  - {issue}");
+        if let Some(repair) = issue.fix() {
+            println!("    fix: {repair}");
+        }
+    }
+    if !fix && remaining.iter().any(|issue| issue.fix().is_some()) {
+        println!("Run `codex team doctor {name} --fix` to repair them.");
+    }
+
+    Ok(())
+}
+
+async fn run_replay(teams: &TeamManager, replay_args: ReplayArgs) -> Result<()> {
+    if let Some(name) = &replay_args.name
+        && !teams.team_exists(name).await
+    {
+        bail!("No team named '{name}' found.");
+    }
+    let path = replay_args.trace_path();
+    let (entries, _) = match TeamEventLog::new(path.clone()).read_trace_from(0).await {
+        Ok(read) => read,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => (Vec::new(), 0),
+        Err(e) => {
+            return Err(e).with_context(|| format!("failed to read team trace {}", path.display()));
+        }
+    };
+    let total = entries.len();
+    let steps = &entries[..replay_args.until.unwrap_or(total).min(total)];
+
+    if replay_args.json {
+        let output = serde_json::to_string_pretty(&serde_json::json!({
+            "steps": steps,
+            "state": TeamReplayState::replay(steps),
+        }))?;
+        println!("{output}");
+        return Ok(());
+    }
+
+    if steps.is_empty() {
+        println!("No recorded steps in {}.", path.display());
+        return Ok(());
+    }
+
+    let mut state = TeamReplayState::default();
+    let width = total.to_string().len();
+    for (i, entry) in steps.iter().enumerate() {
+        state.apply(entry);
+        let time = DateTime::parse_from_rfc3339(entry.timestamp())
+            .map(|timestamp| timestamp.format("%H:%M:%S").to_string())
+            .unwrap_or_else(|_| entry.timestamp().to_string());
+        println!(
+            "{step:>width$}  {time}  {who}: {what}",
+            step = i + 1,
+            who = state.participant(entry.thread_id()),
+            what = describe(entry),
+        );
+    }
+
+    println!();
+    let team = state.team_name.as_deref().unwrap_or("(unnamed)");
+    println!("Team '{team}' after step {} of {total}:", steps.len());
+    if state.members.is_empty() {
+        println!("  No members.");
+    }
+    for member in &state.members {
+        println!("  {}  {}", member.name, status_name(&member.status));
+    }
+    if state.tasks.is_empty() {
+        println!("  No tasks.");
+    }
+    for task in &state.tasks {
+        println!(
+            "  {}  {}  {}  {}",
+            task.id,
+            serde_name(serde_json::json!(task.status)),
+            task.assigned_to.as_deref().unwrap_or("-"),
+            task.title
+        );
+    }
+    println!(
+        "  {} message(s), {} tool call(s), {} failed{}",
+        state.messages,
+        state.tool_calls,
+        state.failed_tool_calls,
+        if state.cleaned_up { "; cleaned up" } else { "" }
+    );
+
+    Ok(())
+}
+
+async fn run_report(
+    config_overrides: &CliConfigOverrides,
+    teams: &TeamManager,
+    report_args: ReportArgs,
+) -> Result<()> {
+    let ReportArgs {
+        name,
+        html,
+        json,
+        output,
+    } = report_args;
+    if !teams.team_exists(&name).await {
+        bail!("No team named '{name}' found.");
+    }
+    // The config supplies the token prices of the cost estimate.
+    let overrides = config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides(overrides)
+        .await
+        .context("failed to load configuration")?;
+    let report = load_team_run_report(teams, &name, &config.teams)
+        .await
+        .with_context(|| format!("failed to read the run of team '{name}'"))?;
+
+    let rendered = if json {
+        format!("{}\n", serde_json::to_string_pretty(&report)?)
+    } else if html {
+        report.to_html()
+    } else {
+        report.to_markdown()
+    };
+    match output {
+        Some(path) => {
+            std::fs::write(&path, rendered)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+            println!("Wrote the report of team '{name}' to {}.", path.display());
+        }
+        None => print!("{rendered}"),
+    }
+
+    Ok(())
+}
+
+async fn run_cleanup(
+    teams: &TeamManager,
+    tasks: &TaskList,
+    cleanup_args: CleanupArgs,
+) -> Result<()> {
+    let CleanupArgs { name, force } = cleanup_args;
+
+    if !teams.team_exists(&name).await {
+        println!("No team named '{name}' found.");
+        return Ok(());
+    }
+    if let Some(leader) = teams.leader_lock(&name).await.ok().flatten()
+        && !leader.is_stale()
+        && !force
+    {
+        bail!(
+            "Team '{name}' is led by a running session (pid {}). Clean it up from that session, or pass --force.",
+            leader.pid
+        );
+    }
+
+    tasks
+        .cleanup(&name)
+        .await
+        .with_context(|| format!("failed to remove the tasks of team '{name}'"))?;
+    teams
+        .cleanup_team(&name)
+        .await
+        .with_context(|| format!("failed to remove team '{name}'"))?;
+    println!("Removed team '{name}'.");
+
+    Ok(())
+}
+
+async fn run_gc(teams: &TeamManager, tasks: &TaskList, gc_args: GcArgs) -> Result<()> {
+    let GcArgs {
+        older_than,
+        dry_run,
+        archive,
+    } = gc_args;
+    let (action, verb) = match (dry_run, archive) {
+        (true, true) => (GcAction::Report, "Would archive"),
+        (true, false) => (GcAction::Report, "Would delete"),
+        (false, true) => (GcAction::Archive, "Archived"),
+        (false, false) => (GcAction::Delete, "Deleted"),
+    };
+
+    // Without a session to ask, a leader counts as alive only through its
+    // team's leader lock, which `gc` checks itself.
+    let collected = teams
+        .gc(older_than, action, |_| async { false })
+        .await
+        .context("failed to collect teams")?;
+
+    // Task lists of the collected teams, and those whose team is already
+    // gone, e.g. after a crash during cleanup.
+    let task_lists = tasks
+        .team_names()
+        .await
+        .context("failed to list task lists")?;
+    let mut stray = Vec::new();
+    for name in task_lists {
+        if !collected.contains(&name) && !teams.team_exists(&name).await {
+            stray.push(name);
+        }
+    }
+    for name in collected.iter().chain(&stray) {
+        if dry_run {
+            continue;
+        }
+        let result = if archive {
+            tasks.archive(name).await
+        } else {
+            tasks.cleanup(name).await
+        };
+        result.with_context(|| format!("failed to collect the task list of '{name}'"))?;
+    }
+
+    for name in &collected {
+        println!("{verb} team '{name}'.");
+    }
+    for name in &stray {
+        println!("{verb} the task list of '{name}', which has no team.");
+    }
+    if collected.is_empty() && stray.is_empty() {
+        println!("No orphaned teams found.");
+    }
+
+    Ok(())
+}
+
+async fn load_team(teams: &TeamManager, name: &str) -> Result<TeamConfig> {
+    if !teams.team_exists(name).await {
+        bail!("No team named '{name}' found.");
+    }
+    teams
+        .load_config(name)
+        .await
+        .with_context(|| format!("failed to load team '{name}'"))
+}
+
+fn is_running(leader: Option<&LeaderLock>) -> bool {
+    leader.is_some_and(|lock| !lock.is_stale())
+}
+
+fn leader_display(leader: Option<&LeaderLock>) -> String {
+    match leader {
+        Some(lock) if !lock.is_stale() => format!("running (pid {})", lock.pid),
+        _ => "dormant".to_string(),
+    }
+}
+
+/// Parse an age such as `90s`, `30m`, `12h`, `7d` or `2w`.
+fn parse_age(value: &str) -> Result<Duration, String> {
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let invalid = || format!("invalid age '{value}': expected e.g. 30m, 12h or 7d");
+    let (amount, unit) = value.split_at(split);
+    let amount: u64 = amount.parse().map_err(|_| invalid())?;
+    let seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return Err(invalid()),
+    };
+    Ok(Duration::from_secs(amount.saturating_mul(seconds)))
+}
+
+/// Parse a `KEY=VALUE` mapping of `--assignee` and `--label`.
+fn parse_mapping(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((key, teammate)) if !key.is_empty() && !teammate.is_empty() => {
+            Ok((key.to_string(), teammate.to_string()))
+        }
+        _ => Err(format!("invalid mapping '{value}': expected KEY=TEAMMATE")),
+    }
+}
+
+fn count_tasks(tasks: &[TeamTaskInfo], status: TeamTaskStatus) -> usize {
+    tasks.iter().filter(|task| task.status == status).count()
+}
+
+/// The name a unit enum variant serializes to, e.g. `in_progress` for a task
+/// status.
+fn serde_name(value: serde_json::Value) -> String {
+    value.as_str().unwrap_or_default().to_string()
+}
@@ -0,0 +1,496 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::Result;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::TeamCreatedEvent;
+use codex_core::protocol::TeamDisplayMode;
+use codex_core::protocol::TeamTaskInfo;
+use codex_core::protocol::TeamTaskStatus;
+use codex_core::teams::event_log::TeamEventLog;
+use codex_core::teams::task_list::TaskList;
+use codex_core::teams::team_manager::MemberConfig;
+use codex_core::teams::team_manager::TeamManager;
+use codex_protocol::ThreadId;
+use predicates::str::contains;
+use pretty_assertions::assert_eq;
+use serde_json::Value as JsonValue;
+use tempfile::TempDir;
+
+fn codex_command(home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::new(codex_utils_cargo_bin::cargo_bin("codex")?);
+    cmd.env("HOME", home).env("USERPROFILE", home);
+    Ok(cmd)
+}
+
+#[test]
+fn list_shows_empty_state() -> Result<()> {
+    let home = TempDir::new()?;
+
+    let output = codex_command(home.path())?
+        .args(["team", "list"])
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("No teams found."));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_show_and_cleanup_a_team() -> Result<()> {
+    let home = TempDir::new()?;
+    let teams = TeamManager::new(home.path().join(".codex").join("teams"));
+    let tasks = TaskList::new(home.path().join(".codex").join("tasks"));
+    let leader = ThreadId::new();
+    teams
+        .create_team(
+            "docs",
+            leader,
+            Some("Rewrite the docs".to_string()),
+            HashMap::new(),
+            TeamDisplayMode::Inline,
+        )
+        .await?;
+    tasks.init("docs").await?;
+    tasks
+        .create_task(
+            "docs",
+            TeamTaskInfo {
+                id: "task-1".to_string(),
+                title: "Outline".to_string(),
+                description: None,
+                status: TeamTaskStatus::InProgress,
+                assigned_to: Some("alice".to_string()),
+                depends_on: Vec::new(),
+                result: None,
+                created_at: None,
+            },
+        )
+        .await?;
+
+    let output = codex_command(home.path())?
+        .args(["team", "list"])
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("Name"));
+    assert!(stdout.contains("docs"));
+    assert!(stdout.contains("0/1"));
+    assert!(stdout.contains("running (pid"));
+
+    let output = codex_command(home.path())?
+        .args(["team", "show", "docs", "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let parsed: JsonValue = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(parsed["config"]["description"], "Rewrite the docs");
+    assert_eq!(parsed["leader_running"], true);
+    assert_eq!(parsed["tasks"][0]["status"], "in_progress");
+
+    codex_command(home.path())?
+        .args(["team", "show", "docs"])
+        .assert()
+        .success()
+        .stdout(contains("[task-1] Outline (in_progress, alice)"));
+
+    codex_command(home.path())?
+        .args(["team", "show", "missing"])
+        .assert()
+        .failure()
+        .stderr(contains("No team named 'missing' found."));
+
+    codex_command(home.path())?
+        .args(["team", "cleanup", "docs"])
+        .assert()
+        .failure()
+        .stderr(contains("--force"));
+    assert!(teams.team_exists("docs").await);
+
+    teams.release_leader_lock("docs", leader).await?;
+    codex_command(home.path())?
+        .args(["team", "cleanup", "docs"])
+        .assert()
+        .success()
+        .stdout(contains("Removed team 'docs'."));
+    assert!(!teams.team_exists("docs").await);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn tasks_lists_status_assignee_deps_and_age() -> Result<()> {
+    let home = TempDir::new()?;
+    let teams = TeamManager::new(home.path().join(".codex").join("teams"));
+    let tasks = TaskList::new(home.path().join(".codex").join("tasks"));
+    teams
+        .create_team(
+            "docs",
+            ThreadId::new(),
+            None,
+            HashMap::new(),
+            TeamDisplayMode::Inline,
+        )
+        .await?;
+    tasks.init("docs").await?;
+    let created_at = (chrono::Utc::now() - chrono::Duration::hours(3)).to_rfc3339();
+    for task in [
+        TeamTaskInfo {
+            id: "task-1".to_string(),
+            title: "Outline".to_string(),
+            description: None,
+            status: TeamTaskStatus::Completed,
+            assigned_to: Some("alice".to_string()),
+            depends_on: Vec::new(),
+            result: None,
+            created_at: Some(created_at),
+        },
+        TeamTaskInfo {
+            id: "task-2".to_string(),
+            title: "Draft".to_string(),
+            description: None,
+            status: TeamTaskStatus::Pending,
+            assigned_to: None,
+            depends_on: vec!["task-1".to_string()],
+            result: None,
+            created_at: None,
+        },
+    ] {
+        tasks.create_task("docs", task).await?;
+    }
+
+    let output = codex_command(home.path())?
+        .args(["team", "tasks", "docs"])
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    let lines: Vec<Vec<&str>> = stdout
+        .lines()
+        .map(|line| line.split_whitespace().collect())
+        .collect();
+    assert_eq!(
+        lines,
+        vec![
+            vec!["ID", "Title", "Status", "Assignee", "Deps", "Age"],
+            vec!["task-1", "Outline", "completed", "alice", "-", "3h"],
+            vec!["task-2", "Draft", "pending", "-", "task-1", "-"],
+        ]
+    );
+
+    let output = codex_command(home.path())?
+        .args(["team", "tasks", "docs", "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let parsed: JsonValue = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(parsed[1]["depends_on"][0], "task-1");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn doctor_reports_and_fixes_dead_members() -> Result<()> {
+    let home = TempDir::new()?;
+    let teams = TeamManager::new(home.path().join(".codex").join("teams"));
+    let tasks = TaskList::new(home.path().join(".codex").join("tasks"));
+    let leader = ThreadId::new();
+    teams
+        .create_team(
+            "docs",
+            leader,
+            None,
+            HashMap::new(),
+            TeamDisplayMode::Inline,
+        )
+        .await?;
+    // No session ever ran, so the member's thread has no rollout.
+    teams
+        .add_member(
+            "docs",
+            MemberConfig {
+                name: "alice".to_string(),
+                thread_id: ThreadId::new(),
+                status: "running".to_string(),
+                ..Default::default()
+            },
+        )
+        .await?;
+    tasks.init("docs").await?;
+    tasks
+        .create_task(
+            "docs",
+            TeamTaskInfo {
+                id: "task-1".to_string(),
+                title: "Outline".to_string(),
+                description: None,
+                status: TeamTaskStatus::InProgress,
+                assigned_to: Some("alice".to_string()),
+                depends_on: Vec::new(),
+                result: None,
+                created_at: None,
+            },
+        )
+        .await?;
+
+    codex_command(home.path())?
+        .args(["team", "doctor", "docs"])
+        .assert()
+        .success()
+        .stdout(contains("member 'alice' has no thread"))
+        .stdout(contains("codex team doctor docs --fix"));
+
+    codex_command(home.path())?
+        .args(["team", "doctor", "docs", "--fix"])
+        .assert()
+        .failure()
+        .stderr(contains("--force"));
+
+    teams.release_leader_lock("docs", leader).await?;
+    let output = codex_command(home.path())?
+        .args(["team", "doctor", "docs", "--fix", "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let parsed: JsonValue = serde_json::from_slice(&output.stdout)?;
+    let fixed: Vec<&str> = parsed["fixed"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|issue| issue["kind"].as_str())
+        .collect();
+    assert_eq!(fixed, vec!["dead_member", "unknown_assignee"]);
+    let task = &tasks.get_all_tasks("docs").await?[0];
+    assert_eq!(task.assigned_to, None);
+    assert_eq!(task.status, TeamTaskStatus::Pending);
+
+    codex_command(home.path())?
+        .args(["team", "doctor", "docs"])
+        .assert()
+        .success()
+        .stdout(contains("Team 'docs' looks healthy."));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn replay_prints_each_step_and_the_final_state() -> Result<()> {
+    let home = TempDir::new()?;
+    let teams = TeamManager::new(home.path().join(".codex").join("teams"));
+    let leader = ThreadId::new();
+    teams
+        .create_team(
+            "docs",
+            leader,
+            None,
+            HashMap::new(),
+            TeamDisplayMode::Inline,
+        )
+        .await?;
+    let log = TeamEventLog::new(teams.events_path("docs"));
+    // Tools return after the events they emit, so their calls come second.
+    log.append(
+        leader,
+        &EventMsg::TeamCreated(TeamCreatedEvent {
+            team_name: "docs".to_string(),
+            leader_thread_id: leader,
+            description: None,
+            display_mode: TeamDisplayMode::Inline,
+        }),
+    )
+    .await?;
+    log.append_tool_call(
+        leader,
+        "create_team",
+        r#"{"name":"docs"}"#,
+        r#"{"status":"created"}"#,
+        true,
+    )
+    .await?;
+    log.append_tool_call(
+        leader,
+        "shutdown_teammate",
+        r#"{"team_name":"docs","name":"ghost"}"#,
+        "teammate 'ghost' not found",
+        false,
+    )
+    .await?;
+
+    codex_command(home.path())?
+        .args(["team", "replay", "docs"])
+        .assert()
+        .success()
+        .stdout(contains(
+            r#"leader: called create_team {"name":"docs"} -> ok"#,
+        ))
+        .stdout(contains("leader: team 'docs' created"))
+        .stdout(contains("error: teammate 'ghost' not found"))
+        .stdout(contains("Team 'docs' after step 3 of 3:"))
+        .stdout(contains("0 message(s), 2 tool call(s), 1 failed"));
+
+    let output = codex_command(home.path())?
+        .args(["team", "replay", "docs", "--until", "1", "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let parsed: JsonValue = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(parsed["steps"].as_array().map(Vec::len), Some(1));
+    assert_eq!(parsed["steps"][0]["msg"]["type"], "team_created");
+    assert_eq!(parsed["state"]["team_name"], "docs");
+    assert_eq!(parsed["state"]["tool_calls"], 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn report_renders_the_recorded_run() -> Result<()> {
+    let home = TempDir::new()?;
+    let teams = TeamManager::new(home.path().join(".codex").join("teams"));
+    let leader = ThreadId::new();
+    teams
+        .create_team(
+            "docs",
+            leader,
+            None,
+            HashMap::new(),
+            TeamDisplayMode::Inline,
+        )
+        .await?;
+    TeamEventLog::new(teams.events_path("docs"))
+        .append(
+            leader,
+            &EventMsg::TeamCreated(TeamCreatedEvent {
+                team_name: "docs".to_string(),
+                leader_thread_id: leader,
+                description: None,
+                display_mode: TeamDisplayMode::Inline,
+            }),
+        )
+        .await?;
+
+    codex_command(home.path())?
+        .args(["team", "report", "docs"])
+        .assert()
+        .success()
+        .stdout(contains("# Team run report: docs"))
+        .stdout(contains("| leader | team 'docs' created |"));
+
+    let path = home.path().join("report.html");
+    codex_command(home.path())?
+        .args(["team", "report", "docs", "--html", "-o"])
+        .arg(&path)
+        .assert()
+        .success();
+    let html = std::fs::read_to_string(&path)?;
+    assert!(html.contains("<h1>Team run report: docs</h1>"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn gc_collects_orphaned_teams_and_stray_task_lists() -> Result<()> {
+    let home = TempDir::new()?;
+    let teams = TeamManager::new(home.path().join(".codex").join("teams"));
+    let tasks = TaskList::new(home.path().join(".codex").join("tasks"));
+    let crashed = ThreadId::new();
+    for (name, leader) in [("alive", ThreadId::new()), ("crashed", crashed)] {
+        teams
+            .create_team(name, leader, None, HashMap::new(), TeamDisplayMode::Inline)
+            .await?;
+        tasks.init(name).await?;
+    }
+    teams.release_leader_lock("crashed", crashed).await?;
+    tasks.init("gone").await?;
+
+    codex_command(home.path())?
+        .args(["team", "gc"])
+        .assert()
+        .success()
+        .stdout(contains(
+            "Deleted the task list of 'gone', which has no team.",
+        ));
+    assert!(teams.team_exists("crashed").await);
+
+    codex_command(home.path())?
+        .args(["team", "gc", "--older-than", "0s", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(contains("Would delete team 'crashed'."));
+    assert!(teams.team_exists("crashed").await);
+
+    codex_command(home.path())?
+        .args(["team", "gc", "--older-than", "0s", "--archive"])
+        .assert()
+        .success()
+        .stdout(contains("Archived team 'crashed'."));
+    assert!(teams.team_exists("alive").await);
+    assert!(!teams.team_exists("crashed").await);
+    assert_eq!(tasks.team_names().await?, vec!["alive".to_string()]);
+    assert!(home.path().join(".codex/tasks/.archive").exists());
+
+    codex_command(home.path())?
+        .args(["team", "gc", "--older-than", "soon"])
+        .assert()
+        .failure()
+        .stderr(contains("invalid age 'soon'"));
+
+    Ok(())
+}
+
+#[test]
+fn import_rejects_urls_that_are_not_milestones_or_projects() -> Result<()> {
+    let home = TempDir::new()?;
+
+    codex_command(home.path())?
+        .args([
+            "team",
+            "import",
+            "https://github.com/acme/widgets/issues/7",
+            "--name",
+            "widgets",
+        ])
+        .assert()
+        .failure()
+        .stderr(contains("is not the URL of a GitHub milestone"));
+
+    codex_command(home.path())?
+        .args([
+            "team",
+            "import",
+            "https://github.com/acme/widgets/milestone/3",
+            "--name",
+            "widgets",
+            "--label",
+            "docs",
+        ])
+        .assert()
+        .failure()
+        .stderr(contains("expected KEY=TEAMMATE"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn resume_member_refuses_while_the_team_is_led() -> Result<()> {
+    let home = TempDir::new()?;
+    let teams = TeamManager::new(home.path().join(".codex").join("teams"));
+    teams
+        .create_team(
+            "docs",
+            ThreadId::new(),
+            None,
+            HashMap::new(),
+            TeamDisplayMode::Inline,
+        )
+        .await?;
+
+    codex_command(home.path())?
+        .args(["team", "resume-member", "docs", "alice"])
+        .assert()
+        .failure()
+        .stderr(contains("restart_teammate, or pass --force"));
+
+    codex_command(home.path())?
+        .args(["team", "resume-member", "docs", "alice", "--force"])
+        .assert()
+        .failure()
+        .stderr(contains("team 'docs' has no member named 'alice'"));
+
+    Ok(())
+}
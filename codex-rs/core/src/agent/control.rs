@@ -1,16 +1,22 @@
 use crate::agent::AgentStatus;
 use crate::agent::guards::Guards;
+use crate::codex::Session;
 use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
 use crate::thread_manager::ThreadManagerState;
 use codex_protocol::ThreadId;
+use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::Op;
 use codex_protocol::protocol::SessionSource;
 use codex_protocol::protocol::TokenUsage;
 use codex_protocol::user_input::UserInput;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::sync::Weak;
+use std::time::Instant;
+use tokio::sync::broadcast;
 use tokio::sync::watch;
 
 /// Control-plane handle for multi-agent operations.
@@ -26,6 +32,8 @@ pub(crate) struct AgentControl {
     /// `ThreadManagerState -> CodexThread -> Session -> SessionServices -> ThreadManagerState`.
     manager: Weak<ThreadManagerState>,
     state: Arc<Guards>,
+    /// When each agent last emitted an event.
+    heartbeats: Arc<Mutex<HashMap<ThreadId, Instant>>>,
 }
 
 impl AgentControl {
@@ -37,7 +45,8 @@ impl AgentControl {
         }
     }
 
-    /// Spawn a new agent thread and submit the initial prompt.
+    /// Spawn a new agent thread and submit the initial prompt. With no
+    /// `items` the thread waits idle until input is sent to it.
     pub(crate) async fn spawn_agent(
         &self,
         config: crate::config::Config,
@@ -63,7 +72,9 @@ impl AgentControl {
         // TODO(jif) add helper for drain
         state.notify_thread_created(new_thread.thread_id);
 
-        self.send_input(new_thread.thread_id, items).await?;
+        if !items.is_empty() {
+            self.send_input(new_thread.thread_id, items).await?;
+        }
 
         Ok(new_thread.thread_id)
     }
@@ -129,9 +140,31 @@ impl AgentControl {
         let result = state.send_op(agent_id, Op::Shutdown {}).await;
         let _ = state.remove_thread(&agent_id).await;
         self.state.release_spawned_thread(agent_id);
+        self.heartbeats
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .remove(&agent_id);
         result
     }
 
+    /// Record that `agent_id` is alive. Sessions call this for every event
+    /// they emit.
+    pub(crate) fn heartbeat(&self, agent_id: ThreadId) {
+        self.heartbeats
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .insert(agent_id, Instant::now());
+    }
+
+    /// When `agent_id` last sent a heartbeat, if it has sent any.
+    pub(crate) fn last_heartbeat(&self, agent_id: ThreadId) -> Option<Instant> {
+        self.heartbeats
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .get(&agent_id)
+            .copied()
+    }
+
     /// Fetch the last known status for `agent_id`, returning `NotFound` when unavailable.
     pub(crate) async fn get_status(&self, agent_id: ThreadId) -> AgentStatus {
         let Ok(state) = self.upgrade() else {
@@ -154,6 +187,23 @@ impl AgentControl {
         Ok(thread.subscribe_status())
     }
 
+    /// Subscribe to the events `agent_id` emits from now on.
+    pub(crate) async fn subscribe_events(
+        &self,
+        agent_id: ThreadId,
+    ) -> CodexResult<broadcast::Receiver<EventMsg>> {
+        let state = self.upgrade()?;
+        let thread = state.get_thread(agent_id).await?;
+        Ok(thread.subscribe_events())
+    }
+
+    /// The session of `agent_id`, for work it takes over from another session.
+    pub(crate) async fn get_session(&self, agent_id: ThreadId) -> CodexResult<Arc<Session>> {
+        let state = self.upgrade()?;
+        let thread = state.get_thread(agent_id).await?;
+        Ok(thread.session())
+    }
+
     pub(crate) async fn get_total_token_usage(&self, agent_id: ThreadId) -> Option<TokenUsage> {
         let Ok(state) = self.upgrade() else {
             return None;
@@ -164,6 +214,17 @@ impl AgentControl {
         thread.total_token_usage().await
     }
 
+    /// Path of the rollout file recording `agent_id`, if it is known and persisted.
+    pub(crate) async fn get_rollout_path(&self, agent_id: ThreadId) -> Option<PathBuf> {
+        let Ok(state) = self.upgrade() else {
+            return None;
+        };
+        let Ok(thread) = state.get_thread(agent_id).await else {
+            return None;
+        };
+        thread.rollout_path()
+    }
+
     fn upgrade(&self) -> CodexResult<Arc<ThreadManagerState>> {
         self.manager
             .upgrade()
@@ -183,7 +244,6 @@ mod tests {
     use assert_matches::assert_matches;
     use codex_protocol::config_types::ModeKind;
     use codex_protocol::protocol::ErrorEvent;
-    use codex_protocol::protocol::EventMsg;
     use codex_protocol::protocol::TurnAbortReason;
     use codex_protocol::protocol::TurnAbortedEvent;
     use codex_protocol::protocol::TurnCompleteEvent;
@@ -269,6 +329,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn heartbeats_are_tracked_per_agent() {
+        let control = AgentControl::default();
+        let alive = ThreadId::new();
+        let before = Instant::now();
+
+        control.heartbeat(alive);
+
+        assert!(control.last_heartbeat(alive).is_some_and(|at| at >= before));
+        assert_eq!(control.last_heartbeat(ThreadId::new()), None);
+    }
+
     #[tokio::test]
     async fn get_status_returns_not_found_without_manager() {
         let control = AgentControl::default();
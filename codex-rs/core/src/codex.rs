@@ -93,6 +93,7 @@ use serde_json;
 use serde_json::Value;
 use tokio::sync::Mutex;
 use tokio::sync::RwLock;
+use tokio::sync::broadcast;
 use tokio::sync::oneshot;
 use tokio::sync::watch;
 use tokio_util::sync::CancellationToken;
@@ -226,6 +227,14 @@ use crate::tasks::RegularTask;
 use crate::tasks::ReviewTask;
 use crate::tasks::SessionTask;
 use crate::tasks::SessionTaskContext;
+use crate::teams::conflicts::record_teammate_edits;
+use crate::teams::event_log::logged_team_name;
+use crate::teams::metrics::record_team_metrics;
+use crate::teams::notifiers::send_notifications;
+use crate::teams::service::TeamService;
+use crate::teams::spans::record_team_span;
+use crate::teams::webhooks::send_webhooks;
+use crate::teams::webhooks::webhook_payloads;
 use crate::tools::ToolRouter;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::handlers::SEARCH_TOOL_BM25_TOOL_NAME;
@@ -235,6 +244,7 @@ use crate::tools::network_approval::build_blocked_request_observer;
 use crate::tools::network_approval::build_network_policy_decider;
 use crate::tools::parallel::ToolCallRuntime;
 use crate::tools::sandboxing::ApprovalStore;
+use crate::tools::spec::TeamToolsScope;
 use crate::tools::spec::ToolsConfig;
 use crate::tools::spec::ToolsConfigParams;
 use crate::turn_diff_tracker::TurnDiffTracker;
@@ -283,6 +293,9 @@ pub struct CodexSpawnOk {
 
 pub(crate) const INITIAL_SUBMIT_ID: &str = "";
 pub(crate) const SUBMISSION_CHANNEL_CAPACITY: usize = 64;
+/// Events buffered for each [`Session::subscribe_events`] receiver before the
+/// slowest one starts missing them.
+const EVENT_TAP_CAPACITY: usize = 256;
 const CYBER_VERIFY_URL: &str = "https://chatgpt.com/cyber";
 const CYBER_SAFETY_URL: &str = "https://developers.openai.com/codex/concepts/cyber-safety";
 
@@ -518,6 +531,9 @@ pub(crate) struct Session {
     pub(crate) conversation_id: ThreadId,
     tx_event: Sender<Event>,
     agent_status: watch::Sender<AgentStatus>,
+    /// Copies of emitted events for in-process observers, such as a team
+    /// leader following its teammates' output.
+    event_tap: broadcast::Sender<EventMsg>,
     state: Mutex<SessionState>,
     /// The set of enabled features should be invariant for the lifetime of the
     /// session.
@@ -609,7 +625,9 @@ impl TurnContext {
             features: &features,
             web_search_mode: self.tools_config.web_search_mode,
         })
-        .with_agent_roles(config.agent_roles.clone());
+        .with_agent_roles(config.agent_roles.clone())
+        .with_team_tools(self.tools_config.team_tools)
+        .with_allowed_tools(self.tools_config.allowed_tools.clone());
 
         Self {
             sub_id: self.sub_id.clone(),
@@ -944,7 +962,12 @@ impl Session {
             features: &per_turn_config.features,
             web_search_mode: Some(per_turn_config.web_search_mode.value()),
         })
-        .with_agent_roles(per_turn_config.agent_roles.clone());
+        .with_agent_roles(per_turn_config.agent_roles.clone())
+        .with_team_tools(TeamToolsScope::for_session(
+            &per_turn_config.teams,
+            &session_source,
+        ))
+        .with_allowed_tools(per_turn_config.teams.allowed_tools.clone());
 
         let cwd = session_configuration.cwd.clone();
         let turn_metadata_state = Arc::new(TurnMetadataState::new(
@@ -1308,6 +1331,7 @@ impl Session {
             skills_manager,
             file_watcher,
             agent_control,
+            teams: Arc::new(TeamService::new(config.teams.max_members)),
             network_proxy,
             network_approval: Arc::clone(&network_approval),
             state_db: state_db_ctx.clone(),
@@ -1344,6 +1368,7 @@ impl Session {
             conversation_id,
             tx_event: tx_event.clone(),
             agent_status,
+            event_tap: broadcast::channel(EVENT_TAP_CAPACITY).0,
             state: Mutex::new(state),
             features: config.features.clone(),
             pending_mcp_server_refresh_config: Mutex::new(None),
@@ -1444,6 +1469,12 @@ impl Session {
             Arc::clone(&config),
             &session_configuration.session_source,
         );
+        crate::teams::gc::start_team_gc_task(&sess, &session_configuration.session_source);
+        crate::teams::join::start_team_join(
+            &sess,
+            &config.teams,
+            &session_configuration.session_source,
+        );
 
         Ok(sess)
     }
@@ -2056,6 +2087,12 @@ impl Session {
         if let Some(status) = agent_status_from_event(&event.msg) {
             self.agent_status.send_replace(status);
         }
+        self.tap_event(&event.msg);
+        record_team_span(&event.msg);
+        record_team_metrics(&event.msg, self.conversation_id);
+        self.log_team_event(&event.msg).await;
+        self.notify_team_event(&event.msg).await;
+        self.services.agent_control.heartbeat(self.conversation_id);
         // Persist the event into rollout (recorder filters as needed)
         let rollout_items = vec![RolloutItem::EventMsg(event.msg.clone())];
         self.persist_rollout_items(&rollout_items).await;
@@ -2074,6 +2111,11 @@ impl Session {
         if let Some(status) = agent_status_from_event(&event.msg) {
             self.agent_status.send_replace(status);
         }
+        self.tap_event(&event.msg);
+        record_team_span(&event.msg);
+        record_team_metrics(&event.msg, self.conversation_id);
+        self.log_team_event(&event.msg).await;
+        self.services.agent_control.heartbeat(self.conversation_id);
         self.persist_rollout_items(&[RolloutItem::EventMsg(event.msg.clone())])
             .await;
         self.flush_rollout().await;
@@ -2082,6 +2124,54 @@ impl Session {
         }
     }
 
+    /// Receive a copy of every event this session emits from now on.
+    pub(crate) fn subscribe_events(&self) -> broadcast::Receiver<EventMsg> {
+        self.event_tap.subscribe()
+    }
+
+    fn tap_event(&self, msg: &EventMsg) {
+        if self.event_tap.receiver_count() > 0 {
+            let _ = self.event_tap.send(msg.clone());
+        }
+    }
+
+    /// Append team events to their team's `events.jsonl`, for observers in
+    /// other processes such as `codex team attach`.
+    async fn log_team_event(&self, msg: &EventMsg) {
+        let Some(team_name) = logged_team_name(msg) else {
+            return;
+        };
+        let log = self.services.teams.event_log(team_name);
+        if let Err(e) = log.append(self.conversation_id, msg).await {
+            debug!("not logging team event for {team_name}: {e}");
+        }
+    }
+
+    /// Send the `teams.webhooks`, `teams.notifiers` and `teams.email`
+    /// messages that `msg` calls for, in the background.
+    async fn notify_team_event(&self, msg: &EventMsg) {
+        if logged_team_name(msg).is_none() {
+            return;
+        }
+        let config = self.get_config().await;
+        let teams = &config.teams;
+        if teams.webhooks.is_empty() && teams.notifiers.is_empty() && teams.email.is_none() {
+            return;
+        }
+        // Building the payloads reads the task list, and an email reads the
+        // whole team for its report, so it happens off the event path.
+        let team_service = Arc::clone(&self.services.teams);
+        let msg = msg.clone();
+        tokio::spawn(async move {
+            let teams = &config.teams;
+            let payloads = webhook_payloads(&team_service, &msg).await;
+            for payload in &payloads {
+                send_webhooks(&teams.webhooks, payload);
+            }
+            send_notifications(&team_service, teams, &payloads).await;
+        });
+    }
+
     pub(crate) async fn emit_turn_item_started(&self, turn_context: &TurnContext, item: &TurnItem) {
         self.send_event(
             turn_context,
@@ -3257,6 +3347,69 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                 )
                 .await;
             }
+            Op::CreateTeamTask {
+                team_name,
+                title,
+                description,
+                assigned_to,
+                depends_on,
+            } => {
+                handlers::create_team_task(
+                    &sess,
+                    sub.id.clone(),
+                    team_name,
+                    title,
+                    description,
+                    assigned_to,
+                    depends_on,
+                )
+                .await;
+            }
+            Op::ReassignTeamTask {
+                team_name,
+                task_id,
+                assigned_to,
+            } => {
+                handlers::reassign_team_task(
+                    &sess,
+                    sub.id.clone(),
+                    team_name,
+                    task_id,
+                    assigned_to,
+                )
+                .await;
+            }
+            Op::ShutdownTeammate { team_name, name } => {
+                handlers::shutdown_teammate(&sess, sub.id.clone(), team_name, name).await;
+            }
+            Op::RestartTeammate { team_name, name } => {
+                handlers::restart_teammate(&sess, sub.id.clone(), team_name, name).await;
+            }
+            Op::SendTeammateMessage {
+                team_name,
+                to,
+                content,
+            } => {
+                handlers::send_teammate_message(&sess, sub.id.clone(), team_name, to, content)
+                    .await;
+            }
+            Op::AnswerTeamEscalation {
+                team_name,
+                escalation_id,
+                answer,
+            } => {
+                handlers::answer_team_escalation(
+                    &sess,
+                    sub.id.clone(),
+                    team_name,
+                    escalation_id,
+                    answer,
+                )
+                .await;
+            }
+            Op::RunTeamPlan { plan } => {
+                handlers::run_team_plan(&sess, sub.id.clone(), plan).await;
+            }
             Op::ResolveElicitation {
                 server_name,
                 request_id,
@@ -3298,6 +3451,13 @@ mod handlers {
     use crate::tasks::UserShellCommandMode;
     use crate::tasks::UserShellCommandTask;
     use crate::tasks::execute_user_shell_command;
+    use crate::tools::handlers::team::answer_user_team_escalation;
+    use crate::tools::handlers::team::create_user_task;
+    use crate::tools::handlers::team::reassign_user_task;
+    use crate::tools::handlers::team::restart_user_teammate;
+    use crate::tools::handlers::team::run_user_team_plan;
+    use crate::tools::handlers::team::send_user_team_message;
+    use crate::tools::handlers::team::shutdown_user_teammate;
     use codex_protocol::custom_prompts::CustomPrompt;
     use codex_protocol::protocol::CodexErrorInfo;
     use codex_protocol::protocol::ErrorEvent;
@@ -3315,6 +3475,7 @@ mod handlers {
     use codex_protocol::protocol::ReviewDecision;
     use codex_protocol::protocol::ReviewRequest;
     use codex_protocol::protocol::SkillsListEntry;
+    use codex_protocol::protocol::TeamPlan;
     use codex_protocol::protocol::ThreadNameUpdatedEvent;
     use codex_protocol::protocol::ThreadRolledBackEvent;
     use codex_protocol::protocol::TurnAbortReason;
@@ -3797,6 +3958,100 @@ mod handlers {
         }
     }
 
+    pub async fn create_team_task(
+        sess: &Arc<Session>,
+        sub_id: String,
+        team_name: String,
+        title: String,
+        description: Option<String>,
+        assigned_to: Option<String>,
+        depends_on: Vec<String>,
+    ) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        create_user_task(
+            Arc::clone(sess),
+            turn_context,
+            team_name,
+            title,
+            description,
+            assigned_to,
+            depends_on,
+        )
+        .await;
+    }
+
+    pub async fn reassign_team_task(
+        sess: &Arc<Session>,
+        sub_id: String,
+        team_name: String,
+        task_id: String,
+        assigned_to: String,
+    ) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        reassign_user_task(
+            Arc::clone(sess),
+            turn_context,
+            team_name,
+            task_id,
+            assigned_to,
+        )
+        .await;
+    }
+
+    pub async fn shutdown_teammate(
+        sess: &Arc<Session>,
+        sub_id: String,
+        team_name: String,
+        name: String,
+    ) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        shutdown_user_teammate(Arc::clone(sess), turn_context, team_name, name).await;
+    }
+
+    pub async fn restart_teammate(
+        sess: &Arc<Session>,
+        sub_id: String,
+        team_name: String,
+        name: String,
+    ) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        restart_user_teammate(Arc::clone(sess), turn_context, team_name, name).await;
+    }
+
+    pub async fn send_teammate_message(
+        sess: &Arc<Session>,
+        sub_id: String,
+        team_name: String,
+        to: String,
+        content: String,
+    ) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        send_user_team_message(Arc::clone(sess), turn_context, team_name, to, content).await;
+    }
+
+    pub async fn answer_team_escalation(
+        sess: &Arc<Session>,
+        sub_id: String,
+        team_name: String,
+        escalation_id: String,
+        answer: String,
+    ) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        answer_user_team_escalation(
+            Arc::clone(sess),
+            turn_context,
+            team_name,
+            escalation_id,
+            answer,
+        )
+        .await;
+    }
+
+    pub async fn run_team_plan(sess: &Arc<Session>, sub_id: String, plan: TeamPlan) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        run_user_team_plan(Arc::clone(sess), turn_context, plan).await;
+    }
+
     pub async fn undo(sess: &Arc<Session>, sub_id: String) {
         let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
         sess.spawn_task(turn_context, Vec::new(), UndoTask::new())
@@ -5723,14 +5978,15 @@ async fn try_run_sampling_request(
     drain_in_flight(&mut in_flight, sess.clone(), turn_context.clone()).await?;
 
     if should_emit_turn_diff {
-        let unified_diff = {
+        let (unified_diff, touched_paths) = {
             let mut tracker = turn_diff_tracker.lock().await;
-            tracker.get_unified_diff()
+            (tracker.get_unified_diff(), tracker.touched_paths())
         };
         if let Ok(Some(unified_diff)) = unified_diff {
             let msg = EventMsg::TurnDiff(TurnDiffEvent { unified_diff });
             sess.clone().send_event(&turn_context, msg).await;
         }
+        record_teammate_edits(&sess, &turn_context, &touched_paths).await;
     }
 
     outcome
@@ -7397,6 +7653,7 @@ mod tests {
             skills_manager,
             file_watcher,
             agent_control,
+            teams: Arc::new(TeamService::new(config.teams.max_members)),
             network_proxy: None,
             network_approval: Arc::clone(&network_approval),
             state_db: None,
@@ -7436,6 +7693,7 @@ mod tests {
             conversation_id,
             tx_event,
             agent_status: agent_status_tx,
+            event_tap: broadcast::channel(EVENT_TAP_CAPACITY).0,
             state: Mutex::new(state),
             features: config.features.clone(),
             pending_mcp_server_refresh_config: Mutex::new(None),
@@ -7546,6 +7804,7 @@ mod tests {
             skills_manager,
             file_watcher,
             agent_control,
+            teams: Arc::new(TeamService::new(config.teams.max_members)),
             network_proxy: None,
             network_approval: Arc::clone(&network_approval),
             state_db: None,
@@ -7585,6 +7844,7 @@ mod tests {
             conversation_id,
             tx_event,
             agent_status: agent_status_tx,
+            event_tap: broadcast::channel(EVENT_TAP_CAPACITY).0,
             state: Mutex::new(state),
             features: config.features.clone(),
             pending_mcp_server_refresh_config: Mutex::new(None),
@@ -1,10 +1,12 @@
 use crate::agent::AgentStatus;
 use crate::codex::Codex;
+use crate::codex::Session;
 use crate::codex::SteerInputError;
 use crate::error::Result as CodexResult;
 use crate::features::Feature;
 use crate::file_watcher::WatchRegistration;
 use crate::protocol::Event;
+use crate::protocol::EventMsg;
 use crate::protocol::Op;
 use crate::protocol::Submission;
 use codex_protocol::config_types::Personality;
@@ -15,6 +17,8 @@ use codex_protocol::protocol::SessionSource;
 use codex_protocol::protocol::TokenUsage;
 use codex_protocol::user_input::UserInput;
 use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::broadcast;
 use tokio::sync::watch;
 
 use crate::state_db::StateDbHandle;
@@ -81,6 +85,14 @@ impl CodexThread {
         self.codex.agent_status.clone()
     }
 
+    pub(crate) fn subscribe_events(&self) -> broadcast::Receiver<EventMsg> {
+        self.codex.session.subscribe_events()
+    }
+
+    pub(crate) fn session(&self) -> Arc<Session> {
+        Arc::clone(&self.codex.session)
+    }
+
     pub(crate) async fn total_token_usage(&self) -> Option<TokenUsage> {
         self.codex.session.total_token_usage().await
     }
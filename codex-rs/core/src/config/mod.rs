@@ -3,6 +3,7 @@ use crate::config::edit::ConfigEdit;
 use crate::config::edit::ConfigEditsBuilder;
 use crate::config::types::AppsConfigToml;
 use crate::config::types::DEFAULT_OTEL_ENVIRONMENT;
+use crate::config::types::DEFAULT_TUI_TEAM_OVERLAY_KEY;
 use crate::config::types::History;
 use crate::config::types::McpServerConfig;
 use crate::config::types::McpServerDisabledReason;
@@ -15,10 +16,13 @@ use crate::config::types::Notifications;
 use crate::config::types::OtelConfig;
 use crate::config::types::OtelConfigToml;
 use crate::config::types::OtelExporterKind;
+use crate::config::types::RESERVED_TUI_TEAM_OVERLAY_KEYS;
 use crate::config::types::SandboxWorkspaceWrite;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyToml;
 use crate::config::types::SkillsConfig;
+use crate::config::types::TeamsConfig;
+use crate::config::types::TeamsToml;
 use crate::config::types::Tui;
 use crate::config::types::UriBasedFileOpener;
 use crate::config::types::WindowsSandboxModeToml;
@@ -263,6 +267,11 @@ pub struct Config {
     /// `current-dir`.
     pub tui_status_line: Option<Vec<String>>,
 
+    /// Letter that toggles the team task overlay together with Ctrl.
+    ///
+    /// This is the same `tui.team_overlay_key` value from `config.toml` (see [`Tui`]).
+    pub tui_team_overlay_key: char,
+
     /// The directory that should be treated as the current working directory
     /// for the session. All relative paths inside the business-logic layer are
     /// resolved against this path.
@@ -312,6 +321,9 @@ pub struct Config {
     /// Memories subsystem settings.
     pub memories: MemoriesConfig,
 
+    /// Agent Teams settings.
+    pub teams: TeamsConfig,
+
     /// Directory containing all Codex state (defaults to `~/.codex` but can be
     /// overridden by the `CODEX_HOME` environment variable).
     pub codex_home: PathBuf,
@@ -1049,6 +1061,9 @@ pub struct ConfigToml {
     /// Memories subsystem settings.
     pub memories: Option<MemoriesToml>,
 
+    /// Agent Teams settings (team size limits, etc.).
+    pub teams: Option<TeamsToml>,
+
     /// User-level skill config entries keyed by SKILL.md path.
     pub skills: Option<SkillsConfig>,
 
@@ -1652,6 +1667,25 @@ impl Config {
                 "agents.max_threads must be at least 1",
             ));
         }
+        let tui_team_overlay_key = cfg
+            .tui
+            .as_ref()
+            .map_or(DEFAULT_TUI_TEAM_OVERLAY_KEY, |t| t.team_overlay_key);
+        if !tui_team_overlay_key.is_ascii_lowercase()
+            || RESERVED_TUI_TEAM_OVERLAY_KEYS.contains(&tui_team_overlay_key)
+        {
+            let reserved: Vec<String> = RESERVED_TUI_TEAM_OVERLAY_KEYS
+                .iter()
+                .map(char::to_string)
+                .collect();
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "tui.team_overlay_key must be a lowercase letter other than {}; got '{tui_team_overlay_key}'",
+                    reserved.join(", ")
+                ),
+            ));
+        }
         let agent_roles = cfg
             .agents
             .as_ref()
@@ -1891,6 +1925,7 @@ impl Config {
             agent_max_threads,
             agent_roles,
             memories: cfg.memories.unwrap_or_default().into(),
+            teams: cfg.teams.unwrap_or_default().into(),
             codex_home,
             log_dir,
             config_layer_stack,
@@ -1964,6 +1999,7 @@ impl Config {
                 .map(|t| t.alternate_screen)
                 .unwrap_or_default(),
             tui_status_line: cfg.tui.as_ref().and_then(|t| t.status_line.clone()),
+            tui_team_overlay_key,
             otel: {
                 let t: OtelConfigToml = cfg.otel.unwrap_or_default();
                 let log_user_prompt = t.log_user_prompt.unwrap_or(false);
@@ -2147,6 +2183,15 @@ mod tests {
     use crate::config::types::MemoriesToml;
     use crate::config::types::NotificationMethod;
     use crate::config::types::Notifications;
+    use crate::config::types::TeamEmailNotifier;
+    use crate::config::types::TeamGitHubIssues;
+    use crate::config::types::TeamJoin;
+    use crate::config::types::TeamLinearSync;
+    use crate::config::types::TeamNotifierConfig;
+    use crate::config::types::TeamNotifierService;
+    use crate::config::types::TeamWebhook;
+    use crate::config::types::TeamWebhookEvent;
+    use crate::config::types::TeammateOutput;
     use crate::config_loader::RequirementSource;
     use crate::features::Feature;
 
@@ -2269,6 +2314,138 @@ phase_2_model = "gpt-5"
                 phase_2_model: Some("gpt-5".to_string()),
             }
         );
+
+        let teams = r#"
+[teams]
+enabled = true
+max_members = 3
+max_depth = 2
+max_members_by_depth = [3, 2]
+max_team_tokens = 500000
+max_concurrent_agents = 2
+max_auto_restarts = 3
+co_author_trailers = true
+teammate_output = "stream"
+heartbeat_timeout_secs = 120
+a2a_endpoints = ["https://agents.example.com/a2a"]
+ssh_hosts = ["builder@build-box"]
+cents_per_million_input_tokens = 125
+cents_per_million_output_tokens = 1000
+metrics_listen = "127.0.0.1:9464"
+
+[[teams.webhooks]]
+url = "https://hooks.example.com/codex"
+events = ["task_failed", "member_errored"]
+
+[[teams.notifiers]]
+service = "slack"
+webhook_url = "https://hooks.slack.com/services/T0/B0/x"
+milestones = ["task_completed"]
+
+[teams.email]
+smtp_host = "smtp.example.com"
+from = "codex@example.com"
+to = ["oncall@example.com"]
+error_threshold = 2
+
+[[teams.github_issues]]
+repo = "acme/widgets"
+team = "zeta"
+labels = ["codex"]
+assignees = { reviewer = "octocat" }
+mirror_comments = true
+
+[[teams.linear]]
+linear_team_id = "team-uuid"
+done_state_id = "done-uuid"
+
+[teams.join]
+team = "zeta"
+name = "alice"
+role = "reviewer"
+"#;
+        let teams_cfg =
+            toml::from_str::<ConfigToml>(teams).expect("TOML deserialization should succeed");
+        let config = Config::load_from_base_config_with_overrides(
+            teams_cfg,
+            ConfigOverrides::default(),
+            tempdir().expect("tempdir").path().to_path_buf(),
+        )
+        .expect("load config from teams settings");
+        assert_eq!(
+            config.teams,
+            TeamsConfig {
+                enabled: true,
+                max_members: 3,
+                max_depth: 2,
+                max_members_by_depth: vec![3, 2],
+                max_team_tokens: Some(500_000),
+                max_concurrent_agents: Some(2),
+                max_auto_restarts: Some(3),
+                co_author_trailers: true,
+                teammate_output: TeammateOutput::Stream,
+                heartbeat_timeout_secs: 120,
+                cents_per_million_input_tokens: Some(125),
+                cents_per_million_output_tokens: Some(1000),
+                webhooks: vec![TeamWebhook {
+                    url: "https://hooks.example.com/codex".to_string(),
+                    events: vec![
+                        TeamWebhookEvent::TaskFailed,
+                        TeamWebhookEvent::MemberErrored,
+                    ],
+                }],
+                notifiers: vec![TeamNotifierConfig {
+                    service: TeamNotifierService::Slack,
+                    webhook_url: "https://hooks.slack.com/services/T0/B0/x".to_string(),
+                    milestones: vec![TeamWebhookEvent::TaskCompleted],
+                }],
+                email: Some(TeamEmailNotifier {
+                    smtp_host: "smtp.example.com".to_string(),
+                    smtp_port: None,
+                    username: None,
+                    password_env_var: None,
+                    from: "codex@example.com".to_string(),
+                    to: vec!["oncall@example.com".to_string()],
+                    error_threshold: Some(2),
+                }),
+                github_issues: vec![TeamGitHubIssues {
+                    repo: "acme/widgets".to_string(),
+                    team: Some("zeta".to_string()),
+                    labels: vec!["codex".to_string()],
+                    assignees: BTreeMap::from([("reviewer".to_string(), "octocat".to_string())]),
+                    mirror_comments: true,
+                    token_env_var: None,
+                    api_url: None,
+                }],
+                jira: Vec::new(),
+                linear: vec![TeamLinearSync {
+                    team: None,
+                    linear_team_id: "team-uuid".to_string(),
+                    token_env_var: None,
+                    label_ids: Vec::new(),
+                    assignees: BTreeMap::new(),
+                    done_state_id: "done-uuid".to_string(),
+                    api_url: None,
+                }],
+                metrics_listen: Some("127.0.0.1:9464".parse().expect("socket address")),
+                join: Some(TeamJoin {
+                    team: "zeta".to_string(),
+                    name: "alice".to_string(),
+                    role: Some("reviewer".to_string()),
+                }),
+                a2a_endpoints: vec!["https://agents.example.com/a2a".to_string()],
+                ssh_hosts: vec!["builder@build-box".to_string()],
+                allowed_tools: None,
+            }
+        );
+        assert_eq!(config.teams.max_members_at(2), 2);
+        assert_eq!(config.teams.max_members_at(3), 3);
+        assert!(config.teams.github_issues_for("zeta").is_some());
+        assert_eq!(config.teams.github_issues_for("other"), None);
+        assert_eq!(
+            config.teams.linear_for("other"),
+            config.teams.linear.first()
+        );
     }
 
     #[test]
@@ -2290,6 +2467,7 @@ phase_2_model = "gpt-5"
                 show_tooltips: true,
                 alternate_screen: AltScreenMode::Auto,
                 status_line: None,
+                team_overlay_key: 'k',
             }
         );
     }
@@ -4126,6 +4304,29 @@ model = "gpt-5.1-codex"
         Ok(())
     }
 
+    #[test]
+    fn load_config_rejects_reserved_team_overlay_keys() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        for key in ['t', 'K', '1', 'é'] {
+            let cfg = ConfigToml {
+                tui: Some(Tui {
+                    team_overlay_key: key,
+                    ..Tui::default()
+                }),
+                ..Default::default()
+            };
+            let err = Config::load_from_base_config_with_overrides(
+                cfg,
+                ConfigOverrides::default(),
+                codex_home.path().to_path_buf(),
+            )
+            .expect_err("unusable overlay key should be rejected");
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput, "{key}");
+            assert!(err.to_string().contains("tui.team_overlay_key"));
+        }
+        Ok(())
+    }
+
     #[tokio::test]
     async fn agent_role_relative_config_file_resolves_against_config_toml() -> std::io::Result<()> {
         let codex_home = TempDir::new()?;
@@ -4318,6 +4519,7 @@ model_verbosity = "high"
                 agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
                 agent_roles: BTreeMap::new(),
                 memories: MemoriesConfig::default(),
+                teams: TeamsConfig::default(),
                 codex_home: fixture.codex_home(),
                 log_dir: fixture.codex_home().join("log"),
                 config_layer_stack: Default::default(),
@@ -4363,6 +4565,7 @@ model_verbosity = "high"
                 feedback_enabled: true,
                 tui_alternate_screen: AltScreenMode::Auto,
                 tui_status_line: None,
+                tui_team_overlay_key: 'k',
                 otel: OtelConfig::default(),
             },
             o3_profile_config
@@ -4432,6 +4635,7 @@ model_verbosity = "high"
             agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
             agent_roles: BTreeMap::new(),
             memories: MemoriesConfig::default(),
+            teams: TeamsConfig::default(),
             codex_home: fixture.codex_home(),
             log_dir: fixture.codex_home().join("log"),
             config_layer_stack: Default::default(),
@@ -4477,6 +4681,7 @@ model_verbosity = "high"
             feedback_enabled: true,
             tui_alternate_screen: AltScreenMode::Auto,
             tui_status_line: None,
+            tui_team_overlay_key: 'k',
             otel: OtelConfig::default(),
         };
 
@@ -4544,6 +4749,7 @@ model_verbosity = "high"
             agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
             agent_roles: BTreeMap::new(),
             memories: MemoriesConfig::default(),
+            teams: TeamsConfig::default(),
             codex_home: fixture.codex_home(),
             log_dir: fixture.codex_home().join("log"),
             config_layer_stack: Default::default(),
@@ -4589,6 +4795,7 @@ model_verbosity = "high"
             feedback_enabled: true,
             tui_alternate_screen: AltScreenMode::Auto,
             tui_status_line: None,
+            tui_team_overlay_key: 'k',
             otel: OtelConfig::default(),
         };
 
@@ -4642,6 +4849,7 @@ model_verbosity = "high"
             agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
             agent_roles: BTreeMap::new(),
             memories: MemoriesConfig::default(),
+            teams: TeamsConfig::default(),
             codex_home: fixture.codex_home(),
             log_dir: fixture.codex_home().join("log"),
             config_layer_stack: Default::default(),
@@ -4687,6 +4895,7 @@ model_verbosity = "high"
             feedback_enabled: true,
             tui_alternate_screen: AltScreenMode::Auto,
             tui_status_line: None,
+            tui_team_overlay_key: 'k',
             otel: OtelConfig::default(),
         };
 
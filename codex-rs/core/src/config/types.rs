@@ -12,6 +12,7 @@ use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::fmt;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::time::Duration;
 use wildmatch::WildMatchPattern;
@@ -27,6 +28,13 @@ pub const DEFAULT_MEMORIES_MAX_ROLLOUTS_PER_STARTUP: usize = 8;
 pub const DEFAULT_MEMORIES_MAX_ROLLOUT_AGE_DAYS: i64 = 30;
 pub const DEFAULT_MEMORIES_MIN_ROLLOUT_IDLE_HOURS: i64 = 12;
 pub const DEFAULT_MEMORIES_MAX_RAW_MEMORIES_FOR_GLOBAL: usize = 1_024;
+pub const DEFAULT_TEAMS_MAX_MEMBERS: usize = 8;
+pub const DEFAULT_TUI_TEAM_OVERLAY_KEY: char = 'k';
+
+/// Letters the TUI already binds with Ctrl (interrupt, quit, external
+/// editor, transcript, suspend) or that terminals send as other keys
+/// (Ctrl+H, I, J and M), so `tui.team_overlay_key` may not use them.
+pub const RESERVED_TUI_TEAM_OVERLAY_KEYS: [char; 9] = ['c', 'd', 'g', 'h', 'i', 'j', 'm', 't', 'z'];
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
@@ -425,6 +433,397 @@ impl From<MemoriesToml> for MemoriesConfig {
     }
 }
 
+/// Agent Teams settings loaded from config.toml.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct TeamsToml {
+    /// Whether the Agent Teams tools are offered to the model. Defaults to
+    /// false.
+    pub enabled: Option<bool>,
+    /// Maximum number of teammates a single team may have. Defaults to 8.
+    #[schemars(range(min = 1))]
+    pub max_members: Option<usize>,
+    /// How many levels of teams may nest. 1 (the default) keeps teammates
+    /// from leading sub-teams; 2 lets them lead one level of sub-teams.
+    #[schemars(range(min = 1))]
+    pub max_depth: Option<usize>,
+    /// Tighter member caps per nesting level, starting with top-level teams.
+    /// Levels without an entry use `max_members`.
+    pub max_members_by_depth: Option<Vec<usize>>,
+    /// Token ceiling for a whole team, leader and teammates combined. New
+    /// teammates are refused once the team reaches it.
+    #[schemars(range(min = 1))]
+    pub max_team_tokens: Option<i64>,
+    /// How many teammates of a team may work at once. Teammates spawned
+    /// beyond it wait in a queue and start as others finish.
+    #[schemars(range(min = 1))]
+    pub max_concurrent_agents: Option<usize>,
+    /// How many times an errored teammate is respawned automatically, with
+    /// exponential backoff. Unset or 0 leaves errored teammates alone unless
+    /// `spawn_teammate` sets `max_restarts`.
+    pub max_auto_restarts: Option<u32>,
+    /// Append a `Co-authored-by: <teammate> (codex teammate)` trailer for
+    /// each teammate whose work a commit brings in. Defaults to false.
+    pub co_author_trailers: Option<bool>,
+    /// How much of each teammate's output is forwarded to the leader's
+    /// session while the teammate works. Defaults to `off`.
+    pub teammate_output: Option<TeammateOutput>,
+    /// How long a teammate working on a turn may go without emitting any
+    /// event before it is marked stale. Defaults to 300 seconds; 0 turns the
+    /// check off.
+    pub heartbeat_timeout_secs: Option<u64>,
+    /// Price of a million input tokens, in US cents, used to estimate each
+    /// teammate's cost in the TUI. Unset hides cost estimates.
+    pub cents_per_million_input_tokens: Option<u64>,
+    /// Price of a million output tokens, in US cents, used to estimate each
+    /// teammate's cost in the TUI. Unset hides cost estimates.
+    pub cents_per_million_output_tokens: Option<u64>,
+    /// Endpoints sent a JSON `POST` when a team is created or cleaned up, a
+    /// task completes or fails, or a teammate errors.
+    pub webhooks: Option<Vec<TeamWebhook>>,
+    /// Slack or Discord channels sent a short progress message at team
+    /// milestones.
+    pub notifiers: Option<Vec<TeamNotifierConfig>>,
+    /// Mailbox sent a summary of a team's run over SMTP when the team
+    /// finishes, too many teammates error, or a teammate goes over its
+    /// budget.
+    pub email: Option<TeamEmailNotifier>,
+    /// GitHub repositories whose issues mirror team tasks, per team.
+    pub github_issues: Option<Vec<TeamGitHubIssues>>,
+    /// Jira projects whose issues mirror team tasks, per team. Needs a build
+    /// with the `jira` feature.
+    pub jira: Option<Vec<TeamJiraSync>>,
+    /// Linear teams whose issues mirror team tasks, per team. Needs a build
+    /// with the `linear` feature.
+    pub linear: Option<Vec<TeamLinearSync>>,
+    /// Address, e.g. `127.0.0.1:9464`, the app server serves team metrics
+    /// on at `/metrics` in the Prometheus text format. Unset serves none.
+    pub metrics_listen: Option<SocketAddr>,
+    /// Team this session joins as a member instead of leading teams of its
+    /// own. Set by `codex team join`.
+    pub join: Option<TeamJoin>,
+    /// A2A endpoints `spawn_teammate` may add as teammates with `kind =
+    /// "a2a"`. Unset or empty allows none.
+    pub a2a_endpoints: Option<Vec<String>>,
+    /// SSH hosts `spawn_teammate` may run teammates on with `kind = "ssh"`,
+    /// as given to `ssh`. Unset or empty allows none.
+    pub ssh_hosts: Option<Vec<String>>,
+}
+
+/// Mirrors a team's tasks as issues of a GitHub repository.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct TeamGitHubIssues {
+    /// Repository the issues are opened in, as `owner/name`.
+    pub repo: String,
+    /// Team the entry applies to. Unset applies it to every team that has
+    /// no entry of its own.
+    pub team: Option<String>,
+    /// Labels added to every issue.
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// GitHub login to assign a teammate's issues to, by teammate name.
+    #[serde(default)]
+    pub assignees: BTreeMap<String, String>,
+    /// Hand comments on the issues to the task's assignee, or to the leader,
+    /// as team messages. Defaults to false.
+    #[serde(default)]
+    pub mirror_comments: bool,
+    /// Environment variable holding the GitHub token. Defaults to
+    /// `GITHUB_TOKEN`.
+    pub token_env_var: Option<String>,
+    /// Root of the GitHub REST API, for GitHub Enterprise. Defaults to
+    /// `https://api.github.com`.
+    pub api_url: Option<String>,
+}
+
+/// Mirrors a team's tasks as issues of a Jira project.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct TeamJiraSync {
+    /// Team the entry applies to. Unset applies it to every team that has
+    /// no entry of its own.
+    pub team: Option<String>,
+    /// Root URL of the Jira site, e.g. `https://acme.atlassian.net`.
+    pub base_url: String,
+    /// Key of the project the issues are created in.
+    pub project_key: String,
+    /// Issue type of the created issues. Defaults to `Task`.
+    pub issue_type: Option<String>,
+    /// Email of the Jira account the API token belongs to.
+    pub email: String,
+    /// Environment variable holding the Jira API token. Defaults to
+    /// `JIRA_API_TOKEN`.
+    pub token_env_var: Option<String>,
+    /// Labels added to every issue.
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Jira account id to assign a teammate's issues to, by teammate name.
+    #[serde(default)]
+    pub assignees: BTreeMap<String, String>,
+    /// Name of the workflow transition that completes an issue. Defaults to
+    /// `Done`.
+    pub done_transition: Option<String>,
+}
+
+/// Mirrors a team's tasks as issues of a Linear team.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct TeamLinearSync {
+    /// Team the entry applies to. Unset applies it to every team that has
+    /// no entry of its own.
+    pub team: Option<String>,
+    /// Id of the Linear team the issues are created in.
+    pub linear_team_id: String,
+    /// Environment variable holding the Linear API key. Defaults to
+    /// `LINEAR_API_KEY`.
+    pub token_env_var: Option<String>,
+    /// Ids of the labels added to every issue.
+    #[serde(default)]
+    pub label_ids: Vec<String>,
+    /// Linear user id to assign a teammate's issues to, by teammate name.
+    #[serde(default)]
+    pub assignees: BTreeMap<String, String>,
+    /// Id of the workflow state completed issues are moved to.
+    pub done_state_id: String,
+    /// Linear GraphQL endpoint. Defaults to `https://api.linear.app/graphql`.
+    pub api_url: Option<String>,
+}
+
+/// An existing team, led by another Codex process, a session joins.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct TeamJoin {
+    /// Name of the team.
+    pub team: String,
+    /// Member name the session joins under.
+    pub name: String,
+    /// Role shown to the rest of the team.
+    pub role: Option<String>,
+}
+
+/// An endpoint notified of team events.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct TeamWebhook {
+    /// URL the events are posted to.
+    pub url: String,
+    /// Events to send. Unset or empty sends every event.
+    #[serde(default)]
+    pub events: Vec<TeamWebhookEvent>,
+}
+
+/// A chat channel notified of team milestones.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct TeamNotifierConfig {
+    /// Chat service the channel belongs to.
+    pub service: TeamNotifierService,
+    /// Incoming webhook URL of the channel.
+    pub webhook_url: String,
+    /// Milestones to post. Unset or empty posts every milestone.
+    #[serde(default)]
+    pub milestones: Vec<TeamWebhookEvent>,
+}
+
+/// Email about long team runs, sent over SMTP.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct TeamEmailNotifier {
+    /// Host name of the SMTP server, e.g. `smtp.example.com`.
+    pub smtp_host: String,
+    /// Port of the SMTP server. Defaults to 587, which requires STARTTLS;
+    /// 465 uses TLS from the start.
+    pub smtp_port: Option<u16>,
+    /// User to log in to the server as. Unset sends without logging in.
+    pub username: Option<String>,
+    /// Environment variable holding the password of `username`. Defaults to
+    /// `SMTP_PASSWORD`.
+    pub password_env_var: Option<String>,
+    /// Sender address.
+    pub from: String,
+    /// Recipient addresses.
+    pub to: Vec<String>,
+    /// Email once this many teammates of a team have errored. Defaults to 3.
+    #[schemars(range(min = 1))]
+    pub error_threshold: Option<u32>,
+}
+
+/// Chat services team milestones can be posted to.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TeamNotifierService {
+    Slack,
+    Discord,
+}
+
+/// Team events a webhook can subscribe to.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TeamWebhookEvent {
+    /// A team was created.
+    TeamCreated,
+    /// A team was cleaned up.
+    TeamCleaned,
+    /// A task was completed.
+    TaskCompleted,
+    /// A task's assignee errored while working on it.
+    TaskFailed,
+    /// A teammate errored.
+    MemberErrored,
+    /// A teammate went over its token budget or ran out of time.
+    BudgetExhausted,
+}
+
+/// Teammate output forwarded to the leader.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TeammateOutput {
+    /// Nothing; teammates are only heard from when they finish a turn.
+    #[default]
+    Off,
+    /// Complete agent messages and the commands teammates run.
+    Messages,
+    /// Also agent messages and command output as they stream.
+    Stream,
+}
+
+/// Effective Agent Teams settings after defaults are applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TeamsConfig {
+    pub enabled: bool,
+    pub max_members: usize,
+    pub max_depth: usize,
+    pub max_members_by_depth: Vec<usize>,
+    pub max_team_tokens: Option<i64>,
+    pub max_concurrent_agents: Option<usize>,
+    pub max_auto_restarts: Option<u32>,
+    pub co_author_trailers: bool,
+    pub teammate_output: TeammateOutput,
+    pub heartbeat_timeout_secs: u64,
+    pub cents_per_million_input_tokens: Option<u64>,
+    pub cents_per_million_output_tokens: Option<u64>,
+    pub webhooks: Vec<TeamWebhook>,
+    pub notifiers: Vec<TeamNotifierConfig>,
+    pub email: Option<TeamEmailNotifier>,
+    pub github_issues: Vec<TeamGitHubIssues>,
+    pub jira: Vec<TeamJiraSync>,
+    pub linear: Vec<TeamLinearSync>,
+    pub metrics_listen: Option<SocketAddr>,
+    pub join: Option<TeamJoin>,
+    pub a2a_endpoints: Vec<String>,
+    pub ssh_hosts: Vec<String>,
+    /// Tools a teammate is limited to, set from its role definition rather
+    /// than read from `config.toml`.
+    pub allowed_tools: Option<Vec<String>>,
+}
+
+impl TeamsConfig {
+    /// Member cap for a team at nesting `level`, where top-level teams are
+    /// level 1.
+    pub fn max_members_at(&self, level: usize) -> usize {
+        level
+            .checked_sub(1)
+            .and_then(|index| self.max_members_by_depth.get(index))
+            .map_or(self.max_members, |&max| max.clamp(1, self.max_members))
+    }
+
+    /// The `github_issues` entry for `team_name`: its own, or else the one
+    /// without a team.
+    pub fn github_issues_for(&self, team_name: &str) -> Option<&TeamGitHubIssues> {
+        entry_for(&self.github_issues, team_name, |sync| sync.team.as_deref())
+    }
+
+    /// The `jira` entry for `team_name`, chosen like [`Self::github_issues_for`].
+    pub fn jira_for(&self, team_name: &str) -> Option<&TeamJiraSync> {
+        entry_for(&self.jira, team_name, |sync| sync.team.as_deref())
+    }
+
+    /// The `linear` entry for `team_name`, chosen like
+    /// [`Self::github_issues_for`].
+    pub fn linear_for(&self, team_name: &str) -> Option<&TeamLinearSync> {
+        entry_for(&self.linear, team_name, |sync| sync.team.as_deref())
+    }
+}
+
+/// The entry of `entries` whose team is `team_name`, or else the first one
+/// without a team.
+fn entry_for<'a, T>(
+    entries: &'a [T],
+    team_name: &str,
+    team: impl Fn(&T) -> Option<&str>,
+) -> Option<&'a T> {
+    entries
+        .iter()
+        .find(|entry| team(entry) == Some(team_name))
+        .or_else(|| entries.iter().find(|entry| team(entry).is_none()))
+}
+
+impl Default for TeamsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_members: DEFAULT_TEAMS_MAX_MEMBERS,
+            max_depth: 1,
+            max_members_by_depth: Vec::new(),
+            max_team_tokens: None,
+            max_concurrent_agents: None,
+            max_auto_restarts: None,
+            co_author_trailers: false,
+            teammate_output: TeammateOutput::Off,
+            heartbeat_timeout_secs: 300,
+            cents_per_million_input_tokens: None,
+            cents_per_million_output_tokens: None,
+            webhooks: Vec::new(),
+            notifiers: Vec::new(),
+            email: None,
+            github_issues: Vec::new(),
+            jira: Vec::new(),
+            linear: Vec::new(),
+            metrics_listen: None,
+            join: None,
+            a2a_endpoints: Vec::new(),
+            ssh_hosts: Vec::new(),
+            allowed_tools: None,
+        }
+    }
+}
+
+impl From<TeamsToml> for TeamsConfig {
+    fn from(toml: TeamsToml) -> Self {
+        let defaults = Self::default();
+        Self {
+            enabled: toml.enabled.unwrap_or(defaults.enabled),
+            max_members: toml.max_members.unwrap_or(defaults.max_members).max(1),
+            max_depth: toml.max_depth.unwrap_or(defaults.max_depth).max(1),
+            max_members_by_depth: toml.max_members_by_depth.unwrap_or_default(),
+            max_team_tokens: toml.max_team_tokens.map(|max| max.max(1)),
+            max_concurrent_agents: toml.max_concurrent_agents.map(|max| max.max(1)),
+            max_auto_restarts: toml.max_auto_restarts,
+            co_author_trailers: toml
+                .co_author_trailers
+                .unwrap_or(defaults.co_author_trailers),
+            teammate_output: toml.teammate_output.unwrap_or(defaults.teammate_output),
+            heartbeat_timeout_secs: toml
+                .heartbeat_timeout_secs
+                .unwrap_or(defaults.heartbeat_timeout_secs),
+            cents_per_million_input_tokens: toml.cents_per_million_input_tokens,
+            cents_per_million_output_tokens: toml.cents_per_million_output_tokens,
+            webhooks: toml.webhooks.unwrap_or_default(),
+            notifiers: toml.notifiers.unwrap_or_default(),
+            email: toml.email,
+            github_issues: toml.github_issues.unwrap_or_default(),
+            jira: toml.jira.unwrap_or_default(),
+            linear: toml.linear.unwrap_or_default(),
+            metrics_listen: toml.metrics_listen,
+            join: toml.join,
+            a2a_endpoints: toml.a2a_endpoints.unwrap_or_default(),
+            ssh_hosts: toml.ssh_hosts.unwrap_or_default(),
+            allowed_tools: None,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum AppDisabledReason {
@@ -623,12 +1022,22 @@ pub struct Tui {
     /// `current-dir`.
     #[serde(default)]
     pub status_line: Option<Vec<String>>,
+
+    /// Letter that, pressed with Ctrl, toggles the team task overlay while an
+    /// agent team is active. Must be a lowercase ASCII letter that the TUI
+    /// does not already bind with Ctrl. Defaults to `k`.
+    #[serde(default = "default_team_overlay_key")]
+    pub team_overlay_key: char,
 }
 
 const fn default_true() -> bool {
     true
 }
 
+const fn default_team_overlay_key() -> char {
+    DEFAULT_TUI_TEAM_OVERLAY_KEY
+}
+
 /// Settings for notices we display to users via the tui and app-server clients
 /// (primarily the Codex IDE extension). NOTE: these are different from
 /// notifications - notices are warnings, NUX screens, acknowledgements, etc.
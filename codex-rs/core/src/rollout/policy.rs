@@ -100,6 +100,24 @@ fn event_msg_persistence_mode(ev: &EventMsg) -> Option<EventPersistenceMode> {
         | EventMsg::TurnAborted(_)
         | EventMsg::TurnStarted(_)
         | EventMsg::TurnComplete(_) => Some(EventPersistenceMode::Limited),
+        // Team events are replayed on resume to rebuild the UI's view of the
+        // team; only live, high-volume ones are left out.
+        EventMsg::TeamCreated(_)
+        | EventMsg::TeamMemberAdded(_)
+        | EventMsg::TeamMemberRemoved(_)
+        | EventMsg::TeamTaskCreated(_)
+        | EventMsg::TeamTaskUpdated(_)
+        | EventMsg::TeamTaskUnblocked(_)
+        | EventMsg::TeamEscalationRequested(_)
+        | EventMsg::TeamMessageSent(_)
+        | EventMsg::TeamCleanup(_)
+        | EventMsg::TeamRenamed(_)
+        | EventMsg::TeamLeaderChanged(_)
+        | EventMsg::TeamMemberStatusChanged(_)
+        | EventMsg::TeamBudgetExhausted(_)
+        | EventMsg::TeamMemberRestarted(_)
+        | EventMsg::TeamEditConflict(_)
+        | EventMsg::TeamDiffSummary(_) => Some(EventPersistenceMode::Limited),
         EventMsg::ItemCompleted(event) => {
             // Plan items are derived from streaming tags and are not part of the
             // raw ResponseItem history, so we persist their completion to replay
@@ -121,9 +139,8 @@ fn event_msg_persistence_mode(ev: &EventMsg) -> Option<EventPersistenceMode> {
         | EventMsg::CollabWaitingEnd(_)
         | EventMsg::CollabCloseEnd(_)
         | EventMsg::CollabResumeEnd(_)
-        | EventMsg::TeamTaskUpdated(_)
-        | EventMsg::TeamMessageSent(_)
-        | EventMsg::TeamCleanup(_) => Some(EventPersistenceMode::Extended),
+        | EventMsg::TeamMemberLivenessLost(_)
+        | EventMsg::TeamError(_) => Some(EventPersistenceMode::Extended),
         EventMsg::Warning(_)
         | EventMsg::ModelReroute(_)
         | EventMsg::AgentMessageDelta(_)
@@ -170,9 +187,8 @@ fn event_msg_persistence_mode(ev: &EventMsg) -> Option<EventPersistenceMode> {
         | EventMsg::CollabWaitingBegin(_)
         | EventMsg::CollabCloseBegin(_)
         | EventMsg::CollabResumeBegin(_)
-        | EventMsg::TeamCreated(_)
-        | EventMsg::TeamMemberAdded(_)
-        | EventMsg::TeamMemberRemoved(_)
-        | EventMsg::TeamTaskCreated(_) => None,
+        | EventMsg::TeamMemberOutput(_)
+        | EventMsg::TeamMemberTokenUsage(_)
+        | EventMsg::TeamProgress(_) => None,
     }
 }
@@ -9,6 +9,7 @@ use crate::state::TaskKind;
 use async_trait::async_trait;
 use codex_otel::OtelManager;
 use codex_protocol::openai_models::ModelInfo;
+use codex_protocol::protocol::SessionSource;
 use codex_protocol::user_input::UserInput;
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
@@ -93,9 +94,13 @@ impl SessionTask for RegularTask {
         let sess = session.clone_session();
         let run_turn_span = trace_span!("run_turn");
         sess.set_server_reasoning_included(false).await;
-        sess.services
-            .otel_manager
-            .apply_traceparent_parent(&run_turn_span);
+        // Sub-agents are spawned inside a span of their parent, so their turns
+        // already continue its trace.
+        if !matches!(ctx.session_source, SessionSource::SubAgent(_)) {
+            sess.services
+                .otel_manager
+                .apply_traceparent_parent(&run_turn_span);
+        }
         let prewarmed_client_session = self.take_prewarmed_session().await;
         run_turn(
             sess,
@@ -0,0 +1,245 @@
+//! Teammates that are remote agents speaking the A2A (agent-to-agent)
+//! protocol.
+//!
+//! `spawn_teammate` with `kind: "a2a"` registers an external member reached
+//! at one of the A2A JSON-RPC endpoints of `teams.a2a_endpoints`, handled
+//! like the MCP-backed members of [`crate::teams::external`]. Each delivery
+//! is a blocking `message/send` with the message as a text part and the
+//! [`ExternalMemberRequest`] as a data part. The agent answers with a message
+//! or a task: their text parts and artifacts make up the reply, a data part
+//! with `task_updates` updates the member's tasks, and a failed, rejected or
+//! canceled task fails the delivery. The context the agent answers in is
+//! kept, so later deliveries continue the same conversation.
+
+use crate::default_client::build_reqwest_client;
+use crate::teams::external::ExternalMemberReply;
+use crate::teams::external::ExternalMemberRequest;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use serde_json::json;
+use std::time::Duration;
+
+/// `kind` of a teammate that is a remote A2A agent.
+pub const A2A_KIND: &str = "a2a";
+
+/// How long an agent may take to answer a delivery; sends are blocking, so
+/// this covers the agent's work on it.
+const A2A_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Task states in which the agent gave up on the delivery.
+const FAILED_STATES: [&str; 4] = ["failed", "rejected", "canceled", "auth-required"];
+/// Task states in which the agent is still working on the delivery.
+const WORKING_STATES: [&str; 2] = ["submitted", "working"];
+
+/// The A2A agent an external teammate is.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct A2aMemberSpec {
+    /// URL of the agent's JSON-RPC endpoint, the `url` of its agent card.
+    pub endpoint: String,
+    /// Context of the conversation with the agent so far.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_id: Option<String>,
+}
+
+/// What an A2A agent answered a delivery with.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct A2aAnswer {
+    pub reply: ExternalMemberReply,
+    pub context_id: Option<String>,
+    /// Whether the agent answered with a task it is still working on.
+    pub working: bool,
+}
+
+/// Send `message` to the agent of `spec` and wait for its answer.
+pub(crate) async fn send_to_a2a_agent(
+    spec: &A2aMemberSpec,
+    message: &str,
+    request: &ExternalMemberRequest<'_>,
+) -> Result<A2aAnswer, String> {
+    let mut a2a_message = json!({
+        "kind": "message",
+        "role": "user",
+        "messageId": uuid::Uuid::new_v4().to_string(),
+        "parts": [
+            { "kind": "text", "text": message },
+            { "kind": "data", "data": request },
+        ],
+    });
+    if let Some(context_id) = &spec.context_id {
+        a2a_message["contextId"] = json!(context_id);
+    }
+    let body = json!({
+        "jsonrpc": "2.0",
+        "id": uuid::Uuid::new_v4().to_string(),
+        "method": "message/send",
+        "params": {
+            "message": a2a_message,
+            "configuration": { "blocking": true },
+        },
+    });
+    let response = build_reqwest_client()
+        .post(&spec.endpoint)
+        .timeout(A2A_TIMEOUT)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    if !status.is_success() {
+        return Err(format!("the agent answered {status}: {body}"));
+    }
+    let body: Value =
+        serde_json::from_str(&body).map_err(|e| format!("unexpected A2A response: {e}"))?;
+    if let Some(error) = body.get("error") {
+        return Err(format!("the agent answered with an error: {error}"));
+    }
+    read_answer(&body["result"])
+}
+
+/// Read the message or task `result` of `message/send`.
+fn read_answer(result: &Value) -> Result<A2aAnswer, String> {
+    let mut parts: Vec<&Value> = Vec::new();
+    let mut state = None;
+    match result["kind"].as_str() {
+        Some("message") => parts.extend(result["parts"].as_array().into_iter().flatten()),
+        Some("task") => {
+            state = result["status"]["state"].as_str();
+            let status_parts = result["status"]["message"]["parts"].as_array();
+            parts.extend(status_parts.into_iter().flatten());
+            for artifact in result["artifacts"].as_array().into_iter().flatten() {
+                parts.extend(artifact["parts"].as_array().into_iter().flatten());
+            }
+        }
+        _ => return Err(format!("unexpected A2A result: {result}")),
+    }
+
+    let mut texts = Vec::new();
+    let mut task_updates = Vec::new();
+    for part in parts {
+        match part["kind"].as_str() {
+            Some("text") => texts.extend(part["text"].as_str().map(str::to_string)),
+            Some("data") => {
+                if let Ok(data) =
+                    serde_json::from_value::<ExternalMemberReply>(part["data"].clone())
+                {
+                    texts.extend(data.reply);
+                    task_updates.extend(data.task_updates);
+                }
+            }
+            _ => {}
+        }
+    }
+    let text = texts.join("\n\n");
+    if let Some(state) = state.filter(|state| FAILED_STATES.contains(state)) {
+        return Err(if text.is_empty() {
+            format!("the agent ended the task as {state}")
+        } else {
+            format!("the agent ended the task as {state}: {text}")
+        });
+    }
+    Ok(A2aAnswer {
+        reply: ExternalMemberReply {
+            reply: (!text.trim().is_empty()).then_some(text),
+            task_updates,
+        },
+        context_id: result["contextId"].as_str().map(str::to_string),
+        working: state.is_some_and(|state| WORKING_STATES.contains(&state)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::teams::external::ExternalTaskUpdate;
+    use codex_protocol::protocol::TeamTaskStatus;
+    use pretty_assertions::assert_eq;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+    use wiremock::matchers::body_partial_json;
+    use wiremock::matchers::method;
+
+    #[tokio::test]
+    async fn deliveries_are_sent_with_message_send() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "method": "message/send",
+                "params": {
+                    "message": {
+                        "contextId": "ctx-1",
+                        "parts": [
+                            { "kind": "text", "text": "Review the API." },
+                            { "kind": "data", "data": { "team_name": "zeta", "member": "reviewer" } },
+                        ],
+                    },
+                    "configuration": { "blocking": true },
+                },
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": "1",
+                "result": {
+                    "kind": "task",
+                    "id": "a2a-task",
+                    "contextId": "ctx-1",
+                    "status": { "state": "completed" },
+                    "artifacts": [{
+                        "parts": [
+                            { "kind": "text", "text": "The API looks fine." },
+                            { "kind": "data", "data": {
+                                "task_updates": [{ "task_id": "task-1", "status": "completed" }],
+                            } },
+                        ],
+                    }],
+                },
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let spec = A2aMemberSpec {
+            endpoint: server.uri(),
+            context_id: Some("ctx-1".to_string()),
+        };
+        let request = ExternalMemberRequest {
+            team_name: "zeta",
+            member: "reviewer",
+            from: "leader",
+            message: "Review the API.",
+            tasks: Vec::new(),
+        };
+
+        let answer = send_to_a2a_agent(&spec, "Review the API.", &request)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            answer,
+            A2aAnswer {
+                reply: ExternalMemberReply {
+                    reply: Some("The API looks fine.".to_string()),
+                    task_updates: vec![ExternalTaskUpdate {
+                        task_id: "task-1".to_string(),
+                        status: TeamTaskStatus::Completed,
+                        result: None,
+                    }],
+                },
+                context_id: Some("ctx-1".to_string()),
+                working: false,
+            }
+        );
+        let failed = json!({
+            "kind": "task",
+            "status": {
+                "state": "failed",
+                "message": { "parts": [{ "kind": "text", "text": "Out of credits." }] },
+            },
+        });
+        assert_eq!(
+            read_answer(&failed),
+            Err("the agent ended the task as failed: Out of credits.".to_string())
+        );
+    }
+}
@@ -0,0 +1,155 @@
+//! Shared team artifacts.
+//!
+//! Teammates hand large outputs (reports, patches, datasets) to each other
+//! by reference: `store_artifact` saves a file or inline content under
+//! `~/.codex/teams/{name}/artifacts/` and `fetch_artifact` reads it back by
+//! name, so messages only need to carry the artifact's name.
+
+use serde::Serialize;
+use std::path::Path;
+use std::path::PathBuf;
+use tokio::fs;
+
+/// Metadata of a stored artifact.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ArtifactInfo {
+    pub name: String,
+    /// Where the artifact is stored on disk.
+    pub path: PathBuf,
+    /// Size in bytes.
+    pub size: u64,
+}
+
+/// A team's `artifacts/` directory.
+pub struct ArtifactStore {
+    dir: PathBuf,
+}
+
+impl ArtifactStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Store `contents` as artifact `name`, replacing any artifact of that
+    /// name.
+    pub async fn store(&self, name: &str, contents: &[u8]) -> std::io::Result<ArtifactInfo> {
+        let path = self.artifact_path(name)?;
+        fs::create_dir_all(&self.dir).await?;
+        fs::write(&path, contents).await?;
+        self.info(name, path).await
+    }
+
+    /// Store a copy of the file at `source` as artifact `name`, replacing
+    /// any artifact of that name.
+    pub async fn store_file(&self, name: &str, source: &Path) -> std::io::Result<ArtifactInfo> {
+        let path = self.artifact_path(name)?;
+        fs::create_dir_all(&self.dir).await?;
+        fs::copy(source, &path).await?;
+        self.info(name, path).await
+    }
+
+    /// Metadata and contents of artifact `name`.
+    pub async fn fetch(&self, name: &str) -> std::io::Result<(ArtifactInfo, Vec<u8>)> {
+        let path = self.artifact_path(name)?;
+        let contents = fs::read(&path).await?;
+        let info = ArtifactInfo {
+            name: name.to_string(),
+            path,
+            size: contents.len() as u64,
+        };
+        Ok((info, contents))
+    }
+
+    /// Every stored artifact, sorted by name.
+    pub async fn list(&self) -> std::io::Result<Vec<ArtifactInfo>> {
+        let mut artifacts = Vec::new();
+        if !self.dir.exists() {
+            return Ok(artifacts);
+        }
+        let mut entries = fs::read_dir(&self.dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let metadata = entry.metadata().await?;
+            let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+            if metadata.is_file() {
+                artifacts.push(ArtifactInfo {
+                    name,
+                    path: entry.path(),
+                    size: metadata.len(),
+                });
+            }
+        }
+        artifacts.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(artifacts)
+    }
+
+    /// Path of artifact `name`. Names are single path components of letters,
+    /// digits, `-`, `_` and `.`, not starting with `.`.
+    fn artifact_path(&self, name: &str) -> std::io::Result<PathBuf> {
+        let valid = !name.is_empty()
+            && !name.starts_with('.')
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+        if !valid {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "invalid artifact name '{name}'; use letters, digits, '-', '_' and '.', e.g. \"report.md\""
+                ),
+            ));
+        }
+        Ok(self.dir.join(name))
+    }
+
+    async fn info(&self, name: &str, path: PathBuf) -> std::io::Result<ArtifactInfo> {
+        let size = fs::metadata(&path).await?.len();
+        Ok(ArtifactInfo {
+            name: name.to_string(),
+            path,
+            size,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn stores_and_fetches_by_name() {
+        let tmp = TempDir::new().unwrap();
+        let store = ArtifactStore::new(tmp.path().join("artifacts"));
+        assert_eq!(store.list().await.unwrap(), Vec::new());
+
+        store.store("report.md", b"# Findings\n").await.unwrap();
+        let source = tmp.path().join("out.patch");
+        std::fs::write(&source, "diff --git a/x b/x\n").unwrap();
+        store.store_file("fix.patch", &source).await.unwrap();
+
+        let (info, contents) = store.fetch("report.md").await.unwrap();
+        assert_eq!(contents, b"# Findings\n");
+        assert_eq!(info.size, 11);
+        assert_eq!(
+            store
+                .list()
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|a| a.name)
+                .collect::<Vec<_>>(),
+            vec!["fix.patch".to_string(), "report.md".to_string()]
+        );
+
+        assert!(store.fetch("missing.md").await.is_err());
+        for name in ["", "../config.json", ".hidden", "a/b"] {
+            assert_eq!(
+                store.store(name, b"x").await.unwrap_err().kind(),
+                std::io::ErrorKind::InvalidInput
+            );
+        }
+    }
+}
@@ -0,0 +1,361 @@
+//! Teammate completion hooks.
+//!
+//! Every spawned teammate gets a watcher on its status. When the teammate
+//! finishes a turn or errors out, the watcher drops a structured message into
+//! the leader's inbox and emits `TeamMemberStatusChanged` with the old and new
+//! status, so the leader learns about it without polling; a teammate shutting
+//! down emits the event too.
+//!
+//! A finished turn's final message becomes the result of the teammate's
+//! in-progress tasks, in case it never calls `complete_task`. The turn is also
+//! checked for files other teammates edited, its changes are summarized, the
+//! team's token usage is refreshed, and a teammate it took over budget is shut
+//! down. On work-stealing teams an idle teammate is handed the next available
+//! task. Errored teammates with a restart policy are respawned, and a finished
+//! or shut down teammate frees a slot for queued teammates.
+
+use crate::agent::AgentStatus;
+use crate::agent::status::is_final;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::config::Config;
+use crate::teams::conflicts::report_edit_conflicts;
+use crate::teams::delegation::hand_next_task;
+use crate::teams::diff_summary::report_diff_summary;
+use crate::teams::inbox::InboxMessage;
+use crate::teams::queue::start_queued_teammates;
+use crate::teams::restart::{restart_backoff, restart_errored, restart_limit};
+use crate::teams::team_manager::MemberConfig;
+use crate::teams::usage::{refresh_team_usage, usage_warning};
+use codex_protocol::protocol::{
+    EventMsg, TeamBudgetExhaustedEvent, TeamMemberEvent, TeamMemberInfo, TeamTaskEvent,
+    WarningEvent,
+};
+use serde_json::json;
+use std::sync::Arc;
+
+/// Inbox that completion notices are written to.
+pub(crate) const LEADER_INBOX: &str = "leader";
+
+/// Watch `member` until it shuts down or its session goes away, notifying
+/// the leader each time the teammate goes from working to finished.
+///
+/// `teammate_config` is used to respawn the teammate if it errors out; its
+/// role, sandbox and worktree are applied on top of it again.
+pub(crate) fn watch_teammate_completion(
+    session: &Arc<Session>,
+    turn: Arc<TurnContext>,
+    team_name: String,
+    member: MemberConfig,
+    teammate_config: Config,
+) {
+    let weak_session = Arc::downgrade(session);
+    let MemberConfig {
+        name: member_name,
+        thread_id,
+        role,
+        budget,
+        restarts,
+        ..
+    } = member.clone();
+    tokio::spawn(async move {
+        let mut turns_used: u32 = 0;
+        let mut status_rx = {
+            let Some(session) = weak_session.upgrade() else {
+                return;
+            };
+            match session
+                .services
+                .agent_control
+                .subscribe_status(thread_id)
+                .await
+            {
+                Ok(status_rx) => status_rx,
+                Err(e) => {
+                    tracing::debug!("not watching teammate {member_name}: {e}");
+                    return;
+                }
+            }
+        };
+        let status_changed = |status, previous_status| {
+            EventMsg::TeamMemberStatusChanged(TeamMemberEvent {
+                team_name: team_name.clone(),
+                member: TeamMemberInfo {
+                    name: member_name.clone(),
+                    thread_id,
+                    role: role.clone(),
+                    status,
+                },
+                previous_status: Some(previous_status),
+            })
+        };
+        let mut previous = status_rx.borrow_and_update().clone();
+        while status_rx.changed().await.is_ok() {
+            let status = status_rx.borrow_and_update().clone();
+            let previous_status = std::mem::replace(&mut previous, status.clone());
+            let was_working = !is_final(&previous_status);
+            if matches!(status, AgentStatus::Shutdown) {
+                if let Some(session) = weak_session.upgrade() {
+                    session
+                        .send_event(&turn, status_changed(status, previous_status))
+                        .await;
+                }
+                break;
+            }
+            if matches!(status, AgentStatus::NotFound) {
+                break;
+            }
+            if !was_working {
+                continue;
+            }
+            let Some(content) = completion_notice(&member_name, &status) else {
+                continue;
+            };
+            turns_used = turns_used.saturating_add(1);
+            let Some(session) = weak_session.upgrade() else {
+                return;
+            };
+            let message = InboxMessage {
+                from: member_name.clone(),
+                timestamp: chrono::Utc::now().to_rfc3339(),
+                content,
+                read: false,
+            };
+            if let Err(e) = session
+                .services
+                .teams
+                .inbox(&team_name)
+                .send_message(LEADER_INBOX, message)
+                .await
+            {
+                tracing::warn!("failed to notify leader that {member_name} finished: {e}");
+            }
+            if let AgentStatus::Completed(Some(last_message)) = &status {
+                match session
+                    .services
+                    .teams
+                    .tasks()
+                    .record_result(&team_name, &member_name, last_message)
+                    .await
+                {
+                    Ok(tasks) => {
+                        for task in tasks {
+                            session
+                                .send_event(
+                                    &turn,
+                                    EventMsg::TeamTaskUpdated(TeamTaskEvent {
+                                        team_name: team_name.clone(),
+                                        task,
+                                    }),
+                                )
+                                .await;
+                        }
+                    }
+                    Err(e) => {
+                        tracing::warn!("failed to record task results from {member_name}: {e}");
+                    }
+                }
+            }
+            session
+                .send_event(&turn, status_changed(status.clone(), previous_status))
+                .await;
+            report_edit_conflicts(&session, &turn, &team_name).await;
+            report_diff_summary(&session, &turn, &team_name, &member).await;
+
+            let teams = &session.services.teams;
+            let previous_tokens = teams
+                .manager()
+                .usage(&team_name)
+                .await
+                .map_or(0, |usage| usage.total().total_tokens);
+            match refresh_team_usage(&session, &team_name).await {
+                Ok(usage) => {
+                    if let Some(cap) = turn.config.teams.max_team_tokens
+                        && let Some(warning) =
+                            usage_warning(previous_tokens, usage.total().total_tokens, cap)
+                    {
+                        let notice = json!({
+                            "type": "team_budget_warning",
+                            "message": warning,
+                        });
+                        let message = InboxMessage {
+                            from: "system".to_string(),
+                            timestamp: chrono::Utc::now().to_rfc3339(),
+                            content: notice.to_string(),
+                            read: false,
+                        };
+                        if let Err(e) = teams
+                            .inbox(&team_name)
+                            .send_message(LEADER_INBOX, message)
+                            .await
+                        {
+                            tracing::warn!("failed to warn leader about team usage: {e}");
+                        }
+                        session
+                            .send_event(&turn, EventMsg::Warning(WarningEvent { message: warning }))
+                            .await;
+                    }
+                }
+                Err(e) => tracing::warn!("failed to record usage for team {team_name}: {e}"),
+            }
+
+            if let AgentStatus::Errored(error) = &status
+                && let Some(max_restarts) = restart_limit(&member, &turn.config.teams)
+                && restarts < max_restarts
+            {
+                drop(session);
+                tokio::time::sleep(restart_backoff(restarts + 1)).await;
+                let Some(session) = weak_session.upgrade() else {
+                    return;
+                };
+                // The respawned teammate gets a watcher of its own.
+                if restart_errored(
+                    &session,
+                    &turn,
+                    &team_name,
+                    &member,
+                    error,
+                    max_restarts,
+                    &teammate_config,
+                )
+                .await
+                {
+                    return;
+                }
+                continue;
+            }
+
+            let agent_control = &session.services.agent_control;
+            let exhausted = match budget {
+                Some(budget) => {
+                    let tokens_used = agent_control
+                        .get_total_token_usage(thread_id)
+                        .await
+                        .map_or(0, |usage| usage.total_tokens);
+                    budget.exceeded(tokens_used, turns_used)
+                }
+                None => None,
+            };
+            let Some(reason) = exhausted else {
+                // Work stealing: an idle teammate picks up the next task
+                // itself. Delegation mode's supervisor does this on its own.
+                if matches!(status, AgentStatus::Completed(_))
+                    && teams
+                        .load_config(&team_name)
+                        .await
+                        .is_ok_and(|config| config.work_stealing && !config.delegation_mode)
+                    && let Err(e) = hand_next_task(&session, &turn, &team_name, &member).await
+                {
+                    tracing::warn!("failed to hand the next task to {member_name}: {e}");
+                }
+                if let Err(e) = start_queued_teammates(&session, &turn, &team_name).await {
+                    tracing::warn!("failed to start queued teammates of {team_name}: {e}");
+                }
+                continue;
+            };
+            tracing::info!("shutting down teammate {member_name}: {reason}");
+            let notice = json!({
+                "type": "teammate_budget_exhausted",
+                "teammate": member_name,
+                "reason": reason,
+            });
+            let message = InboxMessage {
+                from: member_name.clone(),
+                timestamp: chrono::Utc::now().to_rfc3339(),
+                content: notice.to_string(),
+                read: false,
+            };
+            if let Err(e) = session
+                .services
+                .teams
+                .inbox(&team_name)
+                .send_message(LEADER_INBOX, message)
+                .await
+            {
+                tracing::warn!("failed to tell leader that {member_name} ran out of budget: {e}");
+            }
+            session
+                .send_event(
+                    &turn,
+                    EventMsg::TeamBudgetExhausted(TeamBudgetExhaustedEvent {
+                        team_name: team_name.clone(),
+                        member_name: member_name.clone(),
+                        thread_id,
+                        reason,
+                    }),
+                )
+                .await;
+            let _ = agent_control.shutdown_agent(thread_id).await;
+            if let Err(e) = teams
+                .manager()
+                .release_files(&team_name, &member_name, None)
+                .await
+            {
+                tracing::warn!("failed to release file reservations of {member_name}: {e}");
+            }
+            break;
+        }
+        if let Some(session) = weak_session.upgrade()
+            && let Err(e) = start_queued_teammates(&session, &turn, &team_name).await
+        {
+            tracing::warn!("failed to start queued teammates of {team_name}: {e}");
+        }
+    });
+}
+
+/// Inbox message body announcing that `member_name` reached `status`, or
+/// `None` if the status is not a finished one.
+fn completion_notice(member_name: &str, status: &AgentStatus) -> Option<String> {
+    let notice = match status {
+        AgentStatus::Completed(last_message) => json!({
+            "type": "teammate_finished",
+            "teammate": member_name,
+            "status": "completed",
+            "last_message": last_message,
+        }),
+        AgentStatus::Errored(error) => json!({
+            "type": "teammate_finished",
+            "teammate": member_name,
+            "status": "errored",
+            "error": error,
+        }),
+        _ => return None,
+    };
+    Some(notice.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::Value;
+
+    #[test]
+    fn completion_notice_describes_finished_statuses() {
+        let notice = completion_notice("alice", &AgentStatus::Completed(Some("done".into())))
+            .expect("completed is a finished status");
+        assert_eq!(
+            serde_json::from_str::<Value>(&notice).unwrap(),
+            json!({
+                "type": "teammate_finished",
+                "teammate": "alice",
+                "status": "completed",
+                "last_message": "done",
+            })
+        );
+
+        let notice = completion_notice("bob", &AgentStatus::Errored("boom".into()))
+            .expect("errored is a finished status");
+        assert_eq!(
+            serde_json::from_str::<Value>(&notice).unwrap(),
+            json!({
+                "type": "teammate_finished",
+                "teammate": "bob",
+                "status": "errored",
+                "error": "boom",
+            })
+        );
+
+        assert_eq!(completion_notice("carol", &AgentStatus::Running), None);
+    }
+}
@@ -0,0 +1,190 @@
+//! Cross-teammate edit-conflict detection.
+//!
+//! Each teammate session records the files its patches touch, from its turn
+//! diff tracker, in the team's `edits.json`. Paths are stored relative to
+//! their repository root, so teammates working in separate worktrees of the
+//! same repository are compared file for file. Whenever a teammate finishes a
+//! turn, its completion watcher checks for files edited by more than one
+//! teammate and warns the leader, through its inbox and a `TeamEditConflict`
+//! event, before the overlap turns into a merge conflict.
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::teams::completion::LEADER_INBOX;
+use crate::teams::inbox::InboxMessage;
+use crate::teams::repo_relative;
+use codex_protocol::protocol::{
+    EventMsg, SessionSource, SubAgentSource, TeamEditConflictEvent, TeamFileConflict,
+};
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+/// Contents of a team's `edits.json`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TeamEdits {
+    /// Files each teammate has edited, keyed by teammate name.
+    #[serde(default)]
+    pub by_member: BTreeMap<String, BTreeSet<String>>,
+    /// Teammates the leader was last warned about, per file.
+    #[serde(default)]
+    pub reported: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl TeamEdits {
+    /// Files edited by more than one teammate whose editors changed since the
+    /// leader was last warned about them.
+    pub fn unreported_conflicts(&self) -> Vec<TeamFileConflict> {
+        let mut editors: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+        for (member, paths) in &self.by_member {
+            for path in paths {
+                editors
+                    .entry(path.as_str())
+                    .or_default()
+                    .insert(member.as_str());
+            }
+        }
+        editors
+            .into_iter()
+            .filter(|(path, members)| {
+                members.len() > 1
+                    && self
+                        .reported
+                        .get(*path)
+                        .is_none_or(|reported| !members.iter().all(|m| reported.contains(*m)))
+            })
+            .map(|(path, members)| TeamFileConflict {
+                path: path.to_string(),
+                members: members.into_iter().map(str::to_string).collect(),
+            })
+            .collect()
+    }
+}
+
+/// Record `paths`, touched during a turn of `session`, as edits of the
+/// teammate `session` backs, if it is one.
+pub(crate) async fn record_teammate_edits(
+    session: &Session,
+    turn: &TurnContext,
+    paths: &[PathBuf],
+) {
+    if paths.is_empty()
+        || !turn.config.teams.enabled
+        || !matches!(
+            turn.session_source,
+            SessionSource::SubAgent(SubAgentSource::ThreadSpawn { .. })
+        )
+    {
+        return;
+    }
+    let mgr = session.services.teams.manager();
+    let teams = match mgr.list_teams().await {
+        Ok(teams) => teams,
+        Err(e) => {
+            tracing::debug!("not recording teammate edits: {e}");
+            return;
+        }
+    };
+    let paths: Vec<String> = paths.iter().map(|path| repo_relative(path)).collect();
+    for team in teams {
+        let Some(member) = team
+            .members
+            .iter()
+            .find(|m| m.thread_id == session.conversation_id)
+        else {
+            continue;
+        };
+        if let Err(e) = mgr.record_edits(&team.name, &member.name, &paths).await {
+            tracing::warn!("failed to record edits of {}: {e}", member.name);
+        }
+    }
+}
+
+/// Warn the leader of `team_name` about files newly edited by more than one
+/// teammate.
+pub(crate) async fn report_edit_conflicts(session: &Session, turn: &TurnContext, team_name: &str) {
+    let teams = &session.services.teams;
+    let conflicts = match teams.manager().take_unreported_conflicts(team_name).await {
+        Ok(conflicts) => conflicts,
+        Err(e) => {
+            tracing::warn!("failed to check edit conflicts of {team_name}: {e}");
+            return;
+        }
+    };
+    if conflicts.is_empty() {
+        return;
+    }
+    let notice = json!({
+        "type": "edit_conflict",
+        "message": "Several teammates edited the same files; coordinate them before merging.",
+        "conflicts": conflicts,
+    });
+    let message = InboxMessage {
+        from: "system".to_string(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        content: notice.to_string(),
+        read: false,
+    };
+    if let Err(e) = teams
+        .inbox(team_name)
+        .send_message(LEADER_INBOX, message)
+        .await
+    {
+        tracing::warn!("failed to warn leader about edit conflicts: {e}");
+    }
+    session
+        .send_event(
+            turn,
+            EventMsg::TeamEditConflict(TeamEditConflictEvent {
+                team_name: team_name.to_string(),
+                conflicts,
+            }),
+        )
+        .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn paths(paths: &[&str]) -> BTreeSet<String> {
+        paths.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn conflicts_are_reported_once_per_set_of_editors() {
+        let mut edits = TeamEdits {
+            by_member: BTreeMap::from([
+                ("alice".to_string(), paths(&["src/lib.rs", "README.md"])),
+                ("bob".to_string(), paths(&["src/lib.rs", "src/main.rs"])),
+            ]),
+            reported: BTreeMap::new(),
+        };
+        let lib_rs = |members: &[&str]| TeamFileConflict {
+            path: "src/lib.rs".to_string(),
+            members: members.iter().map(|m| m.to_string()).collect(),
+        };
+        assert_eq!(
+            edits.unreported_conflicts(),
+            vec![lib_rs(&["alice", "bob"])]
+        );
+
+        edits
+            .reported
+            .insert("src/lib.rs".to_string(), paths(&["alice", "bob"]));
+        assert_eq!(edits.unreported_conflicts(), Vec::new());
+
+        // A third editor is worth another warning.
+        edits
+            .by_member
+            .insert("carol".to_string(), paths(&["src/lib.rs"]));
+        assert_eq!(
+            edits.unreported_conflicts(),
+            vec![lib_rs(&["alice", "bob", "carol"])]
+        );
+    }
+}
@@ -0,0 +1,450 @@
+//! Delegation-mode supervisor.
+//!
+//! A team created with `delegation_mode` hands orchestration to the runtime:
+//! a background loop gives unblocked tasks to idle teammates, waits for
+//! errored teammates to be restarted as their restart policy allows (see
+//! [`crate::teams::restart`]), and sends the leader one report once the task
+//! list is finished or no further progress is possible.
+
+use crate::agent::AgentStatus;
+use crate::agent::next_thread_spawn_depth;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::config::Config;
+use crate::config::Constrained;
+use crate::teams::completion::watch_teammate_completion;
+use crate::teams::liveness::watch_teammate_liveness;
+use crate::teams::output::forward_teammate_events;
+use crate::teams::restart::restart_limit;
+use crate::teams::roles::apply_role;
+use crate::teams::spans::teammate_span;
+use crate::teams::summary::member_status_label;
+use crate::teams::team_manager::MemberConfig;
+use crate::teams::timeout::start_teammate_timeout;
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::{
+    EventMsg, SessionSource, SubAgentSource, TeamMemberEvent, TeamMemberInfo, TeamTaskEvent,
+    TeamTaskInfo, TeamTaskStatus,
+};
+use codex_protocol::user_input::UserInput;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::Instrument;
+
+/// How often the supervisor re-checks teammates and the task list.
+const DELEGATION_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+enum Step {
+    Continue,
+    Stop,
+    Report(String),
+}
+
+/// Start the supervisor for `team_name`. It runs until the team is cleaned
+/// up, delegation mode is turned off, leadership moves to another thread,
+/// the leader session is dropped, or the final report has been delivered.
+pub(crate) fn start_delegation_supervisor(
+    session: &Arc<Session>,
+    turn: Arc<TurnContext>,
+    team_name: String,
+) {
+    let weak_session = Arc::downgrade(session);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(DELEGATION_POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            let Some(session) = weak_session.upgrade() else {
+                return;
+            };
+            match supervise(&session, &turn, &team_name).await {
+                Ok(Step::Continue) => {}
+                Ok(Step::Stop) => return,
+                Ok(Step::Report(report)) => {
+                    let items = vec![UserInput::Text {
+                        text: report,
+                        text_elements: Vec::new(),
+                    }];
+                    if let Err(e) = session
+                        .services
+                        .agent_control
+                        .send_input(session.conversation_id, items)
+                        .await
+                    {
+                        tracing::warn!("failed to deliver delegation report for {team_name}: {e}");
+                    }
+                    return;
+                }
+                Err(e) => {
+                    tracing::debug!("stopping delegation supervisor for {team_name}: {e}");
+                    return;
+                }
+            }
+        }
+    });
+}
+
+/// One supervisor pass over the team.
+async fn supervise(
+    session: &Arc<Session>,
+    turn: &Arc<TurnContext>,
+    team_name: &str,
+) -> std::io::Result<Step> {
+    let teams = &session.services.teams;
+    let config = teams.load_config(team_name).await?;
+    if !config.delegation_mode || config.leader_thread_id != session.conversation_id {
+        return Ok(Step::Stop);
+    }
+    let agent_control = &session.services.agent_control;
+
+    let mut busy = false;
+    for member in &config.members {
+        match agent_control.get_status(member.thread_id).await {
+            AgentStatus::PendingInit | AgentStatus::Running => busy = true,
+            AgentStatus::Errored(_) => {
+                // The teammate's completion watcher restarts it, if it has
+                // restarts left.
+                if let Some(max_restarts) = restart_limit(member, &turn.config.teams) {
+                    busy |= member.restarts < max_restarts;
+                }
+            }
+            AgentStatus::Completed(_) => {
+                busy |= hand_next_task(session, turn, team_name, member).await?;
+            }
+            AgentStatus::Shutdown | AgentStatus::NotFound => {}
+        }
+    }
+
+    let tasks = teams.get_all_tasks(team_name).await?;
+    if busy || tasks.is_empty() || config.members.is_empty() {
+        return Ok(Step::Continue);
+    }
+    let finished = tasks
+        .iter()
+        .all(|t| matches!(t.status, TeamTaskStatus::Completed));
+    let mut statuses = Vec::with_capacity(config.members.len());
+    for member in &config.members {
+        let status = agent_control.get_status(member.thread_id).await;
+        statuses.push((member.name.as_str(), status));
+    }
+    Ok(Step::Report(final_report(
+        team_name, finished, &tasks, &statuses,
+    )))
+}
+
+/// Give an idle teammate the next available task as its next input, unless
+/// it still holds a task it has not completed. Returns whether a task was
+/// handed out.
+pub(crate) async fn hand_next_task(
+    session: &Session,
+    turn: &TurnContext,
+    team_name: &str,
+    member: &MemberConfig,
+) -> std::io::Result<bool> {
+    let teams = &session.services.teams;
+    let tasks = teams.get_all_tasks(team_name).await?;
+    if tasks.iter().any(|t| {
+        matches!(t.status, TeamTaskStatus::InProgress)
+            && t.assigned_to.as_deref() == Some(member.name.as_str())
+    }) {
+        return Ok(false);
+    }
+    let Some(task) = teams
+        .tasks()
+        .accept_next_task(team_name, &member.name)
+        .await?
+    else {
+        return Ok(false);
+    };
+    let items = vec![UserInput::Text {
+        text: format!(
+            "New task {}: {}\n\nCall complete_task with task_id \"{}\" when you are done.",
+            task.id, task.title, task.id
+        ),
+        text_elements: Vec::new(),
+    }];
+    if let Err(e) = session
+        .services
+        .agent_control
+        .send_input(member.thread_id, items)
+        .await
+    {
+        tracing::warn!("failed to hand task {} to {}: {e}", task.id, member.name);
+        // The teammate holds no other task, so this only puts `task` back.
+        teams.tasks().release_tasks(team_name, &member.name).await?;
+        return Ok(false);
+    }
+    session
+        .send_event(
+            turn,
+            EventMsg::TeamTaskUpdated(TeamTaskEvent {
+                team_name: team_name.to_string(),
+                task,
+            }),
+        )
+        .await;
+    Ok(true)
+}
+
+/// Shut down a teammate and spawn a fresh thread with its original prompt,
+/// pointing the member (and its tasks, which are keyed by name) at the new
+/// thread. Returns the new thread id.
+pub(crate) async fn restart_member(
+    session: &Arc<Session>,
+    turn: &Arc<TurnContext>,
+    team_name: &str,
+    member: &MemberConfig,
+    tasks: &[TeamTaskInfo],
+    teammate_config: &Config,
+) -> Result<ThreadId, String> {
+    let agent_control = &session.services.agent_control;
+    let _ = agent_control.shutdown_agent(member.thread_id).await;
+
+    let prompt = restart_prompt(member, tasks);
+    let session_source = SessionSource::SubAgent(SubAgentSource::ThreadSpawn {
+        parent_thread_id: session.conversation_id,
+        depth: next_thread_spawn_depth(&turn.session_source),
+    });
+    let mut config = teammate_config.clone();
+    configure_member(&mut config, member, &turn.cwd).await?;
+    let thread_id = agent_control
+        .spawn_agent(
+            config,
+            vec![UserInput::Text {
+                text: prompt,
+                text_elements: Vec::new(),
+            }],
+            Some(session_source),
+        )
+        .instrument(teammate_span(
+            team_name,
+            &member.name,
+            member.role.as_deref(),
+        ))
+        .await
+        .map_err(|e| e.to_string())?;
+    session
+        .services
+        .teams
+        .manager()
+        .update_member_thread(team_name, &member.name, thread_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let member_event = |thread_id, status| TeamMemberEvent {
+        team_name: team_name.to_string(),
+        member: TeamMemberInfo {
+            name: member.name.clone(),
+            thread_id,
+            role: member.role.clone(),
+            status,
+        },
+        previous_status: None,
+    };
+    session
+        .send_event(
+            turn,
+            EventMsg::TeamMemberRemoved(member_event(member.thread_id, AgentStatus::Shutdown)),
+        )
+        .await;
+    session
+        .send_event(
+            turn,
+            EventMsg::TeamMemberAdded(member_event(thread_id, AgentStatus::Running)),
+        )
+        .await;
+    let member = MemberConfig {
+        thread_id,
+        ..member.clone()
+    };
+    start_teammate_timeout(session, Arc::clone(turn), team_name.to_string(), &member);
+    forward_teammate_events(session, Arc::clone(turn), team_name.to_string(), &member);
+    watch_teammate_liveness(session, Arc::clone(turn), team_name.to_string(), &member);
+    watch_teammate_completion(
+        session,
+        Arc::clone(turn),
+        team_name.to_string(),
+        member,
+        teammate_config.clone(),
+    );
+    Ok(thread_id)
+}
+
+/// Apply what `member` was spawned with to the config of its thread: its
+/// role, sandbox and working directory, or its worktree's counterpart of
+/// `leader_cwd`.
+pub(crate) async fn configure_member(
+    config: &mut Config,
+    member: &MemberConfig,
+    leader_cwd: &Path,
+) -> Result<(), String> {
+    if let Some(role) = &member.role {
+        apply_role(config, role)
+            .await
+            .map_err(|e| format!("failed to load role '{role}': {e}"))?;
+    }
+    if let Some(policy) = &member.sandbox_policy {
+        config
+            .permissions
+            .sandbox_policy
+            .set(policy.clone())
+            .map_err(|e| e.to_string())?;
+        config.permissions.sandbox_policy = Constrained::allow_only(policy.clone());
+    }
+    if let Some(cwd) = &member.cwd {
+        config.cwd = cwd.clone();
+    }
+    if let Some(worktree) = &member.worktree {
+        config.cwd = worktree.cwd_for(member.cwd.as_deref().unwrap_or(leader_cwd));
+    }
+    Ok(())
+}
+
+/// Prompt for a restarted `member`: its original prompt, what it already
+/// completed, and the tasks it should resume.
+pub(crate) fn restart_prompt(member: &MemberConfig, tasks: &[TeamTaskInfo]) -> String {
+    let mut prompt = member.prompt.clone().unwrap_or_default();
+    let own_tasks = || {
+        tasks
+            .iter()
+            .filter(|t| t.assigned_to.as_deref() == Some(member.name.as_str()))
+    };
+    let completed: Vec<_> = own_tasks()
+        .filter(|t| matches!(t.status, TeamTaskStatus::Completed))
+        .collect();
+    if !completed.is_empty() {
+        prompt.push_str("\n\nBefore the restart you completed:");
+        for task in completed {
+            prompt.push_str(&format!("\n- {} ({})", task.title, task.id));
+            if let Some(result) = &task.result {
+                prompt.push_str(&format!(": {result}"));
+            }
+        }
+    }
+    for task in own_tasks().filter(|t| matches!(t.status, TeamTaskStatus::InProgress)) {
+        prompt.push_str(&format!(
+            "\n\nYou were restarted. Resume task {}: {}",
+            task.id, task.title
+        ));
+    }
+    prompt
+}
+
+fn final_report(
+    team_name: &str,
+    finished: bool,
+    tasks: &[TeamTaskInfo],
+    statuses: &[(&str, AgentStatus)],
+) -> String {
+    let headline = if finished {
+        format!("Team '{team_name}' finished all delegated tasks.")
+    } else {
+        format!("Team '{team_name}' cannot make further progress on its own.")
+    };
+    let mut report = vec![headline, String::new(), "Tasks:".to_string()];
+    for task in tasks {
+        let status = match task.status {
+            TeamTaskStatus::Pending => "pending",
+            TeamTaskStatus::InProgress => "in progress",
+            TeamTaskStatus::Completed => "completed",
+        };
+        let assignee = task.assigned_to.as_deref().unwrap_or("unassigned");
+        report.push(format!(
+            "- [{status}] {} ({}, {assignee})",
+            task.title, task.id
+        ));
+    }
+    report.push(String::new());
+    report.push("Teammates:".to_string());
+    for (name, status) in statuses {
+        report.push(format!("- {name}: {}", member_status_label(status)));
+    }
+    report.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn restart_prompt_summarizes_completed_work() {
+        let member = MemberConfig {
+            name: "alice".to_string(),
+            thread_id: ThreadId::new(),
+            status: "running".to_string(),
+            prompt: Some("Fix the parser.".to_string()),
+            max_restarts: Some(2),
+            restarts: 1,
+            ..Default::default()
+        };
+        let task = |id: &str, title: &str, status, assigned_to: &str| TeamTaskInfo {
+            id: id.to_string(),
+            title: title.to_string(),
+            description: None,
+            status,
+            assigned_to: Some(assigned_to.to_string()),
+            depends_on: Vec::new(),
+            result: None,
+            created_at: None,
+        };
+        let mut lexer = task("task-1", "Lexer", TeamTaskStatus::Completed, "alice");
+        lexer.result = Some("done".to_string());
+        let tasks = vec![
+            lexer,
+            task("task-2", "Grammar", TeamTaskStatus::InProgress, "alice"),
+            task("task-3", "Docs", TeamTaskStatus::Completed, "bob"),
+        ];
+
+        assert_eq!(
+            restart_prompt(&member, &tasks),
+            "Fix the parser.\n\n\
+             Before the restart you completed:\n\
+             - Lexer (task-1): done\n\n\
+             You were restarted. Resume task task-2: Grammar"
+        );
+    }
+
+    #[test]
+    fn final_report_lists_tasks_and_teammates() {
+        let tasks = vec![
+            TeamTaskInfo {
+                id: "task-1".to_string(),
+                title: "Write parser".to_string(),
+                description: None,
+                status: TeamTaskStatus::Completed,
+                assigned_to: Some("alice".to_string()),
+                depends_on: Vec::new(),
+                result: None,
+                created_at: None,
+            },
+            TeamTaskInfo {
+                id: "task-2".to_string(),
+                title: "Write docs".to_string(),
+                description: None,
+                status: TeamTaskStatus::Pending,
+                assigned_to: None,
+                depends_on: vec!["task-3".to_string()],
+                result: None,
+                created_at: None,
+            },
+        ];
+        let statuses = [
+            ("alice", AgentStatus::Completed(None)),
+            ("bob", AgentStatus::Errored("boom".to_string())),
+        ];
+
+        assert_eq!(
+            final_report("docs", false, &tasks, &statuses),
+            "Team 'docs' cannot make further progress on its own.\n\
+             \n\
+             Tasks:\n\
+             - [completed] Write parser (task-1, alice)\n\
+             - [pending] Write docs (task-2, unassigned)\n\
+             \n\
+             Teammates:\n\
+             - alice: idle\n\
+             - bob: errored"
+        );
+    }
+}
@@ -0,0 +1,194 @@
+//! Per-teammate diff summaries.
+//!
+//! Whenever a teammate finishes a turn, its completion watcher summarizes the
+//! changes it has made so far (files with added and removed line counts) and
+//! emits a `TeamDiffSummary` event, so the team dashboard shows who is
+//! producing changes without attaching to each thread. A teammate in its own
+//! worktree is credited with everything on its branch since the worktree was
+//! created, committed or not. A teammate sharing the leader's checkout is
+//! credited only with the uncommitted changes to files it edited itself, as
+//! recorded for edit-conflict detection.
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::teams::team_manager::MemberConfig;
+use crate::teams::worktree::git;
+use codex_protocol::protocol::{EventMsg, TeamDiffSummaryEvent, TeamFileDiff};
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// Emit a `TeamDiffSummary` event with the current changes of `member`.
+pub(crate) async fn report_diff_summary(
+    session: &Session,
+    turn: &TurnContext,
+    team_name: &str,
+    member: &MemberConfig,
+) {
+    let files = match &member.worktree {
+        Some(worktree) => diff_stat(&worktree.path, &worktree.base_commit, None).await,
+        None => match session.services.teams.manager().edits(team_name).await {
+            Ok(edits) => {
+                let edited = edits
+                    .by_member
+                    .get(&member.name)
+                    .cloned()
+                    .unwrap_or_default();
+                let cwd = member.cwd.as_deref().unwrap_or(&turn.cwd);
+                diff_stat(cwd, "HEAD", Some(&edited)).await
+            }
+            Err(e) => Err(e),
+        },
+    };
+    let files = match files {
+        Ok(files) => files,
+        Err(e) => {
+            tracing::debug!("no diff summary for {}: {e}", member.name);
+            return;
+        }
+    };
+    session
+        .send_event(
+            turn,
+            EventMsg::TeamDiffSummary(TeamDiffSummaryEvent {
+                team_name: team_name.to_string(),
+                member_name: member.name.clone(),
+                files,
+            }),
+        )
+        .await;
+}
+
+/// Files changed in the repository containing `cwd` since `base`, untracked
+/// files included, sorted by path. With `paths`, only those
+/// repository-relative paths are considered.
+pub(crate) async fn diff_stat(
+    cwd: &Path,
+    base: &str,
+    paths: Option<&BTreeSet<String>>,
+) -> std::io::Result<Vec<TeamFileDiff>> {
+    let repo_root = PathBuf::from(git(cwd, &["rev-parse", "--show-toplevel"]).await?);
+    let mut pathspec: Vec<&str> = Vec::new();
+    if let Some(paths) = paths {
+        // Edits outside any repository are recorded as absolute paths.
+        pathspec.extend(
+            paths
+                .iter()
+                .map(String::as_str)
+                .filter(|path| Path::new(path).is_relative()),
+        );
+        if pathspec.is_empty() {
+            return Ok(Vec::new());
+        }
+        pathspec.insert(0, "--");
+    }
+
+    let mut args = vec!["diff", "--numstat", base];
+    args.extend(&pathspec);
+    let mut files: Vec<TeamFileDiff> = git(&repo_root, &args)
+        .await?
+        .lines()
+        .filter_map(parse_numstat)
+        .collect();
+    let mut args = vec!["ls-files", "--others", "--exclude-standard"];
+    args.extend(&pathspec);
+    for path in git(&repo_root, &args).await?.lines() {
+        let contents = tokio::fs::read(repo_root.join(path)).await?;
+        files.push(TeamFileDiff {
+            path: path.to_string(),
+            added: line_count(&contents),
+            removed: 0,
+        });
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// A line of `git diff --numstat`: added, removed and path, separated by
+/// tabs. Binary files have `-` counts.
+fn parse_numstat(line: &str) -> Option<TeamFileDiff> {
+    let mut fields = line.splitn(3, '\t');
+    let added = fields.next()?;
+    let removed = fields.next()?;
+    let path = fields.next()?;
+    Some(TeamFileDiff {
+        path: path.to_string(),
+        added: added.parse().unwrap_or(0),
+        removed: removed.parse().unwrap_or(0),
+    })
+}
+
+/// Lines of a new file, or 0 if it looks binary.
+fn line_count(contents: &[u8]) -> u64 {
+    if contents.contains(&0) {
+        return 0;
+    }
+    let newlines = contents.iter().filter(|&&byte| byte == b'\n').count() as u64;
+    if contents.last().is_some_and(|&byte| byte != b'\n') {
+        newlines + 1
+    } else {
+        newlines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn file(path: &str, added: u64, removed: u64) -> TeamFileDiff {
+        TeamFileDiff {
+            path: path.to_string(),
+            added,
+            removed,
+        }
+    }
+
+    #[tokio::test]
+    async fn counts_tracked_and_untracked_changes() {
+        let tmp = TempDir::new().unwrap();
+        let repo = tmp.path();
+        std::fs::create_dir_all(repo.join("src")).unwrap();
+        std::fs::write(repo.join("src/lib.rs"), "a\nb\nc\n").unwrap();
+        std::fs::write(repo.join("README.md"), "readme\n").unwrap();
+        for args in [
+            &["init", "-q"][..],
+            &["config", "user.email", "test@example.com"],
+            &["config", "user.name", "Test"],
+            &["add", "-A"],
+            &["commit", "-q", "-m", "initial"],
+        ] {
+            git(repo, args).await.unwrap();
+        }
+
+        std::fs::write(repo.join("src/lib.rs"), "a\nB\nc\nd\n").unwrap();
+        std::fs::write(repo.join("README.md"), "").unwrap();
+        std::fs::write(repo.join("src/new.rs"), "x\ny").unwrap();
+        std::fs::write(repo.join("logo.png"), [0u8, 1, 2]).unwrap();
+
+        assert_eq!(
+            diff_stat(&repo.join("src"), "HEAD", None).await.unwrap(),
+            vec![
+                file("README.md", 0, 1),
+                file("logo.png", 0, 0),
+                file("src/lib.rs", 2, 1),
+                file("src/new.rs", 2, 0),
+            ]
+        );
+        let edited: BTreeSet<String> = ["src/lib.rs", "src/new.rs", "/outside/notes.md"]
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        assert_eq!(
+            diff_stat(repo, "HEAD", Some(&edited)).await.unwrap(),
+            vec![file("src/lib.rs", 2, 1), file("src/new.rs", 2, 0)]
+        );
+        assert_eq!(
+            diff_stat(repo, "HEAD", Some(&BTreeSet::new()))
+                .await
+                .unwrap(),
+            Vec::new()
+        );
+    }
+}
@@ -0,0 +1,200 @@
+//! Consistency checks for a team's on-disk state, run by `codex team doctor`.
+//!
+//! [`TeamManager::validate`](crate::teams::team_manager::TeamManager::validate)
+//! and [`TaskList::validate`](crate::teams::task_list::TaskList::validate)
+//! report what is wrong as [`TeamIssue`]s; their `repair` counterparts fix
+//! the issues that can be fixed without losing more than the broken part.
+
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::TeamTaskInfo;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::path::PathBuf;
+use tokio::fs;
+
+/// Something wrong with a team's stored state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TeamIssue {
+    /// The team's `config.json` does not parse. Nothing else about the team
+    /// can be trusted, so this is not repaired.
+    UnreadableConfig { path: PathBuf, error: String },
+    /// Another of the team's JSON files does not parse.
+    UnreadableFile { path: PathBuf, error: String },
+    /// A member whose thread is gone, e.g. after a crash wiped its rollout.
+    DeadMember { member: String, thread_id: ThreadId },
+    /// A task assigned to someone who is not a member of the team.
+    UnknownAssignee { task_id: String, assignee: String },
+    /// A task that depends on a task that does not exist.
+    MissingDependency { task_id: String, dependency: String },
+    /// Tasks that each depend on the next, the last on the first, so none of
+    /// them can ever start.
+    DependencyCycle { task_ids: Vec<String> },
+    /// An inbox left behind by someone who is no longer on the team.
+    StrayInbox { name: String, path: PathBuf },
+}
+
+impl TeamIssue {
+    /// What `repair` does about the issue, or `None` if it is left alone.
+    pub fn fix(&self) -> Option<&'static str> {
+        match self {
+            TeamIssue::UnreadableConfig { .. } => None,
+            TeamIssue::UnreadableFile { .. } => {
+                Some("move it aside to a .corrupt file and start from an empty one")
+            }
+            TeamIssue::DeadMember { .. } => {
+                Some("remove the member along with its inbox and file reservations")
+            }
+            TeamIssue::UnknownAssignee { .. } => {
+                Some("unassign the task, returning it to pending if it was in progress")
+            }
+            TeamIssue::MissingDependency { .. } => Some("drop the dependency"),
+            TeamIssue::DependencyCycle { .. } => {
+                Some("drop the dependency of the last task on the first")
+            }
+            TeamIssue::StrayInbox { .. } => Some("delete the inbox"),
+        }
+    }
+}
+
+impl fmt::Display for TeamIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TeamIssue::UnreadableConfig { path, error } => write!(
+                f,
+                "{} is unreadable ({error}); remove the team with `codex team cleanup`",
+                path.display()
+            ),
+            TeamIssue::UnreadableFile { path, error } => {
+                write!(f, "{} is unreadable ({error})", path.display())
+            }
+            TeamIssue::DeadMember { member, thread_id } => {
+                write!(f, "member '{member}' has no thread {thread_id} on disk")
+            }
+            TeamIssue::UnknownAssignee { task_id, assignee } => write!(
+                f,
+                "task {task_id} is assigned to '{assignee}', who is not a member"
+            ),
+            TeamIssue::MissingDependency {
+                task_id,
+                dependency,
+            } => write!(f, "task {task_id} depends on missing task {dependency}"),
+            TeamIssue::DependencyCycle { task_ids } => {
+                let first = task_ids.first().map(String::as_str).unwrap_or_default();
+                write!(
+                    f,
+                    "tasks depend on each other in a cycle: {} -> {first}",
+                    task_ids.join(" -> ")
+                )
+            }
+            TeamIssue::StrayInbox { name, .. } => {
+                write!(f, "inbox of '{name}', who is not a member")
+            }
+        }
+    }
+}
+
+/// The dependency cycles among `tasks`, each once, in the order
+/// [`TeamIssue::DependencyCycle`] expects. Dependencies on missing tasks are
+/// ignored.
+pub(crate) fn dependency_cycles(tasks: &[TeamTaskInfo]) -> Vec<Vec<String>> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Visit {
+        InProgress,
+        Done,
+    }
+
+    let index: HashMap<&str, usize> = tasks
+        .iter()
+        .enumerate()
+        .map(|(i, task)| (task.id.as_str(), i))
+        .collect();
+    let mut visits: Vec<Option<Visit>> = vec![None; tasks.len()];
+    let mut cycles = Vec::new();
+    for start in 0..tasks.len() {
+        if visits[start].is_some() {
+            continue;
+        }
+        // Depth-first over dependencies; `path` holds the tasks being visited.
+        let mut path: Vec<usize> = vec![start];
+        let mut next_dep: Vec<usize> = vec![0];
+        visits[start] = Some(Visit::InProgress);
+        while let Some(&current) = path.last() {
+            let depth = path.len() - 1;
+            let deps = &tasks[current].depends_on;
+            let Some(dep) = deps.get(next_dep[depth]) else {
+                visits[current] = Some(Visit::Done);
+                path.pop();
+                next_dep.pop();
+                continue;
+            };
+            next_dep[depth] += 1;
+            let Some(&dep) = index.get(dep.as_str()) else {
+                continue;
+            };
+            match visits[dep] {
+                None => {
+                    visits[dep] = Some(Visit::InProgress);
+                    path.push(dep);
+                    next_dep.push(0);
+                }
+                Some(Visit::InProgress) => {
+                    let from = path.iter().position(|&i| i == dep).unwrap_or_default();
+                    cycles.push(path[from..].iter().map(|&i| tasks[i].id.clone()).collect());
+                }
+                Some(Visit::Done) => {}
+            }
+        }
+    }
+    cycles
+}
+
+/// Move a broken file out of the way so its store starts over, keeping it
+/// next to the original for inspection.
+pub(crate) async fn set_aside(path: &Path) -> std::io::Result<()> {
+    let mut corrupt = path.as_os_str().to_owned();
+    corrupt.push(".corrupt");
+    fs::rename(path, PathBuf::from(corrupt)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::TeamTaskStatus;
+    use pretty_assertions::assert_eq;
+
+    fn task(id: &str, depends_on: &[&str]) -> TeamTaskInfo {
+        TeamTaskInfo {
+            id: id.to_string(),
+            title: id.to_string(),
+            description: None,
+            status: TeamTaskStatus::Pending,
+            assigned_to: None,
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+            result: None,
+            created_at: None,
+        }
+    }
+
+    #[test]
+    fn finds_each_cycle_once() {
+        let tasks = [
+            task("a", &["b"]),
+            task("b", &["c", "missing"]),
+            task("c", &["a"]),
+            task("d", &["a"]),
+            task("e", &["e"]),
+        ];
+
+        assert_eq!(
+            dependency_cycles(&tasks),
+            vec![
+                vec!["a".to_string(), "b".to_string(), "c".to_string()],
+                vec!["e".to_string()],
+            ]
+        );
+        assert!(dependency_cycles(&tasks[3..4]).is_empty());
+    }
+}
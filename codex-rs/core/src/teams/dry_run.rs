@@ -0,0 +1,158 @@
+//! Dry runs of a team's orchestration.
+//!
+//! A team created with `dry_run` never starts agents. `spawn_teammate`,
+//! `assign_task` and `shutdown_teammate` check their arguments as usual, then
+//! record what they would have done in the team's `dry_run.json` and return
+//! the plan so far, so the user can review how the leader means to split up
+//! the work before paying for it.
+
+use crate::teams::a2a::A2aMemberSpec;
+use crate::teams::external::McpMemberSpec;
+use crate::teams::ssh::SshMemberSpec;
+use crate::teams::team_manager::MemberBudget;
+use serde::Deserialize;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// A teammate `spawn_teammate` would have started.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlannedMember {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    pub prompt: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget: Option<MemberBudget>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<PathBuf>,
+    /// Whether the teammate would get its own git worktree.
+    #[serde(default)]
+    pub worktree: bool,
+    /// MCP tool of an external teammate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp: Option<McpMemberSpec>,
+    /// Remote A2A agent of an external teammate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub a2a: Option<A2aMemberSpec>,
+    /// Host of an external teammate run over SSH.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssh: Option<SshMemberSpec>,
+}
+
+/// A task `assign_task` would have created.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlannedTask {
+    pub id: String,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assigned_to: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+}
+
+/// Contents of a dry-run team's `dry_run.json`, in the order the calls were
+/// made.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DryRunPlan {
+    #[serde(default)]
+    pub members: Vec<PlannedMember>,
+    #[serde(default)]
+    pub tasks: Vec<PlannedTask>,
+    /// Names of the planned members `shutdown_teammate` would have stopped.
+    #[serde(default)]
+    pub shutdowns: Vec<String>,
+}
+
+/// Rough size of a [`DryRunPlan`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct DryRunEstimate {
+    pub members: usize,
+    pub tasks: usize,
+    /// Most tokens the plan's teammates may use: the sum of their
+    /// `max_tokens` budgets, capped by `teams.max_team_tokens`. `None` when
+    /// some teammate has no token budget and there is no cap.
+    pub max_tokens: Option<i64>,
+}
+
+impl DryRunPlan {
+    /// Id for the next planned task: `planned-task-1`, `planned-task-2`, …
+    pub fn next_task_id(&self) -> String {
+        format!("planned-task-{}", self.tasks.len() + 1)
+    }
+
+    /// Planned members that have not been shut down again.
+    pub fn active_members(&self) -> impl Iterator<Item = &PlannedMember> {
+        self.members
+            .iter()
+            .filter(|member| !self.shutdowns.contains(&member.name))
+    }
+
+    pub fn estimate(&self, max_team_tokens: Option<i64>) -> DryRunEstimate {
+        let budgets: Option<i64> = self
+            .members
+            .iter()
+            .map(|member| member.budget.and_then(|budget| budget.max_tokens))
+            .sum();
+        let max_tokens = match (budgets, max_team_tokens) {
+            (Some(budgets), Some(cap)) => Some(budgets.min(cap)),
+            (budgets, cap) => budgets.or(cap),
+        };
+        DryRunEstimate {
+            members: self.members.len(),
+            tasks: self.tasks.len(),
+            max_tokens,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn member(name: &str, max_tokens: Option<i64>) -> PlannedMember {
+        PlannedMember {
+            name: name.to_string(),
+            role: None,
+            prompt: "Work.".to_string(),
+            budget: max_tokens.map(|max_tokens| MemberBudget {
+                max_tokens: Some(max_tokens),
+                max_turns: None,
+            }),
+            cwd: None,
+            worktree: false,
+            mcp: None,
+            a2a: None,
+            ssh: None,
+        }
+    }
+
+    #[test]
+    fn estimate_sums_budgets_up_to_the_team_cap() {
+        let mut plan = DryRunPlan {
+            members: vec![member("alice", Some(1000)), member("bob", Some(500))],
+            tasks: Vec::new(),
+            shutdowns: vec!["bob".to_string()],
+        };
+        assert_eq!(
+            plan.active_members()
+                .map(|member| member.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["alice"]
+        );
+        assert_eq!(plan.next_task_id(), "planned-task-1");
+        assert_eq!(
+            plan.estimate(None),
+            DryRunEstimate {
+                members: 2,
+                tasks: 0,
+                max_tokens: Some(1500),
+            }
+        );
+        assert_eq!(plan.estimate(Some(1200)).max_tokens, Some(1200));
+
+        plan.members.push(member("carol", None));
+        assert_eq!(plan.estimate(None).max_tokens, None);
+        assert_eq!(plan.estimate(Some(1200)).max_tokens, Some(1200));
+    }
+}
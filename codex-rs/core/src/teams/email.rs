@@ -0,0 +1,326 @@
+//! Email notifications of long team runs.
+//!
+//! `teams.email` names an SMTP server and recipients for teams left to run
+//! unattended for hours. Unlike the chat notifiers of
+//! [`crate::teams::notifiers`], it only sends at the milestones someone away
+//! from the terminal needs: the team finished its tasks, `error_threshold`
+//! teammates of the team errored, or a teammate went over its budget. Each
+//! email carries the milestone message and the team's run report so far (see
+//! [`crate::teams::report`]). Mail is handed to the server by `curl`, which
+//! speaks SMTP with STARTTLS or TLS; its settings, the password included, go
+//! to `curl` on stdin rather than on its command line.
+
+use crate::config::types::TeamEmailNotifier;
+use crate::config::types::TeamWebhookEvent;
+use crate::teams::notifiers::TeamNotifier;
+use crate::teams::webhooks::TeamWebhookPayload;
+use async_trait::async_trait;
+use base64::Engine;
+use codex_protocol::protocol::TeamTaskInfo;
+use codex_protocol::protocol::TeamTaskStatus;
+use std::collections::HashMap;
+use std::io::Write as _;
+use std::process::Stdio;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+const DEFAULT_SMTP_PORT: u16 = 587;
+/// Port on which SMTP servers expect TLS from the start.
+const SMTPS_PORT: u16 = 465;
+const DEFAULT_PASSWORD_ENV_VAR: &str = "SMTP_PASSWORD";
+const DEFAULT_ERROR_THRESHOLD: u32 = 3;
+
+/// How long the SMTP server may take to accept an email.
+const EMAIL_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Teammates errored so far, by team name, counted towards
+/// `error_threshold`. [`TeamService`] keeps one per session.
+///
+/// [`TeamService`]: crate::teams::service::TeamService
+#[derive(Debug, Default)]
+pub(crate) struct ErroredTeammates(Mutex<HashMap<String, u32>>);
+
+impl ErroredTeammates {
+    /// Count one more errored teammate of `team_name`. Returns the new count.
+    fn record(&self, team_name: &str) -> u32 {
+        let mut errored = self
+            .0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let count = errored.entry(team_name.to_string()).or_default();
+        *count += 1;
+        *count
+    }
+
+    fn forget(&self, team_name: &str) {
+        self.0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .remove(team_name);
+    }
+}
+
+/// Subject of the email `payload` calls for, or `None` if it is not worth
+/// one. Counts errored teammates in `errored` as it goes, so each payload
+/// must be passed once.
+pub(crate) fn email_subject(
+    email: &TeamEmailNotifier,
+    payload: &TeamWebhookPayload,
+    tasks: &[TeamTaskInfo],
+    errored: &ErroredTeammates,
+) -> Option<String> {
+    let team = &payload.team_name;
+    match payload.event {
+        TeamWebhookEvent::TaskCompleted => {
+            let finished = !tasks.is_empty()
+                && tasks
+                    .iter()
+                    .all(|t| matches!(t.status, TeamTaskStatus::Completed));
+            finished.then(|| format!("Codex team {team} finished"))
+        }
+        TeamWebhookEvent::MemberErrored => {
+            let count = errored.record(team);
+            let threshold = email.error_threshold.unwrap_or(DEFAULT_ERROR_THRESHOLD);
+            (count == threshold)
+                .then(|| format!("Codex team {team}: {threshold} teammates errored"))
+        }
+        TeamWebhookEvent::BudgetExhausted => {
+            let member = payload
+                .member
+                .as_ref()
+                .map_or("a teammate", |m| m.name.as_str());
+            Some(format!("Codex team {team}: {member} went over its budget"))
+        }
+        TeamWebhookEvent::TeamCleaned => {
+            errored.forget(team);
+            None
+        }
+        TeamWebhookEvent::TeamCreated | TeamWebhookEvent::TaskFailed => None,
+    }
+}
+
+/// One email to the recipients of `teams.email`.
+pub(crate) struct EmailNotifier {
+    config: TeamEmailNotifier,
+    subject: String,
+    /// Run report sent below the milestone message.
+    report: String,
+}
+
+impl EmailNotifier {
+    pub(crate) fn new(config: TeamEmailNotifier, subject: String, report: String) -> Self {
+        Self {
+            config,
+            subject,
+            report,
+        }
+    }
+
+    /// The email, headers and body, with CRLF line endings.
+    fn mail(&self, message: &str, date: &str) -> String {
+        let header = |value: &str| value.replace(['\r', '\n'], " ");
+        let subject = if self.subject.is_ascii() {
+            header(&self.subject)
+        } else {
+            let encoded = base64::engine::general_purpose::STANDARD.encode(header(&self.subject));
+            format!("=?UTF-8?B?{encoded}?=")
+        };
+        let body = format!("{message}\n\n{}", self.report);
+        format!(
+            "From: {}\nTo: {}\nSubject: {subject}\nDate: {date}\nMIME-Version: 1.0\nContent-Type: text/plain; charset=utf-8\nContent-Transfer-Encoding: 8bit\n\n{}",
+            header(&self.config.from),
+            header(&self.config.to.join(", ")),
+            body.trim_end()
+        )
+        .replace("\r\n", "\n")
+        .replace('\n', "\r\n")
+            + "\r\n"
+    }
+
+    /// `curl` settings that send the email, in its config file format.
+    fn curl_config(&self, password: Option<&str>) -> String {
+        let quote = |value: &str| {
+            let escaped = value
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace(['\r', '\n'], "");
+            format!("\"{escaped}\"")
+        };
+        let port = self.config.smtp_port.unwrap_or(DEFAULT_SMTP_PORT);
+        let scheme = if port == SMTPS_PORT { "smtps" } else { "smtp" };
+        let mut config = format!(
+            "url = {}\nssl-reqd\nmail-from = {}\n",
+            quote(&format!("{scheme}://{}:{port}", self.config.smtp_host)),
+            quote(&self.config.from)
+        );
+        for to in &self.config.to {
+            config.push_str(&format!("mail-rcpt = {}\n", quote(to)));
+        }
+        if let Some(username) = &self.config.username {
+            let user = format!("{username}:{}", password.unwrap_or_default());
+            config.push_str(&format!("user = {}\n", quote(&user)));
+        }
+        config
+    }
+}
+
+#[async_trait]
+impl TeamNotifier for EmailNotifier {
+    fn destination(&self) -> String {
+        self.config.smtp_host.clone()
+    }
+
+    async fn send(&self, message: &str) -> Result<(), String> {
+        let password = match &self.config.username {
+            Some(_) => {
+                let var = self
+                    .config
+                    .password_env_var
+                    .as_deref()
+                    .unwrap_or(DEFAULT_PASSWORD_ENV_VAR);
+                Some(std::env::var(var).map_err(|_| format!("{var} is not set"))?)
+            }
+            None => None,
+        };
+        let mut mail = tempfile::NamedTempFile::new().map_err(|e| e.to_string())?;
+        mail.write_all(
+            self.mail(message, &chrono::Utc::now().to_rfc2822())
+                .as_bytes(),
+        )
+        .map_err(|e| e.to_string())?;
+
+        let mut child = Command::new("curl")
+            .args(["--silent", "--show-error", "--config", "-", "--upload-file"])
+            .arg(mail.path())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| format!("failed to run curl: {e}"))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(self.curl_config(password.as_deref()).as_bytes())
+                .await
+                .map_err(|e| format!("failed to configure curl: {e}"))?;
+        }
+        let output = tokio::time::timeout(EMAIL_TIMEOUT, child.wait_with_output())
+            .await
+            .map_err(|_| "the SMTP server did not answer in time".to_string())?
+            .map_err(|e| e.to_string())?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!(
+                "curl exited with {}: {}",
+                output.status,
+                stderr.trim()
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::ThreadId;
+    use codex_protocol::protocol::AgentStatus;
+    use codex_protocol::protocol::TeamMemberInfo;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn emails_go_out_at_the_milestones_worth_one() {
+        let email = TeamEmailNotifier {
+            smtp_host: "smtp.example.com".to_string(),
+            smtp_port: None,
+            username: Some("codex".to_string()),
+            password_env_var: None,
+            from: "codex@example.com".to_string(),
+            to: vec![
+                "oncall@example.com".to_string(),
+                "lead@example.com".to_string(),
+            ],
+            error_threshold: Some(2),
+        };
+        let task = |status| TeamTaskInfo {
+            id: "task-1".to_string(),
+            title: "Review".to_string(),
+            description: None,
+            status,
+            assigned_to: Some("reviewer".to_string()),
+            depends_on: Vec::new(),
+            result: None,
+            created_at: None,
+        };
+        let payload = |event| TeamWebhookPayload {
+            event,
+            team_name: "zeta".to_string(),
+            timestamp: String::new(),
+            member: Some(TeamMemberInfo {
+                name: "reviewer".to_string(),
+                thread_id: ThreadId::new(),
+                role: None,
+                status: AgentStatus::Errored("boom".to_string()),
+            }),
+            task: None,
+        };
+        let errored = ErroredTeammates::default();
+        let subject =
+            |event, tasks: &[TeamTaskInfo]| email_subject(&email, &payload(event), tasks, &errored);
+
+        let open = [
+            task(TeamTaskStatus::Completed),
+            task(TeamTaskStatus::Pending),
+        ];
+        assert_eq!(subject(TeamWebhookEvent::TaskCompleted, &open), None);
+        let done = [task(TeamTaskStatus::Completed)];
+        assert_eq!(
+            subject(TeamWebhookEvent::TaskCompleted, &done).as_deref(),
+            Some("Codex team zeta finished")
+        );
+        assert_eq!(subject(TeamWebhookEvent::MemberErrored, &[]), None);
+        assert_eq!(
+            subject(TeamWebhookEvent::MemberErrored, &[]).as_deref(),
+            Some("Codex team zeta: 2 teammates errored")
+        );
+        assert_eq!(subject(TeamWebhookEvent::MemberErrored, &[]), None);
+        assert_eq!(
+            subject(TeamWebhookEvent::BudgetExhausted, &[]).as_deref(),
+            Some("Codex team zeta: reviewer went over its budget")
+        );
+
+        let notifier = EmailNotifier::new(
+            email.clone(),
+            "Codex team zeta finished".to_string(),
+            "# Team run report: zeta\n".to_string(),
+        );
+        assert_eq!(
+            notifier.mail(
+                "team zeta: 1/1 tasks complete, task-1 (Review) done",
+                "Wed, 14 Oct 2026 09:00:00 +0000"
+            ),
+            "From: codex@example.com\r\n\
+             To: oncall@example.com, lead@example.com\r\n\
+             Subject: Codex team zeta finished\r\n\
+             Date: Wed, 14 Oct 2026 09:00:00 +0000\r\n\
+             MIME-Version: 1.0\r\n\
+             Content-Type: text/plain; charset=utf-8\r\n\
+             Content-Transfer-Encoding: 8bit\r\n\
+             \r\n\
+             team zeta: 1/1 tasks complete, task-1 (Review) done\r\n\
+             \r\n\
+             # Team run report: zeta\r\n"
+        );
+        assert_eq!(
+            notifier.curl_config(Some("p\"w")),
+            "url = \"smtp://smtp.example.com:587\"\n\
+             ssl-reqd\n\
+             mail-from = \"codex@example.com\"\n\
+             mail-rcpt = \"oncall@example.com\"\n\
+             mail-rcpt = \"lead@example.com\"\n\
+             user = \"codex:p\\\"w\"\n"
+        );
+    }
+}
@@ -0,0 +1,112 @@
+//! Machine-readable team tool errors.
+//!
+//! Team tools report failures to the model as plain text. Each failure site
+//! builds a [`TeamToolError`] carrying that text and a code, which the team
+//! handler emits as a `TeamError` event, so UIs and logs can react to kinds
+//! of failures without parsing messages themselves.
+
+use crate::function_tool::FunctionCallError;
+use codex_protocol::protocol::TeamErrorCode;
+use serde_json::Value;
+
+/// A failed team tool call: the text the model sees and its code.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TeamToolError {
+    pub(crate) code: TeamErrorCode,
+    pub(crate) message: String,
+}
+
+impl TeamToolError {
+    pub(crate) fn new(code: TeamErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// A failure with no more specific code, usually a bad argument value.
+    pub(crate) fn other(message: impl Into<String>) -> Self {
+        Self::new(TeamErrorCode::Other, message)
+    }
+
+    /// `context` failed on team storage with `err`; a stored file that no
+    /// longer parses is reported as corrupt.
+    pub(crate) fn storage(context: impl std::fmt::Display, err: &std::io::Error) -> Self {
+        let code = if err.kind() == std::io::ErrorKind::InvalidData {
+            TeamErrorCode::StorageCorrupt
+        } else {
+            TeamErrorCode::StorageError
+        };
+        Self::new(code, format!("{context}: {err}"))
+    }
+}
+
+impl std::fmt::Display for TeamToolError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl From<TeamToolError> for FunctionCallError {
+    fn from(err: TeamToolError) -> Self {
+        FunctionCallError::RespondToModel(err.message)
+    }
+}
+
+/// The team a team tool call with `arguments` targets, if it names one.
+pub(crate) fn error_team_name(tool_name: &str, arguments: &str) -> Option<String> {
+    let args: Value = serde_json::from_str(arguments).ok()?;
+    let key = if tool_name == "create_team" {
+        "name"
+    } else {
+        "team_name"
+    };
+    args.get(key)?.as_str().map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn storage_errors_tell_corrupt_files_apart() {
+        let corrupt = std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "expected value at line 1 column 1",
+        );
+        assert_eq!(
+            TeamToolError::storage("failed to load team", &corrupt),
+            TeamToolError::new(
+                TeamErrorCode::StorageCorrupt,
+                "failed to load team: expected value at line 1 column 1"
+            )
+        );
+        let denied = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "permission denied");
+        assert_eq!(
+            TeamToolError::storage("failed to get tasks", &denied),
+            TeamToolError::new(
+                TeamErrorCode::StorageError,
+                "failed to get tasks: permission denied"
+            )
+        );
+        assert_eq!(
+            FunctionCallError::from(TeamToolError::other("paths must not be empty")),
+            FunctionCallError::RespondToModel("paths must not be empty".to_string())
+        );
+    }
+
+    #[test]
+    fn team_name_comes_from_arguments() {
+        assert_eq!(
+            error_team_name("create_team", r#"{"name":"zeta"}"#),
+            Some("zeta".to_string())
+        );
+        assert_eq!(
+            error_team_name("assign_task", r#"{"team_name":"zeta","title":"x"}"#),
+            Some("zeta".to_string())
+        );
+        assert_eq!(error_team_name("list_teams", "{}"), None);
+        assert_eq!(error_team_name("assign_task", "not json"), None);
+    }
+}
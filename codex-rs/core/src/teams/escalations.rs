@@ -0,0 +1,48 @@
+//! Escalations to the user.
+//!
+//! Teammates run without a human at the keyboard. When one needs a decision
+//! only the user can make, it calls `escalate_to_user`, which records the
+//! question in the team's `escalations.json`. The leader's session surfaces
+//! each unanswered escalation as a `TeamEscalationRequested` event, and the
+//! user's answer (`Op::AnswerTeamEscalation`) is written back into the
+//! teammate's inbox.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// A question a teammate asked the user.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TeamEscalation {
+    pub id: String,
+    /// Teammate that asked.
+    pub from: String,
+    pub question: String,
+    /// Suggested answers; the user may still answer freely.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+    pub asked_at: String,
+    /// The user's answer, once given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answer: Option<String>,
+}
+
+/// Contents of a team's `escalations.json`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TeamEscalations {
+    #[serde(default)]
+    pub escalations: Vec<TeamEscalation>,
+}
+
+impl TeamEscalations {
+    /// Id for the next escalation: `escalation-1`, `escalation-2`, …
+    pub fn next_id(&self) -> String {
+        format!("escalation-{}", self.escalations.len() + 1)
+    }
+
+    /// Escalations the user has not answered yet, oldest first.
+    pub fn pending(&self) -> impl Iterator<Item = &TeamEscalation> {
+        self.escalations
+            .iter()
+            .filter(|escalation| escalation.answer.is_none())
+    }
+}
@@ -0,0 +1,252 @@
+//! Team event log.
+//!
+//! Every session in a team, leader or teammate, appends the team events it
+//! emits to the team's `events.jsonl`, one JSON record per line. The log lets
+//! another process follow a live team without joining it: `codex team attach`
+//! replays it and then tails it for new lines. Streamed teammate output
+//! (`TeamMemberOutput`) is left out; it is only useful live and would dwarf
+//! everything else.
+//!
+//! The team tool calls sessions make are logged alongside the events, as
+//! records with a `tool_name` instead of a `msg`, so the log is a complete
+//! trace of the run for `codex team replay`. Readers of events only skip
+//! them.
+
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::EventMsg;
+use serde::Deserialize;
+use serde::Serialize;
+use std::io::SeekFrom;
+use std::path::PathBuf;
+use tokio::fs;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncSeekExt;
+use tokio::io::AsyncWriteExt;
+
+/// One line of `events.jsonl`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamEventRecord {
+    /// When the event was emitted, RFC 3339.
+    pub timestamp: String,
+    /// Session that emitted the event.
+    pub thread_id: ThreadId,
+    pub msg: EventMsg,
+}
+
+/// A team tool call, logged once the tool has returned.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TeamToolCallRecord {
+    /// When the tool returned, RFC 3339.
+    pub timestamp: String,
+    /// Session that made the call.
+    pub thread_id: ThreadId,
+    pub tool_name: String,
+    /// The call's JSON arguments, as the model sent them.
+    pub arguments: String,
+    /// What the tool returned: its JSON result, or the error message.
+    pub output: String,
+    pub success: bool,
+}
+
+/// Any line of `events.jsonl`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TeamTraceEntry {
+    Event(TeamEventRecord),
+    ToolCall(TeamToolCallRecord),
+}
+
+impl TeamTraceEntry {
+    pub fn timestamp(&self) -> &str {
+        match self {
+            TeamTraceEntry::Event(record) => &record.timestamp,
+            TeamTraceEntry::ToolCall(record) => &record.timestamp,
+        }
+    }
+
+    pub fn thread_id(&self) -> ThreadId {
+        match self {
+            TeamTraceEntry::Event(record) => record.thread_id,
+            TeamTraceEntry::ToolCall(record) => record.thread_id,
+        }
+    }
+}
+
+/// A team's `events.jsonl`.
+pub struct TeamEventLog {
+    path: PathBuf,
+}
+
+impl TeamEventLog {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Append `msg`, emitted by `thread_id`. The team directory is not
+    /// created, so events that outlive their team (such as its cleanup) are
+    /// not recorded.
+    pub async fn append(&self, thread_id: ThreadId, msg: &EventMsg) -> std::io::Result<()> {
+        self.append_entry(&TeamTraceEntry::Event(TeamEventRecord {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            thread_id,
+            msg: msg.clone(),
+        }))
+        .await
+    }
+
+    /// Append a tool call `thread_id` made, like [`Self::append`].
+    pub async fn append_tool_call(
+        &self,
+        thread_id: ThreadId,
+        tool_name: &str,
+        arguments: &str,
+        output: &str,
+        success: bool,
+    ) -> std::io::Result<()> {
+        self.append_entry(&TeamTraceEntry::ToolCall(TeamToolCallRecord {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            thread_id,
+            tool_name: tool_name.to_string(),
+            arguments: arguments.to_string(),
+            output: output.to_string(),
+            success,
+        }))
+        .await
+    }
+
+    async fn append_entry(&self, entry: &TeamTraceEntry) -> std::io::Result<()> {
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(line.as_bytes()).await
+    }
+
+    /// Event records of the complete lines from byte `offset` on, with the
+    /// offset just past the last of them. A line still being written is left
+    /// for the next read; lines that do not parse are skipped.
+    pub async fn read_from(&self, offset: u64) -> std::io::Result<(Vec<TeamEventRecord>, u64)> {
+        let (entries, next) = self.read_trace_from(offset).await?;
+        let records = entries
+            .into_iter()
+            .filter_map(|entry| match entry {
+                TeamTraceEntry::Event(record) => Some(record),
+                TeamTraceEntry::ToolCall(_) => None,
+            })
+            .collect();
+        Ok((records, next))
+    }
+
+    /// Like [`Self::read_from`], with the tool calls too.
+    pub async fn read_trace_from(
+        &self,
+        offset: u64,
+    ) -> std::io::Result<(Vec<TeamTraceEntry>, u64)> {
+        let mut file = fs::File::open(&self.path).await?;
+        file.seek(SeekFrom::Start(offset)).await?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes).await?;
+        let Some(end) = bytes.iter().rposition(|byte| *byte == b'\n') else {
+            return Ok((Vec::new(), offset));
+        };
+        let records = bytes[..end]
+            .split(|byte| *byte == b'\n')
+            .filter_map(|line| match serde_json::from_slice(line) {
+                Ok(entry) => Some(entry),
+                Err(e) => {
+                    tracing::debug!("skipping malformed team log line: {e}");
+                    None
+                }
+            })
+            .collect();
+        Ok((records, offset + end as u64 + 1))
+    }
+}
+
+/// Name of the team whose log `msg` belongs in, or `None` if it is not a
+/// team event or is not logged.
+pub(crate) fn logged_team_name(msg: &EventMsg) -> Option<&str> {
+    match msg {
+        EventMsg::TeamCreated(ev) => Some(&ev.team_name),
+        EventMsg::TeamMemberAdded(ev)
+        | EventMsg::TeamMemberRemoved(ev)
+        | EventMsg::TeamMemberStatusChanged(ev) => Some(&ev.team_name),
+        EventMsg::TeamTaskCreated(ev) | EventMsg::TeamTaskUpdated(ev) => Some(&ev.team_name),
+        EventMsg::TeamTaskUnblocked(ev) => Some(&ev.team_name),
+        EventMsg::TeamEscalationRequested(ev) => Some(&ev.team_name),
+        EventMsg::TeamMessageSent(ev) => Some(&ev.team_name),
+        EventMsg::TeamCleanup(ev) => Some(&ev.team_name),
+        EventMsg::TeamRenamed(ev) => Some(&ev.new_name),
+        EventMsg::TeamLeaderChanged(ev) => Some(&ev.team_name),
+        EventMsg::TeamBudgetExhausted(ev) => Some(&ev.team_name),
+        EventMsg::TeamMemberRestarted(ev) => Some(&ev.team_name),
+        EventMsg::TeamEditConflict(ev) => Some(&ev.team_name),
+        EventMsg::TeamDiffSummary(ev) => Some(&ev.team_name),
+        EventMsg::TeamMemberTokenUsage(ev) => Some(&ev.team_name),
+        EventMsg::TeamProgress(ev) => Some(&ev.team_name),
+        EventMsg::TeamMemberLivenessLost(ev) => Some(&ev.team_name),
+        EventMsg::TeamError(ev) => ev.team_name.as_deref(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::TeamCleanupEvent;
+    use codex_protocol::protocol::TeamMemberOutput;
+    use codex_protocol::protocol::TeamMemberOutputEvent;
+    use codex_protocol::protocol::TeamRenamedEvent;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn reads_complete_lines_from_an_offset() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.jsonl");
+        let log = TeamEventLog::new(path.clone());
+        let thread_id = ThreadId::new();
+        let renamed = EventMsg::TeamRenamed(TeamRenamedEvent {
+            old_name: "docs".to_string(),
+            new_name: "guide".to_string(),
+        });
+        log.append(thread_id, &renamed).await.unwrap();
+
+        let (records, offset) = log.read_from(0).await.unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].thread_id, thread_id);
+        assert!(matches!(
+            &records[0].msg,
+            EventMsg::TeamRenamed(ev) if ev.new_name == "guide"
+        ));
+        assert_eq!(offset, std::fs::metadata(&path).unwrap().len());
+
+        // A half-written line waits for its newline.
+        let mut contents = std::fs::read_to_string(&path).unwrap();
+        contents.push_str("{\"timestamp\":");
+        std::fs::write(&path, &contents).unwrap();
+        let (records, next) = log.read_from(offset).await.unwrap();
+        assert!(records.is_empty());
+        assert_eq!(next, offset);
+    }
+
+    #[test]
+    fn member_output_is_not_logged() {
+        let output = TeamMemberOutputEvent {
+            team_name: "docs".to_string(),
+            member_name: "writer".to_string(),
+            thread_id: ThreadId::new(),
+            output: TeamMemberOutput::AgentMessageDelta {
+                delta: "Hel".to_string(),
+            },
+        };
+        assert_eq!(logged_team_name(&EventMsg::TeamMemberOutput(output)), None);
+        let cleanup = EventMsg::TeamCleanup(TeamCleanupEvent {
+            team_name: "docs".to_string(),
+            leader_thread_id: ThreadId::new(),
+        });
+        assert_eq!(logged_team_name(&cleanup), Some("docs"));
+    }
+}
@@ -0,0 +1,530 @@
+//! Teammates backed by an MCP tool instead of a Codex thread.
+//!
+//! `spawn_teammate` with `kind: "mcp"` registers an external agent as a
+//! member: it gets a name, an inbox and tasks like any teammate, but no
+//! thread. Whatever lands in its inbox — the spawn prompt, messages, tasks
+//! assigned to it — is also delivered by calling its MCP tool with an
+//! [`ExternalMemberRequest`]. The tool answers with an
+//! [`ExternalMemberReply`]: a reply for the leader's inbox and updates to the
+//! member's tasks, which are applied to the team's task list as if the member
+//! had called `complete_task`. Members that are remote A2A agents are
+//! delivered to the same way over [`crate::teams::a2a`], and members running
+//! on another machine over [`crate::teams::ssh`].
+
+use crate::agent::AgentStatus;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::teams::a2a::send_to_a2a_agent;
+use crate::teams::completion::LEADER_INBOX;
+use crate::teams::inbox::InboxMessage;
+use crate::teams::ssh::remote_sandbox_mode;
+use crate::teams::ssh::send_to_ssh_member;
+use crate::teams::team_manager::MemberConfig;
+use codex_protocol::mcp::CallToolResult;
+use codex_protocol::protocol::{
+    EventMsg, TeamMemberEvent, TeamMemberInfo, TeamTaskEvent, TeamTaskInfo, TeamTaskStatus,
+    TeamTaskUnblockedEvent,
+};
+use serde::Deserialize;
+use serde::Serialize;
+use std::sync::Arc;
+
+/// `kind` of a teammate backed by an MCP tool.
+pub const MCP_KIND: &str = "mcp";
+
+/// Member status while a delivery to the member's tool is in flight.
+const DELIVERING_STATUS: &str = "running";
+/// Member status once its tool has answered.
+const ANSWERED_STATUS: &str = "completed";
+/// Member status once a delivery to its tool failed.
+const FAILED_STATUS: &str = "errored";
+
+/// The MCP tool an external teammate is reached through.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct McpMemberSpec {
+    /// Name of the MCP server, as configured under `mcp_servers`.
+    pub server: String,
+    pub tool: String,
+}
+
+/// Arguments the member's tool is called with.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExternalMemberRequest<'a> {
+    pub team_name: &'a str,
+    /// The member being called.
+    pub member: &'a str,
+    /// Who the message is from: `leader`, `user` or a teammate's name.
+    pub from: &'a str,
+    pub message: &'a str,
+    /// The member's tasks that are not completed yet.
+    pub tasks: Vec<&'a TeamTaskInfo>,
+}
+
+/// What the member's tool may answer with, as structured content or as JSON
+/// text. Any other text is taken as the reply.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct ExternalMemberReply {
+    #[serde(default)]
+    pub reply: Option<String>,
+    #[serde(default)]
+    pub task_updates: Vec<ExternalTaskUpdate>,
+}
+
+/// A change to one of the member's tasks.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ExternalTaskUpdate {
+    pub task_id: String,
+    pub status: TeamTaskStatus,
+    /// Added to the task's result.
+    #[serde(default)]
+    pub result: Option<String>,
+}
+
+impl ExternalMemberReply {
+    /// Read the answer of the member's tool, or the error it reported.
+    pub fn from_tool_result(result: &CallToolResult) -> Result<Self, String> {
+        let text = result
+            .content
+            .iter()
+            .filter_map(|block| block.get("text").and_then(serde_json::Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n");
+        if result.is_error == Some(true) {
+            return Err(if text.is_empty() {
+                "the tool reported an error".to_string()
+            } else {
+                text
+            });
+        }
+        if let Some(structured) = &result.structured_content
+            && let Ok(reply) = serde_json::from_value(structured.clone())
+        {
+            return Ok(reply);
+        }
+        Ok(Self::from_text(text))
+    }
+
+    /// Read an answer given as text: JSON of the reply, or the reply itself.
+    pub fn from_text(text: String) -> Self {
+        if let Ok(reply) = serde_json::from_str(&text) {
+            return reply;
+        }
+        Self {
+            reply: (!text.trim().is_empty()).then_some(text),
+            task_updates: Vec::new(),
+        }
+    }
+}
+
+/// Status of an external member as the team tools report it.
+pub(crate) fn external_status(member: &MemberConfig) -> AgentStatus {
+    match member.status.as_str() {
+        DELIVERING_STATUS => AgentStatus::Running,
+        ANSWERED_STATUS => AgentStatus::Completed(None),
+        FAILED_STATUS => AgentStatus::Errored(match (&member.mcp, &member.a2a, &member.ssh) {
+            (Some(spec), _, _) => format!("delivery to its MCP tool {} failed", spec.tool),
+            (None, Some(spec), _) => format!("delivery to its A2A agent {} failed", spec.endpoint),
+            (None, None, Some(spec)) => format!("running codex on {} failed", spec.host),
+            (None, None, None) => "delivery failed".to_string(),
+        }),
+        _ => AgentStatus::PendingInit,
+    }
+}
+
+/// Status of any member: the stored one for external members, the one in
+/// its lease for joined members, the thread's for the rest.
+pub(crate) async fn member_status(
+    session: &Session,
+    team_name: &str,
+    member: &MemberConfig,
+) -> AgentStatus {
+    if member.is_external() {
+        return external_status(member);
+    }
+    if member.joined {
+        let manager = session.services.teams.manager();
+        return match manager.member_lease(team_name, &member.name).await {
+            Ok(Some(lease)) if !lease.is_stale() => lease.status,
+            _ => AgentStatus::NotFound,
+        };
+    }
+    session
+        .services
+        .agent_control
+        .get_status(member.thread_id)
+        .await
+}
+
+/// Whether `server` offers `tool` in this session.
+pub(crate) async fn has_mcp_tool(session: &Session, spec: &McpMemberSpec) -> bool {
+    session
+        .services
+        .mcp_connection_manager
+        .read()
+        .await
+        .list_all_tools()
+        .await
+        .values()
+        .any(|info| info.server_name == spec.server && info.tool_name == spec.tool)
+}
+
+/// Deliver `message` to `to` through its MCP tool, A2A endpoint or SSH host if
+/// `to` is an external member of `team_name`, then apply the answer. Does
+/// nothing for other recipients. The message is expected to be in `to`'s inbox already.
+///
+/// The delivery runs in the background, since remote members may take long
+/// to answer: the answer reaches the leader's inbox, and a failed delivery
+/// marks the member errored and is reported to the leader's inbox too.
+pub(crate) fn deliver_to_external_member(
+    session: &Arc<Session>,
+    turn: &Arc<TurnContext>,
+    team_name: &str,
+    to: &str,
+    from: &str,
+    message: &str,
+) {
+    let session = Arc::clone(session);
+    let turn = Arc::clone(turn);
+    let (team_name, to, from, message) = (
+        team_name.to_string(),
+        to.to_string(),
+        from.to_string(),
+        message.to_string(),
+    );
+    tokio::spawn(async move {
+        let Err(e) = deliver(&session, &turn, &team_name, &to, &from, &message).await else {
+            return;
+        };
+        tracing::warn!("{e}");
+        let notice = InboxMessage {
+            from: to.clone(),
+            content: e,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            read: false,
+        };
+        if let Err(e) = session
+            .services
+            .teams
+            .inbox(&team_name)
+            .send_message(LEADER_INBOX, notice)
+            .await
+        {
+            tracing::warn!("failed to report the failed delivery to {to}: {e}");
+        }
+    });
+}
+
+async fn deliver(
+    session: &Session,
+    turn: &TurnContext,
+    team_name: &str,
+    to: &str,
+    from: &str,
+    message: &str,
+) -> Result<(), String> {
+    let teams = &session.services.teams;
+    let Ok(config) = teams.load_config(team_name).await else {
+        return Ok(());
+    };
+    let Some(member) = config.members.into_iter().find(|m| m.name == to) else {
+        return Ok(());
+    };
+    if !member.is_external() {
+        return Ok(());
+    }
+
+    let tasks = teams
+        .get_all_tasks(team_name)
+        .await
+        .map_err(|e| format!("failed to load tasks: {e}"))?;
+    let request = ExternalMemberRequest {
+        team_name,
+        member: to,
+        from,
+        message,
+        tasks: tasks
+            .iter()
+            .filter(|t| {
+                t.assigned_to.as_deref() == Some(to)
+                    && !matches!(t.status, TeamTaskStatus::Completed)
+            })
+            .collect(),
+    };
+    let arguments = serde_json::to_value(&request).map_err(|e| e.to_string())?;
+
+    set_status(session, turn, team_name, &member, DELIVERING_STATUS).await;
+    let mut working = false;
+    let (reply, via) = match (&member.mcp, &member.a2a, &member.ssh) {
+        (Some(spec), _, _) => {
+            let reply = session
+                .call_tool(&spec.server, &spec.tool, Some(arguments))
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|result| ExternalMemberReply::from_tool_result(&result));
+            (reply, format!("{}/{}", spec.server, spec.tool))
+        }
+        (None, Some(spec), _) => {
+            let answer = send_to_a2a_agent(spec, message, &request).await;
+            if let Ok(answer) = &answer
+                && answer.context_id.is_some()
+                && answer.context_id != spec.context_id
+                && let Err(e) = teams
+                    .manager()
+                    .update_a2a_context(team_name, to, answer.context_id.clone())
+                    .await
+            {
+                tracing::warn!("failed to keep the A2A context of {to}: {e}");
+            }
+            working = answer.as_ref().is_ok_and(|answer| answer.working);
+            (answer.map(|answer| answer.reply), spec.endpoint.clone())
+        }
+        (None, None, Some(spec)) => {
+            let sandbox_mode = remote_sandbox_mode(&turn.sandbox_policy, turn.approval_policy);
+            let answer = send_to_ssh_member(spec, sandbox_mode, &request).await;
+            if let Ok(answer) = &answer
+                && answer.session_id.is_some()
+                && answer.session_id != spec.session_id
+                && let Err(e) = teams
+                    .manager()
+                    .update_ssh_session(team_name, to, answer.session_id.clone())
+                    .await
+            {
+                tracing::warn!("failed to keep the remote session of {to}: {e}");
+            }
+            (answer.map(|answer| answer.reply), spec.host.clone())
+        }
+        (None, None, None) => return Ok(()),
+    };
+    let reply = match reply {
+        Ok(reply) => reply,
+        Err(e) => {
+            set_status(session, turn, team_name, &member, FAILED_STATUS).await;
+            return Err(format!(
+                "delivery to external teammate '{to}' through {via} failed: {e}"
+            ));
+        }
+    };
+    apply_reply(session, turn, team_name, to, &tasks, reply).await;
+    // An A2A agent still working on the delivery stays running.
+    if !working {
+        set_status(session, turn, team_name, &member, ANSWERED_STATUS).await;
+    }
+    Ok(())
+}
+
+/// Apply the member's task updates, skipping tasks that are not its own, and
+/// forward its reply to the leader.
+async fn apply_reply(
+    session: &Session,
+    turn: &TurnContext,
+    team_name: &str,
+    member_name: &str,
+    tasks: &[TeamTaskInfo],
+    reply: ExternalMemberReply,
+) {
+    let task_list = session.services.teams.tasks();
+    for update in reply.task_updates {
+        let owned = tasks
+            .iter()
+            .any(|t| t.id == update.task_id && t.assigned_to.as_deref() == Some(member_name));
+        if !owned {
+            tracing::warn!(
+                "external teammate {member_name} updated task {}, which is not its own",
+                update.task_id
+            );
+            continue;
+        }
+        let mut updated = None;
+        if let Some(result) = &update.result {
+            updated = task_list
+                .append_result(team_name, &update.task_id, result)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::warn!("failed to record result of task {}: {e}", update.task_id);
+                    None
+                });
+        }
+        let mut unblocked = Vec::new();
+        match update.status {
+            TeamTaskStatus::Completed => {
+                match task_list.complete_task(team_name, &update.task_id).await {
+                    Ok(Some((task, newly_unblocked))) => {
+                        updated = Some(task);
+                        unblocked = newly_unblocked;
+                    }
+                    Ok(None) => {}
+                    Err(e) => tracing::warn!("failed to complete task {}: {e}", update.task_id),
+                }
+            }
+            TeamTaskStatus::InProgress => {
+                match task_list
+                    .assign_task(team_name, &update.task_id, member_name)
+                    .await
+                {
+                    Ok(Some(task)) => updated = Some(task),
+                    Ok(None) => {}
+                    Err(e) => tracing::warn!("failed to update task {}: {e}", update.task_id),
+                }
+            }
+            TeamTaskStatus::Pending => {}
+        }
+        if let Some(task) = updated {
+            session
+                .send_event(
+                    turn,
+                    EventMsg::TeamTaskUpdated(TeamTaskEvent {
+                        team_name: team_name.to_string(),
+                        task,
+                    }),
+                )
+                .await;
+        }
+        for task in unblocked {
+            session
+                .send_event(
+                    turn,
+                    EventMsg::TeamTaskUnblocked(TeamTaskUnblockedEvent {
+                        team_name: team_name.to_string(),
+                        task,
+                        unblocked_by: update.task_id.clone(),
+                    }),
+                )
+                .await;
+        }
+    }
+
+    let Some(content) = reply.reply else {
+        return;
+    };
+    // The leader's session announces the message once it reads its inbox.
+    let message = InboxMessage {
+        from: member_name.to_string(),
+        content,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        read: false,
+    };
+    if let Err(e) = session
+        .services
+        .teams
+        .inbox(team_name)
+        .send_message(LEADER_INBOX, message)
+        .await
+    {
+        tracing::warn!("failed to forward the reply of {member_name} to the leader: {e}");
+    }
+}
+
+/// Store `status` for the member and emit `TeamMemberStatusChanged` if it
+/// changed.
+async fn set_status(
+    session: &Session,
+    turn: &TurnContext,
+    team_name: &str,
+    member: &MemberConfig,
+    status: &str,
+) {
+    let manager = session.services.teams.manager();
+    let previous = match manager.member_by_thread(team_name, member.thread_id).await {
+        Ok(Some(current)) => current,
+        _ => return,
+    };
+    if previous.status == status {
+        return;
+    }
+    if let Err(e) = manager
+        .update_member_status(team_name, &member.name, status)
+        .await
+    {
+        tracing::warn!("failed to update the status of {}: {e}", member.name);
+        return;
+    }
+    let current = MemberConfig {
+        status: status.to_string(),
+        ..previous.clone()
+    };
+    session
+        .send_event(
+            turn,
+            EventMsg::TeamMemberStatusChanged(TeamMemberEvent {
+                team_name: team_name.to_string(),
+                member: TeamMemberInfo {
+                    name: member.name.clone(),
+                    thread_id: member.thread_id,
+                    role: member.role.clone(),
+                    status: external_status(&current),
+                },
+                previous_status: Some(external_status(&previous)),
+            }),
+        )
+        .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn text_result(text: &str, is_error: bool) -> CallToolResult {
+        CallToolResult {
+            content: vec![json!({ "type": "text", "text": text })],
+            structured_content: None,
+            is_error: Some(is_error),
+            meta: None,
+        }
+    }
+
+    #[test]
+    fn reads_structured_json_and_plain_replies() {
+        let structured = CallToolResult {
+            content: Vec::new(),
+            structured_content: Some(json!({
+                "reply": "Done with the outline.",
+                "task_updates": [
+                    { "task_id": "task-1", "status": "completed", "result": "outline.md" }
+                ],
+            })),
+            is_error: None,
+            meta: None,
+        };
+        assert_eq!(
+            ExternalMemberReply::from_tool_result(&structured),
+            Ok(ExternalMemberReply {
+                reply: Some("Done with the outline.".to_string()),
+                task_updates: vec![ExternalTaskUpdate {
+                    task_id: "task-1".to_string(),
+                    status: TeamTaskStatus::Completed,
+                    result: Some("outline.md".to_string()),
+                }],
+            })
+        );
+
+        let json_text = text_result(
+            r#"{"task_updates":[{"task_id":"task-2","status":"in_progress"}]}"#,
+            false,
+        );
+        assert_eq!(
+            ExternalMemberReply::from_tool_result(&json_text),
+            Ok(ExternalMemberReply {
+                reply: None,
+                task_updates: vec![ExternalTaskUpdate {
+                    task_id: "task-2".to_string(),
+                    status: TeamTaskStatus::InProgress,
+                    result: None,
+                }],
+            })
+        );
+
+        assert_eq!(
+            ExternalMemberReply::from_tool_result(&text_result("On it.", false)),
+            Ok(ExternalMemberReply {
+                reply: Some("On it.".to_string()),
+                task_updates: Vec::new(),
+            })
+        );
+        assert_eq!(
+            ExternalMemberReply::from_tool_result(&text_result("quota exceeded", true)),
+            Err("quota exceeded".to_string())
+        );
+    }
+}
@@ -0,0 +1,77 @@
+//! Background garbage collection of orphaned teams.
+//!
+//! A crashed leader session leaves its team directory and task list on disk
+//! forever. Root sessions periodically archive teams whose data has not been
+//! touched for [`ORPHANED_TEAM_MAX_AGE`] and whose leader is gone: its leader
+//! lock has gone stale, the process that held it has exited, and its thread
+//! is no longer known to this process.
+
+use crate::agent::AgentStatus;
+use crate::codex::Session;
+use crate::teams::task_list::TaskList;
+use crate::teams::team_manager::{GcAction, TeamManager};
+use crate::teams::{default_tasks_root, default_teams_root};
+use codex_protocol::protocol::SessionSource;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Teams untouched for longer than this are eligible for collection.
+pub(crate) const ORPHANED_TEAM_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// How often a running session re-checks for orphaned teams.
+const TEAM_GC_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Start the periodic orphaned-team collection for a root session.
+///
+/// The first pass runs immediately; subsequent passes run every
+/// [`TEAM_GC_INTERVAL`] until the session is dropped. Subagent sessions are
+/// skipped so only one collector runs per user session.
+pub(crate) fn start_team_gc_task(session: &Arc<Session>, source: &SessionSource) {
+    if matches!(source, SessionSource::SubAgent(_)) {
+        return;
+    }
+
+    let weak_session = Arc::downgrade(session);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(TEAM_GC_INTERVAL);
+        loop {
+            interval.tick().await;
+            let Some(session) = weak_session.upgrade() else {
+                return;
+            };
+            collect_orphaned_teams(&session).await;
+        }
+    });
+}
+
+async fn collect_orphaned_teams(session: &Session) {
+    let mgr = TeamManager::new(default_teams_root());
+    let agent_control = &session.services.agent_control;
+    let collected = match mgr
+        .gc(
+            ORPHANED_TEAM_MAX_AGE,
+            GcAction::Archive,
+            |leader| async move {
+                !matches!(
+                    agent_control.get_status(leader).await,
+                    AgentStatus::NotFound
+                )
+            },
+        )
+        .await
+    {
+        Ok(collected) => collected,
+        Err(e) => {
+            tracing::warn!("failed to collect orphaned teams: {e}");
+            return;
+        }
+    };
+
+    let tl = TaskList::new(default_tasks_root());
+    for name in collected {
+        tracing::info!("archived orphaned team {name}");
+        if let Err(e) = tl.archive(&name).await {
+            tracing::warn!("failed to archive task list for orphaned team {name}: {e}");
+        }
+    }
+}
@@ -0,0 +1,584 @@
+//! Team plans bootstrapped from GitHub.
+//!
+//! `codex team import` reads the open issues of a GitHub milestone, or the
+//! open issues and draft items of a GitHub Project, and turns them into a
+//! [`TeamPlan`] that `codex team run` can start from. Each item becomes a
+//! task, in the order of the milestone or the board. A task goes to the
+//! teammate its first assignee's login is mapped to, or else to the teammate
+//! its first mapped label is, or else to a teammate named after its first
+//! assignee; items without either stay unassigned. Each teammate a task went
+//! to becomes a member of the plan.
+
+use crate::config::types::TeamGitHubIssues;
+use crate::default_client::build_reqwest_client;
+use crate::teams::github_issues::DEFAULT_API_URL;
+use crate::teams::github_issues::DEFAULT_TOKEN_ENV_VAR;
+use crate::teams::github_issues::GITHUB_TIMEOUT;
+use crate::teams::plan::validate_plan;
+use codex_protocol::protocol::TeamDisplayMode;
+use codex_protocol::protocol::TeamPlan;
+use codex_protocol::protocol::TeamPlanMember;
+use codex_protocol::protocol::TeamPlanTask;
+use serde_json::Value;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Issues fetched per page of the REST and GraphQL APIs.
+const PAGE_SIZE: usize = 100;
+
+const PROJECT_ITEMS_QUERY: &str = r#"
+query($owner: String!, $number: Int!, $cursor: String) {
+  repositoryOwner(login: $owner) {
+    ... on ProjectV2Owner {
+      projectV2(number: $number) {
+        title
+        items(first: 100, after: $cursor) {
+          pageInfo { hasNextPage endCursor }
+          nodes {
+            content {
+              __typename
+              ... on Issue {
+                number
+                title
+                body
+                url
+                state
+                repository { nameWithOwner }
+                assignees(first: 10) { nodes { login } }
+                labels(first: 20) { nodes { name } }
+              }
+              ... on DraftIssue {
+                title
+                body
+                assignees(first: 10) { nodes { login } }
+              }
+            }
+          }
+        }
+      }
+    }
+  }
+}
+"#;
+
+/// Where on GitHub a plan is imported from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GitHubPlanSource {
+    /// Milestone `number` of the repository `repo`, as `owner/name`.
+    Milestone { repo: String, number: u64 },
+    /// Project `number` of the user or organization `owner`.
+    Project { owner: String, number: u64 },
+}
+
+impl GitHubPlanSource {
+    /// Parse the web URL of a milestone, e.g.
+    /// `https://github.com/acme/widgets/milestone/3`, or of a project, e.g.
+    /// `https://github.com/orgs/acme/projects/5`.
+    pub fn parse(url: &str) -> Result<Self, String> {
+        let invalid = || {
+            format!(
+                "'{url}' is not the URL of a GitHub milestone (…/OWNER/REPO/milestone/N) \
+                 or project (…/orgs/OWNER/projects/N or …/users/OWNER/projects/N)"
+            )
+        };
+        let parsed = url::Url::parse(url).map_err(|_| invalid())?;
+        let segments: Vec<&str> = parsed
+            .path_segments()
+            .map(|segments| segments.filter(|s| !s.is_empty()).collect())
+            .unwrap_or_default();
+        let number = |s: &str| s.parse::<u64>().map_err(|_| invalid());
+        match segments.as_slice() {
+            ["orgs" | "users", owner, "projects", n, ..] => Ok(Self::Project {
+                owner: owner.to_string(),
+                number: number(n)?,
+            }),
+            [owner, repo, "milestone", n, ..] => Ok(Self::Milestone {
+                repo: format!("{owner}/{repo}"),
+                number: number(n)?,
+            }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl fmt::Display for GitHubPlanSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Milestone { repo, number } => write!(f, "milestone {number} of {repo}"),
+            Self::Project { owner, number } => write!(f, "project {number} of {owner}"),
+        }
+    }
+}
+
+/// How imported items are handed out to teammates.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GitHubPlanMapping {
+    /// Teammate of each GitHub login.
+    pub assignees: BTreeMap<String, String>,
+    /// Teammate of each label.
+    pub labels: BTreeMap<String, String>,
+}
+
+/// An open issue or draft item to import.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ImportedItem {
+    /// `owner/name#number` of an issue, or `None` for a draft.
+    reference: Option<String>,
+    title: String,
+    body: String,
+    url: Option<String>,
+    assignees: Vec<String>,
+    labels: Vec<String>,
+}
+
+/// Reads milestones and projects from the GitHub API.
+pub struct GitHubPlanImporter {
+    client: reqwest::Client,
+    api_url: String,
+    token: String,
+}
+
+impl GitHubPlanImporter {
+    /// An importer using the API and token of `settings`, the team's
+    /// `github_issues` entry, or the defaults without one.
+    pub fn new(settings: Option<&TeamGitHubIssues>) -> Result<Self, String> {
+        let token_env_var = settings
+            .and_then(|settings| settings.token_env_var.as_deref())
+            .unwrap_or(DEFAULT_TOKEN_ENV_VAR);
+        let token =
+            std::env::var(token_env_var).map_err(|_| format!("{token_env_var} is not set"))?;
+        let api_url = settings
+            .and_then(|settings| settings.api_url.as_deref())
+            .unwrap_or(DEFAULT_API_URL);
+        Ok(Self::with_token(api_url, token))
+    }
+
+    fn with_token(api_url: &str, token: String) -> Self {
+        Self {
+            client: build_reqwest_client(),
+            api_url: api_url.trim_end_matches('/').to_string(),
+            token,
+        }
+    }
+
+    /// The plan of team `team_name` made of the open items of `source`.
+    pub async fn import(
+        &self,
+        team_name: &str,
+        source: &GitHubPlanSource,
+        mapping: &GitHubPlanMapping,
+    ) -> Result<TeamPlan, String> {
+        let (title, items) = match source {
+            GitHubPlanSource::Milestone { repo, number } => {
+                self.milestone_items(repo, *number).await?
+            }
+            GitHubPlanSource::Project { owner, number } => {
+                self.project_items(owner, *number).await?
+            }
+        };
+        build_plan(team_name, &title, source, items, mapping)
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<Value, String> {
+        let response = request
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .timeout(GITHUB_TIMEOUT)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            return Err(format!("GitHub answered {status}: {body}"));
+        }
+        serde_json::from_str(&body).map_err(|e| format!("unexpected GitHub response: {e}"))
+    }
+
+    /// The title of the milestone and its open issues, oldest first. Pull
+    /// requests in the milestone are left out.
+    async fn milestone_items(
+        &self,
+        repo: &str,
+        number: u64,
+    ) -> Result<(String, Vec<ImportedItem>), String> {
+        let repo_url = format!("{}/repos/{repo}", self.api_url);
+        let milestone = self
+            .send(self.client.get(format!("{repo_url}/milestones/{number}")))
+            .await?;
+        let title = milestone["title"].as_str().unwrap_or_default().to_string();
+
+        let mut items = Vec::new();
+        for page in 1.. {
+            let request = self.client.get(format!("{repo_url}/issues")).query(&[
+                ("milestone", number.to_string()),
+                ("state", "open".to_string()),
+                ("sort", "created".to_string()),
+                ("direction", "asc".to_string()),
+                ("per_page", PAGE_SIZE.to_string()),
+                ("page", page.to_string()),
+            ]);
+            let issues = self.send(request).await?;
+            let issues = issues.as_array().cloned().unwrap_or_default();
+            items.extend(
+                issues
+                    .iter()
+                    .filter(|issue| issue.get("pull_request").is_none())
+                    .map(|issue| ImportedItem {
+                        reference: issue["number"]
+                            .as_u64()
+                            .map(|number| format!("{repo}#{number}")),
+                        title: issue["title"].as_str().unwrap_or_default().to_string(),
+                        body: issue["body"].as_str().unwrap_or_default().to_string(),
+                        url: issue["html_url"].as_str().map(str::to_string),
+                        assignees: names(&issue["assignees"], "login"),
+                        labels: names(&issue["labels"], "name"),
+                    }),
+            );
+            if issues.len() < PAGE_SIZE {
+                break;
+            }
+        }
+        Ok((title, items))
+    }
+
+    /// The title of the project and its open issues and draft items, in the
+    /// board's order. Pull requests and closed issues are left out.
+    async fn project_items(
+        &self,
+        owner: &str,
+        number: u64,
+    ) -> Result<(String, Vec<ImportedItem>), String> {
+        // GitHub Enterprise serves GraphQL at `/api/graphql` next to the REST
+        // API's `/api/v3`.
+        let graphql_url = match self.api_url.strip_suffix("/v3") {
+            Some(api_root) => format!("{api_root}/graphql"),
+            None => format!("{}/graphql", self.api_url),
+        };
+        let mut title;
+        let mut items = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let query = json!({
+                "query": PROJECT_ITEMS_QUERY,
+                "variables": { "owner": owner, "number": number, "cursor": cursor },
+            });
+            let response = self
+                .send(self.client.post(&graphql_url).json(&query))
+                .await?;
+            if let Some(errors) = response.get("errors") {
+                return Err(format!("GitHub answered with errors: {errors}"));
+            }
+            let project = &response["data"]["repositoryOwner"]["projectV2"];
+            if project.is_null() {
+                return Err(format!("no project {number} of {owner} found"));
+            }
+            title = project["title"].as_str().unwrap_or_default().to_string();
+            let page = &project["items"];
+            for content in page["nodes"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|node| &node["content"])
+            {
+                let reference = match content["__typename"].as_str() {
+                    Some("Issue") if content["state"] == "OPEN" => {
+                        let repo = content["repository"]["nameWithOwner"]
+                            .as_str()
+                            .unwrap_or_default();
+                        content["number"]
+                            .as_u64()
+                            .map(|number| format!("{repo}#{number}"))
+                    }
+                    Some("DraftIssue") => None,
+                    _ => continue,
+                };
+                items.push(ImportedItem {
+                    reference,
+                    title: content["title"].as_str().unwrap_or_default().to_string(),
+                    body: content["body"].as_str().unwrap_or_default().to_string(),
+                    url: content["url"].as_str().map(str::to_string),
+                    assignees: names(&content["assignees"]["nodes"], "login"),
+                    labels: names(&content["labels"]["nodes"], "name"),
+                });
+            }
+            cursor = page["pageInfo"]["endCursor"].as_str().map(str::to_string);
+            if page["pageInfo"]["hasNextPage"] != true || cursor.is_none() {
+                break;
+            }
+        }
+        Ok((title, items))
+    }
+}
+
+/// The `key` field of each object in the `list` array.
+fn names(list: &Value, key: &str) -> Vec<String> {
+    list.as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry[key].as_str().map(str::to_string))
+        .collect()
+}
+
+/// The plan with a task for each of `items` of `source`, handed out by
+/// `mapping`.
+fn build_plan(
+    team_name: &str,
+    title: &str,
+    source: &GitHubPlanSource,
+    items: Vec<ImportedItem>,
+    mapping: &GitHubPlanMapping,
+) -> Result<TeamPlan, String> {
+    let mut members: Vec<TeamPlanMember> = Vec::new();
+    let mut tasks = Vec::new();
+    let mut drafts = 0;
+    for item in items {
+        let assignee = item
+            .assignees
+            .iter()
+            .find_map(|login| mapping.assignees.get(login))
+            .or_else(|| {
+                item.labels
+                    .iter()
+                    .find_map(|label| mapping.labels.get(label))
+            })
+            .or_else(|| item.assignees.first())
+            .cloned();
+        if let Some(assignee) = &assignee
+            && !members.iter().any(|member| &member.name == assignee)
+        {
+            members.push(TeamPlanMember {
+                name: assignee.clone(),
+                role: None,
+                prompt: format!(
+                    "Work through the tasks assigned to you in the team's task list. They were \
+                     imported from GitHub {source}."
+                ),
+                max_tokens: None,
+                max_turns: None,
+            });
+        }
+        let id = match &item.reference {
+            Some(reference) => reference.clone(),
+            None => {
+                drafts += 1;
+                format!("draft-{drafts}")
+            }
+        };
+        let mut task_description = item.body.trim().to_string();
+        if let Some(url) = &item.url {
+            if !task_description.is_empty() {
+                task_description.push_str("\n\n");
+            }
+            task_description.push_str(&format!("GitHub issue: {url}"));
+        }
+        tasks.push(TeamPlanTask {
+            id,
+            title: item.title,
+            description: (!task_description.is_empty()).then_some(task_description),
+            assigned_to: assignee,
+            depends_on: Vec::new(),
+        });
+    }
+    if members.is_empty() {
+        return Err(
+            "none of the items is assigned or has a label mapped to a teammate".to_string(),
+        );
+    }
+    let description = if title.is_empty() {
+        source.to_string()
+    } else {
+        format!("{title} ({source})")
+    };
+    let plan = TeamPlan {
+        name: team_name.to_string(),
+        description: Some(description),
+        display_mode: TeamDisplayMode::default(),
+        work_stealing: false,
+        members,
+        tasks,
+    };
+    validate_plan(&plan)?;
+    Ok(plan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+    use wiremock::matchers::body_partial_json;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+    use wiremock::matchers::query_param;
+
+    #[tokio::test]
+    async fn milestones_and_projects_become_plans() {
+        assert_eq!(
+            GitHubPlanSource::parse("https://github.com/acme/widgets/milestone/3"),
+            Ok(GitHubPlanSource::Milestone {
+                repo: "acme/widgets".to_string(),
+                number: 3,
+            })
+        );
+        assert_eq!(
+            GitHubPlanSource::parse("https://github.com/orgs/acme/projects/5/views/1"),
+            Ok(GitHubPlanSource::Project {
+                owner: "acme".to_string(),
+                number: 5,
+            })
+        );
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/widgets/milestones/3"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "title": "v2.0" })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/widgets/issues"))
+            .and(query_param("milestone", "3"))
+            .and(query_param("state", "open"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                {
+                    "number": 7,
+                    "title": "Review the API",
+                    "body": "Check the new endpoints.",
+                    "html_url": "https://github.com/acme/widgets/issues/7",
+                    "assignees": [{ "login": "octocat" }],
+                    "labels": [],
+                },
+                {
+                    "number": 8,
+                    "title": "Bump the version",
+                    "body": null,
+                    "html_url": "https://github.com/acme/widgets/pull/8",
+                    "assignees": [],
+                    "labels": [],
+                    "pull_request": {},
+                },
+                {
+                    "number": 9,
+                    "title": "Update the guide",
+                    "body": null,
+                    "html_url": "https://github.com/acme/widgets/issues/9",
+                    "assignees": [],
+                    "labels": [{ "name": "docs" }],
+                },
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/graphql"))
+            .and(body_partial_json(json!({
+                "variables": { "owner": "acme", "number": 5 },
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": { "repositoryOwner": { "projectV2": {
+                    "title": "Launch",
+                    "items": {
+                        "pageInfo": { "hasNextPage": false, "endCursor": "c1" },
+                        "nodes": [
+                            { "content": {
+                                "__typename": "DraftIssue",
+                                "title": "Write the announcement",
+                                "body": "",
+                                "assignees": { "nodes": [{ "login": "hubot" }] },
+                            } },
+                            { "content": {
+                                "__typename": "Issue",
+                                "number": 2,
+                                "title": "Fixed already",
+                                "body": "",
+                                "url": "https://github.com/acme/site/issues/2",
+                                "state": "CLOSED",
+                                "repository": { "nameWithOwner": "acme/site" },
+                                "assignees": { "nodes": [] },
+                                "labels": { "nodes": [] },
+                            } },
+                        ],
+                    },
+                } } },
+            })))
+            .mount(&server)
+            .await;
+        let importer = GitHubPlanImporter::with_token(&server.uri(), "token".to_string());
+        let mapping = GitHubPlanMapping {
+            assignees: BTreeMap::from([("octocat".to_string(), "reviewer".to_string())]),
+            labels: BTreeMap::from([("docs".to_string(), "writer".to_string())]),
+        };
+        let member = |name: &str, source: &GitHubPlanSource| TeamPlanMember {
+            name: name.to_string(),
+            role: None,
+            prompt: format!(
+                "Work through the tasks assigned to you in the team's task list. They were \
+                 imported from GitHub {source}."
+            ),
+            max_tokens: None,
+            max_turns: None,
+        };
+
+        let milestone = GitHubPlanSource::Milestone {
+            repo: "acme/widgets".to_string(),
+            number: 3,
+        };
+        assert_eq!(
+            importer.import("v2", &milestone, &mapping).await,
+            Ok(TeamPlan {
+                name: "v2".to_string(),
+                description: Some("v2.0 (milestone 3 of acme/widgets)".to_string()),
+                display_mode: TeamDisplayMode::Inline,
+                work_stealing: false,
+                members: vec![member("reviewer", &milestone), member("writer", &milestone)],
+                tasks: vec![
+                    TeamPlanTask {
+                        id: "acme/widgets#7".to_string(),
+                        title: "Review the API".to_string(),
+                        description: Some(
+                            "Check the new endpoints.\n\n\
+                             GitHub issue: https://github.com/acme/widgets/issues/7"
+                                .to_string()
+                        ),
+                        assigned_to: Some("reviewer".to_string()),
+                        depends_on: Vec::new(),
+                    },
+                    TeamPlanTask {
+                        id: "acme/widgets#9".to_string(),
+                        title: "Update the guide".to_string(),
+                        description: Some(
+                            "GitHub issue: https://github.com/acme/widgets/issues/9".to_string()
+                        ),
+                        assigned_to: Some("writer".to_string()),
+                        depends_on: Vec::new(),
+                    },
+                ],
+            })
+        );
+
+        let project = GitHubPlanSource::Project {
+            owner: "acme".to_string(),
+            number: 5,
+        };
+        assert_eq!(
+            importer.import("launch", &project, &mapping).await,
+            Ok(TeamPlan {
+                name: "launch".to_string(),
+                description: Some("Launch (project 5 of acme)".to_string()),
+                display_mode: TeamDisplayMode::Inline,
+                work_stealing: false,
+                members: vec![member("hubot", &project)],
+                tasks: vec![TeamPlanTask {
+                    id: "draft-1".to_string(),
+                    title: "Write the announcement".to_string(),
+                    description: None,
+                    assigned_to: Some("hubot".to_string()),
+                    depends_on: Vec::new(),
+                }],
+            })
+        );
+    }
+}
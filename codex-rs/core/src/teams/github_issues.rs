@@ -0,0 +1,246 @@
+//! GitHub issues as a team task tracker.
+//!
+//! With a `teams.github_issues` entry for a team, each task gets an issue in
+//! the entry's repository with the entry's labels, assigned to the GitHub
+//! login of the task's assignee, and is closed once the task is completed.
+//! Comments on the issues can be handed on as team messages (see
+//! [`crate::teams::task_sync`]).
+
+use crate::config::types::TeamGitHubIssues;
+use crate::default_client::build_reqwest_client;
+use crate::teams::task_sync::ExternalComment;
+use crate::teams::task_sync::TaskSyncAdapter;
+use async_trait::async_trait;
+use codex_protocol::protocol::TeamTaskInfo;
+use serde::Deserialize;
+use serde_json::Value;
+use serde_json::json;
+use std::time::Duration;
+
+pub(crate) const DEFAULT_API_URL: &str = "https://api.github.com";
+pub(crate) const DEFAULT_TOKEN_ENV_VAR: &str = "GITHUB_TOKEN";
+
+/// How long a GitHub API request may take.
+pub(crate) const GITHUB_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// A comment on an issue, as the GitHub API lists it.
+#[derive(Debug, Deserialize)]
+struct IssueComment {
+    id: u64,
+    issue_url: String,
+    body: String,
+    user: CommentAuthor,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommentAuthor {
+    login: String,
+}
+
+/// The issues of one GitHub repository. Items are identified by their issue
+/// number.
+pub(crate) struct GitHubIssuesAdapter {
+    client: reqwest::Client,
+    repo_url: String,
+    token: String,
+    labels: Vec<String>,
+}
+
+impl GitHubIssuesAdapter {
+    pub(crate) fn new(settings: &TeamGitHubIssues) -> Result<Self, String> {
+        let token_env_var = settings
+            .token_env_var
+            .as_deref()
+            .unwrap_or(DEFAULT_TOKEN_ENV_VAR);
+        let token =
+            std::env::var(token_env_var).map_err(|_| format!("{token_env_var} is not set"))?;
+        let api_url = settings.api_url.as_deref().unwrap_or(DEFAULT_API_URL);
+        Ok(Self::with_token(settings, api_url, token))
+    }
+
+    fn with_token(settings: &TeamGitHubIssues, api_url: &str, token: String) -> Self {
+        Self {
+            client: build_reqwest_client(),
+            repo_url: format!("{}/repos/{}", api_url.trim_end_matches('/'), settings.repo),
+            token,
+            labels: settings.labels.clone(),
+        }
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<Value, String> {
+        let response = request
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .timeout(GITHUB_TIMEOUT)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            return Err(format!("GitHub answered {status}: {body}"));
+        }
+        serde_json::from_str(&body).map_err(|e| format!("unexpected GitHub response: {e}"))
+    }
+
+    async fn update_issue(&self, number: &str, changes: &Value) -> Result<(), String> {
+        let url = format!("{}/issues/{number}", self.repo_url);
+        self.send(self.client.patch(url).json(changes))
+            .await
+            .map(drop)
+    }
+}
+
+#[async_trait]
+impl TaskSyncAdapter for GitHubIssuesAdapter {
+    fn tracker(&self) -> &'static str {
+        "github"
+    }
+
+    async fn create(
+        &self,
+        team_name: &str,
+        task: &TeamTaskInfo,
+        assignee: Option<&str>,
+    ) -> Result<String, String> {
+        let mut body = task.description.clone().unwrap_or_default();
+        if !body.is_empty() {
+            body.push_str("\n\n");
+        }
+        body.push_str(&format!("_Task {} of Codex team {team_name}._", task.id));
+        let issue = json!({
+            "title": task.title,
+            "body": body,
+            "labels": self.labels,
+            "assignees": assignee.into_iter().collect::<Vec<_>>(),
+        });
+        let url = format!("{}/issues", self.repo_url);
+        let created = self.send(self.client.post(url).json(&issue)).await?;
+        created["number"]
+            .as_u64()
+            .map(|number| number.to_string())
+            .ok_or_else(|| "GitHub did not return the new issue's number".to_string())
+    }
+
+    async fn update(&self, external_id: &str, assignee: Option<&str>) -> Result<(), String> {
+        let assignees = assignee.into_iter().collect::<Vec<_>>();
+        self.update_issue(external_id, &json!({ "assignees": assignees }))
+            .await
+    }
+
+    async fn complete(&self, external_id: &str) -> Result<(), String> {
+        let changes = json!({ "state": "closed", "state_reason": "completed" });
+        self.update_issue(external_id, &changes).await
+    }
+
+    async fn comments_since(&self, since: &str) -> Result<Vec<ExternalComment>, String> {
+        let url = format!("{}/issues/comments", self.repo_url);
+        let request = self.client.get(url).query(&[
+            ("since", since),
+            ("sort", "created"),
+            ("direction", "asc"),
+            ("per_page", "100"),
+        ]);
+        let comments: Vec<IssueComment> = serde_json::from_value(self.send(request).await?)
+            .map_err(|e| format!("unexpected GitHub response: {e}"))?;
+        Ok(comments
+            .into_iter()
+            .filter_map(|comment| {
+                // `issue_url` ends with the issue number.
+                let number = comment.issue_url.rsplit('/').next()?.to_string();
+                Some(ExternalComment {
+                    id: comment.id.to_string(),
+                    external_id: number,
+                    author: comment.user.login,
+                    body: comment.body,
+                })
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::TeamTaskStatus;
+    use pretty_assertions::assert_eq;
+    use std::collections::BTreeMap;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+    use wiremock::matchers::body_json;
+    use wiremock::matchers::header;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+
+    #[tokio::test]
+    async fn issues_are_opened_closed_and_commented_on() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/repos/acme/widgets/issues"))
+            .and(header("authorization", "Bearer token"))
+            .and(body_json(json!({
+                "title": "Review the API",
+                "body": "_Task task-1 of Codex team zeta._",
+                "labels": ["codex"],
+                "assignees": ["octocat"],
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({ "number": 7 })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/repos/acme/widgets/issues/7"))
+            .and(body_json(
+                json!({ "state": "closed", "state_reason": "completed" }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "number": 7 })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/widgets/issues/comments"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+                "id": 42,
+                "issue_url": format!("{}/repos/acme/widgets/issues/7", server.uri()),
+                "body": "Please also check the docs.",
+                "user": { "login": "octocat" },
+            }])))
+            .mount(&server)
+            .await;
+        let settings = TeamGitHubIssues {
+            repo: "acme/widgets".to_string(),
+            team: None,
+            labels: vec!["codex".to_string()],
+            assignees: BTreeMap::new(),
+            mirror_comments: true,
+            token_env_var: None,
+            api_url: None,
+        };
+        let github = GitHubIssuesAdapter::with_token(&settings, &server.uri(), "token".to_string());
+        let task = TeamTaskInfo {
+            id: "task-1".to_string(),
+            title: "Review the API".to_string(),
+            description: None,
+            status: TeamTaskStatus::Pending,
+            assigned_to: Some("reviewer".to_string()),
+            depends_on: Vec::new(),
+            result: None,
+            created_at: None,
+        };
+
+        let number = github.create("zeta", &task, Some("octocat")).await.unwrap();
+        assert_eq!(number, "7");
+        github.complete(&number).await.unwrap();
+        assert_eq!(
+            github.comments_since("2026-01-01T00:00:00Z").await.unwrap(),
+            vec![ExternalComment {
+                id: "42".to_string(),
+                external_id: "7".to_string(),
+                author: "octocat".to_string(),
+                body: "Please also check the docs.".to_string(),
+            }]
+        );
+    }
+}
@@ -2,8 +2,10 @@
 //!
 //! Each agent has a single JSON file (`inboxes/{name}.json`) containing an
 //! array of messages. `sendMessage` appends to the recipient's inbox;
-//! `broadcast` appends to every inbox.
+//! `broadcast` appends to every inbox. Inboxes are read and written under a
+//! file lock, since the leader's inbox in particular has many writers.
 
+use crate::teams::team_manager::edit_json_locked;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use tokio::fs;
@@ -56,9 +58,15 @@ impl Inbox {
         to: &str,
         message: InboxMessage,
     ) -> std::io::Result<()> {
-        let mut messages = self.read_inbox(to).await?;
-        messages.push(message);
-        self.write_inbox(to, &messages).await
+        edit_json_locked(
+            self.inbox_path(to),
+            "the inbox",
+            move |messages: &mut Vec<InboxMessage>| {
+                messages.push(message);
+                ((), true)
+            },
+        )
+        .await
     }
 
     /// Broadcast a message to all inboxes in the directory.
@@ -92,9 +100,10 @@ impl Inbox {
         if !path.exists() {
             return Ok(Vec::new());
         }
-        let content = fs::read_to_string(&path).await?;
-        serde_json::from_str(&content)
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+        edit_json_locked(path, "the inbox", |messages: &mut Vec<InboxMessage>| {
+            (messages.clone(), false)
+        })
+        .await
     }
 
     /// Read only unread messages and mark them as read.
@@ -102,21 +111,19 @@ impl Inbox {
         &self,
         agent_name: &str,
     ) -> std::io::Result<Vec<InboxMessage>> {
-        let mut all = self.read_inbox(agent_name).await?;
-        let unread: Vec<InboxMessage> = all
-            .iter()
-            .filter(|m| !m.read)
-            .cloned()
-            .collect();
-
-        if !unread.is_empty() {
+        let path = self.inbox_path(agent_name);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        edit_json_locked(path, "the inbox", |all: &mut Vec<InboxMessage>| {
+            let unread: Vec<InboxMessage> = all.iter().filter(|m| !m.read).cloned().collect();
             for msg in all.iter_mut() {
                 msg.read = true;
             }
-            self.write_inbox(agent_name, &all).await?;
-        }
-
-        Ok(unread)
+            let changed = !unread.is_empty();
+            (unread, changed)
+        })
+        .await
     }
 
     /// Format unread messages as `<teammate-message>` tags for injection into
@@ -143,17 +150,6 @@ impl Inbox {
         Ok(Some(tags.join("\n\n")))
     }
 
-    /// Write messages to an agent's inbox.
-    async fn write_inbox(
-        &self,
-        agent_name: &str,
-        messages: &[InboxMessage],
-    ) -> std::io::Result<()> {
-        let json = serde_json::to_string_pretty(messages)
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
-        fs::write(self.inbox_path(agent_name), json).await
-    }
-
     /// List all agents that have inboxes.
     async fn list_agents(&self) -> std::io::Result<Vec<String>> {
         let mut agents = Vec::new();
@@ -196,6 +192,34 @@ mod tests {
         assert!(!messages[0].read);
     }
 
+    #[tokio::test]
+    async fn concurrent_sends_keep_every_message() {
+        let tmp = TempDir::new().unwrap();
+        let inbox = std::sync::Arc::new(Inbox::new(tmp.path().to_path_buf()));
+        inbox.init().await.unwrap();
+        inbox.create_inbox("leader").await.unwrap();
+
+        let handles: Vec<_> = (0..16)
+            .map(|i| {
+                let inbox = std::sync::Arc::clone(&inbox);
+                tokio::spawn(async move {
+                    let msg = InboxMessage {
+                        from: format!("teammate-{i}"),
+                        timestamp: "2026-01-01T00:00:00Z".to_string(),
+                        content: format!("message {i}"),
+                        read: false,
+                    };
+                    inbox.send_message("leader", msg).await
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.unwrap().unwrap();
+        }
+
+        assert_eq!(inbox.read_inbox("leader").await.unwrap().len(), 16);
+    }
+
     #[tokio::test]
     async fn consume_unread_marks_as_read() {
         let tmp = TempDir::new().unwrap();
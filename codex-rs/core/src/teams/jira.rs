@@ -0,0 +1,268 @@
+//! Jira issues as a team task tracker.
+//!
+//! With a `teams.jira` entry for a team, each task gets an issue in the
+//! entry's project, assigned to the Jira account of the task's assignee, and
+//! is moved through the entry's done transition once the task is completed.
+//! Built with the `jira` feature.
+
+use crate::config::types::TeamJiraSync;
+use crate::default_client::build_reqwest_client;
+use crate::teams::task_sync::TaskSyncAdapter;
+use async_trait::async_trait;
+use codex_protocol::protocol::TeamTaskInfo;
+use serde_json::Value;
+use serde_json::json;
+use std::time::Duration;
+
+const DEFAULT_TOKEN_ENV_VAR: &str = "JIRA_API_TOKEN";
+const DEFAULT_ISSUE_TYPE: &str = "Task";
+const DEFAULT_DONE_TRANSITION: &str = "Done";
+
+/// How long a Jira API request may take.
+const JIRA_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// The issues of one Jira project. Items are identified by their issue key,
+/// e.g. `WID-12`.
+pub(crate) struct JiraAdapter {
+    client: reqwest::Client,
+    api_url: String,
+    email: String,
+    token: String,
+    project_key: String,
+    issue_type: String,
+    labels: Vec<String>,
+    done_transition: String,
+}
+
+impl JiraAdapter {
+    pub(crate) fn new(settings: &TeamJiraSync) -> Result<Self, String> {
+        let token_env_var = settings
+            .token_env_var
+            .as_deref()
+            .unwrap_or(DEFAULT_TOKEN_ENV_VAR);
+        let token =
+            std::env::var(token_env_var).map_err(|_| format!("{token_env_var} is not set"))?;
+        Ok(Self::with_token(settings, token))
+    }
+
+    fn with_token(settings: &TeamJiraSync, token: String) -> Self {
+        Self {
+            client: build_reqwest_client(),
+            api_url: format!("{}/rest/api/3", settings.base_url.trim_end_matches('/')),
+            email: settings.email.clone(),
+            token,
+            project_key: settings.project_key.clone(),
+            issue_type: settings
+                .issue_type
+                .clone()
+                .unwrap_or_else(|| DEFAULT_ISSUE_TYPE.to_string()),
+            labels: settings.labels.clone(),
+            done_transition: settings
+                .done_transition
+                .clone()
+                .unwrap_or_else(|| DEFAULT_DONE_TRANSITION.to_string()),
+        }
+    }
+
+    /// Send `request`, returning the response body, or `null` for the empty
+    /// bodies of updates.
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<Value, String> {
+        let response = request
+            .basic_auth(&self.email, Some(&self.token))
+            .header("Accept", "application/json")
+            .timeout(JIRA_TIMEOUT)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            return Err(format!("Jira answered {status}: {body}"));
+        }
+        if body.is_empty() {
+            return Ok(Value::Null);
+        }
+        serde_json::from_str(&body).map_err(|e| format!("unexpected Jira response: {e}"))
+    }
+}
+
+#[async_trait]
+impl TaskSyncAdapter for JiraAdapter {
+    fn tracker(&self) -> &'static str {
+        "jira"
+    }
+
+    async fn create(
+        &self,
+        team_name: &str,
+        task: &TeamTaskInfo,
+        assignee: Option<&str>,
+    ) -> Result<String, String> {
+        let mut texts: Vec<_> = task
+            .description
+            .iter()
+            .filter(|description| !description.is_empty())
+            .cloned()
+            .collect();
+        texts.push(format!("Task {} of Codex team {team_name}.", task.id));
+        // Jira Cloud takes descriptions as Atlassian Document Format.
+        let paragraphs: Vec<_> = texts
+            .into_iter()
+            .map(|text| {
+                json!({
+                    "type": "paragraph",
+                    "content": [{ "type": "text", "text": text }],
+                })
+            })
+            .collect();
+        let mut fields = json!({
+            "project": { "key": self.project_key },
+            "summary": task.title,
+            "issuetype": { "name": self.issue_type },
+            "labels": self.labels,
+            "description": { "type": "doc", "version": 1, "content": paragraphs },
+        });
+        if let Some(account_id) = assignee {
+            fields["assignee"] = json!({ "accountId": account_id });
+        }
+        let url = format!("{}/issue", self.api_url);
+        let created = self
+            .send(self.client.post(url).json(&json!({ "fields": fields })))
+            .await?;
+        created["key"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| "Jira did not return the new issue's key".to_string())
+    }
+
+    async fn update(&self, external_id: &str, assignee: Option<&str>) -> Result<(), String> {
+        let url = format!("{}/issue/{external_id}/assignee", self.api_url);
+        self.send(self.client.put(url).json(&json!({ "accountId": assignee })))
+            .await
+            .map(drop)
+    }
+
+    async fn complete(&self, external_id: &str) -> Result<(), String> {
+        // Transitions are per workflow, so look the done one up by name.
+        let url = format!("{}/issue/{external_id}/transitions", self.api_url);
+        let transitions = self.send(self.client.get(&url)).await?;
+        let id = transitions["transitions"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|transition| {
+                transition["name"]
+                    .as_str()
+                    .is_some_and(|name| name.eq_ignore_ascii_case(&self.done_transition))
+            })
+            .and_then(|transition| transition["id"].as_str())
+            .ok_or_else(|| {
+                format!(
+                    "issue {external_id} has no `{}` transition",
+                    self.done_transition
+                )
+            })?;
+        self.send(
+            self.client
+                .post(url)
+                .json(&json!({ "transition": { "id": id } })),
+        )
+        .await
+        .map(drop)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::TeamTaskStatus;
+    use pretty_assertions::assert_eq;
+    use std::collections::BTreeMap;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+    use wiremock::matchers::body_json;
+    use wiremock::matchers::body_partial_json;
+    use wiremock::matchers::header;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+
+    #[tokio::test]
+    async fn issues_are_created_assigned_and_transitioned() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/issue"))
+            // `bot@acme.test:token`, base64-encoded.
+            .and(header(
+                "authorization",
+                "Basic Ym90QGFjbWUudGVzdDp0b2tlbg==",
+            ))
+            .and(body_partial_json(json!({
+                "fields": {
+                    "project": { "key": "WID" },
+                    "summary": "Review the API",
+                    "issuetype": { "name": "Task" },
+                    "labels": ["codex"],
+                }
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({ "key": "WID-12" })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/rest/api/3/issue/WID-12/assignee"))
+            .and(body_json(
+                json!({ "accountId": "5b10ac8d82e05b22cc7d4ef5" }),
+            ))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/issue/WID-12/transitions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "transitions": [
+                    { "id": "11", "name": "In Progress" },
+                    { "id": "31", "name": "Done" },
+                ]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/issue/WID-12/transitions"))
+            .and(body_json(json!({ "transition": { "id": "31" } })))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let settings = TeamJiraSync {
+            team: None,
+            base_url: server.uri(),
+            project_key: "WID".to_string(),
+            issue_type: None,
+            email: "bot@acme.test".to_string(),
+            token_env_var: None,
+            labels: vec!["codex".to_string()],
+            assignees: BTreeMap::new(),
+            done_transition: None,
+        };
+        let jira = JiraAdapter::with_token(&settings, "token".to_string());
+        let task = TeamTaskInfo {
+            id: "task-1".to_string(),
+            title: "Review the API".to_string(),
+            description: None,
+            status: TeamTaskStatus::Pending,
+            assigned_to: None,
+            depends_on: Vec::new(),
+            result: None,
+            created_at: None,
+        };
+
+        let key = jira.create("zeta", &task, None).await.unwrap();
+        assert_eq!(key, "WID-12");
+        jira.update(&key, Some("5b10ac8d82e05b22cc7d4ef5"))
+            .await
+            .unwrap();
+        jira.complete(&key).await.unwrap();
+    }
+}
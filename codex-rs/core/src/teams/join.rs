@@ -0,0 +1,185 @@
+//! Joining a team led by another Codex process.
+//!
+//! A session started with `teams.join` (`codex team join <team> --as
+//! <name>`) registers itself on disk as a member of the team, marked
+//! `joined`, and keeps a lease in the team directory renewed with the status
+//! of its session. The leader reads a joined member's status from that lease
+//! and presumes the member gone once it goes stale. Whatever lands in the
+//! member's inbox, messages and task assignments alike, is handed to the
+//! session as its next input whenever it is idle, and the member works its
+//! tasks with the usual teammate tools. The loop ends once the member is shut
+//! down, the team is cleaned up, or the session is dropped.
+
+use crate::agent::AgentStatus;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::config::types::TeamJoin;
+use crate::config::types::TeamsConfig;
+use crate::teams::completion::LEADER_INBOX;
+use crate::teams::inbox::InboxMessage;
+use crate::teams::team_manager::MemberConfig;
+use codex_protocol::protocol::{
+    EventMsg, SessionSource, TeamMemberEvent, TeamMemberInfo, WarningEvent,
+};
+use codex_protocol::user_input::UserInput;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Member status stored for a joined member, whose real status is in its
+/// lease.
+const JOINED_STATUS: &str = "joined";
+
+/// How often the lease is renewed and the inbox checked.
+const JOIN_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Join the team of `teams.join`, if set, for a root session.
+pub(crate) fn start_team_join(session: &Arc<Session>, teams: &TeamsConfig, source: &SessionSource) {
+    let Some(join) = teams.join.clone() else {
+        return;
+    };
+    if matches!(source, SessionSource::SubAgent(_)) {
+        return;
+    }
+
+    let weak_session = Arc::downgrade(session);
+    tokio::spawn(async move {
+        let Some(session) = weak_session.upgrade() else {
+            return;
+        };
+        let turn = session.new_default_turn().await;
+        if let Err(e) = join_team(&session, &turn, &join).await {
+            let message = format!("failed to join team '{}' as {}: {e}", join.team, join.name);
+            session
+                .send_event(&turn, EventMsg::Warning(WarningEvent { message }))
+                .await;
+            return;
+        }
+        drop(session);
+        loop {
+            tokio::time::sleep(JOIN_POLL_INTERVAL).await;
+            let Some(session) = weak_session.upgrade() else {
+                return;
+            };
+            if !keep_membership(&session, &turn, &join).await {
+                return;
+            }
+        }
+    });
+}
+
+async fn join_team(session: &Session, turn: &TurnContext, join: &TeamJoin) -> std::io::Result<()> {
+    let teams = &session.services.teams;
+    let member = MemberConfig {
+        name: join.name.clone(),
+        thread_id: session.conversation_id,
+        role: join.role.clone(),
+        status: JOINED_STATUS.to_string(),
+        prompt: None,
+        sandbox_policy: None,
+        budget: None,
+        timeout_minutes: None,
+        max_restarts: None,
+        restarts: 0,
+        worktree: None,
+        cwd: None,
+        mcp: None,
+        a2a: None,
+        ssh: None,
+        joined: true,
+    };
+    teams.manager().join_team(&join.team, member).await?;
+    teams
+        .inbox(&join.team)
+        .send_message(
+            LEADER_INBOX,
+            InboxMessage {
+                from: join.name.clone(),
+                timestamp: chrono::Utc::now().to_rfc3339(),
+                content: format!("{} joined the team from another Codex process.", join.name),
+                read: false,
+            },
+        )
+        .await?;
+    session
+        .send_event(
+            turn,
+            EventMsg::TeamMemberAdded(TeamMemberEvent {
+                team_name: join.team.clone(),
+                member: TeamMemberInfo {
+                    name: join.name.clone(),
+                    thread_id: session.conversation_id,
+                    role: join.role.clone(),
+                    status: AgentStatus::PendingInit,
+                },
+                previous_status: None,
+            }),
+        )
+        .await;
+    Ok(())
+}
+
+/// Renew the lease and hand over unread inbox messages. Returns whether the
+/// session is still a member.
+async fn keep_membership(session: &Session, turn: &TurnContext, join: &TeamJoin) -> bool {
+    let teams = &session.services.teams;
+    let manager = teams.manager();
+    let thread_id = session.conversation_id;
+    let still_member = teams.load_config(&join.team).await.is_ok_and(|config| {
+        config
+            .members
+            .iter()
+            .any(|m| m.name == join.name && m.thread_id == thread_id)
+    });
+    if !still_member {
+        // The name may have been taken over by a later join, with a lease of
+        // its own.
+        if let Ok(Some(lease)) = manager.member_lease(&join.team, &join.name).await
+            && lease.thread_id == thread_id
+            && let Err(e) = manager.release_member_lease(&join.team, &join.name).await
+        {
+            tracing::warn!("failed to release the lease of {}: {e}", join.name);
+        }
+        session
+            .send_event(
+                turn,
+                EventMsg::TeamMemberRemoved(TeamMemberEvent {
+                    team_name: join.team.clone(),
+                    member: TeamMemberInfo {
+                        name: join.name.clone(),
+                        thread_id,
+                        role: join.role.clone(),
+                        status: AgentStatus::Shutdown,
+                    },
+                    previous_status: None,
+                }),
+            )
+            .await;
+        return false;
+    }
+
+    let agent_control = &session.services.agent_control;
+    let status = agent_control.get_status(thread_id).await;
+    if let Err(e) = manager
+        .renew_member_lease(&join.team, &join.name, thread_id, &status)
+        .await
+    {
+        tracing::warn!("failed to renew the lease of {}: {e}", join.name);
+    }
+    if matches!(status, AgentStatus::PendingInit | AgentStatus::Running) {
+        return true;
+    }
+    match teams.inbox(&join.team).consume_as_tags(&join.name).await {
+        Ok(Some(text)) => {
+            let items = vec![UserInput::Text {
+                text,
+                text_elements: Vec::new(),
+            }];
+            if let Err(e) = agent_control.send_input(thread_id, items).await {
+                tracing::warn!("failed to hand team messages to {}: {e}", join.name);
+            }
+        }
+        Ok(None) => {}
+        Err(e) => tracing::debug!("failed to read the inbox of {}: {e}", join.name),
+    }
+    true
+}
@@ -0,0 +1,129 @@
+//! Leader mail.
+//!
+//! Teammates write to the leader's inbox from their own sessions, so the
+//! leader's session never hears of those messages until the leader model
+//! reads its inbox. Every team gets a background loop that polls the leader's
+//! inbox and emits each newly arrived message as a `TeamMessageSent` event in
+//! the leader's session, so UIs can flag questions and shutdown requests
+//! addressed to the leader as they arrive. The same loop watches the team's
+//! escalations and emits a `TeamEscalationRequested` event for each
+//! unanswered one it has not reported yet.
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::teams::completion::LEADER_INBOX;
+use crate::teams::inbox::InboxMessage;
+use codex_protocol::protocol::{EventMsg, TeamEscalationEvent, TeamMessageEvent};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How often the leader's inbox is re-checked.
+const LEADER_MAIL_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Start forwarding messages that arrive in the leader's inbox of `team_name`,
+/// and the team's unanswered escalations. Messages already there are not
+/// forwarded. The loop runs until the team is cleaned up, leadership moves to
+/// another thread, or the leader session is dropped.
+pub(crate) fn forward_leader_mail(
+    session: &Arc<Session>,
+    turn: Arc<TurnContext>,
+    team_name: String,
+) {
+    let weak_session = Arc::downgrade(session);
+    tokio::spawn(async move {
+        let mut seen: Option<usize> = None;
+        let mut reported_escalations: HashSet<String> = HashSet::new();
+        let mut interval = tokio::time::interval(LEADER_MAIL_POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            let Some(session) = weak_session.upgrade() else {
+                return;
+            };
+            let teams = &session.services.teams;
+            match teams.load_config(&team_name).await {
+                Ok(config) if config.leader_thread_id == session.conversation_id => {}
+                Ok(_) => return,
+                Err(e) => {
+                    tracing::debug!("stopping leader mail for {team_name}: {e}");
+                    return;
+                }
+            }
+            let messages = match teams.inbox(&team_name).read_inbox(LEADER_INBOX).await {
+                Ok(messages) => messages,
+                Err(e) => {
+                    tracing::debug!("failed to read leader inbox of {team_name}: {e}");
+                    continue;
+                }
+            };
+            let arrived = seen.map_or(&[][..], |seen| new_messages(seen, &messages));
+            for message in arrived {
+                session
+                    .send_event(
+                        &turn,
+                        EventMsg::TeamMessageSent(TeamMessageEvent {
+                            team_name: team_name.clone(),
+                            from: message.from.clone(),
+                            to: LEADER_INBOX.to_string(),
+                            content: message.content.clone(),
+                        }),
+                    )
+                    .await;
+            }
+            seen = Some(messages.len());
+
+            let escalations = match teams.manager().escalations(&team_name).await {
+                Ok(escalations) => escalations,
+                Err(e) => {
+                    tracing::debug!("failed to read escalations of {team_name}: {e}");
+                    continue;
+                }
+            };
+            for escalation in escalations.pending() {
+                if !reported_escalations.insert(escalation.id.clone()) {
+                    continue;
+                }
+                session
+                    .send_event(
+                        &turn,
+                        EventMsg::TeamEscalationRequested(TeamEscalationEvent {
+                            team_name: team_name.clone(),
+                            escalation_id: escalation.id.clone(),
+                            from: escalation.from.clone(),
+                            question: escalation.question.clone(),
+                            options: escalation.options.clone(),
+                        }),
+                    )
+                    .await;
+            }
+        }
+    });
+}
+
+/// Messages of an inbox that were not among the first `seen` ones. Inboxes
+/// only grow; if this one shrank it was rewritten, and what it holds now was
+/// not seen.
+fn new_messages(seen: usize, messages: &[InboxMessage]) -> &[InboxMessage] {
+    messages.get(seen..).unwrap_or(messages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn only_messages_past_the_seen_ones_are_new() {
+        let message = |content: &str| InboxMessage {
+            from: "alice".to_string(),
+            timestamp: "2025-01-01T12:00:00+00:00".to_string(),
+            content: content.to_string(),
+            read: false,
+        };
+        let messages = [message("one"), message("two"), message("three")];
+
+        assert_eq!(new_messages(1, &messages), &messages[1..]);
+        assert_eq!(new_messages(3, &messages), &[] as &[InboxMessage]);
+        assert_eq!(new_messages(5, &messages), &messages[..]);
+    }
+}
@@ -0,0 +1,235 @@
+//! Leader auto-poll.
+//!
+//! A team created with `supervise` gets a background loop that watches its
+//! teammates, task list and the leader's inbox, so the leader does not have to
+//! spend turns calling `wait_for_teammates`. When something the leader should
+//! act on changes (a teammate finishes or errors out, a task is completed, a
+//! message arrives), the loop wakes the leader with one summary of everything
+//! that changed since it was last woken. Changes that arrive while the leader
+//! is mid-turn are held until it is idle again.
+
+use crate::agent::AgentStatus;
+use crate::codex::Session;
+use crate::teams::completion::LEADER_INBOX;
+use crate::teams::external::member_status;
+use codex_protocol::protocol::{TeamTaskInfo, TeamTaskStatus};
+use codex_protocol::user_input::UserInput;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How often the supervisor re-checks the team.
+const SUPERVISOR_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// What the supervisor last told the leader about.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Snapshot {
+    members: BTreeMap<String, AgentStatus>,
+    tasks: BTreeMap<String, TeamTaskInfo>,
+    unread: usize,
+}
+
+/// Start the leader supervisor for `team_name`. It runs until the team is
+/// cleaned up, leadership moves to another thread, or the leader session is
+/// dropped.
+pub(crate) fn start_leader_supervisor(session: &Arc<Session>, team_name: String) {
+    let weak_session = Arc::downgrade(session);
+    tokio::spawn(async move {
+        let mut reported: Option<Snapshot> = None;
+        let mut interval = tokio::time::interval(SUPERVISOR_POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            let Some(session) = weak_session.upgrade() else {
+                return;
+            };
+            let current = match snapshot(&session, &team_name).await {
+                Ok(Some(current)) => current,
+                Ok(None) => return,
+                Err(e) => {
+                    tracing::debug!("stopping leader supervisor for {team_name}: {e}");
+                    return;
+                }
+            };
+            let Some(previous) = &reported else {
+                reported = Some(current);
+                continue;
+            };
+            let changes = actionable_changes(previous, &current);
+            if changes.is_empty() {
+                // Nothing to report, e.g. a teammate started working or the
+                // leader read its inbox; compare against this from now on.
+                reported = Some(current);
+                continue;
+            }
+            let agent_control = &session.services.agent_control;
+            if matches!(
+                agent_control.get_status(session.conversation_id).await,
+                AgentStatus::PendingInit | AgentStatus::Running
+            ) {
+                continue;
+            }
+            let items = vec![UserInput::Text {
+                text: wake_up_message(&team_name, &changes),
+                text_elements: Vec::new(),
+            }];
+            match agent_control
+                .send_input(session.conversation_id, items)
+                .await
+            {
+                Ok(_) => reported = Some(current),
+                Err(e) => tracing::warn!("failed to wake leader of {team_name}: {e}"),
+            }
+        }
+    });
+}
+
+/// Current state of the team, or `None` if `session` no longer leads it.
+async fn snapshot(session: &Session, team_name: &str) -> std::io::Result<Option<Snapshot>> {
+    let teams = &session.services.teams;
+    let config = teams.load_config(team_name).await?;
+    if config.leader_thread_id != session.conversation_id {
+        return Ok(None);
+    }
+    let mut members = BTreeMap::new();
+    for member in &config.members {
+        let status = member_status(session, team_name, member).await;
+        members.insert(member.name.clone(), status);
+    }
+    let tasks = teams
+        .get_all_tasks(team_name)
+        .await?
+        .into_iter()
+        .map(|task| (task.id.clone(), task))
+        .collect();
+    let unread = teams
+        .inbox(team_name)
+        .read_inbox(LEADER_INBOX)
+        .await?
+        .iter()
+        .filter(|m| !m.read)
+        .count();
+    Ok(Some(Snapshot {
+        members,
+        tasks,
+        unread,
+    }))
+}
+
+/// Changes from `previous` to `current` the leader should act on.
+fn actionable_changes(previous: &Snapshot, current: &Snapshot) -> Vec<String> {
+    let mut changes = Vec::new();
+    for (name, status) in &current.members {
+        if previous.members.get(name) == Some(status) {
+            continue;
+        }
+        match status {
+            AgentStatus::Completed(_) => changes.push(format!("{name} finished its turn.")),
+            AgentStatus::Errored(error) => changes.push(format!("{name} errored out: {error}")),
+            AgentStatus::Shutdown => changes.push(format!("{name} shut down.")),
+            AgentStatus::PendingInit | AgentStatus::Running | AgentStatus::NotFound => {}
+        }
+    }
+    for (id, task) in &current.tasks {
+        let was_completed = previous
+            .tasks
+            .get(id)
+            .is_some_and(|t| matches!(t.status, TeamTaskStatus::Completed));
+        if matches!(task.status, TeamTaskStatus::Completed) && !was_completed {
+            let by = task
+                .assigned_to
+                .as_deref()
+                .map(|name| format!(" by {name}"))
+                .unwrap_or_default();
+            changes.push(format!("Task {id} ({}) was completed{by}.", task.title));
+        }
+    }
+    let all_done = |snapshot: &Snapshot| {
+        !snapshot.tasks.is_empty()
+            && snapshot
+                .tasks
+                .values()
+                .all(|t| matches!(t.status, TeamTaskStatus::Completed))
+    };
+    if all_done(current) && !all_done(previous) {
+        changes.push("All tasks are completed.".to_string());
+    }
+    if current.unread > previous.unread {
+        changes.push(format!(
+            "You have {} unread team message(s).",
+            current.unread
+        ));
+    }
+    changes
+}
+
+fn wake_up_message(team_name: &str, changes: &[String]) -> String {
+    let mut message = vec![format!("Update from team '{team_name}':")];
+    message.extend(changes.iter().map(|change| format!("- {change}")));
+    message.push(String::new());
+    message.push(
+        "Review the results and assign follow-up work, or clean up the team if it is done."
+            .to_string(),
+    );
+    message.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn task(id: &str, status: TeamTaskStatus) -> (String, TeamTaskInfo) {
+        let info = TeamTaskInfo {
+            id: id.to_string(),
+            title: format!("Title of {id}"),
+            description: None,
+            status,
+            assigned_to: Some("alice".to_string()),
+            depends_on: Vec::new(),
+            result: None,
+            created_at: None,
+        };
+        (id.to_string(), info)
+    }
+
+    #[test]
+    fn only_actionable_changes_are_reported() {
+        let previous = Snapshot {
+            members: BTreeMap::from([
+                ("alice".to_string(), AgentStatus::Running),
+                ("bob".to_string(), AgentStatus::Running),
+            ]),
+            tasks: BTreeMap::from([
+                task("task-1", TeamTaskStatus::InProgress),
+                task("task-2", TeamTaskStatus::Completed),
+            ]),
+            unread: 1,
+        };
+        assert_eq!(
+            actionable_changes(&previous, &previous),
+            Vec::<String>::new()
+        );
+
+        let current = Snapshot {
+            members: BTreeMap::from([
+                ("alice".to_string(), AgentStatus::Completed(None)),
+                ("bob".to_string(), AgentStatus::Running),
+                ("carol".to_string(), AgentStatus::PendingInit),
+            ]),
+            tasks: BTreeMap::from([
+                task("task-1", TeamTaskStatus::Completed),
+                task("task-2", TeamTaskStatus::Completed),
+            ]),
+            unread: 2,
+        };
+        assert_eq!(
+            actionable_changes(&previous, &current),
+            vec![
+                "alice finished its turn.".to_string(),
+                "Task task-1 (Title of task-1) was completed by alice.".to_string(),
+                "All tasks are completed.".to_string(),
+                "You have 2 unread team message(s).".to_string(),
+            ]
+        );
+    }
+}
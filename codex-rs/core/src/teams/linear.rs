@@ -0,0 +1,231 @@
+//! Linear issues as a team task tracker.
+//!
+//! With a `teams.linear` entry for a team, each task gets an issue in the
+//! entry's Linear team, assigned to the Linear user of the task's assignee,
+//! and is moved to the entry's done state once the task is completed. Built
+//! with the `linear` feature.
+
+use crate::config::types::TeamLinearSync;
+use crate::default_client::build_reqwest_client;
+use crate::teams::task_sync::TaskSyncAdapter;
+use async_trait::async_trait;
+use codex_protocol::protocol::TeamTaskInfo;
+use serde_json::Value;
+use serde_json::json;
+use std::time::Duration;
+
+const DEFAULT_API_URL: &str = "https://api.linear.app/graphql";
+const DEFAULT_TOKEN_ENV_VAR: &str = "LINEAR_API_KEY";
+
+/// How long a Linear API request may take.
+const LINEAR_TIMEOUT: Duration = Duration::from_secs(20);
+
+const ISSUE_CREATE: &str = "mutation IssueCreate($input: IssueCreateInput!) { \
+    issueCreate(input: $input) { success issue { id } } }";
+const ISSUE_UPDATE: &str = "mutation IssueUpdate($id: String!, $input: IssueUpdateInput!) { \
+    issueUpdate(id: $id, input: $input) { success } }";
+
+/// The issues of one Linear team. Items are identified by their issue id.
+pub(crate) struct LinearAdapter {
+    client: reqwest::Client,
+    api_url: String,
+    api_key: String,
+    linear_team_id: String,
+    label_ids: Vec<String>,
+    done_state_id: String,
+}
+
+impl LinearAdapter {
+    pub(crate) fn new(settings: &TeamLinearSync) -> Result<Self, String> {
+        let token_env_var = settings
+            .token_env_var
+            .as_deref()
+            .unwrap_or(DEFAULT_TOKEN_ENV_VAR);
+        let api_key =
+            std::env::var(token_env_var).map_err(|_| format!("{token_env_var} is not set"))?;
+        Ok(Self::with_api_key(settings, api_key))
+    }
+
+    fn with_api_key(settings: &TeamLinearSync, api_key: String) -> Self {
+        Self {
+            client: build_reqwest_client(),
+            api_url: settings
+                .api_url
+                .clone()
+                .unwrap_or_else(|| DEFAULT_API_URL.to_string()),
+            api_key,
+            linear_team_id: settings.linear_team_id.clone(),
+            label_ids: settings.label_ids.clone(),
+            done_state_id: settings.done_state_id.clone(),
+        }
+    }
+
+    /// Run `query` with `variables` and return its `data`.
+    async fn graphql(&self, query: &str, variables: Value) -> Result<Value, String> {
+        let response = self
+            .client
+            .post(&self.api_url)
+            // Personal API keys are sent as is, without a `Bearer` prefix.
+            .header("Authorization", &self.api_key)
+            .timeout(LINEAR_TIMEOUT)
+            .json(&json!({ "query": query, "variables": variables }))
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            return Err(format!("Linear answered {status}: {body}"));
+        }
+        let mut body: Value =
+            serde_json::from_str(&body).map_err(|e| format!("unexpected Linear response: {e}"))?;
+        // GraphQL errors come with a 200.
+        if let Some(errors) = body.get("errors") {
+            return Err(format!("Linear answered with errors: {errors}"));
+        }
+        Ok(body["data"].take())
+    }
+
+    async fn update_issue(&self, id: &str, input: Value) -> Result<(), String> {
+        let data = self
+            .graphql(ISSUE_UPDATE, json!({ "id": id, "input": input }))
+            .await?;
+        if data["issueUpdate"]["success"] != json!(true) {
+            return Err(format!("Linear did not update issue {id}"));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TaskSyncAdapter for LinearAdapter {
+    fn tracker(&self) -> &'static str {
+        "linear"
+    }
+
+    async fn create(
+        &self,
+        team_name: &str,
+        task: &TeamTaskInfo,
+        assignee: Option<&str>,
+    ) -> Result<String, String> {
+        let mut description = task.description.clone().unwrap_or_default();
+        if !description.is_empty() {
+            description.push_str("\n\n");
+        }
+        description.push_str(&format!("_Task {} of Codex team {team_name}._", task.id));
+        let input = json!({
+            "teamId": self.linear_team_id,
+            "title": task.title,
+            "description": description,
+            "labelIds": self.label_ids,
+            "assigneeId": assignee,
+        });
+        let data = self
+            .graphql(ISSUE_CREATE, json!({ "input": input }))
+            .await?;
+        data["issueCreate"]["issue"]["id"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| "Linear did not return the new issue's id".to_string())
+    }
+
+    async fn update(&self, external_id: &str, assignee: Option<&str>) -> Result<(), String> {
+        self.update_issue(external_id, json!({ "assigneeId": assignee }))
+            .await
+    }
+
+    async fn complete(&self, external_id: &str) -> Result<(), String> {
+        self.update_issue(external_id, json!({ "stateId": self.done_state_id }))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::TeamTaskStatus;
+    use pretty_assertions::assert_eq;
+    use std::collections::BTreeMap;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+    use wiremock::matchers::body_partial_json;
+    use wiremock::matchers::header;
+    use wiremock::matchers::method;
+
+    #[tokio::test]
+    async fn issues_are_created_and_moved_to_the_done_state() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(header("authorization", "lin_api_key"))
+            .and(body_partial_json(json!({
+                "variables": {
+                    "input": {
+                        "teamId": "team-uuid",
+                        "title": "Review the API",
+                        "labelIds": ["label-uuid"],
+                        "assigneeId": "user-uuid",
+                    }
+                }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": { "issueCreate": { "success": true, "issue": { "id": "issue-uuid" } } }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({
+                "variables": { "id": "issue-uuid", "input": { "stateId": "done-uuid" } }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": { "issueUpdate": { "success": true } }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(
+                json!({ "variables": { "id": "missing" } }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": null,
+                "errors": [{ "message": "Entity not found" }],
+            })))
+            .mount(&server)
+            .await;
+        let settings = TeamLinearSync {
+            team: None,
+            linear_team_id: "team-uuid".to_string(),
+            token_env_var: None,
+            label_ids: vec!["label-uuid".to_string()],
+            assignees: BTreeMap::new(),
+            done_state_id: "done-uuid".to_string(),
+            api_url: Some(server.uri()),
+        };
+        let linear = LinearAdapter::with_api_key(&settings, "lin_api_key".to_string());
+        let task = TeamTaskInfo {
+            id: "task-1".to_string(),
+            title: "Review the API".to_string(),
+            description: None,
+            status: TeamTaskStatus::Pending,
+            assigned_to: Some("reviewer".to_string()),
+            depends_on: Vec::new(),
+            result: None,
+            created_at: None,
+        };
+
+        let id = linear
+            .create("zeta", &task, Some("user-uuid"))
+            .await
+            .unwrap();
+        assert_eq!(id, "issue-uuid");
+        linear.complete(&id).await.unwrap();
+        let err = linear.complete("missing").await.unwrap_err();
+        assert_eq!(
+            err,
+            r#"Linear answered with errors: [{"message":"Entity not found"}]"#
+        );
+    }
+}
@@ -0,0 +1,120 @@
+//! Teammate liveness.
+//!
+//! Every event a session emits counts as a heartbeat in `AgentControl`. Each
+//! spawned teammate gets a watcher that checks how long it has been silent
+//! while it works on a turn. Past `teams.heartbeat_timeout_secs` the teammate
+//! is marked [`STALE_STATUS`] in the team config and a
+//! `TeamMemberLivenessLost` event is emitted, so a hung teammate stands out
+//! instead of showing as running forever. The mark is lifted once the
+//! teammate is heard from again or its turn ends.
+
+use crate::agent::AgentStatus;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::teams::team_manager::MemberConfig;
+use codex_protocol::protocol::{EventMsg, TeamMemberLivenessLostEvent};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Member status of a teammate that stopped sending heartbeats mid-turn.
+pub(crate) const STALE_STATUS: &str = "stale";
+
+/// How often the watcher re-checks a teammate, at most.
+const LIVENESS_POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Watch the heartbeats of `member` until it leaves the team or shuts down.
+pub(crate) fn watch_teammate_liveness(
+    session: &Arc<Session>,
+    turn: Arc<TurnContext>,
+    team_name: String,
+    member: &MemberConfig,
+) {
+    let timeout_secs = turn.config.teams.heartbeat_timeout_secs;
+    if timeout_secs == 0 {
+        return;
+    }
+    let timeout = Duration::from_secs(timeout_secs);
+    let weak_session = Arc::downgrade(session);
+    let member_name = member.name.clone();
+    let thread_id = member.thread_id;
+    tokio::spawn(async move {
+        let mut marked_stale = false;
+        let mut interval = tokio::time::interval(LIVENESS_POLL_INTERVAL.min(timeout));
+        loop {
+            interval.tick().await;
+            let Some(session) = weak_session.upgrade() else {
+                return;
+            };
+            let teams = &session.services.teams;
+            // A restarted teammate runs on a new thread with a watcher of its
+            // own.
+            let still_member = teams.load_config(&team_name).await.is_ok_and(|config| {
+                config
+                    .members
+                    .iter()
+                    .any(|m| m.name == member_name && m.thread_id == thread_id)
+            });
+            let agent_control = &session.services.agent_control;
+            let status = agent_control.get_status(thread_id).await;
+            if !still_member || matches!(status, AgentStatus::Shutdown | AgentStatus::NotFound) {
+                return;
+            }
+            let silent = agent_control
+                .last_heartbeat(thread_id)
+                .map(|at| at.elapsed());
+            let stale = is_stale(&status, silent, timeout);
+            if stale == marked_stale {
+                continue;
+            }
+            marked_stale = stale;
+            let member_status = if stale { STALE_STATUS } else { "running" };
+            if let Err(e) = teams
+                .manager()
+                .update_member_status(&team_name, &member_name, member_status)
+                .await
+            {
+                tracing::warn!("failed to mark {member_name} as {member_status}: {e}");
+            }
+            if !stale {
+                continue;
+            }
+            tracing::info!("teammate {member_name} sent no heartbeat for {timeout_secs}s");
+            session
+                .send_event(
+                    &turn,
+                    EventMsg::TeamMemberLivenessLost(TeamMemberLivenessLostEvent {
+                        team_name: team_name.clone(),
+                        member_name: member_name.clone(),
+                        thread_id,
+                        silent_secs: silent.unwrap_or_default().as_secs(),
+                    }),
+                )
+                .await;
+        }
+    });
+}
+
+/// Whether a teammate with `status` that has been `silent` for that long
+/// missed its heartbeat. Only teammates working on a turn are expected to
+/// emit events.
+fn is_stale(status: &AgentStatus, silent: Option<Duration>, timeout: Duration) -> bool {
+    matches!(status, AgentStatus::Running) && silent.is_some_and(|silent| silent >= timeout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_silent_running_teammates_are_stale() {
+        let timeout = Duration::from_secs(300);
+        let long = Some(Duration::from_secs(301));
+        let short = Some(Duration::from_secs(20));
+
+        assert!(is_stale(&AgentStatus::Running, long, timeout));
+        assert!(!is_stale(&AgentStatus::Running, short, timeout));
+        assert!(!is_stale(&AgentStatus::Running, None, timeout));
+        assert!(!is_stale(&AgentStatus::Completed(None), long, timeout));
+        assert!(!is_stale(&AgentStatus::PendingInit, long, timeout));
+    }
+}
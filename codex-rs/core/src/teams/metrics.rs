@@ -0,0 +1,354 @@
+//! Prometheus metrics of team activity.
+//!
+//! Team events update a process-wide registry, like the spans in
+//! [`crate::teams::spans`], which [`render_team_metrics`] writes out in the
+//! Prometheus text format for the app server's metrics endpoint.
+
+use crate::teams::completion::LEADER_INBOX;
+use crate::teams::summary::member_status_label;
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::TeamTaskStatus;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+
+static TEAM_METRICS: LazyLock<Mutex<TeamMetrics>> = LazyLock::new(Default::default);
+
+/// Current values, by team name and then member name or task id.
+#[derive(Default)]
+struct TeamMetrics {
+    /// Leader thread of each team created and not yet cleaned up.
+    leaders: BTreeMap<String, ThreadId>,
+    members: BTreeMap<(String, String), &'static str>,
+    tasks: BTreeMap<(String, String), &'static str>,
+    messages: BTreeMap<String, u64>,
+    member_tokens: BTreeMap<(String, String), i64>,
+}
+
+fn team_metrics() -> MutexGuard<'static, TeamMetrics> {
+    TEAM_METRICS
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Update the metrics `msg`, emitted by the session of thread `emitter`,
+/// changes.
+pub(crate) fn record_team_metrics(msg: &EventMsg, emitter: ThreadId) {
+    team_metrics().record(msg, emitter);
+}
+
+/// All team metrics in the Prometheus text exposition format.
+pub fn render_team_metrics() -> String {
+    team_metrics().render()
+}
+
+impl TeamMetrics {
+    fn record(&mut self, msg: &EventMsg, emitter: ThreadId) {
+        match msg {
+            EventMsg::TeamCreated(ev) => {
+                self.leaders
+                    .insert(ev.team_name.clone(), ev.leader_thread_id);
+            }
+            EventMsg::TeamLeaderChanged(ev) => {
+                self.leaders
+                    .insert(ev.team_name.clone(), ev.leader_thread_id);
+            }
+            EventMsg::TeamCleanup(ev) => {
+                let team = &ev.team_name;
+                self.leaders.remove(team);
+                self.members.retain(|(t, _), _| t != team);
+                self.tasks.retain(|(t, _), _| t != team);
+                self.messages.remove(team);
+                self.member_tokens.retain(|(t, _), _| t != team);
+            }
+            EventMsg::TeamRenamed(ev) => {
+                if let Some(leader) = self.leaders.remove(&ev.old_name) {
+                    self.leaders.insert(ev.new_name.clone(), leader);
+                }
+                if let Some(count) = self.messages.remove(&ev.old_name) {
+                    self.messages.insert(ev.new_name.clone(), count);
+                }
+                rename_team(&mut self.members, &ev.old_name, &ev.new_name);
+                rename_team(&mut self.tasks, &ev.old_name, &ev.new_name);
+                rename_team(&mut self.member_tokens, &ev.old_name, &ev.new_name);
+            }
+            EventMsg::TeamMemberAdded(ev) | EventMsg::TeamMemberStatusChanged(ev) => {
+                self.members.insert(
+                    (ev.team_name.clone(), ev.member.name.clone()),
+                    member_status_label(&ev.member.status),
+                );
+            }
+            EventMsg::TeamMemberRemoved(ev) => {
+                let key = (ev.team_name.clone(), ev.member.name.clone());
+                self.members.remove(&key);
+                self.member_tokens.remove(&key);
+            }
+            EventMsg::TeamTaskCreated(ev) | EventMsg::TeamTaskUpdated(ev) => {
+                let status = match ev.task.status {
+                    TeamTaskStatus::Pending => "pending",
+                    TeamTaskStatus::InProgress => "in_progress",
+                    TeamTaskStatus::Completed => "completed",
+                };
+                self.tasks
+                    .insert((ev.team_name.clone(), ev.task.id.clone()), status);
+            }
+            EventMsg::TeamMessageSent(ev) => {
+                // A message to the leader is announced by its sender and
+                // again by the leader's session once it arrives; count the
+                // arrival.
+                let leader = self.leaders.get(&ev.team_name);
+                if ev.to == LEADER_INBOX && leader.is_some_and(|leader| *leader != emitter) {
+                    return;
+                }
+                *self.messages.entry(ev.team_name.clone()).or_default() += 1;
+            }
+            EventMsg::TeamMemberTokenUsage(ev) => {
+                self.member_tokens.insert(
+                    (ev.team_name.clone(), ev.member_name.clone()),
+                    ev.total_token_usage.total_tokens,
+                );
+            }
+            _ => {}
+        }
+    }
+
+    fn render(&self) -> String {
+        let mut members: BTreeMap<(&str, &str), u64> = BTreeMap::new();
+        for ((team, _), status) in &self.members {
+            *members.entry((team.as_str(), *status)).or_default() += 1;
+        }
+        let mut tasks: BTreeMap<(&str, &str), u64> = BTreeMap::new();
+        for ((team, _), status) in &self.tasks {
+            *tasks.entry((team.as_str(), *status)).or_default() += 1;
+        }
+
+        let mut out = String::new();
+        write_header(
+            &mut out,
+            "codex_team_active_teams",
+            "gauge",
+            "Teams created and not yet cleaned up.",
+        );
+        let _ = writeln!(out, "codex_team_active_teams {}", self.leaders.len());
+        write_header(
+            &mut out,
+            "codex_team_members",
+            "gauge",
+            "Teammates by status.",
+        );
+        for ((team, status), count) in members {
+            let _ = writeln!(
+                out,
+                "codex_team_members{{team=\"{}\",status=\"{status}\"}} {count}",
+                escape_label(team)
+            );
+        }
+        write_header(&mut out, "codex_team_tasks", "gauge", "Tasks by status.");
+        for ((team, status), count) in tasks {
+            let _ = writeln!(
+                out,
+                "codex_team_tasks{{team=\"{}\",status=\"{status}\"}} {count}",
+                escape_label(team)
+            );
+        }
+        write_header(
+            &mut out,
+            "codex_team_messages_total",
+            "counter",
+            "Messages sent between members of a team.",
+        );
+        for (team, count) in &self.messages {
+            let _ = writeln!(
+                out,
+                "codex_team_messages_total{{team=\"{}\"}} {count}",
+                escape_label(team)
+            );
+        }
+        write_header(
+            &mut out,
+            "codex_team_member_tokens",
+            "gauge",
+            "Tokens used by each teammate's session so far.",
+        );
+        for ((team, member), tokens) in &self.member_tokens {
+            let _ = writeln!(
+                out,
+                "codex_team_member_tokens{{team=\"{}\",member=\"{}\"}} {tokens}",
+                escape_label(team),
+                escape_label(member)
+            );
+        }
+        out
+    }
+}
+
+fn write_header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+/// `value` with the characters label values cannot hold escaped.
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Move the entries of team `old_name` in `entries` to `new_name`.
+fn rename_team<V>(entries: &mut BTreeMap<(String, String), V>, old_name: &str, new_name: &str) {
+    *entries = std::mem::take(entries)
+        .into_iter()
+        .map(|((team, key), value)| {
+            let team = if team == old_name {
+                new_name.to_string()
+            } else {
+                team
+            };
+            ((team, key), value)
+        })
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::AgentStatus;
+    use codex_protocol::protocol::TeamCreatedEvent;
+    use codex_protocol::protocol::TeamDisplayMode;
+    use codex_protocol::protocol::TeamMemberEvent;
+    use codex_protocol::protocol::TeamMemberInfo;
+    use codex_protocol::protocol::TeamMemberTokenUsageEvent;
+    use codex_protocol::protocol::TeamMessageEvent;
+    use codex_protocol::protocol::TeamTaskEvent;
+    use codex_protocol::protocol::TeamTaskInfo;
+    use codex_protocol::protocol::TokenUsage;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn team_events_render_as_prometheus_metrics() {
+        let leader = ThreadId::new();
+        let reviewer = ThreadId::new();
+        let member = |name: &str, status| TeamMemberInfo {
+            name: name.to_string(),
+            thread_id: reviewer,
+            role: None,
+            status,
+        };
+        let task = |id: &str, status| TeamTaskInfo {
+            id: id.to_string(),
+            title: "Review".to_string(),
+            description: None,
+            status,
+            assigned_to: None,
+            depends_on: Vec::new(),
+            result: None,
+            created_at: None,
+        };
+        let message = |from: &str, to: &str| {
+            EventMsg::TeamMessageSent(TeamMessageEvent {
+                team_name: "zeta".to_string(),
+                from: from.to_string(),
+                to: to.to_string(),
+                content: "Done.".to_string(),
+            })
+        };
+        let events = [
+            (
+                leader,
+                EventMsg::TeamCreated(TeamCreatedEvent {
+                    team_name: "zeta".to_string(),
+                    leader_thread_id: leader,
+                    description: None,
+                    display_mode: TeamDisplayMode::default(),
+                }),
+            ),
+            (
+                leader,
+                EventMsg::TeamMemberAdded(TeamMemberEvent {
+                    team_name: "zeta".to_string(),
+                    member: member("reviewer", AgentStatus::Running),
+                    previous_status: None,
+                }),
+            ),
+            (
+                leader,
+                EventMsg::TeamMemberAdded(TeamMemberEvent {
+                    team_name: "zeta".to_string(),
+                    member: member("tester", AgentStatus::Running),
+                    previous_status: None,
+                }),
+            ),
+            (
+                leader,
+                EventMsg::TeamMemberStatusChanged(TeamMemberEvent {
+                    team_name: "zeta".to_string(),
+                    member: member("tester", AgentStatus::Completed(None)),
+                    previous_status: Some(AgentStatus::Running),
+                }),
+            ),
+            (
+                leader,
+                EventMsg::TeamTaskCreated(TeamTaskEvent {
+                    team_name: "zeta".to_string(),
+                    task: task("task-1", TeamTaskStatus::InProgress),
+                }),
+            ),
+            (
+                leader,
+                EventMsg::TeamTaskCreated(TeamTaskEvent {
+                    team_name: "zeta".to_string(),
+                    task: task("task-2", TeamTaskStatus::Pending),
+                }),
+            ),
+            (leader, message("leader", "reviewer")),
+            // Sent by the reviewer, then announced again by the leader.
+            (reviewer, message("reviewer", "leader")),
+            (leader, message("reviewer", "leader")),
+            (
+                reviewer,
+                EventMsg::TeamMemberTokenUsage(TeamMemberTokenUsageEvent {
+                    team_name: "zeta".to_string(),
+                    member_name: "reviewer".to_string(),
+                    thread_id: reviewer,
+                    total_token_usage: TokenUsage {
+                        total_tokens: 1200,
+                        ..TokenUsage::default()
+                    },
+                    last_token_usage: TokenUsage::default(),
+                }),
+            ),
+        ];
+
+        let mut metrics = TeamMetrics::default();
+        for (emitter, event) in &events {
+            metrics.record(event, *emitter);
+        }
+
+        assert_eq!(
+            metrics.render(),
+            "\
+# HELP codex_team_active_teams Teams created and not yet cleaned up.
+# TYPE codex_team_active_teams gauge
+codex_team_active_teams 1
+# HELP codex_team_members Teammates by status.
+# TYPE codex_team_members gauge
+codex_team_members{team=\"zeta\",status=\"idle\"} 1
+codex_team_members{team=\"zeta\",status=\"running\"} 1
+# HELP codex_team_tasks Tasks by status.
+# TYPE codex_team_tasks gauge
+codex_team_tasks{team=\"zeta\",status=\"in_progress\"} 1
+codex_team_tasks{team=\"zeta\",status=\"pending\"} 1
+# HELP codex_team_messages_total Messages sent between members of a team.
+# TYPE codex_team_messages_total counter
+codex_team_messages_total{team=\"zeta\"} 2
+# HELP codex_team_member_tokens Tokens used by each teammate's session so far.
+# TYPE codex_team_member_tokens gauge
+codex_team_member_tokens{team=\"zeta\",member=\"reviewer\"} 1200
+"
+        );
+    }
+}
@@ -4,6 +4,93 @@
 //! as a team: a shared task list, per-agent inboxes, and a team manager that
 //! persists configuration to `~/.codex/teams/{name}/`.
 
+pub mod a2a;
+pub mod artifacts;
+pub(crate) mod completion;
+pub mod conflicts;
+pub(crate) mod delegation;
+pub(crate) mod diff_summary;
+pub mod doctor;
+pub mod dry_run;
+pub(crate) mod email;
+pub(crate) mod errors;
+pub mod escalations;
+pub mod event_log;
+pub mod external;
+pub(crate) mod gc;
+pub mod github_import;
+pub(crate) mod github_issues;
 pub mod inbox;
+#[cfg(feature = "jira")]
+pub(crate) mod jira;
+pub(crate) mod join;
+pub(crate) mod leader_mail;
+pub(crate) mod leader_supervisor;
+#[cfg(feature = "linear")]
+pub(crate) mod linear;
+pub(crate) mod liveness;
+pub mod metrics;
+pub(crate) mod notifiers;
+pub mod orchestrator;
+pub(crate) mod output;
+pub mod patches;
+pub mod plan;
+pub(crate) mod progress;
+pub(crate) mod queue;
+pub mod replay;
+pub mod report;
+pub mod reservations;
+pub(crate) mod restart;
+pub mod resume;
+pub mod roles;
+pub mod scratchpad;
+pub(crate) mod service;
+pub(crate) mod spans;
+pub mod ssh;
+pub(crate) mod summary;
 pub mod task_list;
+pub mod task_sync;
 pub mod team_manager;
+pub(crate) mod timeout;
+pub mod usage;
+pub(crate) mod webhooks;
+pub mod worktree;
+
+use crate::git_info::get_git_repo_root;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// Default root for teams data: `~/.codex/teams`
+pub fn default_teams_root() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".codex")
+        .join("teams")
+}
+
+/// Default root for tasks data: `~/.codex/tasks`
+pub fn default_tasks_root() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".codex")
+        .join("tasks")
+}
+
+/// Default root for role definitions: `~/.codex/roles`
+pub(crate) fn default_roles_root() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".codex")
+        .join("roles")
+}
+
+/// `path` relative to the root of its repository (or worktree), or as is
+/// outside a repository. Teammates in different worktrees of one repository
+/// get the same name for the same file.
+pub(crate) fn repo_relative(path: &Path) -> String {
+    get_git_repo_root(path)
+        .and_then(|root| path.strip_prefix(root).ok().map(Path::to_path_buf))
+        .unwrap_or_else(|| path.to_path_buf())
+        .to_string_lossy()
+        .into_owned()
+}
@@ -0,0 +1,254 @@
+//! Notifications of team milestones.
+//!
+//! `teams.notifiers` lists Slack and Discord channels that get a short
+//! progress message, such as `team zeta: 7/9 tasks complete, reviewer
+//! errored`, at the same milestones `teams.webhooks` are sent for. Each chat
+//! service is a [`TeamNotifier`] that wraps the message in the body its
+//! incoming webhooks expect. `teams.email` is one more notifier, for long
+//! unattended runs: it mails the message with a report of the run at the few
+//! milestones worth an email (see [`crate::teams::email`]).
+
+use crate::config::types::TeamNotifierConfig;
+use crate::config::types::TeamNotifierService;
+use crate::config::types::TeamWebhookEvent;
+use crate::config::types::TeamsConfig;
+use crate::teams::email::EmailNotifier;
+use crate::teams::email::email_subject;
+use crate::teams::report::load_team_run_report;
+use crate::teams::service::TeamService;
+use crate::teams::webhooks::TeamWebhookPayload;
+use crate::teams::webhooks::post_json;
+use crate::teams::webhooks::redacted_url;
+use async_trait::async_trait;
+use codex_protocol::protocol::TeamTaskInfo;
+use codex_protocol::protocol::TeamTaskStatus;
+use serde_json::Value;
+use serde_json::json;
+
+/// A channel team milestones are sent to.
+#[async_trait]
+pub(crate) trait TeamNotifier: Send + Sync {
+    /// Where messages go, for logs. Must not reveal secrets such as the
+    /// token in a webhook URL.
+    fn destination(&self) -> String;
+
+    /// Send the milestone `message`.
+    async fn send(&self, message: &str) -> Result<(), String>;
+}
+
+pub(crate) struct SlackNotifier {
+    webhook_url: String,
+}
+
+#[async_trait]
+impl TeamNotifier for SlackNotifier {
+    fn destination(&self) -> String {
+        redacted_url(&self.webhook_url)
+    }
+
+    async fn send(&self, message: &str) -> Result<(), String> {
+        post_json(&self.webhook_url, &self.body(message)).await
+    }
+}
+
+impl SlackNotifier {
+    /// JSON body that posts `message`.
+    fn body(&self, message: &str) -> Value {
+        // Slack reads `&`, `<` and `>` as markup.
+        let text = message
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;");
+        json!({ "text": text })
+    }
+}
+
+pub(crate) struct DiscordNotifier {
+    webhook_url: String,
+}
+
+#[async_trait]
+impl TeamNotifier for DiscordNotifier {
+    fn destination(&self) -> String {
+        redacted_url(&self.webhook_url)
+    }
+
+    async fn send(&self, message: &str) -> Result<(), String> {
+        post_json(&self.webhook_url, &self.body(message)).await
+    }
+}
+
+impl DiscordNotifier {
+    /// JSON body that posts `message`.
+    fn body(&self, message: &str) -> Value {
+        // Keep names like `@everyone` in task titles from pinging anyone.
+        json!({ "content": message, "allowed_mentions": { "parse": [] } })
+    }
+}
+
+fn notifier(config: &TeamNotifierConfig) -> Box<dyn TeamNotifier> {
+    let webhook_url = config.webhook_url.clone();
+    match config.service {
+        TeamNotifierService::Slack => Box::new(SlackNotifier { webhook_url }),
+        TeamNotifierService::Discord => Box::new(DiscordNotifier { webhook_url }),
+    }
+}
+
+/// Send each of `payloads` to the `teams.notifiers` that want its milestone,
+/// and to `teams.email` if it is worth an email, in the background.
+pub(crate) async fn send_notifications(
+    teams: &TeamService,
+    config: &TeamsConfig,
+    payloads: &[TeamWebhookPayload],
+) {
+    for payload in payloads {
+        let mut notifiers: Vec<Box<dyn TeamNotifier>> = config
+            .notifiers
+            .iter()
+            .filter(|n| n.milestones.is_empty() || n.milestones.contains(&payload.event))
+            .map(notifier)
+            .collect();
+        if notifiers.is_empty() && config.email.is_none() {
+            continue;
+        }
+        let tasks = match payload.event {
+            TeamWebhookEvent::TeamCreated | TeamWebhookEvent::TeamCleaned => Vec::new(),
+            _ => teams
+                .get_all_tasks(&payload.team_name)
+                .await
+                .unwrap_or_default(),
+        };
+        let message = milestone_message(payload, &tasks);
+        if let Some(email) = &config.email
+            && let Some(subject) = email_subject(email, payload, &tasks, teams.errored_teammates())
+        {
+            let report =
+                match load_team_run_report(teams.manager(), &payload.team_name, config).await {
+                    Ok(report) => report.to_markdown(),
+                    Err(e) => format!("The report of the run could not be built: {e}"),
+                };
+            notifiers.push(Box::new(EmailNotifier::new(email.clone(), subject, report)));
+        }
+        for notifier in notifiers {
+            let message = message.clone();
+            tokio::spawn(async move {
+                if let Err(e) = notifier.send(&message).await {
+                    tracing::warn!(
+                        "failed to send team milestone to {}: {e}",
+                        notifier.destination()
+                    );
+                }
+            });
+        }
+    }
+}
+
+/// One line announcing `payload`, with the team's progress on `tasks`.
+fn milestone_message(payload: &TeamWebhookPayload, tasks: &[TeamTaskInfo]) -> String {
+    let team = &payload.team_name;
+    let member = payload
+        .member
+        .as_ref()
+        .map_or("a teammate", |m| m.name.as_str());
+    let task = payload
+        .task
+        .as_ref()
+        .map(|t| format!("{} ({})", t.id, t.title))
+        .unwrap_or_default();
+    let what = match payload.event {
+        TeamWebhookEvent::TeamCreated => return format!("team {team} created"),
+        TeamWebhookEvent::TeamCleaned => return format!("team {team} cleaned up"),
+        TeamWebhookEvent::TaskCompleted => format!("{task} done"),
+        TeamWebhookEvent::TaskFailed => format!("{task} failed because {member} errored"),
+        TeamWebhookEvent::MemberErrored => format!("{member} errored"),
+        TeamWebhookEvent::BudgetExhausted => format!("{member} went over its budget"),
+    };
+    let completed = tasks
+        .iter()
+        .filter(|t| matches!(t.status, TeamTaskStatus::Completed))
+        .count();
+    format!(
+        "team {team}: {completed}/{} tasks complete, {what}",
+        tasks.len()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::ThreadId;
+    use codex_protocol::protocol::AgentStatus;
+    use codex_protocol::protocol::TeamMemberInfo;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn destinations_leave_out_the_webhook_secret() {
+        let slack = notifier(&TeamNotifierConfig {
+            service: TeamNotifierService::Slack,
+            webhook_url: "https://hooks.slack.com/services/T000/B000/secret".to_string(),
+            milestones: Vec::new(),
+        });
+        assert_eq!(slack.destination(), "https://hooks.slack.com/…");
+        assert_eq!(redacted_url("not a url"), "an unparsable URL");
+    }
+
+    #[test]
+    fn messages_report_progress_in_the_service_format() {
+        let task = |id: &str, status| TeamTaskInfo {
+            id: id.to_string(),
+            title: "Review <api>".to_string(),
+            description: None,
+            status,
+            assigned_to: Some("reviewer".to_string()),
+            depends_on: Vec::new(),
+            result: None,
+            created_at: None,
+        };
+        let tasks = vec![
+            task("task-1", TeamTaskStatus::Completed),
+            task("task-2", TeamTaskStatus::InProgress),
+            task("task-3", TeamTaskStatus::Pending),
+        ];
+        let mut payload = TeamWebhookPayload {
+            event: TeamWebhookEvent::MemberErrored,
+            team_name: "zeta".to_string(),
+            timestamp: String::new(),
+            member: Some(TeamMemberInfo {
+                name: "reviewer".to_string(),
+                thread_id: ThreadId::new(),
+                role: None,
+                status: AgentStatus::Errored("boom".to_string()),
+            }),
+            task: None,
+        };
+        assert_eq!(
+            milestone_message(&payload, &tasks),
+            "team zeta: 1/3 tasks complete, reviewer errored"
+        );
+
+        payload.event = TeamWebhookEvent::TaskFailed;
+        payload.task = Some(tasks[1].clone());
+        let message = milestone_message(&payload, &tasks);
+        assert_eq!(
+            message,
+            "team zeta: 1/3 tasks complete, task-2 (Review <api>) failed because reviewer errored"
+        );
+
+        let slack = SlackNotifier {
+            webhook_url: "https://hooks.slack.com/x".to_string(),
+        };
+        assert_eq!(
+            slack.body(&message),
+            json!({
+                "text": "team zeta: 1/3 tasks complete, task-2 (Review &lt;api&gt;) failed because reviewer errored"
+            })
+        );
+        let discord = DiscordNotifier {
+            webhook_url: "https://discord.com/api/webhooks/x".to_string(),
+        };
+        assert_eq!(
+            discord.body("team zeta created"),
+            json!({ "content": "team zeta created", "allowed_mentions": { "parse": [] } })
+        );
+    }
+}
@@ -0,0 +1,467 @@
+//! Running agent teams from Rust.
+//!
+//! [`TeamOrchestrator`] builds a [`TeamPlan`] and runs it the way
+//! `codex team run` does, for programs that embed codex-core and want a team
+//! without going through the model-facing team tools:
+//!
+//! ```no_run
+//! # async fn example(config: codex_core::config::Config) -> codex_core::error::Result<()> {
+//! use codex_core::teams::orchestrator::TeamOrchestrator;
+//! use codex_core::teams::orchestrator::TeamRunStatus;
+//!
+//! let report = TeamOrchestrator::new(config)
+//!     .name("review")
+//!     .member("reviewer", "Review the tasks assigned to you.")
+//!     .task("Review the parser changes")
+//!     .run()
+//!     .await?;
+//! assert_eq!(report.status, TeamRunStatus::Completed);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The leader session runs with the given config, so it should not ask for
+//! approvals: nobody is there to answer them.
+
+use crate::AuthManager;
+use crate::NewThread;
+use crate::ThreadManager;
+use crate::config::Config;
+use crate::error::CodexErr;
+use crate::error::Result as CodexResult;
+use crate::teams::default_tasks_root;
+use crate::teams::plan::validate_plan;
+use crate::teams::task_list::TaskList;
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::Op;
+use codex_protocol::protocol::SessionSource;
+use codex_protocol::protocol::TeamDisplayMode;
+use codex_protocol::protocol::TeamPlan;
+use codex_protocol::protocol::TeamPlanMember;
+use codex_protocol::protocol::TeamPlanTask;
+use codex_protocol::protocol::TeamTaskInfo;
+use codex_protocol::protocol::TeamTaskStatus;
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// Builder for a team run.
+pub struct TeamOrchestrator {
+    config: Config,
+    plan: TeamPlan,
+}
+
+impl TeamOrchestrator {
+    /// A team with no members or tasks yet, led by a session with `config`.
+    /// Agent teams are enabled for the run whatever `config` says. The team
+    /// is named `team-<uuid>` unless [`Self::name`] is called.
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            plan: TeamPlan {
+                name: format!("team-{}", uuid::Uuid::new_v4().as_simple()),
+                description: None,
+                display_mode: TeamDisplayMode::default(),
+                work_stealing: false,
+                members: Vec::new(),
+                tasks: Vec::new(),
+            },
+        }
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.plan.name = name.into();
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.plan.description = Some(description.into());
+        self
+    }
+
+    /// Hand idle teammates the next available task automatically.
+    pub fn work_stealing(mut self, enabled: bool) -> Self {
+        self.plan.work_stealing = enabled;
+        self
+    }
+
+    /// Add a teammate with initial instructions `prompt`.
+    pub fn member(self, name: impl Into<String>, prompt: impl Into<String>) -> Self {
+        self.member_with(TeamPlanMember {
+            name: name.into(),
+            role: None,
+            prompt: prompt.into(),
+            max_tokens: None,
+            max_turns: None,
+        })
+    }
+
+    /// Add a teammate with a role or budget.
+    pub fn member_with(mut self, member: TeamPlanMember) -> Self {
+        self.plan.members.push(member);
+        self
+    }
+
+    /// Add an unassigned task with id `task-<n>`, `n` counting from 1.
+    pub fn task(self, title: impl Into<String>) -> Self {
+        let id = format!("task-{}", self.plan.tasks.len() + 1);
+        self.task_with(TeamPlanTask {
+            id,
+            title: title.into(),
+            description: None,
+            assigned_to: None,
+            depends_on: Vec::new(),
+        })
+    }
+
+    /// Add a task with a description, assignee or dependencies. It may only
+    /// depend on tasks added before it.
+    pub fn task_with(mut self, task: TeamPlanTask) -> Self {
+        self.plan.tasks.push(task);
+        self
+    }
+
+    /// The plan built so far, e.g. to submit as `Op::RunTeamPlan` to a
+    /// session the caller already runs, following it with a [`TeamRun`].
+    pub fn into_plan(self) -> TeamPlan {
+        self.plan
+    }
+
+    /// Start a leader session, create the team, and follow it until every
+    /// task is completed, every member has exhausted its budget, or the team
+    /// could not be started. All sessions of the run are shut down before
+    /// this returns.
+    pub async fn run(self) -> CodexResult<TeamRunReport> {
+        let Self { mut config, plan } = self;
+        validate_plan(&plan).map_err(CodexErr::InvalidRequest)?;
+        config.teams.enabled = true;
+
+        let auth_manager = AuthManager::shared(
+            config.codex_home.clone(),
+            true,
+            config.cli_auth_credentials_store_mode,
+        );
+        let thread_manager =
+            ThreadManager::new(config.codex_home.clone(), auth_manager, SessionSource::Exec);
+        let NewThread {
+            thread_id, thread, ..
+        } = thread_manager.start_thread(config).await?;
+
+        let mut run = TeamRun::new(&plan);
+        thread.submit(Op::RunTeamPlan { plan }).await?;
+        let status = loop {
+            let event = thread.next_event().await?;
+            if matches!(event.msg, EventMsg::ShutdownComplete) {
+                break run.leader_shut_down();
+            }
+            let status = run.on_event(&event.msg);
+            if status != TeamRunStatus::Running {
+                break status;
+            }
+        };
+        thread_manager.remove_and_close_all_threads().await?;
+
+        let tasks = TaskList::new(default_tasks_root())
+            .get_all_tasks(&run.team_name)
+            .await?;
+        Ok(TeamRunReport {
+            team_name: run.team_name,
+            leader_thread_id: thread_id,
+            status,
+            tasks,
+        })
+    }
+}
+
+/// How a [`TeamOrchestrator::run`] ended. Serializes to the summary
+/// `codex team run --summary-file` writes, with the status as `status` and,
+/// for a failed run, its reason as `message`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TeamRunReport {
+    pub team_name: String,
+    pub leader_thread_id: ThreadId,
+    /// [`TeamRunStatus::Completed`] or [`TeamRunStatus::Failed`].
+    #[serde(flatten)]
+    pub status: TeamRunStatus,
+    /// The team's tasks when the run ended.
+    pub tasks: Vec<TeamTaskInfo>,
+}
+
+impl TeamRunReport {
+    /// The tasks that were not completed when the run ended.
+    pub fn incomplete_tasks(&self) -> Vec<&TeamTaskInfo> {
+        self.tasks
+            .iter()
+            .filter(|task| task.status != TeamTaskStatus::Completed)
+            .collect()
+    }
+}
+
+/// Follows the events of the leader of a team started from a plan to decide
+/// when the run is over: once every task is completed, every member has
+/// exhausted its budget, or the plan failed to start.
+pub struct TeamRun {
+    team_name: String,
+    members: HashSet<String>,
+    started: HashSet<String>,
+    exhausted: HashSet<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", content = "message", rename_all = "snake_case")]
+pub enum TeamRunStatus {
+    Running,
+    Completed,
+    Failed(String),
+}
+
+impl TeamRun {
+    pub fn new(plan: &TeamPlan) -> Self {
+        Self {
+            team_name: plan.name.clone(),
+            members: plan
+                .members
+                .iter()
+                .map(|member| member.name.clone())
+                .collect(),
+            started: HashSet::new(),
+            exhausted: HashSet::new(),
+        }
+    }
+
+    pub fn team_name(&self) -> &str {
+        &self.team_name
+    }
+
+    /// The status of a run whose leader shut down while it was still
+    /// running, e.g. after Ctrl-C.
+    pub fn leader_shut_down(&self) -> TeamRunStatus {
+        TeamRunStatus::Failed(format!(
+            "The leader of team '{}' shut down before the team finished.",
+            self.team_name
+        ))
+    }
+
+    /// Update the run with an event of the leader's session.
+    pub fn on_event(&mut self, msg: &EventMsg) -> TeamRunStatus {
+        match msg {
+            EventMsg::TeamMemberAdded(ev) if ev.team_name == self.team_name => {
+                self.started.insert(ev.member.name.clone());
+            }
+            EventMsg::TeamError(ev)
+                if ev.team_name.as_deref() == Some(self.team_name.as_str())
+                    && self.started.len() < self.members.len() =>
+            {
+                return TeamRunStatus::Failed(format!(
+                    "Team '{}' could not be started: {}",
+                    self.team_name, ev.message
+                ));
+            }
+            EventMsg::TeamProgress(ev)
+                if ev.team_name == self.team_name
+                    && ev.tasks_total > 0
+                    && ev.tasks_completed == ev.tasks_total =>
+            {
+                return TeamRunStatus::Completed;
+            }
+            EventMsg::TeamBudgetExhausted(ev) if ev.team_name == self.team_name => {
+                self.exhausted.insert(ev.member_name.clone());
+                if self.exhausted.is_superset(&self.members) {
+                    return TeamRunStatus::Failed(format!(
+                        "Every member of team '{}' exhausted its budget before the tasks were done.",
+                        self.team_name
+                    ));
+                }
+            }
+            _ => {}
+        }
+        TeamRunStatus::Running
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::test_config;
+    use codex_protocol::protocol::AgentStatus;
+    use codex_protocol::protocol::TeamBudgetExhaustedEvent;
+    use codex_protocol::protocol::TeamErrorCode;
+    use codex_protocol::protocol::TeamErrorEvent;
+    use codex_protocol::protocol::TeamMemberEvent;
+    use codex_protocol::protocol::TeamMemberInfo;
+    use codex_protocol::protocol::TeamProgressEvent;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn builder_assembles_the_plan() {
+        let plan = TeamOrchestrator::new(test_config())
+            .name("review")
+            .member("reviewer", "Review.")
+            .task("Read the diff")
+            .task_with(TeamPlanTask {
+                id: "report".to_string(),
+                title: "Report findings".to_string(),
+                description: None,
+                assigned_to: Some("reviewer".to_string()),
+                depends_on: vec!["task-1".to_string()],
+            })
+            .into_plan();
+
+        assert_eq!(
+            plan,
+            TeamPlan {
+                name: "review".to_string(),
+                description: None,
+                display_mode: TeamDisplayMode::Inline,
+                work_stealing: false,
+                members: vec![TeamPlanMember {
+                    name: "reviewer".to_string(),
+                    role: None,
+                    prompt: "Review.".to_string(),
+                    max_tokens: None,
+                    max_turns: None,
+                }],
+                tasks: vec![
+                    TeamPlanTask {
+                        id: "task-1".to_string(),
+                        title: "Read the diff".to_string(),
+                        description: None,
+                        assigned_to: None,
+                        depends_on: Vec::new(),
+                    },
+                    TeamPlanTask {
+                        id: "report".to_string(),
+                        title: "Report findings".to_string(),
+                        description: None,
+                        assigned_to: Some("reviewer".to_string()),
+                        depends_on: vec!["task-1".to_string()],
+                    },
+                ],
+            }
+        );
+        assert_eq!(validate_plan(&plan), Ok(()));
+    }
+
+    #[test]
+    fn team_run_ends_when_tasks_complete_or_budgets_run_out() {
+        let plan = TeamOrchestrator::new(test_config())
+            .name("docs")
+            .member("alice", "Work.")
+            .member("bob", "Work.")
+            .into_plan();
+        let progress = |tasks_completed, tasks_total| {
+            EventMsg::TeamProgress(TeamProgressEvent {
+                team_name: "docs".to_string(),
+                tasks_completed,
+                tasks_total,
+                members_running: 2,
+                members_idle: 0,
+                members_errored: 0,
+                elapsed_secs: 0,
+            })
+        };
+        let exhausted = |member_name: &str| {
+            EventMsg::TeamBudgetExhausted(TeamBudgetExhaustedEvent {
+                team_name: "docs".to_string(),
+                member_name: member_name.to_string(),
+                thread_id: ThreadId::new(),
+                reason: "used 2 of 1 tokens".to_string(),
+            })
+        };
+        let added = |name: &str| {
+            EventMsg::TeamMemberAdded(TeamMemberEvent {
+                team_name: "docs".to_string(),
+                member: TeamMemberInfo {
+                    name: name.to_string(),
+                    thread_id: ThreadId::new(),
+                    role: None,
+                    status: AgentStatus::PendingInit,
+                },
+                previous_status: None,
+            })
+        };
+        let error = EventMsg::TeamError(TeamErrorEvent {
+            tool_name: "spawn_teammate".to_string(),
+            team_name: Some("docs".to_string()),
+            code: TeamErrorCode::SpawnFailed,
+            message: "failed to spawn teammate: boom".to_string(),
+        });
+
+        let mut run = TeamRun::new(&plan);
+        assert_eq!(run.on_event(&added("alice")), TeamRunStatus::Running);
+        assert_eq!(
+            run.on_event(&error),
+            TeamRunStatus::Failed(
+                "Team 'docs' could not be started: failed to spawn teammate: boom".to_string()
+            )
+        );
+
+        let mut run = TeamRun::new(&plan);
+        run.on_event(&added("alice"));
+        run.on_event(&added("bob"));
+        // Once the team is up, failed tool calls are the leader's to handle.
+        assert_eq!(run.on_event(&error), TeamRunStatus::Running);
+        assert_eq!(run.on_event(&progress(0, 0)), TeamRunStatus::Running);
+        assert_eq!(run.on_event(&progress(1, 2)), TeamRunStatus::Running);
+        assert_eq!(run.on_event(&progress(2, 2)), TeamRunStatus::Completed);
+
+        let mut run = TeamRun::new(&plan);
+        assert_eq!(run.on_event(&exhausted("alice")), TeamRunStatus::Running);
+        assert_eq!(
+            run.on_event(&exhausted("bob")),
+            TeamRunStatus::Failed(
+                "Every member of team 'docs' exhausted its budget before the tasks were done."
+                    .to_string()
+            )
+        );
+    }
+    #[test]
+    fn report_serializes_to_the_run_summary() {
+        let leader_thread_id = ThreadId::new();
+        let task = |id: &str, status| TeamTaskInfo {
+            id: id.to_string(),
+            title: id.to_string(),
+            description: None,
+            status,
+            assigned_to: None,
+            depends_on: Vec::new(),
+            result: None,
+            created_at: None,
+        };
+        let report = TeamRunReport {
+            team_name: "docs".to_string(),
+            leader_thread_id,
+            status: TeamRunStatus::Failed("stopped".to_string()),
+            tasks: vec![
+                task("outline", TeamTaskStatus::Completed),
+                task("write", TeamTaskStatus::InProgress),
+            ],
+        };
+
+        assert_eq!(
+            report
+                .incomplete_tasks()
+                .iter()
+                .map(|task| task.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["write"]
+        );
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            serde_json::json!({
+                "team_name": "docs",
+                "leader_thread_id": leader_thread_id.to_string(),
+                "status": "failed",
+                "message": "stopped",
+                "tasks": [
+                    {"id": "outline", "title": "outline", "status": "completed"},
+                    {"id": "write", "title": "write", "status": "in_progress"},
+                ],
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(TeamRunStatus::Completed).unwrap(),
+            serde_json::json!({"status": "completed"})
+        );
+    }
+}
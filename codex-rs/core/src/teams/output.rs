@@ -0,0 +1,168 @@
+//! Live teammate output and usage.
+//!
+//! Every spawned teammate gets a forwarder that follows the events of its
+//! session and re-emits parts of them in the leader's session, tagged with the
+//! team and teammate, so UIs can show what teammates are doing before they
+//! finish a turn: each token count update as `TeamMemberTokenUsage`, and, with
+//! `teams.teammate_output` set, its agent messages and commands as
+//! `TeamMemberOutput`.
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::config::types::TeammateOutput;
+use crate::teams::team_manager::MemberConfig;
+use codex_protocol::protocol::{
+    EventMsg, TeamMemberOutput, TeamMemberOutputEvent, TeamMemberTokenUsageEvent, TokenCountEvent,
+};
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+
+/// Forward the usage and output of `member` to the leader's session until the
+/// teammate shuts down, with output at the verbosity of
+/// `teams.teammate_output`.
+pub(crate) fn forward_teammate_events(
+    session: &Arc<Session>,
+    turn: Arc<TurnContext>,
+    team_name: String,
+    member: &MemberConfig,
+) {
+    let verbosity = turn.config.teams.teammate_output;
+    let weak_session = Arc::downgrade(session);
+    let member_name = member.name.clone();
+    let thread_id = member.thread_id;
+    tokio::spawn(async move {
+        let mut events = {
+            let Some(session) = weak_session.upgrade() else {
+                return;
+            };
+            match session
+                .services
+                .agent_control
+                .subscribe_events(thread_id)
+                .await
+            {
+                Ok(events) => events,
+                Err(e) => {
+                    tracing::debug!("not forwarding events of teammate {member_name}: {e}");
+                    return;
+                }
+            }
+        };
+        loop {
+            let msg = match events.recv().await {
+                Ok(msg) => msg,
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::debug!("skipped {skipped} events of teammate {member_name}");
+                    continue;
+                }
+                Err(RecvError::Closed) => break,
+            };
+            let forwarded = match msg {
+                EventMsg::ShutdownComplete => break,
+                EventMsg::TokenCount(TokenCountEvent {
+                    info: Some(info), ..
+                }) => EventMsg::TeamMemberTokenUsage(TeamMemberTokenUsageEvent {
+                    team_name: team_name.clone(),
+                    member_name: member_name.clone(),
+                    thread_id,
+                    total_token_usage: info.total_token_usage,
+                    last_token_usage: info.last_token_usage,
+                }),
+                msg => match teammate_output(&msg, verbosity) {
+                    Some(output) => EventMsg::TeamMemberOutput(TeamMemberOutputEvent {
+                        team_name: team_name.clone(),
+                        member_name: member_name.clone(),
+                        thread_id,
+                        output,
+                    }),
+                    None => continue,
+                },
+            };
+            let Some(session) = weak_session.upgrade() else {
+                break;
+            };
+            session.send_event(&turn, forwarded).await;
+        }
+    });
+}
+
+/// The part of a teammate's `msg` that is forwarded at `verbosity`, if any.
+fn teammate_output(msg: &EventMsg, verbosity: TeammateOutput) -> Option<TeamMemberOutput> {
+    let output = match (msg, verbosity) {
+        (_, TeammateOutput::Off) => return None,
+        (EventMsg::AgentMessage(ev), _) => TeamMemberOutput::AgentMessage {
+            message: ev.message.clone(),
+        },
+        (EventMsg::ExecCommandBegin(ev), _) => TeamMemberOutput::CommandBegin {
+            call_id: ev.call_id.clone(),
+            command: ev.command.clone(),
+        },
+        (EventMsg::ExecCommandEnd(ev), _) => TeamMemberOutput::CommandEnd {
+            call_id: ev.call_id.clone(),
+            exit_code: ev.exit_code,
+        },
+        (EventMsg::AgentMessageDelta(ev), TeammateOutput::Stream) => {
+            TeamMemberOutput::AgentMessageDelta {
+                delta: ev.delta.clone(),
+            }
+        }
+        (EventMsg::ExecCommandOutputDelta(ev), TeammateOutput::Stream) => {
+            TeamMemberOutput::CommandOutputDelta {
+                call_id: ev.call_id.clone(),
+                chunk: String::from_utf8_lossy(&ev.chunk).into_owned(),
+            }
+        }
+        _ => return None,
+    };
+    Some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::{
+        AgentMessageDeltaEvent, AgentMessageEvent, ExecCommandOutputDeltaEvent, ExecOutputStream,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn verbosity_selects_forwarded_events() {
+        let message = EventMsg::AgentMessage(AgentMessageEvent {
+            message: "done".to_string(),
+        });
+        let delta = EventMsg::AgentMessageDelta(AgentMessageDeltaEvent {
+            delta: "do".to_string(),
+        });
+        let output = EventMsg::ExecCommandOutputDelta(ExecCommandOutputDeltaEvent {
+            call_id: "call-1".to_string(),
+            stream: ExecOutputStream::Stdout,
+            chunk: b"ok\n".to_vec(),
+        });
+
+        assert_eq!(teammate_output(&message, TeammateOutput::Off), None);
+        assert_eq!(
+            teammate_output(&message, TeammateOutput::Messages),
+            Some(TeamMemberOutput::AgentMessage {
+                message: "done".to_string()
+            })
+        );
+        assert_eq!(teammate_output(&delta, TeammateOutput::Messages), None);
+        assert_eq!(
+            teammate_output(&delta, TeammateOutput::Stream),
+            Some(TeamMemberOutput::AgentMessageDelta {
+                delta: "do".to_string()
+            })
+        );
+        assert_eq!(
+            teammate_output(&output, TeammateOutput::Stream),
+            Some(TeamMemberOutput::CommandOutputDelta {
+                call_id: "call-1".to_string(),
+                chunk: "ok\n".to_string(),
+            })
+        );
+        assert_eq!(
+            teammate_output(&EventMsg::ShutdownComplete, TeammateOutput::Stream),
+            None
+        );
+    }
+}
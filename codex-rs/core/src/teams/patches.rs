@@ -0,0 +1,239 @@
+//! Patch-review workflow.
+//!
+//! Instead of editing the leader's checkout, a teammate (typically one in its
+//! own worktree or a read-only sandbox) sends its changes with `submit_patch`.
+//! The diff is stored under the team's `patches/`, out of reach of
+//! `store_artifact`, and recorded with its SHA-256 in the team's
+//! `patches.json`; the leader inspects it and approves or rejects it with
+//! `review_patch`, and only an approved patch is applied to the leader's tree
+//! with `apply_patch_from_teammate`. The hash is checked each time the diff
+//! is read back, so what gets applied is what was reviewed.
+
+use crate::teams::worktree::git;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
+use std::path::Path;
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// Review state of a submitted patch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PatchStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Applied,
+}
+
+/// Leader's verdict in `review_patch`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum PatchDecision {
+    Approve,
+    Reject,
+}
+
+/// A patch submitted by a teammate.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TeamPatch {
+    pub id: String,
+    /// Teammate that submitted the patch.
+    pub author: String,
+    pub description: String,
+    pub status: PatchStatus,
+    /// Files the patch touches, relative to the repository root.
+    pub files: Vec<String>,
+    /// SHA-256 of the diff as submitted, in hex.
+    pub sha256: String,
+    pub submitted_at: String,
+    /// The leader's comment from the last review.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// Contents of a team's `patches.json`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TeamPatches {
+    #[serde(default)]
+    pub patches: Vec<TeamPatch>,
+}
+
+impl TeamPatches {
+    /// Id for the next submitted patch: `patch-1`, `patch-2`, …
+    pub fn next_id(&self) -> String {
+        format!("patch-{}", self.patches.len() + 1)
+    }
+
+    pub fn get_mut(&mut self, id: &str) -> Option<&mut TeamPatch> {
+        self.patches.iter_mut().find(|patch| patch.id == id)
+    }
+}
+
+/// Hex SHA-256 of `diff`, as recorded in [`TeamPatch::sha256`].
+pub(crate) fn diff_sha256(diff: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(diff))
+}
+
+/// Files touched by the unified diff `patch`, in order of appearance.
+pub(crate) fn patch_files(patch: &str) -> Vec<String> {
+    let mut files: Vec<String> = Vec::new();
+    let mut previous = "";
+    for line in patch.lines() {
+        let file = if let Some(paths) = line.strip_prefix("diff --git ") {
+            paths.split_once(" b/").map(|(_, new)| new)
+        } else if let Some(new) = line.strip_prefix("+++ ")
+            && previous.starts_with("--- ")
+        {
+            // Plain unified diffs have no `diff --git` header.
+            let new = new.split('\t').next().unwrap_or(new);
+            (new != "/dev/null").then(|| new.strip_prefix("b/").unwrap_or(new))
+        } else {
+            None
+        };
+        if let Some(file) = file
+            && !files.iter().any(|known| known == file)
+        {
+            files.push(file.to_string());
+        }
+        previous = line;
+    }
+    files
+}
+
+/// Diff of the working tree at `cwd` against `base`, untracked files
+/// included, with paths relative to the repository root.
+pub(crate) async fn working_tree_diff(cwd: &Path, base: &str) -> std::io::Result<String> {
+    let repo_root = git(cwd, &["rev-parse", "--show-toplevel"]).await?;
+    let repo_root = Path::new(&repo_root);
+    let mut patch = with_trailing_newline(git(repo_root, &["diff", "--binary", base]).await?);
+    let untracked = git(repo_root, &["ls-files", "--others", "--exclude-standard"]).await?;
+    for file in untracked.lines() {
+        // `git diff --no-index` exits with 1 when the files differ.
+        let output = Command::new("git")
+            .args(["diff", "--no-index", "--binary", "/dev/null", file])
+            .current_dir(repo_root)
+            .kill_on_drop(true)
+            .output()
+            .await?;
+        if output.status.code() != Some(1) {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(std::io::Error::other(format!(
+                "failed to diff untracked file {file}: {}",
+                stderr.trim()
+            )));
+        }
+        patch.push_str(&String::from_utf8_lossy(&output.stdout));
+    }
+    Ok(patch)
+}
+
+/// Apply the unified diff `patch` to the repository containing `cwd`, or
+/// with `check_only` just test that it applies cleanly.
+pub(crate) async fn apply_patch(cwd: &Path, patch: &[u8], check_only: bool) -> std::io::Result<()> {
+    let repo_root = git(cwd, &["rev-parse", "--show-toplevel"]).await?;
+    let mut args = vec!["apply"];
+    if check_only {
+        args.push("--check");
+    }
+    args.push("-");
+    let mut child = Command::new("git")
+        .args(&args)
+        .current_dir(&repo_root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(patch).await?;
+    }
+    let output = child.wait_with_output().await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(std::io::Error::other(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            stderr.trim()
+        )));
+    }
+    Ok(())
+}
+
+/// [`git`] trims its output, but `git apply` needs the diff's final newline.
+fn with_trailing_newline(diff: String) -> String {
+    if diff.is_empty() {
+        diff
+    } else {
+        format!("{diff}\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn patch_files_lists_each_file_once() {
+        let patch = "diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1 +1 @@\n-a\n+b\ndiff --git a/old.rs b/new.rs\nrename from old.rs\nrename to new.rs\n";
+        assert_eq!(
+            patch_files(patch),
+            vec!["src/lib.rs".to_string(), "new.rs".to_string()]
+        );
+        assert_eq!(
+            patch_files("--- a/README.md\n+++ b/README.md\n@@ -1 +1 @@\n-a\n+b\n"),
+            vec!["README.md".to_string()]
+        );
+        assert_eq!(patch_files("not a diff"), Vec::<String>::new());
+    }
+
+    #[tokio::test]
+    async fn working_tree_diff_applies_to_another_checkout() {
+        let tmp = TempDir::new().unwrap();
+        let teammate = tmp.path().join("teammate");
+        std::fs::create_dir_all(teammate.join("src")).unwrap();
+        std::fs::write(teammate.join("src/lib.rs"), "old\n").unwrap();
+        for args in [
+            &["init", "-q"][..],
+            &["config", "user.email", "test@example.com"],
+            &["config", "user.name", "Test"],
+            &["add", "-A"],
+            &["commit", "-q", "-m", "initial"],
+        ] {
+            git(&teammate, args).await.unwrap();
+        }
+        let leader = tmp.path().join("leader");
+        let (teammate_arg, leader_arg) = (teammate.to_string_lossy(), leader.to_string_lossy());
+        git(tmp.path(), &["clone", "-q", &teammate_arg, &leader_arg])
+            .await
+            .unwrap();
+
+        std::fs::write(teammate.join("src/lib.rs"), "new\n").unwrap();
+        std::fs::write(teammate.join("src/added.rs"), "added\n").unwrap();
+        let patch = working_tree_diff(&teammate.join("src"), "HEAD")
+            .await
+            .unwrap();
+        assert_eq!(
+            patch_files(&patch),
+            vec!["src/lib.rs".to_string(), "src/added.rs".to_string()]
+        );
+
+        apply_patch(&leader, patch.as_bytes(), true).await.unwrap();
+        apply_patch(&leader, patch.as_bytes(), false).await.unwrap();
+        assert_eq!(
+            std::fs::read_to_string(leader.join("src/lib.rs")).unwrap(),
+            "new\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(leader.join("src/added.rs")).unwrap(),
+            "added\n"
+        );
+        // It no longer applies once applied.
+        assert!(apply_patch(&leader, patch.as_bytes(), true).await.is_err());
+    }
+}
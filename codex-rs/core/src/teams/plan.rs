@@ -0,0 +1,226 @@
+//! Declarative team plans.
+//!
+//! A plan file describes a team up front: its members with their roles and
+//! prompts, and the initial tasks with their dependencies. `codex team run`
+//! loads one and submits it as `Op::RunTeamPlan`:
+//!
+//! ```toml
+//! name = "docs"
+//! description = "Rewrite the user guide"
+//!
+//! [[members]]
+//! name = "writer"
+//! role = "technical-writer"
+//! prompt = "Rewrite the chapters assigned to you."
+//!
+//! [[tasks]]
+//! id = "outline"
+//! title = "Outline the new guide"
+//! assigned_to = "writer"
+//!
+//! [[tasks]]
+//! id = "draft"
+//! title = "Draft every chapter"
+//! assigned_to = "writer"
+//! depends_on = ["outline"]
+//! ```
+//!
+//! Plans may also be written in YAML, with the same fields.
+
+use codex_protocol::protocol::TeamPlan;
+use std::collections::HashSet;
+use std::path::Path;
+use tokio::fs;
+
+/// Load and validate the plan at `path`. `.yaml` and `.yml` files are read
+/// as YAML, anything else as TOML.
+pub async fn load_plan(path: &Path) -> std::io::Result<TeamPlan> {
+    let contents = fs::read_to_string(path).await?;
+    let is_yaml = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| matches!(extension, "yaml" | "yml"));
+    let invalid = |message: String| std::io::Error::new(std::io::ErrorKind::InvalidData, message);
+    let plan: TeamPlan = if is_yaml {
+        serde_yaml::from_str(&contents).map_err(|e| invalid(e.to_string()))?
+    } else {
+        toml::from_str(&contents).map_err(|e| invalid(e.to_string()))?
+    };
+    validate_plan(&plan).map_err(invalid)?;
+    Ok(plan)
+}
+
+/// Check that member names and task ids are unique, that tasks are assigned
+/// to members of the plan, and that each task depends only on tasks listed
+/// before it, which also rules out cycles.
+pub fn validate_plan(plan: &TeamPlan) -> Result<(), String> {
+    if plan.members.is_empty() {
+        return Err(format!("plan for team '{}' has no members", plan.name));
+    }
+    let mut members = HashSet::new();
+    for member in &plan.members {
+        if !members.insert(member.name.as_str()) {
+            return Err(format!("member '{}' is listed twice", member.name));
+        }
+    }
+    let mut tasks = HashSet::new();
+    for task in &plan.tasks {
+        if let Some(assignee) = &task.assigned_to
+            && !members.contains(assignee.as_str())
+        {
+            return Err(format!(
+                "task '{}' is assigned to '{assignee}', who is not a member of the plan",
+                task.id
+            ));
+        }
+        if let Some(dependency) = task
+            .depends_on
+            .iter()
+            .find(|dependency| !tasks.contains(dependency.as_str()))
+        {
+            return Err(format!(
+                "task '{}' depends on '{dependency}', which is not listed before it",
+                task.id
+            ));
+        }
+        if !tasks.insert(task.id.as_str()) {
+            return Err(format!("task '{}' is listed twice", task.id));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::TeamDisplayMode;
+    use codex_protocol::protocol::TeamPlanMember;
+    use codex_protocol::protocol::TeamPlanTask;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn toml_and_yaml_plans_load_the_same() {
+        let dir = tempfile::tempdir().unwrap();
+        let toml_path = dir.path().join("plan.toml");
+        std::fs::write(
+            &toml_path,
+            r#"
+name = "docs"
+
+[[members]]
+name = "writer"
+role = "technical-writer"
+prompt = "Write."
+
+[[tasks]]
+id = "outline"
+title = "Outline"
+assigned_to = "writer"
+
+[[tasks]]
+id = "draft"
+title = "Draft"
+depends_on = ["outline"]
+"#,
+        )
+        .unwrap();
+        let yaml_path = dir.path().join("plan.yaml");
+        std::fs::write(
+            &yaml_path,
+            r#"
+name: docs
+members:
+  - name: writer
+    role: technical-writer
+    prompt: Write.
+tasks:
+  - id: outline
+    title: Outline
+    assigned_to: writer
+  - id: draft
+    title: Draft
+    depends_on: [outline]
+"#,
+        )
+        .unwrap();
+
+        let plan = load_plan(&toml_path).await.unwrap();
+        assert_eq!(
+            plan,
+            TeamPlan {
+                name: "docs".to_string(),
+                description: None,
+                display_mode: TeamDisplayMode::Inline,
+                work_stealing: false,
+                members: vec![TeamPlanMember {
+                    name: "writer".to_string(),
+                    role: Some("technical-writer".to_string()),
+                    prompt: "Write.".to_string(),
+                    max_tokens: None,
+                    max_turns: None,
+                }],
+                tasks: vec![
+                    TeamPlanTask {
+                        id: "outline".to_string(),
+                        title: "Outline".to_string(),
+                        description: None,
+                        assigned_to: Some("writer".to_string()),
+                        depends_on: Vec::new(),
+                    },
+                    TeamPlanTask {
+                        id: "draft".to_string(),
+                        title: "Draft".to_string(),
+                        description: None,
+                        assigned_to: None,
+                        depends_on: vec!["outline".to_string()],
+                    },
+                ],
+            }
+        );
+        assert_eq!(load_plan(&yaml_path).await.unwrap(), plan);
+    }
+
+    #[test]
+    fn validation_rejects_unknown_assignees_and_forward_dependencies() {
+        let task = |id: &str, assigned_to: Option<&str>, depends_on: &[&str]| TeamPlanTask {
+            id: id.to_string(),
+            title: id.to_string(),
+            description: None,
+            assigned_to: assigned_to.map(str::to_string),
+            depends_on: depends_on.iter().map(|id| (*id).to_string()).collect(),
+        };
+        let mut plan = TeamPlan {
+            name: "docs".to_string(),
+            description: None,
+            display_mode: TeamDisplayMode::default(),
+            work_stealing: false,
+            members: vec![TeamPlanMember {
+                name: "writer".to_string(),
+                role: None,
+                prompt: "Write.".to_string(),
+                max_tokens: None,
+                max_turns: None,
+            }],
+            tasks: vec![task("a", Some("writer"), &[]), task("b", None, &["a"])],
+        };
+        assert_eq!(validate_plan(&plan), Ok(()));
+
+        plan.tasks = vec![task("a", Some("editor"), &[])];
+        assert_eq!(
+            validate_plan(&plan),
+            Err("task 'a' is assigned to 'editor', who is not a member of the plan".to_string())
+        );
+
+        plan.tasks = vec![task("a", None, &["b"]), task("b", None, &["a"])];
+        assert_eq!(
+            validate_plan(&plan),
+            Err("task 'a' depends on 'b', which is not listed before it".to_string())
+        );
+
+        plan.tasks = vec![task("a", None, &["a"])];
+        assert_eq!(
+            validate_plan(&plan),
+            Err("task 'a' depends on 'a', which is not listed before it".to_string())
+        );
+    }
+}
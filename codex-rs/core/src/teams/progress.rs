@@ -0,0 +1,191 @@
+//! Team progress.
+//!
+//! Every team gets a background loop that polls its task list and teammates
+//! and emits a `TeamProgress` event in the leader's session whenever the
+//! counts change, so UIs can show one progress indicator instead of
+//! reconstructing it from task and membership events.
+
+use crate::agent::AgentStatus;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::teams::external::member_status;
+use crate::teams::team_manager::TeamConfig;
+use chrono::DateTime;
+use chrono::Utc;
+use codex_protocol::protocol::{EventMsg, TeamProgressEvent, TeamTaskInfo, TeamTaskStatus};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How often the reporter re-checks the team.
+const PROGRESS_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The numbers a `TeamProgress` event reports, apart from the elapsed time.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Counts {
+    tasks_completed: u32,
+    tasks_total: u32,
+    members_running: u32,
+    members_idle: u32,
+    members_errored: u32,
+}
+
+impl Counts {
+    fn of<'a>(statuses: impl IntoIterator<Item = &'a AgentStatus>, tasks: &[TeamTaskInfo]) -> Self {
+        let mut counts = Self::default();
+        for status in statuses {
+            match status {
+                AgentStatus::PendingInit | AgentStatus::Running => counts.members_running += 1,
+                AgentStatus::Completed(_) | AgentStatus::Shutdown => counts.members_idle += 1,
+                AgentStatus::Errored(_) | AgentStatus::NotFound => counts.members_errored += 1,
+            }
+        }
+        for task in tasks {
+            counts.tasks_total += 1;
+            if matches!(task.status, TeamTaskStatus::Completed) {
+                counts.tasks_completed += 1;
+            }
+        }
+        counts
+    }
+}
+
+/// Start reporting the progress of `team_name`. The reporter runs until the
+/// team is cleaned up, leadership moves to another thread, or the leader
+/// session is dropped.
+pub(crate) fn start_progress_reporter(
+    session: &Arc<Session>,
+    turn: Arc<TurnContext>,
+    team_name: String,
+) {
+    let weak_session = Arc::downgrade(session);
+    tokio::spawn(async move {
+        let mut reported: Option<Counts> = None;
+        let mut interval = tokio::time::interval(PROGRESS_POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            let Some(session) = weak_session.upgrade() else {
+                return;
+            };
+            let teams = &session.services.teams;
+            let config = match teams.load_config(&team_name).await {
+                Ok(config) => config,
+                Err(e) => {
+                    tracing::debug!("stopping progress reporter for {team_name}: {e}");
+                    return;
+                }
+            };
+            if config.leader_thread_id != session.conversation_id {
+                return;
+            }
+            let tasks = match teams.get_all_tasks(&team_name).await {
+                Ok(tasks) => tasks,
+                Err(e) => {
+                    tracing::debug!("stopping progress reporter for {team_name}: {e}");
+                    return;
+                }
+            };
+            let mut statuses = Vec::with_capacity(config.members.len());
+            for member in &config.members {
+                statuses.push(member_status(&session, &team_name, member).await);
+            }
+            let counts = Counts::of(&statuses, &tasks);
+            if reported == Some(counts) {
+                continue;
+            }
+            session
+                .send_event(
+                    &turn,
+                    EventMsg::TeamProgress(TeamProgressEvent {
+                        team_name: team_name.clone(),
+                        tasks_completed: counts.tasks_completed,
+                        tasks_total: counts.tasks_total,
+                        members_running: counts.members_running,
+                        members_idle: counts.members_idle,
+                        members_errored: counts.members_errored,
+                        elapsed_secs: elapsed_secs(&config, Utc::now()),
+                    }),
+                )
+                .await;
+            reported = Some(counts);
+        }
+    });
+}
+
+/// Whole seconds from the creation of the team to `now`; 0 if `created_at`
+/// does not parse.
+fn elapsed_secs(config: &TeamConfig, now: DateTime<Utc>) -> u64 {
+    DateTime::parse_from_rfc3339(&config.created_at)
+        .ok()
+        .and_then(|created| u64::try_from((now - created.with_timezone(&Utc)).num_seconds()).ok())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::ThreadId;
+    use codex_protocol::protocol::TeamDisplayMode;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+
+    #[test]
+    fn counts_group_teammates_and_tasks() {
+        let task = |id: &str, status| TeamTaskInfo {
+            id: id.to_string(),
+            title: id.to_string(),
+            description: None,
+            status,
+            assigned_to: None,
+            depends_on: Vec::new(),
+            result: None,
+            created_at: None,
+        };
+        let statuses = [
+            AgentStatus::PendingInit,
+            AgentStatus::Running,
+            AgentStatus::Completed(None),
+            AgentStatus::Errored("boom".to_string()),
+        ];
+        let tasks = [
+            task("task-1", TeamTaskStatus::Completed),
+            task("task-2", TeamTaskStatus::InProgress),
+            task("task-3", TeamTaskStatus::Pending),
+        ];
+
+        assert_eq!(
+            Counts::of(&statuses, &tasks),
+            Counts {
+                tasks_completed: 1,
+                tasks_total: 3,
+                members_running: 2,
+                members_idle: 1,
+                members_errored: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn elapsed_time_counts_from_team_creation() {
+        let mut config = TeamConfig {
+            name: "zeta".to_string(),
+            description: None,
+            metadata: HashMap::new(),
+            created_at: "2025-01-01T12:00:00+00:00".to_string(),
+            leader_thread_id: ThreadId::new(),
+            members: Vec::new(),
+            display_mode: TeamDisplayMode::default(),
+            delegation_mode: false,
+            work_stealing: false,
+            dry_run: false,
+            parent_team: None,
+            sub_teams: Vec::new(),
+        };
+        let now = DateTime::parse_from_rfc3339("2025-01-01T12:03:20+00:00")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(elapsed_secs(&config, now), 200);
+        config.created_at = "not a timestamp".to_string();
+        assert_eq!(elapsed_secs(&config, now), 0);
+    }
+}
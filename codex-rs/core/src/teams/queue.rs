@@ -0,0 +1,131 @@
+//! Concurrency limit for teammates.
+//!
+//! With `teams.max_concurrent_agents` set, `spawn_teammate` still creates a
+//! thread for every teammate, but teammates over the limit get no prompt yet
+//! and are persisted with status [`QUEUED_STATUS`]. Whenever a teammate
+//! finishes a turn, errors out or shuts down, the longest-queued teammates are
+//! sent their prompts until the limit is reached again.
+
+use crate::agent::AgentStatus;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::teams::team_manager::{MemberConfig, TeamConfig};
+use crate::teams::timeout::start_teammate_timeout;
+use codex_protocol::protocol::{EventMsg, TeamMemberEvent, TeamMemberInfo};
+use codex_protocol::user_input::UserInput;
+use std::sync::Arc;
+
+/// Persisted status of a teammate waiting for a free slot.
+pub(crate) const QUEUED_STATUS: &str = "queued";
+
+/// Number of teammates of `config` that are currently working.
+pub(crate) async fn running_teammates(session: &Session, config: &TeamConfig) -> usize {
+    let mut running = 0;
+    for member in config.members.iter().filter(|m| m.status != QUEUED_STATUS) {
+        if matches!(
+            session
+                .services
+                .agent_control
+                .get_status(member.thread_id)
+                .await,
+            AgentStatus::PendingInit | AgentStatus::Running
+        ) {
+            running += 1;
+        }
+    }
+    running
+}
+
+/// Queued teammates to start, oldest first, given `running` busy teammates
+/// and a limit of `limit`.
+fn next_queued(members: &[MemberConfig], running: usize, limit: usize) -> Vec<&MemberConfig> {
+    members
+        .iter()
+        .filter(|m| m.status == QUEUED_STATUS)
+        .take(limit.saturating_sub(running))
+        .collect()
+}
+
+/// Start as many queued teammates of `team_name` as there are free slots.
+pub(crate) async fn start_queued_teammates(
+    session: &Arc<Session>,
+    turn: &Arc<TurnContext>,
+    team_name: &str,
+) -> std::io::Result<()> {
+    let Some(limit) = turn.config.teams.max_concurrent_agents else {
+        return Ok(());
+    };
+    let teams = &session.services.teams;
+    let config = teams.load_config(team_name).await?;
+    let running = running_teammates(session, &config).await;
+    for member in next_queued(&config.members, running, limit) {
+        let items = vec![UserInput::Text {
+            text: member.prompt.clone().unwrap_or_default(),
+            text_elements: Vec::new(),
+        }];
+        let previous_status = session
+            .services
+            .agent_control
+            .get_status(member.thread_id)
+            .await;
+        if let Err(e) = session
+            .services
+            .agent_control
+            .send_input(member.thread_id, items)
+            .await
+        {
+            tracing::warn!("failed to start queued teammate {}: {e}", member.name);
+            continue;
+        }
+        teams
+            .manager()
+            .update_member_status(team_name, &member.name, "running")
+            .await?;
+        start_teammate_timeout(session, Arc::clone(turn), team_name.to_string(), member);
+        session
+            .send_event(
+                turn,
+                EventMsg::TeamMemberStatusChanged(TeamMemberEvent {
+                    team_name: team_name.to_string(),
+                    member: TeamMemberInfo {
+                        name: member.name.clone(),
+                        thread_id: member.thread_id,
+                        role: member.role.clone(),
+                        status: AgentStatus::Running,
+                    },
+                    previous_status: Some(previous_status),
+                }),
+            )
+            .await;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::ThreadId;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn next_queued_fills_free_slots_in_spawn_order() {
+        let member = |name: &str, status: &str| MemberConfig {
+            name: name.to_string(),
+            thread_id: ThreadId::new(),
+            status: status.to_string(),
+            ..Default::default()
+        };
+        let members = vec![
+            member("alice", "running"),
+            member("bob", QUEUED_STATUS),
+            member("carol", QUEUED_STATUS),
+            member("dave", QUEUED_STATUS),
+        ];
+
+        let names = |queued: Vec<&MemberConfig>| -> Vec<String> {
+            queued.into_iter().map(|m| m.name.clone()).collect()
+        };
+        assert_eq!(names(next_queued(&members, 1, 3)), vec!["bob", "carol"]);
+        assert_eq!(names(next_queued(&members, 3, 3)), Vec::<String>::new());
+    }
+}
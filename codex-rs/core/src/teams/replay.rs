@@ -0,0 +1,281 @@
+//! Replaying a team's trace.
+//!
+//! A team's `events.jsonl` holds every team event and tool call of its run
+//! (see [`crate::teams::event_log`]). [`TeamReplayState`] rebuilds the team's
+//! members and tasks from it one entry at a time, without running any model,
+//! so `codex team replay` can show how a leader orchestrated a run step by
+//! step.
+
+use crate::teams::event_log::TeamTraceEntry;
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::AgentStatus;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::TeamMemberInfo;
+use codex_protocol::protocol::TeamTaskInfo;
+use codex_protocol::protocol::TeamTaskStatus;
+use serde::Serialize;
+
+/// Longest tool call arguments or output [`describe`] shows.
+const MAX_DESCRIBED_LEN: usize = 120;
+
+/// A team as of some entry of its trace.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct TeamReplayState {
+    pub team_name: Option<String>,
+    pub leader_thread_id: Option<ThreadId>,
+    pub members: Vec<TeamMemberInfo>,
+    pub tasks: Vec<TeamTaskInfo>,
+    pub messages: usize,
+    pub tool_calls: usize,
+    pub failed_tool_calls: usize,
+    pub cleaned_up: bool,
+}
+
+impl TeamReplayState {
+    /// The state after each of `entries` in turn.
+    pub fn replay(entries: &[TeamTraceEntry]) -> Self {
+        let mut state = Self::default();
+        for entry in entries {
+            state.apply(entry);
+        }
+        state
+    }
+
+    pub fn apply(&mut self, entry: &TeamTraceEntry) {
+        let record = match entry {
+            TeamTraceEntry::Event(record) => record,
+            TeamTraceEntry::ToolCall(call) => {
+                self.tool_calls += 1;
+                if !call.success {
+                    self.failed_tool_calls += 1;
+                }
+                return;
+            }
+        };
+        match &record.msg {
+            EventMsg::TeamCreated(ev) => {
+                self.team_name = Some(ev.team_name.clone());
+                self.leader_thread_id = Some(ev.leader_thread_id);
+            }
+            EventMsg::TeamMemberAdded(ev) => {
+                self.members.retain(|m| m.name != ev.member.name);
+                self.members.push(ev.member.clone());
+            }
+            EventMsg::TeamMemberRemoved(ev) => {
+                self.members.retain(|m| m.name != ev.member.name);
+            }
+            EventMsg::TeamMemberStatusChanged(ev) => {
+                if let Some(member) = self.members.iter_mut().find(|m| m.name == ev.member.name) {
+                    member.status = ev.member.status.clone();
+                }
+            }
+            EventMsg::TeamMemberRestarted(ev) => {
+                if let Some(member) = self.members.iter_mut().find(|m| m.name == ev.member_name) {
+                    member.thread_id = ev.thread_id;
+                    member.status = AgentStatus::Running;
+                }
+            }
+            EventMsg::TeamTaskCreated(ev) | EventMsg::TeamTaskUpdated(ev) => {
+                match self.tasks.iter_mut().find(|t| t.id == ev.task.id) {
+                    Some(task) => *task = ev.task.clone(),
+                    None => self.tasks.push(ev.task.clone()),
+                }
+            }
+            EventMsg::TeamMessageSent(_) => self.messages += 1,
+            EventMsg::TeamRenamed(ev) => self.team_name = Some(ev.new_name.clone()),
+            EventMsg::TeamLeaderChanged(ev) => self.leader_thread_id = Some(ev.leader_thread_id),
+            EventMsg::TeamCleanup(_) => self.cleaned_up = true,
+            _ => {}
+        }
+    }
+
+    /// Who `thread_id` is in the team: `leader`, a member's name, or the
+    /// thread id itself for a session that has left.
+    pub fn participant(&self, thread_id: ThreadId) -> String {
+        if self.leader_thread_id == Some(thread_id) {
+            return "leader".to_string();
+        }
+        self.members
+            .iter()
+            .find(|m| m.thread_id == thread_id)
+            .map(|m| m.name.clone())
+            .unwrap_or_else(|| thread_id.to_string())
+    }
+}
+
+/// One line saying what `entry` did.
+pub fn describe(entry: &TeamTraceEntry) -> String {
+    let record = match entry {
+        TeamTraceEntry::Event(record) => record,
+        TeamTraceEntry::ToolCall(call) => {
+            let outcome = if call.success {
+                "ok".to_string()
+            } else {
+                format!("error: {}", shorten(&call.output))
+            };
+            return format!(
+                "called {} {} -> {outcome}",
+                call.tool_name,
+                shorten(&call.arguments)
+            );
+        }
+    };
+    match &record.msg {
+        EventMsg::TeamCreated(ev) => format!("team '{}' created", ev.team_name),
+        EventMsg::TeamMemberAdded(ev) => format!(
+            "{} joined ({})",
+            ev.member.name,
+            status_name(&ev.member.status)
+        ),
+        EventMsg::TeamMemberRemoved(ev) => format!("{} left", ev.member.name),
+        EventMsg::TeamMemberStatusChanged(ev) => {
+            format!("{} is {}", ev.member.name, status_name(&ev.member.status))
+        }
+        EventMsg::TeamMemberRestarted(ev) => format!(
+            "{} restarted ({} of {})",
+            ev.member_name, ev.attempt, ev.max_restarts
+        ),
+        EventMsg::TeamTaskCreated(ev) => format!("task {} created: {}", ev.task.id, ev.task.title),
+        EventMsg::TeamTaskUpdated(ev) => {
+            let status = match ev.task.status {
+                TeamTaskStatus::Pending => "pending",
+                TeamTaskStatus::InProgress => "in progress",
+                TeamTaskStatus::Completed => "completed",
+            };
+            match &ev.task.assigned_to {
+                Some(assignee) => format!("task {} is {status} ({assignee})", ev.task.id),
+                None => format!("task {} is {status}", ev.task.id),
+            }
+        }
+        EventMsg::TeamMessageSent(ev) => format!("message from {} to {}", ev.from, ev.to),
+        EventMsg::TeamRenamed(ev) => format!("team renamed to '{}'", ev.new_name),
+        EventMsg::TeamLeaderChanged(ev) => format!("leadership handed to {}", ev.leader_thread_id),
+        EventMsg::TeamError(ev) => format!("{} failed: {}", ev.tool_name, shorten(&ev.message)),
+        EventMsg::TeamCleanup(_) => "team cleaned up".to_string(),
+        msg => msg.to_string(),
+    }
+}
+
+/// How a member status reads in [`describe`], e.g. `shut down`.
+pub fn status_name(status: &AgentStatus) -> &'static str {
+    match status {
+        AgentStatus::PendingInit => "pending",
+        AgentStatus::Running => "running",
+        AgentStatus::Completed(_) => "completed",
+        AgentStatus::Errored(_) => "errored",
+        AgentStatus::Shutdown => "shut down",
+        AgentStatus::NotFound => "not found",
+    }
+}
+
+fn shorten(text: &str) -> String {
+    let text = text.replace('\n', " ");
+    match text.char_indices().nth(MAX_DESCRIBED_LEN) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::teams::event_log::TeamEventRecord;
+    use crate::teams::event_log::TeamToolCallRecord;
+    use codex_protocol::protocol::TeamCreatedEvent;
+    use codex_protocol::protocol::TeamDisplayMode;
+    use codex_protocol::protocol::TeamMemberEvent;
+    use codex_protocol::protocol::TeamTaskEvent;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn replay_rebuilds_the_team_step_by_step() {
+        let leader = ThreadId::new();
+        let writer = TeamMemberInfo {
+            name: "writer".to_string(),
+            thread_id: ThreadId::new(),
+            role: None,
+            status: AgentStatus::Running,
+        };
+        let task = TeamTaskInfo {
+            id: "task-1".to_string(),
+            title: "Outline".to_string(),
+            description: None,
+            status: TeamTaskStatus::InProgress,
+            assigned_to: Some("writer".to_string()),
+            depends_on: Vec::new(),
+            result: None,
+            created_at: None,
+        };
+        let event = |thread_id, msg| {
+            TeamTraceEntry::Event(TeamEventRecord {
+                timestamp: String::new(),
+                thread_id,
+                msg,
+            })
+        };
+        let entries = [
+            TeamTraceEntry::ToolCall(TeamToolCallRecord {
+                timestamp: String::new(),
+                thread_id: leader,
+                tool_name: "create_team".to_string(),
+                arguments: r#"{"name":"docs"}"#.to_string(),
+                output: r#"{"status":"created"}"#.to_string(),
+                success: true,
+            }),
+            event(
+                leader,
+                EventMsg::TeamCreated(TeamCreatedEvent {
+                    team_name: "docs".to_string(),
+                    leader_thread_id: leader,
+                    description: None,
+                    display_mode: TeamDisplayMode::default(),
+                }),
+            ),
+            event(
+                leader,
+                EventMsg::TeamMemberAdded(TeamMemberEvent {
+                    team_name: "docs".to_string(),
+                    member: writer.clone(),
+                    previous_status: None,
+                }),
+            ),
+            event(
+                writer.thread_id,
+                EventMsg::TeamTaskUpdated(TeamTaskEvent {
+                    team_name: "docs".to_string(),
+                    task: task.clone(),
+                }),
+            ),
+            TeamTraceEntry::ToolCall(TeamToolCallRecord {
+                timestamp: String::new(),
+                thread_id: leader,
+                tool_name: "shutdown_teammate".to_string(),
+                arguments: r#"{"team_name":"docs","name":"nobody"}"#.to_string(),
+                output: "teammate 'nobody' not found".to_string(),
+                success: false,
+            }),
+        ];
+
+        assert_eq!(
+            entries.iter().map(describe).collect::<Vec<_>>(),
+            vec![
+                r#"called create_team {"name":"docs"} -> ok"#.to_string(),
+                "team 'docs' created".to_string(),
+                "writer joined (running)".to_string(),
+                "task task-1 is in progress (writer)".to_string(),
+                r#"called shutdown_teammate {"team_name":"docs","name":"nobody"} -> error: teammate 'nobody' not found"#.to_string(),
+            ]
+        );
+
+        let halfway = TeamReplayState::replay(&entries[..2]);
+        assert_eq!(halfway.team_name.as_deref(), Some("docs"));
+        assert!(halfway.members.is_empty());
+
+        let state = TeamReplayState::replay(&entries);
+        assert_eq!(state.members, vec![writer.clone()]);
+        assert_eq!(state.tasks, vec![task]);
+        assert_eq!((state.tool_calls, state.failed_tool_calls), (2, 1));
+        assert_eq!(state.participant(leader), "leader");
+        assert_eq!(state.participant(writer.thread_id), "writer");
+    }
+}
@@ -0,0 +1,583 @@
+//! Reports of team runs.
+//!
+//! [`TeamRunReport`] condenses a team's recorded run, its `events.jsonl` (see
+//! [`crate::teams::event_log`]) and its `usage.json`, into a timeline of what
+//! happened, the outcome of each task with how long it took, and the tokens
+//! and estimated cost of each participant. It renders as Markdown or as a
+//! standalone HTML page, for `codex team report` and `/team export`.
+
+use crate::config::types::TeamsConfig;
+use crate::teams::event_log::TeamEventLog;
+use crate::teams::event_log::TeamTraceEntry;
+use crate::teams::replay::TeamReplayState;
+use crate::teams::replay::describe;
+use crate::teams::team_manager::TeamManager;
+use crate::teams::usage::TeamUsage;
+use chrono::DateTime;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::TeamTaskInfo;
+use codex_protocol::protocol::TeamTaskStatus;
+use codex_protocol::protocol::TokenUsage;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+/// A team's run, ready to render.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TeamRunReport {
+    pub team_name: String,
+    /// Timestamps of the first and last recorded events, RFC 3339.
+    pub started_at: Option<String>,
+    pub ended_at: Option<String>,
+    pub timeline: Vec<TimelineEntry>,
+    pub tasks: Vec<TaskOutcome>,
+    /// Usage per participant, the leader first.
+    pub costs: Vec<ParticipantCost>,
+}
+
+/// One event of the run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TimelineEntry {
+    pub timestamp: String,
+    /// `leader`, a member's name, or the thread id of a session that left.
+    pub participant: String,
+    pub what: String,
+}
+
+/// A task as the run left it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TaskOutcome {
+    pub task: TeamTaskInfo,
+    /// When the task was first in progress and when it was last completed.
+    pub started_at: Option<String>,
+    pub completed_at: Option<String>,
+    pub duration_secs: Option<i64>,
+}
+
+/// Tokens one participant used, summed over a teammate's restarts.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParticipantCost {
+    pub name: String,
+    pub usage: TokenUsage,
+    /// Estimated cost in US dollars, if token prices are configured.
+    pub cost_usd: Option<f64>,
+}
+
+impl TeamRunReport {
+    /// Build the report of `team_name` from its trace `entries` and `usage`,
+    /// pricing tokens at the `cents_per_million_*_tokens` of `teams`.
+    pub fn build(
+        team_name: &str,
+        entries: &[TeamTraceEntry],
+        usage: &TeamUsage,
+        teams: &TeamsConfig,
+    ) -> Self {
+        let mut state = TeamReplayState::default();
+        let mut timeline = Vec::new();
+        let mut started: BTreeMap<String, String> = BTreeMap::new();
+        let mut completed: BTreeMap<String, String> = BTreeMap::new();
+        for entry in entries {
+            state.apply(entry);
+            let TeamTraceEntry::Event(record) = entry else {
+                continue;
+            };
+            match &record.msg {
+                // Usage is in the cost breakdown, and progress reports only
+                // restate the tasks.
+                EventMsg::TeamMemberTokenUsage(_) | EventMsg::TeamProgress(_) => continue,
+                EventMsg::TeamTaskCreated(ev) | EventMsg::TeamTaskUpdated(ev) => {
+                    let timestamp = record.timestamp.clone();
+                    match ev.task.status {
+                        TeamTaskStatus::InProgress => {
+                            started.entry(ev.task.id.clone()).or_insert(timestamp);
+                        }
+                        TeamTaskStatus::Completed => {
+                            completed.insert(ev.task.id.clone(), timestamp);
+                        }
+                        TeamTaskStatus::Pending => {}
+                    }
+                }
+                _ => {}
+            }
+            timeline.push(TimelineEntry {
+                timestamp: record.timestamp.clone(),
+                participant: state.participant(record.thread_id),
+                what: describe(entry),
+            });
+        }
+
+        let tasks = state
+            .tasks
+            .iter()
+            .map(|task| {
+                let started_at = started.get(&task.id).cloned();
+                let completed_at = completed
+                    .get(&task.id)
+                    .filter(|_| task.status == TeamTaskStatus::Completed)
+                    .cloned();
+                let duration_secs = match (&started_at, &completed_at) {
+                    (Some(start), Some(end)) => seconds_between(start, end),
+                    _ => None,
+                };
+                TaskOutcome {
+                    task: task.clone(),
+                    started_at,
+                    completed_at,
+                    duration_secs,
+                }
+            })
+            .collect();
+
+        let mut by_participant: BTreeMap<&str, TokenUsage> = BTreeMap::new();
+        for thread in usage.threads.values() {
+            by_participant
+                .entry(thread.name.as_str())
+                .or_default()
+                .add_assign(&thread.usage);
+        }
+        let leader = by_participant.remove_entry("leader");
+        let costs = leader
+            .into_iter()
+            .chain(by_participant)
+            .map(|(name, usage)| ParticipantCost {
+                name: name.to_string(),
+                cost_usd: estimated_cost_usd(teams, &usage),
+                usage,
+            })
+            .collect();
+
+        Self {
+            team_name: state.team_name.unwrap_or_else(|| team_name.to_string()),
+            started_at: entries.first().map(|entry| entry.timestamp().to_string()),
+            ended_at: entries.last().map(|entry| entry.timestamp().to_string()),
+            timeline,
+            tasks,
+            costs,
+        }
+    }
+
+    /// Combined usage and cost of all participants.
+    fn total(&self) -> ParticipantCost {
+        let mut usage = TokenUsage::default();
+        let mut cost_usd = None;
+        for cost in &self.costs {
+            usage.add_assign(&cost.usage);
+            if let Some(cost) = cost.cost_usd {
+                *cost_usd.get_or_insert(0.0) += cost;
+            }
+        }
+        ParticipantCost {
+            name: "total".to_string(),
+            usage,
+            cost_usd,
+        }
+    }
+
+    /// One sentence on how long the run took and how far it got.
+    fn overview(&self) -> String {
+        let completed = self
+            .tasks
+            .iter()
+            .filter(|t| t.task.status == TeamTaskStatus::Completed)
+            .count();
+        let tasks = format!("{completed} of {} tasks completed", self.tasks.len());
+        match (&self.started_at, &self.ended_at) {
+            (Some(start), Some(end)) => {
+                let took = seconds_between(start, end)
+                    .map(|secs| format!(" ({})", format_duration(secs)))
+                    .unwrap_or_default();
+                format!(
+                    "Ran from {} to {}{took}; {tasks}.",
+                    format_time(start),
+                    format_time(end)
+                )
+            }
+            _ => format!("No recorded events; {tasks}."),
+        }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!(
+            "# Team run report: {}\n\n{}\n",
+            self.team_name,
+            self.overview()
+        );
+
+        out.push_str("\n## Tasks\n\n");
+        if self.tasks.is_empty() {
+            out.push_str("No tasks.\n");
+        } else {
+            out.push_str("| Task | Title | Assignee | Outcome | Took | Result |\n");
+            out.push_str("| --- | --- | --- | --- | --- | --- |\n");
+        }
+        for outcome in &self.tasks {
+            let task = &outcome.task;
+            let _ = writeln!(
+                out,
+                "| `{}` | {} | {} | {} | {} | {} |",
+                task.id,
+                markdown_cell(&task.title),
+                markdown_cell(task.assigned_to.as_deref().unwrap_or("-")),
+                task_status_label(&task.status),
+                outcome
+                    .duration_secs
+                    .map_or("-".to_string(), format_duration),
+                markdown_cell(task.result.as_deref().unwrap_or("-")),
+            );
+        }
+
+        out.push_str("\n## Cost\n\n");
+        if self.costs.is_empty() {
+            out.push_str("No recorded usage.\n");
+        } else {
+            out.push_str("| Participant | Input | Cached input | Output | Total | Est. cost |\n");
+            out.push_str("| --- | ---: | ---: | ---: | ---: | ---: |\n");
+            let total = self.total();
+            for (cost, bold) in self
+                .costs
+                .iter()
+                .map(|cost| (cost, ""))
+                .chain([(&total, "**")])
+            {
+                let usage = &cost.usage;
+                let _ = writeln!(
+                    out,
+                    "| {bold}{}{bold} | {} | {} | {} | {} | {} |",
+                    markdown_cell(&cost.name),
+                    usage.input_tokens,
+                    usage.cached_input_tokens,
+                    usage.output_tokens,
+                    usage.total_tokens,
+                    format_cost(cost.cost_usd),
+                );
+            }
+        }
+
+        out.push_str("\n## Timeline\n\n");
+        if self.timeline.is_empty() {
+            out.push_str("No recorded events.\n");
+        } else {
+            out.push_str("| Time | Who | What |\n| --- | --- | --- |\n");
+        }
+        for entry in &self.timeline {
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} |",
+                format_time(&entry.timestamp),
+                markdown_cell(&entry.participant),
+                markdown_cell(&entry.what),
+            );
+        }
+        out
+    }
+
+    /// The report as a standalone HTML page, styles inlined.
+    pub fn to_html(&self) -> String {
+        let title = html_escape(&format!("Team run report: {}", self.team_name));
+        let mut out = format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>{REPORT_CSS}</style>\n</head>\n<body>\n<h1>{title}</h1>\n<p>{}</p>\n",
+            html_escape(&self.overview())
+        );
+
+        out.push_str("<h2>Tasks</h2>\n");
+        if self.tasks.is_empty() {
+            out.push_str("<p>No tasks.</p>\n");
+        } else {
+            out.push_str(
+                "<table>\n<tr><th>Task</th><th>Title</th><th>Assignee</th><th>Outcome</th><th>Took</th><th>Result</th></tr>\n",
+            );
+            for outcome in &self.tasks {
+                let task = &outcome.task;
+                let status = task_status_label(&task.status);
+                let _ = writeln!(
+                    out,
+                    "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td class=\"{}\">{status}</td><td>{}</td><td class=\"result\">{}</td></tr>",
+                    html_escape(&task.id),
+                    html_escape(&task.title),
+                    html_escape(task.assigned_to.as_deref().unwrap_or("-")),
+                    status.replace(' ', "-"),
+                    outcome
+                        .duration_secs
+                        .map_or("-".to_string(), format_duration),
+                    html_escape(task.result.as_deref().unwrap_or("-")),
+                );
+            }
+            out.push_str("</table>\n");
+        }
+
+        out.push_str("<h2>Cost</h2>\n");
+        if self.costs.is_empty() {
+            out.push_str("<p>No recorded usage.</p>\n");
+        } else {
+            out.push_str(
+                "<table>\n<tr><th>Participant</th><th>Input</th><th>Cached input</th><th>Output</th><th>Total</th><th>Est. cost</th></tr>\n",
+            );
+            let total = self.total();
+            for (cost, class) in self
+                .costs
+                .iter()
+                .map(|cost| (cost, ""))
+                .chain([(&total, " class=\"total\"")])
+            {
+                let usage = &cost.usage;
+                let _ = writeln!(
+                    out,
+                    "<tr{class}><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>",
+                    html_escape(&cost.name),
+                    usage.input_tokens,
+                    usage.cached_input_tokens,
+                    usage.output_tokens,
+                    usage.total_tokens,
+                    format_cost(cost.cost_usd),
+                );
+            }
+            out.push_str("</table>\n");
+        }
+
+        out.push_str("<h2>Timeline</h2>\n");
+        if self.timeline.is_empty() {
+            out.push_str("<p>No recorded events.</p>\n");
+        } else {
+            out.push_str("<table>\n<tr><th>Time</th><th>Who</th><th>What</th></tr>\n");
+            for entry in &self.timeline {
+                let _ = writeln!(
+                    out,
+                    "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    html_escape(&format_time(&entry.timestamp)),
+                    html_escape(&entry.participant),
+                    html_escape(&entry.what),
+                );
+            }
+            out.push_str("</table>\n");
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+}
+
+/// Build the report of `team_name` from its files under `manager`'s root.
+pub async fn load_team_run_report(
+    manager: &TeamManager,
+    team_name: &str,
+    teams: &TeamsConfig,
+) -> std::io::Result<TeamRunReport> {
+    let log = TeamEventLog::new(manager.events_path(team_name));
+    let entries = match log.read_trace_from(0).await {
+        Ok((entries, _)) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(e),
+    };
+    let usage = manager.usage(team_name).await?;
+    Ok(TeamRunReport::build(team_name, &entries, &usage, teams))
+}
+
+const REPORT_CSS: &str = "body{font-family:system-ui,sans-serif;max-width:60rem;margin:2rem auto;padding:0 1rem;color:#222}\
+table{border-collapse:collapse;width:100%;margin-bottom:1.5rem}\
+th,td{border-bottom:1px solid #ddd;padding:.35rem .5rem;text-align:left;vertical-align:top}\
+th{background:#f5f5f5}\
+.num{text-align:right;font-variant-numeric:tabular-nums}\
+.total{font-weight:bold}\
+.completed{color:#1a7f37}.in-progress{color:#9a6700}.pending{color:#666}\
+.result{white-space:pre-wrap}";
+
+fn task_status_label(status: &TeamTaskStatus) -> &'static str {
+    match status {
+        TeamTaskStatus::Pending => "pending",
+        TeamTaskStatus::InProgress => "in progress",
+        TeamTaskStatus::Completed => "completed",
+    }
+}
+
+/// Estimated cost of `usage` in US dollars at the token prices of `teams`,
+/// or `None` if no prices are set. Cached input is priced as regular input.
+fn estimated_cost_usd(teams: &TeamsConfig, usage: &TokenUsage) -> Option<f64> {
+    if teams.cents_per_million_input_tokens.is_none()
+        && teams.cents_per_million_output_tokens.is_none()
+    {
+        return None;
+    }
+    let cents = |tokens: i64, price: Option<u64>| {
+        tokens.max(0) as f64 * price.unwrap_or(0) as f64 / 1_000_000.0
+    };
+    Some(
+        (cents(usage.input_tokens, teams.cents_per_million_input_tokens)
+            + cents(usage.output_tokens, teams.cents_per_million_output_tokens))
+            / 100.0,
+    )
+}
+
+fn format_cost(cost_usd: Option<f64>) -> String {
+    cost_usd.map_or("-".to_string(), |cost| format!("${cost:.2}"))
+}
+
+fn seconds_between(start: &str, end: &str) -> Option<i64> {
+    let start = DateTime::parse_from_rfc3339(start).ok()?;
+    let end = DateTime::parse_from_rfc3339(end).ok()?;
+    Some((end - start).num_seconds().max(0))
+}
+
+/// `secs` as e.g. `45s`, `3m 20s` or `1h 05m`.
+fn format_duration(secs: i64) -> String {
+    match secs {
+        ..60 => format!("{secs}s"),
+        60..3600 => format!("{}m {:02}s", secs / 60, secs % 60),
+        _ => format!("{}h {:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+/// An RFC 3339 `timestamp` as `YYYY-MM-DD HH:MM:SS`, or as is if it does
+/// not parse.
+fn format_time(timestamp: &str) -> String {
+    DateTime::parse_from_rfc3339(timestamp)
+        .map(|time| time.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|_| timestamp.to_string())
+}
+
+/// `text` on one line, with the pipes that would end a table cell escaped.
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', "<br>")
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::teams::event_log::TeamEventRecord;
+    use crate::teams::usage::ThreadUsage;
+    use codex_protocol::ThreadId;
+    use codex_protocol::protocol::AgentStatus;
+    use codex_protocol::protocol::TeamCreatedEvent;
+    use codex_protocol::protocol::TeamDisplayMode;
+    use codex_protocol::protocol::TeamMemberEvent;
+    use codex_protocol::protocol::TeamMemberInfo;
+    use codex_protocol::protocol::TeamTaskEvent;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn report_covers_timeline_outcomes_and_cost() {
+        let leader = ThreadId::new();
+        let writer = ThreadId::new();
+        let task = |status, result: Option<&str>| TeamTaskInfo {
+            id: "task-1".to_string(),
+            title: "Outline | intro".to_string(),
+            description: None,
+            status,
+            assigned_to: Some("writer".to_string()),
+            depends_on: Vec::new(),
+            result: result.map(str::to_string),
+            created_at: None,
+        };
+        let event = |timestamp: &str, thread_id, msg| {
+            TeamTraceEntry::Event(TeamEventRecord {
+                timestamp: timestamp.to_string(),
+                thread_id,
+                msg,
+            })
+        };
+        let entries = [
+            event(
+                "2026-10-14T09:00:00Z",
+                leader,
+                EventMsg::TeamCreated(TeamCreatedEvent {
+                    team_name: "docs".to_string(),
+                    leader_thread_id: leader,
+                    description: None,
+                    display_mode: TeamDisplayMode::default(),
+                }),
+            ),
+            event(
+                "2026-10-14T09:00:05Z",
+                leader,
+                EventMsg::TeamMemberAdded(TeamMemberEvent {
+                    team_name: "docs".to_string(),
+                    member: TeamMemberInfo {
+                        name: "writer".to_string(),
+                        thread_id: writer,
+                        role: None,
+                        status: AgentStatus::Running,
+                    },
+                    previous_status: None,
+                }),
+            ),
+            event(
+                "2026-10-14T09:00:10Z",
+                writer,
+                EventMsg::TeamTaskUpdated(TeamTaskEvent {
+                    team_name: "docs".to_string(),
+                    task: task(TeamTaskStatus::InProgress, None),
+                }),
+            ),
+            event(
+                "2026-10-14T09:03:30Z",
+                writer,
+                EventMsg::TeamTaskUpdated(TeamTaskEvent {
+                    team_name: "docs".to_string(),
+                    task: task(TeamTaskStatus::Completed, Some("Drafted <h1>.")),
+                }),
+            ),
+        ];
+        let thread = |name: &str, input_tokens, output_tokens| ThreadUsage {
+            name: name.to_string(),
+            usage: TokenUsage {
+                input_tokens,
+                output_tokens,
+                total_tokens: input_tokens + output_tokens,
+                ..TokenUsage::default()
+            },
+        };
+        let usage = TeamUsage {
+            threads: BTreeMap::from([
+                ("a".to_string(), thread("writer", 600_000, 100_000)),
+                ("b".to_string(), thread("leader", 400_000, 100_000)),
+            ]),
+        };
+        let teams = TeamsConfig {
+            cents_per_million_input_tokens: Some(100),
+            cents_per_million_output_tokens: Some(1000),
+            ..TeamsConfig::default()
+        };
+
+        let report = TeamRunReport::build("docs", &entries, &usage, &teams);
+
+        assert_eq!(
+            report.to_markdown(),
+            "\
+# Team run report: docs
+
+Ran from 2026-10-14 09:00:00 to 2026-10-14 09:03:30 (3m 30s); 1 of 1 tasks completed.
+
+## Tasks
+
+| Task | Title | Assignee | Outcome | Took | Result |
+| --- | --- | --- | --- | --- | --- |
+| `task-1` | Outline \\| intro | writer | completed | 3m 20s | Drafted <h1>. |
+
+## Cost
+
+| Participant | Input | Cached input | Output | Total | Est. cost |
+| --- | ---: | ---: | ---: | ---: | ---: |
+| leader | 400000 | 0 | 100000 | 500000 | $1.40 |
+| writer | 600000 | 0 | 100000 | 700000 | $1.60 |
+| **total** | 1000000 | 0 | 200000 | 1200000 | $3.00 |
+
+## Timeline
+
+| Time | Who | What |
+| --- | --- | --- |
+| 2026-10-14 09:00:00 | leader | team 'docs' created |
+| 2026-10-14 09:00:05 | leader | writer joined (running) |
+| 2026-10-14 09:00:10 | writer | task task-1 is in progress (writer) |
+| 2026-10-14 09:03:30 | writer | task task-1 is completed (writer) |
+"
+        );
+        let html = report.to_html();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<td class=\"result\">Drafted &lt;h1&gt;.</td>"));
+    }
+}
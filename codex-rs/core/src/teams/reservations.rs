@@ -0,0 +1,126 @@
+//! File reservations.
+//!
+//! Before editing, a teammate can reserve the files or directories it is
+//! about to change with `reserve_files`. Reservations live in the team's
+//! `reservations.json`; a reservation that overlaps one held by someone else
+//! (the same path, or one inside the other) is refused with the holder's
+//! name, so two teammates never edit the same files at once, even when they
+//! share the leader's checkout. Paths are stored relative to their repository
+//! root, so reservations also hold across worktrees.
+
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Contents of a team's `reservations.json`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reservations {
+    /// Holder of each reserved path: `"leader"` or a teammate name.
+    #[serde(default)]
+    pub paths: BTreeMap<String, String>,
+}
+
+impl Reservations {
+    /// Reserve every path in `paths` for `holder`, or none of them if any
+    /// overlaps a reservation of someone else. Returns the conflicting
+    /// reservations on refusal.
+    pub fn reserve(&mut self, holder: &str, paths: &[String]) -> Result<(), Vec<(String, String)>> {
+        let conflicts: Vec<(String, String)> = self
+            .paths
+            .iter()
+            .filter(|(reserved, reserved_by)| {
+                reserved_by.as_str() != holder && paths.iter().any(|path| overlaps(path, reserved))
+            })
+            .map(|(reserved, reserved_by)| (reserved.clone(), reserved_by.clone()))
+            .collect();
+        if !conflicts.is_empty() {
+            return Err(conflicts);
+        }
+        for path in paths {
+            self.paths.insert(path.clone(), holder.to_string());
+        }
+        Ok(())
+    }
+
+    /// Release the reservations of `holder` on `paths`, or all of them if
+    /// `paths` is `None`. Returns the released paths.
+    pub fn release(&mut self, holder: &str, paths: Option<&[String]>) -> Vec<String> {
+        let released: Vec<String> = self
+            .paths
+            .iter()
+            .filter(|(path, reserved_by)| {
+                reserved_by.as_str() == holder && paths.is_none_or(|paths| paths.contains(*path))
+            })
+            .map(|(path, _)| path.clone())
+            .collect();
+        for path in &released {
+            self.paths.remove(path);
+        }
+        released
+    }
+}
+
+/// Whether `a` and `b` are the same path or one contains the other.
+fn overlaps(a: &str, b: &str) -> bool {
+    let (a, b) = (Path::new(a), Path::new(b));
+    a.starts_with(b) || b.starts_with(a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn paths(paths: &[&str]) -> Vec<String> {
+        paths.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn overlapping_reservations_are_refused() {
+        let mut reservations = Reservations::default();
+        reservations
+            .reserve("alice", &paths(&["src/parser"]))
+            .unwrap();
+
+        assert_eq!(
+            reservations.reserve("bob", &paths(&["docs", "src/parser/lexer.rs"])),
+            Err(vec![("src/parser".to_string(), "alice".to_string())])
+        );
+        // Nothing was reserved by the refused call.
+        assert_eq!(reservations.paths.get("docs"), None);
+        // Prefixes only count per path component.
+        assert_eq!(
+            reservations.reserve("bob", &paths(&["src/parser2.rs"])),
+            Ok(())
+        );
+        // The holder can extend its own reservation.
+        assert_eq!(
+            reservations.reserve("alice", &paths(&["src/parser/lexer.rs"])),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn release_only_frees_the_holders_paths() {
+        let mut reservations = Reservations::default();
+        reservations
+            .reserve("alice", &paths(&["a.rs", "b.rs"]))
+            .unwrap();
+        reservations.reserve("bob", &paths(&["c.rs"])).unwrap();
+
+        assert_eq!(
+            reservations.release("bob", Some(paths(&["a.rs"]).as_slice())),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            reservations.release("alice", Some(paths(&["a.rs"]).as_slice())),
+            paths(&["a.rs"])
+        );
+        assert_eq!(reservations.release("alice", None), paths(&["b.rs"]));
+        assert_eq!(
+            reservations.paths,
+            BTreeMap::from([("c.rs".to_string(), "bob".to_string())])
+        );
+    }
+}
@@ -0,0 +1,151 @@
+//! Automatic restarts of errored teammates.
+//!
+//! Teammates spawned with `max_restarts`, or on a session whose config sets
+//! `teams.max_auto_restarts`, are respawned by their completion watcher when
+//! they error out, up to that many times. Each attempt first waits
+//! [`restart_backoff`], so a teammate failing on a persistent error does not
+//! spin.
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::config::Config;
+use crate::config::types::TeamsConfig;
+use crate::teams::delegation::restart_member;
+use crate::teams::team_manager::MemberConfig;
+use codex_protocol::protocol::{EventMsg, TeamMemberRestartedEvent};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Wait before the first restart; doubled for each further attempt.
+const RESTART_BACKOFF_BASE: Duration = Duration::from_secs(10);
+
+/// Longest wait between restarts.
+const RESTART_BACKOFF_MAX: Duration = Duration::from_secs(5 * 60);
+
+/// Automatic restarts allowed for `member`, or `None` if it has no restart
+/// policy.
+pub(crate) fn restart_limit(member: &MemberConfig, teams: &TeamsConfig) -> Option<u32> {
+    member
+        .max_restarts
+        .or(teams.max_auto_restarts)
+        .filter(|max| *max > 0)
+}
+
+/// Wait before restart number `attempt`, starting at 1.
+pub(crate) fn restart_backoff(attempt: u32) -> Duration {
+    let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+    RESTART_BACKOFF_BASE
+        .saturating_mul(factor)
+        .min(RESTART_BACKOFF_MAX)
+}
+
+/// Respawn `member`, which errored out with `error`, and emit
+/// `TeamMemberRestarted`. Returns `false` without restarting if the member
+/// left the team or moved to another thread in the meantime, or if the
+/// restart failed.
+pub(crate) async fn restart_errored(
+    session: &Arc<Session>,
+    turn: &Arc<TurnContext>,
+    team_name: &str,
+    member: &MemberConfig,
+    error: &str,
+    max_restarts: u32,
+    teammate_config: &Config,
+) -> bool {
+    let teams = &session.services.teams;
+    let still_member = teams.load_config(team_name).await.is_ok_and(|config| {
+        config
+            .members
+            .iter()
+            .any(|m| m.name == member.name && m.thread_id == member.thread_id)
+    });
+    if !still_member {
+        return false;
+    }
+    let attempt = match teams
+        .manager()
+        .record_restart(team_name, &member.name)
+        .await
+    {
+        Ok(attempt) => attempt,
+        Err(e) => {
+            tracing::warn!("failed to record restart of {}: {e}", member.name);
+            return false;
+        }
+    };
+    let tasks = match teams.get_all_tasks(team_name).await {
+        Ok(tasks) => tasks,
+        Err(e) => {
+            tracing::warn!("failed to load tasks of team {team_name}: {e}");
+            Vec::new()
+        }
+    };
+    let member = MemberConfig {
+        restarts: attempt,
+        ..member.clone()
+    };
+    tracing::info!(
+        "restarting errored teammate {} (attempt {attempt} of {max_restarts}): {error}",
+        member.name
+    );
+    match restart_member(session, turn, team_name, &member, &tasks, teammate_config).await {
+        Ok(thread_id) => {
+            session
+                .send_event(
+                    turn,
+                    EventMsg::TeamMemberRestarted(TeamMemberRestartedEvent {
+                        team_name: team_name.to_string(),
+                        member_name: member.name.clone(),
+                        previous_thread_id: member.thread_id,
+                        thread_id,
+                        attempt,
+                        max_restarts,
+                        error: error.to_string(),
+                    }),
+                )
+                .await;
+            true
+        }
+        Err(e) => {
+            tracing::warn!("failed to restart teammate {}: {e}", member.name);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::ThreadId;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn restart_backoff_doubles_up_to_the_cap() {
+        assert_eq!(restart_backoff(1), Duration::from_secs(10));
+        assert_eq!(restart_backoff(2), Duration::from_secs(20));
+        assert_eq!(restart_backoff(3), Duration::from_secs(40));
+        assert_eq!(restart_backoff(10), RESTART_BACKOFF_MAX);
+        assert_eq!(restart_backoff(u32::MAX), RESTART_BACKOFF_MAX);
+    }
+
+    #[test]
+    fn spawn_limit_overrides_team_default() {
+        let mut member = MemberConfig {
+            name: "alice".to_string(),
+            thread_id: ThreadId::new(),
+            status: "running".to_string(),
+            ..Default::default()
+        };
+        let teams = TeamsConfig {
+            max_auto_restarts: Some(3),
+            ..TeamsConfig::default()
+        };
+        assert_eq!(restart_limit(&member, &TeamsConfig::default()), None);
+        assert_eq!(restart_limit(&member, &teams), Some(3));
+
+        member.max_restarts = Some(0);
+        assert_eq!(restart_limit(&member, &teams), None);
+        member.max_restarts = Some(1);
+        assert_eq!(restart_limit(&member, &teams), Some(1));
+    }
+}
@@ -0,0 +1,193 @@
+//! Resuming a single teammate from the command line.
+//!
+//! `codex team resume-member` brings back one teammate whose thread stopped,
+//! e.g. because the session that led its team crashed, and runs it headlessly
+//! until its turn ends. The member keeps its thread when the thread's rollout
+//! is still on disk; otherwise a fresh thread is started with the member's
+//! stored prompt and the team config is pointed at it.
+
+use crate::config::Config;
+use crate::find_thread_path_by_id_str;
+use crate::teams::delegation::configure_member;
+use crate::teams::delegation::restart_prompt;
+use crate::teams::task_list::TaskList;
+use crate::teams::team_manager::MemberConfig;
+use crate::teams::team_manager::TeamManager;
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::TeamTaskInfo;
+use codex_protocol::protocol::TeamTaskStatus;
+use std::io;
+use std::path::PathBuf;
+
+/// How a teammate comes back.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemberResume {
+    pub team_name: String,
+    pub member: MemberConfig,
+    /// Rollout of the member's thread to reattach to; `None` starts a fresh
+    /// thread.
+    pub rollout_path: Option<PathBuf>,
+    /// First input of the resumed thread.
+    pub prompt: String,
+}
+
+impl MemberResume {
+    /// Look up `member_name` in `team_name` and set up `config` for its
+    /// thread. With `fresh`, or when its rollout is gone, the member is
+    /// respawned rather than reattached.
+    pub async fn prepare(
+        teams: &TeamManager,
+        tasks: &TaskList,
+        config: &mut Config,
+        team_name: &str,
+        member_name: &str,
+        fresh: bool,
+    ) -> io::Result<Self> {
+        let team = teams.load_config(team_name).await?;
+        let Some(member) = team.members.into_iter().find(|m| m.name == member_name) else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("team '{team_name}' has no member named '{member_name}'"),
+            ));
+        };
+        if member.is_external() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("'{member_name}' is an external agent and has no thread to resume"),
+            ));
+        }
+        if member.joined {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("'{member_name}' joined from another Codex process and runs there"),
+            ));
+        }
+        let team_tasks = tasks.get_all_tasks(team_name).await?;
+        let leader_cwd = config.cwd.clone();
+        configure_member(config, &member, &leader_cwd)
+            .await
+            .map_err(io::Error::other)?;
+
+        let rollout_path = if fresh {
+            None
+        } else {
+            find_thread_path_by_id_str(&config.codex_home, &member.thread_id.to_string()).await?
+        };
+        let prompt = match rollout_path {
+            Some(_) => reattach_prompt(&member, &team_tasks),
+            None => restart_prompt(&member, &team_tasks),
+        };
+        Ok(Self {
+            team_name: team_name.to_string(),
+            member,
+            rollout_path,
+            prompt,
+        })
+    }
+
+    /// Record in the team config that the member runs again, on `thread_id`.
+    pub async fn record(&self, teams: &TeamManager, thread_id: ThreadId) -> io::Result<()> {
+        if thread_id != self.member.thread_id {
+            teams
+                .update_member_thread(&self.team_name, &self.member.name, thread_id)
+                .await?;
+        }
+        teams
+            .update_member_status(&self.team_name, &self.member.name, "running")
+            .await
+    }
+}
+
+/// Prompt for a member reattached to its own thread, which already holds its
+/// original prompt and work so far.
+fn reattach_prompt(member: &MemberConfig, tasks: &[TeamTaskInfo]) -> String {
+    let mut prompt = "Your session stopped and has been resumed. Check your inbox and the \
+                      team's task list for anything that changed meanwhile, then carry on."
+        .to_string();
+    for task in tasks.iter().filter(|t| {
+        t.assigned_to.as_deref() == Some(member.name.as_str())
+            && matches!(t.status, TeamTaskStatus::InProgress)
+    }) {
+        prompt.push_str(&format!("\n\nResume task {}: {}", task.id, task.title));
+    }
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::test_config;
+    use codex_protocol::protocol::TeamDisplayMode;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn respawns_a_member_without_a_rollout() {
+        let tmp = TempDir::new().unwrap();
+        let teams = TeamManager::new(tmp.path().join("teams"));
+        let tasks = TaskList::new(tmp.path().join("tasks"));
+        teams
+            .create_team(
+                "docs",
+                ThreadId::new(),
+                None,
+                HashMap::new(),
+                TeamDisplayMode::default(),
+            )
+            .await
+            .unwrap();
+        let member = MemberConfig {
+            name: "alice".to_string(),
+            thread_id: ThreadId::new(),
+            status: "errored".to_string(),
+            prompt: Some("Write the outline.".to_string()),
+            cwd: Some(tmp.path().to_path_buf()),
+            ..Default::default()
+        };
+        teams.add_member("docs", member.clone()).await.unwrap();
+        tasks.init("docs").await.unwrap();
+        tasks
+            .create_task(
+                "docs",
+                TeamTaskInfo {
+                    id: "task-1".to_string(),
+                    title: "Outline".to_string(),
+                    description: None,
+                    status: TeamTaskStatus::InProgress,
+                    assigned_to: Some("alice".to_string()),
+                    depends_on: Vec::new(),
+                    result: None,
+                    created_at: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let mut config = test_config();
+        config.codex_home = tmp.path().join("home");
+        let resume = MemberResume::prepare(&teams, &tasks, &mut config, "docs", "alice", false)
+            .await
+            .unwrap();
+        assert_eq!(resume.rollout_path, None);
+        assert_eq!(
+            resume.prompt,
+            "Write the outline.\n\nYou were restarted. Resume task task-1: Outline"
+        );
+        assert_eq!(config.cwd, tmp.path());
+
+        let thread_id = ThreadId::new();
+        resume.record(&teams, thread_id).await.unwrap();
+        let stored = teams
+            .member_by_thread("docs", thread_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.status, "running");
+
+        let err = MemberResume::prepare(&teams, &tasks, &mut config, "docs", "bob", false)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+}
@@ -0,0 +1,109 @@
+//! Reusable teammate roles.
+//!
+//! A role is defined once in `~/.codex/roles/{role}.toml` and applies to
+//! every teammate spawned with that `role`, in any team:
+//!
+//! ```toml
+//! instructions = "Review every change for injection and authz bugs."
+//! model = "gpt-5-codex"
+//! allowed_tools = ["shell", "read_file", "grep_files"]
+//! ```
+//!
+//! Roles without a definition file stay free-form labels.
+
+use crate::config::Config;
+use crate::teams::default_roles_root;
+use serde::Deserialize;
+use std::path::Path;
+use tokio::fs;
+
+/// Contents of a role definition file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RoleDefinition {
+    /// Added to the teammate's developer instructions.
+    #[serde(default)]
+    pub instructions: Option<String>,
+    /// Model the teammate runs on instead of the leader's.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Tools the teammate is limited to. Team tools are always available.
+    #[serde(default)]
+    pub allowed_tools: Option<Vec<String>>,
+}
+
+/// Load the definition of `role` from `roles_root`, or `None` if the role
+/// has no definition file. Only plain names (letters, digits, `-`, `_`) are
+/// looked up.
+pub async fn load_role(roles_root: &Path, role: &str) -> std::io::Result<Option<RoleDefinition>> {
+    let is_plain_name = !role.is_empty()
+        && role
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !is_plain_name {
+        return Ok(None);
+    }
+    let path = roles_root.join(format!("{role}.toml"));
+    let contents = match fs::read_to_string(&path).await {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    toml::from_str(&contents)
+        .map(Some)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+/// Apply the definition of `role`, if it has one, to a teammate's config.
+/// Returns whether a definition was found.
+pub(crate) async fn apply_role(config: &mut Config, role: &str) -> std::io::Result<bool> {
+    let Some(definition) = load_role(&default_roles_root(), role).await? else {
+        return Ok(false);
+    };
+    if let Some(instructions) = definition.instructions {
+        config.developer_instructions = Some(match config.developer_instructions.take() {
+            Some(existing) => format!("{existing}\n\n{instructions}"),
+            None => instructions,
+        });
+    }
+    if let Some(model) = definition.model {
+        config.model = Some(model);
+    }
+    if let Some(allowed_tools) = definition.allowed_tools {
+        config.teams.allowed_tools = Some(allowed_tools);
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn loads_defined_roles_only() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("security-reviewer.toml"),
+            "instructions = \"Look for authz bugs.\"\nallowed_tools = [\"read_file\"]\n",
+        )
+        .unwrap();
+        std::fs::write(tmp.path().join("broken.toml"), "colour = \"blue\"\n").unwrap();
+
+        assert_eq!(
+            load_role(tmp.path(), "security-reviewer").await.unwrap(),
+            Some(RoleDefinition {
+                instructions: Some("Look for authz bugs.".to_string()),
+                model: None,
+                allowed_tools: Some(vec!["read_file".to_string()]),
+            })
+        );
+        assert_eq!(load_role(tmp.path(), "test-writer").await.unwrap(), None);
+        assert_eq!(
+            load_role(tmp.path(), "../security-reviewer").await.unwrap(),
+            None
+        );
+        assert!(load_role(tmp.path(), "broken").await.is_err());
+    }
+}
@@ -0,0 +1,188 @@
+//! Shared team scratchpad.
+//!
+//! Each team has a `scratchpad.md` next to its `config.json`: a markdown
+//! document every participant can read and edit, for notes that outlive
+//! point-to-point inbox messages (decisions, interfaces, findings). Sections
+//! are `## ` headings. Every read and write holds an advisory lock on the
+//! file, so concurrent edits from the leader and its teammates do not
+//! clobber each other.
+
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const MAX_LOCK_RETRIES: usize = 50;
+const LOCK_RETRY_SLEEP: Duration = Duration::from_millis(100);
+
+/// A team's `scratchpad.md`.
+pub struct Scratchpad {
+    path: PathBuf,
+}
+
+impl Scratchpad {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Current contents; empty if nothing has been written yet.
+    pub async fn read(&self) -> std::io::Result<String> {
+        self.edit(|_| false).await
+    }
+
+    /// Append a note from `author` at the end of the document.
+    pub async fn append(&self, author: &str, content: &str) -> std::io::Result<String> {
+        let note = format!("**{author}:** {}", content.trim());
+        self.edit(move |doc| {
+            if !doc.is_empty() {
+                doc.truncate(doc.trim_end().len());
+                doc.push_str("\n\n");
+            }
+            doc.push_str(&note);
+            doc.push('\n');
+            true
+        })
+        .await
+    }
+
+    /// Replace the body of the `## {heading}` section, adding the section at
+    /// the end if it does not exist yet.
+    pub async fn replace_section(&self, heading: &str, body: &str) -> std::io::Result<String> {
+        let heading = heading.trim().to_string();
+        let body = body.to_string();
+        self.edit(move |doc| {
+            *doc = replace_section(doc, &heading, &body);
+            true
+        })
+        .await
+    }
+
+    /// Run `edit` on the document under an exclusive lock, writing it back
+    /// if `edit` returns true. Returns the resulting document.
+    async fn edit(
+        &self,
+        edit: impl FnOnce(&mut String) -> bool + Send + 'static,
+    ) -> std::io::Result<String> {
+        let path = self.path.clone();
+        tokio::task::spawn_blocking(move || -> std::io::Result<String> {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let mut file = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(false)
+                .open(&path)?;
+            lock(&file, "the team scratchpad")?;
+            let mut doc = String::new();
+            file.read_to_string(&mut doc)?;
+            if edit(&mut doc) {
+                file.set_len(0)?;
+                file.seek(SeekFrom::Start(0))?;
+                file.write_all(doc.as_bytes())?;
+                file.flush()?;
+            }
+            Ok(doc)
+        })
+        .await?
+    }
+}
+
+/// Take the exclusive lock on `file`, retrying for a few seconds. The lock
+/// is released when the file is closed. `what` names the file in the error.
+pub(crate) fn lock(file: &File, what: &str) -> std::io::Result<()> {
+    for _ in 0..MAX_LOCK_RETRIES {
+        match file.try_lock() {
+            Ok(()) => return Ok(()),
+            Err(std::fs::TryLockError::WouldBlock) => std::thread::sleep(LOCK_RETRY_SLEEP),
+            Err(std::fs::TryLockError::Error(e)) => return Err(e),
+        }
+    }
+    Err(std::io::Error::new(
+        std::io::ErrorKind::WouldBlock,
+        format!("could not lock {what}; another agent is editing it"),
+    ))
+}
+
+/// `doc` with the body of `## {heading}` replaced by `body`, or with the
+/// section appended if it is missing.
+fn replace_section(doc: &str, heading: &str, body: &str) -> String {
+    let heading_line = format!("## {heading}");
+    let section = format!("{heading_line}\n\n{}\n", body.trim());
+    let lines: Vec<&str> = doc.lines().collect();
+    let Some(start) = lines
+        .iter()
+        .position(|line| line.trim_end() == heading_line)
+    else {
+        let mut doc = doc.trim_end().to_string();
+        if !doc.is_empty() {
+            doc.push_str("\n\n");
+        }
+        doc.push_str(&section);
+        return doc;
+    };
+    let end = lines[start + 1..]
+        .iter()
+        .position(|line| line.starts_with("## "))
+        .map_or(lines.len(), |offset| start + 1 + offset);
+
+    let mut out = String::new();
+    for line in &lines[..start] {
+        out.push_str(line);
+        out.push('\n');
+    }
+    out.push_str(&section);
+    if end < lines.len() {
+        out.push('\n');
+        for line in &lines[end..] {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn replace_section_rewrites_only_that_section() {
+        let doc = "# Notes\n\n## API\n\nold api\n\n## Decisions\n\nuse sqlite\n";
+        assert_eq!(
+            replace_section(doc, "API", "POST /items"),
+            "# Notes\n\n## API\n\nPOST /items\n\n## Decisions\n\nuse sqlite\n"
+        );
+        assert_eq!(
+            replace_section(doc, "Open questions", "none"),
+            "# Notes\n\n## API\n\nold api\n\n## Decisions\n\nuse sqlite\n\n## Open questions\n\nnone\n"
+        );
+        assert_eq!(replace_section("", "API", "GET /"), "## API\n\nGET /\n");
+    }
+
+    #[tokio::test]
+    async fn edits_persist_across_instances() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("team").join("scratchpad.md");
+        let pad = Scratchpad::new(path.clone());
+        assert_eq!(pad.read().await.unwrap(), "");
+
+        pad.append("alice", "Parser uses a Pratt loop.")
+            .await
+            .unwrap();
+        pad.replace_section("API", "POST /items").await.unwrap();
+        pad.append("bob", "Docs are in docs/api.md.").await.unwrap();
+
+        assert_eq!(
+            Scratchpad::new(path).read().await.unwrap(),
+            "**alice:** Parser uses a Pratt loop.\n\n## API\n\nPOST /items\n\n**bob:** Docs are in docs/api.md.\n"
+        );
+    }
+}
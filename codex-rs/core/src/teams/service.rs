@@ -0,0 +1,288 @@
+//! Session-scoped access to team storage.
+//!
+//! `TeamService` owns the session's `TeamManager` and `TaskList` and caches
+//! parsed team configs and task lists. Other sessions (teammates, other
+//! leaders) write the same files, so a cached entry is only reused while the
+//! file's modification time and length are unchanged. A write from this
+//! process invalidates every entry, even one the modification time would not
+//! reveal.
+
+use crate::teams::artifacts::ArtifactStore;
+use crate::teams::email::ErroredTeammates;
+use crate::teams::event_log::TeamEventLog;
+use crate::teams::inbox::Inbox;
+use crate::teams::scratchpad::Scratchpad;
+use crate::teams::task_list::TaskList;
+use crate::teams::team_manager::{TeamConfig, TeamManager, store_writes};
+use crate::teams::{default_tasks_root, default_teams_root};
+use codex_protocol::protocol::TeamTaskInfo;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::SystemTime;
+use tokio::fs;
+use tokio::sync::Mutex;
+
+/// Identifies one version of a file on disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    modified: SystemTime,
+    len: u64,
+}
+
+impl FileStamp {
+    async fn of(path: &Path) -> Option<Self> {
+        let metadata = fs::metadata(path).await.ok()?;
+        Some(Self {
+            modified: metadata.modified().ok()?,
+            len: metadata.len(),
+        })
+    }
+}
+
+struct Cached<T> {
+    stamp: FileStamp,
+    /// [`store_writes`] when the file was read.
+    writes: u64,
+    value: T,
+}
+
+/// Shared team storage for a session.
+pub(crate) struct TeamService {
+    manager: TeamManager,
+    tasks: TaskList,
+    configs: Mutex<HashMap<String, Cached<TeamConfig>>>,
+    task_lists: Mutex<HashMap<String, Cached<Vec<TeamTaskInfo>>>>,
+    errored_teammates: ErroredTeammates,
+}
+
+impl TeamService {
+    /// Service over the default `~/.codex/teams` and `~/.codex/tasks` roots.
+    pub(crate) fn new(max_members: usize) -> Self {
+        Self::with_roots(default_teams_root(), default_tasks_root(), max_members)
+    }
+
+    pub(crate) fn with_roots(teams_root: PathBuf, tasks_root: PathBuf, max_members: usize) -> Self {
+        Self {
+            manager: TeamManager::new(teams_root).with_max_members(max_members),
+            tasks: TaskList::new(tasks_root),
+            configs: Mutex::new(HashMap::new()),
+            task_lists: Mutex::new(HashMap::new()),
+            errored_teammates: ErroredTeammates::default(),
+        }
+    }
+
+    pub(crate) fn manager(&self) -> &TeamManager {
+        &self.manager
+    }
+
+    pub(crate) fn tasks(&self) -> &TaskList {
+        &self.tasks
+    }
+
+    /// Inboxes of `team_name`.
+    pub(crate) fn inbox(&self, team_name: &str) -> Inbox {
+        Inbox::new(self.manager.inboxes_dir(team_name))
+    }
+
+    /// Shared scratchpad of `team_name`.
+    pub(crate) fn scratchpad(&self, team_name: &str) -> Scratchpad {
+        Scratchpad::new(self.manager.scratchpad_path(team_name))
+    }
+
+    /// Event log of `team_name`.
+    pub(crate) fn event_log(&self, team_name: &str) -> TeamEventLog {
+        TeamEventLog::new(self.manager.events_path(team_name))
+    }
+
+    /// Shared artifact store of `team_name`.
+    pub(crate) fn artifacts(&self, team_name: &str) -> ArtifactStore {
+        ArtifactStore::new(self.manager.artifacts_dir(team_name))
+    }
+
+    /// Errored teammates counted towards `teams.email.error_threshold`.
+    pub(crate) fn errored_teammates(&self) -> &ErroredTeammates {
+        &self.errored_teammates
+    }
+
+    /// Like [`TeamManager::load_config`], served from cache while
+    /// `config.json` is unchanged.
+    pub(crate) async fn load_config(&self, team_name: &str) -> std::io::Result<TeamConfig> {
+        let writes = store_writes();
+        let stamp = FileStamp::of(&self.manager.config_path(team_name)).await;
+        let mut configs = self.configs.lock().await;
+        if let (Some(stamp), Some(cached)) = (stamp, configs.get(team_name))
+            && cached.stamp == stamp
+            && cached.writes == writes
+        {
+            return Ok(cached.value.clone());
+        }
+        let config = self.manager.load_config(team_name).await?;
+        match stamp {
+            Some(stamp) => {
+                configs.insert(
+                    team_name.to_string(),
+                    Cached {
+                        stamp,
+                        writes,
+                        value: config.clone(),
+                    },
+                );
+            }
+            None => {
+                configs.remove(team_name);
+            }
+        }
+        Ok(config)
+    }
+
+    /// Like [`TaskList::get_all_tasks`], served from cache while
+    /// `tasks.json` is unchanged.
+    pub(crate) async fn get_all_tasks(
+        &self,
+        team_name: &str,
+    ) -> std::io::Result<Vec<TeamTaskInfo>> {
+        let writes = store_writes();
+        let stamp = FileStamp::of(&self.tasks.tasks_path(team_name)).await;
+        let mut task_lists = self.task_lists.lock().await;
+        if let (Some(stamp), Some(cached)) = (stamp, task_lists.get(team_name))
+            && cached.stamp == stamp
+            && cached.writes == writes
+        {
+            return Ok(cached.value.clone());
+        }
+        let tasks = self.tasks.get_all_tasks(team_name).await?;
+        match stamp {
+            Some(stamp) => {
+                task_lists.insert(
+                    team_name.to_string(),
+                    Cached {
+                        stamp,
+                        writes,
+                        value: tasks.clone(),
+                    },
+                );
+            }
+            None => {
+                task_lists.remove(team_name);
+            }
+        }
+        Ok(tasks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::ThreadId;
+    use codex_protocol::protocol::{TeamDisplayMode, TeamTaskStatus};
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn cached_reads_follow_writes() {
+        let tmp = TempDir::new().unwrap();
+        let service =
+            TeamService::with_roots(tmp.path().join("teams"), tmp.path().join("tasks"), 8);
+        service
+            .manager()
+            .create_team(
+                "t",
+                ThreadId::new(),
+                None,
+                HashMap::new(),
+                TeamDisplayMode::default(),
+            )
+            .await
+            .unwrap();
+        service.tasks().init("t").await.unwrap();
+
+        let config = service.load_config("t").await.unwrap();
+        assert!(!config.delegation_mode);
+        assert!(!config.work_stealing);
+        assert!(service.get_all_tasks("t").await.unwrap().is_empty());
+
+        service
+            .manager()
+            .set_delegation_mode("t", true)
+            .await
+            .unwrap();
+        service
+            .manager()
+            .set_work_stealing("t", true)
+            .await
+            .unwrap();
+        service
+            .tasks()
+            .create_task(
+                "t",
+                TeamTaskInfo {
+                    id: "task-1".to_string(),
+                    title: "Write docs".to_string(),
+                    description: None,
+                    status: TeamTaskStatus::Pending,
+                    assigned_to: None,
+                    depends_on: Vec::new(),
+                    result: None,
+                    created_at: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let config = service.load_config("t").await.unwrap();
+        assert!(config.delegation_mode);
+        assert!(config.work_stealing);
+        let tasks = service.get_all_tasks("t").await.unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].id, "task-1");
+    }
+
+    #[tokio::test]
+    async fn own_writes_invalidate_even_with_an_unchanged_stamp() {
+        let tmp = TempDir::new().unwrap();
+        let service =
+            TeamService::with_roots(tmp.path().join("teams"), tmp.path().join("tasks"), 8);
+        service
+            .manager()
+            .create_team(
+                "t",
+                ThreadId::new(),
+                None,
+                HashMap::new(),
+                TeamDisplayMode::default(),
+            )
+            .await
+            .unwrap();
+        service
+            .manager()
+            .set_delegation_mode("t", true)
+            .await
+            .unwrap();
+        let path = service.manager().config_path("t");
+        let modified = std::fs::metadata(&path).unwrap().modified().unwrap();
+        assert!(service.load_config("t").await.unwrap().delegation_mode);
+
+        // Same length as before, and put the old modification time back.
+        service
+            .manager()
+            .set_delegation_mode("t", false)
+            .await
+            .unwrap();
+        service
+            .manager()
+            .set_work_stealing("t", true)
+            .await
+            .unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+
+        let config = service.load_config("t").await.unwrap();
+        assert!(!config.delegation_mode);
+        assert!(config.work_stealing);
+    }
+}
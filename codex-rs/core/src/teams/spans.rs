@@ -0,0 +1,333 @@
+//! Trace spans of team runs.
+//!
+//! Team events open and close `tracing` spans, which the OTEL trace exporter
+//! sends on like any other span: `team` from a team's creation to its
+//! cleanup, `teammate` from a member being spawned to being removed,
+//! `team_task` from a task being accepted to being completed, and a
+//! `team_message` for each message delivered. Teammate spans belong to their
+//! team's span, and tasks and messages to the span of the teammate working on
+//! or sending them. Teammate sessions are spawned inside their teammate span,
+//! so their turns belong to it too and a team run shows up as one trace
+//! continuing the leader turn that created the team.
+//!
+//! Teammates are other sessions of the same process, and a span is often
+//! ended by another session than the one that started it, so the spans are
+//! kept in a process-wide registry.
+
+use crate::teams::summary::member_status_label;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::TeamTaskInfo;
+use codex_protocol::protocol::TeamTaskStatus;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use tracing::Span;
+use tracing::field;
+use tracing::info_span;
+
+static TEAM_SPANS: LazyLock<Mutex<TeamSpans>> = LazyLock::new(Default::default);
+
+/// Open spans, by team name and then member name or task id.
+#[derive(Default)]
+struct TeamSpans {
+    teams: HashMap<String, Span>,
+    teammates: HashMap<(String, String), Span>,
+    tasks: HashMap<(String, String), Span>,
+}
+
+fn team_spans() -> MutexGuard<'static, TeamSpans> {
+    TEAM_SPANS
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Open or close the spans `msg` starts or ends.
+pub(crate) fn record_team_span(msg: &EventMsg) {
+    team_spans().record(msg);
+}
+
+/// Span of teammate `member_name`, opened if it has none yet, to spawn its
+/// session in.
+pub(crate) fn teammate_span(team_name: &str, member_name: &str, role: Option<&str>) -> Span {
+    team_spans().teammate(team_name, member_name, role)
+}
+
+/// Close the span of a teammate that could not be spawned.
+pub(crate) fn forget_teammate_span(team_name: &str, member_name: &str) {
+    team_spans()
+        .teammates
+        .remove(&(team_name.to_string(), member_name.to_string()));
+}
+
+impl TeamSpans {
+    fn record(&mut self, msg: &EventMsg) {
+        match msg {
+            EventMsg::TeamCreated(ev) => {
+                self.teams.entry(ev.team_name.clone()).or_insert_with(|| {
+                    info_span!(
+                        "team",
+                        team = %ev.team_name,
+                        leader_thread_id = %ev.leader_thread_id,
+                    )
+                });
+            }
+            EventMsg::TeamCleanup(ev) => {
+                let team = &ev.team_name;
+                self.teammates.retain(|(t, _), _| t != team);
+                self.tasks.retain(|(t, _), _| t != team);
+                self.teams.remove(team);
+            }
+            EventMsg::TeamRenamed(ev) => {
+                if let Some(span) = self.teams.remove(&ev.old_name) {
+                    span.record("team", ev.new_name.as_str());
+                    self.teams.insert(ev.new_name.clone(), span);
+                }
+                rename_team(&mut self.teammates, &ev.old_name, &ev.new_name);
+                rename_team(&mut self.tasks, &ev.old_name, &ev.new_name);
+            }
+            EventMsg::TeamMemberAdded(ev) => {
+                let member = &ev.member;
+                let span = self.teammate(&ev.team_name, &member.name, member.role.as_deref());
+                span.record("thread_id", field::display(member.thread_id));
+                span.record("status", member_status_label(&member.status));
+            }
+            EventMsg::TeamMemberStatusChanged(ev) => {
+                let key = (ev.team_name.clone(), ev.member.name.clone());
+                if let Some(span) = self.teammates.get(&key) {
+                    span.record("status", member_status_label(&ev.member.status));
+                }
+            }
+            EventMsg::TeamMemberRemoved(ev) => {
+                self.teammates
+                    .remove(&(ev.team_name.clone(), ev.member.name.clone()));
+            }
+            EventMsg::TeamTaskCreated(ev) | EventMsg::TeamTaskUpdated(ev) => {
+                self.record_task(&ev.team_name, &ev.task);
+            }
+            EventMsg::TeamMessageSent(ev) => {
+                // Only the size of the message; spans leave prompts out.
+                let parent = self
+                    .teammates
+                    .get(&(ev.team_name.clone(), ev.from.clone()))
+                    .cloned()
+                    .unwrap_or_else(|| self.team_span(&ev.team_name));
+                let _delivered = info_span!(
+                    parent: &parent,
+                    "team_message",
+                    team = %ev.team_name,
+                    from = %ev.from,
+                    to = %ev.to,
+                    bytes = ev.content.len(),
+                );
+            }
+            _ => {}
+        }
+    }
+
+    /// Open the task's span once it is accepted and close it once it is
+    /// completed, or released back to pending.
+    fn record_task(&mut self, team_name: &str, task: &TeamTaskInfo) {
+        let key = (team_name.to_string(), task.id.clone());
+        match task.status {
+            TeamTaskStatus::InProgress => {
+                if self.tasks.contains_key(&key) {
+                    return;
+                }
+                let parent = task
+                    .assigned_to
+                    .as_ref()
+                    .and_then(|name| self.teammates.get(&(team_name.to_string(), name.clone())))
+                    .cloned()
+                    .unwrap_or_else(|| self.team_span(team_name));
+                let span = info_span!(
+                    parent: &parent,
+                    "team_task",
+                    team = %team_name,
+                    task_id = %task.id,
+                    title = %task.title,
+                    assignee = task.assigned_to.as_deref(),
+                    outcome = field::Empty,
+                );
+                self.tasks.insert(key, span);
+            }
+            TeamTaskStatus::Completed => {
+                if let Some(span) = self.tasks.remove(&key) {
+                    span.record("outcome", "completed");
+                }
+            }
+            TeamTaskStatus::Pending => {
+                if let Some(span) = self.tasks.remove(&key) {
+                    span.record("outcome", "released");
+                }
+            }
+        }
+    }
+
+    fn teammate(&mut self, team_name: &str, member_name: &str, role: Option<&str>) -> Span {
+        let parent = self.team_span(team_name);
+        self.teammates
+            .entry((team_name.to_string(), member_name.to_string()))
+            .or_insert_with(|| {
+                info_span!(
+                    parent: &parent,
+                    "teammate",
+                    team = %team_name,
+                    member = %member_name,
+                    role,
+                    thread_id = field::Empty,
+                    status = field::Empty,
+                )
+            })
+            .clone()
+    }
+
+    /// The team's span, or the current span for teams created before this
+    /// process started.
+    fn team_span(&self, team_name: &str) -> Span {
+        self.teams
+            .get(team_name)
+            .cloned()
+            .unwrap_or_else(Span::current)
+    }
+}
+
+/// Move the entries of team `old_name` in `spans` to `new_name`.
+fn rename_team<V>(spans: &mut HashMap<(String, String), V>, old_name: &str, new_name: &str) {
+    *spans = std::mem::take(spans)
+        .into_iter()
+        .map(|((team, key), value)| {
+            let team = if team == old_name {
+                new_name.to_string()
+            } else {
+                team
+            };
+            ((team, key), value)
+        })
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::ThreadId;
+    use codex_protocol::protocol::AgentStatus;
+    use codex_protocol::protocol::TeamCleanupEvent;
+    use codex_protocol::protocol::TeamCreatedEvent;
+    use codex_protocol::protocol::TeamDisplayMode;
+    use codex_protocol::protocol::TeamMemberEvent;
+    use codex_protocol::protocol::TeamMemberInfo;
+    use codex_protocol::protocol::TeamMessageEvent;
+    use codex_protocol::protocol::TeamTaskEvent;
+    use pretty_assertions::assert_eq;
+    use std::sync::Arc;
+    use tracing::Subscriber;
+    use tracing::span::Attributes;
+    use tracing::span::Id;
+    use tracing_subscriber::layer::Context;
+    use tracing_subscriber::layer::Layer;
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::registry::LookupSpan;
+
+    /// Records which spans open, with their parent, and close.
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for Recorder {
+        fn on_new_span(&self, _attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+            let Some(span) = ctx.span(id) else {
+                return;
+            };
+            let parent = span.parent().map_or("none", |parent| parent.name());
+            let line = format!("open {} in {parent}", span.name());
+            self.0.lock().unwrap().push(line);
+        }
+
+        fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+            if let Some(span) = ctx.span(&id) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(format!("close {}", span.name()));
+            }
+        }
+    }
+
+    #[test]
+    fn team_events_nest_spans_under_the_team() {
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let subscriber = tracing_subscriber::registry().with(Recorder(Arc::clone(&lines)));
+        let leader = ThreadId::new();
+        let reviewer = TeamMemberInfo {
+            name: "reviewer".to_string(),
+            thread_id: ThreadId::new(),
+            role: None,
+            status: AgentStatus::Running,
+        };
+        let task = |status| TeamTaskInfo {
+            id: "task-1".to_string(),
+            title: "Review".to_string(),
+            description: None,
+            status,
+            assigned_to: Some("reviewer".to_string()),
+            depends_on: Vec::new(),
+            result: None,
+            created_at: None,
+        };
+        let events = [
+            EventMsg::TeamCreated(TeamCreatedEvent {
+                team_name: "zeta".to_string(),
+                leader_thread_id: leader,
+                description: None,
+                display_mode: TeamDisplayMode::default(),
+            }),
+            EventMsg::TeamMemberAdded(TeamMemberEvent {
+                team_name: "zeta".to_string(),
+                member: reviewer,
+                previous_status: None,
+            }),
+            EventMsg::TeamTaskUpdated(TeamTaskEvent {
+                team_name: "zeta".to_string(),
+                task: task(TeamTaskStatus::InProgress),
+            }),
+            EventMsg::TeamMessageSent(TeamMessageEvent {
+                team_name: "zeta".to_string(),
+                from: "reviewer".to_string(),
+                to: "leader".to_string(),
+                content: "Done.".to_string(),
+            }),
+            EventMsg::TeamTaskUpdated(TeamTaskEvent {
+                team_name: "zeta".to_string(),
+                task: task(TeamTaskStatus::Completed),
+            }),
+            EventMsg::TeamCleanup(TeamCleanupEvent {
+                team_name: "zeta".to_string(),
+                leader_thread_id: leader,
+            }),
+        ];
+
+        let mut spans = TeamSpans::default();
+        tracing::subscriber::with_default(subscriber, || {
+            spans.record(&events[0]);
+            // Spawning the teammate opens its span, which joining reuses.
+            let spawned = spans.teammate("zeta", "reviewer", None);
+            for event in &events[1..] {
+                spans.record(event);
+            }
+            drop(spawned);
+        });
+
+        assert_eq!(
+            *lines.lock().unwrap(),
+            vec![
+                "open team in none",
+                "open teammate in team",
+                "open team_task in teammate",
+                "open team_message in teammate",
+                "close team_message",
+                "close team_task",
+                "close teammate",
+                "close team",
+            ]
+        );
+    }
+}
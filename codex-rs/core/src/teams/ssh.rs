@@ -0,0 +1,241 @@
+//! Teammates that run on another machine over SSH.
+//!
+//! `spawn_teammate` with `kind: "ssh"` registers an external member on one of
+//! the hosts of `teams.ssh_hosts` whose deliveries each run `codex exec` on
+//! its host through `ssh`, e.g. to give builds and test runs a bigger machine
+//! than the leader's. The remote session gets the leader's sandbox; since it
+//! cannot ask for approval, whatever would need one is refused, and a leader
+//! that approves every untrusted command keeps it read-only. The remote session
+//! has no team of its own, so the team is bridged over the connection: the
+//! prompt of each delivery carries the [`ExternalMemberRequest`], with the
+//! message and the member's open tasks, and asks for an
+//! [`ExternalMemberReply`] as the final message, which is applied like the
+//! answer of the MCP-backed members of [`crate::teams::external`]. Later
+//! deliveries resume the same remote session, so the teammate keeps its
+//! context between messages.
+
+use crate::teams::external::ExternalMemberReply;
+use crate::teams::external::ExternalMemberRequest;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::SandboxPolicy;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// `kind` of a teammate that runs on another machine over SSH.
+pub const SSH_KIND: &str = "ssh";
+
+/// How long a remote run may take; the delivery waits for all of it.
+const SSH_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+/// Asks the remote session for an [`ExternalMemberReply`].
+const REPLY_INSTRUCTIONS: &str = "Work on it, then answer with only a JSON object of the form \
+{\"reply\": \"...\", \"task_updates\": [{\"task_id\": \"...\", \"status\": \"completed\", \"result\": \"...\"}]}: \
+`reply` is passed on to the leader, and `task_updates` lists the tasks you completed, with \
+their results, or started (status \"in_progress\").";
+
+/// The host an external teammate runs on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SshMemberSpec {
+    /// Destination given to `ssh`, e.g. `builder@build-box` or a `Host` of
+    /// `~/.ssh/config`.
+    pub host: String,
+    /// Directory on the host the remote session works in; the login
+    /// directory if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    /// Remote session of the deliveries so far, which the next one resumes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+/// What a remote run answered a delivery with.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SshAnswer {
+    pub reply: ExternalMemberReply,
+    pub session_id: Option<String>,
+}
+
+/// `codex exec --sandbox` mode of a remote session run for a leader with
+/// `sandbox` and `approval`. Remote runs never ask for approval, so they get
+/// no more than the leader is allowed without asking.
+pub(crate) fn remote_sandbox_mode(
+    sandbox: &SandboxPolicy,
+    approval: AskForApproval,
+) -> &'static str {
+    if approval == AskForApproval::UnlessTrusted {
+        return "read-only";
+    }
+    match sandbox {
+        SandboxPolicy::ReadOnly { .. } => "read-only",
+        // The leader's external sandbox does not reach the host.
+        SandboxPolicy::WorkspaceWrite { .. } | SandboxPolicy::ExternalSandbox { .. } => {
+            "workspace-write"
+        }
+        SandboxPolicy::DangerFullAccess => "danger-full-access",
+    }
+}
+
+/// Run the remote session of `spec` in `sandbox_mode` on `request` and wait
+/// for its answer.
+pub(crate) async fn send_to_ssh_member(
+    spec: &SshMemberSpec,
+    sandbox_mode: &str,
+    request: &ExternalMemberRequest<'_>,
+) -> Result<SshAnswer, String> {
+    let request_json = serde_json::to_string_pretty(request).map_err(|e| e.to_string())?;
+    let prompt = format!(
+        "You are `{}`, a teammate in the Codex team `{}`, running on this machine for a \
+         leader on another one. This is the team's delivery to you, with the message and \
+         your open tasks:\n\n{request_json}\n\n{REPLY_INSTRUCTIONS}",
+        request.member, request.team_name
+    );
+    let mut child = Command::new("ssh")
+        .args(["-o", "BatchMode=yes", "-T", "--", spec.host.as_str()])
+        .arg(remote_command(spec, sandbox_mode)?)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("failed to run ssh: {e}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(prompt.as_bytes())
+            .await
+            .map_err(|e| format!("failed to send the prompt: {e}"))?;
+    }
+    let output = tokio::time::timeout(SSH_TIMEOUT, child.wait_with_output())
+        .await
+        .map_err(|_| format!("no answer within {} minutes", SSH_TIMEOUT.as_secs() / 60))?
+        .map_err(|e| e.to_string())?;
+    let (session_id, answer) = read_run(&String::from_utf8_lossy(&output.stdout))?;
+    let Some(answer) = answer else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!(
+            "the remote run ended ({}) without an answer: {}",
+            output.status,
+            stderr.trim()
+        ));
+    };
+    Ok(SshAnswer {
+        reply: ExternalMemberReply::from_text(strip_code_fence(&answer).to_string()),
+        session_id,
+    })
+}
+
+/// The `codex exec` invocation run on the host, reading the prompt from
+/// stdin.
+fn remote_command(spec: &SshMemberSpec, sandbox_mode: &str) -> Result<String, String> {
+    let mut args = vec![
+        "codex",
+        "exec",
+        "--json",
+        "--sandbox",
+        sandbox_mode,
+        "--skip-git-repo-check",
+    ];
+    if let Some(cwd) = &spec.cwd {
+        args.extend(["--cd", cwd.as_str()]);
+    }
+    if let Some(session_id) = &spec.session_id {
+        args.extend(["resume", session_id.as_str()]);
+    }
+    args.push("-");
+    shlex::try_join(args).map_err(|e| format!("cannot quote the remote command: {e}"))
+}
+
+/// The session id and the final agent message of a `codex exec --json` run,
+/// or the error its turn failed with.
+fn read_run(stdout: &str) -> Result<(Option<String>, Option<String>), String> {
+    let mut session_id = None;
+    let mut answer = None;
+    for event in stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+    {
+        match event["type"].as_str() {
+            Some("thread.started") => session_id = event["thread_id"].as_str().map(str::to_string),
+            Some("item.completed") if event["item"]["type"] == "agent_message" => {
+                answer = event["item"]["text"].as_str().map(str::to_string);
+            }
+            Some("turn.failed") => {
+                let message = event["error"]["message"]
+                    .as_str()
+                    .unwrap_or("unknown error");
+                return Err(format!("the remote turn failed: {message}"));
+            }
+            _ => {}
+        }
+    }
+    Ok((session_id, answer))
+}
+
+/// `text` without the Markdown code fence models like to wrap JSON in.
+fn strip_code_fence(text: &str) -> &str {
+    let trimmed = text.trim();
+    trimmed
+        .strip_prefix("```json")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .and_then(|rest| rest.strip_suffix("```"))
+        .map_or(trimmed, str::trim)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::teams::external::ExternalTaskUpdate;
+    use codex_protocol::protocol::TeamTaskStatus;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn remote_runs_resume_their_session_and_answer_in_json() {
+        let spec = SshMemberSpec {
+            host: "builder@build-box".to_string(),
+            cwd: Some("/srv/my repo".to_string()),
+            session_id: Some("thread-1".to_string()),
+        };
+        let workspace_write = SandboxPolicy::new_workspace_write_policy();
+        let sandbox_mode = remote_sandbox_mode(&workspace_write, AskForApproval::OnRequest);
+        assert_eq!(
+            remote_command(&spec, sandbox_mode).unwrap(),
+            "codex exec --json --sandbox workspace-write --skip-git-repo-check --cd '/srv/my repo' resume thread-1 -"
+        );
+        assert_eq!(
+            remote_sandbox_mode(&workspace_write, AskForApproval::UnlessTrusted),
+            "read-only"
+        );
+
+        let stdout = [
+            r#"{"type":"thread.started","thread_id":"thread-1"}"#,
+            r#"{"type":"turn.started"}"#,
+            r#"{"type":"item.completed","item":{"id":"item_0","type":"reasoning","text":"Building."}}"#,
+            r#"{"type":"item.completed","item":{"id":"item_1","type":"agent_message","text":"```json\n{\"reply\": \"Tests pass.\", \"task_updates\": [{\"task_id\": \"task-1\", \"status\": \"completed\"}]}\n```"}}"#,
+            r#"{"type":"turn.completed","usage":{"input_tokens":10,"cached_input_tokens":0,"output_tokens":5}}"#,
+        ]
+        .join("\n");
+        let (session_id, answer) = read_run(&stdout).unwrap();
+        assert_eq!(session_id.as_deref(), Some("thread-1"));
+        assert_eq!(
+            ExternalMemberReply::from_text(strip_code_fence(&answer.unwrap()).to_string()),
+            ExternalMemberReply {
+                reply: Some("Tests pass.".to_string()),
+                task_updates: vec![ExternalTaskUpdate {
+                    task_id: "task-1".to_string(),
+                    status: TeamTaskStatus::Completed,
+                    result: None,
+                }],
+            }
+        );
+
+        let failed = r#"{"type":"turn.failed","error":{"message":"usage limit reached"}}"#;
+        assert_eq!(
+            read_run(failed),
+            Err("the remote turn failed: usage limit reached".to_string())
+        );
+    }
+}
@@ -0,0 +1,201 @@
+//! Compact, natural-language status of a team.
+//!
+//! `team_summary` folds teammate statuses, task progress, blockers, sub-team
+//! progress, and the leader's latest messages into a few lines so the leader
+//! can refresh its picture of the team in a single tool call.
+
+use crate::agent::AgentStatus;
+use crate::teams::inbox::InboxMessage;
+use codex_protocol::protocol::{TeamTaskInfo, TeamTaskStatus};
+use std::collections::HashSet;
+
+/// How many of the leader's most recent messages a summary quotes.
+pub(crate) const SUMMARY_RECENT_MESSAGES: usize = 3;
+
+/// Quoted messages longer than this are cut off.
+const MAX_QUOTED_MESSAGE_CHARS: usize = 120;
+
+/// Progress of a sub-team led by one of the team's teammates.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SubTeamSummary {
+    pub name: String,
+    /// Teammate leading the sub-team, when it still belongs to this team.
+    pub leader: Option<String>,
+    pub teammates: usize,
+    pub completed_tasks: usize,
+    pub total_tasks: usize,
+}
+
+/// Short label for a teammate's status, as reported to the leader.
+pub(crate) fn member_status_label(status: &AgentStatus) -> &'static str {
+    match status {
+        AgentStatus::PendingInit => "starting",
+        AgentStatus::Running => "running",
+        AgentStatus::Completed(_) => "idle",
+        AgentStatus::Errored(_) => "errored",
+        AgentStatus::Shutdown => "shut down",
+        AgentStatus::NotFound => "gone",
+    }
+}
+
+/// Errored teammates and pending tasks still waiting on unfinished
+/// dependencies.
+pub(crate) fn blockers(members: &[(&str, AgentStatus)], tasks: &[TeamTaskInfo]) -> Vec<String> {
+    let mut blockers: Vec<String> = members
+        .iter()
+        .filter_map(|(name, status)| match status {
+            AgentStatus::Errored(error) => Some(format!("{name} errored: {error}")),
+            _ => None,
+        })
+        .collect();
+    let completed: HashSet<&str> = tasks
+        .iter()
+        .filter(|t| matches!(t.status, TeamTaskStatus::Completed))
+        .map(|t| t.id.as_str())
+        .collect();
+    for task in tasks
+        .iter()
+        .filter(|t| matches!(t.status, TeamTaskStatus::Pending))
+    {
+        let waiting: Vec<&str> = task
+            .depends_on
+            .iter()
+            .map(String::as_str)
+            .filter(|dep| !completed.contains(dep))
+            .collect();
+        if !waiting.is_empty() {
+            blockers.push(format!("{} waits on {}", task.id, waiting.join(", ")));
+        }
+    }
+    blockers
+}
+
+/// A few lines describing the team's members, tasks, blockers, sub-teams,
+/// and the latest messages sent to the leader.
+pub(crate) fn team_summary(
+    team_name: &str,
+    members: &[(&str, AgentStatus)],
+    tasks: &[TeamTaskInfo],
+    sub_teams: &[SubTeamSummary],
+    recent_messages: &[InboxMessage],
+) -> String {
+    let count = |status: TeamTaskStatus| tasks.iter().filter(|t| t.status == status).count();
+    let mut lines = vec![format!(
+        "Team '{team_name}': {} teammates, {}/{} tasks completed ({} in progress, {} pending).",
+        members.len(),
+        count(TeamTaskStatus::Completed),
+        tasks.len(),
+        count(TeamTaskStatus::InProgress),
+        count(TeamTaskStatus::Pending),
+    )];
+    if !members.is_empty() {
+        let members: Vec<String> = members
+            .iter()
+            .map(|(name, status)| format!("{name} {}", member_status_label(status)))
+            .collect();
+        lines.push(format!("Teammates: {}.", members.join(", ")));
+    }
+    let blockers = blockers(members, tasks);
+    if !blockers.is_empty() {
+        lines.push(format!("Blockers: {}.", blockers.join("; ")));
+    }
+    if !sub_teams.is_empty() {
+        let sub_teams: Vec<String> = sub_teams
+            .iter()
+            .map(|sub_team| {
+                let leader = sub_team
+                    .leader
+                    .as_deref()
+                    .map(|leader| format!(" led by {leader}"))
+                    .unwrap_or_default();
+                format!(
+                    "'{}'{leader} ({} teammates, {}/{} tasks completed)",
+                    sub_team.name,
+                    sub_team.teammates,
+                    sub_team.completed_tasks,
+                    sub_team.total_tasks
+                )
+            })
+            .collect();
+        lines.push(format!("Sub-teams: {}.", sub_teams.join("; ")));
+    }
+    if !recent_messages.is_empty() {
+        let messages: Vec<String> = recent_messages
+            .iter()
+            .map(|m| format!("{}: \"{}\"", m.from, quote(&m.content)))
+            .collect();
+        lines.push(format!("Recent messages: {}.", messages.join("; ")));
+    }
+    lines.join("\n")
+}
+
+fn quote(content: &str) -> String {
+    let content = content.trim().replace('\n', " ");
+    if content.chars().count() <= MAX_QUOTED_MESSAGE_CHARS {
+        return content;
+    }
+    let truncated: String = content.chars().take(MAX_QUOTED_MESSAGE_CHARS).collect();
+    format!("{truncated}…")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn task(id: &str, status: TeamTaskStatus, depends_on: &[&str]) -> TeamTaskInfo {
+        TeamTaskInfo {
+            id: id.to_string(),
+            title: format!("Task {id}"),
+            description: None,
+            status,
+            assigned_to: None,
+            depends_on: depends_on.iter().map(ToString::to_string).collect(),
+            result: None,
+            created_at: None,
+        }
+    }
+
+    #[test]
+    fn summary_reports_progress_blockers_and_messages() {
+        let members = [
+            ("alice", AgentStatus::Running),
+            ("bob", AgentStatus::Errored("boom".to_string())),
+        ];
+        let tasks = [
+            task("task-1", TeamTaskStatus::Completed, &[]),
+            task("task-2", TeamTaskStatus::InProgress, &[]),
+            task("task-3", TeamTaskStatus::Pending, &["task-1", "task-2"]),
+        ];
+        let messages = [InboxMessage {
+            from: "alice".to_string(),
+            timestamp: "2026-01-01T00:00:00Z".to_string(),
+            content: "Parser done,\nstarting on docs".to_string(),
+            read: false,
+        }];
+        let sub_teams = [SubTeamSummary {
+            name: "docs-review".to_string(),
+            leader: Some("alice".to_string()),
+            teammates: 2,
+            completed_tasks: 1,
+            total_tasks: 4,
+        }];
+
+        assert_eq!(
+            team_summary("docs", &members, &tasks, &sub_teams, &messages),
+            "Team 'docs': 2 teammates, 1/3 tasks completed (1 in progress, 1 pending).\n\
+             Teammates: alice running, bob errored.\n\
+             Blockers: bob errored: boom; task-3 waits on task-2.\n\
+             Sub-teams: 'docs-review' led by alice (2 teammates, 1/4 tasks completed).\n\
+             Recent messages: alice: \"Parser done, starting on docs\"."
+        );
+    }
+
+    #[test]
+    fn summary_of_empty_team_is_one_line() {
+        assert_eq!(
+            team_summary("new", &[], &[], &[], &[]),
+            "Team 'new': 0 teammates, 0/0 tasks completed (0 in progress, 0 pending)."
+        );
+    }
+}
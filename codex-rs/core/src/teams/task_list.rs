@@ -4,6 +4,10 @@
 //! locking to prevent race conditions when multiple agents try to accept
 //! the same task.
 
+use crate::teams::doctor::{TeamIssue, dependency_cycles, set_aside};
+use crate::teams::task_sync::SyncedTasks;
+use crate::teams::team_manager::edit_json_locked;
+use crate::teams::team_manager::note_store_write;
 use codex_protocol::protocol::{TeamTaskInfo, TeamTaskStatus};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -38,7 +42,7 @@ impl TaskList {
     }
 
     /// Path to the tasks.json file.
-    fn tasks_path(&self, team_name: &str) -> PathBuf {
+    pub(crate) fn tasks_path(&self, team_name: &str) -> PathBuf {
         self.team_dir(team_name).join("tasks.json")
     }
 
@@ -50,6 +54,38 @@ impl TaskList {
         self.save(team_name, &data).await
     }
 
+    /// Path to the mapping of the team's tasks to items of `tracker`.
+    fn synced_tasks_path(&self, team_name: &str, tracker: &str) -> PathBuf {
+        self.team_dir(team_name)
+            .join(format!("sync-{tracker}.json"))
+    }
+
+    /// Which items of `tracker` the team's tasks are mirrored to.
+    pub async fn synced_tasks(
+        &self,
+        team_name: &str,
+        tracker: &str,
+    ) -> std::io::Result<SyncedTasks> {
+        let path = self.synced_tasks_path(team_name, tracker);
+        if !path.exists() {
+            return Ok(SyncedTasks::default());
+        }
+        let content = fs::read_to_string(&path).await?;
+        serde_json::from_str(&content)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    pub async fn save_synced_tasks(
+        &self,
+        team_name: &str,
+        tracker: &str,
+        synced: &SyncedTasks,
+    ) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(synced)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        fs::write(self.synced_tasks_path(team_name, tracker), json).await
+    }
+
     /// Load the current task list from disk.
     pub async fn load(&self, team_name: &str) -> std::io::Result<TaskListData> {
         let path = self.tasks_path(team_name);
@@ -65,7 +101,9 @@ impl TaskList {
     async fn save(&self, team_name: &str, data: &TaskListData) -> std::io::Result<()> {
         let json = serde_json::to_string_pretty(data)
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
-        fs::write(self.tasks_path(team_name), json).await
+        fs::write(self.tasks_path(team_name), json).await?;
+        note_store_write();
+        Ok(())
     }
 
     /// Add a new task to the list.
@@ -87,68 +125,172 @@ impl TaskList {
         team_name: &str,
         teammate_name: &str,
     ) -> std::io::Result<Option<TeamTaskInfo>> {
-        let mut data = self.load(team_name).await?;
-
-        // Collect completed task IDs for dependency resolution.
-        let completed: std::collections::HashSet<&str> = data
-            .tasks
-            .iter()
-            .filter(|t| matches!(t.status, TeamTaskStatus::Completed))
-            .map(|t| t.id.as_str())
-            .collect();
-
-        // Find the first pending task whose dependencies are all completed.
-        let idx = data.tasks.iter().position(|t| {
-            matches!(t.status, TeamTaskStatus::Pending)
-                && t.assigned_to.is_none()
-                && t.depends_on.iter().all(|dep| completed.contains(dep.as_str()))
-        });
-
-        if let Some(idx) = idx {
-            data.tasks[idx].status = TeamTaskStatus::InProgress;
-            data.tasks[idx].assigned_to = Some(teammate_name.to_string());
-            let task = data.tasks[idx].clone();
-            self.save(team_name, &data).await?;
-            Ok(Some(task))
-        } else {
-            Ok(None)
+        let path = self.tasks_path(team_name);
+        if !path.exists() {
+            return Ok(None);
         }
+        let teammate_name = teammate_name.to_string();
+        edit_json_locked(path, "the task list", move |data: &mut TaskListData| {
+            // Collect completed task IDs for dependency resolution.
+            let completed: std::collections::HashSet<String> = data
+                .tasks
+                .iter()
+                .filter(|t| matches!(t.status, TeamTaskStatus::Completed))
+                .map(|t| t.id.clone())
+                .collect();
+
+            // Find the first pending task, unassigned or reserved for this
+            // teammate, whose dependencies are all completed.
+            let Some(task) = data.tasks.iter_mut().find(|t| {
+                matches!(t.status, TeamTaskStatus::Pending)
+                    && t.assigned_to
+                        .as_deref()
+                        .is_none_or(|assignee| assignee == teammate_name)
+                    && t.depends_on.iter().all(|dep| completed.contains(dep))
+            }) else {
+                return (None, false);
+            };
+            task.status = TeamTaskStatus::InProgress;
+            task.assigned_to = Some(teammate_name);
+            (Some(task.clone()), true)
+        })
+        .await
     }
 
-    /// Mark a task as completed.
+    /// Mark a task as completed. Returns the updated task together with the
+    /// pending tasks that waited on it and have no unfinished dependencies
+    /// left, or `None` if there is no such task.
     pub async fn complete_task(
         &self,
         team_name: &str,
         task_id: &str,
-    ) -> std::io::Result<bool> {
+    ) -> std::io::Result<Option<(TeamTaskInfo, Vec<TeamTaskInfo>)>> {
         let mut data = self.load(team_name).await?;
-        if let Some(task) = data.tasks.iter_mut().find(|t| t.id == task_id) {
-            task.status = TeamTaskStatus::Completed;
-            self.save(team_name, &data).await?;
-            Ok(true)
+        let Some(task) = data.tasks.iter_mut().find(|t| t.id == task_id) else {
+            return Ok(None);
+        };
+        let was_completed = matches!(task.status, TeamTaskStatus::Completed);
+        task.status = TeamTaskStatus::Completed;
+        let task = task.clone();
+        self.save(team_name, &data).await?;
+
+        let unblocked = if was_completed {
+            Vec::new()
         } else {
-            Ok(false)
-        }
+            let completed: std::collections::HashSet<&str> = data
+                .tasks
+                .iter()
+                .filter(|t| matches!(t.status, TeamTaskStatus::Completed))
+                .map(|t| t.id.as_str())
+                .collect();
+            data.tasks
+                .iter()
+                .filter(|t| {
+                    matches!(t.status, TeamTaskStatus::Pending)
+                        && t.depends_on.iter().any(|dep| dep == task_id)
+                        && t.depends_on
+                            .iter()
+                            .all(|dep| completed.contains(dep.as_str()))
+                })
+                .cloned()
+                .collect()
+        };
+        Ok(Some((task, unblocked)))
     }
 
-    /// Assign a specific task to a teammate.
+    /// Assign a specific task to a teammate. Returns the updated task, or
+    /// `None` if there is no such task.
     pub async fn assign_task(
         &self,
         team_name: &str,
         task_id: &str,
         teammate_name: &str,
-    ) -> std::io::Result<bool> {
+    ) -> std::io::Result<Option<TeamTaskInfo>> {
         let mut data = self.load(team_name).await?;
-        if let Some(task) = data.tasks.iter_mut().find(|t| t.id == task_id) {
-            task.assigned_to = Some(teammate_name.to_string());
-            if matches!(task.status, TeamTaskStatus::Pending) {
-                task.status = TeamTaskStatus::InProgress;
-            }
+        let Some(task) = data.tasks.iter_mut().find(|t| t.id == task_id) else {
+            return Ok(None);
+        };
+        task.assigned_to = Some(teammate_name.to_string());
+        if matches!(task.status, TeamTaskStatus::Pending) {
+            task.status = TeamTaskStatus::InProgress;
+        }
+        let task = task.clone();
+        self.save(team_name, &data).await?;
+        Ok(Some(task))
+    }
+
+    /// Attach `result` to every task `teammate_name` has in progress, leaving
+    /// the tasks in progress. Returns the updated tasks.
+    pub async fn record_result(
+        &self,
+        team_name: &str,
+        teammate_name: &str,
+        result: &str,
+    ) -> std::io::Result<Vec<TeamTaskInfo>> {
+        let mut data = self.load(team_name).await?;
+        let mut updated = Vec::new();
+        for task in data.tasks.iter_mut().filter(|t| {
+            matches!(t.status, TeamTaskStatus::InProgress)
+                && t.assigned_to.as_deref() == Some(teammate_name)
+        }) {
+            task.result = Some(result.to_string());
+            updated.push(task.clone());
+        }
+        if !updated.is_empty() {
             self.save(team_name, &data).await?;
-            Ok(true)
-        } else {
-            Ok(false)
         }
+        Ok(updated)
+    }
+
+    /// Add `note` to the result of task `task_id`, after any result it
+    /// already has. Returns the updated task, or `None` if there is no such
+    /// task.
+    pub async fn append_result(
+        &self,
+        team_name: &str,
+        task_id: &str,
+        note: &str,
+    ) -> std::io::Result<Option<TeamTaskInfo>> {
+        let mut data = self.load(team_name).await?;
+        let Some(task) = data.tasks.iter_mut().find(|t| t.id == task_id) else {
+            return Ok(None);
+        };
+        task.result = Some(match task.result.take() {
+            Some(result) => format!("{result}\n\n{note}"),
+            None => note.to_string(),
+        });
+        let task = task.clone();
+        self.save(team_name, &data).await?;
+        Ok(Some(task))
+    }
+
+    /// Put every task `teammate_name` has in progress back to pending and
+    /// unassigned, so another teammate can accept it. Returns the released
+    /// tasks.
+    pub async fn release_tasks(
+        &self,
+        team_name: &str,
+        teammate_name: &str,
+    ) -> std::io::Result<Vec<TeamTaskInfo>> {
+        let path = self.tasks_path(team_name);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let teammate_name = teammate_name.to_string();
+        edit_json_locked(path, "the task list", move |data: &mut TaskListData| {
+            let mut released = Vec::new();
+            for task in data.tasks.iter_mut().filter(|t| {
+                matches!(t.status, TeamTaskStatus::InProgress)
+                    && t.assigned_to.as_deref() == Some(teammate_name.as_str())
+            }) {
+                task.status = TeamTaskStatus::Pending;
+                task.assigned_to = None;
+                released.push(task.clone());
+            }
+            let changed = !released.is_empty();
+            (released, changed)
+        })
+        .await
     }
 
     /// Get all tasks for display.
@@ -168,6 +310,147 @@ impl TaskList {
         }
         Ok(())
     }
+
+    /// Move a team's task list to a new team name. A missing task list is
+    /// not an error.
+    pub async fn rename(&self, old_name: &str, new_name: &str) -> std::io::Result<()> {
+        let dir = self.team_dir(old_name);
+        if !dir.exists() {
+            return Ok(());
+        }
+        fs::rename(&dir, self.team_dir(new_name)).await
+    }
+
+    /// Move the task list for a team to `{tasks_root}/.archive/{team_name}-{timestamp}`.
+    pub async fn archive(&self, team_name: &str) -> std::io::Result<()> {
+        let dir = self.team_dir(team_name);
+        if !dir.exists() {
+            return Ok(());
+        }
+        let archive_root = self.tasks_root.join(".archive");
+        fs::create_dir_all(&archive_root).await?;
+        let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ");
+        fs::rename(&dir, archive_root.join(format!("{team_name}-{stamp}"))).await
+    }
+
+    /// Names of the teams that have a task list, sorted. The archive is not
+    /// included.
+    pub async fn team_names(&self) -> std::io::Result<Vec<String>> {
+        let mut names = Vec::new();
+        if !self.tasks_root.exists() {
+            return Ok(names);
+        }
+        let mut entries = fs::read_dir(&self.tasks_root).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            if !entry.file_type().await?.is_dir() {
+                continue;
+            }
+            if let Some(name) = entry.file_name().to_str()
+                && !name.starts_with('.')
+            {
+                names.push(name.to_string());
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    /// Check a team's task list: it must parse, its tasks may only depend on
+    /// existing tasks and not in a cycle, and, when `members` is known, only
+    /// members may be assigned tasks.
+    pub async fn validate(
+        &self,
+        team_name: &str,
+        members: Option<&[String]>,
+    ) -> std::io::Result<Vec<TeamIssue>> {
+        let data = match self.load(team_name).await {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::InvalidData => {
+                return Ok(vec![TeamIssue::UnreadableFile {
+                    path: self.tasks_path(team_name),
+                    error: e.to_string(),
+                }]);
+            }
+            Err(e) => return Err(e),
+        };
+        let mut issues = Vec::new();
+        for task in &data.tasks {
+            if let Some(members) = members
+                && let Some(assignee) = &task.assigned_to
+                && !members.contains(assignee)
+            {
+                issues.push(TeamIssue::UnknownAssignee {
+                    task_id: task.id.clone(),
+                    assignee: assignee.clone(),
+                });
+            }
+            for dep in &task.depends_on {
+                if !data.tasks.iter().any(|t| &t.id == dep) {
+                    issues.push(TeamIssue::MissingDependency {
+                        task_id: task.id.clone(),
+                        dependency: dep.clone(),
+                    });
+                }
+            }
+        }
+        issues.extend(
+            dependency_cycles(&data.tasks)
+                .into_iter()
+                .map(|task_ids| TeamIssue::DependencyCycle { task_ids }),
+        );
+        Ok(issues)
+    }
+
+    /// Fix `issues`, as reported by [`TaskList::validate`]. Returns the fixed
+    /// ones.
+    pub async fn repair(
+        &self,
+        team_name: &str,
+        issues: &[TeamIssue],
+    ) -> std::io::Result<Vec<TeamIssue>> {
+        if let [TeamIssue::UnreadableFile { path, .. }] = issues {
+            set_aside(path).await?;
+            return Ok(issues.to_vec());
+        }
+        let mut data = self.load(team_name).await?;
+        let mut fixed = Vec::new();
+        for issue in issues {
+            let task_id = match issue {
+                TeamIssue::UnknownAssignee { task_id, .. }
+                | TeamIssue::MissingDependency { task_id, .. } => task_id,
+                // Dropping the edge from the last task back to the first
+                // breaks the cycle.
+                TeamIssue::DependencyCycle { task_ids } => match task_ids.last() {
+                    Some(last) => last,
+                    None => continue,
+                },
+                _ => continue,
+            };
+            let Some(task) = data.tasks.iter_mut().find(|t| &t.id == task_id) else {
+                continue;
+            };
+            match issue {
+                TeamIssue::UnknownAssignee { .. } => {
+                    task.assigned_to = None;
+                    if task.status == TeamTaskStatus::InProgress {
+                        task.status = TeamTaskStatus::Pending;
+                    }
+                }
+                TeamIssue::MissingDependency { dependency, .. } => {
+                    task.depends_on.retain(|dep| dep != dependency);
+                }
+                TeamIssue::DependencyCycle { task_ids } => {
+                    task.depends_on.retain(|dep| Some(dep) != task_ids.first());
+                }
+                _ => continue,
+            }
+            fixed.push(issue.clone());
+        }
+        if !fixed.is_empty() {
+            self.save(team_name, &data).await?;
+        }
+        Ok(fixed)
+    }
 }
 
 #[cfg(test)]
@@ -179,9 +462,12 @@ mod tests {
         TeamTaskInfo {
             id: id.to_string(),
             title: title.to_string(),
+            description: None,
             status: TeamTaskStatus::Pending,
             assigned_to: None,
             depends_on: depends.iter().map(|s| s.to_string()).collect(),
+            result: None,
+            created_at: None,
         }
     }
 
@@ -204,6 +490,128 @@ mod tests {
         assert!(accepted.is_none());
     }
 
+    #[tokio::test]
+    async fn accept_skips_tasks_reserved_for_others() {
+        let tmp = TempDir::new().unwrap();
+        let tl = TaskList::new(tmp.path().to_path_buf());
+        tl.init("team1").await.unwrap();
+
+        let mut reserved = make_task("t1", "For bob", &[]);
+        reserved.assigned_to = Some("bob".to_string());
+        tl.create_task("team1", reserved).await.unwrap();
+
+        let accepted = tl.accept_next_task("team1", "alice").await.unwrap();
+        assert!(accepted.is_none());
+
+        let accepted = tl.accept_next_task("team1", "bob").await.unwrap();
+        assert_eq!(accepted.map(|t| t.id), Some("t1".to_string()));
+    }
+
+    #[tokio::test]
+    async fn record_result_keeps_tasks_in_progress() {
+        let tmp = TempDir::new().unwrap();
+        let tl = TaskList::new(tmp.path().to_path_buf());
+        tl.init("team1").await.unwrap();
+
+        tl.create_task("team1", make_task("t1", "Parser", &[]))
+            .await
+            .unwrap();
+        tl.create_task("team1", make_task("t2", "Docs", &[]))
+            .await
+            .unwrap();
+        tl.accept_next_task("team1", "alice").await.unwrap();
+        tl.accept_next_task("team1", "bob").await.unwrap();
+
+        let updated = tl
+            .record_result("team1", "alice", "Parser rewritten")
+            .await
+            .unwrap();
+        assert_eq!(updated.len(), 1);
+        assert_eq!(updated[0].id, "t1");
+
+        let tasks = tl.get_all_tasks("team1").await.unwrap();
+        assert_eq!(tasks[0].status, TeamTaskStatus::InProgress);
+        assert_eq!(tasks[0].result.as_deref(), Some("Parser rewritten"));
+        assert_eq!(tasks[1].result, None);
+    }
+
+    #[tokio::test]
+    async fn append_result_keeps_earlier_results() {
+        let tmp = TempDir::new().unwrap();
+        let tl = TaskList::new(tmp.path().to_path_buf());
+        tl.init("team1").await.unwrap();
+
+        tl.create_task("team1", make_task("t1", "Parser", &[]))
+            .await
+            .unwrap();
+        tl.append_result("team1", "t1", "Parser rewritten")
+            .await
+            .unwrap();
+        let task = tl
+            .append_result("team1", "t1", "Merged into main")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            task.result.as_deref(),
+            Some("Parser rewritten\n\nMerged into main")
+        );
+        assert!(
+            tl.append_result("team1", "missing", "note")
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn released_tasks_can_be_accepted_by_others() {
+        let tmp = TempDir::new().unwrap();
+        let tl = TaskList::new(tmp.path().to_path_buf());
+        tl.init("team1").await.unwrap();
+
+        tl.create_task("team1", make_task("t1", "Parser", &[]))
+            .await
+            .unwrap();
+        tl.accept_next_task("team1", "alice").await.unwrap();
+
+        let released = tl.release_tasks("team1", "alice").await.unwrap();
+        assert_eq!(released.len(), 1);
+        assert_eq!(released[0].status, TeamTaskStatus::Pending);
+        assert_eq!(released[0].assigned_to, None);
+
+        let accepted = tl.accept_next_task("team1", "bob").await.unwrap();
+        assert_eq!(accepted.unwrap().id, "t1");
+    }
+
+    #[tokio::test]
+    async fn concurrent_accepts_claim_each_task_once() {
+        let tmp = TempDir::new().unwrap();
+        let tl = std::sync::Arc::new(TaskList::new(tmp.path().to_path_buf()));
+        tl.init("team1").await.unwrap();
+        for i in 0..8 {
+            tl.create_task("team1", make_task(&format!("t{i}"), "Work", &[]))
+                .await
+                .unwrap();
+        }
+
+        let handles: Vec<_> = (0..16)
+            .map(|i| {
+                let tl = std::sync::Arc::clone(&tl);
+                tokio::spawn(
+                    async move { tl.accept_next_task("team1", &format!("teammate-{i}")).await },
+                )
+            })
+            .collect();
+        let mut accepted = Vec::new();
+        for handle in handles {
+            accepted.extend(handle.await.unwrap().unwrap().map(|t| t.id));
+        }
+        accepted.sort();
+
+        assert_eq!(accepted, (0..8).map(|i| format!("t{i}")).collect::<Vec<_>>());
+    }
+
     #[tokio::test]
     async fn dependency_blocks_accept() {
         let tmp = TempDir::new().unwrap();
@@ -225,11 +633,67 @@ mod tests {
         let accepted = tl.accept_next_task("team1", "bob").await.unwrap();
         assert!(accepted.is_none());
 
-        // Complete t1.
-        tl.complete_task("team1", "t1").await.unwrap();
+        // Complete t1, which unblocks t2.
+        let (completed, unblocked) = tl.complete_task("team1", "t1").await.unwrap().unwrap();
+        assert_eq!(completed.status, TeamTaskStatus::Completed);
+        assert_eq!(
+            unblocked.iter().map(|t| t.id.as_str()).collect::<Vec<_>>(),
+            vec!["t2"]
+        );
+        assert_eq!(tl.complete_task("team1", "t9").await.unwrap(), None);
 
         // Now t2 should be available.
         let accepted = tl.accept_next_task("team1", "bob").await.unwrap();
         assert_eq!(accepted.unwrap().id, "t2");
     }
+
+    #[tokio::test]
+    async fn validate_and_repair_assignees_and_dependencies() {
+        let tmp = TempDir::new().unwrap();
+        let tl = TaskList::new(tmp.path().to_path_buf());
+        tl.init("team1").await.unwrap();
+        let mut orphaned = make_task("t1", "Orphaned", &["gone"]);
+        orphaned.status = TeamTaskStatus::InProgress;
+        orphaned.assigned_to = Some("ghost".to_string());
+        tl.create_task("team1", orphaned).await.unwrap();
+        tl.create_task("team1", make_task("t2", "Loop", &["t3"]))
+            .await
+            .unwrap();
+        tl.create_task("team1", make_task("t3", "Back", &["t2"]))
+            .await
+            .unwrap();
+        let members = ["writer".to_string()];
+
+        let issues = tl.validate("team1", Some(&members[..])).await.unwrap();
+        assert_eq!(
+            issues,
+            vec![
+                TeamIssue::UnknownAssignee {
+                    task_id: "t1".to_string(),
+                    assignee: "ghost".to_string(),
+                },
+                TeamIssue::MissingDependency {
+                    task_id: "t1".to_string(),
+                    dependency: "gone".to_string(),
+                },
+                TeamIssue::DependencyCycle {
+                    task_ids: vec!["t2".to_string(), "t3".to_string()],
+                },
+            ]
+        );
+
+        assert_eq!(tl.repair("team1", &issues).await.unwrap(), issues);
+        let tasks = tl.get_all_tasks("team1").await.unwrap();
+        assert_eq!(tasks[0].assigned_to, None);
+        assert_eq!(tasks[0].status, TeamTaskStatus::Pending);
+        assert!(tasks[0].depends_on.is_empty());
+        assert_eq!(tasks[1].depends_on, vec!["t3".to_string()]);
+        assert!(tasks[2].depends_on.is_empty());
+        assert!(
+            tl.validate("team1", Some(&members[..]))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
 }
@@ -0,0 +1,434 @@
+//! Syncing a team's tasks with an external tracker.
+//!
+//! Each tracker is a [`TaskSyncAdapter`] that creates an item for a task,
+//! updates its assignee and completes it. The leader session of a team with a
+//! tracker configured polls the task list and drives the adapter: new tasks
+//! get an item, reassigned tasks update its assignee, and completed tasks
+//! complete it. Which item belongs to which task is kept by the [`TaskList`],
+//! in the team's `sync-{tracker}.json`. Trackers that support it can also hand
+//! comments on their items to the task's assignee, or to the leader, as team
+//! messages.
+//!
+//! GitHub issues are always available; the Jira and Linear adapters are
+//! built with the `jira` and `linear` features.
+
+use crate::codex::Session;
+use crate::config::types::TeamJiraSync;
+use crate::config::types::TeamLinearSync;
+use crate::config::types::TeamsConfig;
+use crate::teams::completion::LEADER_INBOX;
+use crate::teams::github_issues::GitHubIssuesAdapter;
+use crate::teams::inbox::Inbox;
+use crate::teams::inbox::InboxMessage;
+use crate::teams::task_list::TaskList;
+use async_trait::async_trait;
+use codex_protocol::protocol::TeamTaskInfo;
+use codex_protocol::protocol::TeamTaskStatus;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::btree_map::Entry;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How often the leader syncs the team's tasks.
+const TASK_SYNC_INTERVAL: Duration = Duration::from_secs(30);
+
+/// An external tracker team tasks are mirrored to.
+#[async_trait]
+pub trait TaskSyncAdapter: Send + Sync {
+    /// Short name of the tracker, e.g. `github`, used in its mapping file and
+    /// in messages.
+    fn tracker(&self) -> &'static str;
+
+    /// Create an item for `task`, assigned to the tracker user `assignee`,
+    /// and return its id in the tracker.
+    async fn create(
+        &self,
+        team_name: &str,
+        task: &TeamTaskInfo,
+        assignee: Option<&str>,
+    ) -> Result<String, String>;
+
+    /// Assign item `external_id` to `assignee`, or to nobody.
+    async fn update(&self, external_id: &str, assignee: Option<&str>) -> Result<(), String>;
+
+    /// Mark item `external_id` as done.
+    async fn complete(&self, external_id: &str) -> Result<(), String>;
+
+    /// Comments on the tracker's items updated since `since`, RFC 3339,
+    /// oldest first. Trackers that do not mirror comments have none.
+    async fn comments_since(&self, _since: &str) -> Result<Vec<ExternalComment>, String> {
+        Ok(Vec::new())
+    }
+}
+
+/// A comment on a tracker item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalComment {
+    pub id: String,
+    /// Id of the item the comment is on, as returned by
+    /// [`TaskSyncAdapter::create`].
+    pub external_id: String,
+    pub author: String,
+    pub body: String,
+}
+
+/// Contents of a team's `sync-{tracker}.json`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncedTasks {
+    /// Item of each synced task, by task id.
+    #[serde(default)]
+    pub tasks: BTreeMap<String, SyncedTask>,
+    /// When comments were first looked for, RFC 3339; older comments are not
+    /// handed on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comments_since: Option<String>,
+    /// Ids of the comments already handed on.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub seen_comments: BTreeSet<String>,
+}
+
+/// The tracker item a task is mirrored to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncedTask {
+    pub external_id: String,
+    /// Tracker user the item was last assigned to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    #[serde(default)]
+    pub completed: bool,
+}
+
+/// A tracker configured for a team.
+pub struct TaskSync {
+    pub adapter: Box<dyn TaskSyncAdapter>,
+    /// Tracker user of each teammate, by teammate name.
+    pub assignees: BTreeMap<String, String>,
+    /// Hand comments on the tracker's items on as team messages.
+    pub mirror_comments: bool,
+}
+
+/// The trackers `config` sets up for `team_name`, skipping those that cannot
+/// be used, e.g. because their token is not set.
+pub(crate) fn task_syncs(config: &TeamsConfig, team_name: &str) -> Vec<TaskSync> {
+    let mut syncs = Vec::new();
+    let mut add = |tracker: &str,
+                   adapter: Result<Box<dyn TaskSyncAdapter>, String>,
+                   assignees: &BTreeMap<String, String>,
+                   mirror_comments: bool| {
+        match adapter {
+            Ok(adapter) => syncs.push(TaskSync {
+                adapter,
+                assignees: assignees.clone(),
+                mirror_comments,
+            }),
+            Err(e) => tracing::warn!("not syncing team {team_name} with {tracker}: {e}"),
+        }
+    };
+    if let Some(settings) = config.github_issues_for(team_name) {
+        let adapter = GitHubIssuesAdapter::new(settings).map(|adapter| Box::new(adapter) as _);
+        add(
+            "GitHub issues",
+            adapter,
+            &settings.assignees,
+            settings.mirror_comments,
+        );
+    }
+    if let Some(settings) = config.jira_for(team_name) {
+        add("Jira", jira_adapter(settings), &settings.assignees, false);
+    }
+    if let Some(settings) = config.linear_for(team_name) {
+        add(
+            "Linear",
+            linear_adapter(settings),
+            &settings.assignees,
+            false,
+        );
+    }
+    syncs
+}
+
+#[cfg(feature = "jira")]
+fn jira_adapter(settings: &TeamJiraSync) -> Result<Box<dyn TaskSyncAdapter>, String> {
+    crate::teams::jira::JiraAdapter::new(settings).map(|adapter| Box::new(adapter) as _)
+}
+
+#[cfg(not(feature = "jira"))]
+fn jira_adapter(_settings: &TeamJiraSync) -> Result<Box<dyn TaskSyncAdapter>, String> {
+    Err("built without Jira support".to_string())
+}
+
+#[cfg(feature = "linear")]
+fn linear_adapter(settings: &TeamLinearSync) -> Result<Box<dyn TaskSyncAdapter>, String> {
+    crate::teams::linear::LinearAdapter::new(settings).map(|adapter| Box::new(adapter) as _)
+}
+
+#[cfg(not(feature = "linear"))]
+fn linear_adapter(_settings: &TeamLinearSync) -> Result<Box<dyn TaskSyncAdapter>, String> {
+    Err("built without Linear support".to_string())
+}
+
+/// Start syncing the tasks of `team_name` with each of `syncs`. The sync runs
+/// until the team is cleaned up, leadership moves to another thread, or the
+/// leader session is dropped.
+pub(crate) fn start_task_sync(session: &Arc<Session>, syncs: Vec<TaskSync>, team_name: String) {
+    if syncs.is_empty() {
+        return;
+    }
+    let weak_session = Arc::downgrade(session);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(TASK_SYNC_INTERVAL);
+        loop {
+            interval.tick().await;
+            let Some(session) = weak_session.upgrade() else {
+                return;
+            };
+            let teams = &session.services.teams;
+            match teams.load_config(&team_name).await {
+                Ok(config) if config.leader_thread_id == session.conversation_id => {}
+                Ok(_) => return,
+                Err(e) => {
+                    tracing::debug!("stopping task sync for {team_name}: {e}");
+                    return;
+                }
+            }
+            let inbox = teams.inbox(&team_name);
+            for sync in &syncs {
+                if let Err(e) = sync_tasks(teams.tasks(), &inbox, sync, &team_name).await {
+                    let tracker = sync.adapter.tracker();
+                    tracing::warn!("failed to sync team {team_name} with {tracker}: {e}");
+                }
+            }
+        }
+    });
+}
+
+/// Bring the tracker of `sync` up to date with the tasks of `team_name`,
+/// then hand new comments on to `inbox`. Keeps going past failed items and
+/// returns the last failure.
+pub(crate) async fn sync_tasks(
+    task_list: &TaskList,
+    inbox: &Inbox,
+    sync: &TaskSync,
+    team_name: &str,
+) -> Result<(), String> {
+    let adapter = sync.adapter.as_ref();
+    let tracker = adapter.tracker();
+    let mut synced = task_list
+        .synced_tasks(team_name, tracker)
+        .await
+        .map_err(|e| e.to_string())?;
+    let tasks = task_list
+        .get_all_tasks(team_name)
+        .await
+        .map_err(|e| e.to_string())?;
+    let before = synced.clone();
+    let mut result = Ok(());
+    for task in &tasks {
+        let assignee = task
+            .assigned_to
+            .as_ref()
+            .and_then(|name| sync.assignees.get(name))
+            .cloned();
+        let completed = matches!(task.status, TeamTaskStatus::Completed);
+        let item = match synced.tasks.entry(task.id.clone()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                match adapter.create(team_name, task, assignee.as_deref()).await {
+                    Ok(external_id) => entry.insert(SyncedTask {
+                        external_id,
+                        assignee: assignee.clone(),
+                        completed: false,
+                    }),
+                    Err(e) => {
+                        result = Err(e);
+                        continue;
+                    }
+                }
+            }
+        };
+        if item.completed {
+            continue;
+        }
+        if completed {
+            match adapter.complete(&item.external_id).await {
+                Ok(()) => item.completed = true,
+                Err(e) => result = Err(e),
+            }
+        } else if item.assignee != assignee {
+            match adapter.update(&item.external_id, assignee.as_deref()).await {
+                Ok(()) => item.assignee = assignee,
+                Err(e) => result = Err(e),
+            }
+        }
+    }
+    if sync.mirror_comments {
+        let since = synced
+            .comments_since
+            .get_or_insert_with(|| chrono::Utc::now().to_rfc3339())
+            .clone();
+        match adapter.comments_since(&since).await {
+            Ok(comments) => {
+                for comment in comments {
+                    if !synced.seen_comments.insert(comment.id.clone()) {
+                        continue;
+                    }
+                    let Some(task) = synced
+                        .tasks
+                        .iter()
+                        .find(|(_, item)| item.external_id == comment.external_id)
+                        .and_then(|(id, _)| tasks.iter().find(|t| &t.id == id))
+                    else {
+                        continue;
+                    };
+                    let message = InboxMessage {
+                        from: format!("{tracker}:{}", comment.author),
+                        timestamp: chrono::Utc::now().to_rfc3339(),
+                        content: format!(
+                            "Comment on {tracker} item {} (task {}): {}",
+                            comment.external_id, task.id, comment.body
+                        ),
+                        read: false,
+                    };
+                    let to = task.assigned_to.as_deref().unwrap_or(LEADER_INBOX);
+                    if let Err(e) = inbox.send_message(to, message).await {
+                        tracing::warn!("failed to hand on a {tracker} comment: {e}");
+                    }
+                }
+            }
+            Err(e) => result = Err(e),
+        }
+    }
+    if synced != before {
+        task_list
+            .save_synced_tasks(team_name, tracker, &synced)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    /// Records the calls made to it.
+    struct RecordingAdapter {
+        calls: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl TaskSyncAdapter for RecordingAdapter {
+        fn tracker(&self) -> &'static str {
+            "fake"
+        }
+
+        async fn create(
+            &self,
+            _team_name: &str,
+            task: &TeamTaskInfo,
+            assignee: Option<&str>,
+        ) -> Result<String, String> {
+            let mut calls = self.calls.lock().unwrap();
+            calls.push(format!("create {} for {assignee:?}", task.id));
+            Ok(format!("FAKE-{}", calls.len()))
+        }
+
+        async fn update(&self, external_id: &str, assignee: Option<&str>) -> Result<(), String> {
+            let call = format!("update {external_id} for {assignee:?}");
+            self.calls.lock().unwrap().push(call);
+            Ok(())
+        }
+
+        async fn complete(&self, external_id: &str) -> Result<(), String> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("complete {external_id}"));
+            Ok(())
+        }
+
+        async fn comments_since(&self, _since: &str) -> Result<Vec<ExternalComment>, String> {
+            Ok(vec![ExternalComment {
+                id: "c1".to_string(),
+                external_id: "FAKE-1".to_string(),
+                author: "octocat".to_string(),
+                body: "Looks good.".to_string(),
+            }])
+        }
+    }
+
+    #[tokio::test]
+    async fn tasks_drive_the_adapter_once_per_change() {
+        let tmp = TempDir::new().unwrap();
+        let task_list = TaskList::new(tmp.path().join("tasks"));
+        let inbox = Inbox::new(tmp.path().join("inboxes"));
+        inbox.init().await.unwrap();
+        task_list.init("zeta").await.unwrap();
+        task_list
+            .create_task(
+                "zeta",
+                TeamTaskInfo {
+                    id: "task-1".to_string(),
+                    title: "Review".to_string(),
+                    description: None,
+                    status: TeamTaskStatus::Pending,
+                    assigned_to: None,
+                    depends_on: Vec::new(),
+                    result: None,
+                    created_at: None,
+                },
+            )
+            .await
+            .unwrap();
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let sync = TaskSync {
+            adapter: Box::new(RecordingAdapter {
+                calls: Arc::clone(&calls),
+            }),
+            assignees: BTreeMap::from([("reviewer".to_string(), "U1".to_string())]),
+            mirror_comments: true,
+        };
+
+        sync_tasks(&task_list, &inbox, &sync, "zeta").await.unwrap();
+        task_list
+            .assign_task("zeta", "task-1", "reviewer")
+            .await
+            .unwrap();
+        sync_tasks(&task_list, &inbox, &sync, "zeta").await.unwrap();
+        task_list.complete_task("zeta", "task-1").await.unwrap();
+        sync_tasks(&task_list, &inbox, &sync, "zeta").await.unwrap();
+        sync_tasks(&task_list, &inbox, &sync, "zeta").await.unwrap();
+
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![
+                "create task-1 for None".to_string(),
+                "update FAKE-1 for Some(\"U1\")".to_string(),
+                "complete FAKE-1".to_string(),
+            ]
+        );
+        let synced = task_list.synced_tasks("zeta", "fake").await.unwrap();
+        assert_eq!(
+            synced.tasks["task-1"],
+            SyncedTask {
+                external_id: "FAKE-1".to_string(),
+                assignee: Some("U1".to_string()),
+                completed: true,
+            }
+        );
+        // The comment arrived while the task was still unassigned.
+        let messages = inbox.read_inbox(LEADER_INBOX).await.unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].from, "fake:octocat");
+        assert_eq!(
+            messages[0].content,
+            "Comment on fake item FAKE-1 (task task-1): Looks good."
+        );
+    }
+}
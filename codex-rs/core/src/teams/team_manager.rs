@@ -2,14 +2,55 @@
 //!
 //! Team configuration lives at `~/.codex/teams/{name}/config.json`.
 
+use crate::teams::a2a::A2aMemberSpec;
+use crate::teams::completion::LEADER_INBOX;
+use crate::teams::conflicts::TeamEdits;
+use crate::teams::doctor::{TeamIssue, set_aside};
+use crate::teams::dry_run::DryRunPlan;
+use crate::teams::escalations::{TeamEscalation, TeamEscalations};
+use crate::teams::external::McpMemberSpec;
+use crate::teams::inbox::InboxMessage;
+use crate::teams::patches::{PatchStatus, TeamPatch, TeamPatches, diff_sha256};
+use crate::teams::reservations::Reservations;
+use crate::teams::scratchpad::lock;
+use crate::teams::ssh::SshMemberSpec;
+use crate::teams::task_list::TaskList;
+use crate::teams::usage::{TeamUsage, ThreadUsage};
+use crate::teams::worktree::MemberWorktree;
 use codex_protocol::ThreadId;
+use codex_protocol::protocol::AgentStatus;
+use codex_protocol::protocol::SandboxPolicy;
+use codex_protocol::protocol::TeamDisplayMode;
+use codex_protocol::protocol::TeamFileConflict;
+use codex_protocol::protocol::TokenUsage;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::future::Future;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::Path;
 use std::path::PathBuf;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::SystemTime;
 use tokio::fs;
+use tokio::io::AsyncWriteExt;
+
+/// Directory under the teams root that receives archived teams.
+const ARCHIVE_DIR: &str = ".archive";
+
+/// A leader lock whose heartbeat is older than this may be taken over.
+pub const LEADER_LOCK_STALE_AFTER: Duration = Duration::from_secs(5 * 60);
+
+/// A joined member whose lease is older than this is presumed gone.
+pub const MEMBER_LEASE_STALE_AFTER: Duration = Duration::from_secs(30);
 
 /// Persisted state of a single team member.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MemberConfig {
     pub name: String,
     pub thread_id: ThreadId,
@@ -18,31 +59,233 @@ pub struct MemberConfig {
     pub status: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub prompt: Option<String>,
+    /// Sandbox the teammate was narrowed to at spawn; `None` inherits the
+    /// leader's policy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox_policy: Option<SandboxPolicy>,
+    /// Usage limits set at spawn; the teammate is shut down once it exceeds
+    /// them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget: Option<MemberBudget>,
+    /// Wall-clock limit set at spawn; the teammate is shut down once its
+    /// thread has been running this long.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_minutes: Option<u64>,
+    /// Automatic restarts allowed after an error, overriding
+    /// `teams.max_auto_restarts`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_restarts: Option<u32>,
+    /// Automatic restarts so far.
+    #[serde(default)]
+    pub restarts: u32,
+    /// Dedicated checkout the teammate works in, if it was spawned with
+    /// worktree isolation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worktree: Option<MemberWorktree>,
+    /// Working directory set at spawn; `None` uses the leader's.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<PathBuf>,
+    /// MCP tool of an external teammate, which has no Codex thread; its
+    /// `thread_id` only identifies it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp: Option<McpMemberSpec>,
+    /// Remote A2A agent of an external teammate, likewise without a thread.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub a2a: Option<A2aMemberSpec>,
+    /// Host an external teammate runs `codex exec` on over SSH, likewise
+    /// without a thread here.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssh: Option<SshMemberSpec>,
+    /// The member is a session of another Codex process that joined the
+    /// team; its status is read from its lease instead of its thread.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub joined: bool,
+}
+
+impl MemberConfig {
+    /// Whether the member is an external agent rather than a Codex thread.
+    pub fn is_external(&self) -> bool {
+        self.mcp.is_some() || self.a2a.is_some() || self.ssh.is_some()
+    }
+}
+
+/// Token and turn limits for a teammate, checked each time it finishes a
+/// turn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemberBudget {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_turns: Option<u32>,
+}
+
+impl MemberBudget {
+    /// Describe the exceeded limit, if `tokens_used` or `turns_used` is over
+    /// budget.
+    pub fn exceeded(&self, tokens_used: i64, turns_used: u32) -> Option<String> {
+        if let Some(max_tokens) = self.max_tokens
+            && tokens_used > max_tokens
+        {
+            return Some(format!("used {tokens_used} of {max_tokens} tokens"));
+        }
+        if let Some(max_turns) = self.max_turns
+            && turns_used >= max_turns
+        {
+            return Some(format!("used {turns_used} of {max_turns} turns"));
+        }
+        None
+    }
 }
 
 /// Persisted team configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TeamConfig {
     pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Free-form key/value pairs supplied by the leader.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
     pub created_at: String,
     pub leader_thread_id: ThreadId,
     pub members: Vec<MemberConfig>,
+    /// Older configs store `"in-process"`, which maps to
+    /// [`TeamDisplayMode::Inline`].
     #[serde(default)]
-    pub display_mode: String,
+    pub display_mode: TeamDisplayMode,
     #[serde(default)]
     pub delegation_mode: bool,
+    /// Teammates that go idle are handed the next available task.
+    #[serde(default)]
+    pub work_stealing: bool,
+    /// Spawns, task assignments and shutdowns are only planned, in
+    /// `dry_run.json`; no agents are started.
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Team one of whose teammates leads this team, for nested teams.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_team: Option<String>,
+    /// Teams led by this team's teammates.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sub_teams: Vec<String>,
+}
+
+impl TeamConfig {
+    /// `Co-authored-by` trailers crediting those of `authors` who are members
+    /// of the team, in order and each once.
+    pub fn co_author_trailers<'a>(
+        &self,
+        authors: impl IntoIterator<Item = &'a str>,
+    ) -> Vec<String> {
+        let mut trailers: Vec<String> = Vec::new();
+        for author in authors {
+            let trailer = format!("Co-authored-by: {author} (codex teammate)");
+            if self.members.iter().any(|m| m.name == author) && !trailers.contains(&trailer) {
+                trailers.push(trailer);
+            }
+        }
+        trailers
+    }
+}
+
+/// Contents of `leader.lock`, identifying the session currently driving a team.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LeaderLock {
+    pub thread_id: ThreadId,
+    pub pid: u32,
+    /// RFC 3339 timestamp of the last heartbeat from the holder.
+    pub heartbeat_at: String,
+}
+
+impl LeaderLock {
+    /// Whether the holder has not sent a heartbeat for
+    /// [`LEADER_LOCK_STALE_AFTER`] and may be presumed gone.
+    pub fn is_stale(&self) -> bool {
+        let Ok(heartbeat) = chrono::DateTime::parse_from_rfc3339(&self.heartbeat_at) else {
+            return true;
+        };
+        let age = chrono::Utc::now().signed_duration_since(heartbeat);
+        age.to_std().unwrap_or_default() >= LEADER_LOCK_STALE_AFTER
+    }
+
+    /// Whether the lock was written by another process that is still
+    /// running. A lock of this process says nothing about its leader thread.
+    pub fn held_by_running_process(&self) -> bool {
+        self.pid != std::process::id() && process_is_running(self.pid)
+    }
+}
+
+#[cfg(unix)]
+fn process_is_running(pid: u32) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    // Signal 0 only checks that the process exists; EPERM means it does
+    // but belongs to another user.
+    let result = unsafe { libc::kill(pid, 0) };
+    result == 0 || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+#[cfg(not(unix))]
+fn process_is_running(_pid: u32) -> bool {
+    false
+}
+
+/// Contents of `leases/{member}.json`, kept fresh by the process a joined
+/// member runs in.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MemberLease {
+    pub thread_id: ThreadId,
+    pub pid: u32,
+    /// RFC 3339 timestamp of the last renewal.
+    pub heartbeat_at: String,
+    /// Status of the member's session as of the last renewal.
+    pub status: AgentStatus,
+}
+
+impl MemberLease {
+    /// Whether the lease was not renewed for [`MEMBER_LEASE_STALE_AFTER`].
+    pub fn is_stale(&self) -> bool {
+        let Ok(heartbeat) = chrono::DateTime::parse_from_rfc3339(&self.heartbeat_at) else {
+            return true;
+        };
+        let age = chrono::Utc::now().signed_duration_since(heartbeat);
+        age.to_std().unwrap_or_default() >= MEMBER_LEASE_STALE_AFTER
+    }
+}
+
+/// What [`TeamManager::gc`] does with an orphaned team.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GcAction {
+    /// Move the team directory to `{teams_root}/.archive/{name}-{timestamp}`.
+    Archive,
+    /// Remove the team directory entirely.
+    Delete,
+    /// Leave the team in place; only report that it would be collected.
+    Report,
 }
 
 /// Manages lifecycle of a single agent team.
 pub struct TeamManager {
     /// Root directory for all teams, typically `~/.codex/teams`.
     teams_root: PathBuf,
+    /// Upper bound on `members.len()` enforced by [`TeamManager::add_member`].
+    max_members: Option<usize>,
 }
 
 impl TeamManager {
     /// Create a new `TeamManager` rooted at the given directory.
     pub fn new(teams_root: PathBuf) -> Self {
-        Self { teams_root }
+        Self {
+            teams_root,
+            max_members: None,
+        }
+    }
+
+    /// Refuse to grow any team beyond `max_members` teammates.
+    pub fn with_max_members(mut self, max_members: usize) -> Self {
+        self.max_members = Some(max_members);
+        self
     }
 
     /// Directory for a specific team.
@@ -51,48 +294,158 @@ impl TeamManager {
     }
 
     /// Path to the team's config.json.
-    fn config_path(&self, name: &str) -> PathBuf {
+    pub(crate) fn config_path(&self, name: &str) -> PathBuf {
         self.team_dir(name).join("config.json")
     }
 
+    /// Path to the team's leader lock file.
+    fn lock_path(&self, name: &str) -> PathBuf {
+        self.team_dir(name).join("leader.lock")
+    }
+
+    /// Path to the lease of a joined member.
+    fn lease_path(&self, name: &str, member_name: &str) -> PathBuf {
+        self.team_dir(name)
+            .join("leases")
+            .join(format!("{member_name}.json"))
+    }
+
+    /// Path to the results recorded for idempotent tool calls.
+    fn idempotency_path(&self, name: &str) -> PathBuf {
+        self.team_dir(name).join("idempotency.json")
+    }
+
+    fn usage_path(&self, name: &str) -> PathBuf {
+        self.team_dir(name).join("usage.json")
+    }
+
+    fn edits_path(&self, name: &str) -> PathBuf {
+        self.team_dir(name).join("edits.json")
+    }
+
+    fn reservations_path(&self, name: &str) -> PathBuf {
+        self.team_dir(name).join("reservations.json")
+    }
+
+    fn patches_path(&self, name: &str) -> PathBuf {
+        self.team_dir(name).join("patches.json")
+    }
+
+    /// Directory of the diffs of submitted patches. It lies outside
+    /// `artifacts/`, so no teammate can overwrite one with `store_artifact`.
+    fn patches_dir(&self, name: &str) -> PathBuf {
+        self.team_dir(name).join("patches")
+    }
+
+    /// Path to the diff of submitted patch `id`.
+    pub fn patch_diff_path(&self, name: &str, id: &str) -> PathBuf {
+        self.patches_dir(name).join(format!("{id}.patch"))
+    }
+
+    fn escalations_path(&self, name: &str) -> PathBuf {
+        self.team_dir(name).join("escalations.json")
+    }
+
+    fn dry_run_path(&self, name: &str) -> PathBuf {
+        self.team_dir(name).join("dry_run.json")
+    }
+
+    /// Path to the log of the team's events.
+    pub fn events_path(&self, name: &str) -> PathBuf {
+        self.team_dir(name).join("events.jsonl")
+    }
+
     /// Path to the team's inboxes directory.
     pub fn inboxes_dir(&self, name: &str) -> PathBuf {
         self.team_dir(name).join("inboxes")
     }
 
+    /// Path to the team's shared scratchpad.
+    pub fn scratchpad_path(&self, name: &str) -> PathBuf {
+        self.team_dir(name).join("scratchpad.md")
+    }
+
+    /// Path to the team's shared artifacts directory.
+    pub fn artifacts_dir(&self, name: &str) -> PathBuf {
+        self.team_dir(name).join("artifacts")
+    }
+
+    /// Path to the git worktree of a teammate spawned with worktree isolation.
+    pub fn worktree_path(&self, name: &str, member_name: &str) -> PathBuf {
+        self.team_dir(name).join("worktrees").join(member_name)
+    }
+
     /// Create a new team, persisting the initial config to disk.
     pub async fn create_team(
         &self,
         name: &str,
         leader_thread_id: ThreadId,
+        description: Option<String>,
+        metadata: HashMap<String, String>,
+        display_mode: TeamDisplayMode,
     ) -> std::io::Result<TeamConfig> {
+        validate_name("team", name)?;
         let dir = self.team_dir(name);
         fs::create_dir_all(&dir).await?;
         fs::create_dir_all(self.inboxes_dir(name)).await?;
+        self.acquire_leader_lock(name, leader_thread_id).await?;
 
         let config = TeamConfig {
             name: name.to_string(),
+            description,
+            metadata,
             created_at: chrono::Utc::now().to_rfc3339(),
             leader_thread_id,
             members: Vec::new(),
-            display_mode: "in-process".to_string(),
+            display_mode,
             delegation_mode: false,
+            work_stealing: false,
+            dry_run: false,
+            parent_team: None,
+            sub_teams: Vec::new(),
         };
 
         let json = serde_json::to_string_pretty(&config)
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
         fs::write(self.config_path(name), json).await?;
+        note_store_write();
 
         Ok(config)
     }
 
     /// Add a member to the team configuration and create their inbox.
+    ///
+    /// Fails with [`std::io::ErrorKind::QuotaExceeded`] when the team is
+    /// already at the configured maximum size, with `AlreadyExists` when it
+    /// has a member of that name, and with `InvalidInput` when the name is
+    /// not plain (see [`validate_name`]); it becomes a path and a branch.
     pub async fn add_member(
         &self,
         team_name: &str,
         member: MemberConfig,
     ) -> std::io::Result<()> {
+        validate_name("teammate", &member.name)?;
         let mut config = self.load_config(team_name).await?;
+        if config.members.iter().any(|m| m.name == member.name) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!(
+                    "team '{team_name}' already has a member named '{}'",
+                    member.name
+                ),
+            ));
+        }
+        if let Some(max) = self.max_members
+            && config.members.len() >= max
+        {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::QuotaExceeded,
+                format!(
+                    "team '{team_name}' already has {} members (teams.max_members = {max})",
+                    config.members.len()
+                ),
+            ));
+        }
 
         // Create inbox file for the new member
         let inbox_path = self.inboxes_dir(team_name).join(format!("{}.json", member.name));
@@ -129,6 +482,208 @@ impl TeamManager {
         self.save_config(team_name, &config).await
     }
 
+    /// Point a member at a new agent thread, e.g. after it was restarted.
+    pub async fn update_member_thread(
+        &self,
+        team_name: &str,
+        member_name: &str,
+        thread_id: ThreadId,
+    ) -> std::io::Result<()> {
+        let mut config = self.load_config(team_name).await?;
+        if let Some(member) = config.members.iter_mut().find(|m| m.name == member_name) {
+            member.thread_id = thread_id;
+        }
+        self.save_config(team_name, &config).await
+    }
+
+    /// Remember the A2A context a member's agent answered in.
+    pub async fn update_a2a_context(
+        &self,
+        team_name: &str,
+        member_name: &str,
+        context_id: Option<String>,
+    ) -> std::io::Result<()> {
+        let mut config = self.load_config(team_name).await?;
+        if let Some(spec) = config
+            .members
+            .iter_mut()
+            .find(|m| m.name == member_name)
+            .and_then(|m| m.a2a.as_mut())
+        {
+            spec.context_id = context_id;
+        }
+        self.save_config(team_name, &config).await
+    }
+
+    /// Add `member`, a session of another process, to the team along with
+    /// its lease. A joined member of the same name whose lease went stale is
+    /// replaced; any other member of that name makes this fail with
+    /// [`std::io::ErrorKind::AlreadyExists`].
+    pub async fn join_team(&self, team_name: &str, member: MemberConfig) -> std::io::Result<()> {
+        let config = self.load_config(team_name).await?;
+        if let Some(existing) = config.members.iter().find(|m| m.name == member.name) {
+            let lease = self.member_lease(team_name, &member.name).await?;
+            if existing.thread_id != member.thread_id
+                && (!existing.joined || lease.is_some_and(|lease| !lease.is_stale()))
+            {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::AlreadyExists,
+                    format!(
+                        "team '{team_name}' already has a member named '{}'",
+                        member.name
+                    ),
+                ));
+            }
+            self.remove_member(team_name, &member.name).await?;
+        }
+        let (name, thread_id) = (member.name.clone(), member.thread_id);
+        self.add_member(team_name, member).await?;
+        self.renew_member_lease(team_name, &name, thread_id, &AgentStatus::PendingInit)
+            .await
+    }
+
+    /// Write a joined member's lease, reporting `status` as its session's.
+    pub async fn renew_member_lease(
+        &self,
+        team_name: &str,
+        member_name: &str,
+        thread_id: ThreadId,
+        status: &AgentStatus,
+    ) -> std::io::Result<()> {
+        let lease = MemberLease {
+            thread_id,
+            pid: std::process::id(),
+            heartbeat_at: chrono::Utc::now().to_rfc3339(),
+            status: status.clone(),
+        };
+        let json = serde_json::to_string_pretty(&lease)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        let path = self.lease_path(team_name, member_name);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).await?;
+        }
+        fs::write(path, json).await
+    }
+
+    /// Read a joined member's lease, if any. Like [`TeamManager::leader_lock`],
+    /// an unreadable lease is reported as absent.
+    pub async fn member_lease(
+        &self,
+        team_name: &str,
+        member_name: &str,
+    ) -> std::io::Result<Option<MemberLease>> {
+        let path = self.lease_path(team_name, member_name);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let data = fs::read_to_string(&path).await?;
+        Ok(serde_json::from_str(&data).ok())
+    }
+
+    /// Remove a joined member's lease.
+    pub async fn release_member_lease(
+        &self,
+        team_name: &str,
+        member_name: &str,
+    ) -> std::io::Result<()> {
+        match fs::remove_file(self.lease_path(team_name, member_name)).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// Remember the remote session an SSH member's deliveries continue.
+    pub async fn update_ssh_session(
+        &self,
+        team_name: &str,
+        member_name: &str,
+        session_id: Option<String>,
+    ) -> std::io::Result<()> {
+        let mut config = self.load_config(team_name).await?;
+        if let Some(spec) = config
+            .members
+            .iter_mut()
+            .find(|m| m.name == member_name)
+            .and_then(|m| m.ssh.as_mut())
+        {
+            spec.session_id = session_id;
+        }
+        self.save_config(team_name, &config).await
+    }
+
+    /// Count an automatic restart of a member, returning the new total.
+    pub async fn record_restart(&self, team_name: &str, member_name: &str) -> std::io::Result<u32> {
+        let mut config = self.load_config(team_name).await?;
+        let Some(member) = config.members.iter_mut().find(|m| m.name == member_name) else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("teammate '{member_name}' not found"),
+            ));
+        };
+        member.restarts += 1;
+        let restarts = member.restarts;
+        self.save_config(team_name, &config).await?;
+        Ok(restarts)
+    }
+
+    /// Turn delegation mode on or off for a team.
+    pub async fn set_delegation_mode(&self, name: &str, enabled: bool) -> std::io::Result<()> {
+        let mut config = self.load_config(name).await?;
+        config.delegation_mode = enabled;
+        self.save_config(name, &config).await
+    }
+
+    /// Turn work stealing on or off for a team.
+    pub async fn set_work_stealing(&self, name: &str, enabled: bool) -> std::io::Result<()> {
+        let mut config = self.load_config(name).await?;
+        config.work_stealing = enabled;
+        self.save_config(name, &config).await
+    }
+
+    /// Turn dry-run mode on or off for a team.
+    pub async fn set_dry_run(&self, name: &str, enabled: bool) -> std::io::Result<()> {
+        let mut config = self.load_config(name).await?;
+        config.dry_run = enabled;
+        self.save_config(name, &config).await
+    }
+
+    /// Record `child` as a sub-team of `parent`.
+    pub async fn link_sub_team(&self, parent: &str, child: &str) -> std::io::Result<()> {
+        let mut child_config = self.load_config(child).await?;
+        child_config.parent_team = Some(parent.to_string());
+        self.save_config(child, &child_config).await?;
+        let mut parent_config = self.load_config(parent).await?;
+        if !parent_config.sub_teams.iter().any(|name| name == child) {
+            parent_config.sub_teams.push(child.to_string());
+        }
+        self.save_config(parent, &parent_config).await
+    }
+
+    /// Point the parent and sub-teams of `config` at `new_name`, or drop
+    /// the links when the team is going away. Linked teams that no longer
+    /// exist are skipped.
+    async fn relink(
+        &self,
+        config: &TeamConfig,
+        old_name: &str,
+        new_name: Option<&str>,
+    ) -> std::io::Result<()> {
+        if let Some(parent) = &config.parent_team
+            && let Ok(mut parent_config) = self.load_config(parent).await
+        {
+            parent_config.sub_teams.retain(|name| name != old_name);
+            parent_config.sub_teams.extend(new_name.map(str::to_string));
+            self.save_config(parent, &parent_config).await?;
+        }
+        for child in &config.sub_teams {
+            if let Ok(mut child_config) = self.load_config(child).await {
+                child_config.parent_team = new_name.map(str::to_string);
+                self.save_config(child, &child_config).await?;
+            }
+        }
+        Ok(())
+    }
+
     /// Load team config from disk.
     pub async fn load_config(&self, name: &str) -> std::io::Result<TeamConfig> {
         let data = fs::read_to_string(self.config_path(name)).await?;
@@ -140,79 +695,1999 @@ impl TeamManager {
     async fn save_config(&self, name: &str, config: &TeamConfig) -> std::io::Result<()> {
         let json = serde_json::to_string_pretty(config)
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
-        fs::write(self.config_path(name), json).await
+        fs::write(self.config_path(name), json).await?;
+        note_store_write();
+        Ok(())
     }
 
-    /// Get list of all member names and their thread IDs.
-    pub async fn list_members(
+    /// The result recorded for a call made with `key`, if one already ran.
+    pub async fn idempotent_result(
         &self,
-        team_name: &str,
-    ) -> std::io::Result<HashMap<String, ThreadId>> {
-        let config = self.load_config(team_name).await?;
-        Ok(config
-            .members
-            .into_iter()
-            .map(|m| (m.name, m.thread_id))
-            .collect())
+        name: &str,
+        key: &str,
+    ) -> std::io::Result<Option<String>> {
+        Ok(self.load_idempotent_results(name).await?.remove(key))
     }
 
-    /// Clean up all team resources: config, inboxes directory, etc.
-    pub async fn cleanup_team(&self, name: &str) -> std::io::Result<()> {
-        let dir = self.team_dir(name);
-        if dir.exists() {
-            fs::remove_dir_all(&dir).await?;
+    /// Record `result` as the outcome of the call made with `key`.
+    pub async fn record_idempotent_result(
+        &self,
+        name: &str,
+        key: &str,
+        result: &str,
+    ) -> std::io::Result<()> {
+        let mut results = self.load_idempotent_results(name).await?;
+        results.insert(key.to_string(), result.to_string());
+        let json = serde_json::to_string_pretty(&results)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        fs::write(self.idempotency_path(name), json).await
+    }
+
+    async fn load_idempotent_results(
+        &self,
+        name: &str,
+    ) -> std::io::Result<HashMap<String, String>> {
+        let path = self.idempotency_path(name);
+        if !path.exists() {
+            return Ok(HashMap::new());
         }
-        Ok(())
+        let data = fs::read_to_string(&path).await?;
+        serde_json::from_str(&data)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
     }
 
-    /// Check whether a team with the given name exists on disk.
-    pub async fn team_exists(&self, name: &str) -> bool {
-        self.config_path(name).exists()
+    /// Token usage recorded for the team's leader and teammates.
+    pub async fn usage(&self, name: &str) -> std::io::Result<TeamUsage> {
+        let path = self.usage_path(name);
+        if !path.exists() {
+            return Ok(TeamUsage::default());
+        }
+        let data = fs::read_to_string(&path).await?;
+        serde_json::from_str(&data)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use tempfile::TempDir;
+    /// Record the latest total usage of each `(thread, participant, usage)`
+    /// snapshot, replacing earlier snapshots of the same thread.
+    pub async fn record_usage(
+        &self,
+        name: &str,
+        snapshots: Vec<(ThreadId, String, TokenUsage)>,
+    ) -> std::io::Result<TeamUsage> {
+        let mut usage = self.usage(name).await?;
+        for (thread_id, participant, thread_usage) in snapshots {
+            usage.threads.insert(
+                thread_id.to_string(),
+                ThreadUsage {
+                    name: participant,
+                    usage: thread_usage,
+                },
+            );
+        }
+        let json = serde_json::to_string_pretty(&usage)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        fs::write(self.usage_path(name), json).await?;
+        Ok(usage)
+    }
 
-    #[tokio::test]
-    async fn create_and_cleanup_team() {
-        let tmp = TempDir::new().unwrap();
-        let mgr = TeamManager::new(tmp.path().to_path_buf());
-        let leader = ThreadId::new();
+    /// Files edited by the team's teammates.
+    pub async fn edits(&self, name: &str) -> std::io::Result<TeamEdits> {
+        let path = self.edits_path(name);
+        if !path.exists() {
+            return Ok(TeamEdits::default());
+        }
+        let data = fs::read_to_string(&path).await?;
+        serde_json::from_str(&data)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
 
-        let config = mgr.create_team("test-team", leader.clone()).await.unwrap();
-        assert_eq!(config.name, "test-team");
-        assert!(mgr.team_exists("test-team").await);
+    async fn save_edits(&self, name: &str, edits: &TeamEdits) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(edits)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        fs::write(self.edits_path(name), json).await
+    }
 
-        mgr.cleanup_team("test-team").await.unwrap();
-        assert!(!mgr.team_exists("test-team").await);
+    /// Record that `member_name` edited `paths`.
+    pub async fn record_edits(
+        &self,
+        name: &str,
+        member_name: &str,
+        paths: &[String],
+    ) -> std::io::Result<()> {
+        let mut edits = self.edits(name).await?;
+        let member_paths = edits.by_member.entry(member_name.to_string()).or_default();
+        let before = member_paths.len();
+        member_paths.extend(paths.iter().cloned());
+        if member_paths.len() == before {
+            return Ok(());
+        }
+        self.save_edits(name, &edits).await
     }
 
-    #[tokio::test]
-    async fn add_and_remove_member() {
-        let tmp = TempDir::new().unwrap();
-        let mgr = TeamManager::new(tmp.path().to_path_buf());
-        let leader = ThreadId::new();
+    /// Files edited by more than one teammate that the leader has not been
+    /// warned about yet. They count as reported from now on.
+    pub async fn take_unreported_conflicts(
+        &self,
+        name: &str,
+    ) -> std::io::Result<Vec<TeamFileConflict>> {
+        let mut edits = self.edits(name).await?;
+        let conflicts = edits.unreported_conflicts();
+        if conflicts.is_empty() {
+            return Ok(conflicts);
+        }
+        for conflict in &conflicts {
+            edits.reported.insert(
+                conflict.path.clone(),
+                conflict.members.iter().cloned().collect(),
+            );
+        }
+        self.save_edits(name, &edits).await?;
+        Ok(conflicts)
+    }
 
-        mgr.create_team("t", leader).await.unwrap();
+    /// Current file reservations of the team.
+    pub async fn reservations(&self, name: &str) -> std::io::Result<Reservations> {
+        let path = self.reservations_path(name);
+        if !path.exists() {
+            return Ok(Reservations::default());
+        }
+        edit_json_locked(
+            path,
+            "the team's file reservations",
+            |reservations: &mut Reservations| (reservations.clone(), false),
+        )
+        .await
+    }
 
-        let member = MemberConfig {
-            name: "reviewer".to_string(),
-            thread_id: ThreadId::new(),
-            role: Some("security".to_string()),
-            status: "idle".to_string(),
-            prompt: None,
-        };
-        mgr.add_member("t", member).await.unwrap();
+    /// Reserve `paths` for `holder`. Fails with `ResourceBusy`, naming the
+    /// holders, if any of them overlaps someone else's reservation.
+    pub async fn reserve_files(
+        &self,
+        name: &str,
+        holder: &str,
+        paths: &[String],
+    ) -> std::io::Result<()> {
+        let holder = holder.to_string();
+        let paths = paths.to_vec();
+        let reserved = edit_json_locked(
+            self.reservations_path(name),
+            "the team's file reservations",
+            move |reservations: &mut Reservations| match reservations.reserve(&holder, &paths) {
+                Ok(()) => (Ok(()), true),
+                Err(conflicts) => (Err(conflicts), false),
+            },
+        )
+        .await?;
+        reserved.map_err(|conflicts| {
+            let held = conflicts
+                .iter()
+                .map(|(path, reserved_by)| format!("{path} is reserved by {reserved_by}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            std::io::Error::new(std::io::ErrorKind::ResourceBusy, held)
+        })
+    }
+
+    /// Release the reservations `holder` has on `paths`, or all of them if
+    /// `paths` is `None`. Returns the released paths.
+    pub async fn release_files(
+        &self,
+        name: &str,
+        holder: &str,
+        paths: Option<&[String]>,
+    ) -> std::io::Result<Vec<String>> {
+        let path = self.reservations_path(name);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let holder = holder.to_string();
+        let paths = paths.map(<[String]>::to_vec);
+        edit_json_locked(
+            path,
+            "the team's file reservations",
+            move |reservations: &mut Reservations| {
+                let released = reservations.release(&holder, paths.as_deref());
+                let changed = !released.is_empty();
+                (released, changed)
+            },
+        )
+        .await
+    }
+
+    /// Patches teammates submitted for review, oldest first.
+    pub async fn patches(&self, name: &str) -> std::io::Result<TeamPatches> {
+        let path = self.patches_path(name);
+        if !path.exists() {
+            return Ok(TeamPatches::default());
+        }
+        edit_json_locked(path, "the team's patches", |patches: &mut TeamPatches| {
+            (patches.clone(), false)
+        })
+        .await
+    }
+
+    /// Record a pending patch from `author` with the unified diff `diff`
+    /// touching `files`, and store the diff. Returns the patch with its new
+    /// id.
+    pub async fn add_patch(
+        &self,
+        name: &str,
+        author: &str,
+        description: &str,
+        files: Vec<String>,
+        diff: &str,
+    ) -> std::io::Result<TeamPatch> {
+        let dir = self.patches_dir(name);
+        let author = author.to_string();
+        let description = description.to_string();
+        let diff = diff.to_string();
+        edit_json_locked(
+            self.patches_path(name),
+            "the team's patches",
+            move |patches: &mut TeamPatches| {
+                let id = patches.next_id();
+                let stored = std::fs::create_dir_all(&dir)
+                    .and_then(|()| std::fs::write(dir.join(format!("{id}.patch")), &diff));
+                if let Err(e) = stored {
+                    return (Err(e), false);
+                }
+                let patch = TeamPatch {
+                    id,
+                    author,
+                    description,
+                    status: PatchStatus::Pending,
+                    files,
+                    sha256: diff_sha256(diff.as_bytes()),
+                    submitted_at: chrono::Utc::now().to_rfc3339(),
+                    comment: None,
+                };
+                patches.patches.push(patch.clone());
+                (Ok(patch), true)
+            },
+        )
+        .await?
+    }
+
+    /// The diff of `patch`. Fails with `InvalidData` if it no longer matches
+    /// the hash recorded when it was submitted.
+    pub async fn patch_diff(&self, name: &str, patch: &TeamPatch) -> std::io::Result<Vec<u8>> {
+        let diff = fs::read(self.patch_diff_path(name, &patch.id)).await?;
+        if diff_sha256(&diff) != patch.sha256 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("the diff of {} changed after it was submitted", patch.id),
+            ));
+        }
+        Ok(diff)
+    }
+
+    /// Apply `update` to patch `id`. Returns the updated patch, or `None` if
+    /// there is no such patch.
+    pub async fn update_patch(
+        &self,
+        name: &str,
+        id: &str,
+        update: impl FnOnce(&mut TeamPatch) + Send + 'static,
+    ) -> std::io::Result<Option<TeamPatch>> {
+        let id = id.to_string();
+        edit_json_locked(
+            self.patches_path(name),
+            "the team's patches",
+            move |patches: &mut TeamPatches| {
+                let Some(patch) = patches.get_mut(&id) else {
+                    return (None, false);
+                };
+                update(patch);
+                (Some(patch.clone()), true)
+            },
+        )
+        .await
+    }
+
+    /// Questions teammates escalated to the user, oldest first.
+    pub async fn escalations(&self, name: &str) -> std::io::Result<TeamEscalations> {
+        let path = self.escalations_path(name);
+        if !path.exists() {
+            return Ok(TeamEscalations::default());
+        }
+        let data = fs::read_to_string(&path).await?;
+        serde_json::from_str(&data)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    /// What the tools of a dry-run team would have done so far.
+    pub async fn dry_run_plan(&self, name: &str) -> std::io::Result<DryRunPlan> {
+        let path = self.dry_run_path(name);
+        if !path.exists() {
+            return Ok(DryRunPlan::default());
+        }
+        let data = fs::read_to_string(&path).await?;
+        serde_json::from_str(&data)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    /// Apply `update` to the team's dry-run plan and save it. Returns the
+    /// updated plan.
+    pub async fn update_dry_run_plan(
+        &self,
+        name: &str,
+        update: impl FnOnce(&mut DryRunPlan),
+    ) -> std::io::Result<DryRunPlan> {
+        let mut plan = self.dry_run_plan(name).await?;
+        update(&mut plan);
+        let json = serde_json::to_string_pretty(&plan)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        fs::write(self.dry_run_path(name), json).await?;
+        Ok(plan)
+    }
+
+    async fn save_escalations(
+        &self,
+        name: &str,
+        escalations: &TeamEscalations,
+    ) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(escalations)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        fs::write(self.escalations_path(name), json).await
+    }
+
+    /// Record an unanswered question from teammate `from`. Returns it with
+    /// its new id.
+    pub async fn add_escalation(
+        &self,
+        name: &str,
+        from: &str,
+        question: &str,
+        options: Vec<String>,
+    ) -> std::io::Result<TeamEscalation> {
+        let mut escalations = self.escalations(name).await?;
+        let escalation = TeamEscalation {
+            id: escalations.next_id(),
+            from: from.to_string(),
+            question: question.to_string(),
+            options,
+            asked_at: chrono::Utc::now().to_rfc3339(),
+            answer: None,
+        };
+        escalations.escalations.push(escalation.clone());
+        self.save_escalations(name, &escalations).await?;
+        Ok(escalation)
+    }
+
+    /// Record the user's answer to escalation `id`. Returns the answered
+    /// escalation, or `None` if there is no such escalation or it was
+    /// already answered.
+    pub async fn answer_escalation(
+        &self,
+        name: &str,
+        id: &str,
+        answer: &str,
+    ) -> std::io::Result<Option<TeamEscalation>> {
+        let mut escalations = self.escalations(name).await?;
+        let Some(escalation) = escalations
+            .escalations
+            .iter_mut()
+            .find(|escalation| escalation.id == id && escalation.answer.is_none())
+        else {
+            return Ok(None);
+        };
+        escalation.answer = Some(answer.to_string());
+        let escalation = escalation.clone();
+        self.save_escalations(name, &escalations).await?;
+        Ok(Some(escalation))
+    }
+
+    /// Find the member backed by `thread_id`, if any.
+    pub async fn member_by_thread(
+        &self,
+        team_name: &str,
+        thread_id: ThreadId,
+    ) -> std::io::Result<Option<MemberConfig>> {
+        let config = self.load_config(team_name).await?;
+        Ok(config
+            .members
+            .into_iter()
+            .find(|m| m.thread_id == thread_id))
+    }
+
+    /// Get list of all member names and their thread IDs.
+    pub async fn list_members(
+        &self,
+        team_name: &str,
+    ) -> std::io::Result<HashMap<String, ThreadId>> {
+        let config = self.load_config(team_name).await?;
+        Ok(config
+            .members
+            .into_iter()
+            .map(|m| (m.name, m.thread_id))
+            .collect())
+    }
+
+    /// Clean up all team resources: config, inboxes directory, etc.
+    pub async fn cleanup_team(&self, name: &str) -> std::io::Result<()> {
+        if let Ok(config) = self.load_config(name).await {
+            self.relink(&config, name, None).await?;
+        }
+        let dir = self.team_dir(name);
+        if dir.exists() {
+            fs::remove_dir_all(&dir).await?;
+        }
+        Ok(())
+    }
+
+    /// Rename a team, moving its directory and its task list and rewriting
+    /// the name stored in `config.json`. `new_name` may only contain
+    /// letters, digits, `-` and `_`. Teammates' worktrees move with the team
+    /// directory. If the task list cannot be moved, the team directory is
+    /// moved back.
+    pub async fn rename_team(
+        &self,
+        old_name: &str,
+        new_name: &str,
+        tasks: &TaskList,
+    ) -> std::io::Result<TeamConfig> {
+        if !self.team_exists(old_name).await {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("team '{old_name}' not found"),
+            ));
+        }
+        validate_name("team", new_name)?;
+        if self.team_dir(new_name).exists() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!("team '{new_name}' already exists"),
+            ));
+        }
+
+        fs::rename(self.team_dir(old_name), self.team_dir(new_name)).await?;
+        // Move the task list before touching any config, so a failure here
+        // only has the directory move to undo.
+        if let Err(e) = tasks.rename(old_name, new_name).await {
+            if let Err(rollback) =
+                fs::rename(self.team_dir(new_name), self.team_dir(old_name)).await
+            {
+                tracing::warn!("failed to move team '{new_name}' back to '{old_name}': {rollback}");
+            }
+            return Err(e);
+        }
+        let mut config = self.load_config(new_name).await?;
+        config.name = new_name.to_string();
+        self.save_config(new_name, &config).await?;
+        self.relink(&config, old_name, Some(new_name)).await?;
+        Ok(config)
+    }
+
+    /// Check whether a team with the given name exists on disk.
+    pub async fn team_exists(&self, name: &str) -> bool {
+        self.config_path(name).exists()
+    }
+
+    /// Acquire (or refresh the heartbeat of) the leader lock for a team.
+    ///
+    /// Only the team's leader thread may hold the lock; before `config.json`
+    /// exists, while the team is being created, any thread may. Succeeds when
+    /// the lock is free, stale, or already held by `thread_id`. Fails with
+    /// [`std::io::ErrorKind::PermissionDenied`] when `thread_id` does not
+    /// lead the team, and with [`std::io::ErrorKind::ResourceBusy`] when
+    /// another session holds a live lock or is taking over a stale one.
+    pub async fn acquire_leader_lock(
+        &self,
+        name: &str,
+        thread_id: ThreadId,
+    ) -> std::io::Result<()> {
+        if self.team_exists(name).await {
+            let leader = self.load_config(name).await?.leader_thread_id;
+            if leader != thread_id {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::PermissionDenied,
+                    format!("team '{name}' is led by another session (thread {leader})"),
+                ));
+            }
+        }
+
+        let path = self.lock_path(name);
+        let lock = LeaderLock {
+            thread_id,
+            pid: std::process::id(),
+            heartbeat_at: chrono::Utc::now().to_rfc3339(),
+        };
+        let temp = write_temp_lock(&path, &lock).await?;
+        // Linking fails if the lock exists, so a free lock goes to exactly
+        // one session, which never sees it half-written.
+        let linked = fs::hard_link(&temp, &path).await;
+        match linked {
+            Ok(()) => return fs::remove_file(&temp).await,
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+            Err(e) => {
+                let _ = fs::remove_file(&temp).await;
+                return Err(e);
+            }
+        }
+
+        let busy = |holder: &LeaderLock| {
+            std::io::Error::new(
+                std::io::ErrorKind::ResourceBusy,
+                format!(
+                    "team '{name}' is controlled by another session (thread {}, pid {})",
+                    holder.thread_id, holder.pid
+                ),
+            )
+        };
+        match self.leader_lock(name).await {
+            Ok(Some(holder)) if holder.thread_id == thread_id => {
+                return fs::rename(&temp, &path).await;
+            }
+            Ok(Some(holder)) if !holder.is_stale() => {
+                let _ = fs::remove_file(&temp).await;
+                return Err(busy(&holder));
+            }
+            Ok(_) => {}
+            Err(e) => {
+                let _ = fs::remove_file(&temp).await;
+                return Err(e);
+            }
+        }
+
+        // The lock is stale or unreadable. Only the session that creates the
+        // takeover file may replace it, after checking that nobody else took
+        // it over in the meantime.
+        let takeover = path.with_extension("lock.takeover");
+        let claimed = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&takeover)
+            .await;
+        if let Err(e) = claimed {
+            let _ = fs::remove_file(&temp).await;
+            if e.kind() != std::io::ErrorKind::AlreadyExists {
+                return Err(e);
+            }
+            // A takeover file this old was left by a session that crashed
+            // mid-takeover; clear it so the next attempt can proceed.
+            let modified = fs::metadata(&takeover)
+                .await
+                .and_then(|metadata| metadata.modified());
+            if let Ok(modified) = modified
+                && SystemTime::now()
+                    .duration_since(modified)
+                    .unwrap_or_default()
+                    >= LEADER_LOCK_STALE_AFTER
+            {
+                let _ = fs::remove_file(&takeover).await;
+            }
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::ResourceBusy,
+                format!("team '{name}' is being taken over by another session"),
+            ));
+        }
+        let result = match self.leader_lock(name).await {
+            Ok(Some(holder)) if holder.thread_id != thread_id && !holder.is_stale() => {
+                Err(busy(&holder))
+            }
+            Ok(_) => fs::rename(&temp, &path).await,
+            Err(e) => Err(e),
+        };
+        let _ = fs::remove_file(&temp).await;
+        let _ = fs::remove_file(&takeover).await;
+        result
+    }
+
+    /// Transfer leadership of a team to `thread_id`, updating `config.json`
+    /// and handing the leader lock to the new leader.
+    pub async fn set_leader(&self, name: &str, thread_id: ThreadId) -> std::io::Result<TeamConfig> {
+        let mut config = self.load_config(name).await?;
+        config.leader_thread_id = thread_id;
+        self.save_config(name, &config).await?;
+
+        let lock = LeaderLock {
+            thread_id,
+            pid: std::process::id(),
+            heartbeat_at: chrono::Utc::now().to_rfc3339(),
+        };
+        let path = self.lock_path(name);
+        let temp = write_temp_lock(&path, &lock).await?;
+        fs::rename(&temp, &path).await?;
+        Ok(config)
+    }
+
+    /// Read the current leader lock, if any. An unreadable lock file is
+    /// reported as absent so a corrupt lock cannot wedge the team.
+    pub async fn leader_lock(&self, name: &str) -> std::io::Result<Option<LeaderLock>> {
+        let path = self.lock_path(name);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let data = fs::read_to_string(&path).await?;
+        Ok(serde_json::from_str(&data).ok())
+    }
+
+    /// Release the leader lock if it is held by `thread_id`.
+    pub async fn release_leader_lock(
+        &self,
+        name: &str,
+        thread_id: ThreadId,
+    ) -> std::io::Result<()> {
+        if let Some(holder) = self.leader_lock(name).await?
+            && holder.thread_id == thread_id
+        {
+            fs::remove_file(self.lock_path(name)).await?;
+        }
+        Ok(())
+    }
+
+    /// Load the config of every team on disk, sorted by name.
+    ///
+    /// Directories without a readable `config.json` are skipped.
+    pub async fn list_teams(&self) -> std::io::Result<Vec<TeamConfig>> {
+        let mut teams = Vec::new();
+        if !self.teams_root.exists() {
+            return Ok(teams);
+        }
+        let mut entries = fs::read_dir(&self.teams_root).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            if !entry.file_type().await?.is_dir() {
+                continue;
+            }
+            let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+            if name.starts_with('.') {
+                continue;
+            }
+            match self.load_config(&name).await {
+                Ok(config) => teams.push(config),
+                Err(e) => tracing::debug!("skipping team directory {name}: {e}"),
+            }
+        }
+        teams.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(teams)
+    }
+
+    /// Load the config of every top-level team that no session is leading:
+    /// its leader lock is missing or stale. Sorted by name.
+    pub async fn dormant_teams(&self) -> std::io::Result<Vec<TeamConfig>> {
+        let mut dormant = Vec::new();
+        for config in self.list_teams().await? {
+            if config.parent_team.is_some() {
+                continue;
+            }
+            match self.leader_lock(&config.name).await? {
+                Some(lock) if !lock.is_stale() => {}
+                _ => dormant.push(config),
+            }
+        }
+        Ok(dormant)
+    }
+
+    /// Move a team's directory into the archive under the teams root, named
+    /// after the team and the current time. Returns the archived directory.
+    pub async fn archive_team(&self, name: &str) -> std::io::Result<PathBuf> {
+        let archive_root = self.teams_root.join(ARCHIVE_DIR);
+        fs::create_dir_all(&archive_root).await?;
+        let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ");
+        let archived = archive_root.join(format!("{name}-{stamp}"));
+        fs::rename(self.team_dir(name), &archived).await?;
+        Ok(archived)
+    }
+
+    /// Collect orphaned teams.
+    ///
+    /// A team is orphaned when nothing in the team directory has been
+    /// modified for at least `max_age`, its leader lock is missing or stale
+    /// and no longer held by a running process, and `check_liveness`
+    /// reports its leader thread as gone. Teams that cannot be read or
+    /// collected are skipped. Returns the names of the collected teams.
+    pub async fn gc<F, Fut>(
+        &self,
+        max_age: Duration,
+        action: GcAction,
+        check_liveness: F,
+    ) -> std::io::Result<Vec<String>>
+    where
+        F: Fn(ThreadId) -> Fut,
+        Fut: Future<Output = bool>,
+    {
+        let mut collected = Vec::new();
+        for config in self.list_teams().await? {
+            let name = config.name;
+            let dir = self.team_dir(&name);
+            let modified = match last_modified(&dir).await {
+                Ok(modified) => modified,
+                Err(e) => {
+                    tracing::warn!("skipping unreadable team directory {name}: {e}");
+                    continue;
+                }
+            };
+            let age = SystemTime::now()
+                .duration_since(modified)
+                .unwrap_or_default();
+            if age < max_age {
+                continue;
+            }
+            match self.leader_lock(&name).await {
+                Ok(Some(lock)) if !lock.is_stale() || lock.held_by_running_process() => continue,
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!("skipping team {name} with an unreadable leader lock: {e}");
+                    continue;
+                }
+            }
+            if check_liveness(config.leader_thread_id).await {
+                continue;
+            }
+            let result = match action {
+                GcAction::Archive => self.archive_team(&name).await.map(drop),
+                GcAction::Delete => fs::remove_dir_all(&dir).await,
+                GcAction::Report => Ok(()),
+            };
+            match result {
+                Ok(()) => collected.push(name),
+                Err(e) => tracing::warn!("failed to collect team {name}: {e}"),
+            }
+        }
+        Ok(collected)
+    }
+
+    /// Check a team's files and members. Every JSON file of the team must
+    /// parse, every member's thread other than an external or joined
+    /// member's must still exist according to `is_alive`, every joined
+    /// member must hold a fresh lease, and every inbox must belong to the
+    /// leader or a member.
+    pub async fn validate<F, Fut>(&self, name: &str, is_alive: F) -> std::io::Result<Vec<TeamIssue>>
+    where
+        F: Fn(ThreadId) -> Fut,
+        Fut: Future<Output = bool>,
+    {
+        let mut issues = Vec::new();
+        let config = match self.load_config(name).await {
+            Ok(config) => Some(config),
+            Err(e) if e.kind() == std::io::ErrorKind::InvalidData => {
+                issues.push(TeamIssue::UnreadableConfig {
+                    path: self.config_path(name),
+                    error: e.to_string(),
+                });
+                None
+            }
+            Err(e) => return Err(e),
+        };
+        let loaded = [
+            (self.usage_path(name), self.usage(name).await.err()),
+            (self.edits_path(name), self.edits(name).await.err()),
+            (
+                self.reservations_path(name),
+                self.reservations(name).await.err(),
+            ),
+            (self.patches_path(name), self.patches(name).await.err()),
+            (
+                self.escalations_path(name),
+                self.escalations(name).await.err(),
+            ),
+            (
+                self.idempotency_path(name),
+                self.load_idempotent_results(name).await.err(),
+            ),
+            (self.dry_run_path(name), self.dry_run_plan(name).await.err()),
+        ];
+        for (path, error) in loaded {
+            match error {
+                Some(e) if e.kind() == std::io::ErrorKind::InvalidData => {
+                    issues.push(TeamIssue::UnreadableFile {
+                        path,
+                        error: e.to_string(),
+                    });
+                }
+                Some(e) => return Err(e),
+                None => {}
+            }
+        }
+
+        let inboxes_dir = self.inboxes_dir(name);
+        if inboxes_dir.exists() {
+            let mut entries = fs::read_dir(&inboxes_dir).await?;
+            let mut inboxes = Vec::new();
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                if path.extension().is_some_and(|ext| ext == "json")
+                    && let Some(stem) = path.file_stem().and_then(|s| s.to_str())
+                {
+                    inboxes.push((stem.to_string(), path));
+                }
+            }
+            inboxes.sort();
+            for (inbox, path) in inboxes {
+                let data = fs::read_to_string(&path).await?;
+                if let Err(e) = serde_json::from_str::<Vec<InboxMessage>>(&data) {
+                    issues.push(TeamIssue::UnreadableFile {
+                        path,
+                        error: e.to_string(),
+                    });
+                } else if let Some(config) = &config
+                    && inbox != LEADER_INBOX
+                    && !config.members.iter().any(|m| m.name == inbox)
+                {
+                    issues.push(TeamIssue::StrayInbox { name: inbox, path });
+                }
+            }
+        }
+
+        // External members have no thread to check, and joined members'
+        // threads live in another process.
+        for member in config.iter().flat_map(|config| &config.members) {
+            let alive = if member.joined {
+                self.member_lease(name, &member.name)
+                    .await?
+                    .is_some_and(|lease| !lease.is_stale())
+            } else {
+                member.is_external() || is_alive(member.thread_id).await
+            };
+            if !alive {
+                issues.push(TeamIssue::DeadMember {
+                    member: member.name.clone(),
+                    thread_id: member.thread_id,
+                });
+            }
+        }
+        Ok(issues)
+    }
+
+    /// Fix those of `issues`, as reported by [`TeamManager::validate`], that
+    /// can be fixed. Returns the fixed ones.
+    pub async fn repair(
+        &self,
+        name: &str,
+        issues: &[TeamIssue],
+    ) -> std::io::Result<Vec<TeamIssue>> {
+        let mut fixed = Vec::new();
+        for issue in issues {
+            match issue {
+                TeamIssue::UnreadableFile { path, .. } => set_aside(path).await?,
+                TeamIssue::DeadMember { member, .. } => {
+                    self.remove_member(name, member).await?;
+                    self.release_files(name, member, None).await?;
+                    self.release_member_lease(name, member).await?;
+                    let inbox = self.inboxes_dir(name).join(format!("{member}.json"));
+                    if inbox.exists() {
+                        fs::remove_file(inbox).await?;
+                    }
+                }
+                TeamIssue::StrayInbox { path, .. } => fs::remove_file(path).await?,
+                _ => continue,
+            }
+            fixed.push(issue.clone());
+        }
+        Ok(fixed)
+    }
+}
+
+/// Check that `name` is a plain name of letters, digits, `-` and `_`, fit to
+/// be a directory or branch name. Fails with `InvalidInput` naming `kind`
+/// (`"team"`, `"teammate"`) otherwise.
+pub fn validate_name(kind: &str, name: &str) -> std::io::Result<()> {
+    let is_plain_name = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if is_plain_name {
+        Ok(())
+    } else {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("{kind} name '{name}' may only contain letters, digits, '-' and '_'"),
+        ))
+    }
+}
+
+/// Run `edit` on the JSON document at `path` under an exclusive lock,
+/// writing it back if `edit` returns true alongside its result. A missing or
+/// empty file reads as `T::default()`.
+pub(crate) async fn edit_json_locked<T, R>(
+    path: PathBuf,
+    what: &'static str,
+    edit: impl FnOnce(&mut T) -> (R, bool) + Send + 'static,
+) -> std::io::Result<R>
+where
+    T: Serialize + DeserializeOwned + Default + Send + 'static,
+    R: Send + 'static,
+{
+    tokio::task::spawn_blocking(move || -> std::io::Result<R> {
+        let mut file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)?;
+        lock(&file, what)?;
+        let mut data = String::new();
+        file.read_to_string(&mut data)?;
+        let mut doc = if data.trim().is_empty() {
+            T::default()
+        } else {
+            serde_json::from_str(&data)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?
+        };
+        let (result, changed) = edit(&mut doc);
+        if changed {
+            let json = serde_json::to_string_pretty(&doc)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+            file.set_len(0)?;
+            file.seek(SeekFrom::Start(0))?;
+            file.write_all(json.as_bytes())?;
+            file.flush()?;
+            note_store_write();
+        }
+        Ok(result)
+    })
+    .await?
+}
+
+/// Team files this process has written. A file's modification time may not
+/// change on a quick rewrite of the same length, so cached copies (see
+/// [`crate::teams::service`]) also remember this count.
+static STORE_WRITES: AtomicU64 = AtomicU64::new(0);
+
+/// How many team files this process has written so far.
+pub(crate) fn store_writes() -> u64 {
+    STORE_WRITES.load(Ordering::SeqCst)
+}
+
+/// Record that this process wrote a team file.
+pub(crate) fn note_store_write() {
+    STORE_WRITES.fetch_add(1, Ordering::SeqCst);
+}
+
+/// Write `lock` to a file next to `path` that is only this thread's, so it
+/// can be moved over or linked to `path` in one step. Returns the file.
+async fn write_temp_lock(path: &Path, lock: &LeaderLock) -> std::io::Result<PathBuf> {
+    let json = serde_json::to_string_pretty(lock)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+    let temp = path.with_extension(format!("lock.{}.tmp", lock.thread_id));
+    fs::write(&temp, json).await?;
+    Ok(temp)
+}
+
+/// Most recent modification time of `dir` or any file directly inside it or
+/// one level below (e.g. `inboxes/*.json`).
+async fn last_modified(dir: &Path) -> std::io::Result<SystemTime> {
+    let mut latest = fs::metadata(dir).await?.modified()?;
+    let mut pending = vec![(dir.to_path_buf(), 0)];
+    while let Some((current, depth)) = pending.pop() {
+        let mut entries = fs::read_dir(&current).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let metadata = entry.metadata().await?;
+            latest = latest.max(metadata.modified()?);
+            if metadata.is_dir() && depth == 0 {
+                pending.push((entry.path(), depth + 1));
+            }
+        }
+    }
+    Ok(latest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn create_and_cleanup_team() {
+        let tmp = TempDir::new().unwrap();
+        let mgr = TeamManager::new(tmp.path().to_path_buf());
+        let leader = ThreadId::new();
+
+        let metadata = HashMap::from([("ticket".to_string(), "ABC-1".to_string())]);
+        let config = mgr
+            .create_team(
+                "test-team",
+                leader.clone(),
+                Some("Refactor auth".to_string()),
+                metadata.clone(),
+                TeamDisplayMode::Quiet,
+            )
+            .await
+            .unwrap();
+        assert_eq!(config.name, "test-team");
+
+        // Description and metadata survive a round-trip through disk.
+        let loaded = mgr.load_config("test-team").await.unwrap();
+        assert_eq!(loaded.description.as_deref(), Some("Refactor auth"));
+        assert_eq!(loaded.metadata, metadata);
+        assert_eq!(loaded.display_mode, TeamDisplayMode::Quiet);
+        assert!(mgr.team_exists("test-team").await);
+
+        mgr.cleanup_team("test-team").await.unwrap();
+        assert!(!mgr.team_exists("test-team").await);
+    }
+
+    #[tokio::test]
+    async fn create_team_rejects_names_that_are_not_plain() {
+        let tmp = TempDir::new().unwrap();
+        let mgr = TeamManager::new(tmp.path().join("teams"));
+
+        for name in ["../x", "a/b", "", "my team"] {
+            let err = mgr
+                .create_team(
+                    name,
+                    ThreadId::new(),
+                    None,
+                    HashMap::new(),
+                    TeamDisplayMode::default(),
+                )
+                .await
+                .unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput, "{name}");
+        }
+        assert!(!tmp.path().join("x").exists());
+    }
+
+    #[test]
+    fn legacy_display_mode_maps_to_inline() {
+        let mode: TeamDisplayMode = serde_json::from_str("\"in-process\"").unwrap();
+        assert_eq!(mode, TeamDisplayMode::Inline);
+    }
+
+    #[tokio::test]
+    async fn add_and_remove_member() {
+        let tmp = TempDir::new().unwrap();
+        let mgr = TeamManager::new(tmp.path().to_path_buf());
+        let leader = ThreadId::new();
+
+        mgr.create_team(
+            "t",
+            leader,
+            None,
+            HashMap::new(),
+            TeamDisplayMode::default(),
+        )
+        .await
+        .unwrap();
+
+        let member = MemberConfig {
+            name: "reviewer".to_string(),
+            thread_id: ThreadId::new(),
+            role: Some("security".to_string()),
+            status: "idle".to_string(),
+            ..Default::default()
+        };
+        mgr.add_member("t", member).await.unwrap();
 
         let members = mgr.list_members("t").await.unwrap();
         assert_eq!(members.len(), 1);
         assert!(members.contains_key("reviewer"));
+        let found = mgr
+            .member_by_thread("t", members["reviewer"])
+            .await
+            .unwrap();
+        assert_eq!(found.map(|m| m.name), Some("reviewer".to_string()));
+        assert!(mgr.member_by_thread("t", leader).await.unwrap().is_none());
+
+        let restarted = ThreadId::new();
+        mgr.update_member_thread("t", "reviewer", restarted)
+            .await
+            .unwrap();
+        assert_eq!(
+            mgr.list_members("t").await.unwrap().get("reviewer"),
+            Some(&restarted)
+        );
+        assert_eq!(mgr.record_restart("t", "reviewer").await.unwrap(), 1);
+        assert_eq!(mgr.record_restart("t", "reviewer").await.unwrap(), 2);
+        assert!(mgr.record_restart("t", "nobody").await.is_err());
 
         mgr.remove_member("t", "reviewer").await.unwrap();
         let members = mgr.list_members("t").await.unwrap();
         assert!(members.is_empty());
     }
+
+    #[test]
+    fn member_budget_reports_the_exceeded_limit() {
+        let budget = MemberBudget {
+            max_tokens: Some(1_000),
+            max_turns: Some(3),
+        };
+        assert_eq!(budget.exceeded(1_000, 2), None);
+        assert_eq!(
+            budget.exceeded(1_001, 2).as_deref(),
+            Some("used 1001 of 1000 tokens")
+        );
+        assert_eq!(budget.exceeded(10, 3).as_deref(), Some("used 3 of 3 turns"));
+        assert_eq!(MemberBudget::default().exceeded(i64::MAX, u32::MAX), None);
+    }
+
+    #[tokio::test]
+    async fn add_member_rejects_unsafe_and_duplicate_names() {
+        let tmp = TempDir::new().unwrap();
+        let mgr = TeamManager::new(tmp.path().to_path_buf());
+        mgr.create_team(
+            "t",
+            ThreadId::new(),
+            None,
+            HashMap::new(),
+            TeamDisplayMode::default(),
+        )
+        .await
+        .unwrap();
+        let member = |name: &str| MemberConfig {
+            name: name.to_string(),
+            thread_id: ThreadId::new(),
+            status: "idle".to_string(),
+            ..Default::default()
+        };
+
+        for name in ["../../..", "my teammate", "a..b"] {
+            let err = mgr.add_member("t", member(name)).await.unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput, "{name}");
+        }
+        mgr.add_member("t", member("alice")).await.unwrap();
+        let err = mgr.add_member("t", member("alice")).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+        assert_eq!(mgr.list_members("t").await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn add_member_respects_max_members() {
+        let tmp = TempDir::new().unwrap();
+        let mgr = TeamManager::new(tmp.path().to_path_buf()).with_max_members(1);
+
+        mgr.create_team(
+            "t",
+            ThreadId::new(),
+            None,
+            HashMap::new(),
+            TeamDisplayMode::default(),
+        )
+        .await
+        .unwrap();
+        let member = |name: &str| MemberConfig {
+            name: name.to_string(),
+            thread_id: ThreadId::new(),
+            status: "idle".to_string(),
+            ..Default::default()
+        };
+        mgr.add_member("t", member("first")).await.unwrap();
+
+        let err = mgr.add_member("t", member("second")).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::QuotaExceeded);
+        assert_eq!(
+            err.to_string(),
+            "team 't' already has 1 members (teams.max_members = 1)"
+        );
+        assert_eq!(mgr.list_members("t").await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn rename_team_moves_config_and_tasks() {
+        let tmp = TempDir::new().unwrap();
+        let mgr = TeamManager::new(tmp.path().join("teams"));
+        let tasks = TaskList::new(tmp.path().join("tasks"));
+
+        mgr.create_team(
+            "typo",
+            ThreadId::new(),
+            None,
+            HashMap::new(),
+            TeamDisplayMode::default(),
+        )
+        .await
+        .unwrap();
+        mgr.create_team(
+            "taken",
+            ThreadId::new(),
+            None,
+            HashMap::new(),
+            TeamDisplayMode::default(),
+        )
+        .await
+        .unwrap();
+        tasks.init("typo").await.unwrap();
+
+        let err = mgr.rename_team("typo", "taken", &tasks).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+        for bad in ["", "../escape", "a/b", "a b", "."] {
+            let err = mgr.rename_team("typo", bad, &tasks).await.unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput, "{bad:?}");
+        }
+
+        // A task list already at the new name blocks the move, and the team
+        // directory goes back where it was.
+        let tasks_root = tmp.path().join("tasks");
+        std::fs::create_dir_all(tasks_root.join("blocked")).unwrap();
+        std::fs::write(tasks_root.join("blocked").join("tasks.json"), "[]").unwrap();
+        assert!(mgr.rename_team("typo", "blocked", &tasks).await.is_err());
+        assert!(mgr.team_exists("typo").await);
+        assert!(!mgr.team_exists("blocked").await);
+        assert!(tasks_root.join("typo").join("tasks.json").exists());
+
+        let config = mgr.rename_team("typo", "fixed", &tasks).await.unwrap();
+        assert_eq!(config.name, "fixed");
+        assert!(!mgr.team_exists("typo").await);
+        assert_eq!(mgr.load_config("fixed").await.unwrap().name, "fixed");
+        assert!(mgr.leader_lock("fixed").await.unwrap().is_some());
+        assert!(tasks_root.join("fixed").join("tasks.json").exists());
+        assert!(!tasks_root.join("typo").exists());
+    }
+
+    #[tokio::test]
+    async fn sub_team_links_follow_rename_and_cleanup() {
+        let tmp = TempDir::new().unwrap();
+        let mgr = TeamManager::new(tmp.path().join("teams"));
+        let tasks = TaskList::new(tmp.path().join("tasks"));
+        for name in ["parent", "child"] {
+            mgr.create_team(
+                name,
+                ThreadId::new(),
+                None,
+                HashMap::new(),
+                TeamDisplayMode::default(),
+            )
+            .await
+            .unwrap();
+        }
+        tasks.init("child").await.unwrap();
+
+        mgr.link_sub_team("parent", "child").await.unwrap();
+        assert_eq!(
+            mgr.load_config("child")
+                .await
+                .unwrap()
+                .parent_team
+                .as_deref(),
+            Some("parent")
+        );
+        assert_eq!(
+            mgr.load_config("parent").await.unwrap().sub_teams,
+            vec!["child".to_string()]
+        );
+
+        mgr.rename_team("child", "docs", &tasks).await.unwrap();
+        assert_eq!(
+            mgr.load_config("parent").await.unwrap().sub_teams,
+            vec!["docs".to_string()]
+        );
+
+        mgr.cleanup_team("docs").await.unwrap();
+        assert!(
+            mgr.load_config("parent")
+                .await
+                .unwrap()
+                .sub_teams
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn worktrees_follow_rename_and_cleanup() {
+        use crate::teams::worktree::{create_worktree, git, teammate_branch};
+
+        let tmp = TempDir::new().unwrap();
+        let repo = tmp.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        std::fs::write(repo.join("a.txt"), "a\n").unwrap();
+        for args in [
+            &["init", "-q"][..],
+            &["config", "user.email", "test@example.com"],
+            &["config", "user.name", "Test"],
+            &["add", "-A"],
+            &["commit", "-q", "-m", "initial"],
+        ] {
+            git(&repo, args).await.unwrap();
+        }
+        let mgr = TeamManager::new(tmp.path().join("teams"));
+        let tasks = TaskList::new(tmp.path().join("tasks"));
+        mgr.create_team(
+            "typo",
+            ThreadId::new(),
+            None,
+            HashMap::new(),
+            TeamDisplayMode::default(),
+        )
+        .await
+        .unwrap();
+        let branch = teammate_branch("typo", "alice");
+        let worktree = create_worktree(&repo, &mgr.worktree_path("typo", "alice"), &branch)
+            .await
+            .unwrap();
+        mgr.add_member(
+            "typo",
+            MemberConfig {
+                name: "alice".to_string(),
+                worktree: Some(worktree),
+                ..MemberConfig::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let config = mgr.rename_team("typo", "fixed", &tasks).await.unwrap();
+        let path = mgr.worktree_path("fixed", "alice");
+        assert_eq!(
+            config.members[0].worktree.as_ref().map(|w| w.path.clone()),
+            Some(path.clone())
+        );
+        assert_eq!(
+            git(&path, &["rev-parse", "--abbrev-ref", "HEAD"])
+                .await
+                .unwrap(),
+            branch
+        );
+        let listed = git(&repo, &["worktree", "list", "--porcelain"])
+            .await
+            .unwrap();
+        assert!(listed.contains(&*path.to_string_lossy()), "{listed}");
+
+        mgr.cleanup_team("fixed").await.unwrap();
+        assert!(!path.exists());
+        assert_eq!(
+            git(&repo, &["branch", "--list", &branch]).await.unwrap(),
+            ""
+        );
+    }
+
+    #[tokio::test]
+    async fn record_usage_replaces_snapshots_per_thread() {
+        let tmp = TempDir::new().unwrap();
+        let mgr = TeamManager::new(tmp.path().to_path_buf());
+        mgr.create_team(
+            "t",
+            ThreadId::new(),
+            None,
+            HashMap::new(),
+            TeamDisplayMode::default(),
+        )
+        .await
+        .unwrap();
+        let tokens = |total_tokens| TokenUsage {
+            total_tokens,
+            ..TokenUsage::default()
+        };
+        let alice = ThreadId::new();
+
+        assert_eq!(mgr.usage("t").await.unwrap(), TeamUsage::default());
+        mgr.record_usage("t", vec![(alice, "alice".to_string(), tokens(10))])
+            .await
+            .unwrap();
+        let usage = mgr
+            .record_usage("t", vec![(alice, "alice".to_string(), tokens(25))])
+            .await
+            .unwrap();
+        assert_eq!(usage.total().total_tokens, 25);
+        assert_eq!(mgr.usage("t").await.unwrap(), usage);
+    }
+
+    #[tokio::test]
+    async fn edit_conflicts_are_taken_once() {
+        let tmp = TempDir::new().unwrap();
+        let mgr = TeamManager::new(tmp.path().to_path_buf());
+        mgr.create_team(
+            "t",
+            ThreadId::new(),
+            None,
+            HashMap::new(),
+            TeamDisplayMode::default(),
+        )
+        .await
+        .unwrap();
+        let paths = |paths: &[&str]| paths.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+
+        mgr.record_edits("t", "alice", &paths(&["src/lib.rs"]))
+            .await
+            .unwrap();
+        assert_eq!(
+            mgr.take_unreported_conflicts("t").await.unwrap(),
+            Vec::new()
+        );
+        mgr.record_edits("t", "bob", &paths(&["src/lib.rs", "docs.md"]))
+            .await
+            .unwrap();
+        assert_eq!(
+            mgr.take_unreported_conflicts("t").await.unwrap(),
+            vec![TeamFileConflict {
+                path: "src/lib.rs".to_string(),
+                members: vec!["alice".to_string(), "bob".to_string()],
+            }]
+        );
+        assert_eq!(
+            mgr.take_unreported_conflicts("t").await.unwrap(),
+            Vec::new()
+        );
+    }
+
+    #[tokio::test]
+    async fn escalations_are_answered_once() {
+        let tmp = TempDir::new().unwrap();
+        let mgr = TeamManager::new(tmp.path().to_path_buf());
+        mgr.create_team(
+            "t",
+            ThreadId::new(),
+            None,
+            HashMap::new(),
+            TeamDisplayMode::default(),
+        )
+        .await
+        .unwrap();
+
+        let first = mgr
+            .add_escalation(
+                "t",
+                "alice",
+                "Drop the legacy API?",
+                vec!["yes".to_string()],
+            )
+            .await
+            .unwrap();
+        let second = mgr
+            .add_escalation("t", "bob", "Which license?", Vec::new())
+            .await
+            .unwrap();
+        assert_eq!(
+            (first.id.as_str(), second.id.as_str()),
+            ("escalation-1", "escalation-2")
+        );
+
+        let answered = mgr
+            .answer_escalation("t", "escalation-1", "yes")
+            .await
+            .unwrap();
+        assert_eq!(
+            answered.and_then(|escalation| escalation.answer),
+            Some("yes".to_string())
+        );
+        assert_eq!(
+            mgr.answer_escalation("t", "escalation-1", "no")
+                .await
+                .unwrap(),
+            None
+        );
+        let escalations = mgr.escalations("t").await.unwrap();
+        let pending: Vec<&str> = escalations
+            .pending()
+            .map(|escalation| escalation.id.as_str())
+            .collect();
+        assert_eq!(pending, vec!["escalation-2"]);
+    }
+
+    #[tokio::test]
+    async fn concurrent_reservations_of_one_path_admit_one_holder() {
+        let tmp = TempDir::new().unwrap();
+        let mgr = std::sync::Arc::new(TeamManager::new(tmp.path().to_path_buf()));
+        mgr.create_team(
+            "t",
+            ThreadId::new(),
+            None,
+            HashMap::new(),
+            TeamDisplayMode::default(),
+        )
+        .await
+        .unwrap();
+
+        let handles: Vec<_> = ["alice", "bob", "carol", "dave"]
+            .into_iter()
+            .map(|holder| {
+                let mgr = std::sync::Arc::clone(&mgr);
+                tokio::spawn(async move {
+                    mgr.reserve_files("t", holder, &["src/lib.rs".to_string()])
+                        .await
+                })
+            })
+            .collect();
+        let mut reserved = 0;
+        for handle in handles {
+            match handle.await.unwrap() {
+                Ok(()) => reserved += 1,
+                Err(e) => assert_eq!(e.kind(), std::io::ErrorKind::ResourceBusy),
+            }
+        }
+
+        assert_eq!(reserved, 1);
+        assert_eq!(mgr.reservations("t").await.unwrap().paths.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn patches_get_sequential_ids() {
+        let tmp = TempDir::new().unwrap();
+        let mgr = TeamManager::new(tmp.path().to_path_buf());
+        mgr.create_team(
+            "t",
+            ThreadId::new(),
+            None,
+            HashMap::new(),
+            TeamDisplayMode::default(),
+        )
+        .await
+        .unwrap();
+
+        let first = mgr
+            .add_patch(
+                "t",
+                "alice",
+                "Fix parser",
+                vec!["src/lib.rs".to_string()],
+                "diff --git a/src/lib.rs b/src/lib.rs\n",
+            )
+            .await
+            .unwrap();
+        let second = mgr
+            .add_patch(
+                "t",
+                "bob",
+                "Update docs",
+                vec!["README.md".to_string()],
+                "diff --git a/README.md b/README.md\n",
+            )
+            .await
+            .unwrap();
+        assert_eq!(first.id, "patch-1");
+        assert_eq!(second.id, "patch-2");
+        assert_eq!(
+            mgr.patch_diff("t", &first).await.unwrap(),
+            b"diff --git a/src/lib.rs b/src/lib.rs\n"
+        );
+
+        // A diff rewritten after submission is refused.
+        std::fs::write(mgr.patch_diff_path("t", "patch-2"), "rm -rf\n").unwrap();
+        assert_eq!(
+            mgr.patch_diff("t", &second).await.unwrap_err().kind(),
+            std::io::ErrorKind::InvalidData
+        );
+
+        let approved = mgr
+            .update_patch("t", "patch-1", |patch| patch.status = PatchStatus::Approved)
+            .await
+            .unwrap();
+        assert_eq!(
+            approved.map(|patch| patch.status),
+            Some(PatchStatus::Approved)
+        );
+        assert_eq!(
+            mgr.update_patch("t", "patch-9", |_| {}).await.unwrap(),
+            None
+        );
+        let statuses: Vec<PatchStatus> = mgr
+            .patches("t")
+            .await
+            .unwrap()
+            .patches
+            .into_iter()
+            .map(|patch| patch.status)
+            .collect();
+        assert_eq!(statuses, vec![PatchStatus::Approved, PatchStatus::Pending]);
+    }
+
+    #[tokio::test]
+    async fn co_author_trailers_credit_members_only() {
+        let tmp = TempDir::new().unwrap();
+        let mgr = TeamManager::new(tmp.path().to_path_buf());
+        let mut config = mgr
+            .create_team(
+                "t",
+                ThreadId::new(),
+                None,
+                HashMap::new(),
+                TeamDisplayMode::default(),
+            )
+            .await
+            .unwrap();
+        config.members.push(MemberConfig {
+            name: "alice".to_string(),
+            thread_id: ThreadId::new(),
+            status: "idle".to_string(),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            config.co_author_trailers(["alice", "mallory", "alice"]),
+            vec!["Co-authored-by: alice (codex teammate)".to_string()]
+        );
+        assert_eq!(
+            config.co_author_trailers(std::iter::empty()),
+            Vec::<String>::new()
+        );
+    }
+
+    #[tokio::test]
+    async fn idempotent_results_round_trip() {
+        let tmp = TempDir::new().unwrap();
+        let mgr = TeamManager::new(tmp.path().to_path_buf());
+        mgr.create_team(
+            "t",
+            ThreadId::new(),
+            None,
+            HashMap::new(),
+            TeamDisplayMode::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(mgr.idempotent_result("t", "k").await.unwrap(), None);
+        mgr.record_idempotent_result("t", "k", "{\"status\":\"spawned\"}")
+            .await
+            .unwrap();
+        assert_eq!(
+            mgr.idempotent_result("t", "k").await.unwrap().as_deref(),
+            Some("{\"status\":\"spawned\"}")
+        );
+        assert_eq!(mgr.idempotent_result("t", "other").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn leader_lock_rejects_second_leader() {
+        let tmp = TempDir::new().unwrap();
+        let mgr = TeamManager::new(tmp.path().to_path_buf());
+        let first = ThreadId::new();
+        let second = ThreadId::new();
+
+        mgr.create_team("t", first, None, HashMap::new(), TeamDisplayMode::default())
+            .await
+            .unwrap();
+        let err = mgr
+            .create_team(
+                "t",
+                second,
+                None,
+                HashMap::new(),
+                TeamDisplayMode::default(),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::PermissionDenied);
+
+        // The holder can refresh its heartbeat.
+        mgr.acquire_leader_lock("t", first).await.unwrap();
+
+        // A stale lock can be taken over, but only by the team's leader.
+        let stale = LeaderLock {
+            thread_id: ThreadId::new(),
+            pid: 1,
+            heartbeat_at: "2000-01-01T00:00:00Z".to_string(),
+        };
+        fs::write(mgr.lock_path("t"), serde_json::to_string(&stale).unwrap())
+            .await
+            .unwrap();
+        let err = mgr.acquire_leader_lock("t", second).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::PermissionDenied);
+
+        // Nobody takes it over while another session is doing so.
+        let takeover = mgr.lock_path("t").with_extension("lock.takeover");
+        fs::write(&takeover, "").await.unwrap();
+        let err = mgr.acquire_leader_lock("t", first).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::ResourceBusy);
+        fs::remove_file(&takeover).await.unwrap();
+
+        mgr.acquire_leader_lock("t", first).await.unwrap();
+        let holder = mgr.leader_lock("t").await.unwrap();
+        assert_eq!(holder.map(|lock| lock.thread_id), Some(first));
+        assert!(!takeover.exists());
+
+        mgr.release_leader_lock("t", first).await.unwrap();
+        assert_eq!(mgr.leader_lock("t").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn joined_member_is_replaced_once_its_lease_goes_stale() {
+        let tmp = TempDir::new().unwrap();
+        let mgr = TeamManager::new(tmp.path().to_path_buf());
+        mgr.create_team(
+            "t",
+            ThreadId::new(),
+            None,
+            HashMap::new(),
+            TeamDisplayMode::default(),
+        )
+        .await
+        .unwrap();
+        let joined = |thread_id| MemberConfig {
+            name: "alice".to_string(),
+            thread_id,
+            status: "joined".to_string(),
+            joined: true,
+            ..Default::default()
+        };
+        let first = ThreadId::new();
+        let second = ThreadId::new();
+
+        mgr.join_team("t", joined(first)).await.unwrap();
+        let lease = mgr.member_lease("t", "alice").await.unwrap().unwrap();
+        assert_eq!(lease.thread_id, first);
+        assert_eq!(lease.status, AgentStatus::PendingInit);
+        assert!(
+            mgr.validate("t", |_| async { false })
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        let err = mgr.join_team("t", joined(second)).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+
+        // A process that stopped renewing its lease is gone.
+        let stale = MemberLease {
+            heartbeat_at: "2000-01-01T00:00:00Z".to_string(),
+            ..lease
+        };
+        fs::write(
+            mgr.lease_path("t", "alice"),
+            serde_json::to_string(&stale).unwrap(),
+        )
+        .await
+        .unwrap();
+        let issues = mgr.validate("t", |_| async { true }).await.unwrap();
+        assert_eq!(
+            issues,
+            vec![TeamIssue::DeadMember {
+                member: "alice".to_string(),
+                thread_id: first,
+            }]
+        );
+        mgr.join_team("t", joined(second)).await.unwrap();
+        let config = mgr.load_config("t").await.unwrap();
+        let members: Vec<_> = config.members.iter().map(|m| m.thread_id).collect();
+        assert_eq!(members, vec![second]);
+    }
+
+    #[tokio::test]
+    async fn set_leader_transfers_config_and_lock() {
+        let tmp = TempDir::new().unwrap();
+        let mgr = TeamManager::new(tmp.path().to_path_buf());
+        let original = ThreadId::new();
+        let successor = ThreadId::new();
+
+        mgr.create_team(
+            "t",
+            original,
+            None,
+            HashMap::new(),
+            TeamDisplayMode::default(),
+        )
+        .await
+        .unwrap();
+        let config = mgr.set_leader("t", successor).await.unwrap();
+        assert_eq!(config.leader_thread_id, successor);
+        let loaded = mgr.load_config("t").await.unwrap();
+        assert_eq!(loaded.leader_thread_id, successor);
+
+        let holder = mgr.leader_lock("t").await.unwrap();
+        assert_eq!(holder.map(|lock| lock.thread_id), Some(successor));
+        let err = mgr.acquire_leader_lock("t", original).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::PermissionDenied);
+    }
+
+    #[tokio::test]
+    async fn gc_collects_only_orphaned_teams() {
+        let tmp = TempDir::new().unwrap();
+        let mgr = TeamManager::new(tmp.path().to_path_buf());
+        let live_leader = ThreadId::new();
+
+        mgr.create_team(
+            "alive",
+            live_leader,
+            None,
+            HashMap::new(),
+            TeamDisplayMode::default(),
+        )
+        .await
+        .unwrap();
+        mgr.create_team(
+            "orphan",
+            ThreadId::new(),
+            None,
+            HashMap::new(),
+            TeamDisplayMode::default(),
+        )
+        .await
+        .unwrap();
+
+        // Nothing is old enough yet.
+        let collected = mgr
+            .gc(Duration::from_secs(3600), GcAction::Delete, |_| async {
+                false
+            })
+            .await
+            .unwrap();
+        assert!(collected.is_empty());
+
+        let collected = mgr
+            .gc(Duration::ZERO, GcAction::Report, |leader| async move {
+                leader == live_leader
+            })
+            .await
+            .unwrap();
+        assert_eq!(collected, vec!["orphan".to_string()]);
+        assert!(mgr.team_exists("orphan").await);
+
+        let collected = mgr
+            .gc(Duration::ZERO, GcAction::Archive, |leader| async move {
+                leader == live_leader
+            })
+            .await
+            .unwrap();
+        assert_eq!(collected, vec!["orphan".to_string()]);
+        assert!(mgr.team_exists("alive").await);
+        assert!(!mgr.team_exists("orphan").await);
+
+        let teams: Vec<String> = mgr
+            .list_teams()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|t| t.name)
+            .collect();
+        assert_eq!(teams, vec!["alive".to_string()]);
+        assert!(tmp.path().join(ARCHIVE_DIR).exists());
+    }
+
+    #[tokio::test]
+    async fn gc_spares_teams_whose_leader_lock_is_alive() {
+        let tmp = TempDir::new().unwrap();
+        let mgr = TeamManager::new(tmp.path().to_path_buf());
+        let leader = ThreadId::new();
+        mgr.create_team(
+            "t",
+            leader,
+            None,
+            HashMap::new(),
+            TeamDisplayMode::default(),
+        )
+        .await
+        .unwrap();
+
+        // A fresh heartbeat keeps the team even if no thread answers for it.
+        mgr.acquire_leader_lock("t", leader).await.unwrap();
+        let collected = mgr
+            .gc(Duration::ZERO, GcAction::Report, |_| async { false })
+            .await
+            .unwrap();
+        assert!(collected.is_empty());
+
+        let stale = |pid| LeaderLock {
+            thread_id: leader,
+            pid,
+            heartbeat_at: "2000-01-01T00:00:00Z".to_string(),
+        };
+        // So does a stale lock of another process that is still running.
+        #[cfg(unix)]
+        {
+            let json = serde_json::to_string(&stale(1)).unwrap();
+            fs::write(mgr.lock_path("t"), json).await.unwrap();
+            let collected = mgr
+                .gc(Duration::ZERO, GcAction::Report, |_| async { false })
+                .await
+                .unwrap();
+            assert!(collected.is_empty());
+        }
+
+        let json = serde_json::to_string(&stale(std::process::id())).unwrap();
+        fs::write(mgr.lock_path("t"), json).await.unwrap();
+        let collected = mgr
+            .gc(Duration::ZERO, GcAction::Report, |_| async { false })
+            .await
+            .unwrap();
+        assert_eq!(collected, vec!["t".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn validate_and_repair_dead_members_and_stray_files() {
+        let tmp = TempDir::new().unwrap();
+        let mgr = TeamManager::new(tmp.path().to_path_buf());
+        mgr.create_team(
+            "docs",
+            ThreadId::new(),
+            None,
+            HashMap::new(),
+            TeamDisplayMode::default(),
+        )
+        .await
+        .unwrap();
+        let alive = ThreadId::new();
+        let dead = ThreadId::new();
+        for (name, thread_id) in [("writer", alive), ("reviewer", dead)] {
+            mgr.add_member(
+                "docs",
+                MemberConfig {
+                    name: name.to_string(),
+                    thread_id,
+                    status: "running".to_string(),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        }
+        let inboxes = mgr.inboxes_dir("docs");
+        fs::write(inboxes.join("leader.json"), "[]").await.unwrap();
+        fs::write(inboxes.join("ghost.json"), "[]").await.unwrap();
+        fs::write(mgr.usage_path("docs"), "{").await.unwrap();
+
+        let issues = mgr
+            .validate("docs", |thread_id| async move { thread_id == alive })
+            .await
+            .unwrap();
+        assert_eq!(issues.len(), 3);
+        assert!(matches!(
+            &issues[0],
+            TeamIssue::UnreadableFile { path, .. } if path == &mgr.usage_path("docs")
+        ));
+        assert_eq!(
+            issues[1..],
+            [
+                TeamIssue::StrayInbox {
+                    name: "ghost".to_string(),
+                    path: inboxes.join("ghost.json"),
+                },
+                TeamIssue::DeadMember {
+                    member: "reviewer".to_string(),
+                    thread_id: dead,
+                },
+            ]
+        );
+
+        let fixed = mgr.repair("docs", &issues).await.unwrap();
+        assert_eq!(fixed, issues);
+        let names: Vec<String> = mgr
+            .load_config("docs")
+            .await
+            .unwrap()
+            .members
+            .into_iter()
+            .map(|m| m.name)
+            .collect();
+        assert_eq!(names, vec!["writer".to_string()]);
+        assert!(!inboxes.join("reviewer.json").exists());
+        assert!(tmp.path().join("docs").join("usage.json.corrupt").exists());
+        assert!(
+            mgr.validate("docs", |_| async { true })
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn dormant_teams_have_no_live_leader_lock() {
+        let tmp = TempDir::new().unwrap();
+        let mgr = TeamManager::new(tmp.path().to_path_buf());
+        for name in ["led", "released", "stale", "child"] {
+            mgr.create_team(
+                name,
+                ThreadId::new(),
+                None,
+                HashMap::new(),
+                TeamDisplayMode::default(),
+            )
+            .await
+            .unwrap();
+        }
+        for name in ["released", "child"] {
+            let config = mgr.load_config(name).await.unwrap();
+            mgr.release_leader_lock(name, config.leader_thread_id)
+                .await
+                .unwrap();
+        }
+        let stale = LeaderLock {
+            thread_id: ThreadId::new(),
+            pid: 1,
+            heartbeat_at: "2000-01-01T00:00:00Z".to_string(),
+        };
+        fs::write(
+            mgr.lock_path("stale"),
+            serde_json::to_string(&stale).unwrap(),
+        )
+        .await
+        .unwrap();
+        mgr.link_sub_team("stale", "child").await.unwrap();
+
+        let dormant: Vec<String> = mgr
+            .dormant_teams()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|t| t.name)
+            .collect();
+        assert_eq!(dormant, vec!["released".to_string(), "stale".to_string()]);
+
+        let archived = mgr.archive_team("released").await.unwrap();
+        assert!(!mgr.team_exists("released").await);
+        assert!(archived.join("config.json").exists());
+        assert_eq!(
+            archived.parent(),
+            Some(tmp.path().join(ARCHIVE_DIR).as_path())
+        );
+    }
 }
@@ -0,0 +1,117 @@
+//! Wall-clock limits for teammates.
+//!
+//! A teammate spawned with `timeout_minutes` gets a timer once it starts
+//! working. If the same thread still works for the team when the timer fires,
+//! the teammate is shut down, its in-progress tasks go back to pending so
+//! another teammate can accept them, and the leader is told through its inbox
+//! and a `TeamBudgetExhausted` event.
+
+use crate::agent::AgentStatus;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::teams::completion::LEADER_INBOX;
+use crate::teams::inbox::InboxMessage;
+use crate::teams::team_manager::MemberConfig;
+use codex_protocol::protocol::{EventMsg, TeamBudgetExhaustedEvent, TeamTaskEvent};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Arm the timeout of `member`, if it has one.
+pub(crate) fn start_teammate_timeout(
+    session: &Arc<Session>,
+    turn: Arc<TurnContext>,
+    team_name: String,
+    member: &MemberConfig,
+) {
+    let Some(minutes) = member.timeout_minutes else {
+        return;
+    };
+    let weak_session = Arc::downgrade(session);
+    let member_name = member.name.clone();
+    let thread_id = member.thread_id;
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(minutes.saturating_mul(60))).await;
+        let Some(session) = weak_session.upgrade() else {
+            return;
+        };
+        let teams = &session.services.teams;
+        // The teammate may have been removed or restarted on a new thread,
+        // which arms a timer of its own.
+        let still_member = teams.load_config(&team_name).await.is_ok_and(|config| {
+            config
+                .members
+                .iter()
+                .any(|m| m.name == member_name && m.thread_id == thread_id)
+        });
+        let agent_control = &session.services.agent_control;
+        if !still_member
+            || matches!(
+                agent_control.get_status(thread_id).await,
+                AgentStatus::Shutdown | AgentStatus::NotFound
+            )
+        {
+            return;
+        }
+
+        let reason = format!("exceeded its timeout of {minutes} minutes");
+        tracing::info!("shutting down teammate {member_name}: {reason}");
+        let _ = agent_control.shutdown_agent(thread_id).await;
+
+        if let Err(e) = teams
+            .manager()
+            .release_files(&team_name, &member_name, None)
+            .await
+        {
+            tracing::warn!("failed to release file reservations of {member_name}: {e}");
+        }
+        let released = match teams.tasks().release_tasks(&team_name, &member_name).await {
+            Ok(released) => released,
+            Err(e) => {
+                tracing::warn!("failed to release tasks of {member_name}: {e}");
+                Vec::new()
+            }
+        };
+        let notice = json!({
+            "type": "teammate_timed_out",
+            "teammate": member_name,
+            "reason": reason,
+            "released_tasks": released.iter().map(|t| t.id.as_str()).collect::<Vec<_>>(),
+        });
+        let message = InboxMessage {
+            from: member_name.clone(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            content: notice.to_string(),
+            read: false,
+        };
+        if let Err(e) = teams
+            .inbox(&team_name)
+            .send_message(LEADER_INBOX, message)
+            .await
+        {
+            tracing::warn!("failed to tell leader that {member_name} timed out: {e}");
+        }
+        for task in released {
+            session
+                .send_event(
+                    &turn,
+                    EventMsg::TeamTaskUpdated(TeamTaskEvent {
+                        team_name: team_name.clone(),
+                        task,
+                    }),
+                )
+                .await;
+        }
+        session
+            .send_event(
+                &turn,
+                EventMsg::TeamBudgetExhausted(TeamBudgetExhaustedEvent {
+                    team_name,
+                    member_name,
+                    thread_id,
+                    reason,
+                }),
+            )
+            .await;
+    });
+}
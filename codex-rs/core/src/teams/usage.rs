@@ -0,0 +1,132 @@
+//! Team-wide token accounting.
+//!
+//! Each team keeps the latest token totals of its leader and of every
+//! teammate thread in `usage.json`. Entries are keyed by thread, so the usage
+//! of a teammate's earlier threads still counts after it is restarted.
+//! `teams.max_team_tokens` caps the sum: spawns are refused once the team
+//! reaches it, and the leader is warned when usage crosses
+//! [`USAGE_WARNING_PERCENT`] of it.
+
+use crate::codex::Session;
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::TokenUsage;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Share of `teams.max_team_tokens` at which the leader is warned.
+pub(crate) const USAGE_WARNING_PERCENT: i64 = 80;
+
+/// Contents of a team's `usage.json`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TeamUsage {
+    /// Latest usage of each thread that worked for the team, keyed by thread
+    /// id.
+    #[serde(default)]
+    pub threads: BTreeMap<String, ThreadUsage>,
+}
+
+/// Token usage of one thread on a team.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThreadUsage {
+    /// `"leader"` or the teammate's name.
+    pub name: String,
+    pub usage: TokenUsage,
+}
+
+impl TeamUsage {
+    /// Combined usage of every thread.
+    pub fn total(&self) -> TokenUsage {
+        let mut total = TokenUsage::default();
+        for thread in self.threads.values() {
+            total.add_assign(&thread.usage);
+        }
+        total
+    }
+
+    /// Total tokens per participant, summed over a teammate's restarts.
+    pub fn tokens_by_participant(&self) -> BTreeMap<&str, i64> {
+        let mut totals = BTreeMap::new();
+        for thread in self.threads.values() {
+            *totals.entry(thread.name.as_str()).or_default() += thread.usage.total_tokens;
+        }
+        totals
+    }
+}
+
+/// Warning for the leader when team usage moves from `previous` to `current`
+/// tokens across the warning threshold of `cap`.
+pub(crate) fn usage_warning(previous: i64, current: i64, cap: i64) -> Option<String> {
+    let threshold = cap.saturating_mul(USAGE_WARNING_PERCENT) / 100;
+    (previous < threshold && current >= threshold).then(|| {
+        format!(
+            "The team has used {current} of its {cap} token budget (teams.max_team_tokens); new teammates are refused once it is reached."
+        )
+    })
+}
+
+/// Snapshot the usage of `session` (the leader) and of every live teammate
+/// into the team's `usage.json`, returning the updated totals.
+pub(crate) async fn refresh_team_usage(
+    session: &Session,
+    team_name: &str,
+) -> std::io::Result<TeamUsage> {
+    let teams = &session.services.teams;
+    let config = teams.load_config(team_name).await?;
+    let mut snapshots: Vec<(ThreadId, String, TokenUsage)> = Vec::new();
+    if let Some(usage) = session.total_token_usage().await {
+        snapshots.push((session.conversation_id, "leader".to_string(), usage));
+    }
+    for member in &config.members {
+        if let Some(usage) = session
+            .services
+            .agent_control
+            .get_total_token_usage(member.thread_id)
+            .await
+        {
+            snapshots.push((member.thread_id, member.name.clone(), usage));
+        }
+    }
+    teams.manager().record_usage(team_name, snapshots).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn usage(total_tokens: i64) -> TokenUsage {
+        TokenUsage {
+            total_tokens,
+            ..TokenUsage::default()
+        }
+    }
+
+    #[test]
+    fn totals_include_restarted_threads() {
+        let thread = |name: &str, tokens| ThreadUsage {
+            name: name.to_string(),
+            usage: usage(tokens),
+        };
+        let team_usage = TeamUsage {
+            threads: BTreeMap::from([
+                ("a".to_string(), thread("leader", 100)),
+                ("b".to_string(), thread("alice", 40)),
+                ("c".to_string(), thread("alice", 10)),
+            ]),
+        };
+
+        assert_eq!(team_usage.total().total_tokens, 150);
+        assert_eq!(
+            team_usage.tokens_by_participant(),
+            BTreeMap::from([("alice", 50), ("leader", 100)])
+        );
+    }
+
+    #[test]
+    fn usage_warning_fires_once_when_crossing_the_threshold() {
+        assert_eq!(usage_warning(0, 799, 1_000), None);
+        assert!(usage_warning(799, 800, 1_000).is_some());
+        assert_eq!(usage_warning(800, 900, 1_000), None);
+    }
+}
@@ -0,0 +1,296 @@
+//! Team webhooks.
+//!
+//! `teams.webhooks` lists endpoints that are sent a JSON `POST` for the team
+//! events a human may want to be paged about: a team created or cleaned up, a
+//! task completed or failed, and a teammate errored or went over its budget.
+//! A task fails when its assignee errors while working on it. Every session
+//! of a team sends the webhooks of the events it emits itself, in the
+//! background; a webhook that cannot be delivered is only logged.
+
+use crate::config::types::TeamWebhook;
+use crate::config::types::TeamWebhookEvent;
+use crate::default_client::build_reqwest_client;
+use crate::teams::service::TeamService;
+use codex_protocol::protocol::AgentStatus;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::TeamMemberInfo;
+use codex_protocol::protocol::TeamTaskInfo;
+use codex_protocol::protocol::TeamTaskStatus;
+use serde::Serialize;
+use std::time::Duration;
+
+/// How long a webhook endpoint may take to answer.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Body of a webhook request.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct TeamWebhookPayload {
+    pub event: TeamWebhookEvent,
+    pub team_name: String,
+    /// When the event happened, RFC 3339.
+    pub timestamp: String,
+    /// The teammate that errored, or the assignee of the task.
+    pub member: Option<TeamMemberInfo>,
+    pub task: Option<TeamTaskInfo>,
+}
+
+/// Webhook payloads for `msg`: none for events no webhook is sent for, and
+/// one `task_failed` per task an errored teammate had in progress besides its
+/// `member_errored`.
+pub(crate) async fn webhook_payloads(
+    teams: &TeamService,
+    msg: &EventMsg,
+) -> Vec<TeamWebhookPayload> {
+    let payload =
+        |event, team_name: &str, member: Option<&TeamMemberInfo>, task| TeamWebhookPayload {
+            event,
+            team_name: team_name.to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            member: member.cloned(),
+            task,
+        };
+    match msg {
+        EventMsg::TeamCreated(ev) => {
+            vec![payload(
+                TeamWebhookEvent::TeamCreated,
+                &ev.team_name,
+                None,
+                None,
+            )]
+        }
+        EventMsg::TeamCleanup(ev) => {
+            vec![payload(
+                TeamWebhookEvent::TeamCleaned,
+                &ev.team_name,
+                None,
+                None,
+            )]
+        }
+        EventMsg::TeamTaskUpdated(ev) if matches!(ev.task.status, TeamTaskStatus::Completed) => {
+            vec![payload(
+                TeamWebhookEvent::TaskCompleted,
+                &ev.team_name,
+                None,
+                Some(ev.task.clone()),
+            )]
+        }
+        EventMsg::TeamBudgetExhausted(ev) => {
+            // The teammate is shut down for going over its budget.
+            let member = TeamMemberInfo {
+                name: ev.member_name.clone(),
+                thread_id: ev.thread_id,
+                role: None,
+                status: AgentStatus::Shutdown,
+            };
+            vec![payload(
+                TeamWebhookEvent::BudgetExhausted,
+                &ev.team_name,
+                Some(&member),
+                None,
+            )]
+        }
+        EventMsg::TeamMemberStatusChanged(ev)
+            if matches!(ev.member.status, AgentStatus::Errored(_))
+                && !matches!(ev.previous_status, Some(AgentStatus::Errored(_))) =>
+        {
+            let mut payloads = vec![payload(
+                TeamWebhookEvent::MemberErrored,
+                &ev.team_name,
+                Some(&ev.member),
+                None,
+            )];
+            match teams.get_all_tasks(&ev.team_name).await {
+                Ok(tasks) => payloads.extend(
+                    tasks
+                        .into_iter()
+                        .filter(|task| {
+                            matches!(task.status, TeamTaskStatus::InProgress)
+                                && task.assigned_to.as_deref() == Some(ev.member.name.as_str())
+                        })
+                        .map(|task| {
+                            payload(
+                                TeamWebhookEvent::TaskFailed,
+                                &ev.team_name,
+                                Some(&ev.member),
+                                Some(task),
+                            )
+                        }),
+                ),
+                Err(e) => tracing::warn!("failed to load tasks of {}: {e}", ev.team_name),
+            }
+            payloads
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Post `payload` to each of `webhooks` subscribed to its event, in the
+/// background.
+pub(crate) fn send_webhooks(webhooks: &[TeamWebhook], payload: &TeamWebhookPayload) {
+    for webhook in webhooks
+        .iter()
+        .filter(|webhook| webhook.events.is_empty() || webhook.events.contains(&payload.event))
+    {
+        let url = webhook.url.clone();
+        let payload = payload.clone();
+        tokio::spawn(async move {
+            if let Err(e) = post_json(&url, &payload).await {
+                tracing::warn!("failed to send team webhook to {}: {e}", redacted_url(&url));
+            }
+        });
+    }
+}
+
+/// Post `body` to `url` as JSON.
+pub(crate) async fn post_json(url: &str, body: &impl Serialize) -> Result<(), String> {
+    let response = build_reqwest_client()
+        .post(url)
+        .timeout(WEBHOOK_TIMEOUT)
+        .json(body)
+        .send()
+        .await
+        .map_err(|e| e.without_url().to_string())?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("endpoint answered {status}"));
+    }
+    Ok(())
+}
+
+/// `url` with everything after the host left out, for logs: webhook URLs
+/// often carry their secret in the path or query.
+pub(crate) fn redacted_url(url: &str) -> String {
+    match url::Url::parse(url) {
+        Ok(parsed) => match parsed.host_str() {
+            Some(host) => format!("{}://{host}/…", parsed.scheme()),
+            None => format!("{}:…", parsed.scheme()),
+        },
+        Err(_) => "an unparsable URL".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::ThreadId;
+    use codex_protocol::protocol::TeamDisplayMode;
+    use codex_protocol::protocol::TeamMemberEvent;
+    use codex_protocol::protocol::TeamTaskEvent;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+    use wiremock::matchers::body_partial_json;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+
+    fn task(id: &str, status: TeamTaskStatus) -> TeamTaskInfo {
+        TeamTaskInfo {
+            id: id.to_string(),
+            title: format!("Task {id}"),
+            description: None,
+            status,
+            assigned_to: Some("reviewer".to_string()),
+            depends_on: Vec::new(),
+            result: None,
+            created_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn errored_teammate_fails_its_tasks_in_progress() {
+        let tmp = TempDir::new().unwrap();
+        let teams = TeamService::with_roots(tmp.path().join("teams"), tmp.path().join("tasks"), 8);
+        teams
+            .manager()
+            .create_team(
+                "zeta",
+                ThreadId::new(),
+                None,
+                HashMap::new(),
+                TeamDisplayMode::default(),
+            )
+            .await
+            .unwrap();
+        teams.tasks().init("zeta").await.unwrap();
+        for task in [
+            task("task-1", TeamTaskStatus::Completed),
+            task("task-2", TeamTaskStatus::InProgress),
+        ] {
+            teams.tasks().create_task("zeta", task).await.unwrap();
+        }
+        let reviewer = TeamMemberInfo {
+            name: "reviewer".to_string(),
+            thread_id: ThreadId::new(),
+            role: None,
+            status: AgentStatus::Errored("boom".to_string()),
+        };
+        let errored = EventMsg::TeamMemberStatusChanged(TeamMemberEvent {
+            team_name: "zeta".to_string(),
+            member: reviewer.clone(),
+            previous_status: Some(AgentStatus::Running),
+        });
+
+        let payloads = webhook_payloads(&teams, &errored).await;
+        assert_eq!(
+            payloads
+                .iter()
+                .map(|p| (p.event, p.task.as_ref().map(|t| t.id.as_str())))
+                .collect::<Vec<_>>(),
+            vec![
+                (TeamWebhookEvent::MemberErrored, None),
+                (TeamWebhookEvent::TaskFailed, Some("task-2")),
+            ]
+        );
+        assert_eq!(payloads[1].member, Some(reviewer));
+
+        let completed = EventMsg::TeamTaskUpdated(TeamTaskEvent {
+            team_name: "zeta".to_string(),
+            task: task("task-1", TeamTaskStatus::Completed),
+        });
+        let payloads = webhook_payloads(&teams, &completed).await;
+        assert_eq!(payloads.len(), 1);
+        assert_eq!(payloads[0].event, TeamWebhookEvent::TaskCompleted);
+
+        let accepted = EventMsg::TeamTaskUpdated(TeamTaskEvent {
+            team_name: "zeta".to_string(),
+            task: task("task-2", TeamTaskStatus::InProgress),
+        });
+        assert_eq!(webhook_payloads(&teams, &accepted).await, Vec::new());
+    }
+
+    #[tokio::test]
+    async fn posts_the_payload_as_json() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .and(body_partial_json(json!({
+                "event": "team_cleaned",
+                "team_name": "zeta",
+                "member": null,
+                "task": null,
+            })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let payload = TeamWebhookPayload {
+            event: TeamWebhookEvent::TeamCleaned,
+            team_name: "zeta".to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            member: None,
+            task: None,
+        };
+
+        post_json(&format!("{}/hook", server.uri()), &payload)
+            .await
+            .unwrap();
+        let err = post_json(&format!("{}/missing", server.uri()), &payload)
+            .await
+            .unwrap_err();
+        assert_eq!(err, "endpoint answered 404 Not Found");
+    }
+}
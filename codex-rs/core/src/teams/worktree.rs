@@ -0,0 +1,369 @@
+//! Per-teammate git worktrees.
+//!
+//! A teammate spawned with `isolation: "worktree"` works in its own checkout
+//! on its own branch instead of the leader's cwd, so parallel teammates do not
+//! overwrite each other's edits. The worktree is created from the leader's
+//! `HEAD` under the team directory and recorded in the member's
+//! [`MemberWorktree`]; `merge_teammate_work` later brings the branch back
+//! into the leader's checkout with [`merge_worktree`].
+
+use serde::Deserialize;
+use serde::Serialize;
+use std::path::Path;
+use std::path::PathBuf;
+use tokio::process::Command;
+
+/// `isolation` value that gives a teammate its own worktree.
+pub(crate) const WORKTREE_ISOLATION: &str = "worktree";
+
+/// How a teammate's branch is brought into the leader's checkout.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum MergeStrategy {
+    /// Merge commit on the leader's branch.
+    #[default]
+    Merge,
+    /// Rebase the teammate's branch onto the leader's `HEAD`, then
+    /// fast-forward the leader's branch to it.
+    Rebase,
+}
+
+/// Result of [`merge_worktree`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum MergeOutcome {
+    /// The leader's branch now contains the teammate's `commits` and is at
+    /// `head`.
+    Merged { head: String, commits: usize },
+    /// The teammate's branch has nothing the leader's branch lacks.
+    UpToDate,
+    /// The merge was aborted, leaving both checkouts as they were, because
+    /// these files conflict.
+    Conflicts { files: Vec<String> },
+}
+
+/// Git checkout a teammate works in.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemberWorktree {
+    /// Top-level directory of the leader's repository.
+    pub repo_root: PathBuf,
+    /// Directory of the teammate's worktree.
+    pub path: PathBuf,
+    /// Branch checked out in the worktree.
+    pub branch: String,
+    /// Commit the branch was created from.
+    pub base_commit: String,
+}
+
+impl MemberWorktree {
+    /// Directory in the worktree that corresponds to `leader_cwd` in the
+    /// leader's checkout, so a leader working in a subdirectory hands its
+    /// teammates the same subdirectory. Falls back to the worktree root if
+    /// that directory is not checked in.
+    pub fn cwd_for(&self, leader_cwd: &Path) -> PathBuf {
+        leader_cwd
+            .strip_prefix(&self.repo_root)
+            .map(|relative| self.path.join(relative))
+            .ok()
+            .filter(|cwd| cwd.is_dir())
+            .unwrap_or_else(|| self.path.clone())
+    }
+}
+
+/// Branch a teammate's worktree is created on.
+pub(crate) fn teammate_branch(team_name: &str, member_name: &str) -> String {
+    format!("codex/{team_name}/{member_name}")
+}
+
+/// Create a worktree at `path` on a new `branch` starting from the `HEAD` of
+/// the repository containing `leader_cwd`.
+pub(crate) async fn create_worktree(
+    leader_cwd: &Path,
+    path: &Path,
+    branch: &str,
+) -> std::io::Result<MemberWorktree> {
+    let repo_root = PathBuf::from(git(leader_cwd, &["rev-parse", "--show-toplevel"]).await?);
+    let base_commit = git(&repo_root, &["rev-parse", "HEAD"]).await?;
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let path_arg = path.to_string_lossy();
+    git(
+        &repo_root,
+        &["worktree", "add", "-b", branch, &path_arg, &base_commit],
+    )
+    .await?;
+    Ok(MemberWorktree {
+        repo_root,
+        path: path.to_path_buf(),
+        branch: branch.to_string(),
+        base_commit,
+    })
+}
+
+/// Commit whatever the teammate left uncommitted in `worktree`, then bring
+/// its branch into the leader's checkout at `leader_cwd` using `strategy`.
+/// The commits this creates end with `trailers`.
+pub(crate) async fn merge_worktree(
+    leader_cwd: &Path,
+    worktree: &MemberWorktree,
+    strategy: MergeStrategy,
+    trailers: &[String],
+) -> std::io::Result<MergeOutcome> {
+    let branch = worktree.branch.as_str();
+    if !git(&worktree.path, &["status", "--porcelain"])
+        .await?
+        .is_empty()
+    {
+        git(&worktree.path, &["add", "-A"]).await?;
+        let message = with_trailers(format!("Uncommitted work from {branch}"), trailers);
+        git(&worktree.path, &["commit", "-q", "-m", &message]).await?;
+    }
+    let range = format!("HEAD..{branch}");
+    let commits: usize = git(leader_cwd, &["rev-list", "--count", &range])
+        .await?
+        .parse()
+        .map_err(std::io::Error::other)?;
+    if commits == 0 {
+        return Ok(MergeOutcome::UpToDate);
+    }
+
+    match strategy {
+        MergeStrategy::Merge => {
+            let message = with_trailers(format!("Merge branch '{branch}'"), trailers);
+            if let Err(e) = git(leader_cwd, &["merge", "--no-ff", "-m", &message, branch]).await {
+                let files = unmerged_files(leader_cwd).await?;
+                if files.is_empty() {
+                    return Err(e);
+                }
+                git(leader_cwd, &["merge", "--abort"]).await?;
+                return Ok(MergeOutcome::Conflicts { files });
+            }
+        }
+        MergeStrategy::Rebase => {
+            let leader_head = git(leader_cwd, &["rev-parse", "HEAD"]).await?;
+            if let Err(e) = git(&worktree.path, &["rebase", &leader_head]).await {
+                let files = unmerged_files(&worktree.path).await?;
+                if files.is_empty() {
+                    return Err(e);
+                }
+                git(&worktree.path, &["rebase", "--abort"]).await?;
+                return Ok(MergeOutcome::Conflicts { files });
+            }
+            git(leader_cwd, &["merge", "--ff-only", branch]).await?;
+        }
+    }
+    let head = git(leader_cwd, &["rev-parse", "HEAD"]).await?;
+    Ok(MergeOutcome::Merged { head, commits })
+}
+
+/// `message` followed by a blank line and `trailers`, one per line.
+fn with_trailers(message: String, trailers: &[String]) -> String {
+    if trailers.is_empty() {
+        message
+    } else {
+        format!("{message}\n\n{}", trailers.join("\n"))
+    }
+}
+
+async fn unmerged_files(cwd: &Path) -> std::io::Result<Vec<String>> {
+    let output = git(cwd, &["diff", "--name-only", "--diff-filter=U"]).await?;
+    Ok(output.lines().map(str::to_string).collect())
+}
+
+/// Remove `worktree` and its branch, discarding any work on it.
+pub(crate) async fn remove_worktree(worktree: &MemberWorktree) -> std::io::Result<()> {
+    let path_arg = worktree.path.to_string_lossy();
+    git(
+        &worktree.repo_root,
+        &["worktree", "remove", "--force", &path_arg],
+    )
+    .await?;
+    git(&worktree.repo_root, &["branch", "-D", &worktree.branch]).await?;
+    Ok(())
+}
+
+/// Run `git` in `cwd` and return its trimmed stdout, or an error carrying
+/// git's stderr.
+pub(crate) async fn git(cwd: &Path, args: &[&str]) -> std::io::Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(cwd)
+        .kill_on_drop(true)
+        .output()
+        .await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(std::io::Error::other(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            stderr.trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    async fn init_repo(dir: &Path) {
+        for args in [
+            &["init", "-q"][..],
+            &["config", "user.email", "test@example.com"],
+            &["config", "user.name", "Test"],
+            &["add", "-A"],
+            &["commit", "-q", "-m", "initial"],
+        ] {
+            git(dir, args).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn creates_a_branch_from_head() {
+        let tmp = TempDir::new().unwrap();
+        let repo = tmp.path().join("repo");
+        std::fs::create_dir_all(repo.join("crates/app")).unwrap();
+        std::fs::write(repo.join("crates/app/lib.rs"), "").unwrap();
+        init_repo(&repo).await;
+        let head = git(&repo, &["rev-parse", "HEAD"]).await.unwrap();
+
+        let path = tmp.path().join("worktrees").join("alice");
+        let branch = teammate_branch("refactor", "alice");
+        let worktree = create_worktree(&repo.join("crates/app"), &path, &branch)
+            .await
+            .unwrap();
+
+        assert_eq!(worktree.branch, "codex/refactor/alice");
+        assert_eq!(worktree.base_commit, head);
+        assert_eq!(
+            git(&path, &["rev-parse", "--abbrev-ref", "HEAD"])
+                .await
+                .unwrap(),
+            "codex/refactor/alice"
+        );
+        let repo_root = worktree.repo_root.clone();
+        assert_eq!(
+            worktree.cwd_for(&repo_root.join("crates/app")),
+            path.join("crates/app")
+        );
+        assert_eq!(worktree.cwd_for(tmp.path()), path);
+
+        // Reusing the branch of an existing teammate is refused by git.
+        assert!(
+            create_worktree(&repo, &tmp.path().join("other"), &branch)
+                .await
+                .is_err()
+        );
+
+        remove_worktree(&worktree).await.unwrap();
+        assert!(!path.exists());
+        assert_eq!(
+            git(&repo, &["branch", "--list", &branch]).await.unwrap(),
+            ""
+        );
+    }
+
+    /// A repository with a committed `a.txt` and `b.txt`, plus a teammate
+    /// worktree created from it.
+    async fn repo_with_worktree(tmp: &TempDir) -> (PathBuf, MemberWorktree) {
+        let repo = tmp.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        std::fs::write(repo.join("a.txt"), "a\n").unwrap();
+        std::fs::write(repo.join("b.txt"), "b\n").unwrap();
+        init_repo(&repo).await;
+        let worktree = create_worktree(
+            &repo,
+            &tmp.path().join("alice"),
+            &teammate_branch("team", "alice"),
+        )
+        .await
+        .unwrap();
+        (repo, worktree)
+    }
+
+    #[tokio::test]
+    async fn merges_committed_and_uncommitted_work() {
+        for strategy in [MergeStrategy::Merge, MergeStrategy::Rebase] {
+            let tmp = TempDir::new().unwrap();
+            let (repo, worktree) = repo_with_worktree(&tmp).await;
+            assert_eq!(
+                merge_worktree(&repo, &worktree, strategy, &[])
+                    .await
+                    .unwrap(),
+                MergeOutcome::UpToDate
+            );
+
+            // The teammate leaves its edit uncommitted while the leader moves on.
+            std::fs::write(worktree.path.join("a.txt"), "alice\n").unwrap();
+            std::fs::write(repo.join("b.txt"), "leader\n").unwrap();
+            git(&repo, &["commit", "-q", "-am", "leader"])
+                .await
+                .unwrap();
+
+            let trailers = vec!["Co-authored-by: alice (codex teammate)".to_string()];
+            let MergeOutcome::Merged { head, commits } =
+                merge_worktree(&repo, &worktree, strategy, &trailers)
+                    .await
+                    .unwrap()
+            else {
+                panic!("expected {strategy:?} to succeed");
+            };
+            assert_eq!(commits, 1);
+            assert_eq!(head, git(&repo, &["rev-parse", "HEAD"]).await.unwrap());
+            assert_eq!(
+                std::fs::read_to_string(repo.join("a.txt")).unwrap(),
+                "alice\n"
+            );
+            assert_eq!(
+                std::fs::read_to_string(repo.join("b.txt")).unwrap(),
+                "leader\n"
+            );
+            let merges = git(&repo, &["rev-list", "--merges", "--count", "HEAD"])
+                .await
+                .unwrap();
+            let expected_merges = match strategy {
+                MergeStrategy::Merge => "1",
+                MergeStrategy::Rebase => "0",
+            };
+            assert_eq!(merges, expected_merges);
+            // The merge commit, or the rebased commit of the uncommitted work,
+            // credits the teammate.
+            let message = git(&repo, &["log", "-1", "--format=%B"]).await.unwrap();
+            assert!(
+                message.ends_with("\n\nCo-authored-by: alice (codex teammate)"),
+                "{message}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn conflicts_are_reported_and_aborted() {
+        for strategy in [MergeStrategy::Merge, MergeStrategy::Rebase] {
+            let tmp = TempDir::new().unwrap();
+            let (repo, worktree) = repo_with_worktree(&tmp).await;
+            std::fs::write(worktree.path.join("a.txt"), "alice\n").unwrap();
+            std::fs::write(repo.join("a.txt"), "leader\n").unwrap();
+            git(&repo, &["commit", "-q", "-am", "leader"])
+                .await
+                .unwrap();
+
+            assert_eq!(
+                merge_worktree(&repo, &worktree, strategy, &[])
+                    .await
+                    .unwrap(),
+                MergeOutcome::Conflicts {
+                    files: vec!["a.txt".to_string()]
+                }
+            );
+            assert_eq!(git(&repo, &["status", "--porcelain"]).await.unwrap(), "");
+            assert_eq!(
+                git(&worktree.path, &["status", "--porcelain"])
+                    .await
+                    .unwrap(),
+                ""
+            );
+        }
+    }
+}
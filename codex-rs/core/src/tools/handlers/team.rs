@@ -1,29 +1,78 @@
 //! Tool handler for Agent Teams tools.
 //!
 //! Dispatches tool calls to the `TeamManager`, `TaskList`, and `Inbox` backends
-//! defined in `crate::teams`, and wires `spawn_teammate` / `shutdown_teammate`
-//! through `AgentControl` so that real agent threads are created.
+//! defined in `crate::teams` (shared per session through `TeamService`), and
+//! wires `spawn_teammate` / `shutdown_teammate` through `AgentControl` so that
+//! real agent threads are created.
 
 use async_trait::async_trait;
+use codex_protocol::ThreadId;
+use codex_protocol::config_types::SandboxMode;
 use codex_protocol::models::FunctionCallOutputBody;
 use codex_protocol::protocol::{
-    TeamCleanupEvent, TeamCreatedEvent, TeamMemberEvent, TeamMemberInfo, TeamTaskEvent,
-    TeamTaskInfo, TeamTaskStatus,
+    TeamCleanupEvent, TeamCreatedEvent, TeamDisplayMode, TeamErrorCode, TeamErrorEvent,
+    TeamLeaderChangedEvent, TeamMemberEvent, TeamMemberInfo, TeamMessageEvent, TeamPlan,
+    TeamRenamedEvent, TeamTaskEvent, TeamTaskInfo, TeamTaskStatus, TeamTaskUnblockedEvent,
 };
 use crate::agent::AgentStatus;
-use codex_protocol::protocol::{EventMsg, SessionSource, SubAgentSource};
+use crate::agent::next_thread_spawn_depth;
+use crate::agent::status::is_final;
+use codex_protocol::protocol::{
+    EventMsg, ReadOnlyAccess, RolloutItem, SandboxPolicy, SessionSource, SubAgentSource,
+};
+use codex_utils_absolute_path::AbsolutePathBuf;
 
 use serde::Deserialize;
+use serde::Serialize;
 use serde_json::json;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::Instrument;
 
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::config::Constrained;
 use crate::function_tool::FunctionCallError;
-use crate::teams::inbox::{Inbox, InboxMessage};
-use crate::teams::task_list::TaskList;
-use crate::teams::team_manager::{MemberConfig, TeamManager};
+use crate::rollout::RolloutRecorder;
+use crate::teams::a2a::{A2A_KIND, A2aMemberSpec};
+use crate::teams::artifacts::ArtifactInfo;
+use crate::teams::completion::{LEADER_INBOX, watch_teammate_completion};
+use crate::teams::delegation::{restart_member, start_delegation_supervisor};
+use crate::teams::dry_run::{DryRunEstimate, DryRunPlan, PlannedMember, PlannedTask};
+use crate::teams::errors::{TeamToolError, error_team_name};
+use crate::teams::external::{
+    MCP_KIND, McpMemberSpec, deliver_to_external_member, external_status, has_mcp_tool,
+    member_status,
+};
+use crate::teams::inbox::InboxMessage;
+use crate::teams::leader_mail::forward_leader_mail;
+use crate::teams::leader_supervisor::start_leader_supervisor;
+use crate::teams::liveness::watch_teammate_liveness;
+use crate::teams::output::forward_teammate_events;
+use crate::teams::patches::{
+    PatchDecision, PatchStatus, TeamPatch, apply_patch, patch_files, working_tree_diff,
+};
+use crate::teams::plan::validate_plan;
+use crate::teams::progress::start_progress_reporter;
+use crate::teams::queue::{QUEUED_STATUS, running_teammates};
+use crate::teams::repo_relative;
+use crate::teams::roles::apply_role;
+use crate::teams::spans::{forget_teammate_span, teammate_span};
+use crate::teams::ssh::{SSH_KIND, SshMemberSpec};
+use crate::teams::summary::{SUMMARY_RECENT_MESSAGES, SubTeamSummary, blockers, team_summary};
+use crate::teams::task_sync::{start_task_sync, task_syncs};
+use crate::teams::team_manager::{MemberBudget, MemberConfig, TeamConfig, validate_name};
+use crate::teams::timeout::start_teammate_timeout;
+use crate::teams::usage::refresh_team_usage;
+use crate::teams::worktree::{
+    MergeOutcome, MergeStrategy, WORKTREE_ISOLATION, create_worktree, merge_worktree,
+    remove_worktree, teammate_branch,
+};
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
@@ -35,13 +84,66 @@ use codex_protocol::user_input::UserInput;
 
 pub struct TeamHandler;
 
+/// How often a leader refreshes its team lock while its session is alive.
+/// Must stay well below `LEADER_LOCK_STALE_AFTER`.
+const LEADER_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Mutating tools that accept an `idempotency_key`, so a retried call
+/// returns the original result instead of repeating its side effects. All
+/// of them are leader tools, which `handle` relies on before answering from
+/// the recorded results.
+const IDEMPOTENT_TOOLS: &[&str] = &[
+    "spawn_teammate",
+    "assign_task",
+    "send_team_message",
+    "broadcast_team_message",
+    "shutdown_teammate",
+    "restart_teammate",
+];
+
+/// Sender of messages the user sends a teammate from a UI.
+const USER_SENDER: &str = "user";
+
+const DEFAULT_WAIT_TIMEOUT_SECS: u64 = 60;
+const MAX_WAIT_TIMEOUT_SECS: u64 = 600;
+/// How often `wait_for_teammates` re-reads the status of teammates whose
+/// status it cannot subscribe to (external and joined members).
+const WAIT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Text artifacts larger than this are truncated in `fetch_artifact` output;
+/// the full file stays readable at the returned path.
+const MAX_ARTIFACT_CONTENT_BYTES: usize = 100 * 1024;
+
 // ── argument structs ────────────────────────────────────────────────────
 
+#[derive(Deserialize)]
+struct IdempotencyArgs {
+    team_name: String,
+    #[serde(default)]
+    idempotency_key: Option<String>,
+}
+
 #[derive(Deserialize)]
 struct CreateTeamArgs {
     name: String,
     #[serde(default)]
     description: Option<String>,
+    #[serde(default)]
+    metadata: HashMap<String, String>,
+    #[serde(default)]
+    display_mode: TeamDisplayMode,
+    #[serde(default)]
+    delegation_mode: bool,
+    /// Wake the leader with a summary when teammates, tasks or its inbox
+    /// change, instead of having it poll. Implied by `delegation_mode`.
+    #[serde(default)]
+    supervise: bool,
+    /// Hand idle teammates the next available task automatically.
+    #[serde(default)]
+    work_stealing: bool,
+    /// Only plan spawns, task assignments and shutdowns; start no agents.
+    #[serde(default)]
+    dry_run: bool,
 }
 
 #[derive(Deserialize)]
@@ -51,6 +153,51 @@ struct SpawnTeammateArgs {
     #[serde(default)]
     role: Option<String>,
     prompt: String,
+    /// Narrower sandbox than the leader's, e.g. read-only for a reviewer.
+    #[serde(default)]
+    sandbox_mode: Option<SandboxMode>,
+    /// Replaces the leader's extra writable roots; each must already be
+    /// writable by the leader. Relative paths resolve against the cwd.
+    #[serde(default)]
+    writable_roots: Option<Vec<PathBuf>>,
+    /// The only places the teammate may write, cwd included; each must
+    /// already be writable by the leader.
+    #[serde(default)]
+    writable_paths: Option<Vec<PathBuf>>,
+    /// Shut the teammate down once it has used more tokens than this.
+    #[serde(default)]
+    max_tokens: Option<i64>,
+    /// Shut the teammate down after this many turns.
+    #[serde(default)]
+    max_turns: Option<u32>,
+    /// Shut the teammate down once it has been running this long.
+    #[serde(default)]
+    timeout_minutes: Option<u64>,
+    /// Restart the teammate automatically this many times if it errors out,
+    /// overriding `teams.max_auto_restarts`.
+    #[serde(default)]
+    max_restarts: Option<u32>,
+    /// `"worktree"` to give the teammate its own git worktree and branch.
+    #[serde(default)]
+    isolation: Option<String>,
+    /// Working directory instead of the leader's, e.g. a sub-crate of a
+    /// monorepo. Relative paths resolve against the leader's cwd.
+    #[serde(default)]
+    cwd: Option<PathBuf>,
+    /// `"mcp"` to register an external agent reached through `server`'s
+    /// `tool`, `"a2a"` for a remote A2A agent at `endpoint`, or `"ssh"` for a
+    /// teammate running `codex exec` on `host`, instead of spawning a Codex
+    /// thread.
+    #[serde(default)]
+    kind: Option<String>,
+    #[serde(default)]
+    server: Option<String>,
+    #[serde(default)]
+    tool: Option<String>,
+    #[serde(default)]
+    endpoint: Option<String>,
+    #[serde(default)]
+    host: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -58,11 +205,20 @@ struct AssignTaskArgs {
     team_name: String,
     title: String,
     #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
     assigned_to: Option<String>,
     #[serde(default)]
     depends_on: Vec<String>,
 }
 
+#[derive(Deserialize)]
+struct ReassignTaskArgs {
+    team_name: String,
+    task_id: String,
+    assigned_to: String,
+}
+
 #[derive(Deserialize)]
 struct SendTeamMessageArgs {
     team_name: String,
@@ -76,589 +232,4292 @@ struct BroadcastTeamMessageArgs {
     content: String,
 }
 
+/// When `wait_for_teammates` returns.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum WaitCondition {
+    /// At least one watched teammate has finished its turn.
+    AnyCompleted,
+    /// Every watched teammate has finished its turn.
+    #[default]
+    AllCompleted,
+    /// Every watched teammate has finished and none holds an unfinished task.
+    AllIdle,
+}
+
+#[derive(Deserialize)]
+struct WaitForTeammatesArgs {
+    team_name: String,
+    /// Teammates to watch; empty means the whole team.
+    #[serde(default)]
+    names: Vec<String>,
+    #[serde(default)]
+    timeout_secs: Option<u64>,
+    #[serde(default)]
+    condition: WaitCondition,
+}
+
 #[derive(Deserialize)]
 struct TeamNameArgs {
     team_name: String,
 }
 
 #[derive(Deserialize)]
-struct ShutdownTeammateArgs {
+struct AppendScratchpadArgs {
     team_name: String,
-    name: String,
+    content: String,
 }
 
 #[derive(Deserialize)]
-struct CompleteTaskArgs {
+struct ReplaceSectionArgs {
     team_name: String,
-    task_id: String,
+    heading: String,
+    content: String,
 }
 
-// ── helpers ─────────────────────────────────────────────────────────────
+#[derive(Deserialize)]
+struct ReserveFilesArgs {
+    team_name: String,
+    paths: Vec<PathBuf>,
+}
 
-fn ok_text(msg: impl Into<String>) -> Result<ToolOutput, FunctionCallError> {
-    Ok(ToolOutput::Function {
-        body: FunctionCallOutputBody::Text(msg.into()),
-        success: Some(true),
-    })
+#[derive(Deserialize)]
+struct ReleaseFilesArgs {
+    team_name: String,
+    /// Paths to release; all of the caller's reservations if omitted.
+    #[serde(default)]
+    paths: Option<Vec<PathBuf>>,
 }
 
-fn err_text(msg: impl Into<String>) -> Result<ToolOutput, FunctionCallError> {
-    Err(FunctionCallError::RespondToModel(msg.into()))
+#[derive(Deserialize)]
+struct SubmitPatchArgs {
+    team_name: String,
+    description: String,
+    /// Unified diff to submit; defaults to the changes in the caller's cwd.
+    #[serde(default)]
+    patch: Option<String>,
 }
 
-fn extract_args(payload: ToolPayload) -> Result<String, FunctionCallError> {
-    match payload {
-        ToolPayload::Function { arguments } => Ok(arguments),
-        _ => Err(FunctionCallError::RespondToModel(
-            "team handler received unsupported payload".to_string(),
-        )),
-    }
+#[derive(Deserialize)]
+struct EscalateToUserArgs {
+    team_name: String,
+    question: String,
+    #[serde(default)]
+    options: Vec<String>,
 }
 
-/// Default root for teams data: `~/.codex/teams`
-fn default_teams_root() -> std::path::PathBuf {
-    dirs::home_dir()
-        .unwrap_or_else(|| std::path::PathBuf::from("."))
-        .join(".codex")
-        .join("teams")
+#[derive(Deserialize)]
+struct StoreArtifactArgs {
+    team_name: String,
+    name: String,
+    /// Inline content to store.
+    #[serde(default)]
+    content: Option<String>,
+    /// File to copy into the store, relative to the caller's cwd.
+    #[serde(default)]
+    path: Option<PathBuf>,
 }
 
-/// Default root for tasks data: `~/.codex/tasks`
-fn default_tasks_root() -> std::path::PathBuf {
-    dirs::home_dir()
-        .unwrap_or_else(|| std::path::PathBuf::from("."))
-        .join(".codex")
-        .join("tasks")
+#[derive(Deserialize)]
+struct FetchArtifactArgs {
+    team_name: String,
+    name: String,
 }
 
-/// Build a child config for a teammate agent.
-fn build_teammate_config(
-    turn: &TurnContext,
-) -> Result<crate::config::Config, FunctionCallError> {
-    let base_config = turn.config.clone();
-    let mut config = (*base_config).clone();
-    config.model = Some(turn.model_info.slug.clone());
-    config.model_provider = turn.provider.clone();
-    config.model_reasoning_effort = turn.reasoning_effort;
-    config.model_reasoning_summary = turn.reasoning_summary;
-    config.developer_instructions = turn.developer_instructions.clone();
-    config.compact_prompt = turn.compact_prompt.clone();
-    config.permissions.shell_environment_policy = turn.shell_environment_policy.clone();
-    config.codex_linux_sandbox_exe = turn.codex_linux_sandbox_exe.clone();
-    config.cwd = turn.cwd.clone();
-    config
-        .permissions
-        .sandbox_policy
-        .set(turn.sandbox_policy.clone())
-        .map_err(|err| {
-            FunctionCallError::RespondToModel(format!("sandbox_policy is invalid: {err}"))
-        })?;
-    // Teammates should never prompt for approval.
-    config.permissions.approval_policy = Constrained::allow_only(AskForApproval::Never);
-    Ok(config)
+#[derive(Deserialize)]
+struct RenameTeamArgs {
+    team_name: String,
+    new_name: String,
 }
 
-// ── handler ─────────────────────────────────────────────────────────────
+#[derive(Deserialize)]
+struct HandoffLeadershipArgs {
+    team_name: String,
+    /// Name of the teammate that becomes the new leader.
+    new_leader: String,
+}
 
-#[async_trait]
-impl ToolHandler for TeamHandler {
-    fn kind(&self) -> ToolKind {
-        ToolKind::Function
-    }
+#[derive(Deserialize)]
+struct GetTeammateArgs {
+    team_name: String,
+    name: String,
+}
 
-    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
-        let ToolInvocation {
-            session,
-            turn,
-            tool_name,
-            payload,
-            call_id,
-            ..
-        } = invocation;
+#[derive(Deserialize)]
+struct GetTeammateOutputArgs {
+    team_name: String,
+    name: String,
+    /// How many of the teammate's most recent turns to include.
+    #[serde(default)]
+    turns: Option<usize>,
+}
 
-        let arguments = extract_args(payload)?;
+/// One turn of a teammate's conversation, rebuilt from its rollout.
+#[derive(Debug, PartialEq, Serialize)]
+struct TeammateTurn {
+    input: String,
+    messages: Vec<String>,
+}
 
-        match tool_name.as_str() {
-            // ── Leader tools ─────────────────────────────────────────
-            "create_team" => {
-                handle_create_team(session, turn, call_id, arguments).await
-            }
-            "spawn_teammate" => {
-                handle_spawn_teammate(session, turn, call_id, arguments).await
-            }
-            "assign_task" => handle_assign_task(session, turn, call_id, arguments).await,
-            "send_team_message" => handle_send_team_message(arguments).await,
-            "broadcast_team_message" => handle_broadcast_team_message(arguments).await,
-            "wait_for_teammates" => handle_wait_for_teammates(session, arguments).await,
-            "get_task_status" => handle_get_task_status(arguments).await,
-            "shutdown_teammate" => {
-                handle_shutdown_teammate(session, turn, call_id, arguments).await
-            }
-            "cleanup_team" => {
-                handle_cleanup_team(session, turn, call_id, arguments).await
-            }
+#[derive(Deserialize)]
+struct ShutdownTeammateArgs {
+    team_name: String,
+    name: String,
+}
 
-            // ── Teammate tools ───────────────────────────────────────
-            "accept_task" => handle_accept_task(arguments).await,
-            "complete_task" => handle_complete_task(arguments).await,
-            "get_tasks" => handle_get_tasks(arguments).await,
-            "request_shutdown" => handle_request_shutdown(arguments).await,
+#[derive(Deserialize)]
+struct RestartTeammateArgs {
+    team_name: String,
+    name: String,
+}
 
-            other => err_text(format!("unknown team tool: {other}")),
-        }
-    }
+#[derive(Deserialize)]
+struct MergeTeammateWorkArgs {
+    team_name: String,
+    name: String,
+    #[serde(default)]
+    strategy: MergeStrategy,
+    /// Task to record the merge result on; defaults to the teammate's tasks.
+    #[serde(default)]
+    task_id: Option<String>,
 }
 
-// ═══════════════════════════════════════════════════════════════════════
-// Leader tool implementations
-// ═══════════════════════════════════════════════════════════════════════
+#[derive(Deserialize)]
+struct ReviewPatchArgs {
+    team_name: String,
+    /// Patch to show or review; lists every patch if omitted.
+    #[serde(default)]
+    patch_id: Option<String>,
+    /// Verdict on the patch; without one the patch is only shown.
+    #[serde(default)]
+    decision: Option<PatchDecision>,
+    #[serde(default)]
+    comment: Option<String>,
+}
 
-async fn handle_create_team(
-    session: Arc<Session>,
-    turn: Arc<TurnContext>,
-    _call_id: String,
-    arguments: String,
-) -> Result<ToolOutput, FunctionCallError> {
-    let args: CreateTeamArgs = parse_arguments(&arguments)?;
-    let mgr = TeamManager::new(default_teams_root());
-    let leader_tid = session.conversation_id;
-    match mgr.create_team(&args.name, leader_tid).await {
-        Ok(_config) => {
-            // Initialize task list for this team.
-            let tl = TaskList::new(default_tasks_root());
-            let _ = tl.init(&args.name).await;
+#[derive(Deserialize)]
+struct ApplyPatchFromTeammateArgs {
+    team_name: String,
+    patch_id: String,
+}
 
-            // Emit TeamCreated event.
-            session
-                .send_event(
-                    &turn,
-                    EventMsg::TeamCreated(TeamCreatedEvent {
-                        team_name: args.name.clone(),
-                        leader_thread_id: leader_tid,
-                    }),
-                )
-                .await;
+#[derive(Deserialize)]
+struct CompleteTaskArgs {
+    team_name: String,
+    task_id: String,
+}
 
-            ok_text(
-                json!({
-                    "status": "created",
-                    "team_name": args.name,
-                    "leader_thread_id": leader_tid.to_string(),
-                    "description": args.description,
-                })
-                .to_string(),
-            )
-        }
-        Err(e) => err_text(format!("failed to create team: {e}")),
-    }
+// ── result structs ──────────────────────────────────────────────────────
+//
+// Tool outputs are the JSON serialization of these structs. Models and
+// downstream tools parse them, so fields are only ever added: missing
+// values serialize as `null` rather than being left out, enums use their
+// serde names, and tasks and patches keep their on-disk form. The snapshot
+// test below pins the format.
+
+/// What a mutating tool did, reported as its `status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ToolStatus {
+    Created,
+    Spawned,
+    Queued,
+    Reassigned,
+    Sent,
+    Answered,
+    Broadcast,
+    Shutdown,
+    Restarted,
+    Applied,
+    CleanedUp,
+    Renamed,
+    HandedOff,
+    Completed,
+    ShutdownRequested,
+    Submitted,
+    Escalated,
+    Appended,
+    Replaced,
+    Reserved,
+    Released,
+    Stored,
+    /// Recorded in a dry-run team's plan instead of being carried out.
+    Planned,
 }
 
-async fn handle_spawn_teammate(
-    session: Arc<Session>,
-    turn: Arc<TurnContext>,
-    _call_id: String,
-    arguments: String,
-) -> Result<ToolOutput, FunctionCallError> {
-    let args: SpawnTeammateArgs = parse_arguments(&arguments)?;
-    let mgr = TeamManager::new(default_teams_root());
+/// Result of a tool that only reports its status.
+#[derive(Debug, Serialize)]
+struct StatusResult {
+    status: ToolStatus,
+}
 
-    // Build config for the teammate agent.
-    let config = build_teammate_config(&turn)?;
+#[derive(Debug, Serialize)]
+struct CreateTeamResult {
+    status: ToolStatus,
+    team_name: String,
+    leader_thread_id: ThreadId,
+    description: Option<String>,
+    metadata: BTreeMap<String, String>,
+    display_mode: TeamDisplayMode,
+    delegation_mode: bool,
+    work_stealing: bool,
+    dry_run: bool,
+    parent_team: Option<String>,
+}
 
-    // Prepare the prompt as UserInput.
-    let input_items = vec![UserInput::Text {
-        text: args.prompt.clone(),
-        text_elements: Vec::new(),
-    }];
+/// Result of `spawn_teammate`, `assign_task` or `shutdown_teammate` in a
+/// dry-run team: the whole plan so far.
+#[derive(Debug, Serialize)]
+struct DryRunResult {
+    status: ToolStatus,
+    team_name: String,
+    plan: DryRunPlan,
+    estimate: DryRunEstimate,
+}
 
-    // Spawn a real agent thread via AgentControl.
-    let session_source = SessionSource::SubAgent(SubAgentSource::ThreadSpawn {
-        parent_thread_id: session.conversation_id,
-        depth: 1,
-    });
+#[derive(Debug, Serialize)]
+struct SpawnTeammateResult {
+    /// `spawned`, or `queued` when the teammate waits for a free slot.
+    status: ToolStatus,
+    teammate: String,
+    thread_id: ThreadId,
+    team_name: String,
+    branch: Option<String>,
+    worktree: Option<String>,
+    cwd: Option<String>,
+}
 
-    let thread_id = session
-        .services
-        .agent_control
-        .spawn_agent(config, input_items, Some(session_source))
-        .await
-        .map_err(|e| FunctionCallError::RespondToModel(format!("failed to spawn teammate agent: {e}")))?;
+#[derive(Debug, Serialize)]
+struct AssignTaskResult {
+    status: ToolStatus,
+    task_id: String,
+    title: String,
+    assigned_to: Option<String>,
+}
 
-    // Persist member config to disk.
-    let member = MemberConfig {
-        name: args.name.clone(),
-        thread_id,
-        role: args.role.clone(),
-        status: "running".to_string(),
-        prompt: Some(args.prompt.clone()),
-    };
-    if let Err(e) = mgr.add_member(&args.team_name, member).await {
-        // Agent was spawned but config persistence failed — still report success.
-        tracing::warn!("spawned teammate {}, but failed to persist config: {e}", args.name);
-    }
+#[derive(Debug, Serialize)]
+struct ReassignTaskResult {
+    status: ToolStatus,
+    task_id: String,
+    assigned_to: String,
+    previous_assignee: Option<String>,
+}
 
-    // Emit TeamMemberAdded event.
-    session
-        .send_event(
-            &turn,
-            EventMsg::TeamMemberAdded(TeamMemberEvent {
-                team_name: args.team_name.clone(),
-                member: TeamMemberInfo {
-                    name: args.name.clone(),
+/// Result of delivering a message or an answer to `to`.
+#[derive(Debug, Serialize)]
+struct MessageResult {
+    status: ToolStatus,
+    to: String,
+}
+
+/// A teammate and its current status.
+#[derive(Debug, Serialize)]
+struct TeammateStatus {
+    name: String,
+    thread_id: ThreadId,
+    role: Option<String>,
+    status: AgentStatus,
+}
+
+#[derive(Debug, Serialize)]
+struct WaitForTeammatesResult {
+    condition: WaitCondition,
+    condition_met: bool,
+    timed_out: bool,
+    members: Vec<TeammateStatus>,
+}
+
+#[derive(Debug, Serialize)]
+struct TasksResult {
+    tasks: Vec<TeamTaskInfo>,
+}
+
+#[derive(Debug, Serialize)]
+struct TeamSummaryResult {
+    summary: String,
+    completed_tasks: usize,
+    total_tasks: usize,
+    blockers: Vec<String>,
+    sub_teams: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct TeamUsageResult<'a> {
+    team_name: String,
+    /// Total tokens per participant, keyed by name.
+    participants: BTreeMap<&'a str, i64>,
+    input_tokens: i64,
+    output_tokens: i64,
+    total_tokens: i64,
+    max_team_tokens: Option<i64>,
+    remaining_tokens: Option<i64>,
+}
+
+/// A task as listed under the teammate it is assigned to.
+#[derive(Debug, Serialize)]
+struct TeammateTask {
+    id: String,
+    title: String,
+    status: TeamTaskStatus,
+}
+
+#[derive(Debug, Serialize)]
+struct TeammateResult {
+    name: String,
+    thread_id: ThreadId,
+    role: Option<String>,
+    status: AgentStatus,
+    tasks: Vec<TeammateTask>,
+    unread_messages: usize,
+    last_activity_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct TeammateOutputResult {
+    name: String,
+    status: AgentStatus,
+    last_message: Option<String>,
+    turns: Vec<TeammateTurn>,
+}
+
+#[derive(Debug, Serialize)]
+struct ShutdownTeammateResult {
+    status: ToolStatus,
+    teammate: String,
+    thread_id: ThreadId,
+}
+
+#[derive(Debug, Serialize)]
+struct RestartTeammateResult {
+    status: ToolStatus,
+    teammate: String,
+    previous_thread_id: ThreadId,
+    thread_id: ThreadId,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum MergeTeammateWorkResult {
+    Merged {
+        branch: String,
+        commits: usize,
+        head: String,
+    },
+    UpToDate {
+        branch: String,
+    },
+    Conflicts {
+        branch: String,
+        conflicts: Vec<String>,
+    },
+}
+
+#[derive(Debug, Serialize)]
+struct PatchListResult {
+    patches: Vec<TeamPatch>,
+}
+
+/// A patch shown without a decision, with its diff.
+#[derive(Debug, Serialize)]
+struct PatchPreviewResult<'a> {
+    patch: TeamPatch,
+    diff: &'a str,
+    truncated: bool,
+    path: PathBuf,
+    applies_cleanly: bool,
+    apply_error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct PatchReviewResult {
+    status: PatchStatus,
+    patch_id: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ApplyPatchResult {
+    status: ToolStatus,
+    patch_id: String,
+    files: Vec<String>,
+    /// `Co-authored-by` trailers for the commit that lands the patch.
+    commit_trailers: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct CleanupTeamResult {
+    status: ToolStatus,
+    team_name: String,
+}
+
+/// A team as listed by `list_teams`.
+#[derive(Debug, Serialize)]
+struct TeamListing {
+    name: String,
+    description: Option<String>,
+    metadata: BTreeMap<String, String>,
+    created_at: String,
+    leader_thread_id: ThreadId,
+    member_count: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct ListTeamsResult {
+    teams: Vec<TeamListing>,
+}
+
+#[derive(Debug, Serialize)]
+struct RenameTeamResult {
+    status: ToolStatus,
+    old_name: String,
+    team_name: String,
+}
+
+#[derive(Debug, Serialize)]
+struct HandoffLeadershipResult {
+    status: ToolStatus,
+    team_name: String,
+    new_leader: String,
+    leader_thread_id: ThreadId,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum AcceptTaskResult {
+    Accepted {
+        task_id: String,
+        title: String,
+        description: Option<String>,
+    },
+    NoTasksAvailable,
+}
+
+#[derive(Debug, Serialize)]
+struct CompleteTaskResult {
+    status: ToolStatus,
+    task_id: String,
+    unblocked_task_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct SubmitPatchResult {
+    status: ToolStatus,
+    patch_id: String,
+    files: Vec<String>,
+    path: PathBuf,
+}
+
+#[derive(Debug, Serialize)]
+struct EscalateToUserResult {
+    status: ToolStatus,
+    escalation_id: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ScratchpadResult {
+    content: String,
+}
+
+/// Result of `append_scratchpad` and `replace_section`: the updated
+/// scratchpad.
+#[derive(Debug, Serialize)]
+struct ScratchpadUpdateResult {
+    status: ToolStatus,
+    content: String,
+}
+
+/// Result of `reserve_files` and `release_files`.
+#[derive(Debug, Serialize)]
+struct FilesResult {
+    status: ToolStatus,
+    paths: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct StoreArtifactResult {
+    status: ToolStatus,
+    artifact: ArtifactInfo,
+}
+
+#[derive(Debug, Serialize)]
+struct FetchArtifactResult<'a> {
+    artifact: ArtifactInfo,
+    /// Binary artifacts have no `content`; read them at the artifact's path.
+    binary: bool,
+    content: Option<&'a str>,
+    truncated: bool,
+}
+
+// ── helpers ─────────────────────────────────────────────────────────────
+
+fn ok_text(msg: impl Into<String>) -> Result<ToolOutput, TeamToolError> {
+    Ok(ToolOutput::Function {
+        body: FunctionCallOutputBody::Text(msg.into()),
+        success: Some(true),
+    })
+}
+
+/// Output of a tool that produced `result`, one of the result structs.
+fn ok_json(result: &impl Serialize) -> Result<ToolOutput, TeamToolError> {
+    match serde_json::to_string(result) {
+        Ok(json) => ok_text(json),
+        Err(e) => err_text(
+            TeamErrorCode::Other,
+            format!("failed to serialize the result: {e}"),
+        ),
+    }
+}
+
+fn err_text(code: TeamErrorCode, msg: impl Into<String>) -> Result<ToolOutput, TeamToolError> {
+    Err(TeamToolError::new(code, msg))
+}
+
+/// The tool's `arguments`, parsed as the handlers' args struct `T`.
+fn parse_args<T: for<'de> Deserialize<'de>>(arguments: &str) -> Result<T, TeamToolError> {
+    parse_arguments(arguments)
+        .map_err(|e| TeamToolError::new(TeamErrorCode::InvalidArguments, e.to_string()))
+}
+
+fn extract_args(payload: ToolPayload) -> Result<String, FunctionCallError> {
+    match payload {
+        ToolPayload::Function { arguments } => Ok(arguments),
+        _ => Err(FunctionCallError::RespondToModel(
+            "team handler received unsupported payload".to_string(),
+        )),
+    }
+}
+
+/// Ensure the calling session holds the team's leader lock, refreshing its
+/// heartbeat. Fails when another session is driving the team.
+async fn ensure_leader(session: &Session, team_name: &str) -> Result<(), TeamToolError> {
+    let mgr = session.services.teams.manager();
+    if !mgr.team_exists(team_name).await {
+        return Err(TeamToolError::new(
+            TeamErrorCode::TeamNotFound,
+            format!("team '{team_name}' not found"),
+        ));
+    }
+    mgr.acquire_leader_lock(team_name, session.conversation_id)
+        .await
+        .map_err(|e| {
+            if matches!(
+                e.kind(),
+                std::io::ErrorKind::ResourceBusy | std::io::ErrorKind::PermissionDenied
+            ) {
+                TeamToolError::new(TeamErrorCode::NotLeader, e.to_string())
+            } else {
+                TeamToolError::storage(format!("failed to lock team '{team_name}'"), &e)
+            }
+        })
+}
+
+/// Resolve the member name the calling session is registered under in
+/// `team_name`. Teammate tools act on behalf of this name.
+async fn calling_member_name(session: &Session, team_name: &str) -> Result<String, TeamToolError> {
+    match session
+        .services
+        .teams
+        .manager()
+        .member_by_thread(team_name, session.conversation_id)
+        .await
+    {
+        Ok(Some(member)) => Ok(member.name),
+        Ok(None) => Err(TeamToolError::new(
+            TeamErrorCode::MemberNotFound,
+            format!("this agent is not a registered member of team '{team_name}'"),
+        )),
+        Err(e) => Err(TeamToolError::storage(
+            format!("failed to load team '{team_name}'"),
+            &e,
+        )),
+    }
+}
+
+/// Name the calling session writes under in `team_name`: `"leader"` for the
+/// team's leader, otherwise its member name.
+async fn calling_participant_name(
+    session: &Session,
+    team_name: &str,
+) -> Result<String, TeamToolError> {
+    let config = session
+        .services
+        .teams
+        .load_config(team_name)
+        .await
+        .map_err(|e| TeamToolError::storage(format!("failed to load team '{team_name}'"), &e))?;
+    if config.leader_thread_id == session.conversation_id {
+        return Ok(LEADER_INBOX.to_string());
+    }
+    calling_member_name(session, team_name).await
+}
+
+/// Keep `leader`'s lock on the team fresh until the session goes away, the
+/// team is cleaned up or renamed, or leadership moves elsewhere. `leader` is
+/// either this session or, after a handoff, one of its teammates.
+fn spawn_leader_heartbeat(session: &Arc<Session>, team_name: String, leader: ThreadId) {
+    let weak_session = Arc::downgrade(session);
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(LEADER_HEARTBEAT_INTERVAL).await;
+            let Some(session) = weak_session.upgrade() else {
+                return;
+            };
+            let teams = &session.services.teams;
+            let Ok(config) = teams.load_config(&team_name).await else {
+                return;
+            };
+            if config.leader_thread_id != leader {
+                return;
+            }
+            if leader != session.conversation_id {
+                let status = session.services.agent_control.get_status(leader).await;
+                if matches!(status, AgentStatus::NotFound | AgentStatus::Shutdown) {
+                    return;
+                }
+            }
+            if let Err(e) = teams
+                .manager()
+                .acquire_leader_lock(&team_name, leader)
+                .await
+            {
+                tracing::warn!("stopping leader heartbeat for team {team_name}: {e}");
+                return;
+            }
+        }
+    });
+}
+
+/// Nesting level of a team led by the turn's session: 1 for a top-level
+/// session, one more for each teammate hop below it.
+fn team_level(turn: &TurnContext) -> usize {
+    usize::try_from(next_thread_spawn_depth(&turn.session_source)).unwrap_or(usize::MAX)
+}
+
+/// Build a child config for a teammate agent.
+fn build_teammate_config(turn: &TurnContext) -> Result<crate::config::Config, TeamToolError> {
+    let base_config = turn.config.clone();
+    let mut config = (*base_config).clone();
+    config.model = Some(turn.model_info.slug.clone());
+    config.model_provider = turn.provider.clone();
+    config.model_reasoning_effort = turn.reasoning_effort;
+    config.model_reasoning_summary = turn.reasoning_summary;
+    config.developer_instructions = turn.developer_instructions.clone();
+    config.compact_prompt = turn.compact_prompt.clone();
+    config.permissions.shell_environment_policy = turn.shell_environment_policy.clone();
+    config.codex_linux_sandbox_exe = turn.codex_linux_sandbox_exe.clone();
+    config.cwd = turn.cwd.clone();
+    config
+        .permissions
+        .sandbox_policy
+        .set(teammate_leader_sandbox(
+            &turn.sandbox_policy,
+            turn.approval_policy,
+        ))
+        .map_err(|err| TeamToolError::other(format!("sandbox_policy is invalid: {err}")))?;
+    // Teammates should never prompt for approval.
+    config.permissions.approval_policy = Constrained::allow_only(AskForApproval::Never);
+    Ok(config)
+}
+
+/// The sandbox teammates of a leader with `sandbox` and `approval` inherit.
+/// Teammates never ask for approval, so they get no more than the leader is
+/// allowed without asking: under `untrusted`, only reading.
+fn teammate_leader_sandbox(sandbox: &SandboxPolicy, approval: AskForApproval) -> SandboxPolicy {
+    if approval == AskForApproval::UnlessTrusted {
+        read_only_sandbox(sandbox)
+    } else {
+        sandbox.clone()
+    }
+}
+
+/// A read-only sandbox that reads what `leader` does.
+fn read_only_sandbox(leader: &SandboxPolicy) -> SandboxPolicy {
+    let access = match leader {
+        SandboxPolicy::ReadOnly { access } => access.clone(),
+        SandboxPolicy::WorkspaceWrite {
+            read_only_access, ..
+        } => read_only_access.clone(),
+        SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. } => {
+            ReadOnlyAccess::FullAccess
+        }
+    };
+    SandboxPolicy::ReadOnly { access }
+}
+
+/// The sandbox a teammate asked for, or `None` to inherit the leader's.
+/// Requests that would grant more than `leader` allows are refused.
+fn teammate_sandbox_policy(
+    leader: &SandboxPolicy,
+    cwd: &Path,
+    sandbox_mode: Option<SandboxMode>,
+    writable_roots: Option<Vec<PathBuf>>,
+) -> Result<Option<SandboxPolicy>, String> {
+    let mode = match (sandbox_mode, &writable_roots) {
+        (None, None) => return Ok(None),
+        (Some(mode), _) => mode,
+        (None, Some(_)) => SandboxMode::WorkspaceWrite,
+    };
+    match mode {
+        SandboxMode::ReadOnly => {
+            if writable_roots.is_some() {
+                return Err(
+                    "writable_roots cannot be combined with a read-only sandbox".to_string()
+                );
+            }
+            Ok(Some(read_only_sandbox(leader)))
+        }
+        SandboxMode::WorkspaceWrite => {
+            let mut policy = match leader {
+                SandboxPolicy::ReadOnly { .. } => {
+                    return Err(
+                        "the leader's sandbox is read-only; a teammate cannot be given write access"
+                            .to_string(),
+                    );
+                }
+                SandboxPolicy::WorkspaceWrite { .. } => leader.clone(),
+                SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. } => {
+                    SandboxPolicy::new_workspace_write_policy()
+                }
+            };
+            if let Some(requested) = writable_roots
+                && let SandboxPolicy::WorkspaceWrite {
+                    writable_roots: roots,
+                    ..
+                } = &mut policy
+            {
+                let leader_roots = leader.get_writable_roots_with_cwd(cwd);
+                let mut resolved = Vec::with_capacity(requested.len());
+                for path in requested {
+                    let root = AbsolutePathBuf::resolve_path_against_base(&path, cwd)
+                        .map_err(|e| format!("invalid writable root {}: {e}", path.display()))?;
+                    if !leader.has_full_disk_write_access()
+                        && !leader_roots
+                            .iter()
+                            .any(|leader_root| leader_root.is_path_writable(root.as_path()))
+                    {
+                        return Err(format!(
+                            "writable root {} is not writable under the leader's sandbox",
+                            root.display()
+                        ));
+                    }
+                    resolved.push(root);
+                }
+                *roots = resolved;
+            }
+            Ok(Some(policy))
+        }
+        SandboxMode::DangerFullAccess => match leader {
+            SandboxPolicy::DangerFullAccess => Ok(Some(SandboxPolicy::DangerFullAccess)),
+            _ => Err("a teammate's sandbox cannot be broader than the leader's".to_string()),
+        },
+    }
+}
+
+/// The working directory a teammate asked for. It must be an existing
+/// directory inside the leader's cwd or one of the leader's writable roots.
+fn teammate_cwd(leader: &SandboxPolicy, leader_cwd: &Path, cwd: &Path) -> Result<PathBuf, String> {
+    let cwd = AbsolutePathBuf::resolve_path_against_base(cwd, leader_cwd)
+        .map_err(|e| format!("invalid cwd {}: {e}", cwd.display()))?;
+    if !cwd.as_path().is_dir() {
+        return Err(format!("cwd {} is not a directory", cwd.display()));
+    }
+    let allowed = leader.has_full_disk_write_access()
+        || cwd.as_path().starts_with(leader_cwd)
+        || leader
+            .get_writable_roots_with_cwd(leader_cwd)
+            .iter()
+            .any(|root| cwd.as_path().starts_with(root.root.as_path()));
+    if !allowed {
+        return Err(format!(
+            "cwd {} is outside the leader's cwd and writable roots",
+            cwd.display()
+        ));
+    }
+    Ok(cwd.into_path_buf())
+}
+
+/// Working directory of a teammate restricted to `writable_paths`. The
+/// sandbox always lets an agent write its cwd, so the cwd must lie inside
+/// one of them: the requested or leader's cwd if it does, otherwise the first
+/// writable directory. `None` keeps the leader's cwd.
+fn writable_paths_cwd(
+    leader_cwd: &Path,
+    cwd: Option<PathBuf>,
+    writable_paths: &[AbsolutePathBuf],
+) -> Result<Option<PathBuf>, String> {
+    let inside = |dir: &Path| writable_paths.iter().any(|path| dir.starts_with(path));
+    match cwd {
+        Some(cwd) if inside(&cwd) => Ok(Some(cwd)),
+        Some(cwd) => Err(format!(
+            "cwd {} must be inside one of writable_paths",
+            cwd.display()
+        )),
+        None if inside(leader_cwd) => Ok(None),
+        None => writable_paths
+            .iter()
+            .find(|path| path.as_path().is_dir())
+            .map(|path| Some(path.to_path_buf()))
+            .ok_or_else(|| {
+                "writable_paths must include a directory for the teammate to work in".to_string()
+            }),
+    }
+}
+
+/// The team and scoped key under which a call's result is recorded, if the
+/// tool supports idempotency and the call supplied a key.
+fn idempotency_scope(tool_name: &str, arguments: &str) -> Option<(String, String)> {
+    if !IDEMPOTENT_TOOLS.contains(&tool_name) {
+        return None;
+    }
+    let args: IdempotencyArgs = serde_json::from_str(arguments).ok()?;
+    let key = args.idempotency_key?;
+    Some((args.team_name, format!("{tool_name}:{key}")))
+}
+
+// ── handler ─────────────────────────────────────────────────────────────
+
+#[async_trait]
+impl ToolHandler for TeamHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            tool_name,
+            payload,
+            call_id,
+            ..
+        } = invocation;
+
+        let arguments = extract_args(payload)?;
+
+        let idempotency = idempotency_scope(&tool_name, &arguments);
+        let team_name = error_team_name(&tool_name, &arguments);
+        let caller = Arc::clone(&session);
+        let caller_turn = Arc::clone(&turn);
+        let mgr = caller.services.teams.manager();
+        // Every idempotent tool is a leader tool; anyone else falls through
+        // to the handler, which refuses them.
+        if let Some((team_name, key)) = &idempotency
+            && ensure_leader(&caller, team_name).await.is_ok()
+            && let Ok(Some(result)) = mgr.idempotent_result(team_name, key).await
+        {
+            return Ok(ToolOutput::Function {
+                body: FunctionCallOutputBody::Text(result),
+                success: Some(true),
+            });
+        }
+
+        // Kept for the team's trace; the handlers take the arguments.
+        let traced_arguments = team_name.as_ref().map(|_| arguments.clone());
+        let output = match tool_name.as_str() {
+            // ── Leader tools ─────────────────────────────────────────
+            "create_team" => {
+                handle_create_team(session, turn, call_id, arguments).await
+            }
+            "spawn_teammate" => {
+                handle_spawn_teammate(session, turn, call_id, arguments).await
+            }
+            "assign_task" => handle_assign_task(session, turn, call_id, arguments).await,
+            "reassign_task" => handle_reassign_task(session, turn, arguments).await,
+            "send_team_message" => handle_send_team_message(session, turn, arguments).await,
+            "broadcast_team_message" => {
+                handle_broadcast_team_message(session, turn, arguments).await
+            }
+            "wait_for_teammates" => handle_wait_for_teammates(session, arguments).await,
+            "get_task_status" => handle_get_task_status(session, arguments).await,
+            "get_teammate" => handle_get_teammate(session, arguments).await,
+            "get_teammate_output" => handle_get_teammate_output(session, arguments).await,
+            "team_summary" => handle_team_summary(session, arguments).await,
+            "get_team_usage" => handle_get_team_usage(session, turn, arguments).await,
+            "shutdown_teammate" => {
+                handle_shutdown_teammate(session, turn, call_id, arguments).await
+            }
+            "restart_teammate" => handle_restart_teammate(session, turn, arguments).await,
+            "merge_teammate_work" => handle_merge_teammate_work(session, turn, arguments).await,
+            "review_patch" => handle_review_patch(session, turn, arguments).await,
+            "apply_patch_from_teammate" => {
+                handle_apply_patch_from_teammate(session, turn, arguments).await
+            }
+            "cleanup_team" => {
+                handle_cleanup_team(session, turn, call_id, arguments).await
+            }
+            "list_teams" => handle_list_teams(session).await,
+            "rename_team" => handle_rename_team(session, turn, arguments).await,
+            "handoff_leadership" => handle_handoff_leadership(session, arguments).await,
+
+            // ── Teammate tools ───────────────────────────────────────
+            "accept_task" => handle_accept_task(session, turn, arguments).await,
+            "complete_task" => handle_complete_task(session, turn, arguments).await,
+            "get_tasks" => handle_get_tasks(session, arguments).await,
+            "request_shutdown" => handle_request_shutdown(session, arguments).await,
+            "submit_patch" => handle_submit_patch(session, turn, arguments).await,
+            "escalate_to_user" => handle_escalate_to_user(session, arguments).await,
+
+            // ── Shared tools ─────────────────────────────────────────
+            "read_scratchpad" => handle_read_scratchpad(session, arguments).await,
+            "append_scratchpad" => handle_append_scratchpad(session, arguments).await,
+            "replace_section" => handle_replace_section(session, arguments).await,
+            "reserve_files" => handle_reserve_files(session, turn, arguments).await,
+            "release_files" => handle_release_files(session, turn, arguments).await,
+            "store_artifact" => handle_store_artifact(session, turn, arguments).await,
+            "fetch_artifact" => handle_fetch_artifact(session, arguments).await,
+
+            other => err_text(TeamErrorCode::Other, format!("unknown team tool: {other}")),
+        };
+
+        if let Some((team_name, key)) = &idempotency
+            && let Ok(ToolOutput::Function {
+                body: FunctionCallOutputBody::Text(result),
+                ..
+            }) = &output
+            && let Err(e) = mgr.record_idempotent_result(team_name, key, result).await
+        {
+            tracing::warn!("failed to record idempotency key {key}: {e}");
+        }
+        if let (Some(team_name), Some(arguments)) = (&team_name, &traced_arguments) {
+            trace_tool_call(&caller, team_name, &tool_name, arguments, &output).await;
+        }
+        if let Err(err) = &output {
+            caller
+                .send_event(
+                    &caller_turn,
+                    EventMsg::TeamError(TeamErrorEvent {
+                        tool_name,
+                        team_name,
+                        code: err.code,
+                        message: err.message.clone(),
+                    }),
+                )
+                .await;
+        }
+        output.map_err(FunctionCallError::from)
+    }
+}
+
+/// Append a finished tool call to its team's event log. A renamed team's
+/// log has moved with it, and a cleaned-up team has none.
+async fn trace_tool_call(
+    session: &Session,
+    team_name: &str,
+    tool_name: &str,
+    arguments: &str,
+    output: &Result<ToolOutput, TeamToolError>,
+) {
+    let (output, success) = match output {
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(result),
+            ..
+        }) => (result.clone(), true),
+        Ok(_) => (String::new(), true),
+        Err(e) => (e.to_string(), false),
+    };
+    let renamed = (tool_name == "rename_team" && success)
+        .then(|| serde_json::from_str::<RenameTeamArgs>(arguments).ok())
+        .flatten()
+        .map(|args| args.new_name);
+    let team_name = renamed.as_deref().unwrap_or(team_name);
+    let log = session.services.teams.event_log(team_name);
+    if let Err(e) = log
+        .append_tool_call(
+            session.conversation_id,
+            tool_name,
+            arguments,
+            &output,
+            success,
+        )
+        .await
+    {
+        tracing::debug!("not tracing {tool_name} call for {team_name}: {e}");
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// Leader tool implementations
+// ═══════════════════════════════════════════════════════════════════════
+
+async fn handle_create_team(
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
+    _call_id: String,
+    arguments: String,
+) -> Result<ToolOutput, TeamToolError> {
+    let args: CreateTeamArgs = parse_args(&arguments)?;
+    if let Err(e) = validate_name("team", &args.name) {
+        return err_text(TeamErrorCode::InvalidArguments, e.to_string());
+    }
+    let teams = &session.services.teams;
+    let mgr = teams.manager();
+    let leader_tid = session.conversation_id;
+    let level = team_level(&turn);
+    let max_depth = turn.config.teams.max_depth;
+    if level > max_depth {
+        return err_text(
+            TeamErrorCode::LimitReached,
+            format!("teams may only nest {max_depth} level(s) deep (teams.max_depth)"),
+        );
+    }
+    // A teammate creating a team starts a sub-team of the team it belongs to.
+    let parent_team = if level > 1 {
+        mgr.list_teams().await.ok().and_then(|configs| {
+            configs
+                .into_iter()
+                .find(|t| t.members.iter().any(|m| m.thread_id == leader_tid))
+                .map(|t| t.name)
+        })
+    } else {
+        None
+    };
+    if args.dry_run && args.delegation_mode {
+        return err_text(
+            TeamErrorCode::InvalidArguments,
+            "dry_run cannot be combined with delegation_mode",
+        );
+    }
+    // Built up front so a bad config fails the call before anything is written.
+    match mgr
+        .create_team(
+            &args.name,
+            leader_tid,
+            args.description.clone(),
+            args.metadata.clone(),
+            args.display_mode,
+        )
+        .await
+    {
+        Ok(_config) => {
+            // Initialize task list for this team.
+            let tl = teams.tasks();
+            let _ = tl.init(&args.name).await;
+            if let Some(parent) = &parent_team
+                && let Err(e) = mgr.link_sub_team(parent, &args.name).await
+            {
+                tracing::warn!("failed to link team {} under {parent}: {e}", args.name);
+            }
+            spawn_leader_heartbeat(&session, args.name.clone(), leader_tid);
+            if args.work_stealing
+                && let Err(e) = mgr.set_work_stealing(&args.name, true).await
+            {
+                return Err(TeamToolError::storage("failed to enable work stealing", &e));
+            }
+            if args.dry_run
+                && let Err(e) = mgr.set_dry_run(&args.name, true).await
+            {
+                return Err(TeamToolError::storage("failed to enable dry-run mode", &e));
+            }
+            if args.delegation_mode {
+                if let Err(e) = mgr.set_delegation_mode(&args.name, true).await {
+                    return Err(TeamToolError::storage(
+                        "failed to enable delegation mode",
+                        &e,
+                    ));
+                }
+                start_delegation_supervisor(&session, Arc::clone(&turn), args.name.clone());
+            } else if args.supervise {
+                start_leader_supervisor(&session, args.name.clone());
+            }
+
+            // Emit TeamCreated event.
+            session
+                .send_event(
+                    &turn,
+                    EventMsg::TeamCreated(TeamCreatedEvent {
+                        team_name: args.name.clone(),
+                        leader_thread_id: leader_tid,
+                        description: args.description.clone(),
+                        display_mode: args.display_mode,
+                    }),
+                )
+                .await;
+            start_progress_reporter(&session, Arc::clone(&turn), args.name.clone());
+            forward_leader_mail(&session, Arc::clone(&turn), args.name.clone());
+            if !args.dry_run {
+                let syncs = task_syncs(&turn.config.teams, &args.name);
+                start_task_sync(&session, syncs, args.name.clone());
+            }
+
+            ok_json(&CreateTeamResult {
+                status: ToolStatus::Created,
+                team_name: args.name,
+                leader_thread_id: leader_tid,
+                description: args.description,
+                metadata: args.metadata.into_iter().collect(),
+                display_mode: args.display_mode,
+                delegation_mode: args.delegation_mode,
+                work_stealing: args.work_stealing,
+                dry_run: args.dry_run,
+                parent_team,
+            })
+        }
+        Err(e) => Err(TeamToolError::storage("failed to create team", &e)),
+    }
+}
+
+async fn handle_spawn_teammate(
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
+    _call_id: String,
+    arguments: String,
+) -> Result<ToolOutput, TeamToolError> {
+    let args: SpawnTeammateArgs = parse_args(&arguments)?;
+    ensure_leader(&session, &args.team_name).await?;
+    if let Err(e) = validate_name("teammate", &args.name) {
+        return err_text(TeamErrorCode::InvalidArguments, e.to_string());
+    }
+    let level = team_level(&turn);
+    let max_members = turn.config.teams.max_members_at(level);
+    let teams = &session.services.teams;
+    let mgr = teams.manager();
+
+    // Refuse before spawning so a full team doesn't leave a stray agent behind.
+    let team = teams
+        .load_config(&args.team_name)
+        .await
+        .map_err(|e| TeamToolError::storage("failed to load team", &e))?;
+    let planned = if team.dry_run {
+        mgr.dry_run_plan(&args.team_name)
+            .await
+            .map_err(|e| TeamToolError::storage("failed to load dry-run plan", &e))?
+    } else {
+        DryRunPlan::default()
+    };
+    let members = team.members.len() + planned.active_members().count();
+    if members >= max_members {
+        return err_text(
+            TeamErrorCode::LimitReached,
+            format!(
+                "team '{}' already has {members} members (the limit at nesting level {level} is {max_members}); shut down a teammate before spawning another",
+                args.team_name
+            ),
+        );
+    }
+    if team.members.iter().any(|m| m.name == args.name)
+        || planned.active_members().any(|m| m.name == args.name)
+    {
+        return err_text(
+            TeamErrorCode::InvalidArguments,
+            format!(
+                "team '{}' already has a teammate named '{}'",
+                args.team_name, args.name
+            ),
+        );
+    }
+    if let Some(cap) = turn.config.teams.max_team_tokens {
+        let used = refresh_team_usage(&session, &args.team_name)
+            .await
+            .map_err(|e| TeamToolError::storage("failed to load usage", &e))?
+            .total()
+            .total_tokens;
+        if used >= cap {
+            return err_text(
+                TeamErrorCode::BudgetExceeded,
+                format!(
+                    "team '{}' has used {used} of its {cap} token budget (teams.max_team_tokens); no more teammates can be spawned",
+                    args.team_name
+                ),
+            );
+        }
+    }
+    if let Some(kind) = args.kind.as_deref() {
+        if kind != MCP_KIND && kind != A2A_KIND && kind != SSH_KIND {
+            return err_text(
+                TeamErrorCode::InvalidArguments,
+                format!(
+                    "unknown kind '{kind}'; supported values are \"{MCP_KIND}\", \"{A2A_KIND}\" and \"{SSH_KIND}\""
+                ),
+            );
+        }
+        return spawn_external_teammate(&session, &turn, team.dry_run, args).await;
+    }
+
+    if args.max_tokens.is_some_and(|max| max <= 0)
+        || args.max_turns == Some(0)
+        || args.timeout_minutes == Some(0)
+    {
+        return err_text(
+            TeamErrorCode::InvalidArguments,
+            "max_tokens, max_turns and timeout_minutes must be positive",
+        );
+    }
+    if let Some(isolation) = args.isolation.as_deref()
+        && isolation != WORKTREE_ISOLATION
+    {
+        return err_text(
+            TeamErrorCode::InvalidArguments,
+            format!(
+                "unknown isolation '{isolation}'; the only supported value is \"{WORKTREE_ISOLATION}\""
+            ),
+        );
+    }
+    if args.writable_paths.is_some()
+        && (args.writable_roots.is_some()
+            || args.isolation.is_some()
+            || args
+                .sandbox_mode
+                .is_some_and(|mode| !matches!(mode, SandboxMode::WorkspaceWrite)))
+    {
+        return err_text(
+            TeamErrorCode::InvalidArguments,
+            "writable_paths cannot be combined with writable_roots, isolation or a sandbox_mode other than workspace-write",
+        );
+    }
+    let mut cwd = args
+        .cwd
+        .as_deref()
+        .map(|cwd| teammate_cwd(&turn.sandbox_policy, &turn.cwd, cwd))
+        .transpose()
+        .map_err(TeamToolError::other)?;
+    let budget = (args.max_tokens.is_some() || args.max_turns.is_some()).then_some(MemberBudget {
+        max_tokens: args.max_tokens,
+        max_turns: args.max_turns,
+    });
+
+    // Build config for the teammate agent. The watcher respawns errored
+    // teammates from the base config, re-applying role, sandbox and worktree.
+    let base_config = build_teammate_config(&turn)?;
+    let mut config = base_config.clone();
+    if let Some(role) = &args.role {
+        apply_role(&mut config, role)
+            .await
+            .map_err(|e| TeamToolError::storage(format!("failed to load role '{role}'"), &e))?;
+    }
+    let narrowed = args.writable_paths.is_some();
+    let sandbox_policy = teammate_sandbox_policy(
+        &teammate_leader_sandbox(&turn.sandbox_policy, turn.approval_policy),
+        &turn.cwd,
+        args.sandbox_mode,
+        args.writable_roots.or(args.writable_paths),
+    )
+    .map_err(TeamToolError::other)?;
+    if narrowed && let Some(SandboxPolicy::WorkspaceWrite { writable_roots, .. }) = &sandbox_policy
+    {
+        cwd = writable_paths_cwd(&turn.cwd, cwd, writable_roots).map_err(TeamToolError::other)?;
+    }
+    if let Some(policy) = &sandbox_policy {
+        config
+            .permissions
+            .sandbox_policy
+            .set(policy.clone())
+            .map_err(|err| TeamToolError::other(format!("sandbox_mode is not allowed: {err}")))?;
+        // The teammate can never widen the sandbox it was narrowed to.
+        config.permissions.sandbox_policy = Constrained::allow_only(policy.clone());
+    }
+
+    if let Some(cwd) = &cwd {
+        config.cwd = cwd.clone();
+    }
+    if team.dry_run {
+        let member = PlannedMember {
+            name: args.name,
+            role: args.role,
+            prompt: args.prompt,
+            budget,
+            cwd,
+            worktree: args.isolation.is_some(),
+            mcp: None,
+            a2a: None,
+            ssh: None,
+        };
+        let plan = mgr
+            .update_dry_run_plan(&args.team_name, |plan| plan.members.push(member))
+            .await;
+        return dry_run_output(&turn, args.team_name, plan);
+    }
+    let worktree = if args.isolation.is_some() {
+        let path = mgr.worktree_path(&args.team_name, &args.name);
+        let branch = teammate_branch(&args.team_name, &args.name);
+        let worktree = create_worktree(&config.cwd, &path, &branch)
+            .await
+            .map_err(|e| {
+                TeamToolError::new(
+                    TeamErrorCode::SpawnFailed,
+                    format!("failed to create worktree: {e}"),
+                )
+            })?;
+        config.cwd = worktree.cwd_for(&config.cwd);
+        Some(worktree)
+    } else {
+        None
+    };
+
+    // Over the concurrency limit the teammate is spawned idle and queued; it
+    // gets its prompt once a slot frees up.
+    let queued = match turn.config.teams.max_concurrent_agents {
+        Some(limit) => running_teammates(&session, &team).await >= limit,
+        None => false,
+    };
+    let input_items = if queued {
+        Vec::new()
+    } else {
+        vec![UserInput::Text {
+            text: args.prompt.clone(),
+            text_elements: Vec::new(),
+        }]
+    };
+
+    // Spawn a real agent thread via AgentControl.
+    let session_source = SessionSource::SubAgent(SubAgentSource::ThreadSpawn {
+        parent_thread_id: session.conversation_id,
+        depth: next_thread_spawn_depth(&turn.session_source),
+    });
+
+    // The teammate's session is spawned in its span, so its turns are traced
+    // under it.
+    let span = teammate_span(&args.team_name, &args.name, args.role.as_deref());
+    let thread_id = match session
+        .services
+        .agent_control
+        .spawn_agent(config, input_items, Some(session_source))
+        .instrument(span)
+        .await
+    {
+        Ok(thread_id) => thread_id,
+        Err(e) => {
+            forget_teammate_span(&args.team_name, &args.name);
+            if let Some(worktree) = &worktree
+                && let Err(e) = remove_worktree(worktree).await
+            {
+                tracing::warn!("failed to remove worktree of {}: {e}", args.name);
+            }
+            return err_text(
+                TeamErrorCode::SpawnFailed,
+                format!("failed to spawn teammate agent: {e}"),
+            );
+        }
+    };
+
+    // Persist member config to disk.
+    let member = MemberConfig {
+        name: args.name.clone(),
+        thread_id,
+        role: args.role.clone(),
+        status: if queued { QUEUED_STATUS } else { "running" }.to_string(),
+        prompt: Some(args.prompt.clone()),
+        sandbox_policy,
+        budget,
+        timeout_minutes: args.timeout_minutes,
+        max_restarts: args.max_restarts,
+        restarts: 0,
+        worktree: worktree.clone(),
+        cwd: cwd.clone(),
+        mcp: None,
+        a2a: None,
+        ssh: None,
+        joined: false,
+    };
+    match mgr.add_member(&args.team_name, member.clone()).await {
+        Ok(()) => {}
+        Err(e)
+            if matches!(
+                e.kind(),
+                std::io::ErrorKind::QuotaExceeded | std::io::ErrorKind::AlreadyExists
+            ) =>
+        {
+            // Another spawn filled the last slot or took the name while this
+            // agent was starting.
+            let _ = session
+                .services
+                .agent_control
+                .shutdown_agent(thread_id)
+                .await;
+            forget_teammate_span(&args.team_name, &args.name);
+            if let Some(worktree) = &worktree
+                && let Err(e) = remove_worktree(worktree).await
+            {
+                tracing::warn!("failed to remove worktree of {}: {e}", args.name);
+            }
+            let code = if e.kind() == std::io::ErrorKind::QuotaExceeded {
+                TeamErrorCode::LimitReached
+            } else {
+                TeamErrorCode::InvalidArguments
+            };
+            return err_text(code, e.to_string());
+        }
+        Err(e) => {
+            // Agent was spawned but config persistence failed — still report success.
+            tracing::warn!(
+                "spawned teammate {}, but failed to persist config: {e}",
+                args.name
+            );
+        }
+    }
+
+    // Emit TeamMemberAdded event.
+    session
+        .send_event(
+            &turn,
+            EventMsg::TeamMemberAdded(TeamMemberEvent {
+                team_name: args.team_name.clone(),
+                member: TeamMemberInfo {
+                    name: args.name.clone(),
+                    thread_id,
+                    role: args.role.clone(),
+                    status: if queued {
+                        AgentStatus::PendingInit
+                    } else {
+                        AgentStatus::Running
+                    },
+                },
+                previous_status: None,
+            }),
+        )
+        .await;
+    if !queued {
+        start_teammate_timeout(&session, Arc::clone(&turn), args.team_name.clone(), &member);
+    }
+    forward_teammate_events(&session, Arc::clone(&turn), args.team_name.clone(), &member);
+    watch_teammate_liveness(&session, Arc::clone(&turn), args.team_name.clone(), &member);
+    watch_teammate_completion(
+        &session,
+        Arc::clone(&turn),
+        args.team_name.clone(),
+        member,
+        base_config,
+    );
+
+    ok_json(&SpawnTeammateResult {
+        status: if queued {
+            ToolStatus::Queued
+        } else {
+            ToolStatus::Spawned
+        },
+        teammate: args.name,
+        thread_id,
+        team_name: args.team_name,
+        branch: worktree.as_ref().map(|w| w.branch.clone()),
+        worktree: worktree.as_ref().map(|w| w.path.display().to_string()),
+        cwd: cwd.as_ref().map(|cwd| cwd.display().to_string()),
+    })
+}
+
+/// Register an external teammate reached through an MCP tool, at an A2A
+/// endpoint or on an SSH host, then deliver its prompt to it.
+async fn spawn_external_teammate(
+    session: &Arc<Session>,
+    turn: &Arc<TurnContext>,
+    dry_run: bool,
+    args: SpawnTeammateArgs,
+) -> Result<ToolOutput, TeamToolError> {
+    if args.sandbox_mode.is_some()
+        || args.writable_roots.is_some()
+        || args.writable_paths.is_some()
+        || args.max_tokens.is_some()
+        || args.max_turns.is_some()
+        || args.timeout_minutes.is_some()
+        || args.max_restarts.is_some()
+        || args.isolation.is_some()
+    {
+        return err_text(
+            TeamErrorCode::InvalidArguments,
+            "an external teammate has no thread: sandbox_mode, writable_roots, writable_paths, max_tokens, max_turns, timeout_minutes, max_restarts and isolation do not apply",
+        );
+    }
+    let kind = args.kind.as_deref();
+    if args.cwd.is_some() && kind != Some(SSH_KIND) {
+        return err_text(
+            TeamErrorCode::InvalidArguments,
+            format!("cwd only applies to kind \"{SSH_KIND}\", as the directory on the host"),
+        );
+    }
+    let (mcp, a2a, ssh) = if kind == Some(SSH_KIND) {
+        let Some(host) = args.host else {
+            return err_text(
+                TeamErrorCode::InvalidArguments,
+                format!("kind \"{SSH_KIND}\" needs the host the teammate runs on"),
+            );
+        };
+        if !turn.config.teams.ssh_hosts.contains(&host) {
+            return err_text(
+                TeamErrorCode::InvalidArguments,
+                format!("SSH host '{host}' is not listed in teams.ssh_hosts"),
+            );
+        }
+        let spec = SshMemberSpec {
+            host,
+            cwd: args.cwd.map(|cwd| cwd.to_string_lossy().into_owned()),
+            session_id: None,
+        };
+        (None, None, Some(spec))
+    } else if kind == Some(A2A_KIND) {
+        let Some(endpoint) = args.endpoint else {
+            return err_text(
+                TeamErrorCode::InvalidArguments,
+                format!("kind \"{A2A_KIND}\" needs the endpoint of the agent"),
+            );
+        };
+        let allowed = turn
+            .config
+            .teams
+            .a2a_endpoints
+            .iter()
+            .any(|allowed| allowed.trim_end_matches('/') == endpoint.trim_end_matches('/'));
+        if !allowed {
+            return err_text(
+                TeamErrorCode::InvalidArguments,
+                format!("endpoint '{endpoint}' is not listed in teams.a2a_endpoints"),
+            );
+        }
+        let spec = A2aMemberSpec {
+            endpoint,
+            context_id: None,
+        };
+        (None, Some(spec), None)
+    } else {
+        let (Some(server), Some(tool)) = (args.server, args.tool) else {
+            return err_text(
+                TeamErrorCode::InvalidArguments,
+                format!(
+                    "kind \"{MCP_KIND}\" needs the server and tool the teammate is reached through"
+                ),
+            );
+        };
+        let spec = McpMemberSpec { server, tool };
+        if !has_mcp_tool(session, &spec).await {
+            return err_text(
+                TeamErrorCode::InvalidArguments,
+                format!("MCP server '{}' has no tool '{}'", spec.server, spec.tool),
+            );
+        }
+        (Some(spec), None, None)
+    };
+    let mgr = session.services.teams.manager();
+    if dry_run {
+        let member = PlannedMember {
+            name: args.name,
+            role: args.role,
+            prompt: args.prompt,
+            budget: None,
+            cwd: None,
+            worktree: false,
+            mcp,
+            a2a,
+            ssh,
+        };
+        let plan = mgr
+            .update_dry_run_plan(&args.team_name, |plan| plan.members.push(member))
+            .await;
+        return dry_run_output(turn, args.team_name, plan);
+    }
+
+    let member = MemberConfig {
+        name: args.name.clone(),
+        thread_id: ThreadId::new(),
+        role: args.role.clone(),
+        status: "pending".to_string(),
+        prompt: Some(args.prompt.clone()),
+        sandbox_policy: None,
+        budget: None,
+        timeout_minutes: None,
+        max_restarts: None,
+        restarts: 0,
+        worktree: None,
+        cwd: None,
+        mcp,
+        a2a,
+        ssh,
+        joined: false,
+    };
+    match mgr.add_member(&args.team_name, member.clone()).await {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::QuotaExceeded => {
+            return err_text(TeamErrorCode::LimitReached, e.to_string());
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+            return err_text(TeamErrorCode::InvalidArguments, e.to_string());
+        }
+        Err(e) => return Err(TeamToolError::storage("failed to add teammate", &e)),
+    }
+    session
+        .send_event(
+            turn,
+            EventMsg::TeamMemberAdded(TeamMemberEvent {
+                team_name: args.team_name.clone(),
+                member: TeamMemberInfo {
+                    name: args.name.clone(),
+                    thread_id: member.thread_id,
+                    role: args.role,
+                    status: external_status(&member),
+                },
+                previous_status: None,
+            }),
+        )
+        .await;
+    deliver_to_external_member(
+        session,
+        turn,
+        &args.team_name,
+        &args.name,
+        "leader",
+        &args.prompt,
+    );
+
+    ok_json(&SpawnTeammateResult {
+        status: ToolStatus::Spawned,
+        teammate: args.name,
+        thread_id: member.thread_id,
+        team_name: args.team_name,
+        branch: None,
+        worktree: None,
+        cwd: None,
+    })
+}
+
+async fn handle_assign_task(
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
+    _call_id: String,
+    arguments: String,
+) -> Result<ToolOutput, TeamToolError> {
+    let args: AssignTaskArgs = parse_args(&arguments)?;
+    ensure_leader(&session, &args.team_name).await?;
+    let tl = session.services.teams.tasks();
+    let _ = tl.init(&args.team_name).await;
+    let team = session
+        .services
+        .teams
+        .load_config(&args.team_name)
+        .await
+        .map_err(|e| {
+            TeamToolError::storage(format!("failed to load team '{}'", args.team_name), &e)
+        })?;
+    if team.dry_run {
+        return plan_task(&session, &turn, &team, args).await;
+    }
+    let tasks = tl
+        .get_all_tasks(&args.team_name)
+        .await
+        .map_err(|e| TeamToolError::storage("failed to load tasks", &e))?;
+    check_assignment(&team, &tasks, None, &args)?;
+
+    let task_id = format!("task-{}", uuid::Uuid::new_v4().as_simple());
+    let task = TeamTaskInfo {
+        id: task_id.clone(),
+        title: args.title.clone(),
+        description: args.description.clone(),
+        status: TeamTaskStatus::Pending,
+        assigned_to: args.assigned_to.clone(),
+        depends_on: args.depends_on.clone(),
+        result: None,
+        created_at: Some(chrono::Utc::now().to_rfc3339()),
+    };
+    match tl.create_task(&args.team_name, task.clone()).await {
+        Ok(()) => {
+            // Emit TeamTaskCreated event.
+            session
+                .send_event(
+                    &turn,
+                    EventMsg::TeamTaskCreated(TeamTaskEvent {
+                        team_name: args.team_name.clone(),
+                        task: task,
+                    }),
+                )
+                .await;
+
+            if let Some(ref assignee) = args.assigned_to
+                && let Ok(Some(task)) = tl.assign_task(&args.team_name, &task_id, assignee).await
+            {
+                let notice = format!("You have been assigned task {}: {}", task.id, task.title);
+                session
+                    .send_event(
+                        &turn,
+                        EventMsg::TeamTaskUpdated(TeamTaskEvent {
+                            team_name: args.team_name.clone(),
+                            task,
+                        }),
+                    )
+                    .await;
+                deliver_to_external_member(
+                    &session,
+                    &turn,
+                    &args.team_name,
+                    assignee,
+                    "leader",
+                    &notice,
+                );
+            }
+
+            ok_json(&AssignTaskResult {
+                status: ToolStatus::Created,
+                task_id,
+                title: args.title,
+                assigned_to: args.assigned_to,
+            })
+        }
+        Err(e) => Err(TeamToolError::storage("failed to create task", &e)),
+    }
+}
+
+/// Create a task the user asked for (`Op::CreateTeamTask`) the way the
+/// `assign_task` tool would, reporting a failure as a `TeamError` event.
+pub(crate) async fn create_user_task(
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
+    team_name: String,
+    title: String,
+    description: Option<String>,
+    assigned_to: Option<String>,
+    depends_on: Vec<String>,
+) {
+    let arguments = json!({
+        "team_name": team_name,
+        "title": title,
+        "description": description,
+        "assigned_to": assigned_to,
+        "depends_on": depends_on,
+    })
+    .to_string();
+    let output = handle_assign_task(
+        Arc::clone(&session),
+        Arc::clone(&turn),
+        String::new(),
+        arguments,
+    )
+    .await;
+    report_user_tool_error(&session, &turn, "assign_task", team_name, output).await;
+}
+
+/// `assign_task` in a dry-run team: check the assignee and dependencies
+/// against the team and its plan, then add the task to the plan.
+async fn plan_task(
+    session: &Session,
+    turn: &TurnContext,
+    team: &TeamConfig,
+    args: AssignTaskArgs,
+) -> Result<ToolOutput, TeamToolError> {
+    let mgr = session.services.teams.manager();
+    let plan = mgr
+        .dry_run_plan(&args.team_name)
+        .await
+        .map_err(|e| TeamToolError::storage("failed to load dry-run plan", &e))?;
+    let tasks = session
+        .services
+        .teams
+        .tasks()
+        .get_all_tasks(&args.team_name)
+        .await
+        .unwrap_or_default();
+    check_assignment(team, &tasks, Some(&plan), &args)?;
+    let task = PlannedTask {
+        id: plan.next_task_id(),
+        title: args.title,
+        assigned_to: args.assigned_to,
+        depends_on: args.depends_on,
+    };
+    let plan = mgr
+        .update_dry_run_plan(&args.team_name, |plan| plan.tasks.push(task))
+        .await;
+    dry_run_output(turn, args.team_name, plan)
+}
+
+/// Check that the `assign_task` assignee is a member of `team` and that
+/// every dependency is one of `tasks`. In a dry run the members and tasks of
+/// `plan` count as well.
+fn check_assignment(
+    team: &TeamConfig,
+    tasks: &[TeamTaskInfo],
+    plan: Option<&DryRunPlan>,
+    args: &AssignTaskArgs,
+) -> Result<(), TeamToolError> {
+    let scope = if plan.is_some() { " in the plan" } else { "" };
+    if let Some(assignee) = &args.assigned_to
+        && !team.members.iter().any(|m| &m.name == assignee)
+        && !plan.is_some_and(|plan| plan.active_members().any(|m| &m.name == assignee))
+    {
+        return Err(TeamToolError::new(
+            TeamErrorCode::MemberNotFound,
+            format!("teammate '{assignee}' not found{scope}"),
+        ));
+    }
+    if let Some(missing) = args.depends_on.iter().find(|dep| {
+        !tasks.iter().any(|t| &t.id == *dep)
+            && !plan.is_some_and(|plan| plan.tasks.iter().any(|t| &t.id == *dep))
+    }) {
+        return Err(TeamToolError::new(
+            TeamErrorCode::TaskNotFound,
+            format!("task '{missing}' not found{scope}"),
+        ));
+    }
+    Ok(())
+}
+
+fn dry_run_output(
+    turn: &TurnContext,
+    team_name: String,
+    plan: std::io::Result<DryRunPlan>,
+) -> Result<ToolOutput, TeamToolError> {
+    match plan {
+        Ok(plan) => ok_json(&DryRunResult {
+            status: ToolStatus::Planned,
+            team_name,
+            estimate: plan.estimate(turn.config.teams.max_team_tokens),
+            plan,
+        }),
+        Err(e) => Err(TeamToolError::storage("failed to update dry-run plan", &e)),
+    }
+}
+
+async fn handle_reassign_task(
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
+    arguments: String,
+) -> Result<ToolOutput, TeamToolError> {
+    let args: ReassignTaskArgs = parse_args(&arguments)?;
+    ensure_leader(&session, &args.team_name).await?;
+    let teams = &session.services.teams;
+    match teams.load_config(&args.team_name).await {
+        Ok(config) if config.members.iter().any(|m| m.name == args.assigned_to) => {}
+        Ok(_) => {
+            return err_text(
+                TeamErrorCode::MemberNotFound,
+                format!("teammate '{}' not found", args.assigned_to),
+            );
+        }
+        Err(e) => {
+            return Err(TeamToolError::storage(
+                format!("failed to load team '{}'", args.team_name),
+                &e,
+            ));
+        }
+    }
+    let tl = teams.tasks();
+    let previous = match tl.get_all_tasks(&args.team_name).await {
+        Ok(tasks) => match tasks.into_iter().find(|t| t.id == args.task_id) {
+            Some(task) if task.status == TeamTaskStatus::Completed => {
+                return err_text(
+                    TeamErrorCode::InvalidArguments,
+                    format!("task '{}' is already completed", args.task_id),
+                );
+            }
+            Some(task) => task.assigned_to,
+            None => {
+                return err_text(
+                    TeamErrorCode::TaskNotFound,
+                    format!("task '{}' not found", args.task_id),
+                );
+            }
+        },
+        Err(e) => return Err(TeamToolError::storage("failed to get tasks", &e)),
+    };
+    let task = match tl
+        .assign_task(&args.team_name, &args.task_id, &args.assigned_to)
+        .await
+    {
+        Ok(Some(task)) => task,
+        Ok(None) => {
+            return err_text(
+                TeamErrorCode::TaskNotFound,
+                format!("task '{}' not found", args.task_id),
+            );
+        }
+        Err(e) => return Err(TeamToolError::storage("failed to reassign task", &e)),
+    };
+    session
+        .send_event(
+            &turn,
+            EventMsg::TeamTaskUpdated(TeamTaskEvent {
+                team_name: args.team_name.clone(),
+                task: task.clone(),
+            }),
+        )
+        .await;
+
+    // Tell the new assignee about the task, and the previous one to drop it.
+    let mut notices = vec![(
+        args.assigned_to.clone(),
+        format!("You have been assigned task {}: {}", task.id, task.title),
+    )];
+    if let Some(previous) = previous.as_ref().filter(|p| **p != args.assigned_to) {
+        notices.push((
+            previous.clone(),
+            format!(
+                "Task {} ({}) has been reassigned to {}; stop working on it.",
+                task.id, task.title, args.assigned_to
+            ),
+        ));
+    }
+    let inbox = teams.inbox(&args.team_name);
+    for (to, content) in notices {
+        let message = InboxMessage {
+            from: "leader".to_string(),
+            content: content.clone(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            read: false,
+        };
+        if let Err(e) = inbox.send_message(&to, message).await {
+            tracing::warn!("failed to tell {to} about reassigned task {}: {e}", task.id);
+            continue;
+        }
+        session
+            .send_event(
+                &turn,
+                EventMsg::TeamMessageSent(TeamMessageEvent {
+                    team_name: args.team_name.clone(),
+                    from: "leader".to_string(),
+                    to: to.clone(),
+                    content: content.clone(),
+                }),
+            )
+            .await;
+        deliver_to_external_member(&session, &turn, &args.team_name, &to, "leader", &content);
+    }
+
+    ok_json(&ReassignTaskResult {
+        status: ToolStatus::Reassigned,
+        task_id: task.id,
+        assigned_to: args.assigned_to,
+        previous_assignee: previous,
+    })
+}
+
+/// Reassign a task as the user asked (`Op::ReassignTeamTask`) the way the
+/// `reassign_task` tool would, reporting a failure as a `TeamError` event.
+pub(crate) async fn reassign_user_task(
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
+    team_name: String,
+    task_id: String,
+    assigned_to: String,
+) {
+    let arguments = json!({
+        "team_name": team_name,
+        "task_id": task_id,
+        "assigned_to": assigned_to,
+    })
+    .to_string();
+    let output = handle_reassign_task(Arc::clone(&session), Arc::clone(&turn), arguments).await;
+    report_user_tool_error(&session, &turn, "reassign_task", team_name, output).await;
+}
+
+/// Emit the failure of a team tool the user ran through a UI as a
+/// `TeamError` event, as the handler does for the model's calls.
+async fn report_user_tool_error(
+    session: &Session,
+    turn: &TurnContext,
+    tool_name: &str,
+    team_name: String,
+    output: Result<ToolOutput, TeamToolError>,
+) {
+    if let Err(TeamToolError { code, message }) = output {
+        session
+            .send_event(
+                turn,
+                EventMsg::TeamError(TeamErrorEvent {
+                    tool_name: tool_name.to_string(),
+                    team_name: Some(team_name),
+                    code,
+                    message,
+                }),
+            )
+            .await;
+    }
+}
+
+async fn handle_send_team_message(
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
+    arguments: String,
+) -> Result<ToolOutput, TeamToolError> {
+    let args: SendTeamMessageArgs = parse_args(&arguments)?;
+    ensure_leader(&session, &args.team_name).await?;
+    let members = session
+        .services
+        .teams
+        .manager()
+        .list_members(&args.team_name)
+        .await
+        .map_err(|e| {
+            TeamToolError::storage(format!("failed to load team '{}'", args.team_name), &e)
+        })?;
+    if !members.contains_key(&args.to) {
+        return Err(TeamToolError::new(
+            TeamErrorCode::MemberNotFound,
+            format!(
+                "teammate '{}' not found in team '{}'",
+                args.to, args.team_name
+            ),
+        ));
+    }
+    let inbox = session.services.teams.inbox(&args.team_name);
+    let msg = InboxMessage {
+        from: "leader".to_string(),
+        content: args.content.clone(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        read: false,
+    };
+    if let Err(e) = inbox.send_message(&args.to, msg).await {
+        return Err(TeamToolError::storage("failed to send message", &e));
+    }
+
+    session
+        .send_event(
+            &turn,
+            EventMsg::TeamMessageSent(TeamMessageEvent {
+                team_name: args.team_name.clone(),
+                from: "leader".to_string(),
+                to: args.to.clone(),
+                content: args.content.clone(),
+            }),
+        )
+        .await;
+    deliver_to_external_member(
+        &session,
+        &turn,
+        &args.team_name,
+        &args.to,
+        "leader",
+        &args.content,
+    );
+
+    ok_json(&MessageResult {
+        status: ToolStatus::Sent,
+        to: args.to,
+    })
+}
+
+/// Deliver a message the user typed to teammate `to`, signed `"user"` so the
+/// teammate can tell it from the leader's instructions.
+pub(crate) async fn send_user_team_message(
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
+    team_name: String,
+    to: String,
+    content: String,
+) {
+    let output = async {
+        ensure_leader(&session, &team_name).await?;
+        let members = session
+            .services
+            .teams
+            .manager()
+            .list_members(&team_name)
+            .await
+            .map_err(|e| {
+                TeamToolError::storage(format!("failed to load team '{team_name}'"), &e)
+            })?;
+        if !members.contains_key(&to) {
+            return Err(TeamToolError::new(
+                TeamErrorCode::MemberNotFound,
+                format!("teammate '{to}' not found in team '{team_name}'"),
+            ));
+        }
+        let msg = InboxMessage {
+            from: USER_SENDER.to_string(),
+            content: content.clone(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            read: false,
+        };
+        session
+            .services
+            .teams
+            .inbox(&team_name)
+            .send_message(&to, msg)
+            .await
+            .map_err(|e| TeamToolError::storage("failed to send message", &e))?;
+        session
+            .send_event(
+                &turn,
+                EventMsg::TeamMessageSent(TeamMessageEvent {
+                    team_name: team_name.clone(),
+                    from: USER_SENDER.to_string(),
+                    to: to.clone(),
+                    content: content.clone(),
+                }),
+            )
+            .await;
+        deliver_to_external_member(&session, &turn, &team_name, &to, USER_SENDER, &content);
+        ok_json(&MessageResult {
+            status: ToolStatus::Sent,
+            to,
+        })
+    }
+    .await;
+    report_user_tool_error(&session, &turn, "send_team_message", team_name, output).await;
+}
+
+/// Answer a teammate's escalation as the user asked
+/// (`Op::AnswerTeamEscalation`): record the answer and write it to the
+/// teammate's inbox, signed by the user.
+pub(crate) async fn answer_user_team_escalation(
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
+    team_name: String,
+    escalation_id: String,
+    answer: String,
+) {
+    let output = async {
+        ensure_leader(&session, &team_name).await?;
+        let teams = &session.services.teams;
+        let escalation = teams
+            .manager()
+            .answer_escalation(&team_name, &escalation_id, &answer)
+            .await
+            .map_err(|e| TeamToolError::storage("failed to record the answer", &e))?
+            .ok_or_else(|| {
+                TeamToolError::other(format!(
+                    "escalation '{escalation_id}' not found or already answered in team '{team_name}'"
+                ))
+            })?;
+        let content = format!(
+            "Answer to your question \"{}\" ({escalation_id}): {answer}",
+            escalation.question
+        );
+        let msg = InboxMessage {
+            from: USER_SENDER.to_string(),
+            content: content.clone(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            read: false,
+        };
+        teams
+            .inbox(&team_name)
+            .send_message(&escalation.from, msg)
+            .await
+            .map_err(|e| TeamToolError::storage("failed to send the answer", &e))?;
+        session
+            .send_event(
+                &turn,
+                EventMsg::TeamMessageSent(TeamMessageEvent {
+                    team_name: team_name.clone(),
+                    from: USER_SENDER.to_string(),
+                    to: escalation.from.clone(),
+                    content,
+                }),
+            )
+            .await;
+        ok_json(&MessageResult {
+            status: ToolStatus::Answered,
+            to: escalation.from,
+        })
+    }
+    .await;
+    report_user_tool_error(&session, &turn, "escalate_to_user", team_name, output).await;
+}
+
+/// Run a plan the user started (`Op::RunTeamPlan`): create the team with
+/// `supervise` on, spawn its members and then seed their tasks, each as the
+/// matching tool would. Stops at the first step that fails, reporting it
+/// under that tool's name.
+pub(crate) async fn run_user_team_plan(
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
+    plan: TeamPlan,
+) {
+    let team_name = plan.name.clone();
+    if let Err(message) = validate_plan(&plan) {
+        report_user_tool_error(
+            &session,
+            &turn,
+            "run_team_plan",
+            team_name,
+            err_text(TeamErrorCode::InvalidArguments, message),
+        )
+        .await;
+        return;
+    }
+
+    let arguments = json!({
+        "name": plan.name,
+        "description": plan.description,
+        "display_mode": plan.display_mode,
+        "work_stealing": plan.work_stealing,
+        "supervise": true,
+    })
+    .to_string();
+    let output = handle_create_team(
+        Arc::clone(&session),
+        Arc::clone(&turn),
+        String::new(),
+        arguments,
+    )
+    .await;
+    if output.is_err() {
+        report_user_tool_error(&session, &turn, "create_team", team_name, output).await;
+        return;
+    }
+
+    for member in plan.members {
+        let arguments = json!({
+            "team_name": team_name,
+            "name": member.name,
+            "role": member.role,
+            "prompt": member.prompt,
+            "max_tokens": member.max_tokens,
+            "max_turns": member.max_turns,
+        })
+        .to_string();
+        let output = handle_spawn_teammate(
+            Arc::clone(&session),
+            Arc::clone(&turn),
+            String::new(),
+            arguments,
+        )
+        .await;
+        if output.is_err() {
+            report_user_tool_error(&session, &turn, "spawn_teammate", team_name, output).await;
+            return;
+        }
+    }
+
+    // Dependencies name plan ids; the task list assigns its own.
+    let mut task_ids: HashMap<String, String> = HashMap::new();
+    for task in plan.tasks {
+        let depends_on = match task
+            .depends_on
+            .iter()
+            .map(|id| task_ids.get(id).ok_or(id))
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(depends_on) => depends_on,
+            Err(id) => {
+                let message = format!(
+                    "task '{}' depends on '{id}', which was not created",
+                    task.id
+                );
+                let output = err_text(TeamErrorCode::InvalidArguments, message);
+                report_user_tool_error(&session, &turn, "assign_task", team_name, output).await;
+                return;
+            }
+        };
+        let arguments = json!({
+            "team_name": team_name,
+            "title": task.title,
+            "description": task.description,
+            "assigned_to": task.assigned_to,
+            "depends_on": depends_on,
+        })
+        .to_string();
+        let output = handle_assign_task(
+            Arc::clone(&session),
+            Arc::clone(&turn),
+            String::new(),
+            arguments,
+        )
+        .await;
+        let created_id = match &output {
+            Ok(ToolOutput::Function { body, .. }) => body
+                .to_text()
+                .and_then(|text| serde_json::from_str::<serde_json::Value>(&text).ok())
+                .and_then(|value| value["task_id"].as_str().map(str::to_string)),
+            _ => None,
+        };
+        let Some(created_id) = created_id else {
+            report_user_tool_error(&session, &turn, "assign_task", team_name, output).await;
+            return;
+        };
+        task_ids.insert(task.id, created_id);
+    }
+}
+
+async fn handle_broadcast_team_message(
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
+    arguments: String,
+) -> Result<ToolOutput, TeamToolError> {
+    let args: BroadcastTeamMessageArgs = parse_args(&arguments)?;
+    ensure_leader(&session, &args.team_name).await?;
+    let inbox = session.services.teams.inbox(&args.team_name);
+    if let Err(e) = inbox.broadcast("leader", &args.content, true).await {
+        return Err(TeamToolError::storage("failed to broadcast", &e));
+    }
+
+    session
+        .send_event(
+            &turn,
+            EventMsg::TeamMessageSent(TeamMessageEvent {
+                team_name: args.team_name,
+                from: "leader".to_string(),
+                to: "all".to_string(),
+                content: args.content,
+            }),
+        )
+        .await;
+
+    ok_json(&StatusResult {
+        status: ToolStatus::Broadcast,
+    })
+}
+
+async fn handle_wait_for_teammates(
+    session: Arc<Session>,
+    arguments: String,
+) -> Result<ToolOutput, TeamToolError> {
+    let args: WaitForTeammatesArgs = parse_args(&arguments)?;
+    let teams = &session.services.teams;
+    let config = teams
+        .load_config(&args.team_name)
+        .await
+        .map_err(|e| TeamToolError::storage("failed to poll teammates", &e))?;
+    let members: Vec<&MemberConfig> = if args.names.is_empty() {
+        config.members.iter().collect()
+    } else {
+        let mut members = Vec::with_capacity(args.names.len());
+        for name in &args.names {
+            let Some(member) = config.members.iter().find(|m| &m.name == name) else {
+                return err_text(
+                    TeamErrorCode::MemberNotFound,
+                    format!("teammate '{name}' not found in team '{}'", args.team_name),
+                );
+            };
+            members.push(member);
+        }
+        members
+    };
+
+    let timeout_secs = args
+        .timeout_secs
+        .unwrap_or(DEFAULT_WAIT_TIMEOUT_SECS)
+        .clamp(1, MAX_WAIT_TIMEOUT_SECS);
+    let deadline = Instant::now() + Duration::from_secs(timeout_secs);
+    let agent_control = &session.services.agent_control;
+
+    let mut receivers = Vec::with_capacity(members.len());
+    for member in &members {
+        if let Ok(rx) = agent_control.subscribe_status(member.thread_id).await {
+            receivers.push(rx);
+        }
+    }
+
+    let (statuses, condition_met, timed_out) = loop {
+        let mut statuses = Vec::with_capacity(members.len());
+        for member in &members {
+            statuses.push(member_status(&session, &args.team_name, member).await);
+        }
+        let has_open_tasks = args.condition == WaitCondition::AllIdle
+            && teams
+                .get_all_tasks(&args.team_name)
+                .await
+                .unwrap_or_default()
+                .iter()
+                .any(|t| {
+                    !matches!(t.status, TeamTaskStatus::Completed)
+                        && members
+                            .iter()
+                            .any(|m| t.assigned_to.as_deref() == Some(m.name.as_str()))
+                });
+        if wait_condition_met(args.condition, &statuses, has_open_tasks) {
+            break (statuses, true, false);
+        }
+
+        // Members without a status channel are polled until the deadline.
+        let wake_at = if receivers.len() < members.len() {
+            deadline.min(Instant::now() + WAIT_POLL_INTERVAL)
+        } else {
+            deadline
+        };
+        let outcome = if receivers.is_empty() {
+            tokio::time::sleep_until(wake_at).await;
+            None
+        } else {
+            let changed =
+                futures::future::select_all(receivers.iter_mut().map(|rx| Box::pin(rx.changed())));
+            match tokio::time::timeout_at(wake_at, changed).await {
+                Ok((result, index, _)) => Some((result.is_err(), index)),
+                Err(_) => None,
+            }
+        };
+        match outcome {
+            // The teammate's thread went away; stop watching it.
+            Some((true, index)) => {
+                receivers.swap_remove(index);
+            }
+            Some((false, _)) => {}
+            None if Instant::now() >= deadline => break (statuses, false, true),
+            None => {}
+        }
+    };
+
+    let members = members
+        .iter()
+        .zip(statuses)
+        .map(|(member, status)| TeammateStatus {
+            name: member.name.clone(),
+            thread_id: member.thread_id,
+            role: member.role.clone(),
+            status,
+        })
+        .collect();
+    ok_json(&WaitForTeammatesResult {
+        condition: args.condition,
+        condition_met,
+        timed_out,
+        members,
+    })
+}
+
+/// Whether the watched teammates satisfy `condition`. `has_open_tasks` is
+/// only consulted for [`WaitCondition::AllIdle`].
+fn wait_condition_met(
+    condition: WaitCondition,
+    statuses: &[AgentStatus],
+    has_open_tasks: bool,
+) -> bool {
+    match condition {
+        WaitCondition::AnyCompleted => statuses.iter().any(is_final),
+        WaitCondition::AllCompleted => statuses.iter().all(is_final),
+        WaitCondition::AllIdle => statuses.iter().all(is_final) && !has_open_tasks,
+    }
+}
+
+async fn handle_get_task_status(
+    session: Arc<Session>,
+    arguments: String,
+) -> Result<ToolOutput, TeamToolError> {
+    let args: TeamNameArgs = parse_args(&arguments)?;
+    match session.services.teams.get_all_tasks(&args.team_name).await {
+        Ok(tasks) => ok_json(&TasksResult { tasks }),
+        Err(e) => Err(TeamToolError::storage("failed to get tasks", &e)),
+    }
+}
+
+async fn handle_team_summary(
+    session: Arc<Session>,
+    arguments: String,
+) -> Result<ToolOutput, TeamToolError> {
+    let args: TeamNameArgs = parse_args(&arguments)?;
+    let teams = &session.services.teams;
+    let config = teams
+        .load_config(&args.team_name)
+        .await
+        .map_err(|e| TeamToolError::storage("failed to load team", &e))?;
+    let tasks = teams
+        .get_all_tasks(&args.team_name)
+        .await
+        .map_err(|e| TeamToolError::storage("failed to get tasks", &e))?;
+    let mut members = Vec::with_capacity(config.members.len());
+    for member in &config.members {
+        let status = member_status(&session, &args.team_name, member).await;
+        members.push((member.name.as_str(), status));
+    }
+    let mut messages = teams
+        .inbox(&args.team_name)
+        .read_inbox("leader")
+        .await
+        .unwrap_or_default();
+    let recent = messages.split_off(messages.len().saturating_sub(SUMMARY_RECENT_MESSAGES));
+    let mut sub_teams = Vec::with_capacity(config.sub_teams.len());
+    for name in &config.sub_teams {
+        let (Ok(sub_config), Ok(sub_tasks)) = (
+            teams.load_config(name).await,
+            teams.get_all_tasks(name).await,
+        ) else {
+            continue;
+        };
+        sub_teams.push(SubTeamSummary {
+            name: name.clone(),
+            leader: config
+                .members
+                .iter()
+                .find(|m| m.thread_id == sub_config.leader_thread_id)
+                .map(|m| m.name.clone()),
+            teammates: sub_config.members.len(),
+            completed_tasks: sub_tasks
+                .iter()
+                .filter(|t| matches!(t.status, TeamTaskStatus::Completed))
+                .count(),
+            total_tasks: sub_tasks.len(),
+        });
+    }
+
+    let completed = tasks
+        .iter()
+        .filter(|t| matches!(t.status, TeamTaskStatus::Completed))
+        .count();
+    ok_json(&TeamSummaryResult {
+        summary: team_summary(&args.team_name, &members, &tasks, &sub_teams, &recent),
+        completed_tasks: completed,
+        total_tasks: tasks.len(),
+        blockers: blockers(&members, &tasks),
+        sub_teams: config.sub_teams,
+    })
+}
+
+async fn handle_get_team_usage(
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
+    arguments: String,
+) -> Result<ToolOutput, TeamToolError> {
+    let args: TeamNameArgs = parse_args(&arguments)?;
+    let usage = refresh_team_usage(&session, &args.team_name)
+        .await
+        .map_err(|e| TeamToolError::storage("failed to load usage", &e))?;
+    let total = usage.total();
+    let cap = turn.config.teams.max_team_tokens;
+    ok_json(&TeamUsageResult {
+        team_name: args.team_name,
+        participants: usage.tokens_by_participant(),
+        input_tokens: total.input_tokens,
+        output_tokens: total.output_tokens,
+        total_tokens: total.total_tokens,
+        max_team_tokens: cap,
+        remaining_tokens: cap.map(|cap| (cap - total.total_tokens).max(0)),
+    })
+}
+
+async fn handle_get_teammate(
+    session: Arc<Session>,
+    arguments: String,
+) -> Result<ToolOutput, TeamToolError> {
+    let args: GetTeammateArgs = parse_args(&arguments)?;
+    let teams = &session.services.teams;
+    let config = teams
+        .load_config(&args.team_name)
+        .await
+        .map_err(|e| TeamToolError::storage("failed to load team", &e))?;
+    let Some(member) = config.members.iter().find(|m| m.name == args.name) else {
+        return err_text(
+            TeamErrorCode::MemberNotFound,
+            format!(
+                "teammate '{}' not found in team '{}'",
+                args.name, args.team_name
+            ),
+        );
+    };
+
+    let agent_control = &session.services.agent_control;
+    let status = member_status(&session, &args.team_name, member).await;
+    let tasks = teams
+        .get_all_tasks(&args.team_name)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|t| t.assigned_to.as_deref() == Some(member.name.as_str()))
+        .map(|t| TeammateTask {
+            id: t.id,
+            title: t.title,
+            status: t.status,
+        })
+        .collect();
+    let unread_messages = teams
+        .inbox(&args.team_name)
+        .read_inbox(&member.name)
+        .await
+        .map(|messages| messages.iter().filter(|m| !m.read).count())
+        .unwrap_or_default();
+    // The rollout file is appended to on every turn item, so its mtime is a
+    // cheap proxy for the teammate's last activity.
+    let last_activity_at = match agent_control.get_rollout_path(member.thread_id).await {
+        Some(path) => tokio::fs::metadata(path)
+            .await
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .map(|modified| chrono::DateTime::<chrono::Utc>::from(modified).to_rfc3339()),
+        None => None,
+    };
+
+    ok_json(&TeammateResult {
+        name: member.name.clone(),
+        thread_id: member.thread_id,
+        role: member.role.clone(),
+        status,
+        tasks,
+        unread_messages,
+        last_activity_at,
+    })
+}
+
+async fn handle_get_teammate_output(
+    session: Arc<Session>,
+    arguments: String,
+) -> Result<ToolOutput, TeamToolError> {
+    let args: GetTeammateOutputArgs = parse_args(&arguments)?;
+    let config = session
+        .services
+        .teams
+        .load_config(&args.team_name)
+        .await
+        .map_err(|e| TeamToolError::storage("failed to load team", &e))?;
+    let Some(member) = config.members.iter().find(|m| m.name == args.name) else {
+        return err_text(
+            TeamErrorCode::MemberNotFound,
+            format!(
+                "teammate '{}' not found in team '{}'",
+                args.name, args.team_name
+            ),
+        );
+    };
+
+    let agent_control = &session.services.agent_control;
+    let status = member_status(&session, &args.team_name, member).await;
+    let mut last_message = match &status {
+        AgentStatus::Completed(message) => message.clone(),
+        _ => None,
+    };
+
+    // The status only carries the final message of a finished turn; fall
+    // back to the rollout for running teammates and for turn history.
+    let turns_requested = args.turns.unwrap_or_default();
+    let mut turns = Vec::new();
+    if (last_message.is_none() || turns_requested > 0)
+        && let Some(path) = agent_control.get_rollout_path(member.thread_id).await
+    {
+        let items = RolloutRecorder::get_rollout_history(&path)
+            .await
+            .map(|history| history.get_rollout_items())
+            .map_err(|e| TeamToolError::storage("failed to read teammate rollout", &e))?;
+        turns = recent_turns(&items, turns_requested.max(1));
+        if last_message.is_none() {
+            last_message = turns.last().and_then(|turn| turn.messages.last().cloned());
+        }
+        if turns_requested == 0 {
+            turns.clear();
+        }
+    }
+
+    ok_json(&TeammateOutputResult {
+        name: member.name.clone(),
+        status,
+        last_message,
+        turns,
+    })
+}
+
+/// The last `limit` turns in `items`. A turn starts at each user message and
+/// collects the assistant messages that follow it.
+fn recent_turns(items: &[RolloutItem], limit: usize) -> Vec<TeammateTurn> {
+    let mut turns: Vec<TeammateTurn> = Vec::new();
+    for item in items {
+        match item {
+            RolloutItem::EventMsg(EventMsg::UserMessage(ev)) => turns.push(TeammateTurn {
+                input: ev.message.clone(),
+                messages: Vec::new(),
+            }),
+            RolloutItem::EventMsg(EventMsg::AgentMessage(ev)) => {
+                if let Some(turn) = turns.last_mut() {
+                    turn.messages.push(ev.message.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+    let skip = turns.len().saturating_sub(limit);
+    turns.split_off(skip)
+}
+
+async fn handle_shutdown_teammate(
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
+    _call_id: String,
+    arguments: String,
+) -> Result<ToolOutput, TeamToolError> {
+    let args: ShutdownTeammateArgs = parse_args(&arguments)?;
+    ensure_leader(&session, &args.team_name).await?;
+    let teams = &session.services.teams;
+    let mgr = teams.manager();
+
+    // Find the teammate's ThreadId from the config.
+    let config = teams
+        .load_config(&args.team_name)
+        .await
+        .map_err(|e| TeamToolError::storage("failed to load team", &e))?;
+    if config.dry_run {
+        let plan = mgr
+            .dry_run_plan(&args.team_name)
+            .await
+            .map_err(|e| TeamToolError::storage("failed to load dry-run plan", &e))?;
+        if !plan.active_members().any(|m| m.name == args.name) {
+            return err_text(
+                TeamErrorCode::MemberNotFound,
+                format!("teammate '{}' not found in the plan", args.name),
+            );
+        }
+        let plan = mgr
+            .update_dry_run_plan(&args.team_name, |plan| plan.shutdowns.push(args.name))
+            .await;
+        return dry_run_output(&turn, args.team_name, plan);
+    }
+
+    let member = config
+        .members
+        .iter()
+        .find(|m| m.name == args.name)
+        .ok_or_else(|| {
+            TeamToolError::new(
+                TeamErrorCode::MemberNotFound,
+                format!("teammate '{}' not found", args.name),
+            )
+        })?;
+
+    let thread_id = member.thread_id;
+    let worktree = member.worktree.clone();
+
+    // Shut down the actual agent thread.
+    let _ = session
+        .services
+        .agent_control
+        .shutdown_agent(thread_id)
+        .await;
+    if let Some(worktree) = &worktree
+        && let Err(e) = remove_worktree(worktree).await
+    {
+        tracing::warn!("failed to remove worktree of {}: {e}", args.name);
+    }
+
+    // Remove from config.
+    if let Err(e) = mgr.remove_member(&args.team_name, &args.name).await {
+        tracing::warn!("failed to remove teammate '{}' from config: {e}", args.name);
+    }
+    if let Err(e) = mgr.release_files(&args.team_name, &args.name, None).await {
+        tracing::warn!(
+            "failed to release file reservations of '{}': {e}",
+            args.name
+        );
+    }
+
+    // Emit TeamMemberRemoved event.
+    session
+        .send_event(
+            &turn,
+            EventMsg::TeamMemberRemoved(TeamMemberEvent {
+                team_name: args.team_name.clone(),
+                member: TeamMemberInfo {
+                    name: args.name.clone(),
                     thread_id,
-                    role: args.role.clone(),
-                    status: AgentStatus::Running,
+                    role: None,
+                    status: AgentStatus::Shutdown,
                 },
+                previous_status: None,
+            }),
+        )
+        .await;
+
+    ok_json(&ShutdownTeammateResult {
+        status: ToolStatus::Shutdown,
+        teammate: args.name,
+        thread_id,
+    })
+}
+
+pub(crate) async fn shutdown_user_teammate(
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
+    team_name: String,
+    name: String,
+) {
+    let arguments = json!({ "team_name": team_name, "name": name }).to_string();
+    let output = handle_shutdown_teammate(
+        Arc::clone(&session),
+        Arc::clone(&turn),
+        String::new(),
+        arguments,
+    )
+    .await;
+    report_user_tool_error(&session, &turn, "shutdown_teammate", team_name, output).await;
+}
+
+async fn handle_restart_teammate(
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
+    arguments: String,
+) -> Result<ToolOutput, TeamToolError> {
+    let args: RestartTeammateArgs = parse_args(&arguments)?;
+    ensure_leader(&session, &args.team_name).await?;
+    let teams = &session.services.teams;
+    let config = teams
+        .load_config(&args.team_name)
+        .await
+        .map_err(|e| TeamToolError::storage("failed to load team", &e))?;
+    let member = config
+        .members
+        .iter()
+        .find(|m| m.name == args.name)
+        .ok_or_else(|| {
+            TeamToolError::new(
+                TeamErrorCode::MemberNotFound,
+                format!("teammate '{}' not found", args.name),
+            )
+        })?;
+    if member.is_external() {
+        return err_text(
+            TeamErrorCode::InvalidArguments,
+            format!(
+                "teammate '{}' is an external agent and has no thread to restart",
+                args.name
+            ),
+        );
+    }
+    if member.joined {
+        return err_text(
+            TeamErrorCode::InvalidArguments,
+            format!(
+                "teammate '{}' joined from another Codex process and cannot be restarted",
+                args.name
+            ),
+        );
+    }
+    let tasks = teams
+        .get_all_tasks(&args.team_name)
+        .await
+        .map_err(|e| TeamToolError::storage("failed to load tasks", &e))?;
+    let teammate_config = build_teammate_config(&turn)?;
+
+    let thread_id = restart_member(
+        &session,
+        &turn,
+        &args.team_name,
+        member,
+        &tasks,
+        &teammate_config,
+    )
+    .await
+    .map_err(|e| {
+        TeamToolError::new(
+            TeamErrorCode::SpawnFailed,
+            format!("failed to restart teammate: {e}"),
+        )
+    })?;
+
+    ok_json(&RestartTeammateResult {
+        status: ToolStatus::Restarted,
+        teammate: args.name,
+        previous_thread_id: member.thread_id,
+        thread_id,
+    })
+}
+
+pub(crate) async fn restart_user_teammate(
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
+    team_name: String,
+    name: String,
+) {
+    let arguments = json!({ "team_name": team_name, "name": name }).to_string();
+    let output = handle_restart_teammate(Arc::clone(&session), Arc::clone(&turn), arguments).await;
+    report_user_tool_error(&session, &turn, "restart_teammate", team_name, output).await;
+}
+
+async fn handle_merge_teammate_work(
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
+    arguments: String,
+) -> Result<ToolOutput, TeamToolError> {
+    let args: MergeTeammateWorkArgs = parse_args(&arguments)?;
+    ensure_leader(&session, &args.team_name).await?;
+    let teams = &session.services.teams;
+    let config = teams
+        .load_config(&args.team_name)
+        .await
+        .map_err(|e| TeamToolError::storage("failed to load team", &e))?;
+    let member = config
+        .members
+        .iter()
+        .find(|m| m.name == args.name)
+        .ok_or_else(|| {
+            TeamToolError::new(
+                TeamErrorCode::MemberNotFound,
+                format!("teammate '{}' not found", args.name),
+            )
+        })?;
+    let Some(worktree) = &member.worktree else {
+        return err_text(
+            TeamErrorCode::InvalidArguments,
+            format!(
+                "teammate '{}' works in your checkout; only teammates spawned with isolation \"{WORKTREE_ISOLATION}\" have a branch to merge",
+                args.name
+            ),
+        );
+    };
+
+    // Resolve the tasks to annotate before merging, so an unknown task id
+    // leaves the leader's branch untouched.
+    let tasks = teams
+        .get_all_tasks(&args.team_name)
+        .await
+        .map_err(|e| TeamToolError::storage("failed to load tasks", &e))?;
+    let task_ids: Vec<String> = match args.task_id {
+        Some(task_id) => {
+            if !tasks.iter().any(|task| task.id == task_id) {
+                return err_text(
+                    TeamErrorCode::TaskNotFound,
+                    format!("task '{task_id}' not found"),
+                );
+            }
+            vec![task_id]
+        }
+        None => tasks
+            .into_iter()
+            .filter(|task| task.assigned_to.as_deref() == Some(args.name.as_str()))
+            .map(|task| task.id)
+            .collect(),
+    };
+
+    let trailers = if turn.config.teams.co_author_trailers {
+        config.co_author_trailers([member.name.as_str()])
+    } else {
+        Vec::new()
+    };
+    let outcome = merge_worktree(&turn.cwd, worktree, args.strategy, &trailers)
+        .await
+        .map_err(|e| TeamToolError::other(format!("failed to merge: {e}")))?;
+    let branch = worktree.branch.clone();
+    let (note, output) = match outcome {
+        MergeOutcome::Merged { head, commits } => (
+            format!("Merged {commits} commit(s) from {branch}; the leader's branch is at {head}."),
+            MergeTeammateWorkResult::Merged {
+                branch,
+                commits,
+                head,
+            },
+        ),
+        MergeOutcome::UpToDate => (
+            format!("{branch} had nothing new to merge."),
+            MergeTeammateWorkResult::UpToDate { branch },
+        ),
+        MergeOutcome::Conflicts { files } => (
+            format!(
+                "Merging {branch} was aborted; conflicts in: {}.",
+                files.join(", ")
+            ),
+            MergeTeammateWorkResult::Conflicts {
+                branch,
+                conflicts: files,
+            },
+        ),
+    };
+
+    for task_id in task_ids {
+        match teams
+            .tasks()
+            .append_result(&args.team_name, &task_id, &note)
+            .await
+        {
+            Ok(Some(task)) => {
+                session
+                    .send_event(
+                        &turn,
+                        EventMsg::TeamTaskUpdated(TeamTaskEvent {
+                            team_name: args.team_name.clone(),
+                            task,
+                        }),
+                    )
+                    .await;
+            }
+            Ok(None) => tracing::warn!("task {task_id} was removed before the merge was recorded"),
+            Err(e) => tracing::warn!("failed to record merge result on {task_id}: {e}"),
+        }
+    }
+
+    ok_json(&output)
+}
+
+async fn handle_review_patch(
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
+    arguments: String,
+) -> Result<ToolOutput, TeamToolError> {
+    let args: ReviewPatchArgs = parse_args(&arguments)?;
+    ensure_leader(&session, &args.team_name).await?;
+    let teams = &session.services.teams;
+    let patches = teams
+        .manager()
+        .patches(&args.team_name)
+        .await
+        .map_err(|e| TeamToolError::storage("failed to load patches", &e))?
+        .patches;
+    let Some(patch_id) = args.patch_id else {
+        return ok_json(&PatchListResult { patches });
+    };
+    let Some(patch) = patches.into_iter().find(|patch| patch.id == patch_id) else {
+        return err_text(
+            TeamErrorCode::PatchNotFound,
+            format!("patch '{patch_id}' not found"),
+        );
+    };
+
+    let Some(decision) = args.decision else {
+        let contents = teams
+            .manager()
+            .patch_diff(&args.team_name, &patch)
+            .await
+            .map_err(|e| TeamToolError::storage("failed to read patch", &e))?;
+        let check = apply_patch(&turn.cwd, &contents, true).await;
+        let diff = String::from_utf8_lossy(&contents);
+        let (diff, truncated) = truncate_artifact_content(&diff);
+        let path = teams.manager().patch_diff_path(&args.team_name, &patch.id);
+        return ok_json(&PatchPreviewResult {
+            patch,
+            diff,
+            truncated,
+            path,
+            applies_cleanly: check.is_ok(),
+            apply_error: check.err().map(|e| e.to_string()),
+        });
+    };
+
+    if patch.status == PatchStatus::Applied {
+        return err_text(
+            TeamErrorCode::InvalidArguments,
+            format!("patch '{patch_id}' is already applied"),
+        );
+    }
+    let status = match decision {
+        PatchDecision::Approve => PatchStatus::Approved,
+        PatchDecision::Reject => PatchStatus::Rejected,
+    };
+    if status == PatchStatus::Approved {
+        teams
+            .manager()
+            .patch_diff(&args.team_name, &patch)
+            .await
+            .map_err(|e| TeamToolError::storage("failed to read patch", &e))?;
+    }
+    let comment = args.comment.clone();
+    teams
+        .manager()
+        .update_patch(&args.team_name, &patch_id, move |patch| {
+            patch.status = status;
+            patch.comment = comment;
+        })
+        .await
+        .map_err(|e| TeamToolError::storage("failed to record review", &e))?;
+    send_patch_notice(
+        &session,
+        &args.team_name,
+        LEADER_INBOX,
+        &patch.author,
+        json!({
+            "type": "patch_reviewed",
+            "patch_id": patch_id,
+            "status": status,
+            "comment": args.comment,
+        }),
+    )
+    .await;
+    ok_json(&PatchReviewResult { status, patch_id })
+}
+
+async fn handle_apply_patch_from_teammate(
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
+    arguments: String,
+) -> Result<ToolOutput, TeamToolError> {
+    let args: ApplyPatchFromTeammateArgs = parse_args(&arguments)?;
+    ensure_leader(&session, &args.team_name).await?;
+    let mgr = session.services.teams.manager();
+    let patch = mgr
+        .patches(&args.team_name)
+        .await
+        .map_err(|e| TeamToolError::storage("failed to load patches", &e))?
+        .patches
+        .into_iter()
+        .find(|patch| patch.id == args.patch_id)
+        .ok_or_else(|| {
+            TeamToolError::new(
+                TeamErrorCode::PatchNotFound,
+                format!("patch '{}' not found", args.patch_id),
+            )
+        })?;
+    match patch.status {
+        PatchStatus::Approved => {}
+        PatchStatus::Applied => {
+            return err_text(
+                TeamErrorCode::InvalidArguments,
+                format!("patch '{}' is already applied", args.patch_id),
+            );
+        }
+        PatchStatus::Pending | PatchStatus::Rejected => {
+            return err_text(
+                TeamErrorCode::InvalidArguments,
+                format!(
+                    "patch '{}' has not been approved; approve it with review_patch first",
+                    args.patch_id
+                ),
+            );
+        }
+    }
+
+    let diff = mgr
+        .patch_diff(&args.team_name, &patch)
+        .await
+        .map_err(|e| TeamToolError::storage("failed to read patch", &e))?;
+    if let Err(e) = apply_patch(&turn.cwd, &diff, false).await {
+        return err_text(
+            TeamErrorCode::Other,
+            format!("patch '{}' does not apply: {e}", args.patch_id),
+        );
+    }
+    if let Err(e) = mgr
+        .update_patch(&args.team_name, &args.patch_id, |patch| {
+            patch.status = PatchStatus::Applied;
+        })
+        .await
+    {
+        tracing::warn!("applied {}, but failed to record it: {e}", args.patch_id);
+    }
+    send_patch_notice(
+        &session,
+        &args.team_name,
+        LEADER_INBOX,
+        &patch.author,
+        json!({
+            "type": "patch_applied",
+            "patch_id": args.patch_id,
+        }),
+    )
+    .await;
+    // The patch lands uncommitted; the leader's own commit carries the credit.
+    let commit_trailers = if turn.config.teams.co_author_trailers {
+        session
+            .services
+            .teams
+            .load_config(&args.team_name)
+            .await
+            .map(|config| config.co_author_trailers([patch.author.as_str()]))
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+    ok_json(&ApplyPatchResult {
+        status: ToolStatus::Applied,
+        patch_id: args.patch_id,
+        files: patch.files,
+        commit_trailers,
+    })
+}
+
+/// Deliver a patch-review `notice` from `from` to the inbox of `to`.
+async fn send_patch_notice(
+    session: &Session,
+    team_name: &str,
+    from: &str,
+    to: &str,
+    notice: serde_json::Value,
+) {
+    let message = InboxMessage {
+        from: from.to_string(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        content: notice.to_string(),
+        read: false,
+    };
+    if let Err(e) = session
+        .services
+        .teams
+        .inbox(team_name)
+        .send_message(to, message)
+        .await
+    {
+        tracing::warn!("failed to notify {to} about a patch: {e}");
+    }
+}
+
+async fn handle_cleanup_team(
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
+    _call_id: String,
+    arguments: String,
+) -> Result<ToolOutput, TeamToolError> {
+    let args: TeamNameArgs = parse_args(&arguments)?;
+    ensure_leader(&session, &args.team_name).await?;
+    let teams = &session.services.teams;
+    let mgr = teams.manager();
+    let tl = teams.tasks();
+
+    // Shut down all teammates before cleanup.
+    if let Ok(config) = teams.load_config(&args.team_name).await {
+        for member in &config.members {
+            let _ = session
+                .services
+                .agent_control
+                .shutdown_agent(member.thread_id)
+                .await;
+        }
+    }
+
+    let _ = tl.cleanup(&args.team_name).await;
+    let _ = mgr.cleanup_team(&args.team_name).await;
+
+    // Emit TeamCleanup event.
+    session
+        .send_event(
+            &turn,
+            EventMsg::TeamCleanup(TeamCleanupEvent {
+                team_name: args.team_name.clone(),
+                leader_thread_id: session.conversation_id,
+            }),
+        )
+        .await;
+
+    ok_json(&CleanupTeamResult {
+        status: ToolStatus::CleanedUp,
+        team_name: args.team_name,
+    })
+}
+
+async fn handle_list_teams(session: Arc<Session>) -> Result<ToolOutput, TeamToolError> {
+    match session.services.teams.manager().list_teams().await {
+        Ok(teams) => {
+            let teams = teams
+                .into_iter()
+                .map(|t| TeamListing {
+                    member_count: t.members.len(),
+                    name: t.name,
+                    description: t.description,
+                    metadata: t.metadata.into_iter().collect(),
+                    created_at: t.created_at,
+                    leader_thread_id: t.leader_thread_id,
+                })
+                .collect();
+            ok_json(&ListTeamsResult { teams })
+        }
+        Err(e) => Err(TeamToolError::storage("failed to list teams", &e)),
+    }
+}
+
+async fn handle_rename_team(
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
+    arguments: String,
+) -> Result<ToolOutput, TeamToolError> {
+    let args: RenameTeamArgs = parse_args(&arguments)?;
+    ensure_leader(&session, &args.team_name).await?;
+    let teams = &session.services.teams;
+    let mgr = teams.manager();
+    let tl = teams.tasks();
+
+    let config = match mgr.rename_team(&args.team_name, &args.new_name, tl).await {
+        Ok(config) => config,
+        Err(e) if e.kind() == std::io::ErrorKind::InvalidInput => {
+            return Err(TeamToolError::new(
+                TeamErrorCode::InvalidArguments,
+                e.to_string(),
+            ));
+        }
+        Err(e) => return Err(TeamToolError::storage("failed to rename team", &e)),
+    };
+    // The old heartbeat stops once the old name disappears from disk.
+    spawn_leader_heartbeat(&session, args.new_name.clone(), config.leader_thread_id);
+
+    session
+        .send_event(
+            &turn,
+            EventMsg::TeamRenamed(TeamRenamedEvent {
+                old_name: args.team_name.clone(),
+                new_name: args.new_name.clone(),
+            }),
+        )
+        .await;
+
+    ok_json(&RenameTeamResult {
+        status: ToolStatus::Renamed,
+        old_name: args.team_name,
+        team_name: config.name,
+    })
+}
+
+async fn handle_handoff_leadership(
+    session: Arc<Session>,
+    arguments: String,
+) -> Result<ToolOutput, TeamToolError> {
+    let args: HandoffLeadershipArgs = parse_args(&arguments)?;
+    ensure_leader(&session, &args.team_name).await?;
+    let teams = &session.services.teams;
+    let mgr = teams.manager();
+
+    let team = teams
+        .load_config(&args.team_name)
+        .await
+        .map_err(|e| TeamToolError::storage("failed to load team", &e))?;
+    let Some(successor) = team.members.iter().find(|m| m.name == args.new_leader) else {
+        return err_text(
+            TeamErrorCode::MemberNotFound,
+            format!(
+                "teammate '{}' not found in team '{}'",
+                args.new_leader, args.team_name
+            ),
+        );
+    };
+    let successor_thread_id = successor.thread_id;
+    let previous_leader = team.leader_thread_id;
+    let successor_session = session
+        .services
+        .agent_control
+        .get_session(successor_thread_id)
+        .await
+        .map_err(|e| {
+            TeamToolError::other(format!(
+                "teammate '{}' is not running in this process: {e}",
+                args.new_leader
+            ))
+        })?;
+    let successor_turn = successor_session.new_default_turn().await;
+    // Built up front so a bad config fails the call before anything changes.
+    if let Err(e) = mgr.set_leader(&args.team_name, successor_thread_id).await {
+        return Err(TeamToolError::storage("failed to hand off leadership", &e));
+    }
+    // From here on the successor's session keeps the leader lock alive and
+    // emits the team's events. The old leader's loops stop on their own once
+    // the config names a new leader.
+    spawn_leader_heartbeat(
+        &successor_session,
+        args.team_name.clone(),
+        successor_thread_id,
+    );
+    if team.delegation_mode {
+        start_delegation_supervisor(
+            &successor_session,
+            Arc::clone(&successor_turn),
+            args.team_name.clone(),
+        );
+    }
+    start_progress_reporter(
+        &successor_session,
+        Arc::clone(&successor_turn),
+        args.team_name.clone(),
+    );
+    forward_leader_mail(
+        &successor_session,
+        Arc::clone(&successor_turn),
+        args.team_name.clone(),
+    );
+    if !team.dry_run {
+        let syncs = task_syncs(&successor_turn.config.teams, &args.team_name);
+        start_task_sync(&successor_session, syncs, args.team_name.clone());
+    }
+
+    let notice = format!(
+        "Leadership of team '{}' has been handed to {}. Direct questions and results to them from now on.",
+        args.team_name, args.new_leader
+    );
+    let inbox = teams.inbox(&args.team_name);
+    if let Err(e) = inbox.broadcast("leader", &notice, false).await {
+        tracing::warn!(
+            "failed to notify team {} of leader handoff: {e}",
+            args.team_name
+        );
+    }
+    // Inboxes are passive, so tell the new leader directly so it can start
+    // using the leader tools.
+    let briefing = format!(
+        "You are now the leader of team '{}'. Use the team leader tools (assign_task, wait_for_teammates, cleanup_team, ...) to coordinate the remaining work.",
+        args.team_name
+    );
+    let _ = session
+        .services
+        .agent_control
+        .send_input(
+            successor_thread_id,
+            vec![UserInput::Text {
+                text: briefing,
+                text_elements: Vec::new(),
+            }],
+        )
+        .await;
+
+    successor_session
+        .send_event(
+            &successor_turn,
+            EventMsg::TeamLeaderChanged(TeamLeaderChangedEvent {
+                team_name: args.team_name.clone(),
+                previous_leader_thread_id: previous_leader,
+                leader_thread_id: successor_thread_id,
             }),
         )
         .await;
 
-    ok_text(
-        json!({
-            "status": "spawned",
-            "teammate": args.name,
-            "thread_id": thread_id.to_string(),
-            "team_name": args.team_name,
-        })
-        .to_string(),
-    )
+    ok_json(&HandoffLeadershipResult {
+        status: ToolStatus::HandedOff,
+        team_name: args.team_name,
+        new_leader: args.new_leader,
+        leader_thread_id: successor_thread_id,
+    })
 }
 
-async fn handle_assign_task(
+// ═══════════════════════════════════════════════════════════════════════
+// Teammate tool implementations
+// ═══════════════════════════════════════════════════════════════════════
+
+async fn handle_accept_task(
     session: Arc<Session>,
     turn: Arc<TurnContext>,
-    _call_id: String,
     arguments: String,
-) -> Result<ToolOutput, FunctionCallError> {
-    let args: AssignTaskArgs = parse_arguments(&arguments)?;
-    let tl = TaskList::new(default_tasks_root());
-    let _ = tl.init(&args.team_name).await;
-
-    let task_id = format!("task-{}", uuid::Uuid::new_v4().as_simple());
-    let task = TeamTaskInfo {
-        id: task_id.clone(),
-        title: args.title.clone(),
-        status: TeamTaskStatus::Pending,
-        assigned_to: args.assigned_to.clone(),
-        depends_on: args.depends_on.clone(),
-    };
-    match tl.create_task(&args.team_name, task.clone()).await {
-        Ok(()) => {
-            if let Some(ref assignee) = args.assigned_to {
-                let _ = tl
-                    .assign_task(&args.team_name, &task_id, assignee)
-                    .await;
-            }
+) -> Result<ToolOutput, TeamToolError> {
+    let args: TeamNameArgs = parse_args(&arguments)?;
+    let name = calling_member_name(&session, &args.team_name).await?;
+    let tl = session.services.teams.tasks();
+    match tl.accept_next_task(&args.team_name, &name).await {
+        Ok(Some(task)) => {
+            let output = AcceptTaskResult::Accepted {
+                task_id: task.id.clone(),
+                title: task.title.clone(),
+                description: task.description.clone(),
+            };
+            session
+                .send_event(
+                    &turn,
+                    EventMsg::TeamTaskUpdated(TeamTaskEvent {
+                        team_name: args.team_name,
+                        task,
+                    }),
+                )
+                .await;
+            ok_json(&output)
+        }
+        Ok(None) => ok_json(&AcceptTaskResult::NoTasksAvailable),
+        Err(e) => Err(TeamToolError::storage("failed to accept task", &e)),
+    }
+}
 
-            // Emit TeamTaskCreated event.
+async fn handle_complete_task(
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
+    arguments: String,
+) -> Result<ToolOutput, TeamToolError> {
+    let args: CompleteTaskArgs = parse_args(&arguments)?;
+    let caller = calling_participant_name(&session, &args.team_name).await?;
+    let tl = session.services.teams.tasks();
+    // Teammates may only complete their own tasks; the leader may complete any.
+    if caller != LEADER_INBOX {
+        let tasks = tl
+            .get_all_tasks(&args.team_name)
+            .await
+            .map_err(|e| TeamToolError::storage("failed to get tasks", &e))?;
+        let Some(task) = tasks.into_iter().find(|t| t.id == args.task_id) else {
+            return err_text(
+                TeamErrorCode::TaskNotFound,
+                format!("task '{}' not found", args.task_id),
+            );
+        };
+        if task.assigned_to.as_deref() != Some(caller.as_str()) {
+            return err_text(
+                TeamErrorCode::InvalidArguments,
+                format!(
+                    "task '{}' is not assigned to you; only its assignee or the leader can complete it",
+                    args.task_id
+                ),
+            );
+        }
+    }
+    match tl.complete_task(&args.team_name, &args.task_id).await {
+        Ok(Some((task, unblocked))) => {
             session
                 .send_event(
                     &turn,
-                    EventMsg::TeamTaskCreated(TeamTaskEvent {
+                    EventMsg::TeamTaskUpdated(TeamTaskEvent {
                         team_name: args.team_name.clone(),
-                        task: task,
+                        task,
                     }),
                 )
                 .await;
-
-            ok_text(
-                json!({
-                    "status": "created",
-                    "task_id": task_id,
-                    "title": args.title,
-                    "assigned_to": args.assigned_to,
-                })
-                .to_string(),
-            )
+            let unblocked_ids: Vec<_> = unblocked.iter().map(|t| t.id.clone()).collect();
+            for task in unblocked {
+                session
+                    .send_event(
+                        &turn,
+                        EventMsg::TeamTaskUnblocked(TeamTaskUnblockedEvent {
+                            team_name: args.team_name.clone(),
+                            task,
+                            unblocked_by: args.task_id.clone(),
+                        }),
+                    )
+                    .await;
+            }
+            ok_json(&CompleteTaskResult {
+                status: ToolStatus::Completed,
+                task_id: args.task_id,
+                unblocked_task_ids: unblocked_ids,
+            })
         }
-        Err(e) => err_text(format!("failed to create task: {e}")),
+        Ok(None) => err_text(
+            TeamErrorCode::TaskNotFound,
+            format!("task '{}' not found", args.task_id),
+        ),
+        Err(e) => Err(TeamToolError::storage("failed to complete task", &e)),
+    }
+}
+
+async fn handle_get_tasks(
+    session: Arc<Session>,
+    arguments: String,
+) -> Result<ToolOutput, TeamToolError> {
+    let args: TeamNameArgs = parse_args(&arguments)?;
+    calling_member_name(&session, &args.team_name).await?;
+    match session.services.teams.get_all_tasks(&args.team_name).await {
+        Ok(tasks) => ok_json(&TasksResult { tasks }),
+        Err(e) => Err(TeamToolError::storage("failed to get tasks", &e)),
     }
 }
 
-async fn handle_send_team_message(arguments: String) -> Result<ToolOutput, FunctionCallError> {
-    let args: SendTeamMessageArgs = parse_arguments(&arguments)?;
-    let mgr = TeamManager::new(default_teams_root());
-    let inbox = Inbox::new(mgr.inboxes_dir(&args.team_name));
+async fn handle_request_shutdown(
+    session: Arc<Session>,
+    arguments: String,
+) -> Result<ToolOutput, TeamToolError> {
+    let args: TeamNameArgs = parse_args(&arguments)?;
+    let name = calling_member_name(&session, &args.team_name).await?;
+    let inbox = session.services.teams.inbox(&args.team_name);
     let msg = InboxMessage {
-        from: "leader".to_string(),
-        content: args.content.clone(),
+        from: name,
+        content: "Requesting shutdown — work complete.".to_string(),
         timestamp: chrono::Utc::now().to_rfc3339(),
         read: false,
     };
-    match inbox.send_message(&args.to, msg).await {
-        Ok(()) => ok_text(
-            json!({
-                "status": "sent",
-                "to": args.to,
-            })
-            .to_string(),
-        ),
-        Err(e) => err_text(format!("failed to send message: {e}")),
+    match inbox.send_message("leader", msg).await {
+        Ok(()) => ok_json(&StatusResult {
+            status: ToolStatus::ShutdownRequested,
+        }),
+        Err(e) => Err(TeamToolError::storage("failed to request shutdown", &e)),
     }
 }
 
-async fn handle_broadcast_team_message(
+async fn handle_submit_patch(
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
     arguments: String,
-) -> Result<ToolOutput, FunctionCallError> {
-    let args: BroadcastTeamMessageArgs = parse_arguments(&arguments)?;
-    let mgr = TeamManager::new(default_teams_root());
-    let inbox = Inbox::new(mgr.inboxes_dir(&args.team_name));
-    match inbox.broadcast("leader", &args.content, true).await {
-        Ok(()) => ok_text(
-            json!({
-                "status": "broadcast",
-            })
-            .to_string(),
-        ),
-        Err(e) => err_text(format!("failed to broadcast: {e}")),
+) -> Result<ToolOutput, TeamToolError> {
+    let args: SubmitPatchArgs = parse_args(&arguments)?;
+    let name = calling_member_name(&session, &args.team_name).await?;
+    let teams = &session.services.teams;
+    let mgr = teams.manager();
+    let patch = match args.patch {
+        Some(patch) => patch,
+        None => {
+            // In a worktree the teammate's own commits are part of its work.
+            let base = mgr
+                .member_by_thread(&args.team_name, session.conversation_id)
+                .await
+                .ok()
+                .flatten()
+                .and_then(|member| member.worktree)
+                .map_or_else(|| "HEAD".to_string(), |worktree| worktree.base_commit);
+            working_tree_diff(&turn.cwd, &base)
+                .await
+                .map_err(|e| TeamToolError::other(format!("failed to diff your changes: {e}")))?
+        }
+    };
+    let files = patch_files(&patch);
+    if files.is_empty() {
+        return err_text(
+            TeamErrorCode::InvalidArguments,
+            "nothing to submit: the patch must be a unified diff such as git diff prints",
+        );
     }
+
+    let submitted = mgr
+        .add_patch(&args.team_name, &name, &args.description, files, &patch)
+        .await
+        .map_err(|e| TeamToolError::storage("failed to record patch", &e))?;
+    send_patch_notice(
+        &session,
+        &args.team_name,
+        &name,
+        LEADER_INBOX,
+        json!({
+            "type": "patch_submitted",
+            "patch_id": submitted.id,
+            "description": submitted.description,
+            "files": submitted.files,
+        }),
+    )
+    .await;
+    ok_json(&SubmitPatchResult {
+        status: ToolStatus::Submitted,
+        patch_id: submitted.id,
+        path: mgr.patch_diff_path(&args.team_name, &submitted.id),
+        files: submitted.files,
+    })
 }
 
-async fn handle_wait_for_teammates(
+async fn handle_escalate_to_user(
     session: Arc<Session>,
     arguments: String,
-) -> Result<ToolOutput, FunctionCallError> {
-    let args: TeamNameArgs = parse_arguments(&arguments)?;
-    let mgr = TeamManager::new(default_teams_root());
-    match mgr.load_config(&args.team_name).await {
-        Ok(config) => {
-            let mut statuses = Vec::new();
-            for member in &config.members {
-                let status = session
-                    .services
-                    .agent_control
-                    .get_status(member.thread_id)
-                    .await;
-                statuses.push(json!({
-                    "name": member.name,
-                    "thread_id": member.thread_id.to_string(),
-                    "role": member.role,
-                    "status": format!("{:?}", status),
-                }));
-            }
-            ok_text(
-                json!({
-                    "status": "polled",
-                    "members": statuses,
-                })
-                .to_string(),
-            )
-        }
-        Err(e) => err_text(format!("failed to poll teammates: {e}")),
+) -> Result<ToolOutput, TeamToolError> {
+    let args: EscalateToUserArgs = parse_args(&arguments)?;
+    let question = args.question.trim();
+    if question.is_empty() {
+        return err_text(
+            TeamErrorCode::InvalidArguments,
+            "question must not be empty",
+        );
+    }
+    let name = calling_member_name(&session, &args.team_name).await?;
+    match session
+        .services
+        .teams
+        .manager()
+        .add_escalation(&args.team_name, &name, question, args.options)
+        .await
+    {
+        Ok(escalation) => ok_json(&EscalateToUserResult {
+            status: ToolStatus::Escalated,
+            escalation_id: escalation.id,
+        }),
+        Err(e) => Err(TeamToolError::storage("failed to escalate to the user", &e)),
     }
 }
 
-async fn handle_get_task_status(arguments: String) -> Result<ToolOutput, FunctionCallError> {
-    let args: TeamNameArgs = parse_arguments(&arguments)?;
-    let tl = TaskList::new(default_tasks_root());
-    match tl.get_all_tasks(&args.team_name).await {
-        Ok(tasks) => {
-            let task_json: Vec<_> = tasks
-                .iter()
-                .map(|t| {
-                    json!({
-                        "id": t.id,
-                        "title": t.title,
-                        "status": format!("{:?}", t.status),
-                        "assigned_to": t.assigned_to,
-                        "depends_on": t.depends_on,
-                    })
-                })
-                .collect();
-            ok_text(json!({ "tasks": task_json }).to_string())
-        }
-        Err(e) => err_text(format!("failed to get tasks: {e}")),
+// ═══════════════════════════════════════════════════════════════════════
+// Shared tool implementations
+// ═══════════════════════════════════════════════════════════════════════
+
+async fn handle_read_scratchpad(
+    session: Arc<Session>,
+    arguments: String,
+) -> Result<ToolOutput, TeamToolError> {
+    let args: TeamNameArgs = parse_args(&arguments)?;
+    calling_participant_name(&session, &args.team_name).await?;
+    match session
+        .services
+        .teams
+        .scratchpad(&args.team_name)
+        .read()
+        .await
+    {
+        Ok(content) => ok_json(&ScratchpadResult { content }),
+        Err(e) => Err(TeamToolError::storage("failed to read scratchpad", &e)),
     }
 }
 
-async fn handle_shutdown_teammate(
+async fn handle_append_scratchpad(
     session: Arc<Session>,
-    turn: Arc<TurnContext>,
-    _call_id: String,
     arguments: String,
-) -> Result<ToolOutput, FunctionCallError> {
-    let args: ShutdownTeammateArgs = parse_arguments(&arguments)?;
-    let mgr = TeamManager::new(default_teams_root());
-
-    // Find the teammate's ThreadId from the config.
-    let config = mgr
-        .load_config(&args.team_name)
+) -> Result<ToolOutput, TeamToolError> {
+    let args: AppendScratchpadArgs = parse_args(&arguments)?;
+    let author = calling_participant_name(&session, &args.team_name).await?;
+    match session
+        .services
+        .teams
+        .scratchpad(&args.team_name)
+        .append(&author, &args.content)
         .await
-        .map_err(|e| FunctionCallError::RespondToModel(format!("failed to load team: {e}")))?;
-
-    let member = config
-        .members
-        .iter()
-        .find(|m| m.name == args.name)
-        .ok_or_else(|| {
-            FunctionCallError::RespondToModel(format!("teammate '{}' not found", args.name))
-        })?;
+    {
+        Ok(content) => ok_json(&ScratchpadUpdateResult {
+            status: ToolStatus::Appended,
+            content,
+        }),
+        Err(e) => Err(TeamToolError::storage("failed to append to scratchpad", &e)),
+    }
+}
 
-    let thread_id = member.thread_id;
+async fn handle_replace_section(
+    session: Arc<Session>,
+    arguments: String,
+) -> Result<ToolOutput, TeamToolError> {
+    let args: ReplaceSectionArgs = parse_args(&arguments)?;
+    calling_participant_name(&session, &args.team_name).await?;
+    if args.heading.trim().is_empty() || args.heading.contains('\n') {
+        return err_text(
+            TeamErrorCode::InvalidArguments,
+            "heading must be a single non-empty line",
+        );
+    }
+    match session
+        .services
+        .teams
+        .scratchpad(&args.team_name)
+        .replace_section(&args.heading, &args.content)
+        .await
+    {
+        Ok(content) => ok_json(&ScratchpadUpdateResult {
+            status: ToolStatus::Replaced,
+            content,
+        }),
+        Err(e) => Err(TeamToolError::storage("failed to update scratchpad", &e)),
+    }
+}
 
-    // Shut down the actual agent thread.
-    let _ = session
+async fn handle_reserve_files(
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
+    arguments: String,
+) -> Result<ToolOutput, TeamToolError> {
+    let args: ReserveFilesArgs = parse_args(&arguments)?;
+    let holder = calling_participant_name(&session, &args.team_name).await?;
+    if args.paths.is_empty() {
+        return err_text(TeamErrorCode::InvalidArguments, "paths must not be empty");
+    }
+    let paths = reservation_paths(&turn, &args.paths)?;
+    match session
         .services
-        .agent_control
-        .shutdown_agent(thread_id)
-        .await;
+        .teams
+        .manager()
+        .reserve_files(&args.team_name, &holder, &paths)
+        .await
+    {
+        Ok(()) => ok_json(&FilesResult {
+            status: ToolStatus::Reserved,
+            paths,
+        }),
+        Err(e) if e.kind() == std::io::ErrorKind::ResourceBusy => err_text(
+            TeamErrorCode::Other,
+            format!("could not reserve files: {e}"),
+        ),
+        Err(e) => Err(TeamToolError::storage("failed to reserve files", &e)),
+    }
+}
 
-    // Remove from config.
-    if let Err(e) = mgr.remove_member(&args.team_name, &args.name).await {
-        tracing::warn!("failed to remove teammate '{}' from config: {e}", args.name);
+async fn handle_release_files(
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
+    arguments: String,
+) -> Result<ToolOutput, TeamToolError> {
+    let args: ReleaseFilesArgs = parse_args(&arguments)?;
+    let holder = calling_participant_name(&session, &args.team_name).await?;
+    let paths = match &args.paths {
+        Some(paths) => Some(reservation_paths(&turn, paths)?),
+        None => None,
+    };
+    match session
+        .services
+        .teams
+        .manager()
+        .release_files(&args.team_name, &holder, paths.as_deref())
+        .await
+    {
+        Ok(released) => ok_json(&FilesResult {
+            status: ToolStatus::Released,
+            paths: released,
+        }),
+        Err(e) => Err(TeamToolError::storage("failed to release files", &e)),
     }
+}
 
-    // Emit TeamMemberRemoved event.
-    session
-        .send_event(
-            &turn,
-            EventMsg::TeamMemberRemoved(TeamMemberEvent {
-                team_name: args.team_name.clone(),
-                member: TeamMemberInfo {
-                    name: args.name.clone(),
-                    thread_id,
-                    role: None,
-                    status: AgentStatus::Shutdown,
-                },
-            }),
+/// The file to copy into the artifact store for `path`. Symlinks are
+/// resolved, and the file must lie inside `cwd` or one of the writable roots
+/// of `policy`, so teammates can't share files from elsewhere on disk.
+fn artifact_source(policy: &SandboxPolicy, cwd: &Path, path: &Path) -> Result<PathBuf, String> {
+    let source = std::fs::canonicalize(cwd.join(path))
+        .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+    let allowed = std::iter::once(cwd.to_path_buf())
+        .chain(
+            policy
+                .get_writable_roots_with_cwd(cwd)
+                .into_iter()
+                .map(|root| root.root.into_path_buf()),
         )
-        .await;
-
-    ok_text(
-        json!({
-            "status": "shutdown",
-            "teammate": args.name,
-            "thread_id": thread_id.to_string(),
-        })
-        .to_string(),
-    )
+        .filter_map(|root| std::fs::canonicalize(root).ok())
+        .any(|root| source.starts_with(root));
+    if !allowed {
+        return Err(format!(
+            "{} is outside the cwd and writable roots",
+            path.display()
+        ));
+    }
+    Ok(source)
 }
 
-async fn handle_cleanup_team(
+async fn handle_store_artifact(
     session: Arc<Session>,
     turn: Arc<TurnContext>,
-    _call_id: String,
     arguments: String,
-) -> Result<ToolOutput, FunctionCallError> {
-    let args: TeamNameArgs = parse_arguments(&arguments)?;
-    let mgr = TeamManager::new(default_teams_root());
-    let tl = TaskList::new(default_tasks_root());
-
-    // Shut down all teammates before cleanup.
-    if let Ok(config) = mgr.load_config(&args.team_name).await {
-        for member in &config.members {
-            let _ = session
-                .services
-                .agent_control
-                .shutdown_agent(member.thread_id)
-                .await;
+) -> Result<ToolOutput, TeamToolError> {
+    let args: StoreArtifactArgs = parse_args(&arguments)?;
+    calling_participant_name(&session, &args.team_name).await?;
+    let store = session.services.teams.artifacts(&args.team_name);
+    let stored = match (&args.content, &args.path) {
+        (Some(content), None) => store.store(&args.name, content.as_bytes()).await,
+        (None, Some(path)) => {
+            let source = match artifact_source(&turn.sandbox_policy, &turn.cwd, path) {
+                Ok(source) => source,
+                Err(message) => return err_text(TeamErrorCode::InvalidArguments, message),
+            };
+            store.store_file(&args.name, &source).await
+        }
+        _ => {
+            return err_text(
+                TeamErrorCode::InvalidArguments,
+                "provide exactly one of content or path",
+            );
         }
+    };
+    match stored {
+        Ok(artifact) => ok_json(&StoreArtifactResult {
+            status: ToolStatus::Stored,
+            artifact,
+        }),
+        Err(e) => Err(TeamToolError::storage("failed to store artifact", &e)),
     }
+}
 
-    let _ = tl.cleanup(&args.team_name).await;
-    let _ = mgr.cleanup_team(&args.team_name).await;
+async fn handle_fetch_artifact(
+    session: Arc<Session>,
+    arguments: String,
+) -> Result<ToolOutput, TeamToolError> {
+    let args: FetchArtifactArgs = parse_args(&arguments)?;
+    calling_participant_name(&session, &args.team_name).await?;
+    let store = session.services.teams.artifacts(&args.team_name);
+    let (info, contents) = match store.fetch(&args.name).await {
+        Ok(fetched) => fetched,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            let available: Vec<String> = store
+                .list()
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .map(|artifact| artifact.name)
+                .collect();
+            return err_text(
+                TeamErrorCode::InvalidArguments,
+                format!(
+                    "no artifact named '{}'; available: [{}]",
+                    args.name,
+                    available.join(", ")
+                ),
+            );
+        }
+        Err(e) => return Err(TeamToolError::storage("failed to fetch artifact", &e)),
+    };
+    // Binary artifacts are handed over by path only.
+    let Ok(content) = String::from_utf8(contents) else {
+        return ok_json(&FetchArtifactResult {
+            artifact: info,
+            binary: true,
+            content: None,
+            truncated: false,
+        });
+    };
+    let (content, truncated) = truncate_artifact_content(&content);
+    ok_json(&FetchArtifactResult {
+        artifact: info,
+        binary: false,
+        content: Some(content),
+        truncated,
+    })
+}
 
-    // Emit TeamCleanup event.
-    session
-        .send_event(
-            &turn,
-            EventMsg::TeamCleanup(TeamCleanupEvent {
-                team_name: args.team_name.clone(),
-                leader_thread_id: session.conversation_id,
-            }),
-        )
-        .await;
+/// `content` cut to [`MAX_ARTIFACT_CONTENT_BYTES`] at a char boundary, and
+/// whether it was cut.
+fn truncate_artifact_content(content: &str) -> (&str, bool) {
+    if content.len() <= MAX_ARTIFACT_CONTENT_BYTES {
+        return (content, false);
+    }
+    let mut end = MAX_ARTIFACT_CONTENT_BYTES;
+    while !content.is_char_boundary(end) {
+        end -= 1;
+    }
+    (&content[..end], true)
+}
 
-    ok_text(
-        json!({
-            "status": "cleaned_up",
-            "team_name": args.team_name,
+/// Reservation keys for `paths`, which may be relative to the caller's cwd.
+fn reservation_paths(turn: &TurnContext, paths: &[PathBuf]) -> Result<Vec<String>, TeamToolError> {
+    paths
+        .iter()
+        .map(|path| {
+            AbsolutePathBuf::resolve_path_against_base(path, &turn.cwd)
+                .map(|path| repo_relative(path.as_path()))
+                .map_err(|e| TeamToolError::other(format!("invalid path {}: {e}", path.display())))
         })
-        .to_string(),
-    )
+        .collect()
 }
 
-// ═══════════════════════════════════════════════════════════════════════
-// Teammate tool implementations
-// ═══════════════════════════════════════════════════════════════════════
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::{AgentMessageEvent, UserMessageEvent};
+    use pretty_assertions::assert_eq;
 
-async fn handle_accept_task(arguments: String) -> Result<ToolOutput, FunctionCallError> {
-    let args: TeamNameArgs = parse_arguments(&arguments)?;
-    let tl = TaskList::new(default_tasks_root());
-    match tl.accept_next_task(&args.team_name, "self").await {
-        Ok(Some(task)) => ok_text(
-            json!({
-                "status": "accepted",
-                "task_id": task.id,
-                "title": task.title,
-            })
-            .to_string(),
-        ),
-        Ok(None) => ok_text(
-            json!({
-                "status": "no_tasks_available",
-            })
-            .to_string(),
-        ),
-        Err(e) => err_text(format!("failed to accept task: {e}")),
+    fn user(message: &str) -> RolloutItem {
+        RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+            message: message.to_string(),
+            images: None,
+            local_images: Vec::new(),
+            text_elements: Vec::new(),
+        }))
     }
-}
 
-async fn handle_complete_task(arguments: String) -> Result<ToolOutput, FunctionCallError> {
-    let args: CompleteTaskArgs = parse_arguments(&arguments)?;
-    let tl = TaskList::new(default_tasks_root());
-    match tl.complete_task(&args.team_name, &args.task_id).await {
-        Ok(_) => ok_text(
-            json!({
-                "status": "completed",
-                "task_id": args.task_id,
-            })
-            .to_string(),
-        ),
-        Err(e) => err_text(format!("failed to complete task: {e}")),
+    fn agent(message: &str) -> RolloutItem {
+        RolloutItem::EventMsg(EventMsg::AgentMessage(AgentMessageEvent {
+            message: message.to_string(),
+        }))
     }
-}
 
-async fn handle_get_tasks(arguments: String) -> Result<ToolOutput, FunctionCallError> {
-    let args: TeamNameArgs = parse_arguments(&arguments)?;
-    let tl = TaskList::new(default_tasks_root());
-    match tl.get_all_tasks(&args.team_name).await {
-        Ok(tasks) => {
-            let task_json: Vec<_> = tasks
-                .iter()
-                .map(|t| {
-                    json!({
-                        "id": t.id,
-                        "title": t.title,
-                        "status": format!("{:?}", t.status),
-                        "assigned_to": t.assigned_to,
-                        "depends_on": t.depends_on,
-                    })
-                })
-                .collect();
-            ok_text(json!({ "tasks": task_json }).to_string())
+    #[test]
+    fn teammate_sandbox_only_narrows_the_leader_policy() {
+        let cwd = Path::new("/repo");
+        let leader = SandboxPolicy::new_workspace_write_policy();
+
+        assert_eq!(teammate_sandbox_policy(&leader, cwd, None, None), Ok(None));
+        assert_eq!(
+            teammate_sandbox_policy(&leader, cwd, Some(SandboxMode::ReadOnly), None),
+            Ok(Some(SandboxPolicy::new_read_only_policy()))
+        );
+        assert!(
+            teammate_sandbox_policy(&leader, cwd, Some(SandboxMode::DangerFullAccess), None)
+                .is_err()
+        );
+
+        let narrowed = teammate_sandbox_policy(&leader, cwd, None, Some(vec!["src".into()]));
+        let Ok(Some(SandboxPolicy::WorkspaceWrite { writable_roots, .. })) = narrowed else {
+            panic!("expected a workspace-write policy, got {narrowed:?}");
+        };
+        assert_eq!(
+            writable_roots,
+            vec![AbsolutePathBuf::from_absolute_path("/repo/src").unwrap()]
+        );
+        assert!(teammate_sandbox_policy(&leader, cwd, None, Some(vec!["/etc".into()])).is_err());
+
+        let read_only = SandboxPolicy::new_read_only_policy();
+        assert!(
+            teammate_sandbox_policy(&read_only, cwd, Some(SandboxMode::WorkspaceWrite), None)
+                .is_err()
+        );
+
+        // Teammates cannot ask, so a leader who is asked about every
+        // untrusted command only lends them read access.
+        assert_eq!(
+            teammate_leader_sandbox(&leader, AskForApproval::OnRequest),
+            leader
+        );
+        assert_eq!(
+            teammate_leader_sandbox(
+                &SandboxPolicy::DangerFullAccess,
+                AskForApproval::UnlessTrusted
+            ),
+            SandboxPolicy::new_read_only_policy()
+        );
+    }
+
+    #[test]
+    fn teammate_cwd_stays_inside_the_leader_roots() {
+        let repo = tempfile::TempDir::new().unwrap();
+        let extra = tempfile::TempDir::new().unwrap();
+        let outside = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(repo.path().join("crates")).unwrap();
+        let leader = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![AbsolutePathBuf::from_absolute_path(extra.path()).unwrap()],
+            read_only_access: ReadOnlyAccess::FullAccess,
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+        };
+
+        assert_eq!(
+            teammate_cwd(&leader, repo.path(), Path::new("crates")),
+            Ok(repo.path().join("crates"))
+        );
+        assert_eq!(
+            teammate_cwd(&leader, repo.path(), extra.path()),
+            Ok(extra.path().to_path_buf())
+        );
+        assert!(teammate_cwd(&leader, repo.path(), Path::new("missing")).is_err());
+        assert!(teammate_cwd(&leader, repo.path(), outside.path()).is_err());
+        assert!(teammate_cwd(&leader, &repo.path().join("crates"), Path::new("..")).is_err());
+    }
+
+    #[test]
+    fn artifact_sources_stay_inside_the_writable_roots() {
+        let repo = tempfile::TempDir::new().unwrap();
+        let outside = tempfile::TempDir::new().unwrap();
+        std::fs::write(repo.path().join("notes.md"), "").unwrap();
+        std::fs::write(outside.path().join("secret"), "").unwrap();
+        let policy = SandboxPolicy::new_read_only_policy();
+        let repo_path = std::fs::canonicalize(repo.path()).unwrap();
+
+        assert_eq!(
+            artifact_source(&policy, repo.path(), Path::new("notes.md")),
+            Ok(repo_path.join("notes.md"))
+        );
+        assert!(artifact_source(&policy, repo.path(), &outside.path().join("secret")).is_err());
+        assert!(artifact_source(&policy, repo.path(), Path::new("missing")).is_err());
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(outside.path().join("secret"), repo.path().join("link"))
+                .unwrap();
+            assert!(artifact_source(&policy, repo.path(), Path::new("link")).is_err());
         }
-        Err(e) => err_text(format!("failed to get tasks: {e}")),
     }
-}
 
-async fn handle_request_shutdown(arguments: String) -> Result<ToolOutput, FunctionCallError> {
-    let args: TeamNameArgs = parse_arguments(&arguments)?;
-    let mgr = TeamManager::new(default_teams_root());
-    let inbox = Inbox::new(mgr.inboxes_dir(&args.team_name));
-    let msg = InboxMessage {
-        from: "self".to_string(),
-        content: "Requesting shutdown — work complete.".to_string(),
-        timestamp: chrono::Utc::now().to_rfc3339(),
-        read: false,
-    };
-    match inbox.send_message("leader", msg).await {
-        Ok(()) => ok_text(
-            json!({
-                "status": "shutdown_requested",
-            })
-            .to_string(),
-        ),
-        Err(e) => err_text(format!("failed to request shutdown: {e}")),
+    #[test]
+    fn writable_paths_contain_the_teammate_cwd() {
+        let repo = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(repo.path().join("docs")).unwrap();
+        std::fs::write(repo.path().join("README.md"), "").unwrap();
+        let path = |p: &str| AbsolutePathBuf::from_absolute_path(repo.path().join(p)).unwrap();
+        let docs = vec![path("README.md"), path("docs")];
+
+        // The leader's cwd is not writable, so the teammate starts in docs/.
+        assert_eq!(
+            writable_paths_cwd(repo.path(), None, &docs),
+            Ok(Some(repo.path().join("docs")))
+        );
+        assert_eq!(
+            writable_paths_cwd(&repo.path().join("docs"), None, &docs),
+            Ok(None)
+        );
+        assert!(writable_paths_cwd(repo.path(), Some(repo.path().to_path_buf()), &docs).is_err());
+        assert!(writable_paths_cwd(repo.path(), None, &[path("README.md")]).is_err());
+    }
+
+    #[test]
+    fn idempotency_scope_is_per_tool() {
+        let arguments = r#"{"team_name":"t","title":"x","idempotency_key":"k1"}"#;
+
+        assert_eq!(
+            idempotency_scope("assign_task", arguments),
+            Some(("t".to_string(), "assign_task:k1".to_string()))
+        );
+        assert_eq!(idempotency_scope("get_task_status", arguments), None);
+        assert_eq!(
+            idempotency_scope("assign_task", r#"{"team_name":"t","title":"x"}"#),
+            None
+        );
+    }
+
+    #[test]
+    fn recent_turns_groups_messages_by_user_input() {
+        let items = [
+            agent("stray"),
+            user("first"),
+            agent("one"),
+            user("second"),
+            agent("two"),
+            agent("three"),
+        ];
+
+        assert_eq!(
+            recent_turns(&items, 1),
+            vec![TeammateTurn {
+                input: "second".to_string(),
+                messages: vec!["two".to_string(), "three".to_string()],
+            }]
+        );
+        assert_eq!(recent_turns(&items, 5).len(), 2);
+    }
+
+    #[test]
+    fn tool_results_keep_their_json_schema() {
+        let leader = ThreadId::from_string("00000000-0000-0000-0000-000000000001").unwrap();
+        let writer = ThreadId::from_string("00000000-0000-0000-0000-000000000002").unwrap();
+        let task = TeamTaskInfo {
+            id: "task-1".to_string(),
+            title: "Outline".to_string(),
+            description: None,
+            status: TeamTaskStatus::InProgress,
+            assigned_to: Some("writer".to_string()),
+            depends_on: Vec::new(),
+            result: None,
+            created_at: None,
+        };
+        let results = [
+            (
+                "create_team",
+                serde_json::to_string(&CreateTeamResult {
+                    status: ToolStatus::Created,
+                    team_name: "docs".to_string(),
+                    leader_thread_id: leader,
+                    description: None,
+                    metadata: BTreeMap::from([("owner".to_string(), "ana".to_string())]),
+                    display_mode: TeamDisplayMode::SplitPane,
+                    delegation_mode: false,
+                    work_stealing: true,
+                    dry_run: false,
+                    parent_team: None,
+                }),
+            ),
+            (
+                "spawn_teammate",
+                serde_json::to_string(&SpawnTeammateResult {
+                    status: ToolStatus::Queued,
+                    teammate: "writer".to_string(),
+                    thread_id: writer,
+                    team_name: "docs".to_string(),
+                    branch: None,
+                    worktree: None,
+                    cwd: Some("/repo/docs".to_string()),
+                }),
+            ),
+            (
+                "spawn_teammate (dry run)",
+                serde_json::to_string(&DryRunResult {
+                    status: ToolStatus::Planned,
+                    team_name: "docs".to_string(),
+                    plan: DryRunPlan {
+                        members: vec![PlannedMember {
+                            name: "writer".to_string(),
+                            role: Some("docs".to_string()),
+                            prompt: "Write the guide.".to_string(),
+                            budget: Some(MemberBudget {
+                                max_tokens: Some(20_000),
+                                max_turns: None,
+                            }),
+                            cwd: None,
+                            worktree: true,
+                            mcp: None,
+                            a2a: None,
+                            ssh: None,
+                        }],
+                        tasks: vec![PlannedTask {
+                            id: "planned-task-1".to_string(),
+                            title: "Outline".to_string(),
+                            assigned_to: Some("writer".to_string()),
+                            depends_on: Vec::new(),
+                        }],
+                        shutdowns: Vec::new(),
+                    },
+                    estimate: DryRunEstimate {
+                        members: 1,
+                        tasks: 1,
+                        max_tokens: Some(20_000),
+                    },
+                }),
+            ),
+            (
+                "wait_for_teammates",
+                serde_json::to_string(&WaitForTeammatesResult {
+                    condition: WaitCondition::AllIdle,
+                    condition_met: false,
+                    timed_out: true,
+                    members: vec![
+                        TeammateStatus {
+                            name: "writer".to_string(),
+                            thread_id: writer,
+                            role: Some("docs".to_string()),
+                            status: AgentStatus::Completed(Some("Done".to_string())),
+                        },
+                        TeammateStatus {
+                            name: "tester".to_string(),
+                            thread_id: leader,
+                            role: None,
+                            status: AgentStatus::Running,
+                        },
+                    ],
+                }),
+            ),
+            (
+                "get_task_status",
+                serde_json::to_string(&TasksResult {
+                    tasks: vec![task.clone()],
+                }),
+            ),
+            (
+                "get_teammate",
+                serde_json::to_string(&TeammateResult {
+                    name: "writer".to_string(),
+                    thread_id: writer,
+                    role: None,
+                    status: AgentStatus::Errored("boom".to_string()),
+                    tasks: vec![TeammateTask {
+                        id: task.id.clone(),
+                        title: task.title.clone(),
+                        status: task.status,
+                    }],
+                    unread_messages: 2,
+                    last_activity_at: None,
+                }),
+            ),
+            (
+                "merge_teammate_work",
+                serde_json::to_string(&MergeTeammateWorkResult::Conflicts {
+                    branch: "codex/docs/writer".to_string(),
+                    conflicts: vec!["README.md".to_string()],
+                }),
+            ),
+            (
+                "accept_task",
+                serde_json::to_string(&AcceptTaskResult::NoTasksAvailable),
+            ),
+            (
+                "request_shutdown",
+                serde_json::to_string(&StatusResult {
+                    status: ToolStatus::ShutdownRequested,
+                }),
+            ),
+            (
+                "fetch_artifact",
+                serde_json::to_string(&FetchArtifactResult {
+                    artifact: ArtifactInfo {
+                        name: "logo.png".to_string(),
+                        path: PathBuf::from("/teams/docs/artifacts/logo.png"),
+                        size: 3,
+                    },
+                    binary: true,
+                    content: None,
+                    truncated: false,
+                }),
+            ),
+        ];
+        let rendered: Vec<String> = results
+            .into_iter()
+            .map(|(tool, json)| format!("{tool}: {}", json.unwrap()))
+            .collect();
+
+        insta::assert_snapshot!(rendered.join("\n"));
+    }
+
+    #[test]
+    fn wait_conditions() {
+        let running = AgentStatus::Running;
+        let done = AgentStatus::Completed(None);
+        let mixed = [running.clone(), done.clone()];
+        let finished = [done, AgentStatus::Errored("boom".to_string())];
+
+        let cases = [
+            (WaitCondition::AnyCompleted, &mixed[..], false, true),
+            (WaitCondition::AllCompleted, &mixed[..], false, false),
+            (WaitCondition::AllCompleted, &finished[..], false, true),
+            (WaitCondition::AllIdle, &finished[..], false, true),
+            (WaitCondition::AllIdle, &finished[..], true, false),
+            (WaitCondition::AllIdle, &[running][..], false, false),
+        ];
+        for (condition, statuses, has_open_tasks, expected) in cases {
+            assert_eq!(
+                wait_condition_met(condition, statuses, has_open_tasks),
+                expected,
+                "{condition:?} with {statuses:?}, open tasks: {has_open_tasks}"
+            );
+        }
+    }
+
+    #[test]
+    fn assignments_name_members_and_existing_tasks() {
+        let team = TeamConfig {
+            name: "docs".to_string(),
+            description: None,
+            metadata: HashMap::new(),
+            created_at: String::new(),
+            leader_thread_id: ThreadId::new(),
+            members: vec![MemberConfig {
+                name: "writer".to_string(),
+                ..Default::default()
+            }],
+            display_mode: TeamDisplayMode::default(),
+            delegation_mode: false,
+            work_stealing: false,
+            dry_run: false,
+            parent_team: None,
+            sub_teams: Vec::new(),
+        };
+        let tasks = vec![TeamTaskInfo {
+            id: "task-1".to_string(),
+            title: "Outline".to_string(),
+            description: None,
+            status: TeamTaskStatus::Pending,
+            assigned_to: None,
+            depends_on: Vec::new(),
+            result: None,
+            created_at: None,
+        }];
+        let plan = DryRunPlan {
+            tasks: vec![PlannedTask {
+                id: "planned-task-1".to_string(),
+                title: "Review".to_string(),
+                assigned_to: None,
+                depends_on: Vec::new(),
+            }],
+            ..Default::default()
+        };
+        let check = |plan: Option<&DryRunPlan>, assigned_to: &str, depends_on: &str| {
+            let args = AssignTaskArgs {
+                team_name: "docs".to_string(),
+                title: "Draft".to_string(),
+                description: None,
+                assigned_to: Some(assigned_to.to_string()),
+                depends_on: vec![depends_on.to_string()],
+            };
+            check_assignment(&team, &tasks, plan, &args).map_err(|err| err.code)
+        };
+
+        assert_eq!(check(None, "writer", "task-1"), Ok(()));
+        assert_eq!(
+            check(None, "editor", "task-1"),
+            Err(TeamErrorCode::MemberNotFound)
+        );
+        assert_eq!(
+            check(None, "writer", "task-2"),
+            Err(TeamErrorCode::TaskNotFound)
+        );
+        assert_eq!(
+            check(None, "writer", "planned-task-1"),
+            Err(TeamErrorCode::TaskNotFound)
+        );
+        assert_eq!(check(Some(&plan), "writer", "planned-task-1"), Ok(()));
     }
 }
@@ -272,6 +272,12 @@ impl ToolRegistryBuilder {
     //     }
     // }
 
+    /// Drop every tool, and its handler, whose name `keep` rejects.
+    pub fn retain(&mut self, keep: impl Fn(&str) -> bool) {
+        self.specs.retain(|tool| keep(tool.spec.name()));
+        self.handlers.retain(|name, _| keep(name));
+    }
+
     pub fn build(self) -> (Vec<ConfiguredToolSpec>, ToolRegistry) {
         let registry = ToolRegistry::new(self.handlers);
         (self.specs, registry)
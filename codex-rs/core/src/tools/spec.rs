@@ -3,6 +3,7 @@ use crate::client_common::tools::FreeformToolFormat;
 use crate::client_common::tools::ResponsesApiTool;
 use crate::client_common::tools::ToolSpec;
 use crate::config::AgentRoleConfig;
+use crate::config::types::TeamsConfig;
 use crate::features::Feature;
 use crate::features::Features;
 use crate::mcp_connection_manager::ToolInfo;
@@ -22,6 +23,8 @@ use codex_protocol::models::VIEW_IMAGE_TOOL_NAME;
 use codex_protocol::openai_models::ApplyPatchToolType;
 use codex_protocol::openai_models::ConfigShellToolType;
 use codex_protocol::openai_models::ModelInfo;
+use codex_protocol::protocol::SessionSource;
+use codex_protocol::protocol::SubAgentSource;
 use serde::Deserialize;
 use serde::Serialize;
 use serde_json::Value as JsonValue;
@@ -42,10 +45,49 @@ pub(crate) struct ToolsConfig {
     pub js_repl_enabled: bool,
     pub js_repl_tools_only: bool,
     pub collab_tools: bool,
+    pub team_tools: Option<TeamToolsScope>,
+    /// Limits the non-team tools offered, e.g. by a teammate's role.
+    pub allowed_tools: Option<Vec<String>>,
     pub collaboration_modes_tools: bool,
     pub experimental_supported_tools: Vec<String>,
 }
 
+/// Which Agent Teams tools a session is offered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TeamToolsScope {
+    /// Tools for running a team: creating it, spawning teammates, assigning
+    /// and tracking work.
+    Leader,
+    /// Tools for a spawned teammate: accepting and completing tasks.
+    Teammate,
+    /// A teammate allowed to lead its own sub-team (`teams.max_depth`): both
+    /// sets of tools.
+    SubTeamLeader,
+}
+
+impl TeamToolsScope {
+    /// Scope for a session started from `session_source`. `None` when the
+    /// teams feature is off or the session is an internal sub-agent such as
+    /// a review. A session joining a team (`teams.join`) is a teammate.
+    pub(crate) fn for_session(teams: &TeamsConfig, session_source: &SessionSource) -> Option<Self> {
+        if !teams.enabled {
+            return None;
+        }
+        match session_source {
+            // A sub-team led by a thread at `depth` sits at level `depth + 1`.
+            SessionSource::SubAgent(SubAgentSource::ThreadSpawn { depth, .. })
+                if usize::try_from(*depth).unwrap_or(usize::MAX) < teams.max_depth =>
+            {
+                Some(Self::SubTeamLeader)
+            }
+            SessionSource::SubAgent(SubAgentSource::ThreadSpawn { .. }) => Some(Self::Teammate),
+            SessionSource::SubAgent(_) => None,
+            _ if teams.join.is_some() => Some(Self::Teammate),
+            _ => Some(Self::Leader),
+        }
+    }
+}
+
 pub(crate) struct ToolsConfigParams<'a> {
     pub(crate) model_info: &'a ModelInfo,
     pub(crate) features: &'a Features,
@@ -103,6 +145,8 @@ impl ToolsConfig {
             js_repl_enabled: include_js_repl,
             js_repl_tools_only: include_js_repl_tools_only,
             collab_tools: include_collab_tools,
+            team_tools: None,
+            allowed_tools: None,
             collaboration_modes_tools: include_collaboration_modes_tools,
             experimental_supported_tools: model_info.experimental_supported_tools.clone(),
         }
@@ -112,6 +156,16 @@ impl ToolsConfig {
         self.agent_roles = agent_roles;
         self
     }
+
+    pub fn with_team_tools(mut self, team_tools: Option<TeamToolsScope>) -> Self {
+        self.team_tools = team_tools;
+        self
+    }
+
+    pub fn with_allowed_tools(mut self, allowed_tools: Option<Vec<String>>) -> Self {
+        self.allowed_tools = allowed_tools;
+        self
+    }
 }
 
 /// Generic JSON‑Schema subset needed for our tool definitions
@@ -778,6 +832,67 @@ pub(crate) fn create_team_tool() -> ToolSpec {
                 description: Some("Short description of the team's goal.".to_string()),
             },
         ),
+        (
+            "metadata".to_string(),
+            JsonSchema::Object {
+                properties: BTreeMap::new(),
+                required: None,
+                additional_properties: Some(
+                    JsonSchema::String {
+                        description: Some(
+                            "Arbitrary string metadata to store with the team (e.g. ticket IDs)."
+                                .to_string(),
+                        ),
+                    }
+                    .into(),
+                ),
+            },
+        ),
+        (
+            "display_mode".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "How teammate activity is shown to the user: \"inline\" (default), \"split-pane\", \"quiet\", or \"compact\" (status line only)."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "delegation_mode".to_string(),
+            JsonSchema::Boolean {
+                description: Some(
+                    "When true, the runtime assigns unblocked tasks to idle teammates and sends you a single report when the work is done. Errored teammates are restarted only as their max_restarts allow. Defaults to false."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "supervise".to_string(),
+            JsonSchema::Boolean {
+                description: Some(
+                    "When true, you are woken with a summary whenever a teammate finishes or errors out, a task is completed, or a team message arrives, so you do not need to call wait_for_teammates. Defaults to false."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "work_stealing".to_string(),
+            JsonSchema::Boolean {
+                description: Some(
+                    "When true, a teammate that finishes its turn without an unfinished task is automatically handed the next available task. Defaults to false."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "dry_run".to_string(),
+            JsonSchema::Boolean {
+                description: Some(
+                    "When true, no agents are started: spawn_teammate, assign_task and shutdown_teammate check their arguments and return the plan so far, with the teammates, tasks and an upper bound on tokens, for the user to review. Cannot be combined with delegation_mode. Defaults to false."
+                        .to_string(),
+                ),
+            },
+        ),
     ]);
 
     ToolSpec::Function(ResponsesApiTool {
@@ -794,6 +909,20 @@ pub(crate) fn create_team_tool() -> ToolSpec {
     })
 }
 
+/// Optional key that makes a retried mutating team tool call return the
+/// original result instead of running again.
+fn idempotency_key_property() -> (String, JsonSchema) {
+    (
+        "idempotency_key".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Optional unique key for this call. Retrying with the same key returns the original result instead of repeating the action."
+                    .to_string(),
+            ),
+        },
+    )
+}
+
 pub(crate) fn create_spawn_teammate_tool() -> ToolSpec {
     let properties = BTreeMap::from([
         (
@@ -814,7 +943,7 @@ pub(crate) fn create_spawn_teammate_tool() -> ToolSpec {
             "role".to_string(),
             JsonSchema::String {
                 description: Some(
-                    "Focus area or role description for the teammate.".to_string(),
+                    "Focus area or role description for the teammate. A role defined in ~/.codex/roles/{role}.toml also sets the teammate's instructions, model, and allowed tools.".to_string(),
                 ),
             },
         ),
@@ -827,12 +956,140 @@ pub(crate) fn create_spawn_teammate_tool() -> ToolSpec {
                 ),
             },
         ),
+        (
+            "sandbox_mode".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Optional sandbox for this teammate: \"read-only\", \"workspace-write\", or \"danger-full-access\". It cannot be broader than your own sandbox, and is read-only when your approval policy is untrusted. Defaults to your sandbox. Teammates never ask for approval."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "writable_roots".to_string(),
+            JsonSchema::Array {
+                items: Box::new(JsonSchema::String { description: None }),
+                description: Some(
+                    "Optional extra writable directories for a workspace-write teammate, replacing yours. Each must already be writable by you."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "writable_paths".to_string(),
+            JsonSchema::Array {
+                items: Box::new(JsonSchema::String { description: None }),
+                description: Some(
+                    "Optional files and directories that are the only places this teammate can write, e.g. [\"docs\"] for a docs writer. Each must already be writable by you. The teammate works inside one of them: its cwd if that is inside, otherwise the first directory listed. Cannot be combined with writable_roots or isolation."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "max_tokens".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Optional token budget. The teammate is shut down after the turn that takes it past this many tokens."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "max_turns".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Optional turn budget. The teammate is shut down after finishing this many turns."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "timeout_minutes".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Optional wall-clock limit. The teammate is shut down after running this many minutes and its in-progress tasks go back to pending."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "max_restarts".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Optional number of automatic restarts, with backoff, if the teammate errors out. Overrides the team default; 0 disables restarts."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "isolation".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Optional \"worktree\" to run the teammate in its own git worktree on a new branch created from your HEAD, so its edits do not collide with other teammates'. The branch is returned so you can merge it later."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "cwd".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Optional working directory for the teammate, e.g. one sub-crate or repository of a monorepo. Must be inside your cwd or one of your writable roots; relative paths resolve against your cwd. Defaults to your cwd. With kind \"ssh\": the directory on the host instead."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "kind".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Optional \"mcp\" to add an external agent reached through an MCP tool, \"a2a\" for a remote agent speaking the A2A protocol, or \"ssh\" for a Codex agent running on another machine, instead of spawning a Codex agent here. The prompt, your messages and tasks assigned to it are delivered by calling the tool, sending the agent an A2A message or running `codex exec` on the host; its reply lands in your inbox and the task updates it returns are applied to the task list. Sandbox, budget, timeout, restart and isolation options do not apply, nor does cwd except with \"ssh\"."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "server".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "With kind \"mcp\": the MCP server the external agent is reached through."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "tool".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "With kind \"mcp\": the tool of that server to call.".to_string(),
+                ),
+            },
+        ),
+        (
+            "endpoint".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "With kind \"a2a\": the URL of the agent's A2A JSON-RPC endpoint, as its agent card gives it. Must be one the user allowed in their config."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "host".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "With kind \"ssh\": the host to run the teammate on, as given to ssh, e.g. \"builder@build-box\". It needs key-based login and codex installed, and must be one the user allowed in their config."
+                        .to_string(),
+                ),
+            },
+        ),
+        idempotency_key_property(),
     ]);
 
     ToolSpec::Function(ResponsesApiTool {
         name: "spawn_teammate".to_string(),
         description:
-            "Spawn a new teammate agent in the team. The teammate gets its own context, inbox, and can accept tasks."
+            "Spawn a new teammate agent in the team. The teammate gets its own context, inbox, and can accept tasks. When the team is at its concurrency limit the teammate is queued and starts once another teammate finishes."
                 .to_string(),
         strict: false,
         parameters: JsonSchema::Object {
@@ -861,6 +1118,14 @@ pub(crate) fn create_assign_task_tool() -> ToolSpec {
                 description: Some("Short description of the task.".to_string()),
             },
         ),
+        (
+            "description".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Details of the work, shown to the teammate that accepts the task.".to_string(),
+                ),
+            },
+        ),
         (
             "assigned_to".to_string(),
             JsonSchema::String {
@@ -878,22 +1143,623 @@ pub(crate) fn create_assign_task_tool() -> ToolSpec {
                 ),
             },
         ),
+        idempotency_key_property(),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "assign_task".to_string(),
+        description: "Create a new task in the shared task list, optionally assigned to a specific teammate."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["team_name".to_string(), "title".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub(crate) fn create_reassign_task_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "team_name".to_string(),
+            JsonSchema::String {
+                description: Some("Name of the team.".to_string()),
+            },
+        ),
+        (
+            "task_id".to_string(),
+            JsonSchema::String {
+                description: Some("ID of the task to reassign.".to_string()),
+            },
+        ),
+        (
+            "assigned_to".to_string(),
+            JsonSchema::String {
+                description: Some("Name of the teammate that takes over the task.".to_string()),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "reassign_task".to_string(),
+        description: "Hand an unfinished task to another teammate. The new assignee and the previous one are told through their inboxes."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec![
+                "team_name".to_string(),
+                "task_id".to_string(),
+                "assigned_to".to_string(),
+            ]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub(crate) fn create_send_team_message_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "team_name".to_string(),
+            JsonSchema::String {
+                description: Some("Name of the team.".to_string()),
+            },
+        ),
+        (
+            "to".to_string(),
+            JsonSchema::String {
+                description: Some("Name of the recipient teammate.".to_string()),
+            },
+        ),
+        (
+            "content".to_string(),
+            JsonSchema::String {
+                description: Some("Message content.".to_string()),
+            },
+        ),
+        idempotency_key_property(),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "send_team_message".to_string(),
+        description: "Send a message to a specific teammate's inbox.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec![
+                "team_name".to_string(),
+                "to".to_string(),
+                "content".to_string(),
+            ]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub(crate) fn create_broadcast_team_message_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "team_name".to_string(),
+            JsonSchema::String {
+                description: Some("Name of the team.".to_string()),
+            },
+        ),
+        (
+            "content".to_string(),
+            JsonSchema::String {
+                description: Some("Message to broadcast to all teammates.".to_string()),
+            },
+        ),
+        idempotency_key_property(),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "broadcast_team_message".to_string(),
+        description: "Send a message to all teammates' inboxes.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["team_name".to_string(), "content".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub(crate) fn create_wait_for_teammates_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "team_name".to_string(),
+            JsonSchema::String {
+                description: Some("Name of the team.".to_string()),
+            },
+        ),
+        (
+            "names".to_string(),
+            JsonSchema::Array {
+                items: Box::new(JsonSchema::String { description: None }),
+                description: Some(
+                    "Teammate names to wait for. Empty array waits for all.".to_string(),
+                ),
+            },
+        ),
+        (
+            "timeout_secs".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Maximum time to wait in seconds. Defaults to 60, capped at 600.".to_string(),
+                ),
+            },
+        ),
+        (
+            "condition".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "When to return: \"any_completed\" (a teammate finished its turn), \"all_completed\" (default; every teammate finished), or \"all_idle\" (every teammate finished and none holds an unfinished task)."
+                        .to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "wait_for_teammates".to_string(),
+        description: "Block until the teammates satisfy the condition or the timeout elapses, then return their statuses."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["team_name".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub(crate) fn create_get_task_status_tool() -> ToolSpec {
+    let properties = BTreeMap::from([(
+        "team_name".to_string(),
+        JsonSchema::String {
+            description: Some("Name of the team.".to_string()),
+        },
+    )]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "get_task_status".to_string(),
+        description: "Get the status of all tasks in the team's shared task list.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["team_name".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub(crate) fn create_get_teammate_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "team_name".to_string(),
+            JsonSchema::String {
+                description: Some("Name of the team.".to_string()),
+            },
+        ),
+        (
+            "name".to_string(),
+            JsonSchema::String {
+                description: Some("Teammate name to inspect.".to_string()),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "get_teammate".to_string(),
+        description: "Get detailed info for one teammate: role, agent status, assigned tasks, unread inbox count, and last activity time."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["team_name".to_string(), "name".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub(crate) fn create_team_summary_tool() -> ToolSpec {
+    let properties = BTreeMap::from([(
+        "team_name".to_string(),
+        JsonSchema::String {
+            description: Some("Name of the team.".to_string()),
+        },
+    )]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "team_summary".to_string(),
+        description: "Get a short summary of the team: teammate statuses, task progress, blockers, and the latest messages sent to you. Use this to refresh your picture of the team in one call.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["team_name".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub(crate) fn create_get_team_usage_tool() -> ToolSpec {
+    let properties = BTreeMap::from([(
+        "team_name".to_string(),
+        JsonSchema::String {
+            description: Some("Name of the team.".to_string()),
+        },
+    )]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "get_team_usage".to_string(),
+        description: "Get the tokens used by you and each teammate, the team total, and how much of the team's token budget (teams.max_team_tokens) is left.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["team_name".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub(crate) fn create_get_teammate_output_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "team_name".to_string(),
+            JsonSchema::String {
+                description: Some("Name of the team.".to_string()),
+            },
+        ),
+        (
+            "name".to_string(),
+            JsonSchema::String {
+                description: Some("Teammate name.".to_string()),
+            },
+        ),
+        (
+            "turns".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Also return this many of the teammate's most recent turns (input plus assistant messages). Defaults to 0."
+                        .to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "get_teammate_output".to_string(),
+        description: "Fetch a teammate's latest assistant message, and optionally its recent turns, without messaging it.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["team_name".to_string(), "name".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub(crate) fn create_shutdown_teammate_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "team_name".to_string(),
+            JsonSchema::String {
+                description: Some("Name of the team.".to_string()),
+            },
+        ),
+        (
+            "name".to_string(),
+            JsonSchema::String {
+                description: Some("Teammate name to shut down.".to_string()),
+            },
+        ),
+        idempotency_key_property(),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "shutdown_teammate".to_string(),
+        description: "Shut down a specific teammate and release their resources.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["team_name".to_string(), "name".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub(crate) fn create_restart_teammate_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "team_name".to_string(),
+            JsonSchema::String {
+                description: Some("Name of the team.".to_string()),
+            },
+        ),
+        (
+            "name".to_string(),
+            JsonSchema::String {
+                description: Some("Teammate name to restart.".to_string()),
+            },
+        ),
+        idempotency_key_property(),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "restart_teammate".to_string(),
+        description: "Restart a teammate on a fresh thread, keeping its name, role, prompt, and assigned tasks. Use this when a teammate has errored or is stuck.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["team_name".to_string(), "name".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub(crate) fn create_merge_teammate_work_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "team_name".to_string(),
+            JsonSchema::String {
+                description: Some("Name of the team.".to_string()),
+            },
+        ),
+        (
+            "name".to_string(),
+            JsonSchema::String {
+                description: Some("Teammate whose branch to merge.".to_string()),
+            },
+        ),
+        (
+            "strategy".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Optional \"merge\" (default) for a merge commit, or \"rebase\" to rebase the teammate's branch onto your HEAD and fast-forward."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "task_id".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Optional task to record the merge result on. Defaults to every task assigned to the teammate."
+                        .to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "merge_teammate_work".to_string(),
+        description: "Merge the branch of a teammate spawned with isolation \"worktree\" into your current branch. Uncommitted work in the teammate's worktree is committed first. On conflicts the merge is aborted and the conflicting files are returned.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["team_name".to_string(), "name".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub(crate) fn create_cleanup_team_tool() -> ToolSpec {
+    let properties = BTreeMap::from([(
+        "team_name".to_string(),
+        JsonSchema::String {
+            description: Some("Name of the team to clean up.".to_string()),
+        },
+    )]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "cleanup_team".to_string(),
+        description:
+            "Clean up all team resources: shut down all teammates, remove config, inboxes, and task list."
+                .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["team_name".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub(crate) fn create_list_teams_tool() -> ToolSpec {
+    ToolSpec::Function(ResponsesApiTool {
+        name: "list_teams".to_string(),
+        description: "List all teams on disk with their description, metadata, and member count."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties: BTreeMap::new(),
+            required: None,
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub(crate) fn create_rename_team_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "team_name".to_string(),
+            JsonSchema::String {
+                description: Some("Current name of the team.".to_string()),
+            },
+        ),
+        (
+            "new_name".to_string(),
+            JsonSchema::String {
+                description: Some("New name for the team.".to_string()),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "rename_team".to_string(),
+        description: "Rename a team, keeping its members, inboxes, and task list.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["team_name".to_string(), "new_name".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub(crate) fn create_handoff_leadership_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "team_name".to_string(),
+            JsonSchema::String {
+                description: Some("Name of the team.".to_string()),
+            },
+        ),
+        (
+            "new_leader".to_string(),
+            JsonSchema::String {
+                description: Some("Name of the teammate that becomes the new leader.".to_string()),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "handoff_leadership".to_string(),
+        description: "Hand leadership of the team to one of its teammates. The new leader takes over the leader tools and every teammate is notified."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["team_name".to_string(), "new_leader".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+// --- Teammate tools ---
+
+pub(crate) fn create_accept_task_tool() -> ToolSpec {
+    let properties = BTreeMap::from([(
+        "team_name".to_string(),
+        JsonSchema::String {
+            description: Some("Name of the team.".to_string()),
+        },
+    )]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "accept_task".to_string(),
+        description:
+            "Accept the next available (pending, unblocked) task from the shared task list."
+                .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["team_name".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub(crate) fn create_complete_task_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "team_name".to_string(),
+            JsonSchema::String {
+                description: Some("Name of the team.".to_string()),
+            },
+        ),
+        (
+            "task_id".to_string(),
+            JsonSchema::String {
+                description: Some("ID of the task to mark as completed.".to_string()),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "complete_task".to_string(),
+        description: "Mark a task assigned to you as completed in the shared task list. Reports the ids of the tasks that were waiting on it and are now ready."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["team_name".to_string(), "task_id".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub(crate) fn create_get_tasks_tool() -> ToolSpec {
+    let properties = BTreeMap::from([(
+        "team_name".to_string(),
+        JsonSchema::String {
+            description: Some("Name of the team.".to_string()),
+        },
+    )]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "get_tasks".to_string(),
+        description: "Get the list of all tasks in the team's shared task list.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["team_name".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub(crate) fn create_read_scratchpad_tool() -> ToolSpec {
+    let properties = BTreeMap::from([(
+        "team_name".to_string(),
+        JsonSchema::String {
+            description: Some("Name of the team.".to_string()),
+        },
+    )]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "read_scratchpad".to_string(),
+        description: "Read the team's shared scratchpad, a markdown document of notes, decisions, and findings that every teammate and the leader can edit.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["team_name".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub(crate) fn create_append_scratchpad_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "team_name".to_string(),
+            JsonSchema::String {
+                description: Some("Name of the team.".to_string()),
+            },
+        ),
+        (
+            "content".to_string(),
+            JsonSchema::String {
+                description: Some("Markdown note to add, signed with your name.".to_string()),
+            },
+        ),
     ]);
 
     ToolSpec::Function(ResponsesApiTool {
-        name: "assign_task".to_string(),
-        description: "Create a new task in the shared task list, optionally assigned to a specific teammate."
-            .to_string(),
+        name: "append_scratchpad".to_string(),
+        description: "Append a note to the end of the team's shared scratchpad. Returns the updated scratchpad.".to_string(),
         strict: false,
         parameters: JsonSchema::Object {
             properties,
-            required: Some(vec!["team_name".to_string(), "title".to_string()]),
+            required: Some(vec!["team_name".to_string(), "content".to_string()]),
             additional_properties: Some(false.into()),
         },
     })
 }
 
-pub(crate) fn create_send_team_message_tool() -> ToolSpec {
+pub(crate) fn create_replace_section_tool() -> ToolSpec {
     let properties = BTreeMap::from([
         (
             "team_name".to_string(),
@@ -902,28 +1768,28 @@ pub(crate) fn create_send_team_message_tool() -> ToolSpec {
             },
         ),
         (
-            "to".to_string(),
+            "heading".to_string(),
             JsonSchema::String {
-                description: Some("Name of the recipient teammate.".to_string()),
+                description: Some("Section heading, without the leading \"## \".".to_string()),
             },
         ),
         (
             "content".to_string(),
             JsonSchema::String {
-                description: Some("Message content.".to_string()),
+                description: Some("New markdown body of the section.".to_string()),
             },
         ),
     ]);
 
     ToolSpec::Function(ResponsesApiTool {
-        name: "send_team_message".to_string(),
-        description: "Send a message to a specific teammate's inbox.".to_string(),
+        name: "replace_section".to_string(),
+        description: "Replace one `## ` section of the team's shared scratchpad, creating it at the end if it does not exist. Returns the updated scratchpad.".to_string(),
         strict: false,
         parameters: JsonSchema::Object {
             properties,
             required: Some(vec![
                 "team_name".to_string(),
-                "to".to_string(),
+                "heading".to_string(),
                 "content".to_string(),
             ]),
             additional_properties: Some(false.into()),
@@ -931,7 +1797,7 @@ pub(crate) fn create_send_team_message_tool() -> ToolSpec {
     })
 }
 
-pub(crate) fn create_broadcast_team_message_tool() -> ToolSpec {
+pub(crate) fn create_reserve_files_tool() -> ToolSpec {
     let properties = BTreeMap::from([
         (
             "team_name".to_string(),
@@ -940,26 +1806,30 @@ pub(crate) fn create_broadcast_team_message_tool() -> ToolSpec {
             },
         ),
         (
-            "content".to_string(),
-            JsonSchema::String {
-                description: Some("Message to broadcast to all teammates.".to_string()),
+            "paths".to_string(),
+            JsonSchema::Array {
+                items: Box::new(JsonSchema::String { description: None }),
+                description: Some(
+                    "Files or directories you are about to edit. Relative paths resolve against your cwd."
+                        .to_string(),
+                ),
             },
         ),
     ]);
 
     ToolSpec::Function(ResponsesApiTool {
-        name: "broadcast_team_message".to_string(),
-        description: "Send a message to all teammates' inboxes.".to_string(),
+        name: "reserve_files".to_string(),
+        description: "Reserve files or directories before editing them so no other teammate edits them at the same time. Fails, naming the holder, if another teammate already reserved any of them or anything inside them; then coordinate with that teammate instead of editing. Release them with release_files when you are done.".to_string(),
         strict: false,
         parameters: JsonSchema::Object {
             properties,
-            required: Some(vec!["team_name".to_string(), "content".to_string()]),
+            required: Some(vec!["team_name".to_string(), "paths".to_string()]),
             additional_properties: Some(false.into()),
         },
     })
 }
 
-pub(crate) fn create_wait_for_teammates_tool() -> ToolSpec {
+pub(crate) fn create_release_files_tool() -> ToolSpec {
     let properties = BTreeMap::from([
         (
             "team_name".to_string(),
@@ -968,25 +1838,19 @@ pub(crate) fn create_wait_for_teammates_tool() -> ToolSpec {
             },
         ),
         (
-            "names".to_string(),
+            "paths".to_string(),
             JsonSchema::Array {
                 items: Box::new(JsonSchema::String { description: None }),
                 description: Some(
-                    "Teammate names to wait for. Empty array waits for all.".to_string(),
+                    "Optional paths to release. Defaults to all of your reservations.".to_string(),
                 ),
             },
         ),
-        (
-            "timeout_ms".to_string(),
-            JsonSchema::Number {
-                description: Some("Timeout in milliseconds. Defaults to 60000.".to_string()),
-            },
-        ),
     ]);
 
     ToolSpec::Function(ResponsesApiTool {
-        name: "wait_for_teammates".to_string(),
-        description: "Wait for specified teammates to complete their current tasks.".to_string(),
+        name: "release_files".to_string(),
+        description: "Release file reservations made with reserve_files so other teammates can edit those files.".to_string(),
         strict: false,
         parameters: JsonSchema::Object {
             properties,
@@ -996,27 +1860,53 @@ pub(crate) fn create_wait_for_teammates_tool() -> ToolSpec {
     })
 }
 
-pub(crate) fn create_get_task_status_tool() -> ToolSpec {
-    let properties = BTreeMap::from([(
-        "team_name".to_string(),
-        JsonSchema::String {
-            description: Some("Name of the team.".to_string()),
-        },
-    )]);
+pub(crate) fn create_store_artifact_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "team_name".to_string(),
+            JsonSchema::String {
+                description: Some("Name of the team.".to_string()),
+            },
+        ),
+        (
+            "name".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Artifact name, e.g. \"api-report.md\": letters, digits, '-', '_' and '.'. Storing under an existing name replaces it."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "content".to_string(),
+            JsonSchema::String {
+                description: Some("Content to store. Provide either content or path.".to_string()),
+            },
+        ),
+        (
+            "path".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "File to copy into the store, relative to your cwd. It must be inside your cwd or writable roots. Provide either content or path."
+                        .to_string(),
+                ),
+            },
+        ),
+    ]);
 
     ToolSpec::Function(ResponsesApiTool {
-        name: "get_task_status".to_string(),
-        description: "Get the status of all tasks in the team's shared task list.".to_string(),
+        name: "store_artifact".to_string(),
+        description: "Store a large output (report, patch, dataset) in the team's shared artifact store so teammates can fetch it with fetch_artifact. Mention the artifact name in messages instead of pasting the content.".to_string(),
         strict: false,
         parameters: JsonSchema::Object {
             properties,
-            required: Some(vec!["team_name".to_string()]),
+            required: Some(vec!["team_name".to_string(), "name".to_string()]),
             additional_properties: Some(false.into()),
         },
     })
 }
 
-pub(crate) fn create_shutdown_teammate_tool() -> ToolSpec {
+pub(crate) fn create_fetch_artifact_tool() -> ToolSpec {
     let properties = BTreeMap::from([
         (
             "team_name".to_string(),
@@ -1027,14 +1917,14 @@ pub(crate) fn create_shutdown_teammate_tool() -> ToolSpec {
         (
             "name".to_string(),
             JsonSchema::String {
-                description: Some("Teammate name to shut down.".to_string()),
+                description: Some("Name of the artifact to fetch.".to_string()),
             },
         ),
     ]);
 
     ToolSpec::Function(ResponsesApiTool {
-        name: "shutdown_teammate".to_string(),
-        description: "Shut down a specific teammate and release their resources.".to_string(),
+        name: "fetch_artifact".to_string(),
+        description: "Fetch an artifact stored with store_artifact. Returns its path and size plus its content if it is text; content over 100 KB is truncated, so read the rest from the path.".to_string(),
         strict: false,
         parameters: JsonSchema::Object {
             properties,
@@ -1044,19 +1934,45 @@ pub(crate) fn create_shutdown_teammate_tool() -> ToolSpec {
     })
 }
 
-pub(crate) fn create_cleanup_team_tool() -> ToolSpec {
-    let properties = BTreeMap::from([(
-        "team_name".to_string(),
-        JsonSchema::String {
-            description: Some("Name of the team to clean up.".to_string()),
-        },
-    )]);
+pub(crate) fn create_review_patch_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "team_name".to_string(),
+            JsonSchema::String {
+                description: Some("Name of the team.".to_string()),
+            },
+        ),
+        (
+            "patch_id".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Patch to review. Omit to list all submitted patches with their status."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "decision".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Optional \"approve\" or \"reject\". Omit to see the diff and whether it applies cleanly to your checkout."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "comment".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Optional feedback sent to the author with your decision.".to_string(),
+                ),
+            },
+        ),
+    ]);
 
     ToolSpec::Function(ResponsesApiTool {
-        name: "cleanup_team".to_string(),
-        description:
-            "Clean up all team resources: shut down all teammates, remove config, inboxes, and task list."
-                .to_string(),
+        name: "review_patch".to_string(),
+        description: "Review patches teammates submitted with submit_patch: list them, inspect one, or approve or reject it. The author is notified of your decision. Approved patches are applied with apply_patch_from_teammate.".to_string(),
         strict: false,
         parameters: JsonSchema::Object {
             properties,
@@ -1066,31 +1982,35 @@ pub(crate) fn create_cleanup_team_tool() -> ToolSpec {
     })
 }
 
-// --- Teammate tools ---
-
-pub(crate) fn create_accept_task_tool() -> ToolSpec {
-    let properties = BTreeMap::from([(
-        "team_name".to_string(),
-        JsonSchema::String {
-            description: Some("Name of the team.".to_string()),
-        },
-    )]);
+pub(crate) fn create_apply_patch_from_teammate_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "team_name".to_string(),
+            JsonSchema::String {
+                description: Some("Name of the team.".to_string()),
+            },
+        ),
+        (
+            "patch_id".to_string(),
+            JsonSchema::String {
+                description: Some("Approved patch to apply.".to_string()),
+            },
+        ),
+    ]);
 
     ToolSpec::Function(ResponsesApiTool {
-        name: "accept_task".to_string(),
-        description:
-            "Accept the next available (pending, unblocked) task from the shared task list."
-                .to_string(),
+        name: "apply_patch_from_teammate".to_string(),
+        description: "Apply a teammate's patch, approved with review_patch, to your checkout. Fails without changing anything if the patch does not apply cleanly. The changes are left uncommitted; when commit_trailers is returned, end the commit message with those lines.".to_string(),
         strict: false,
         parameters: JsonSchema::Object {
             properties,
-            required: Some(vec!["team_name".to_string()]),
+            required: Some(vec!["team_name".to_string(), "patch_id".to_string()]),
             additional_properties: Some(false.into()),
         },
     })
 }
 
-pub(crate) fn create_complete_task_tool() -> ToolSpec {
+pub(crate) fn create_submit_patch_tool() -> ToolSpec {
     let properties = BTreeMap::from([
         (
             "team_name".to_string(),
@@ -1099,26 +2019,35 @@ pub(crate) fn create_complete_task_tool() -> ToolSpec {
             },
         ),
         (
-            "task_id".to_string(),
+            "description".to_string(),
             JsonSchema::String {
-                description: Some("ID of the task to mark as completed.".to_string()),
+                description: Some("What the patch changes and why.".to_string()),
+            },
+        ),
+        (
+            "patch".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Optional unified diff to submit. Defaults to your changes in your cwd's repository, untracked files included."
+                        .to_string(),
+                ),
             },
         ),
     ]);
 
     ToolSpec::Function(ResponsesApiTool {
-        name: "complete_task".to_string(),
-        description: "Mark a task as completed in the shared task list.".to_string(),
+        name: "submit_patch".to_string(),
+        description: "Submit your changes as a patch for the leader to review instead of writing to the leader's checkout. The leader is notified and approves or rejects it; you get the decision in your inbox.".to_string(),
         strict: false,
         parameters: JsonSchema::Object {
             properties,
-            required: Some(vec!["team_name".to_string(), "task_id".to_string()]),
+            required: Some(vec!["team_name".to_string(), "description".to_string()]),
             additional_properties: Some(false.into()),
         },
     })
 }
 
-pub(crate) fn create_get_tasks_tool() -> ToolSpec {
+pub(crate) fn create_request_shutdown_tool() -> ToolSpec {
     let properties = BTreeMap::from([(
         "team_name".to_string(),
         JsonSchema::String {
@@ -1127,8 +2056,10 @@ pub(crate) fn create_get_tasks_tool() -> ToolSpec {
     )]);
 
     ToolSpec::Function(ResponsesApiTool {
-        name: "get_tasks".to_string(),
-        description: "Get the list of all tasks in the team's shared task list.".to_string(),
+        name: "request_shutdown".to_string(),
+        description:
+            "Request to be shut down. Sends a message to the leader that this teammate has finished."
+                .to_string(),
         strict: false,
         parameters: JsonSchema::Object {
             properties,
@@ -1138,23 +2069,41 @@ pub(crate) fn create_get_tasks_tool() -> ToolSpec {
     })
 }
 
-pub(crate) fn create_request_shutdown_tool() -> ToolSpec {
-    let properties = BTreeMap::from([(
-        "team_name".to_string(),
-        JsonSchema::String {
-            description: Some("Name of the team.".to_string()),
-        },
-    )]);
+pub(crate) fn create_escalate_to_user_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "team_name".to_string(),
+            JsonSchema::String {
+                description: Some("Name of the team.".to_string()),
+            },
+        ),
+        (
+            "question".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "The decision you need from the user, with the context needed to make it."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "options".to_string(),
+            JsonSchema::Array {
+                items: Box::new(JsonSchema::String { description: None }),
+                description: Some(
+                    "Optional suggested answers for the user to pick from.".to_string(),
+                ),
+            },
+        ),
+    ]);
 
     ToolSpec::Function(ResponsesApiTool {
-        name: "request_shutdown".to_string(),
-        description:
-            "Request to be shut down. Sends a message to the leader that this teammate has finished."
-                .to_string(),
+        name: "escalate_to_user".to_string(),
+        description: "Ask the user a question only they can answer, such as a product or risk decision. The question is shown to the user in the leader's session; their answer arrives later as a message from \"user\" in your inbox. Keep working on anything that does not depend on the answer.".to_string(),
         strict: false,
         parameters: JsonSchema::Object {
             properties,
-            required: Some(vec!["team_name".to_string()]),
+            required: Some(vec!["team_name".to_string(), "question".to_string()]),
             additional_properties: Some(false.into()),
         },
     })
@@ -1976,44 +2925,109 @@ pub(crate) fn build_specs(
         builder.register_handler("resume_agent", multi_agent_handler.clone());
         builder.register_handler("wait", multi_agent_handler.clone());
         builder.register_handler("close_agent", multi_agent_handler);
+    }
+
+    // A teammate's role can limit it to some tools; team tools stay available
+    // so it can still take part in the team.
+    let is_allowed = |name: &str| {
+        config
+            .allowed_tools
+            .as_ref()
+            .is_none_or(|allowed| allowed.iter().any(|tool| tool == name))
+    };
+    builder.retain(&is_allowed);
 
-        // Agent Teams tools — leader tools
+    if matches!(
+        config.team_tools,
+        Some(TeamToolsScope::Leader | TeamToolsScope::SubTeamLeader)
+    ) {
         let team_handler = Arc::new(TeamHandler);
         builder.push_spec(create_team_tool());
         builder.push_spec(create_spawn_teammate_tool());
         builder.push_spec(create_assign_task_tool());
+        builder.push_spec(create_reassign_task_tool());
         builder.push_spec(create_send_team_message_tool());
         builder.push_spec(create_broadcast_team_message_tool());
         builder.push_spec(create_wait_for_teammates_tool());
         builder.push_spec(create_get_task_status_tool());
+        builder.push_spec(create_get_teammate_tool());
+        builder.push_spec(create_get_teammate_output_tool());
+        builder.push_spec(create_team_summary_tool());
+        builder.push_spec(create_get_team_usage_tool());
         builder.push_spec(create_shutdown_teammate_tool());
+        builder.push_spec(create_restart_teammate_tool());
+        builder.push_spec(create_merge_teammate_work_tool());
+        builder.push_spec(create_review_patch_tool());
+        builder.push_spec(create_apply_patch_from_teammate_tool());
         builder.push_spec(create_cleanup_team_tool());
+        builder.push_spec(create_list_teams_tool());
+        builder.push_spec(create_rename_team_tool());
+        builder.push_spec(create_handoff_leadership_tool());
         builder.register_handler("create_team", team_handler.clone());
         builder.register_handler("spawn_teammate", team_handler.clone());
         builder.register_handler("assign_task", team_handler.clone());
+        builder.register_handler("reassign_task", team_handler.clone());
         builder.register_handler("send_team_message", team_handler.clone());
         builder.register_handler("broadcast_team_message", team_handler.clone());
         builder.register_handler("wait_for_teammates", team_handler.clone());
         builder.register_handler("get_task_status", team_handler.clone());
+        builder.register_handler("get_teammate", team_handler.clone());
+        builder.register_handler("get_teammate_output", team_handler.clone());
+        builder.register_handler("team_summary", team_handler.clone());
+        builder.register_handler("get_team_usage", team_handler.clone());
         builder.register_handler("shutdown_teammate", team_handler.clone());
+        builder.register_handler("restart_teammate", team_handler.clone());
+        builder.register_handler("merge_teammate_work", team_handler.clone());
+        builder.register_handler("review_patch", team_handler.clone());
+        builder.register_handler("apply_patch_from_teammate", team_handler.clone());
         builder.register_handler("cleanup_team", team_handler.clone());
+        builder.register_handler("list_teams", team_handler.clone());
+        builder.register_handler("rename_team", team_handler.clone());
+        builder.register_handler("handoff_leadership", team_handler);
+    }
 
-        // Agent Teams tools — teammate tools
+    if matches!(
+        config.team_tools,
+        Some(TeamToolsScope::Teammate | TeamToolsScope::SubTeamLeader)
+    ) {
+        let team_handler = Arc::new(TeamHandler);
         builder.push_spec(create_accept_task_tool());
         builder.push_spec(create_complete_task_tool());
         builder.push_spec(create_get_tasks_tool());
         builder.push_spec(create_request_shutdown_tool());
+        builder.push_spec(create_submit_patch_tool());
+        builder.push_spec(create_escalate_to_user_tool());
         builder.register_handler("accept_task", team_handler.clone());
         builder.register_handler("complete_task", team_handler.clone());
         builder.register_handler("get_tasks", team_handler.clone());
-        builder.register_handler("request_shutdown", team_handler);
+        builder.register_handler("request_shutdown", team_handler.clone());
+        builder.register_handler("submit_patch", team_handler.clone());
+        builder.register_handler("escalate_to_user", team_handler);
+    }
+
+    if config.team_tools.is_some() {
+        let team_handler = Arc::new(TeamHandler);
+        builder.push_spec(create_read_scratchpad_tool());
+        builder.push_spec(create_append_scratchpad_tool());
+        builder.push_spec(create_replace_section_tool());
+        builder.push_spec(create_reserve_files_tool());
+        builder.push_spec(create_release_files_tool());
+        builder.push_spec(create_store_artifact_tool());
+        builder.push_spec(create_fetch_artifact_tool());
+        builder.register_handler("read_scratchpad", team_handler.clone());
+        builder.register_handler("append_scratchpad", team_handler.clone());
+        builder.register_handler("replace_section", team_handler.clone());
+        builder.register_handler("reserve_files", team_handler.clone());
+        builder.register_handler("release_files", team_handler.clone());
+        builder.register_handler("store_artifact", team_handler.clone());
+        builder.register_handler("fetch_artifact", team_handler);
     }
 
     if let Some(mcp_tools) = mcp_tools {
         let mut entries: Vec<(String, rmcp::model::Tool)> = mcp_tools.into_iter().collect();
         entries.sort_by(|a, b| a.0.cmp(&b.0));
 
-        for (name, tool) in entries.into_iter() {
+        for (name, tool) in entries.into_iter().filter(|(name, _)| is_allowed(name)) {
             match mcp_tool_to_openai_tool(name.clone(), tool.clone()) {
                 Ok(converted_tool) => {
                     builder.push_spec(ToolSpec::Function(converted_tool));
@@ -2027,7 +3041,7 @@ pub(crate) fn build_specs(
     }
 
     if !dynamic_tools.is_empty() {
-        for tool in dynamic_tools {
+        for tool in dynamic_tools.iter().filter(|tool| is_allowed(&tool.name)) {
             match dynamic_tool_to_openai_tool(tool) {
                 Ok(converted_tool) => {
                     builder.push_spec(ToolSpec::Function(converted_tool));
@@ -2050,6 +3064,7 @@ pub(crate) fn build_specs(
 mod tests {
     use crate::client_common::tools::FreeformTool;
     use crate::config::test_config;
+    use crate::config::types::TeamJoin;
     use crate::models_manager::manager::ModelsManager;
     use crate::models_manager::model_info::with_config_overrides;
     use crate::tools::registry::ConfiguredToolSpec;
@@ -2285,6 +3300,121 @@ mod tests {
         );
     }
 
+    #[test]
+    fn allowed_tools_keep_team_tools() {
+        let config = test_config();
+        let model_info =
+            ModelsManager::construct_model_info_offline_for_tests("gpt-5-codex", &config);
+        let features = Features::with_defaults();
+        let teams = TeamsConfig {
+            enabled: true,
+            ..TeamsConfig::default()
+        };
+        let teammate_source = SessionSource::SubAgent(SubAgentSource::ThreadSpawn {
+            parent_thread_id: codex_protocol::ThreadId::new(),
+            depth: 1,
+        });
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+        })
+        .with_team_tools(TeamToolsScope::for_session(&teams, &teammate_source))
+        .with_allowed_tools(Some(vec!["view_image".to_string()]));
+        let (tools, _) = build_specs(&tools_config, None, None, &[]).build();
+        let names: Vec<&str> = tools.iter().map(|t| tool_name(&t.spec)).collect();
+
+        assert_eq!(
+            names,
+            vec![
+                "view_image",
+                "accept_task",
+                "complete_task",
+                "get_tasks",
+                "request_shutdown",
+                "submit_patch",
+                "escalate_to_user",
+                "read_scratchpad",
+                "append_scratchpad",
+                "replace_section",
+                "reserve_files",
+                "release_files",
+                "store_artifact",
+                "fetch_artifact"
+            ]
+        );
+    }
+
+    #[test]
+    fn team_tools_follow_session_scope() {
+        let config = test_config();
+        let model_info =
+            ModelsManager::construct_model_info_offline_for_tests("gpt-5-codex", &config);
+        let features = Features::with_defaults();
+        let teams = TeamsConfig {
+            enabled: true,
+            ..TeamsConfig::default()
+        };
+        let tool_names = |source: &SessionSource| {
+            let tools_config = ToolsConfig::new(&ToolsConfigParams {
+                model_info: &model_info,
+                features: &features,
+                web_search_mode: Some(WebSearchMode::Cached),
+            })
+            .with_team_tools(TeamToolsScope::for_session(&teams, source));
+            let (tools, _) = build_specs(&tools_config, None, None, &[]).build();
+            tools
+                .iter()
+                .map(|t| tool_name(&t.spec).to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let leader = tool_names(&SessionSource::Cli);
+        assert!(leader.iter().any(|name| name == "spawn_teammate"));
+        assert!(!leader.iter().any(|name| name == "accept_task"));
+        assert!(leader.iter().any(|name| name == "read_scratchpad"));
+
+        let teammate_source = SessionSource::SubAgent(SubAgentSource::ThreadSpawn {
+            parent_thread_id: codex_protocol::ThreadId::new(),
+            depth: 1,
+        });
+        let teammate = tool_names(&teammate_source);
+        assert!(teammate.iter().any(|name| name == "accept_task"));
+        assert!(!teammate.iter().any(|name| name == "spawn_teammate"));
+
+        let review = tool_names(&SessionSource::SubAgent(SubAgentSource::Review));
+        assert!(
+            !review
+                .iter()
+                .any(|name| name == "accept_task" || name == "spawn_teammate")
+        );
+
+        assert_eq!(
+            TeamToolsScope::for_session(&TeamsConfig::default(), &SessionSource::Cli),
+            None
+        );
+        let nested = TeamsConfig {
+            max_depth: 2,
+            ..teams.clone()
+        };
+        assert_eq!(
+            TeamToolsScope::for_session(&nested, &teammate_source),
+            Some(TeamToolsScope::SubTeamLeader)
+        );
+        let joining = TeamsConfig {
+            join: Some(TeamJoin {
+                team: "zeta".to_string(),
+                name: "alice".to_string(),
+                role: None,
+            }),
+            ..teams
+        };
+        assert_eq!(
+            TeamToolsScope::for_session(&joining, &SessionSource::Cli),
+            Some(TeamToolsScope::Teammate)
+        );
+    }
+
     #[test]
     fn request_user_input_requires_collaboration_modes_feature() {
         let config = test_config();
@@ -127,6 +127,20 @@ impl TurnDiffTracker {
         }
     }
 
+    /// Every path the turn's patches touched, including the original path of
+    /// moved files, sorted.
+    pub fn touched_paths(&self) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = self
+            .baseline_file_info
+            .values()
+            .map(|info| info.path.clone())
+            .chain(self.temp_name_to_current_path.values().cloned())
+            .collect();
+        paths.sort();
+        paths.dedup();
+        paths
+    }
+
     fn get_path_for_internal(&self, internal: &str) -> Option<PathBuf> {
         self.temp_name_to_current_path
             .get(internal)
@@ -645,6 +659,7 @@ index {left_oid}..{right_oid}
             )
         };
         assert_eq!(out, expected);
+        assert_eq!(acc.touched_paths(), vec![dest, src]);
     }
 
     #[test]
@@ -132,6 +132,13 @@ pub enum Command {
 
     /// Run a code review against the current repository.
     Review(ReviewArgs),
+
+    /// Create a team from a plan file and lead it until its tasks are done.
+    RunTeam(TeamRunArgs),
+
+    /// Bring back a single teammate whose thread stopped and run it until
+    /// its turn ends.
+    ResumeMember(TeamResumeMemberArgs),
 }
 
 #[derive(Args, Debug)]
@@ -260,6 +267,43 @@ pub struct ReviewArgs {
     pub prompt: Option<String>,
 }
 
+#[derive(Parser, Debug)]
+pub struct TeamRunArgs {
+    /// Plan file describing the team's members and tasks (TOML, or YAML
+    /// with a `.yaml`/`.yml` extension).
+    #[arg(value_name = "PLAN", value_hint = clap::ValueHint::FilePath)]
+    pub plan: PathBuf,
+
+    /// Exit with an error unless every task of the team is completed when
+    /// the run ends, including a run stopped with Ctrl-C.
+    #[arg(long = "require-all-tasks", default_value_t = false)]
+    pub require_all_tasks: bool,
+
+    /// Write a JSON summary of the run (status and final task list) to FILE.
+    #[arg(long = "summary-file", value_name = "FILE", value_hint = clap::ValueHint::FilePath)]
+    pub summary_file: Option<PathBuf>,
+}
+
+#[derive(Parser, Debug)]
+pub struct TeamResumeMemberArgs {
+    /// Name of the team.
+    #[arg(value_name = "TEAM")]
+    pub team_name: String,
+
+    /// Name of the teammate to resume.
+    #[arg(value_name = "NAME")]
+    pub name: String,
+
+    /// Start a fresh thread with the teammate's stored prompt even if its
+    /// rollout is still on disk.
+    #[arg(long = "fresh", default_value_t = false)]
+    pub fresh: bool,
+
+    /// Resume the teammate even while a session is leading its team.
+    #[arg(long = "force", default_value_t = false)]
+    pub force: bool,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
 #[value(rename_all = "kebab-case")]
 pub enum Color {
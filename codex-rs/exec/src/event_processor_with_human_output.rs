@@ -808,7 +808,21 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::TeamTaskCreated(_)
             | EventMsg::TeamTaskUpdated(_)
             | EventMsg::TeamMessageSent(_)
-            | EventMsg::TeamCleanup(_) => {}
+            | EventMsg::TeamCleanup(_)
+            | EventMsg::TeamRenamed(_)
+            | EventMsg::TeamLeaderChanged(_)
+            | EventMsg::TeamMemberStatusChanged(_)
+            | EventMsg::TeamBudgetExhausted(_)
+            | EventMsg::TeamMemberRestarted(_)
+            | EventMsg::TeamEditConflict(_)
+            | EventMsg::TeamDiffSummary(_)
+            | EventMsg::TeamMemberOutput(_)
+            | EventMsg::TeamMemberTokenUsage(_)
+            | EventMsg::TeamProgress(_)
+            | EventMsg::TeamMemberLivenessLost(_)
+            | EventMsg::TeamError(_)
+            | EventMsg::TeamTaskUnblocked(_)
+            | EventMsg::TeamEscalationRequested(_) => {}
         }
         CodexStatus::Running
     }
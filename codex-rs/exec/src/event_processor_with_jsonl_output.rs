@@ -26,6 +26,11 @@ use crate::exec_events::McpToolCallStatus;
 use crate::exec_events::PatchApplyStatus;
 use crate::exec_events::PatchChangeKind;
 use crate::exec_events::ReasoningItem;
+use crate::exec_events::TeamEvent;
+use crate::exec_events::TeamMemberSnapshot;
+use crate::exec_events::TeamSnapshotEvent;
+use crate::exec_events::TeamTaskSnapshot;
+use crate::exec_events::TeamTaskStatus;
 use crate::exec_events::ThreadErrorEvent;
 use crate::exec_events::ThreadEvent;
 use crate::exec_events::ThreadItem;
@@ -49,6 +54,7 @@ use codex_core::protocol::CollabCloseBeginEvent;
 use codex_core::protocol::CollabCloseEndEvent;
 use codex_core::protocol::CollabWaitingBeginEvent;
 use codex_core::protocol::CollabWaitingEndEvent;
+use codex_core::protocol::TeamTaskStatus as CoreTeamTaskStatus;
 use codex_protocol::models::WebSearchAction;
 use codex_protocol::plan_tool::StepStatus;
 use codex_protocol::plan_tool::UpdatePlanArgs;
@@ -70,6 +76,8 @@ pub struct EventProcessorWithJsonOutput {
     running_collab_tool_calls: HashMap<String, RunningCollabToolCall>,
     running_web_search_calls: HashMap<String, String>,
     last_critical_error: Option<ThreadErrorEvent>,
+    // Members and tasks of each team, keyed by team name, for snapshots.
+    team_snapshots: HashMap<String, TeamSnapshotEvent>,
 }
 
 #[derive(Debug, Clone)]
@@ -113,6 +121,7 @@ impl EventProcessorWithJsonOutput {
             running_collab_tool_calls: HashMap::new(),
             running_web_search_calls: HashMap::new(),
             last_critical_error: None,
+            team_snapshots: HashMap::new(),
         }
     }
 
@@ -187,6 +196,27 @@ impl EventProcessorWithJsonOutput {
                 vec![ThreadEvent::Error(ThreadErrorEvent { message })]
             }
             protocol::EventMsg::PlanUpdate(ev) => self.handle_plan_update(ev),
+            protocol::EventMsg::TeamCreated(_)
+            | protocol::EventMsg::TeamMemberAdded(_)
+            | protocol::EventMsg::TeamMemberRemoved(_)
+            | protocol::EventMsg::TeamTaskCreated(_)
+            | protocol::EventMsg::TeamTaskUpdated(_)
+            | protocol::EventMsg::TeamMessageSent(_)
+            | protocol::EventMsg::TeamCleanup(_)
+            | protocol::EventMsg::TeamRenamed(_)
+            | protocol::EventMsg::TeamLeaderChanged(_)
+            | protocol::EventMsg::TeamMemberStatusChanged(_)
+            | protocol::EventMsg::TeamBudgetExhausted(_)
+            | protocol::EventMsg::TeamMemberRestarted(_)
+            | protocol::EventMsg::TeamEditConflict(_)
+            | protocol::EventMsg::TeamDiffSummary(_)
+            | protocol::EventMsg::TeamMemberOutput(_)
+            | protocol::EventMsg::TeamMemberTokenUsage(_)
+            | protocol::EventMsg::TeamProgress(_)
+            | protocol::EventMsg::TeamMemberLivenessLost(_)
+            | protocol::EventMsg::TeamError(_)
+            | protocol::EventMsg::TeamTaskUnblocked(_)
+            | protocol::EventMsg::TeamEscalationRequested(_) => self.handle_team_event(&event.msg),
             _ => Vec::new(),
         }
     }
@@ -744,6 +774,104 @@ impl EventProcessorWithJsonOutput {
         vec![ThreadEvent::ItemStarted(ItemStartedEvent { item })]
     }
 
+    fn handle_team_event(&mut self, msg: &protocol::EventMsg) -> Vec<ThreadEvent> {
+        let event = match serde_json::to_value(msg) {
+            Ok(event) => event,
+            Err(e) => {
+                error!("Failed to serialize team event: {e:?}");
+                return Vec::new();
+            }
+        };
+        let team_name = event
+            .get("team_name")
+            .or_else(|| event.get("new_name"))
+            .and_then(JsonValue::as_str)
+            .map(str::to_string);
+        let mut events = vec![ThreadEvent::TeamEvent(TeamEvent { team_name, event })];
+        if let Some(snapshot) = self.update_team_snapshot(msg) {
+            events.push(ThreadEvent::TeamSnapshot(snapshot));
+        }
+        events
+    }
+
+    /// Apply a membership or task event to the team's snapshot, returning
+    /// the updated snapshot, or `None` if the event changed neither.
+    fn update_team_snapshot(&mut self, msg: &protocol::EventMsg) -> Option<TeamSnapshotEvent> {
+        let team_name = match msg {
+            protocol::EventMsg::TeamCreated(ev) => {
+                self.team_snapshots.insert(
+                    ev.team_name.clone(),
+                    TeamSnapshotEvent {
+                        team_name: ev.team_name.clone(),
+                        ..Default::default()
+                    },
+                );
+                &ev.team_name
+            }
+            protocol::EventMsg::TeamMemberAdded(ev)
+            | protocol::EventMsg::TeamMemberStatusChanged(ev) => {
+                let snapshot = self.team_snapshot(&ev.team_name);
+                let member = TeamMemberSnapshot {
+                    name: ev.member.name.clone(),
+                    thread_id: ev.member.thread_id.to_string(),
+                    role: ev.member.role.clone(),
+                    status: CollabAgentState::from(ev.member.status.clone()).status,
+                };
+                match snapshot.members.iter_mut().find(|m| m.name == member.name) {
+                    Some(existing) => *existing = member,
+                    None => snapshot.members.push(member),
+                }
+                &ev.team_name
+            }
+            protocol::EventMsg::TeamMemberRemoved(ev) => {
+                self.team_snapshot(&ev.team_name)
+                    .members
+                    .retain(|m| m.name != ev.member.name);
+                &ev.team_name
+            }
+            protocol::EventMsg::TeamTaskCreated(ev) | protocol::EventMsg::TeamTaskUpdated(ev) => {
+                let snapshot = self.team_snapshot(&ev.team_name);
+                let task = TeamTaskSnapshot {
+                    id: ev.task.id.clone(),
+                    title: ev.task.title.clone(),
+                    status: match ev.task.status {
+                        CoreTeamTaskStatus::Pending => TeamTaskStatus::Pending,
+                        CoreTeamTaskStatus::InProgress => TeamTaskStatus::InProgress,
+                        CoreTeamTaskStatus::Completed => TeamTaskStatus::Completed,
+                    },
+                    assigned_to: ev.task.assigned_to.clone(),
+                    depends_on: ev.task.depends_on.clone(),
+                };
+                match snapshot.tasks.iter_mut().find(|t| t.id == task.id) {
+                    Some(existing) => *existing = task,
+                    None => snapshot.tasks.push(task),
+                }
+                &ev.team_name
+            }
+            protocol::EventMsg::TeamRenamed(ev) => {
+                let mut snapshot = self.team_snapshots.remove(&ev.old_name)?;
+                snapshot.team_name = ev.new_name.clone();
+                self.team_snapshots.insert(ev.new_name.clone(), snapshot);
+                &ev.new_name
+            }
+            protocol::EventMsg::TeamCleanup(ev) => {
+                self.team_snapshots.remove(&ev.team_name);
+                return None;
+            }
+            _ => return None,
+        };
+        self.team_snapshots.get(team_name).cloned()
+    }
+
+    fn team_snapshot(&mut self, team_name: &str) -> &mut TeamSnapshotEvent {
+        self.team_snapshots
+            .entry(team_name.to_string())
+            .or_insert_with(|| TeamSnapshotEvent {
+                team_name: team_name.to_string(),
+                ..Default::default()
+            })
+    }
+
     fn handle_task_started(&mut self, _: &protocol::TurnStartedEvent) -> Vec<ThreadEvent> {
         self.last_critical_error = None;
         vec![ThreadEvent::TurnStarted(TurnStartedEvent {})]
@@ -34,6 +34,12 @@ pub enum ThreadEvent {
     /// Represents an unrecoverable error emitted directly by the event stream.
     #[serde(rename = "error")]
     Error(ThreadErrorEvent),
+    /// Emitted for every event of an agent team, as sent by the team's leader.
+    #[serde(rename = "team.event")]
+    TeamEvent(TeamEvent),
+    /// Emitted after a team's members or tasks change, with all of them.
+    #[serde(rename = "team.snapshot")]
+    TeamSnapshot(TeamSnapshotEvent),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
@@ -309,3 +315,48 @@ pub struct TodoItem {
 pub struct TodoListItem {
     pub items: Vec<TodoItem>,
 }
+
+/// An agent team event. `event` is the protocol event, tagged with its
+/// `type`, e.g. `{"type": "team_task_created", "team_name": ..., "task": ...}`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct TeamEvent {
+    /// Team the event is about; absent for errors of calls that named none.
+    pub team_name: Option<String>,
+    pub event: JsonValue,
+}
+
+/// Current members and tasks of an agent team.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS)]
+pub struct TeamSnapshotEvent {
+    pub team_name: String,
+    pub members: Vec<TeamMemberSnapshot>,
+    pub tasks: Vec<TeamTaskSnapshot>,
+}
+
+/// A teammate in a [`TeamSnapshotEvent`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct TeamMemberSnapshot {
+    pub name: String,
+    pub thread_id: String,
+    pub role: Option<String>,
+    pub status: CollabAgentStatus,
+}
+
+/// A task in a [`TeamSnapshotEvent`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct TeamTaskSnapshot {
+    pub id: String,
+    pub title: String,
+    pub status: TeamTaskStatus,
+    pub assigned_to: Option<String>,
+    pub depends_on: Vec<String>,
+}
+
+/// The status of a team task.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum TeamTaskStatus {
+    Pending,
+    InProgress,
+    Completed,
+}
@@ -10,9 +10,12 @@ mod event_processor_with_human_output;
 pub mod event_processor_with_jsonl_output;
 pub mod exec_events;
 
+use anyhow::Context;
 pub use cli::Cli;
 pub use cli::Command;
 pub use cli::ReviewArgs;
+pub use cli::TeamResumeMemberArgs;
+pub use cli::TeamRunArgs;
 use codex_cloud_requirements::cloud_requirements_loader;
 use codex_core::AuthManager;
 use codex_core::LMSTUDIO_OSS_PROVIDER_ID;
@@ -39,6 +42,16 @@ use codex_core::protocol::Op;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
 use codex_core::protocol::SessionSource;
+use codex_core::protocol::TeamPlan;
+use codex_core::teams::default_tasks_root;
+use codex_core::teams::default_teams_root;
+use codex_core::teams::orchestrator::TeamRun;
+use codex_core::teams::orchestrator::TeamRunReport;
+use codex_core::teams::orchestrator::TeamRunStatus;
+use codex_core::teams::plan::load_plan;
+use codex_core::teams::resume::MemberResume;
+use codex_core::teams::task_list::TaskList;
+use codex_core::teams::team_manager::TeamManager;
 use codex_protocol::approvals::ElicitationAction;
 use codex_protocol::config_types::SandboxMode;
 use codex_protocol::user_input::UserInput;
@@ -79,6 +92,9 @@ enum InitialOperation {
     Review {
         review_request: ReviewRequest,
     },
+    TeamPlan {
+        plan: TeamPlan,
+    },
 }
 
 #[derive(Clone)]
@@ -268,12 +284,28 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         additional_writable_roots: add_dir,
     };
 
-    let config = ConfigBuilder::default()
+    let mut config = ConfigBuilder::default()
         .cli_overrides(cli_kv_overrides)
         .harness_overrides(overrides)
         .cloud_requirements(cloud_requirements)
         .build()
         .await?;
+    // Running a team plan or a teammate implies agent teams, whatever
+    // config.toml says.
+    if matches!(
+        command,
+        Some(ExecCommand::RunTeam(_) | ExecCommand::ResumeMember(_))
+    ) {
+        config.teams.enabled = true;
+    }
+    // A resumed teammate runs with the role, sandbox and cwd it was spawned
+    // with.
+    let member_resume = match &command {
+        Some(ExecCommand::ResumeMember(args)) => {
+            Some(prepare_member_resume(&mut config, args).await?)
+        }
+        _ => None,
+    };
 
     #[allow(clippy::print_stderr)]
     match check_execpolicy_for_warnings(&config.config_layer_stack).await {
@@ -397,15 +429,62 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         } else {
             thread_manager.start_thread(config.clone()).await?
         }
+    } else if let Some(path) = member_resume
+        .as_ref()
+        .and_then(|resume| resume.rollout_path.clone())
+    {
+        thread_manager
+            .resume_thread_from_rollout(config.clone(), path, auth_manager.clone())
+            .await?
     } else {
         thread_manager.start_thread(config.clone()).await?
     };
+    if let Some(resume) = &member_resume {
+        resume
+            .record(&TeamManager::new(default_teams_root()), primary_thread_id)
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to update teammate '{}' of team '{}'",
+                    resume.member.name, resume.team_name
+                )
+            })?;
+    }
+    // What to do with the result of a team run once it ends.
+    let mut team_run_args: Option<TeamRunArgs> = None;
     let (initial_operation, prompt_summary) = match (command, prompt, images) {
         (Some(ExecCommand::Review(review_cli)), _, _) => {
             let review_request = build_review_request(review_cli)?;
             let summary = codex_core::review_prompts::user_facing_hint(&review_request.target);
             (InitialOperation::Review { review_request }, summary)
         }
+        (Some(ExecCommand::RunTeam(run_args)), _, _) => {
+            let plan = load_plan(&run_args.plan)
+                .await
+                .with_context(|| format!("failed to load team plan {}", run_args.plan.display()))?;
+            let summary = format!("run team '{}' from {}", plan.name, run_args.plan.display());
+            team_run_args = Some(run_args);
+            (InitialOperation::TeamPlan { plan }, summary)
+        }
+        (Some(ExecCommand::ResumeMember(args)), _, _) => {
+            let prompt_text = member_resume
+                .map(|resume| resume.prompt)
+                .unwrap_or_default();
+            let summary = format!(
+                "resume teammate '{}' of team '{}'",
+                args.name, args.team_name
+            );
+            (
+                InitialOperation::UserTurn {
+                    items: vec![UserInput::Text {
+                        text: prompt_text,
+                        text_elements: Vec::new(),
+                    }],
+                    output_schema: None,
+                },
+                summary,
+            )
+        }
         (Some(ExecCommand::Resume(args)), root_prompt, imgs) => {
             let prompt_arg = args
                 .prompt
@@ -470,13 +549,23 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     let attached_threads = Arc::new(Mutex::new(HashSet::from([primary_thread_id])));
     spawn_thread_listener(primary_thread_id, thread.clone(), tx.clone());
 
+    let mut team_run = match &initial_operation {
+        InitialOperation::TeamPlan { plan } => Some(TeamRun::new(plan)),
+        _ => None,
+    };
+
     {
         let thread = thread.clone();
+        let shutdown_on_interrupt = team_run.is_some();
         tokio::spawn(async move {
             if tokio::signal::ctrl_c().await.is_ok() {
                 tracing::debug!("Keyboard interrupt");
                 // Immediately notify Codex to abort any in-flight task.
                 thread.submit(Op::Interrupt).await.ok();
+                // A team run has no turn of its own whose abort ends the run.
+                if shutdown_on_interrupt {
+                    thread.submit(Op::Shutdown).await.ok();
+                }
             }
         });
     }
@@ -539,6 +628,11 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
             info!("Sent review request with event ID: {task_id}");
             task_id
         }
+        InitialOperation::TeamPlan { plan } => {
+            let task_id = thread.submit(Op::RunTeamPlan { plan }).await?;
+            info!("Sent team plan with event ID: {task_id}");
+            task_id
+        }
     };
 
     // Run the loop until the task is complete.
@@ -546,6 +640,7 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     // exit with a non-zero status for automation-friendly signaling.
     let mut error_seen = false;
     let mut shutdown_requested = false;
+    let mut team_run_status = TeamRunStatus::Running;
     while let Some(envelope) = rx.recv().await {
         let ThreadEventEnvelope {
             thread_id,
@@ -584,10 +679,45 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
                 shutdown_requested = true;
             }
         }
+        if thread_id == primary_thread_id
+            && let Some(run) = team_run.as_mut()
+        {
+            let status = run.on_event(&event.msg);
+            match &status {
+                TeamRunStatus::Running => {}
+                TeamRunStatus::Completed => {
+                    eprintln!("Team '{}' completed all of its tasks.", run.team_name());
+                }
+                TeamRunStatus::Failed(message) => {
+                    error_seen = true;
+                    eprintln!("{message}");
+                }
+            }
+            if status != TeamRunStatus::Running && !shutdown_requested {
+                thread.submit(Op::Shutdown).await?;
+                shutdown_requested = true;
+            }
+            if team_run_status == TeamRunStatus::Running {
+                team_run_status = status;
+            }
+        }
         if thread_id != primary_thread_id && matches!(&event.msg, EventMsg::TurnComplete(_)) {
             continue;
         }
+        // The leader's turns in a team run are wake-ups by its supervisor;
+        // the run ends with the team instead.
+        let leader_turn_complete = team_run.is_some()
+            && matches!(
+                &event.msg,
+                EventMsg::TurnComplete(_) | EventMsg::TurnAborted(_)
+            );
         let shutdown = event_processor.process_event(event);
+        let shutdown = if leader_turn_complete && matches!(shutdown, CodexStatus::InitiateShutdown)
+        {
+            CodexStatus::Running
+        } else {
+            shutdown
+        };
         if thread_id != primary_thread_id && matches!(shutdown, CodexStatus::InitiateShutdown) {
             continue;
         }
@@ -604,6 +734,40 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         }
     }
     event_processor.print_final_output();
+    if let (Some(run), Some(run_args)) = (&team_run, team_run_args)
+        && (run_args.require_all_tasks || run_args.summary_file.is_some())
+    {
+        if team_run_status == TeamRunStatus::Running {
+            team_run_status = run.leader_shut_down();
+        }
+        let tasks = TaskList::new(default_tasks_root())
+            .get_all_tasks(run.team_name())
+            .await
+            .with_context(|| format!("failed to read the tasks of team '{}'", run.team_name()))?;
+        let report = TeamRunReport {
+            team_name: run.team_name().to_string(),
+            leader_thread_id: primary_thread_id,
+            status: team_run_status,
+            tasks,
+        };
+        if let Some(path) = &run_args.summary_file {
+            let summary = serde_json::to_string_pretty(&report)?;
+            std::fs::write(path, format!("{summary}\n"))
+                .with_context(|| format!("failed to write run summary {}", path.display()))?;
+        }
+        let incomplete = report.incomplete_tasks();
+        if run_args.require_all_tasks && !incomplete.is_empty() {
+            error_seen = true;
+            let ids: Vec<&str> = incomplete.iter().map(|task| task.id.as_str()).collect();
+            eprintln!(
+                "{} of {} tasks of team '{}' were not completed: {}",
+                ids.len(),
+                report.tasks.len(),
+                report.team_name,
+                ids.join(", ")
+            );
+        }
+    }
     if error_seen {
         std::process::exit(1);
     }
@@ -647,6 +811,39 @@ fn spawn_thread_listener(
     });
 }
 
+/// Find the teammate `args` names and set up `config` for its thread.
+/// Refuses while a session leads the team, since the teammate may still be
+/// running there, unless `--force` is given.
+async fn prepare_member_resume(
+    config: &mut Config,
+    args: &TeamResumeMemberArgs,
+) -> anyhow::Result<MemberResume> {
+    let teams = TeamManager::new(default_teams_root());
+    let team_name = &args.team_name;
+    if !teams.team_exists(team_name).await {
+        anyhow::bail!("No team named '{team_name}' found.");
+    }
+    if !args.force
+        && let Some(leader) = teams.leader_lock(team_name).await.ok().flatten()
+        && !leader.is_stale()
+    {
+        anyhow::bail!(
+            "Team '{team_name}' is led by a running session (pid {}). Restart the teammate from that session with restart_teammate, or pass --force.",
+            leader.pid
+        );
+    }
+    MemberResume::prepare(
+        &teams,
+        &TaskList::new(default_tasks_root()),
+        config,
+        team_name,
+        &args.name,
+        args.fresh,
+    )
+    .await
+    .with_context(|| format!("failed to resume teammate '{}'", args.name))
+}
+
 async fn resolve_resume_path(
     config: &Config,
     args: &crate::cli::ResumeArgs,
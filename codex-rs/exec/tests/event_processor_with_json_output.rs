@@ -21,6 +21,13 @@ use codex_core::protocol::PatchApplyEndEvent;
 use codex_core::protocol::PatchApplyStatus as CorePatchApplyStatus;
 use codex_core::protocol::SandboxPolicy;
 use codex_core::protocol::SessionConfiguredEvent;
+use codex_core::protocol::TeamCreatedEvent;
+use codex_core::protocol::TeamDisplayMode;
+use codex_core::protocol::TeamMemberEvent;
+use codex_core::protocol::TeamMemberInfo;
+use codex_core::protocol::TeamTaskEvent;
+use codex_core::protocol::TeamTaskInfo;
+use codex_core::protocol::TeamTaskStatus as CoreTeamTaskStatus;
 use codex_core::protocol::WarningEvent;
 use codex_core::protocol::WebSearchBeginEvent;
 use codex_core::protocol::WebSearchEndEvent;
@@ -44,6 +51,11 @@ use codex_exec::exec_events::McpToolCallStatus;
 use codex_exec::exec_events::PatchApplyStatus;
 use codex_exec::exec_events::PatchChangeKind;
 use codex_exec::exec_events::ReasoningItem;
+use codex_exec::exec_events::TeamEvent;
+use codex_exec::exec_events::TeamMemberSnapshot;
+use codex_exec::exec_events::TeamSnapshotEvent;
+use codex_exec::exec_events::TeamTaskSnapshot;
+use codex_exec::exec_events::TeamTaskStatus;
 use codex_exec::exec_events::ThreadErrorEvent;
 use codex_exec::exec_events::ThreadEvent;
 use codex_exec::exec_events::ThreadItem;
@@ -1304,3 +1316,96 @@ fn task_complete_produces_turn_completed_with_usage() {
         })]
     );
 }
+
+#[test]
+fn team_events_stream_with_snapshots() {
+    let mut ep = EventProcessorWithJsonOutput::new(None);
+    let leader_thread_id = ThreadId::new();
+    let alice_thread_id = ThreadId::new();
+
+    let created = ep.collect_thread_events(&event(
+        "t1",
+        EventMsg::TeamCreated(TeamCreatedEvent {
+            team_name: "docs".to_string(),
+            leader_thread_id,
+            description: None,
+            display_mode: TeamDisplayMode::Inline,
+        }),
+    ));
+    assert_eq!(
+        created,
+        vec![
+            ThreadEvent::TeamEvent(TeamEvent {
+                team_name: Some("docs".to_string()),
+                event: json!({
+                    "type": "team_created",
+                    "team_name": "docs",
+                    "leader_thread_id": leader_thread_id.to_string(),
+                    "display_mode": "inline",
+                }),
+            }),
+            ThreadEvent::TeamSnapshot(TeamSnapshotEvent {
+                team_name: "docs".to_string(),
+                members: Vec::new(),
+                tasks: Vec::new(),
+            }),
+        ]
+    );
+
+    ep.collect_thread_events(&event(
+        "t2",
+        EventMsg::TeamMemberAdded(TeamMemberEvent {
+            team_name: "docs".to_string(),
+            member: TeamMemberInfo {
+                name: "alice".to_string(),
+                thread_id: alice_thread_id,
+                role: Some("writer".to_string()),
+                status: AgentStatus::Running,
+            },
+            previous_status: None,
+        }),
+    ));
+    let task = |status| TeamTaskInfo {
+        id: "task-1".to_string(),
+        title: "Outline".to_string(),
+        description: None,
+        status,
+        assigned_to: Some("alice".to_string()),
+        depends_on: Vec::new(),
+        result: None,
+        created_at: None,
+    };
+    ep.collect_thread_events(&event(
+        "t3",
+        EventMsg::TeamTaskCreated(TeamTaskEvent {
+            team_name: "docs".to_string(),
+            task: task(CoreTeamTaskStatus::Pending),
+        }),
+    ));
+    let updated = ep.collect_thread_events(&event(
+        "t4",
+        EventMsg::TeamTaskUpdated(TeamTaskEvent {
+            team_name: "docs".to_string(),
+            task: task(CoreTeamTaskStatus::InProgress),
+        }),
+    ));
+    assert_eq!(
+        updated.last(),
+        Some(&ThreadEvent::TeamSnapshot(TeamSnapshotEvent {
+            team_name: "docs".to_string(),
+            members: vec![TeamMemberSnapshot {
+                name: "alice".to_string(),
+                thread_id: alice_thread_id.to_string(),
+                role: Some("writer".to_string()),
+                status: CollabAgentStatus::Running,
+            }],
+            tasks: vec![TeamTaskSnapshot {
+                id: "task-1".to_string(),
+                title: "Outline".to_string(),
+                status: TeamTaskStatus::InProgress,
+                assigned_to: Some("alice".to_string()),
+                depends_on: Vec::new(),
+            }],
+        }))
+    );
+}
@@ -381,7 +381,21 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::TeamTaskCreated(_)
                     | EventMsg::TeamTaskUpdated(_)
                     | EventMsg::TeamMessageSent(_)
-                    | EventMsg::TeamCleanup(_) => {
+                    | EventMsg::TeamCleanup(_)
+                    | EventMsg::TeamRenamed(_)
+                    | EventMsg::TeamLeaderChanged(_)
+                    | EventMsg::TeamMemberStatusChanged(_)
+                    | EventMsg::TeamBudgetExhausted(_)
+                    | EventMsg::TeamMemberRestarted(_)
+                    | EventMsg::TeamEditConflict(_)
+                    | EventMsg::TeamDiffSummary(_)
+                    | EventMsg::TeamMemberOutput(_)
+                    | EventMsg::TeamMemberTokenUsage(_)
+                    | EventMsg::TeamProgress(_)
+                    | EventMsg::TeamMemberLivenessLost(_)
+                    | EventMsg::TeamError(_)
+                    | EventMsg::TeamTaskUnblocked(_)
+                    | EventMsg::TeamEscalationRequested(_) => {
                         // For now, we do not do anything extra for these
                         // events. Note that
                         // send(codex_event_to_notification(&event)) above has
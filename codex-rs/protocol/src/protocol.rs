@@ -335,6 +335,99 @@ pub enum Op {
         command: String,
     },
 
+    /// Add a task to the shared task list of a team led by this session, as
+    /// the `assign_task` tool would. The server sends
+    /// [`EventMsg::TeamTaskCreated`] (and [`EventMsg::TeamTaskUpdated`] when
+    /// the task is assigned), or [`EventMsg::TeamError`] if the task could
+    /// not be created.
+    CreateTeamTask {
+        /// Name of the team.
+        team_name: String,
+        /// Short description of the task.
+        title: String,
+        /// Longer description of the work.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        /// Teammate to assign the task to; unassigned when `None`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        assigned_to: Option<String>,
+        /// Task IDs that must be completed before this task can start.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        depends_on: Vec<String>,
+    },
+
+    /// Hand a task of a team led by this session to another teammate, as the
+    /// `reassign_task` tool would. The server sends
+    /// [`EventMsg::TeamTaskUpdated`], or [`EventMsg::TeamError`] if the task
+    /// could not be reassigned.
+    ReassignTeamTask {
+        /// Name of the team.
+        team_name: String,
+        /// ID of the task to reassign.
+        task_id: String,
+        /// Teammate that takes over the task.
+        assigned_to: String,
+    },
+
+    /// Shut down a teammate of a team led by this session, as the
+    /// `shutdown_teammate` tool would. The server sends
+    /// [`EventMsg::TeamMemberRemoved`], or [`EventMsg::TeamError`] if the
+    /// teammate could not be shut down.
+    ShutdownTeammate {
+        /// Name of the team.
+        team_name: String,
+        /// Name of the teammate.
+        name: String,
+    },
+
+    /// Restart a teammate of a team led by this session, as the
+    /// `restart_teammate` tool would. The server sends the membership events
+    /// of the restart, or [`EventMsg::TeamError`] if the teammate could not be
+    /// restarted.
+    RestartTeammate {
+        /// Name of the team.
+        team_name: String,
+        /// Name of the teammate.
+        name: String,
+    },
+
+    /// Send a message from the user to the inbox of a teammate of a team led
+    /// by this session. The server sends [`EventMsg::TeamMessageSent`] from
+    /// `"user"`, or [`EventMsg::TeamError`] if the message could not be
+    /// delivered.
+    SendTeammateMessage {
+        /// Name of the team.
+        team_name: String,
+        /// Name of the teammate.
+        to: String,
+        /// The message.
+        content: String,
+    },
+
+    /// Answer a teammate's `escalate_to_user` request in a team led by this
+    /// session. The answer is written to the teammate's inbox and the server
+    /// sends [`EventMsg::TeamMessageSent`] from `"user"`, or
+    /// [`EventMsg::TeamError`] if the escalation is unknown or was already
+    /// answered.
+    AnswerTeamEscalation {
+        /// Name of the team.
+        team_name: String,
+        /// Id of the escalation, as in [`TeamEscalationEvent::escalation_id`].
+        escalation_id: String,
+        /// The user's answer.
+        answer: String,
+    },
+
+    /// Create a team from a declarative plan and lead it from this session:
+    /// the team is created with `supervise` on, the plan's tasks are seeded
+    /// and its members spawned, each as the matching team tool would. The
+    /// server sends the usual team events, or [`EventMsg::TeamError`] for the
+    /// step that failed.
+    RunTeamPlan {
+        /// The plan to run.
+        plan: TeamPlan,
+    },
+
     /// Request the list of available models.
     ListModels,
 }
@@ -1060,6 +1153,36 @@ pub enum EventMsg {
     TeamMessageSent(TeamMessageEvent),
     /// Agent Teams: the team was cleaned up.
     TeamCleanup(TeamCleanupEvent),
+    /// Agent Teams: the team was renamed.
+    TeamRenamed(TeamRenamedEvent),
+    /// Agent Teams: leadership of the team moved to another thread.
+    TeamLeaderChanged(TeamLeaderChangedEvent),
+    /// Agent Teams: a teammate finished its turn, errored out or shut down,
+    /// or a queued teammate started running.
+    TeamMemberStatusChanged(TeamMemberEvent),
+    /// Agent Teams: a teammate ran out of budget and was shut down.
+    TeamBudgetExhausted(TeamBudgetExhaustedEvent),
+    /// Agent Teams: an errored teammate was restarted automatically.
+    TeamMemberRestarted(TeamMemberRestartedEvent),
+    /// Agent Teams: two or more teammates edited the same files.
+    TeamEditConflict(TeamEditConflictEvent),
+    /// Agent Teams: a teammate's uncommitted and branch changes after a turn.
+    TeamDiffSummary(TeamDiffSummaryEvent),
+    /// Agent Teams: live output of a teammate, forwarded to its leader.
+    TeamMemberOutput(TeamMemberOutputEvent),
+    /// Agent Teams: updated token usage of a teammate, forwarded to its leader.
+    TeamMemberTokenUsage(TeamMemberTokenUsageEvent),
+    /// Agent Teams: task and teammate counts of a team changed.
+    TeamProgress(TeamProgressEvent),
+    /// Agent Teams: a working teammate stopped sending heartbeats.
+    TeamMemberLivenessLost(TeamMemberLivenessLostEvent),
+    /// Agent Teams: a team tool call failed.
+    TeamError(TeamErrorEvent),
+    /// Agent Teams: a completed task left a pending task with all of its
+    /// dependencies completed.
+    TeamTaskUnblocked(TeamTaskUnblockedEvent),
+    /// Agent Teams: a teammate is waiting for the user to answer a question.
+    TeamEscalationRequested(TeamEscalationEvent),
 }
 
 impl From<CollabAgentSpawnBeginEvent> for EventMsg {
@@ -1152,6 +1275,18 @@ impl From<TeamCleanupEvent> for EventMsg {
     }
 }
 
+impl From<TeamRenamedEvent> for EventMsg {
+    fn from(event: TeamRenamedEvent) -> Self {
+        EventMsg::TeamRenamed(event)
+    }
+}
+
+impl From<TeamLeaderChangedEvent> for EventMsg {
+    fn from(event: TeamLeaderChangedEvent) -> Self {
+        EventMsg::TeamLeaderChanged(event)
+    }
+}
+
 /// Agent lifecycle status, derived from emitted events.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS, Default)]
 #[serde(rename_all = "snake_case")]
@@ -2850,6 +2985,92 @@ pub enum TeamTaskStatus {
     Completed,
 }
 
+/// A declarative description of a team to run, as read from a plan file by
+/// `codex team run`.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct TeamPlan {
+    /// Name of the team to create.
+    pub name: String,
+    /// What the team is for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub description: Option<String>,
+    /// How clients render teammate activity.
+    #[serde(default)]
+    pub display_mode: TeamDisplayMode,
+    /// Hand idle teammates the next available task automatically.
+    #[serde(default)]
+    pub work_stealing: bool,
+    /// Teammates to spawn.
+    #[serde(default)]
+    pub members: Vec<TeamPlanMember>,
+    /// Tasks to seed the task list with, in order.
+    #[serde(default)]
+    pub tasks: Vec<TeamPlanTask>,
+}
+
+/// A teammate in a [`TeamPlan`].
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct TeamPlanMember {
+    /// Name of the teammate.
+    pub name: String,
+    /// Role, matched against the definitions in `~/.codex/roles`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub role: Option<String>,
+    /// Initial instructions for the teammate.
+    pub prompt: String,
+    /// Shut the teammate down once it has used more tokens than this.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub max_tokens: Option<i64>,
+    /// Shut the teammate down after this many turns.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub max_turns: Option<u32>,
+}
+
+/// A task in a [`TeamPlan`].
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct TeamPlanTask {
+    /// Identifier other tasks of the plan refer to in `depends_on`. The task
+    /// list assigns its own ID when the task is created.
+    pub id: String,
+    /// Short description of the task.
+    pub title: String,
+    /// Longer description of the work.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub description: Option<String>,
+    /// Teammate to assign the task to; unassigned when `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub assigned_to: Option<String>,
+    /// Plan IDs of earlier tasks that must be completed first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+}
+
+/// How clients render teammate activity for a team.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "kebab-case")]
+#[ts(rename_all = "kebab-case")]
+pub enum TeamDisplayMode {
+    /// Teammate activity appears inline in the leader's transcript.
+    #[default]
+    #[serde(alias = "in-process")]
+    Inline,
+    /// Teammate activity gets its own pane next to the leader's transcript.
+    /// Clients without a split view render it inline.
+    SplitPane,
+    /// Teammate activity stays out of the transcript; only team lifecycle
+    /// events (created, renamed, cleaned up, ...) are shown.
+    Quiet,
+    /// Nothing about the team is shown in the transcript; clients only keep
+    /// a status summary of the team up to date.
+    Compact,
+}
+
 /// Information about a team member.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
 pub struct TeamMemberInfo {
@@ -2872,15 +3093,27 @@ pub struct TeamCreatedEvent {
     pub team_name: String,
     /// Thread ID of the team leader.
     pub leader_thread_id: ThreadId,
+    /// Short description of the team's goal, if one was given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub description: Option<String>,
+    /// How clients should render teammate activity.
+    #[serde(default)]
+    pub display_mode: TeamDisplayMode,
 }
 
-/// Emitted when a member is added to or removed from a team.
+/// Emitted when a member is added to or removed from a team, or its status
+/// changes.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
 pub struct TeamMemberEvent {
     /// Name of the team.
     pub team_name: String,
     /// Information about the member.
     pub member: TeamMemberInfo,
+    /// Status the member had before, for `TeamMemberStatusChanged`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub previous_status: Option<AgentStatus>,
 }
 
 /// A single task in the shared task list.
@@ -2890,6 +3123,10 @@ pub struct TeamTaskInfo {
     pub id: String,
     /// Short description of the task.
     pub title: String,
+    /// Longer description of the work, if one was given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub description: Option<String>,
     /// Current status.
     pub status: TeamTaskStatus,
     /// Name of the teammate assigned to this task, if any.
@@ -2899,6 +3136,15 @@ pub struct TeamTaskInfo {
     /// Task IDs that must be completed before this task can be accepted.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub depends_on: Vec<String>,
+    /// Final message of the assignee's latest turn while it worked on this
+    /// task.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub result: Option<String>,
+    /// When the task was created, RFC 3339.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub created_at: Option<String>,
 }
 
 /// Emitted when a task is created or its status changes.
@@ -2910,6 +3156,34 @@ pub struct TeamTaskEvent {
     pub task: TeamTaskInfo,
 }
 
+/// Emitted for each pending task that a completed task unblocked, i.e. whose
+/// dependencies are now all completed.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct TeamTaskUnblockedEvent {
+    /// Name of the team.
+    pub team_name: String,
+    /// The task that is now ready to be worked on.
+    pub task: TeamTaskInfo,
+    /// Id of the completed task that unblocked it.
+    pub unblocked_by: String,
+}
+
+/// Emitted when a teammate calls `escalate_to_user` to ask the user a question.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct TeamEscalationEvent {
+    /// Name of the team.
+    pub team_name: String,
+    /// Id to answer the escalation with, e.g. "escalation-1".
+    pub escalation_id: String,
+    /// Name of the teammate that asked.
+    pub from: String,
+    /// The question for the user.
+    pub question: String,
+    /// Suggested answers, if the teammate offered any.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+}
+
 /// Emitted when a message is sent between teammates.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
 pub struct TeamMessageEvent {
@@ -2932,6 +3206,227 @@ pub struct TeamCleanupEvent {
     pub leader_thread_id: ThreadId,
 }
 
+/// Emitted when a team is renamed.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct TeamRenamedEvent {
+    /// Previous name of the team.
+    pub old_name: String,
+    /// New name of the team.
+    pub new_name: String,
+}
+
+/// Emitted when leadership of a team is handed to another thread.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct TeamLeaderChangedEvent {
+    /// Name of the team.
+    pub team_name: String,
+    /// Thread ID of the outgoing leader.
+    pub previous_leader_thread_id: ThreadId,
+    /// Thread ID of the new leader.
+    pub leader_thread_id: ThreadId,
+}
+
+/// Emitted when a teammate exceeds its token or turn budget and is shut down.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct TeamBudgetExhaustedEvent {
+    /// Name of the team.
+    pub team_name: String,
+    /// Name of the teammate.
+    pub member_name: String,
+    /// Thread ID of the teammate's session.
+    pub thread_id: ThreadId,
+    /// Which limit was exceeded, e.g. "used 120000 of 100000 tokens".
+    pub reason: String,
+}
+
+/// Emitted when an errored teammate is respawned by its restart policy.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct TeamMemberRestartedEvent {
+    /// Name of the team.
+    pub team_name: String,
+    /// Name of the teammate.
+    pub member_name: String,
+    /// Thread ID of the errored session.
+    pub previous_thread_id: ThreadId,
+    /// Thread ID of the new session.
+    pub thread_id: ThreadId,
+    /// Which restart this was, starting at 1.
+    pub attempt: u32,
+    /// Restarts allowed by the policy.
+    pub max_restarts: u32,
+    /// Error the previous session stopped with.
+    pub error: String,
+}
+
+/// Emitted when teammates edit files another teammate has already edited.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct TeamEditConflictEvent {
+    /// Name of the team.
+    pub team_name: String,
+    /// Newly shared files.
+    pub conflicts: Vec<TeamFileConflict>,
+}
+
+/// A file edited by more than one teammate.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct TeamFileConflict {
+    /// Path relative to the repository root, or absolute outside a repository.
+    pub path: String,
+    /// Teammates that edited the file, sorted by name.
+    pub members: Vec<String>,
+}
+
+/// Emitted when a teammate finishes a turn, summarizing the changes it has
+/// made so far.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct TeamDiffSummaryEvent {
+    /// Name of the team.
+    pub team_name: String,
+    /// Name of the teammate.
+    pub member_name: String,
+    /// Changed files, sorted by path.
+    pub files: Vec<TeamFileDiff>,
+}
+
+/// Line counts of one file changed by a teammate.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct TeamFileDiff {
+    /// Path relative to the repository root.
+    pub path: String,
+    /// Lines added; 0 for binary files.
+    #[ts(type = "number")]
+    pub added: u64,
+    /// Lines removed; 0 for binary files.
+    #[ts(type = "number")]
+    pub removed: u64,
+}
+
+/// Emitted in the leader's session for output of one of its teammates,
+/// as configured by `teams.teammate_output`.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct TeamMemberOutputEvent {
+    /// Name of the team.
+    pub team_name: String,
+    /// Name of the teammate.
+    pub member_name: String,
+    /// Thread ID of the teammate's session.
+    pub thread_id: ThreadId,
+    pub output: TeamMemberOutput,
+}
+
+/// A piece of teammate output.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "snake_case", tag = "type")]
+#[ts(rename_all = "snake_case", tag = "type")]
+pub enum TeamMemberOutput {
+    /// A complete agent message.
+    AgentMessage { message: String },
+    /// Text appended to the agent message being streamed.
+    AgentMessageDelta { delta: String },
+    /// A command started.
+    CommandBegin {
+        call_id: String,
+        command: Vec<String>,
+    },
+    /// Output of a running command, decoded lossily as UTF-8.
+    CommandOutputDelta { call_id: String, chunk: String },
+    /// A command finished.
+    CommandEnd { call_id: String, exit_code: i32 },
+}
+
+/// Emitted in the leader's session each time the token usage of one of its
+/// teammates is updated.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct TeamMemberTokenUsageEvent {
+    /// Name of the team.
+    pub team_name: String,
+    /// Name of the teammate.
+    pub member_name: String,
+    /// Thread ID of the teammate's session.
+    pub thread_id: ThreadId,
+    /// Usage of the teammate's session so far.
+    pub total_token_usage: TokenUsage,
+    /// Usage of the teammate's latest model request.
+    pub last_token_usage: TokenUsage,
+}
+
+/// Emitted in the leader's session whenever the task or teammate counts of a
+/// team change.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct TeamProgressEvent {
+    /// Name of the team.
+    pub team_name: String,
+    /// Tasks completed.
+    pub tasks_completed: u32,
+    /// Tasks in the task list, completed or not.
+    pub tasks_total: u32,
+    /// Teammates starting up or working on a turn.
+    pub members_running: u32,
+    /// Teammates that finished their turn or were shut down.
+    pub members_idle: u32,
+    /// Teammates that errored or whose session is gone.
+    pub members_errored: u32,
+    /// Seconds since the team was created.
+    #[ts(type = "number")]
+    pub elapsed_secs: u64,
+}
+
+/// Emitted in the leader's session when a teammate working on a turn has
+/// emitted no events for longer than `teams.heartbeat_timeout_secs`. The
+/// teammate is marked stale in the team config until it is heard from again.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct TeamMemberLivenessLostEvent {
+    /// Name of the team.
+    pub team_name: String,
+    /// Name of the teammate.
+    pub member_name: String,
+    /// Thread ID of the teammate's session.
+    pub thread_id: ThreadId,
+    /// Seconds since the teammate's last event.
+    #[ts(type = "number")]
+    pub silent_secs: u64,
+}
+
+/// Emitted alongside the error a team tool reports to the model.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct TeamErrorEvent {
+    /// Name of the team tool that failed.
+    pub tool_name: String,
+    /// Team named in the call's arguments, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub team_name: Option<String>,
+    pub code: TeamErrorCode,
+    /// The error as reported to the model.
+    pub message: String,
+}
+
+/// Kind of failure of a team tool.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum TeamErrorCode {
+    /// The arguments did not parse.
+    InvalidArguments,
+    TeamNotFound,
+    MemberNotFound,
+    TaskNotFound,
+    PatchNotFound,
+    /// Another session holds the team's leader lock.
+    NotLeader,
+    /// The team's token budget is used up.
+    BudgetExceeded,
+    /// A member or nesting limit would be exceeded.
+    LimitReached,
+    /// The teammate's agent or worktree could not be created.
+    SpawnFailed,
+    /// A team file on disk does not parse.
+    StorageCorrupt,
+    /// Reading or writing team files failed.
+    StorageError,
+    Other,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3290,6 +3785,311 @@ mod tests {
         Ok(())
     }
 
+    /// Clients match on these names; renaming any of them breaks them.
+    #[test]
+    fn team_event_wire_names_are_stable() -> Result<()> {
+        let thread_id = ThreadId::new();
+        let team_name = || "zeta".to_string();
+        let member = TeamMemberInfo {
+            name: "alice".to_string(),
+            thread_id,
+            role: Some("reviewer".to_string()),
+            status: AgentStatus::Running,
+        };
+        let task = TeamTaskInfo {
+            id: "task-2".to_string(),
+            title: "Write docs".to_string(),
+            description: None,
+            status: TeamTaskStatus::InProgress,
+            assigned_to: Some("alice".to_string()),
+            depends_on: vec!["task-1".to_string()],
+            result: Some("done".to_string()),
+            created_at: None,
+        };
+        let member_event = || TeamMemberEvent {
+            team_name: team_name(),
+            member: member.clone(),
+            previous_status: None,
+        };
+        let task_event = || TeamTaskEvent {
+            team_name: team_name(),
+            task: task.clone(),
+        };
+        let events = [
+            (
+                EventMsg::TeamCreated(TeamCreatedEvent {
+                    team_name: team_name(),
+                    leader_thread_id: thread_id,
+                    description: None,
+                    display_mode: TeamDisplayMode::Inline,
+                }),
+                "team_created",
+            ),
+            (
+                EventMsg::TeamMemberAdded(member_event()),
+                "team_member_added",
+            ),
+            (
+                EventMsg::TeamMemberRemoved(member_event()),
+                "team_member_removed",
+            ),
+            (EventMsg::TeamTaskCreated(task_event()), "team_task_created"),
+            (EventMsg::TeamTaskUpdated(task_event()), "team_task_updated"),
+            (
+                EventMsg::TeamMessageSent(TeamMessageEvent {
+                    team_name: team_name(),
+                    from: "leader".to_string(),
+                    to: "alice".to_string(),
+                    content: "hi".to_string(),
+                }),
+                "team_message_sent",
+            ),
+            (
+                EventMsg::TeamCleanup(TeamCleanupEvent {
+                    team_name: team_name(),
+                    leader_thread_id: thread_id,
+                }),
+                "team_cleanup",
+            ),
+            (
+                EventMsg::TeamRenamed(TeamRenamedEvent {
+                    old_name: team_name(),
+                    new_name: "eta".to_string(),
+                }),
+                "team_renamed",
+            ),
+            (
+                EventMsg::TeamLeaderChanged(TeamLeaderChangedEvent {
+                    team_name: team_name(),
+                    previous_leader_thread_id: thread_id,
+                    leader_thread_id: thread_id,
+                }),
+                "team_leader_changed",
+            ),
+            (
+                EventMsg::TeamMemberStatusChanged(member_event()),
+                "team_member_status_changed",
+            ),
+            (
+                EventMsg::TeamBudgetExhausted(TeamBudgetExhaustedEvent {
+                    team_name: team_name(),
+                    member_name: "alice".to_string(),
+                    thread_id,
+                    reason: "used 2 of 1 tokens".to_string(),
+                }),
+                "team_budget_exhausted",
+            ),
+            (
+                EventMsg::TeamMemberRestarted(TeamMemberRestartedEvent {
+                    team_name: team_name(),
+                    member_name: "alice".to_string(),
+                    previous_thread_id: thread_id,
+                    thread_id,
+                    attempt: 1,
+                    max_restarts: 3,
+                    error: "boom".to_string(),
+                }),
+                "team_member_restarted",
+            ),
+            (
+                EventMsg::TeamEditConflict(TeamEditConflictEvent {
+                    team_name: team_name(),
+                    conflicts: Vec::new(),
+                }),
+                "team_edit_conflict",
+            ),
+            (
+                EventMsg::TeamDiffSummary(TeamDiffSummaryEvent {
+                    team_name: team_name(),
+                    member_name: "alice".to_string(),
+                    files: Vec::new(),
+                }),
+                "team_diff_summary",
+            ),
+            (
+                EventMsg::TeamMemberOutput(TeamMemberOutputEvent {
+                    team_name: team_name(),
+                    member_name: "alice".to_string(),
+                    thread_id,
+                    output: TeamMemberOutput::AgentMessage {
+                        message: "done".to_string(),
+                    },
+                }),
+                "team_member_output",
+            ),
+            (
+                EventMsg::TeamMemberTokenUsage(TeamMemberTokenUsageEvent {
+                    team_name: team_name(),
+                    member_name: "alice".to_string(),
+                    thread_id,
+                    total_token_usage: TokenUsage::default(),
+                    last_token_usage: TokenUsage::default(),
+                }),
+                "team_member_token_usage",
+            ),
+            (
+                EventMsg::TeamProgress(TeamProgressEvent {
+                    team_name: team_name(),
+                    tasks_completed: 0,
+                    tasks_total: 0,
+                    members_running: 0,
+                    members_idle: 0,
+                    members_errored: 0,
+                    elapsed_secs: 0,
+                }),
+                "team_progress",
+            ),
+            (
+                EventMsg::TeamMemberLivenessLost(TeamMemberLivenessLostEvent {
+                    team_name: team_name(),
+                    member_name: "alice".to_string(),
+                    thread_id,
+                    silent_secs: 0,
+                }),
+                "team_member_liveness_lost",
+            ),
+            (
+                EventMsg::TeamError(TeamErrorEvent {
+                    tool_name: "spawn_teammate".to_string(),
+                    team_name: None,
+                    code: TeamErrorCode::Other,
+                    message: "boom".to_string(),
+                }),
+                "team_error",
+            ),
+            (
+                EventMsg::TeamTaskUnblocked(TeamTaskUnblockedEvent {
+                    team_name: team_name(),
+                    task: task.clone(),
+                    unblocked_by: "task-1".to_string(),
+                }),
+                "team_task_unblocked",
+            ),
+            (
+                EventMsg::TeamEscalationRequested(TeamEscalationEvent {
+                    team_name: team_name(),
+                    escalation_id: "escalation-1".to_string(),
+                    from: "alice".to_string(),
+                    question: "Drop the legacy API?".to_string(),
+                    options: vec!["yes".to_string(), "no".to_string()],
+                }),
+                "team_escalation_requested",
+            ),
+        ];
+        for (event, name) in events {
+            assert_eq!(serde_json::to_value(&event)?["type"], name);
+        }
+
+        assert_eq!(
+            serde_json::to_value(&member)?,
+            json!({
+                "name": "alice",
+                "thread_id": thread_id.to_string(),
+                "role": "reviewer",
+                "status": "running",
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(&task)?,
+            json!({
+                "id": "task-2",
+                "title": "Write docs",
+                "status": "in_progress",
+                "assigned_to": "alice",
+                "depends_on": ["task-1"],
+                "result": "done",
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(TeamDisplayMode::SplitPane)?,
+            json!("split-pane")
+        );
+        assert_eq!(
+            serde_json::from_value::<TeamDisplayMode>(json!("in-process"))?,
+            TeamDisplayMode::Inline
+        );
+        assert_eq!(
+            serde_json::to_value(TeamErrorCode::StorageCorrupt)?,
+            json!("storage_corrupt")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn serialize_team_member_output_event() -> Result<()> {
+        let event = Event {
+            id: "turn".to_string(),
+            msg: EventMsg::TeamMemberOutput(TeamMemberOutputEvent {
+                team_name: "zeta".to_string(),
+                member_name: "alice".to_string(),
+                thread_id: ThreadId::new(),
+                output: TeamMemberOutput::CommandEnd {
+                    call_id: "call-1".to_string(),
+                    exit_code: 2,
+                },
+            }),
+        };
+
+        let value = serde_json::to_value(&event)?;
+        assert_eq!(value["msg"]["type"], "team_member_output");
+        assert_eq!(value["msg"]["member_name"], "alice");
+        assert_eq!(value["msg"]["output"]["type"], "command_end");
+        assert_eq!(value["msg"]["output"]["exit_code"], 2);
+        Ok(())
+    }
+
+    #[test]
+    fn serialize_team_member_token_usage_event() -> Result<()> {
+        let usage = TokenUsage {
+            input_tokens: 120,
+            cached_input_tokens: 20,
+            output_tokens: 30,
+            reasoning_output_tokens: 10,
+            total_tokens: 150,
+        };
+        let event = Event {
+            id: "turn".to_string(),
+            msg: EventMsg::TeamMemberTokenUsage(TeamMemberTokenUsageEvent {
+                team_name: "zeta".to_string(),
+                member_name: "alice".to_string(),
+                thread_id: ThreadId::new(),
+                total_token_usage: usage.clone(),
+                last_token_usage: usage,
+            }),
+        };
+
+        let value = serde_json::to_value(&event)?;
+        assert_eq!(value["msg"]["type"], "team_member_token_usage");
+        assert_eq!(value["msg"]["member_name"], "alice");
+        assert_eq!(value["msg"]["total_token_usage"]["total_tokens"], 150);
+        Ok(())
+    }
+
+    #[test]
+    fn serialize_team_error_event() -> Result<()> {
+        let event = Event {
+            id: "turn".to_string(),
+            msg: EventMsg::TeamError(TeamErrorEvent {
+                tool_name: "spawn_teammate".to_string(),
+                team_name: None,
+                code: TeamErrorCode::SpawnFailed,
+                message: "failed to spawn teammate agent: boom".to_string(),
+            }),
+        };
+
+        let value = serde_json::to_value(&event)?;
+        assert_eq!(
+            value["msg"],
+            json!({
+                "type": "team_error",
+                "tool_name": "spawn_teammate",
+                "code": "spawn_failed",
+                "message": "failed to spawn teammate agent: boom",
+            })
+        );
+        Ok(())
+    }
+
     #[test]
     fn serialize_mcp_startup_complete_event() -> Result<()> {
         let event = Event {
@@ -1,6 +1,8 @@
 use crate::app_backtrack::BacktrackState;
 use crate::app_event::AppEvent;
+use crate::app_event::DormantTeam;
 use crate::app_event::ExitMode;
+use crate::app_event::TeammateAction;
 #[cfg(target_os = "windows")]
 use crate::app_event::WindowsSandboxEnableMode;
 use crate::app_event_sender::AppEventSender;
@@ -20,10 +22,12 @@ use crate::history_cell;
 use crate::history_cell::HistoryCell;
 #[cfg(not(debug_assertions))]
 use crate::history_cell::UpdateAvailableHistoryCell;
+use crate::key_hint;
 use crate::model_migration::ModelMigrationOutcome;
 use crate::model_migration::migration_copy_for_models;
 use crate::model_migration::run_model_migration_prompt;
 use crate::pager_overlay::Overlay;
+use crate::pager_overlay::OverlayAction;
 use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::renderable::Renderable;
 use crate::resume_picker::SessionSelection;
@@ -42,6 +46,7 @@ use codex_core::config::edit::ConfigEdit;
 use codex_core::config::edit::ConfigEditsBuilder;
 use codex_core::config_loader::ConfigLayerStackOrdering;
 use codex_core::features::Feature;
+use codex_core::find_thread_path_by_id_str;
 use codex_core::models_manager::manager::RefreshStrategy;
 use codex_core::models_manager::model_presets::HIDE_GPT_5_1_CODEX_MAX_MIGRATION_PROMPT_CONFIG;
 use codex_core::models_manager::model_presets::HIDE_GPT5_1_MIGRATION_PROMPT_CONFIG;
@@ -55,6 +60,11 @@ use codex_core::protocol::SandboxPolicy;
 use codex_core::protocol::SessionSource;
 use codex_core::protocol::SkillErrorInfo;
 use codex_core::protocol::TokenUsage;
+use codex_core::teams::default_tasks_root;
+use codex_core::teams::default_teams_root;
+use codex_core::teams::report::load_team_run_report;
+use codex_core::teams::task_list::TaskList;
+use codex_core::teams::team_manager::TeamManager;
 #[cfg(target_os = "windows")]
 use codex_core::windows_sandbox::WindowsSandboxLevelExt;
 use codex_otel::OtelManager;
@@ -589,6 +599,16 @@ pub(crate) struct App {
     /// When true the leader only coordinates; shell/write tools are
     /// disabled and all implementation work is delegated to teammates.
     delegation_mode: bool,
+    /// Threads of the team whose leader and teammates Alt+] / Alt+[ cycle
+    /// through, remembered from the leader's view so cycling keeps working
+    /// while a teammate's transcript is shown.
+    team_focus: Option<TeamFocus>,
+}
+
+struct TeamFocus {
+    team_name: String,
+    /// The leader and each teammate, with the name shown for each.
+    threads: Vec<(ThreadId, String)>,
 }
 
 #[derive(Default)]
@@ -678,6 +698,83 @@ impl App {
         }
     }
 
+    /// Replace the current session with the one recorded at `path`, asking
+    /// which directory to use if it was recorded elsewhere.
+    async fn resume_session(&mut self, tui: &mut tui::Tui, path: PathBuf) -> Result<AppRunControl> {
+        let current_cwd = self.config.cwd.clone();
+        let resume_cwd = match crate::resolve_cwd_for_resume_or_fork(
+            tui,
+            &current_cwd,
+            &path,
+            CwdPromptAction::Resume,
+            true,
+        )
+        .await?
+        {
+            crate::ResolveCwdOutcome::Continue(Some(cwd)) => cwd,
+            crate::ResolveCwdOutcome::Continue(None) => current_cwd.clone(),
+            crate::ResolveCwdOutcome::Exit => {
+                return Ok(AppRunControl::Exit(ExitReason::UserRequested));
+            }
+        };
+        let mut resume_config = if crate::cwds_differ(&current_cwd, &resume_cwd) {
+            match self.rebuild_config_for_cwd(resume_cwd).await {
+                Ok(cfg) => cfg,
+                Err(err) => {
+                    self.chat_widget.add_error_message(format!(
+                        "Failed to rebuild configuration for resume: {err}"
+                    ));
+                    return Ok(AppRunControl::Continue);
+                }
+            }
+        } else {
+            // No rebuild needed: current_cwd comes from self.config.cwd.
+            self.config.clone()
+        };
+        self.apply_runtime_policy_overrides(&mut resume_config);
+        let summary = session_summary(
+            self.chat_widget.token_usage(),
+            self.chat_widget.thread_id(),
+            self.chat_widget.thread_name(),
+        );
+        match self
+            .server
+            .resume_thread_from_rollout(
+                resume_config.clone(),
+                path.clone(),
+                self.auth_manager.clone(),
+            )
+            .await
+        {
+            Ok(resumed) => {
+                self.shutdown_current_thread().await;
+                self.config = resume_config;
+                tui.set_notification_method(self.config.tui_notification_method);
+                self.file_search.update_search_dir(self.config.cwd.clone());
+                let init =
+                    self.chatwidget_init_for_forked_or_resumed_thread(tui, self.config.clone());
+                self.chat_widget =
+                    ChatWidget::new_from_existing(init, resumed.thread, resumed.session_configured);
+                self.reset_thread_event_state();
+                if let Some(summary) = summary {
+                    let mut lines: Vec<Line<'static>> = vec![summary.usage_line.clone().into()];
+                    if let Some(command) = summary.resume_command {
+                        let spans = vec!["To continue this session, run ".into(), command.cyan()];
+                        lines.push(spans.into());
+                    }
+                    self.chat_widget.add_plain_history_lines(lines);
+                }
+            }
+            Err(err) => {
+                let path_display = path.display();
+                self.chat_widget.add_error_message(format!(
+                    "Failed to resume session from {path_display}: {err}"
+                ));
+            }
+        }
+        Ok(AppRunControl::Continue)
+    }
+
     fn open_url_in_browser(&mut self, url: String) {
         if let Err(err) = webbrowser::open(&url) {
             self.chat_widget
@@ -1234,6 +1331,7 @@ impl App {
             team_name,
             teammate_mode,
             delegation_mode,
+            team_focus: None,
         };
 
         // On startup, if Agent mode (workspace-write) or ReadOnly is active, warn about world-writable dirs on Windows.
@@ -1283,6 +1381,40 @@ impl App {
             }
         }
 
+        // A fresh session offers to pick up teams that earlier sessions left
+        // behind; resumed sessions and sessions started with `--team` skip it.
+        if wait_for_initial_session_configured
+            && app.config.teams.enabled
+            && app.team_name.is_none()
+        {
+            let codex_home = app.config.codex_home.clone();
+            let tx = app.app_event_tx.clone();
+            tokio::spawn(async move {
+                let teams = match TeamManager::new(default_teams_root()).dormant_teams().await {
+                    Ok(teams) => teams,
+                    Err(err) => {
+                        tracing::warn!("failed to look for dormant teams: {err}");
+                        return;
+                    }
+                };
+                let mut dormant = Vec::new();
+                for team in teams {
+                    let leader_thread_id = team.leader_thread_id.to_string();
+                    let rollout_path = find_thread_path_by_id_str(&codex_home, &leader_thread_id)
+                        .await
+                        .ok()
+                        .flatten();
+                    dormant.push(DormantTeam {
+                        name: team.name,
+                        rollout_path,
+                    });
+                }
+                if !dormant.is_empty() {
+                    tx.send(AppEvent::OfferDormantTeams(dormant));
+                }
+            });
+        }
+
         let tui_events = tui.event_stream();
         tokio::pin!(tui_events);
 
@@ -1457,85 +1589,8 @@ impl App {
             AppEvent::OpenResumePicker => {
                 match crate::resume_picker::run_resume_picker(tui, &self.config, false).await? {
                     SessionSelection::Resume(path) => {
-                        let current_cwd = self.config.cwd.clone();
-                        let resume_cwd = match crate::resolve_cwd_for_resume_or_fork(
-                            tui,
-                            &current_cwd,
-                            &path,
-                            CwdPromptAction::Resume,
-                            true,
-                        )
-                        .await?
-                        {
-                            crate::ResolveCwdOutcome::Continue(Some(cwd)) => cwd,
-                            crate::ResolveCwdOutcome::Continue(None) => current_cwd.clone(),
-                            crate::ResolveCwdOutcome::Exit => {
-                                return Ok(AppRunControl::Exit(ExitReason::UserRequested));
-                            }
-                        };
-                        let mut resume_config = if crate::cwds_differ(&current_cwd, &resume_cwd) {
-                            match self.rebuild_config_for_cwd(resume_cwd).await {
-                                Ok(cfg) => cfg,
-                                Err(err) => {
-                                    self.chat_widget.add_error_message(format!(
-                                        "Failed to rebuild configuration for resume: {err}"
-                                    ));
-                                    return Ok(AppRunControl::Continue);
-                                }
-                            }
-                        } else {
-                            // No rebuild needed: current_cwd comes from self.config.cwd.
-                            self.config.clone()
-                        };
-                        self.apply_runtime_policy_overrides(&mut resume_config);
-                        let summary = session_summary(
-                            self.chat_widget.token_usage(),
-                            self.chat_widget.thread_id(),
-                            self.chat_widget.thread_name(),
-                        );
-                        match self
-                            .server
-                            .resume_thread_from_rollout(
-                                resume_config.clone(),
-                                path.clone(),
-                                self.auth_manager.clone(),
-                            )
-                            .await
-                        {
-                            Ok(resumed) => {
-                                self.shutdown_current_thread().await;
-                                self.config = resume_config;
-                                tui.set_notification_method(self.config.tui_notification_method);
-                                self.file_search.update_search_dir(self.config.cwd.clone());
-                                let init = self.chatwidget_init_for_forked_or_resumed_thread(
-                                    tui,
-                                    self.config.clone(),
-                                );
-                                self.chat_widget = ChatWidget::new_from_existing(
-                                    init,
-                                    resumed.thread,
-                                    resumed.session_configured,
-                                );
-                                self.reset_thread_event_state();
-                                if let Some(summary) = summary {
-                                    let mut lines: Vec<Line<'static>> =
-                                        vec![summary.usage_line.clone().into()];
-                                    if let Some(command) = summary.resume_command {
-                                        let spans = vec![
-                                            "To continue this session, run ".into(),
-                                            command.cyan(),
-                                        ];
-                                        lines.push(spans.into());
-                                    }
-                                    self.chat_widget.add_plain_history_lines(lines);
-                                }
-                            }
-                            Err(err) => {
-                                let path_display = path.display();
-                                self.chat_widget.add_error_message(format!(
-                                    "Failed to resume session from {path_display}: {err}"
-                                ));
-                            }
+                        if let AppRunControl::Exit(reason) = self.resume_session(tui, path).await? {
+                            return Ok(AppRunControl::Exit(reason));
                         }
                     }
                     SessionSelection::Exit
@@ -1546,6 +1601,36 @@ impl App {
                 // Leaving alt-screen may blank the inline viewport; force a redraw either way.
                 tui.frame_requester().schedule_frame();
             }
+            AppEvent::ResumeSession(path) => {
+                let control = self.resume_session(tui, path).await?;
+                tui.frame_requester().schedule_frame();
+                return Ok(control);
+            }
+            AppEvent::OfferDormantTeams(teams) => {
+                self.chat_widget.open_dormant_teams_prompt(teams);
+            }
+            AppEvent::OpenTeamEscalationAnswer(ev) => {
+                self.chat_widget.show_team_escalation_answer_prompt(ev);
+            }
+            AppEvent::ArchiveTeam(name) => {
+                match TeamManager::new(default_teams_root())
+                    .archive_team(&name)
+                    .await
+                {
+                    Ok(archived) => {
+                        if let Err(err) = TaskList::new(default_tasks_root()).archive(&name).await {
+                            tracing::warn!("failed to archive task list of team {name}: {err}");
+                        }
+                        self.chat_widget.add_info_message(
+                            format!("Archived team {name}."),
+                            Some(archived.display().to_string()),
+                        );
+                    }
+                    Err(err) => self
+                        .chat_widget
+                        .add_error_message(format!("Failed to archive team {name}: {err}")),
+                }
+            }
             AppEvent::ForkCurrentSession => {
                 self.otel_manager
                     .counter("codex.thread.fork", 1, &[("source", "slash_command")]);
@@ -2382,6 +2467,129 @@ impl App {
             AppEvent::SelectAgentThread(thread_id) => {
                 self.select_agent_thread(tui, thread_id).await?;
             }
+            AppEvent::OpenTeamDashboard => {
+                let team_state = self.chat_widget.team_state();
+                if team_state.is_active() {
+                    let board = Overlay::new_static_with_lines(
+                        team_state.task_board_lines(),
+                        "T A S K   B O A R D".to_string(),
+                    );
+                    let shutdown_tx = self.app_event_tx.clone();
+                    let restart_tx = self.app_event_tx.clone();
+                    let dashboard = Overlay::new_static_with_selection(
+                        team_state.dashboard_lines(&self.chat_widget.config_ref().teams),
+                        "T E A M".to_string(),
+                        team_state.dashboard_member_rows(),
+                        vec![
+                            OverlayAction::new(
+                                key_hint::plain(KeyCode::Char('x')),
+                                "to shut down",
+                                move |name| {
+                                    shutdown_tx.send(AppEvent::ConfirmTeammateAction {
+                                        name: name.to_string(),
+                                        action: TeammateAction::Shutdown,
+                                    });
+                                },
+                            ),
+                            OverlayAction::new(
+                                key_hint::plain(KeyCode::Char('r')),
+                                "to restart",
+                                move |name| {
+                                    restart_tx.send(AppEvent::ConfirmTeammateAction {
+                                        name: name.to_string(),
+                                        action: TeammateAction::Restart,
+                                    });
+                                },
+                            ),
+                        ],
+                    );
+                    self.chat_widget.mark_team_messages_read();
+                    let _ = tui.enter_alt_screen();
+                    self.overlay = Some(dashboard.linked_to(
+                        key_hint::plain(KeyCode::Char('b')),
+                        "to view the task board",
+                        "to go back to the dashboard",
+                        board,
+                    ));
+                    tui.frame_requester().schedule_frame();
+                } else {
+                    self.chat_widget.add_info_message(
+                        "No active team. Ask Codex to create one with the team tools.".to_string(),
+                        None,
+                    );
+                }
+            }
+            AppEvent::OpenTeamMessageFeed { participant } => {
+                let team_state = self.chat_widget.team_state();
+                if team_state.is_active() {
+                    let _ = tui.enter_alt_screen();
+                    self.overlay = Some(Overlay::new_static_with_lines(
+                        team_state.message_feed_lines(participant.as_deref()),
+                        "T E A M   M E S S A G E S".to_string(),
+                    ));
+                    tui.frame_requester().schedule_frame();
+                } else {
+                    self.chat_widget.add_info_message(
+                        "No active team. Ask Codex to create one with the team tools.".to_string(),
+                        None,
+                    );
+                }
+            }
+            AppEvent::ExportTeamRun { path } => {
+                let team_state = self.chat_widget.team_state();
+                if team_state.is_active() {
+                    let report = match path.extension().and_then(|ext| ext.to_str()) {
+                        Some("json") => Ok(format!("{:#}\n", team_state.export_json())),
+                        // The HTML report is `codex team report`'s, built from
+                        // the team's recorded run rather than what the TUI saw.
+                        Some("html") => {
+                            let team_name = team_state.team_name.clone().unwrap_or_default();
+                            load_team_run_report(
+                                &TeamManager::new(default_teams_root()),
+                                &team_name,
+                                &self.config.teams,
+                            )
+                            .await
+                            .map(|report| report.to_html())
+                        }
+                        _ => Ok(team_state.export_markdown()),
+                    };
+                    let written = match report {
+                        Ok(report) => tokio::fs::write(&path, report).await,
+                        Err(err) => Err(err),
+                    };
+                    match written {
+                        Ok(()) => self.chat_widget.add_info_message(
+                            format!("Exported the team run to {}.", path.display()),
+                            None,
+                        ),
+                        Err(err) => self.chat_widget.add_error_message(format!(
+                            "Failed to export the team run to {}: {err}",
+                            path.display()
+                        )),
+                    }
+                } else {
+                    self.chat_widget.add_info_message(
+                        "No active team. Ask Codex to create one with the team tools.".to_string(),
+                        None,
+                    );
+                }
+            }
+            AppEvent::OpenTaskDetail { task_id } => {
+                if let Some(lines) = self.chat_widget.team_state().task_detail_lines(&task_id) {
+                    let _ = tui.enter_alt_screen();
+                    self.overlay =
+                        Some(Overlay::new_static_with_lines(lines, "T A S K".to_string()));
+                    tui.frame_requester().schedule_frame();
+                }
+            }
+            AppEvent::OpenTaskAssigneePicker { task_id } => {
+                self.chat_widget.open_task_assignee_picker(&task_id);
+            }
+            AppEvent::ConfirmTeammateAction { name, action } => {
+                self.chat_widget
+                    .open_teammate_action_confirmation(&name, action);
+            }
             AppEvent::OpenSkillsList => {
                 self.chat_widget.open_skills_list();
             }
@@ -2810,44 +3018,63 @@ impl App {
                 self.overlay = Some(Overlay::new_transcript(self.transcript_cells.clone()));
                 tui.frame_requester().schedule_frame();
             }
-            // Ctrl+K – team task list overlay
+            // Ctrl+K (`tui.team_overlay_key`) – team task list overlay; the
+            // same key closes it again.
             KeyEvent {
-                code: KeyCode::Char('k'),
+                code: KeyCode::Char(c),
                 modifiers: crossterm::event::KeyModifiers::CONTROL,
                 kind: KeyEventKind::Press,
                 ..
-            } if self.chat_widget.team_state().is_active() => {
-                let lines = self.chat_widget.team_state().task_overlay_lines();
+            } if c == self.config.tui_team_overlay_key
+                && self.chat_widget.team_state().is_active() =>
+            {
+                let team_state = self.chat_widget.team_state();
+                let tx = self.app_event_tx.clone();
+                let detail_tx = self.app_event_tx.clone();
                 let _ = tui.enter_alt_screen();
-                self.overlay = Some(Overlay::new_static_with_lines(
-                    lines,
-                    "T E A M   T A S K S".to_string(),
-                ));
+                self.overlay = Some(
+                    Overlay::new_team_tasks(
+                        team_state.clone(),
+                        vec![
+                            OverlayAction::new(
+                                key_hint::plain(KeyCode::Enter),
+                                "for details",
+                                move |task_id| {
+                                    detail_tx.send(AppEvent::OpenTaskDetail {
+                                        task_id: task_id.to_string(),
+                                    });
+                                },
+                            ),
+                            OverlayAction::new(
+                                key_hint::plain(KeyCode::Char('a')),
+                                "to reassign the task",
+                                move |task_id| {
+                                    tx.send(AppEvent::OpenTaskAssigneePicker {
+                                        task_id: task_id.to_string(),
+                                    });
+                                },
+                            ),
+                        ],
+                    )
+                    .closed_by(key_hint::ctrl(KeyCode::Char(c))),
+                );
                 tui.frame_requester().schedule_frame();
             }
-            // Shift+Down – switch to next teammate thread
+            // Alt+] / Shift+Down – focus the next thread of the team
+            // Alt+[ / Shift+Up – focus the previous one
             KeyEvent {
-                code: KeyCode::Down,
-                modifiers: crossterm::event::KeyModifiers::SHIFT,
+                code: code @ (KeyCode::Char(']' | '[') | KeyCode::Down | KeyCode::Up),
+                modifiers,
                 kind: KeyEventKind::Press,
                 ..
-            } if self.chat_widget.team_state().is_active() => {
-                let ids = self.chat_widget.team_state().teammate_thread_ids();
-                if let Some(next) = self.next_teammate_thread(&ids, true) {
-                    let _ = self.select_agent_thread(tui, next).await;
-                }
-            }
-            // Shift+Up – switch to previous teammate thread
-            KeyEvent {
-                code: KeyCode::Up,
-                modifiers: crossterm::event::KeyModifiers::SHIFT,
-                kind: KeyEventKind::Press,
-                ..
-            } if self.chat_widget.team_state().is_active() => {
-                let ids = self.chat_widget.team_state().teammate_thread_ids();
-                if let Some(prev) = self.next_teammate_thread(&ids, false) {
-                    let _ = self.select_agent_thread(tui, prev).await;
-                }
+            } if (self.chat_widget.team_state().is_active() || self.team_focus.is_some())
+                && match code {
+                    KeyCode::Char(_) => modifiers == crossterm::event::KeyModifiers::ALT,
+                    _ => modifiers == crossterm::event::KeyModifiers::SHIFT,
+                } =>
+            {
+                let forward = matches!(code, KeyCode::Char(']') | KeyCode::Down);
+                let _ = self.cycle_team_focus(tui, forward).await;
             }
             KeyEvent {
                 code: KeyCode::Char('g'),
@@ -2916,25 +3143,42 @@ impl App {
         self.chat_widget.refresh_status_line();
     }
 
-    /// Cycle through teammate thread IDs.  `forward` = true means next,
-    /// false means previous.  Returns the target thread ID or None.
-    fn next_teammate_thread(&self, ids: &[ThreadId], forward: bool) -> Option<ThreadId> {
-        if ids.is_empty() {
-            return None;
+    /// Focus the next (`forward`) or previous thread of the team, leader
+    /// included, and show whose transcript it is in the status line.
+    async fn cycle_team_focus(&mut self, tui: &mut tui::Tui, forward: bool) -> Result<()> {
+        let team_state = self.chat_widget.team_state();
+        if let Some(team_name) = &team_state.team_name {
+            self.team_focus = Some(TeamFocus {
+                team_name: team_name.clone(),
+                threads: team_state.focus_threads(),
+            });
+        } else if self.active_thread_id == self.primary_thread_id {
+            // The team was cleaned up.
+            self.team_focus = None;
         }
-        let current = self.active_thread_id?;
-        let pos = ids.iter().position(|id| *id == current);
-        let idx = match pos {
-            Some(i) => {
-                if forward {
-                    (i + 1) % ids.len()
-                } else {
-                    (i + ids.len() - 1) % ids.len()
-                }
-            }
+        let Some(focus) = &self.team_focus else {
+            return Ok(());
+        };
+        let len = focus.threads.len();
+        if len == 0 {
+            return Ok(());
+        }
+        let idx = match focus
+            .threads
+            .iter()
+            .position(|(id, _)| Some(*id) == self.active_thread_id)
+        {
+            Some(i) if forward => (i + 1) % len,
+            Some(i) => (i + len - 1) % len,
             None => 0,
         };
-        Some(ids[idx])
+        let (thread_id, name) = focus.threads[idx].clone();
+        let label = format!("team {} › {name}", focus.team_name);
+        self.select_agent_thread(tui, thread_id).await?;
+        if self.active_thread_id == Some(thread_id) {
+            self.chat_widget.set_team_focus(Some(label));
+        }
+        Ok(())
     }
 
     #[cfg(target_os = "windows")]
@@ -25,6 +25,7 @@ use crate::history_cell::HistoryCell;
 use codex_core::features::Feature;
 use codex_core::protocol::AskForApproval;
 use codex_core::protocol::SandboxPolicy;
+use codex_core::protocol::TeamEscalationEvent;
 use codex_protocol::config_types::CollaborationModeMask;
 use codex_protocol::config_types::Personality;
 use codex_protocol::openai_models::ReasoningEffort;
@@ -50,6 +51,41 @@ pub(crate) enum AppEvent {
     OpenAgentPicker,
     /// Switch the active thread to the selected agent.
     SelectAgentThread(ThreadId),
+    /// Open the `/team` dashboard of the active team.
+    OpenTeamDashboard,
+    /// Open the feed of messages sent within the active team, showing only
+    /// those sent by or to `participant` when set.
+    OpenTeamMessageFeed {
+        participant: Option<String>,
+    },
+    /// Open the picker for handing task `task_id` of the active team to
+    /// another teammate.
+    OpenTaskAssigneePicker {
+        task_id: String,
+    },
+    /// Open the details of task `task_id` of the active team.
+    OpenTaskDetail {
+        task_id: String,
+    },
+    /// Ask the user to confirm `action` on teammate `name` of the active
+    /// team.
+    ConfirmTeammateAction {
+        name: String,
+        action: TeammateAction,
+    },
+    /// Write a report of the active team's run to `path`: JSON if it ends in
+    /// `.json`, an HTML page if it ends in `.html`, Markdown otherwise.
+    ExportTeamRun {
+        path: PathBuf,
+    },
+    /// Offer to resume or archive teams that no session is leading.
+    OfferDormantTeams(Vec<DormantTeam>),
+    /// Archive dormant team `name` and its task list.
+    ArchiveTeam(String),
+    /// Resume the session recorded at the given rollout path.
+    ResumeSession(PathBuf),
+    /// Ask the user for a free-form answer to a teammate's escalation.
+    OpenTeamEscalationAnswer(TeamEscalationEvent),
 
     /// Start a new session.
     NewSession,
@@ -350,6 +386,21 @@ pub(crate) enum AppEvent {
     StatusLineSetupCancelled,
 }
 
+/// What the `/team` dashboard can do to a teammate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TeammateAction {
+    Shutdown,
+    Restart,
+}
+
+/// A team left on disk that no session is leading, found at startup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DormantTeam {
+    pub name: String,
+    /// Rollout of the session that led the team, if it can still be resumed.
+    pub rollout_path: Option<PathBuf>,
+}
+
 /// The exit strategy requested by the UI layer.
 ///
 /// Most user-initiated exits should use `ShutdownFirst` so core cleanup runs and the UI exits only
@@ -323,6 +323,9 @@ pub(crate) struct ChatComposer {
     context_window_used_tokens: Option<i64>,
     skills: Option<Vec<SkillMetadata>>,
     connectors_snapshot: Option<ConnectorsSnapshot>,
+    /// Name and role of each teammate of the active team, offered in the
+    /// `@` popup ahead of file search.
+    teammates: Vec<(String, Option<String>)>,
     dismissed_mention_popup_token: Option<String>,
     mention_bindings: HashMap<u64, ComposerMentionBinding>,
     recent_submission_mention_bindings: Vec<MentionBinding>,
@@ -336,6 +339,7 @@ pub(crate) struct ChatComposer {
     windows_degraded_sandbox_active: bool,
     status_line_value: Option<Line<'static>>,
     status_line_enabled: bool,
+    team_overlay_key: Option<KeyBinding>,
 }
 
 #[derive(Clone, Debug)]
@@ -424,6 +428,7 @@ impl ChatComposer {
             context_window_used_tokens: None,
             skills: None,
             connectors_snapshot: None,
+            teammates: Vec::new(),
             dismissed_mention_popup_token: None,
             mention_bindings: HashMap::new(),
             recent_submission_mention_bindings: Vec::new(),
@@ -436,6 +441,7 @@ impl ChatComposer {
             windows_degraded_sandbox_active: false,
             status_line_value: None,
             status_line_enabled: false,
+            team_overlay_key: None,
         };
         // Apply configuration via the setter to keep side-effects centralized.
         this.set_disable_paste_burst(disable_paste_burst);
@@ -446,6 +452,10 @@ impl ChatComposer {
         self.skills = skills;
     }
 
+    pub fn set_teammate_mentions(&mut self, teammates: Vec<(String, Option<String>)>) {
+        self.teammates = teammates;
+    }
+
     /// Toggle composer-side image paste handling.
     ///
     /// This only affects whether image-like paste content is converted into attachments; the
@@ -1597,6 +1607,9 @@ impl ChatComposer {
             } => {
                 if let Some(tok) = self.current_mention_token() {
                     self.dismissed_mention_popup_token = Some(tok);
+                } else if let Some(tok) = Self::current_at_token(&self.textarea) {
+                    // The popup listed teammates for an `@token`.
+                    self.dismissed_file_popup_token = Some(tok);
                 }
                 self.active_popup = ActivePopup::None;
                 (InputResult::None, true)
@@ -2856,6 +2869,7 @@ impl ChatComposer {
             context_window_used_tokens: self.context_window_used_tokens,
             status_line_value: self.status_line_value.clone(),
             status_line_enabled: self.status_line_enabled,
+            team_overlay_key: self.team_overlay_key,
         }
     }
 
@@ -2947,7 +2961,30 @@ impl ChatComposer {
         self.dismissed_mention_popup_token = None;
 
         if let Some(token) = file_token {
-            self.sync_file_search_popup(token);
+            let teammates = self.teammate_mention_items(&token);
+            if teammates.is_empty() {
+                self.sync_file_search_popup(token);
+                return;
+            }
+            if self.current_file_query.is_some() {
+                self.app_event_tx
+                    .send(AppEvent::StartFileSearch(String::new()));
+                self.current_file_query = None;
+            }
+            if self.dismissed_file_popup_token.as_ref() == Some(&token) {
+                return;
+            }
+            match &mut self.active_popup {
+                ActivePopup::Skill(popup) => {
+                    popup.set_query(&token);
+                    popup.set_mentions(teammates);
+                }
+                _ => {
+                    let mut popup = SkillPopup::new(teammates);
+                    popup.set_query(&token);
+                    self.active_popup = ActivePopup::Skill(popup);
+                }
+            }
             return;
         }
 
@@ -3233,6 +3270,23 @@ impl ChatComposer {
         }
     }
 
+    /// Teammates whose name starts with `query`, as `@` mentions.
+    fn teammate_mention_items(&self, query: &str) -> Vec<MentionItem> {
+        let query = query.to_lowercase();
+        self.teammates
+            .iter()
+            .filter(|(name, _)| name.to_lowercase().starts_with(&query))
+            .map(|(name, role)| MentionItem {
+                display_name: name.clone(),
+                description: role.clone(),
+                insert_text: format!("@{name}"),
+                search_terms: vec![name.clone()],
+                path: None,
+                category_tag: Some("[Teammate]".to_string()),
+            })
+            .collect()
+    }
+
     fn mention_items(&self) -> Vec<MentionItem> {
         let mut mentions = Vec::new();
 
@@ -3359,6 +3413,14 @@ impl ChatComposer {
         self.status_line_enabled = enabled;
         true
     }
+
+    pub(crate) fn set_team_overlay_key(&mut self, key: Option<KeyBinding>) -> bool {
+        if self.team_overlay_key == key {
+            return false;
+        }
+        self.team_overlay_key = key;
+        true
+    }
 }
 
 fn skill_display_name(skill: &SkillMetadata) -> &str {
@@ -4615,6 +4677,41 @@ mod tests {
         assert_eq!(mention.path, Some("app://connector_1".to_string()));
     }
 
+    #[test]
+    fn at_token_offers_matching_teammates_before_files() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+        composer.set_teammate_mentions(vec![
+            ("alice".to_string(), Some("reviewer".to_string())),
+            ("bob".to_string(), None),
+        ]);
+
+        composer.set_text_content("@Al".to_string(), Vec::new(), Vec::new());
+        let ActivePopup::Skill(popup) = &composer.active_popup else {
+            panic!("expected the teammate popup for @Al");
+        };
+        let mention = popup
+            .selected_mention()
+            .expect("expected alice to be selected");
+        assert_eq!(mention.insert_text, "@alice".to_string());
+
+        let (result, _) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(matches!(result, InputResult::None));
+        assert_eq!(composer.textarea.text(), "@alice ");
+        assert!(matches!(composer.active_popup, ActivePopup::None));
+
+        composer.set_text_content("@src".to_string(), Vec::new(), Vec::new());
+        assert!(matches!(composer.active_popup, ActivePopup::File(_)));
+    }
+
     #[test]
     fn set_connector_mentions_excludes_disabled_apps_from_mention_popup() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
@@ -70,6 +70,8 @@ pub(crate) struct FooterProps {
     pub(crate) context_window_used_tokens: Option<i64>,
     pub(crate) status_line_value: Option<Line<'static>>,
     pub(crate) status_line_enabled: bool,
+    /// Key that toggles the team task overlay, while an agent team is active.
+    pub(crate) team_overlay_key: Option<KeyBinding>,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -594,6 +596,7 @@ fn footer_from_props_lines(
                 esc_backtrack_hint: props.esc_backtrack_hint,
                 is_wsl: props.is_wsl,
                 collaboration_modes_enabled: props.collaboration_modes_enabled,
+                team_overlay_key: props.team_overlay_key,
             };
             shortcut_overlay_lines(state)
         }
@@ -657,6 +660,7 @@ struct ShortcutsState {
     esc_backtrack_hint: bool,
     is_wsl: bool,
     collaboration_modes_enabled: bool,
+    team_overlay_key: Option<KeyBinding>,
 }
 
 fn quit_shortcut_reminder_line(key: KeyBinding) -> Line<'static> {
@@ -725,6 +729,9 @@ fn shortcut_overlay_lines(state: ShortcutsState) -> Vec<Line<'static>> {
     }
     ordered.push(Line::from(""));
     ordered.push(show_transcript);
+    if let Some(key) = state.team_overlay_key {
+        ordered.push(Line::from(vec![key.into(), " to view team tasks".into()]));
+    }
 
     build_columns(ordered)
 }
@@ -1197,6 +1204,7 @@ mod tests {
                 context_window_used_tokens: None,
                 status_line_value: None,
                 status_line_enabled: false,
+                team_overlay_key: None,
             },
         );
 
@@ -1215,6 +1223,7 @@ mod tests {
                 context_window_used_tokens: None,
                 status_line_value: None,
                 status_line_enabled: false,
+                team_overlay_key: None,
             },
         );
 
@@ -1233,6 +1242,7 @@ mod tests {
                 context_window_used_tokens: None,
                 status_line_value: None,
                 status_line_enabled: false,
+                team_overlay_key: None,
             },
         );
 
@@ -1251,6 +1261,7 @@ mod tests {
                 context_window_used_tokens: None,
                 status_line_value: None,
                 status_line_enabled: false,
+                team_overlay_key: None,
             },
         );
 
@@ -1269,6 +1280,7 @@ mod tests {
                 context_window_used_tokens: None,
                 status_line_value: None,
                 status_line_enabled: false,
+                team_overlay_key: None,
             },
         );
 
@@ -1287,6 +1299,7 @@ mod tests {
                 context_window_used_tokens: None,
                 status_line_value: None,
                 status_line_enabled: false,
+                team_overlay_key: None,
             },
         );
 
@@ -1305,6 +1318,7 @@ mod tests {
                 context_window_used_tokens: None,
                 status_line_value: None,
                 status_line_enabled: false,
+                team_overlay_key: None,
             },
         );
 
@@ -1323,6 +1337,7 @@ mod tests {
                 context_window_used_tokens: None,
                 status_line_value: None,
                 status_line_enabled: false,
+                team_overlay_key: None,
             },
         );
 
@@ -1341,6 +1356,7 @@ mod tests {
                 context_window_used_tokens: Some(123_456),
                 status_line_value: None,
                 status_line_enabled: false,
+                team_overlay_key: None,
             },
         );
 
@@ -1359,6 +1375,7 @@ mod tests {
                 context_window_used_tokens: None,
                 status_line_value: None,
                 status_line_enabled: false,
+                team_overlay_key: None,
             },
         );
 
@@ -1377,6 +1394,7 @@ mod tests {
                 context_window_used_tokens: None,
                 status_line_value: None,
                 status_line_enabled: false,
+                team_overlay_key: None,
             },
         );
 
@@ -1393,6 +1411,7 @@ mod tests {
             context_window_used_tokens: None,
             status_line_value: None,
             status_line_enabled: false,
+            team_overlay_key: None,
         };
 
         snapshot_footer_with_mode_indicator(
@@ -1422,6 +1441,7 @@ mod tests {
             context_window_used_tokens: None,
             status_line_value: None,
             status_line_enabled: false,
+            team_overlay_key: None,
         };
 
         snapshot_footer_with_mode_indicator(
@@ -1444,10 +1464,32 @@ mod tests {
             context_window_used_tokens: None,
             status_line_value: Some(Line::from("Status line content".to_string())),
             status_line_enabled: true,
+            team_overlay_key: None,
         };
 
         snapshot_footer("footer_status_line_overrides_shortcuts", props);
 
+        snapshot_footer(
+            "footer_status_line_team_summary",
+            FooterProps {
+                mode: FooterMode::ComposerEmpty,
+                esc_backtrack_hint: false,
+                use_shift_enter_hint: false,
+                is_task_running: false,
+                steer_enabled: false,
+                collaboration_modes_enabled: false,
+                is_wsl: false,
+                quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
+                context_window_percent: None,
+                context_window_used_tokens: None,
+                status_line_value: Some(Line::from(
+                    "team: zeta  3▶ 1✔ 0✖  5/9 tasks  ✉ 2".to_string(),
+                )),
+                status_line_enabled: true,
+                team_overlay_key: Some(key_hint::ctrl(KeyCode::Char('k'))),
+            },
+        );
+
         let props = FooterProps {
             mode: FooterMode::ComposerEmpty,
             esc_backtrack_hint: false,
@@ -1461,6 +1503,7 @@ mod tests {
             context_window_used_tokens: None,
             status_line_value: None, // command timed out / empty
             status_line_enabled: true,
+            team_overlay_key: None,
         };
 
         snapshot_footer_with_mode_indicator(
@@ -1483,6 +1526,7 @@ mod tests {
             context_window_used_tokens: None,
             status_line_value: None,
             status_line_enabled: false,
+            team_overlay_key: None,
         };
 
         snapshot_footer_with_mode_indicator(
@@ -1505,6 +1549,7 @@ mod tests {
             context_window_used_tokens: None,
             status_line_value: None,
             status_line_enabled: true,
+            team_overlay_key: None,
         };
 
         // has status line and no collaboration mode
@@ -1530,6 +1575,7 @@ mod tests {
                 "Status line content that should truncate before the mode indicator".to_string(),
             )),
             status_line_enabled: true,
+            team_overlay_key: None,
         };
 
         snapshot_footer_with_mode_indicator(
@@ -1558,6 +1604,7 @@ mod tests {
                     .to_string(),
             )),
             status_line_enabled: true,
+            team_overlay_key: None,
         };
 
         let screen =
@@ -1577,6 +1624,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn shortcut_overlay_lists_team_overlay_key_while_team_is_active() {
+        snapshot_footer(
+            "footer_shortcuts_team_overlay_key",
+            FooterProps {
+                mode: FooterMode::ShortcutOverlay,
+                esc_backtrack_hint: true,
+                use_shift_enter_hint: true,
+                is_task_running: false,
+                steer_enabled: false,
+                collaboration_modes_enabled: false,
+                is_wsl: false,
+                quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
+                context_window_percent: None,
+                context_window_used_tokens: None,
+                status_line_value: None,
+                status_line_enabled: false,
+                team_overlay_key: Some(key_hint::ctrl(KeyCode::Char('k'))),
+            },
+        );
+    }
+
     #[test]
     fn paste_image_shortcut_prefers_ctrl_alt_v_under_wsl() {
         let descriptor = SHORTCUTS
@@ -1607,6 +1676,7 @@ mod tests {
                 esc_backtrack_hint: false,
                 is_wsl,
                 collaboration_modes_enabled: false,
+                team_overlay_key: None,
             })
             .expect("shortcut binding")
             .key;
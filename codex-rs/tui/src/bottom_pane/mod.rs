@@ -75,6 +75,7 @@ mod prompt_args;
 mod skill_popup;
 mod skills_toggle_view;
 mod slash_commands;
+mod team_task_form;
 pub(crate) use footer::CollaborationModeIndicator;
 pub(crate) use list_selection_view::ColumnWidthMode;
 pub(crate) use list_selection_view::SelectionViewParams;
@@ -87,6 +88,7 @@ pub(crate) use skills_toggle_view::SkillsToggleItem;
 pub(crate) use skills_toggle_view::SkillsToggleView;
 pub(crate) use status_line_setup::StatusLineItem;
 pub(crate) use status_line_setup::StatusLineSetupView;
+pub(crate) use team_task_form::TeamTaskFormView;
 mod paste_burst;
 pub mod popup_consts;
 mod queued_user_messages;
@@ -226,6 +228,12 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    /// Offer `teammates`, by name and role, as `@` mentions in the composer.
+    pub(crate) fn set_teammate_mentions(&mut self, teammates: Vec<(String, Option<String>)>) {
+        self.composer.set_teammate_mentions(teammates);
+        self.request_redraw();
+    }
+
     /// Update image-paste behavior for the active composer and repaint immediately.
     ///
     /// Callers use this to keep composer affordances aligned with model capabilities.
@@ -954,6 +962,14 @@ impl BottomPane {
             self.request_redraw();
         }
     }
+
+    /// Advertise `key` in the shortcut overlay as toggling the team task
+    /// overlay, or stop advertising it with `None`.
+    pub(crate) fn set_team_overlay_key(&mut self, key: Option<KeyBinding>) {
+        if self.composer.set_team_overlay_key(key) {
+            self.request_redraw();
+        }
+    }
 }
 
 impl Renderable for BottomPane {
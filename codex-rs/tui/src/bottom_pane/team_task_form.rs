@@ -0,0 +1,575 @@
+//! Form for adding a task to an agent team (`/task new`).
+//!
+//! The form collects a title, an optional description, an assignee picked
+//! among the team's members and the open tasks the new one depends on, then
+//! sends `Op::CreateTeamTask`, which core handles like an `assign_task` call
+//! of the leader.
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Constraint;
+use ratatui::layout::Layout;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::StatefulWidgetRef;
+use ratatui::widgets::Widget;
+use std::cell::RefCell;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::key_hint;
+use crate::render::renderable::Renderable;
+use codex_core::protocol::Op;
+use codex_core::protocol::TeamTaskInfo;
+use codex_core::protocol::TeamTaskStatus;
+
+use super::CancellationEvent;
+use super::bottom_pane_view::BottomPaneView;
+use super::textarea::TextArea;
+use super::textarea::TextAreaState;
+
+/// Width of the column holding the field labels.
+const LABEL_WIDTH: u16 = 14;
+
+/// How many open tasks are listed at once under "Depends on".
+const MAX_DEPENDENCY_ROWS: usize = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Title,
+    Description,
+    Assignee,
+    Dependencies,
+}
+
+impl Field {
+    const ALL: [Field; 4] = [
+        Field::Title,
+        Field::Description,
+        Field::Assignee,
+        Field::Dependencies,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Field::Title => "Title",
+            Field::Description => "Description",
+            Field::Assignee => "Assignee",
+            Field::Dependencies => "Depends on",
+        }
+    }
+}
+
+/// An open task the new task may depend on.
+struct DependencyChoice {
+    id: String,
+    title: String,
+    selected: bool,
+}
+
+/// Areas of the form's rows, split into label and value columns.
+struct FormAreas {
+    header: Rect,
+    title: [Rect; 2],
+    description: [Rect; 2],
+    assignee: [Rect; 2],
+    dependencies: [Rect; 2],
+    hint: Rect,
+}
+
+pub(crate) struct TeamTaskFormView {
+    team_name: String,
+    /// Names of the teammates the task can be assigned to.
+    members: Vec<String>,
+    dependencies: Vec<DependencyChoice>,
+    app_event_tx: AppEventSender,
+
+    // UI state
+    focus: Field,
+    title: TextArea,
+    title_state: RefCell<TextAreaState>,
+    description: TextArea,
+    description_state: RefCell<TextAreaState>,
+    /// Index into `members` of the assignee; `None` leaves the task
+    /// unassigned.
+    assignee: Option<usize>,
+    dependency_cursor: usize,
+    complete: bool,
+}
+
+impl TeamTaskFormView {
+    pub(crate) fn new(
+        team_name: String,
+        members: Vec<String>,
+        tasks: &[TeamTaskInfo],
+        app_event_tx: AppEventSender,
+    ) -> Self {
+        let dependencies = tasks
+            .iter()
+            .filter(|task| task.status != TeamTaskStatus::Completed)
+            .map(|task| DependencyChoice {
+                id: task.id.clone(),
+                title: task.title.clone(),
+                selected: false,
+            })
+            .collect();
+        Self {
+            team_name,
+            members,
+            dependencies,
+            app_event_tx,
+            focus: Field::Title,
+            title: TextArea::new(),
+            title_state: RefCell::new(TextAreaState::default()),
+            description: TextArea::new(),
+            description_state: RefCell::new(TextAreaState::default()),
+            assignee: None,
+            dependency_cursor: 0,
+            complete: false,
+        }
+    }
+
+    fn move_focus(&mut self, forward: bool) {
+        let count = Field::ALL.len();
+        let current = Field::ALL
+            .iter()
+            .position(|field| *field == self.focus)
+            .unwrap_or_default();
+        let next = if forward {
+            (current + 1) % count
+        } else {
+            (current + count - 1) % count
+        };
+        self.focus = Field::ALL[next];
+    }
+
+    /// Pick the next (or previous) teammate as assignee, passing through
+    /// "unassigned" between the last teammate and the first.
+    fn cycle_assignee(&mut self, forward: bool) {
+        let count = self.members.len() + 1;
+        let current = self.assignee.map_or(0, |index| index + 1);
+        let next = if forward {
+            (current + 1) % count
+        } else {
+            (current + count - 1) % count
+        };
+        self.assignee = next.checked_sub(1);
+    }
+
+    fn submit(&mut self) {
+        let title = self.title.text().trim().to_string();
+        if title.is_empty() {
+            self.focus = Field::Title;
+            return;
+        }
+        let description = Some(self.description.text().trim().to_string())
+            .filter(|description| !description.is_empty());
+        let assigned_to = self.assignee.map(|index| self.members[index].clone());
+        let depends_on = self
+            .dependencies
+            .iter()
+            .filter(|dependency| dependency.selected)
+            .map(|dependency| dependency.id.clone())
+            .collect();
+        self.app_event_tx
+            .send(AppEvent::CodexOp(Op::CreateTeamTask {
+                team_name: self.team_name.clone(),
+                title,
+                description,
+                assigned_to,
+                depends_on,
+            }));
+        self.complete = true;
+    }
+
+    fn text_height(textarea: &TextArea, width: u16, max: u16) -> u16 {
+        textarea.desired_height(width).clamp(1, max)
+    }
+
+    fn dependency_rows(&self) -> u16 {
+        self.dependencies.len().clamp(1, MAX_DEPENDENCY_ROWS) as u16
+    }
+
+    fn layout(&self, area: Rect) -> FormAreas {
+        let value_width = area.width.saturating_sub(LABEL_WIDTH);
+        let [
+            header,
+            _,
+            title,
+            description,
+            assignee,
+            dependencies,
+            _,
+            hint,
+        ] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(Self::text_height(&self.title, value_width, 2)),
+            Constraint::Length(Self::text_height(&self.description, value_width, 4)),
+            Constraint::Length(1),
+            Constraint::Length(self.dependency_rows()),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+        let columns = |row: Rect| {
+            Layout::horizontal([Constraint::Length(LABEL_WIDTH), Constraint::Fill(1)]).areas(row)
+        };
+        FormAreas {
+            header,
+            title: columns(title),
+            description: columns(description),
+            assignee: columns(assignee),
+            dependencies: columns(dependencies),
+            hint,
+        }
+    }
+
+    fn label_line(&self, field: Field) -> Line<'static> {
+        if field == self.focus {
+            Line::from(format!("› {}", field.label()).cyan().bold())
+        } else {
+            Line::from(format!("  {}", field.label()).dim())
+        }
+    }
+
+    fn render_text_field(
+        textarea: &TextArea,
+        state: &RefCell<TextAreaState>,
+        placeholder: &'static str,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let mut state = state.borrow_mut();
+        StatefulWidgetRef::render_ref(&textarea, area, buf, &mut state);
+        if textarea.text().is_empty() {
+            Paragraph::new(Line::from(placeholder.dim())).render(area, buf);
+        }
+    }
+
+    fn assignee_line(&self) -> Line<'static> {
+        let value: Span<'static> = match self.assignee {
+            Some(index) => self.members[index].clone().into(),
+            None if self.members.is_empty() => "unassigned (no teammates yet)".dim(),
+            None => "unassigned".dim(),
+        };
+        if self.focus == Field::Assignee {
+            Line::from(vec!["‹ ".cyan(), value, " ›".cyan()])
+        } else {
+            Line::from(value)
+        }
+    }
+
+    fn dependency_lines(&self) -> Vec<Line<'static>> {
+        if self.dependencies.is_empty() {
+            return vec![Line::from("no open tasks".dim())];
+        }
+        let start = self
+            .dependency_cursor
+            .saturating_sub(MAX_DEPENDENCY_ROWS - 1);
+        self.dependencies
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(MAX_DEPENDENCY_ROWS)
+            .map(|(index, dependency)| {
+                let marker = if dependency.selected { "[x] " } else { "[ ] " };
+                let line = Line::from(vec![
+                    marker.into(),
+                    dependency.id.clone().dim(),
+                    " ".into(),
+                    dependency.title.clone().into(),
+                ]);
+                if self.focus == Field::Dependencies && index == self.dependency_cursor {
+                    line.cyan()
+                } else {
+                    line
+                }
+            })
+            .collect()
+    }
+
+    fn hint_line(&self) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> =
+            vec![key_hint::plain(KeyCode::Tab).into(), " next field  ".dim()];
+        match self.focus {
+            Field::Assignee => spans.extend([
+                key_hint::plain(KeyCode::Left).into(),
+                "/".dim(),
+                key_hint::plain(KeyCode::Right).into(),
+                " pick teammate  ".dim(),
+            ]),
+            Field::Dependencies => spans.extend([
+                key_hint::plain(KeyCode::Char(' ')).into(),
+                " toggle  ".dim(),
+            ]),
+            Field::Title | Field::Description => {}
+        }
+        spans.extend([
+            key_hint::plain(KeyCode::Enter).into(),
+            " create task  ".dim(),
+            key_hint::plain(KeyCode::Esc).into(),
+            " cancel".dim(),
+        ]);
+        Line::from(spans)
+    }
+}
+
+impl BottomPaneView for TeamTaskFormView {
+    fn handle_key_event(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Esc => {
+                self.on_ctrl_c();
+            }
+            KeyCode::Enter if key_event.modifiers == KeyModifiers::NONE => self.submit(),
+            KeyCode::Tab => self.move_focus(true),
+            KeyCode::BackTab => self.move_focus(false),
+            KeyCode::Up if self.focus == Field::Dependencies && self.dependency_cursor > 0 => {
+                self.dependency_cursor -= 1;
+            }
+            KeyCode::Down if self.focus == Field::Dependencies => {
+                if self.dependency_cursor + 1 < self.dependencies.len() {
+                    self.dependency_cursor += 1;
+                }
+            }
+            KeyCode::Up => self.move_focus(false),
+            KeyCode::Down => self.move_focus(true),
+            KeyCode::Left | KeyCode::Right if self.focus == Field::Assignee => {
+                self.cycle_assignee(key_event.code == KeyCode::Right);
+            }
+            KeyCode::Char(' ') if self.focus == Field::Dependencies => {
+                if let Some(dependency) = self.dependencies.get_mut(self.dependency_cursor) {
+                    dependency.selected = !dependency.selected;
+                }
+            }
+            // Titles are a single line.
+            KeyCode::Enter if self.focus == Field::Title => {}
+            _ => match self.focus {
+                Field::Title => self.title.input(key_event),
+                Field::Description => self.description.input(key_event),
+                Field::Assignee | Field::Dependencies => {}
+            },
+        }
+    }
+
+    fn on_ctrl_c(&mut self) -> CancellationEvent {
+        self.complete = true;
+        CancellationEvent::Handled
+    }
+
+    fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    fn handle_paste(&mut self, pasted: String) -> bool {
+        match self.focus {
+            Field::Title if !pasted.is_empty() => {
+                self.title.insert_str(&pasted.replace(['\r', '\n'], " "));
+                true
+            }
+            Field::Description if !pasted.is_empty() => {
+                self.description.insert_str(&pasted);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Renderable for TeamTaskFormView {
+    fn desired_height(&self, width: u16) -> u16 {
+        let value_width = width.saturating_sub(LABEL_WIDTH);
+        // Header, blank line, assignee, blank line and hint, plus the fields
+        // that grow.
+        5 + Self::text_height(&self.title, value_width, 2)
+            + Self::text_height(&self.description, value_width, 4)
+            + self.dependency_rows()
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() {
+            return;
+        }
+        let areas = self.layout(area);
+        Paragraph::new(Line::from(vec![
+            "New task".bold(),
+            format!("  team {}", self.team_name).dim(),
+        ]))
+        .render(areas.header, buf);
+
+        let [label, value] = areas.title;
+        Paragraph::new(self.label_line(Field::Title)).render(label, buf);
+        Self::render_text_field(&self.title, &self.title_state, "required", value, buf);
+
+        let [label, value] = areas.description;
+        Paragraph::new(self.label_line(Field::Description)).render(label, buf);
+        Self::render_text_field(
+            &self.description,
+            &self.description_state,
+            "optional",
+            value,
+            buf,
+        );
+
+        let [label, value] = areas.assignee;
+        Paragraph::new(self.label_line(Field::Assignee)).render(label, buf);
+        Paragraph::new(self.assignee_line()).render(value, buf);
+
+        let [label, value] = areas.dependencies;
+        Paragraph::new(self.label_line(Field::Dependencies)).render(label, buf);
+        Paragraph::new(self.dependency_lines()).render(value, buf);
+
+        Paragraph::new(self.hint_line()).render(areas.hint, buf);
+    }
+
+    fn cursor_pos(&self, area: Rect) -> Option<(u16, u16)> {
+        let areas = self.layout(area);
+        match self.focus {
+            Field::Title => {
+                let state = *self.title_state.borrow();
+                self.title.cursor_pos_with_state(areas.title[1], state)
+            }
+            Field::Description => {
+                let state = *self.description_state.borrow();
+                self.description
+                    .cursor_pos_with_state(areas.description[1], state)
+            }
+            Field::Assignee | Field::Dependencies => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    fn task(id: &str, status: TeamTaskStatus) -> TeamTaskInfo {
+        TeamTaskInfo {
+            id: id.to_string(),
+            title: format!("work on {id}"),
+            description: None,
+            status,
+            assigned_to: None,
+            depends_on: Vec::new(),
+            result: None,
+            created_at: None,
+        }
+    }
+
+    fn press(view: &mut TeamTaskFormView, code: KeyCode) {
+        view.handle_key_event(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn type_text(view: &mut TeamTaskFormView, text: &str) {
+        for c in text.chars() {
+            press(view, KeyCode::Char(c));
+        }
+    }
+
+    #[test]
+    fn submitting_the_form_creates_the_task() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let tasks = [
+            task("task-1", TeamTaskStatus::Completed),
+            task("task-2", TeamTaskStatus::InProgress),
+            task("task-3", TeamTaskStatus::Pending),
+        ];
+        let mut view = TeamTaskFormView::new(
+            "zeta".to_string(),
+            vec!["alice".to_string(), "bob".to_string()],
+            &tasks,
+            AppEventSender::new(tx_raw),
+        );
+
+        // An empty title keeps the form open.
+        press(&mut view, KeyCode::Enter);
+        assert!(!view.is_complete());
+
+        type_text(&mut view, "Fix parser");
+        press(&mut view, KeyCode::Tab);
+        type_text(&mut view, "Handle trailing commas");
+        press(&mut view, KeyCode::Tab);
+        press(&mut view, KeyCode::Right);
+        press(&mut view, KeyCode::Right);
+        press(&mut view, KeyCode::Tab);
+        press(&mut view, KeyCode::Down);
+        press(&mut view, KeyCode::Char(' '));
+        press(&mut view, KeyCode::Enter);
+
+        assert!(view.is_complete());
+        let Some(AppEvent::CodexOp(op)) = rx.try_recv().ok() else {
+            panic!("expected a CodexOp");
+        };
+        assert_eq!(
+            op,
+            Op::CreateTeamTask {
+                team_name: "zeta".to_string(),
+                title: "Fix parser".to_string(),
+                description: Some("Handle trailing commas".to_string()),
+                assigned_to: Some("bob".to_string()),
+                depends_on: vec!["task-3".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn form_shows_fields_and_open_tasks() {
+        let (tx_raw, _rx) = unbounded_channel::<AppEvent>();
+        let tasks = [
+            task("task-1", TeamTaskStatus::Completed),
+            task("task-2", TeamTaskStatus::InProgress),
+            task("task-3", TeamTaskStatus::Pending),
+        ];
+        let mut view = TeamTaskFormView::new(
+            "zeta".to_string(),
+            vec!["alice".to_string(), "bob".to_string()],
+            &tasks,
+            AppEventSender::new(tx_raw),
+        );
+        type_text(&mut view, "Fix parser");
+        press(&mut view, KeyCode::Tab);
+        press(&mut view, KeyCode::Tab);
+        press(&mut view, KeyCode::Right);
+
+        let width = 70;
+        let area = Rect::new(0, 0, width, view.desired_height(width));
+        let mut buf = Buffer::empty(area);
+        view.render(area, &mut buf);
+        let rendered = (0..area.height)
+            .map(|row| {
+                (0..area.width)
+                    .map(|col| buf[(col, row)].symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        insta::assert_snapshot!(rendered);
+    }
+
+    #[test]
+    fn assignee_cycles_back_to_unassigned() {
+        let (tx_raw, _rx) = unbounded_channel::<AppEvent>();
+        let mut view = TeamTaskFormView::new(
+            "zeta".to_string(),
+            vec!["alice".to_string()],
+            &[],
+            AppEventSender::new(tx_raw),
+        );
+
+        view.cycle_assignee(true);
+        assert_eq!(view.assignee, Some(0));
+        view.cycle_assignee(true);
+        assert_eq!(view.assignee, None);
+        view.cycle_assignee(false);
+        assert_eq!(view.assignee, Some(0));
+    }
+}
@@ -96,6 +96,9 @@ use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
 use codex_core::protocol::SkillMetadata as ProtocolSkillMetadata;
 use codex_core::protocol::StreamErrorEvent;
+use codex_core::protocol::TeamDisplayMode;
+use codex_core::protocol::TeamEscalationEvent;
+use codex_core::protocol::TeamTaskStatus;
 use codex_core::protocol::TerminalInteractionEvent;
 use codex_core::protocol::TokenUsage;
 use codex_core::protocol::TokenUsageInfo;
@@ -157,10 +160,14 @@ const PLAN_IMPLEMENTATION_YES: &str = "Yes, implement this plan";
 const PLAN_IMPLEMENTATION_NO: &str = "No, stay in Plan mode";
 const PLAN_IMPLEMENTATION_CODING_MESSAGE: &str = "Implement the plan.";
 const CONNECTORS_SELECTION_VIEW_ID: &str = "connectors-selection";
+const TASK_USAGE: &str = "Usage: /task new";
+const TEAM_USAGE: &str = "Usage: /team [messages [<teammate>] | export <path>]";
 
 use crate::app_event::AppEvent;
 use crate::app_event::ConnectorsSnapshot;
+use crate::app_event::DormantTeam;
 use crate::app_event::ExitMode;
+use crate::app_event::TeammateAction;
 #[cfg(target_os = "windows")]
 use crate::app_event::WindowsSandboxEnableMode;
 use crate::app_event_sender::AppEventSender;
@@ -181,12 +188,12 @@ use crate::bottom_pane::QUIT_SHORTCUT_TIMEOUT;
 use crate::bottom_pane::SelectionAction;
 use crate::bottom_pane::SelectionItem;
 use crate::bottom_pane::SelectionViewParams;
+use crate::bottom_pane::TeamTaskFormView;
 use crate::bottom_pane::custom_prompt_view::CustomPromptView;
 use crate::bottom_pane::popup_consts::standard_popup_hint_line;
 use crate::clipboard_paste::paste_image_to_temp_png;
 use crate::collaboration_modes;
 use crate::diff_render::display_path_for;
-use crate::team_events;
 use crate::exec_cell::CommandOutput;
 use crate::exec_cell::ExecCell;
 use crate::exec_cell::new_active_exec_command;
@@ -210,6 +217,9 @@ use crate::render::renderable::RenderableExt;
 use crate::render::renderable::RenderableItem;
 use crate::slash_command::SlashCommand;
 use crate::status::RateLimitSnapshotDisplay;
+use crate::team_events;
+use crate::team_events::TeamUpdatesCell;
+use crate::teammate_panes::TeammatePanes;
 use crate::text_formatting::truncate_text;
 use crate::tui::FrameRequester;
 mod interrupts;
@@ -626,6 +636,11 @@ pub(crate) struct ChatWidget {
     external_editor_state: ExternalEditorState,
     /// Accumulated team state from Team* events.
     team_state: team_events::TeamState,
+    /// Teammate output shown in panes for split-pane teams.
+    teammate_panes: TeammatePanes,
+    /// Which thread of a team this widget shows, when focused by cycling
+    /// through the team; leads the status line.
+    team_focus: Option<String>,
 }
 
 /// Snapshot of active-cell state that affects transcript overlay rendering.
@@ -919,7 +934,8 @@ impl ChatWidget {
             self.status_line_branch_pending = false;
             self.status_line_branch_lookup_complete = false;
         }
-        let enabled = !items.is_empty();
+        let team_status = self.team_state.status_line_summary();
+        let enabled = !items.is_empty() || self.team_focus.is_some() || team_status.is_some();
         self.bottom_pane.set_status_line_enabled(enabled);
         if !enabled {
             self.set_status_line(None);
@@ -933,7 +949,8 @@ impl ChatWidget {
             self.request_status_line_branch(cwd);
         }
 
-        let mut parts = Vec::new();
+        let mut parts: Vec<String> = self.team_focus.iter().cloned().collect();
+        parts.extend(team_status);
         for item in items {
             if let Some(value) = self.status_line_value_for_item(&item) {
                 parts.push(value);
@@ -948,6 +965,21 @@ impl ChatWidget {
         self.set_status_line(line);
     }
 
+    /// Clear the unread-message badge once the `/team` dashboard shows the
+    /// messages.
+    pub(crate) fn mark_team_messages_read(&mut self) {
+        if self.team_state.unread_messages > 0 {
+            self.team_state.unread_messages = 0;
+            self.refresh_status_line();
+        }
+    }
+
+    /// Show which thread of a team this widget displays in the status line.
+    pub(crate) fn set_team_focus(&mut self, focus: Option<String>) {
+        self.team_focus = focus;
+        self.refresh_status_line();
+    }
+
     /// Records that status-line setup was canceled.
     ///
     /// Cancellation is intentionally side-effect free for config state; the existing configuration
@@ -2101,12 +2133,38 @@ impl ChatWidget {
         self.had_work_activity = true;
     }
 
+    /// Add a team lifecycle cell to the transcript, unless the team is in
+    /// compact display mode.
+    fn on_team_event(&mut self, cell: PlainHistoryCell) {
+        if self.team_state.shows_team_in_transcript() {
+            self.on_collab_event(cell);
+        }
+    }
+
     fn on_collab_event(&mut self, cell: PlainHistoryCell) {
         self.flush_answer_stream_with_separator();
         self.add_to_history(cell);
         self.request_redraw();
     }
 
+    /// Add a routine team event to the chat history, folding it into the
+    /// active cell when that holds the team updates just before it.
+    fn on_team_update(&mut self, cell: PlainHistoryCell) {
+        self.flush_answer_stream_with_separator();
+        if let Some(updates) = self
+            .active_cell
+            .as_mut()
+            .and_then(|c| c.as_any_mut().downcast_mut::<TeamUpdatesCell>())
+        {
+            updates.push(cell);
+        } else {
+            self.flush_active_cell();
+            self.active_cell = Some(Box::new(TeamUpdatesCell::new(cell)));
+        }
+        self.bump_active_cell_revision();
+        self.request_redraw();
+    }
+
     fn on_get_history_entry_response(
         &mut self,
         event: codex_core::protocol::GetHistoryEntryResponseEvent,
@@ -2690,6 +2748,8 @@ impl ChatWidget {
             status_line_branch_pending: false,
             status_line_branch_lookup_complete: false,
             team_state: team_events::TeamState::default(),
+            teammate_panes: TeammatePanes::default(),
+            team_focus: None,
             external_editor_state: ExternalEditorState::Closed,
         };
 
@@ -2854,6 +2914,8 @@ impl ChatWidget {
             status_line_branch_pending: false,
             status_line_branch_lookup_complete: false,
             team_state: team_events::TeamState::default(),
+            teammate_panes: TeammatePanes::default(),
+            team_focus: None,
             external_editor_state: ExternalEditorState::Closed,
         };
 
@@ -3007,6 +3069,8 @@ impl ChatWidget {
             status_line_branch_pending: false,
             status_line_branch_lookup_complete: false,
             team_state: team_events::TeamState::default(),
+            teammate_panes: TeammatePanes::default(),
+            team_focus: None,
             external_editor_state: ExternalEditorState::Closed,
         };
 
@@ -3104,6 +3168,16 @@ impl ChatWidget {
             {
                 self.cycle_collaboration_mode();
             }
+            KeyEvent {
+                code: KeyCode::Char(c @ '1'..='9'),
+                modifiers: KeyModifiers::ALT,
+                kind: KeyEventKind::Press,
+                ..
+            } if self.team_state.display_mode == TeamDisplayMode::SplitPane
+                && self.teammate_panes.cycle_pane(c as usize - '1' as usize) =>
+            {
+                self.request_redraw();
+            }
             KeyEvent {
                 code: KeyCode::Up,
                 modifiers: KeyModifiers::ALT,
@@ -3118,6 +3192,11 @@ impl ChatWidget {
                 }
             }
             _ => match self.bottom_pane.handle_key_event(key_event) {
+                InputResult::Submitted { text, .. } | InputResult::Queued { text, .. }
+                    if self.teammate_message_target(&text).is_some() =>
+                {
+                    self.send_teammate_message(&text);
+                }
                 InputResult::Submitted {
                     text,
                     text_elements,
@@ -3313,6 +3392,12 @@ impl ChatWidget {
             SlashCommand::Agent => {
                 self.app_event_tx.send(AppEvent::OpenAgentPicker);
             }
+            SlashCommand::Team => {
+                self.app_event_tx.send(AppEvent::OpenTeamDashboard);
+            }
+            SlashCommand::Task => {
+                self.add_error_message(TASK_USAGE.to_string());
+            }
             SlashCommand::Approvals => {
                 self.open_permissions_popup();
             }
@@ -3591,10 +3676,337 @@ impl ChatWidget {
                     });
                 self.bottom_pane.drain_pending_submission_state();
             }
+            SlashCommand::Team if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                let mut args = prepared_args.split_whitespace();
+                match (args.next(), args.next(), args.next()) {
+                    (Some("messages"), participant, None) => {
+                        self.app_event_tx.send(AppEvent::OpenTeamMessageFeed {
+                            participant: participant.map(str::to_string),
+                        });
+                    }
+                    (Some("export"), Some(path), None) => {
+                        self.app_event_tx.send(AppEvent::ExportTeamRun {
+                            path: self.config.cwd.join(path),
+                        });
+                    }
+                    _ => self.add_error_message(TEAM_USAGE.to_string()),
+                }
+                self.bottom_pane.drain_pending_submission_state();
+            }
+            SlashCommand::Task if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                match prepared_args.as_str() {
+                    "new" => self.show_team_task_form(),
+                    _ => self.add_error_message(TASK_USAGE.to_string()),
+                }
+                self.bottom_pane.drain_pending_submission_state();
+            }
             _ => self.dispatch_command(cmd),
         }
     }
 
+    fn show_team_task_form(&mut self) {
+        let Some(team_name) = self.team_state.team_name.clone() else {
+            self.add_info_message(
+                "No active team. Ask Codex to create one with the team tools.".to_string(),
+                None,
+            );
+            return;
+        };
+        let members = self
+            .team_state
+            .members
+            .iter()
+            .map(|member| member.name.clone())
+            .collect();
+        let view = TeamTaskFormView::new(
+            team_name,
+            members,
+            &self.team_state.tasks,
+            self.app_event_tx.clone(),
+        );
+        self.bottom_pane.show_view(Box::new(view));
+    }
+
+    /// Offer the teammates of the active team to take over task `task_id`.
+    pub(crate) fn open_task_assignee_picker(&mut self, task_id: &str) {
+        if self.team_state.members.is_empty() {
+            self.add_info_message("The team has no teammates yet.".to_string(), None);
+            return;
+        }
+        let (Some(team_name), Some(task)) = (
+            self.team_state.team_name.clone(),
+            self.team_state.tasks.iter().find(|task| task.id == task_id),
+        ) else {
+            return;
+        };
+        if task.status == TeamTaskStatus::Completed {
+            self.add_info_message(format!("Task {task_id} is already completed."), None);
+            return;
+        }
+        let items: Vec<SelectionItem> = self
+            .team_state
+            .members
+            .iter()
+            .map(|member| {
+                let team_name = team_name.clone();
+                let task_id = task.id.clone();
+                let assigned_to = member.name.clone();
+                let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                    tx.send(AppEvent::CodexOp(Op::ReassignTeamTask {
+                        team_name: team_name.clone(),
+                        task_id: task_id.clone(),
+                        assigned_to: assigned_to.clone(),
+                    }));
+                })];
+                SelectionItem {
+                    name: member.name.clone(),
+                    description: member.role.clone(),
+                    is_current: task.assigned_to.as_deref() == Some(member.name.as_str()),
+                    actions,
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        let mut header = ColumnRenderable::new();
+        header.push(Line::from("Reassign Task".bold()));
+        header.push(Line::from(format!("{} {}", task.id, task.title).dim()));
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            header: Box::new(header),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    /// Ask the user to confirm shutting down or restarting teammate `name`,
+    /// then send the operation to core.
+    pub(crate) fn open_teammate_action_confirmation(&mut self, name: &str, action: TeammateAction) {
+        let Some(team_name) = self.team_state.team_name.clone() else {
+            return;
+        };
+        let (title, confirm, description) = match action {
+            TeammateAction::Shutdown => (
+                format!("Shut down {name}?"),
+                "Yes, shut down",
+                "End the teammate's thread and remove it from the team",
+            ),
+            TeammateAction::Restart => (
+                format!("Restart {name}?"),
+                "Yes, restart",
+                "Replace the teammate's thread with a fresh one given its original prompt",
+            ),
+        };
+        let name = name.to_string();
+        let confirm_actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+            let team_name = team_name.clone();
+            let name = name.clone();
+            tx.send(AppEvent::CodexOp(match action {
+                TeammateAction::Shutdown => Op::ShutdownTeammate { team_name, name },
+                TeammateAction::Restart => Op::RestartTeammate { team_name, name },
+            }));
+        })];
+        let items = vec![
+            SelectionItem {
+                name: confirm.to_string(),
+                description: Some(description.to_string()),
+                actions: confirm_actions,
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Cancel".to_string(),
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+        ];
+
+        let mut header = ColumnRenderable::new();
+        header.push(Line::from(title.bold()));
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            header: Box::new(header),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    /// Offer the active team's teammates as `@` mentions in the composer.
+    fn sync_teammate_mentions(&mut self) {
+        let teammates = self
+            .team_state
+            .members
+            .iter()
+            .map(|member| (member.name.clone(), member.role.clone()))
+            .collect();
+        self.bottom_pane.set_teammate_mentions(teammates);
+    }
+
+    /// The teammate a submitted `text` is addressed to: the active team's
+    /// teammate named by its leading `@mention`, if any.
+    fn teammate_message_target<'a>(&self, text: &'a str) -> Option<&'a str> {
+        if !self.team_state.is_active() {
+            return None;
+        }
+        let mention = text.strip_prefix('@')?.split_whitespace().next()?;
+        self.team_state
+            .members
+            .iter()
+            .any(|member| member.name == mention)
+            .then_some(mention)
+    }
+
+    /// Send `text`, minus its leading `@mention`, to the inbox of the
+    /// teammate it names instead of to the leader model.
+    fn send_teammate_message(&mut self, text: &str) {
+        let (Some(team_name), Some(to)) = (
+            self.team_state.team_name.clone(),
+            self.teammate_message_target(text),
+        ) else {
+            return;
+        };
+        let content = text
+            .strip_prefix('@')
+            .and_then(|rest| rest.strip_prefix(to))
+            .unwrap_or_default()
+            .trim();
+        if content.is_empty() {
+            self.add_error_message(format!("Type a message for {to} after @{to}."));
+            return;
+        }
+        self.submit_op(Op::SendTeammateMessage {
+            team_name,
+            to: to.to_string(),
+            content: content.to_string(),
+        });
+    }
+
+    /// Offer to resume the session that led each of `teams`, or to archive
+    /// the team, at startup.
+    pub(crate) fn open_dormant_teams_prompt(&mut self, teams: Vec<DormantTeam>) {
+        let mut items = Vec::new();
+        for team in teams {
+            if let Some(path) = team.rollout_path {
+                items.push(SelectionItem {
+                    name: format!("Resume team {}", team.name),
+                    description: Some("Reopen the session that led it".to_string()),
+                    actions: vec![Box::new(move |tx| {
+                        tx.send(AppEvent::ResumeSession(path.clone()));
+                    })],
+                    dismiss_on_select: true,
+                    ..Default::default()
+                });
+            }
+            let name = team.name.clone();
+            items.push(SelectionItem {
+                name: format!("Archive team {}", team.name),
+                description: Some("Move it and its tasks to the archive".to_string()),
+                actions: vec![Box::new(move |tx| {
+                    tx.send(AppEvent::ArchiveTeam(name.clone()));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            });
+        }
+        items.push(SelectionItem {
+            name: "Ignore".to_string(),
+            description: Some("Leave the teams as they are".to_string()),
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+
+        let mut header = ColumnRenderable::new();
+        header.push(Line::from("Teams from earlier sessions".bold()));
+        header.push(Line::from("No session is leading these teams.".dim()));
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            header: Box::new(header),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    /// Ask the user to answer a teammate's escalation, approval-style: one
+    /// item per suggested answer, plus a free-form answer.
+    fn open_team_escalation_prompt(&mut self, ev: TeamEscalationEvent) {
+        if ev.options.is_empty() {
+            self.show_team_escalation_answer_prompt(ev);
+            return;
+        }
+        let mut items: Vec<SelectionItem> = ev
+            .options
+            .iter()
+            .map(|option| {
+                let op = Op::AnswerTeamEscalation {
+                    team_name: ev.team_name.clone(),
+                    escalation_id: ev.escalation_id.clone(),
+                    answer: option.clone(),
+                };
+                SelectionItem {
+                    name: option.clone(),
+                    actions: vec![Box::new(move |tx| {
+                        tx.send(AppEvent::CodexOp(op.clone()));
+                    })],
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+        let mut header = ColumnRenderable::new();
+        header.push(Line::from(format!("{} needs your answer", ev.from).bold()));
+        header.push(Line::from(ev.question.clone()));
+        items.push(SelectionItem {
+            name: "Answer in your own words".to_string(),
+            actions: vec![Box::new(move |tx| {
+                tx.send(AppEvent::OpenTeamEscalationAnswer(ev.clone()));
+            })],
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            header: Box::new(header),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn show_team_escalation_answer_prompt(&mut self, ev: TeamEscalationEvent) {
+        let tx = self.app_event_tx.clone();
+        let view = CustomPromptView::new(
+            format!("{} needs your answer", ev.from),
+            "Type an answer and press Enter".to_string(),
+            Some(ev.question),
+            Box::new(move |answer: String| {
+                let answer = answer.trim().to_string();
+                if answer.is_empty() {
+                    return;
+                }
+                tx.send(AppEvent::CodexOp(Op::AnswerTeamEscalation {
+                    team_name: ev.team_name.clone(),
+                    escalation_id: ev.escalation_id.clone(),
+                    answer,
+                }));
+            }),
+        );
+        self.bottom_pane.show_view(Box::new(view));
+    }
+
     fn show_rename_prompt(&mut self) {
         let tx = self.app_event_tx.clone();
         let has_name = self
@@ -3977,6 +4389,19 @@ impl ChatWidget {
         if !is_stream_error {
             self.restore_retry_status_header_if_present();
         }
+        let updates_team_status = matches!(
+            &msg,
+            EventMsg::TeamCreated(_)
+                | EventMsg::TeamMemberAdded(_)
+                | EventMsg::TeamMemberRemoved(_)
+                | EventMsg::TeamMemberStatusChanged(_)
+                | EventMsg::TeamTaskCreated(_)
+                | EventMsg::TeamTaskUpdated(_)
+                | EventMsg::TeamMessageSent(_)
+                | EventMsg::TeamProgress(_)
+                | EventMsg::TeamRenamed(_)
+                | EventMsg::TeamCleanup(_)
+        );
 
         match msg {
             EventMsg::AgentMessageDelta(_)
@@ -4125,30 +4550,117 @@ impl ChatWidget {
             // Agent Teams events – update state tracker, then render as info cells.
             EventMsg::TeamCreated(ev) => {
                 self.team_state.on_team_created(&ev);
-                self.on_collab_event(team_events::team_created(ev))
+                self.bottom_pane
+                    .set_team_overlay_key(Some(key_hint::ctrl(KeyCode::Char(
+                        self.config.tui_team_overlay_key,
+                    ))));
+                self.on_team_event(team_events::team_created(ev))
             }
             EventMsg::TeamMemberAdded(ev) => {
                 self.team_state.on_member_added(&ev);
-                self.on_collab_event(team_events::team_member_added(ev))
+                self.teammate_panes.on_member_added(&ev.member.name);
+                self.sync_teammate_mentions();
+                if self.team_state.shows_activity_in_transcript() {
+                    self.on_team_update(team_events::team_member_added(ev));
+                }
             }
             EventMsg::TeamMemberRemoved(ev) => {
                 self.team_state.on_member_removed(&ev);
-                self.on_collab_event(team_events::team_member_removed(ev))
+                self.teammate_panes.on_member_removed(&ev.member.name);
+                self.sync_teammate_mentions();
+                if self.team_state.shows_activity_in_transcript() {
+                    self.on_team_update(team_events::team_member_removed(ev));
+                }
             }
             EventMsg::TeamTaskCreated(ev) => {
                 self.team_state.on_task_created(&ev);
-                self.on_collab_event(team_events::team_task_created(ev))
+                if self.team_state.shows_activity_in_transcript() {
+                    self.on_team_update(team_events::team_task_created(ev));
+                }
             }
             EventMsg::TeamTaskUpdated(ev) => {
                 self.team_state.on_task_updated(&ev);
-                self.on_collab_event(team_events::team_task_updated(ev))
+                if self.team_state.shows_activity_in_transcript() {
+                    self.on_team_update(team_events::team_task_updated(ev));
+                }
+            }
+            EventMsg::TeamTaskUnblocked(ev) => {
+                if self.team_state.shows_activity_in_transcript() {
+                    self.on_team_update(team_events::team_task_unblocked(ev));
+                }
             }
             EventMsg::TeamMessageSent(ev) => {
-                self.on_collab_event(team_events::team_message_sent(ev))
+                self.team_state.on_message_sent(&ev);
+                if !from_replay && team_events::is_for_leader(&ev) {
+                    self.team_state.unread_messages += 1;
+                    self.notify(Notification::TeamMessage {
+                        from: ev.from.clone(),
+                        content: ev.content.clone(),
+                    });
+                }
+                if self.team_state.shows_activity_in_transcript() {
+                    let accent = self.team_state.teammate_accent(&ev.from);
+                    self.on_team_update(team_events::team_message_sent(ev, accent));
+                }
             }
             EventMsg::TeamCleanup(ev) => {
+                // Cleanup forgets the display mode, so render first.
+                self.on_team_event(team_events::team_cleanup(ev));
                 self.team_state.on_cleanup();
-                self.on_collab_event(team_events::team_cleanup(ev))
+                self.teammate_panes.clear();
+                self.sync_teammate_mentions();
+                self.bottom_pane.set_team_overlay_key(None);
+            }
+            EventMsg::TeamRenamed(ev) => {
+                self.team_state.on_team_renamed(&ev);
+                self.on_team_event(team_events::team_renamed(ev))
+            }
+            EventMsg::TeamLeaderChanged(ev) => {
+                self.team_state.on_leader_changed(&ev);
+                self.on_team_event(team_events::team_leader_changed(ev))
+            }
+            EventMsg::TeamMemberStatusChanged(ev) => {
+                self.team_state.on_member_status_changed(&ev);
+                if self.team_state.shows_activity_in_transcript() {
+                    self.on_team_update(team_events::team_member_status_changed(ev));
+                }
+            }
+            EventMsg::TeamBudgetExhausted(ev) => {
+                self.on_team_event(team_events::team_budget_exhausted(ev))
+            }
+            EventMsg::TeamError(ev) => self.on_team_event(team_events::team_error(ev)),
+            EventMsg::TeamEscalationRequested(ev) => {
+                if !from_replay {
+                    self.notify(Notification::TeamEscalation {
+                        from: ev.from.clone(),
+                        question: ev.question.clone(),
+                    });
+                    self.open_team_escalation_prompt(ev.clone());
+                }
+                self.on_team_event(team_events::team_escalation_requested(ev))
+            }
+            EventMsg::TeamMemberLivenessLost(ev) => {
+                self.on_team_event(team_events::team_member_liveness_lost(ev))
+            }
+            EventMsg::TeamMemberRestarted(ev) => {
+                self.on_team_event(team_events::team_member_restarted(ev))
+            }
+            EventMsg::TeamEditConflict(ev) => {
+                self.on_team_event(team_events::team_edit_conflict(ev))
+            }
+            EventMsg::TeamDiffSummary(ev) => self.team_state.on_diff_summary(&ev),
+            EventMsg::TeamMemberTokenUsage(ev) => self.team_state.on_member_token_usage(&ev),
+            EventMsg::TeamProgress(ev) => self.team_state.on_progress(&ev),
+            EventMsg::TeamMemberOutput(ev) => {
+                self.teammate_panes.on_member_output(&ev);
+                let accent = self.team_state.teammate_accent(&ev.member_name);
+                if self.team_state.display_mode == TeamDisplayMode::SplitPane {
+                    self.request_redraw();
+                } else if self.team_state.shows_activity_in_transcript()
+                    && let Some(cell) = team_events::team_member_output(ev, accent)
+                {
+                    self.on_collab_event(cell);
+                }
             }
             EventMsg::ThreadRolledBack(rollback) => {
                 if from_replay {
@@ -4177,6 +4689,9 @@ impl ChatWidget {
         if !from_replay && self.agent_turn_running {
             self.refresh_runtime_metrics();
         }
+        if updates_team_status {
+            self.refresh_status_line();
+        }
     }
 
     fn on_entered_review_mode(&mut self, review: ReviewRequest, from_replay: bool) {
@@ -7156,6 +7671,12 @@ impl ChatWidget {
         };
         let mut flex = FlexRenderable::new();
         flex.push(1, active_cell_renderable);
+        if self.team_state.display_mode == TeamDisplayMode::SplitPane {
+            flex.push(
+                0,
+                RenderableItem::Borrowed(&self.teammate_panes).inset(Insets::tlbr(1, 0, 0, 0)),
+            );
+        }
         flex.push(
             0,
             RenderableItem::Borrowed(&self.bottom_pane).inset(Insets::tlbr(1, 0, 0, 0)),
@@ -7199,6 +7720,8 @@ enum Notification {
     ExecApprovalRequested { command: String },
     EditApprovalRequested { cwd: PathBuf, changes: Vec<PathBuf> },
     ElicitationRequested { server_name: String },
+    TeamMessage { from: String, content: String },
+    TeamEscalation { from: String, question: String },
 }
 
 impl Notification {
@@ -7225,6 +7748,12 @@ impl Notification {
             Notification::ElicitationRequested { server_name } => {
                 format!("Approval requested by {server_name}")
             }
+            Notification::TeamMessage { from, content } => {
+                format!("{from}: {}", truncate_text(content, 30))
+            }
+            Notification::TeamEscalation { from, question } => {
+                format!("{from} asks: {}", truncate_text(question, 30))
+            }
         }
     }
 
@@ -7233,7 +7762,9 @@ impl Notification {
             Notification::AgentTurnComplete { .. } => "agent-turn-complete",
             Notification::ExecApprovalRequested { .. }
             | Notification::EditApprovalRequested { .. }
-            | Notification::ElicitationRequested { .. } => "approval-requested",
+            | Notification::ElicitationRequested { .. }
+            | Notification::TeamEscalation { .. } => "approval-requested",
+            Notification::TeamMessage { .. } => "team-message",
         }
     }
 
@@ -29,6 +29,7 @@ use codex_core::protocol::AgentMessageDeltaEvent;
 use codex_core::protocol::AgentMessageEvent;
 use codex_core::protocol::AgentReasoningDeltaEvent;
 use codex_core::protocol::AgentReasoningEvent;
+use codex_core::protocol::AgentStatus;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
 use codex_core::protocol::BackgroundEventEvent;
 use codex_core::protocol::CreditsSnapshot;
@@ -55,6 +56,17 @@ use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
 use codex_core::protocol::SessionSource;
 use codex_core::protocol::StreamErrorEvent;
+use codex_core::protocol::TeamCleanupEvent;
+use codex_core::protocol::TeamCreatedEvent;
+use codex_core::protocol::TeamDisplayMode;
+use codex_core::protocol::TeamErrorCode;
+use codex_core::protocol::TeamErrorEvent;
+use codex_core::protocol::TeamEscalationEvent;
+use codex_core::protocol::TeamMemberEvent;
+use codex_core::protocol::TeamMemberInfo;
+use codex_core::protocol::TeamTaskEvent;
+use codex_core::protocol::TeamTaskInfo;
+use codex_core::protocol::TeamTaskStatus;
 use codex_core::protocol::TerminalInteractionEvent;
 use codex_core::protocol::ThreadRolledBackEvent;
 use codex_core::protocol::TokenCountEvent;
@@ -2362,6 +2374,311 @@ async fn replayed_thread_rollback_emits_ordered_app_event() {
     assert!(saw, "expected replay rollback app event");
 }
 
+fn team_created(display_mode: TeamDisplayMode) -> EventMsg {
+    EventMsg::TeamCreated(TeamCreatedEvent {
+        team_name: "zeta".to_string(),
+        leader_thread_id: ThreadId::new(),
+        description: None,
+        display_mode,
+    })
+}
+
+fn team_member_added(name: &str) -> EventMsg {
+    EventMsg::TeamMemberAdded(TeamMemberEvent {
+        team_name: "zeta".to_string(),
+        member: TeamMemberInfo {
+            name: name.to_string(),
+            thread_id: ThreadId::new(),
+            role: None,
+            status: AgentStatus::Running,
+        },
+        previous_status: None,
+    })
+}
+
+#[tokio::test]
+async fn replayed_team_events_restore_team_state() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(Some("gpt-5")).await;
+    let task = |status| TeamTaskInfo {
+        id: "task-1".to_string(),
+        title: "Write docs".to_string(),
+        description: None,
+        status,
+        assigned_to: Some("alice".to_string()),
+        depends_on: Vec::new(),
+        result: None,
+        created_at: None,
+    };
+
+    chat.replay_initial_messages(vec![
+        team_created(TeamDisplayMode::Inline),
+        team_member_added("alice"),
+        EventMsg::TeamTaskCreated(TeamTaskEvent {
+            team_name: "zeta".to_string(),
+            task: task(TeamTaskStatus::InProgress),
+        }),
+        EventMsg::TeamTaskUpdated(TeamTaskEvent {
+            team_name: "zeta".to_string(),
+            task: task(TeamTaskStatus::Completed),
+        }),
+    ]);
+
+    assert_eq!(chat.team_state.team_name.as_deref(), Some("zeta"));
+    assert_eq!(
+        chat.team_state
+            .members
+            .iter()
+            .map(|member| member.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["alice"]
+    );
+    assert_eq!(chat.team_state.tasks, vec![task(TeamTaskStatus::Completed)]);
+}
+
+#[tokio::test]
+async fn consecutive_team_updates_fold_into_one_history_cell() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(Some("gpt-5")).await;
+    chat.replay_initial_messages(vec![
+        team_created(TeamDisplayMode::Inline),
+        team_member_added("alice"),
+        team_member_added("bob"),
+        EventMsg::TeamError(TeamErrorEvent {
+            tool_name: "spawn_teammate".to_string(),
+            team_name: Some("zeta".to_string()),
+            code: TeamErrorCode::Other,
+            message: "no more teammates".to_string(),
+        }),
+    ]);
+
+    let titles: Vec<String> = drain_insert_history(&mut rx)
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .filter_map(|text| text.trim().lines().next().map(str::to_string))
+        .collect();
+    assert_eq!(
+        titles,
+        vec![
+            "• 🏗  Team created".to_string(),
+            "• 2 team updates  ctrl + t to view".to_string(),
+            "• ⚠️  Team tool failed".to_string(),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn task_assignee_picker_reassigns_the_task() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(Some("gpt-5")).await;
+    chat.replay_initial_messages(vec![
+        team_created(TeamDisplayMode::Inline),
+        team_member_added("alice"),
+        team_member_added("bob"),
+        EventMsg::TeamTaskCreated(TeamTaskEvent {
+            team_name: "zeta".to_string(),
+            task: TeamTaskInfo {
+                id: "task-1".to_string(),
+                title: "Write docs".to_string(),
+                description: None,
+                status: TeamTaskStatus::InProgress,
+                assigned_to: Some("alice".to_string()),
+                depends_on: Vec::new(),
+                result: None,
+                created_at: None,
+            },
+        }),
+    ]);
+    chat.flush_active_cell();
+    while rx.try_recv().is_ok() {}
+
+    chat.open_task_assignee_picker("task-1");
+    let popup = render_bottom_popup(&chat, 80);
+    assert_snapshot!("task_assignee_picker", popup);
+    chat.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    let mut reassigned = None;
+    while let Ok(event) = rx.try_recv() {
+        if let AppEvent::CodexOp(op @ Op::ReassignTeamTask { .. }) = event {
+            reassigned = Some(op);
+        }
+    }
+    assert_eq!(
+        reassigned,
+        Some(Op::ReassignTeamTask {
+            team_name: "zeta".to_string(),
+            task_id: "task-1".to_string(),
+            assigned_to: "bob".to_string(),
+        })
+    );
+}
+
+#[tokio::test]
+async fn teammate_action_confirmation_sends_the_op_once_confirmed() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(Some("gpt-5")).await;
+    chat.replay_initial_messages(vec![team_created(TeamDisplayMode::Inline)]);
+    while rx.try_recv().is_ok() {}
+
+    let team_ops = |rx: &mut tokio::sync::mpsc::UnboundedReceiver<AppEvent>| {
+        let mut ops = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if let AppEvent::CodexOp(
+                op @ (Op::ShutdownTeammate { .. } | Op::RestartTeammate { .. }),
+            ) = event
+            {
+                ops.push(op);
+            }
+        }
+        ops
+    };
+
+    chat.open_teammate_action_confirmation("alice", TeammateAction::Shutdown);
+    let popup = render_bottom_popup(&chat, 80);
+    assert_snapshot!("teammate_shutdown_confirmation", popup);
+    chat.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert_eq!(team_ops(&mut rx), Vec::<Op>::new());
+
+    chat.open_teammate_action_confirmation("alice", TeammateAction::Restart);
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert_eq!(
+        team_ops(&mut rx),
+        vec![Op::RestartTeammate {
+            team_name: "zeta".to_string(),
+            name: "alice".to_string(),
+        }]
+    );
+}
+
+#[tokio::test]
+async fn dormant_teams_prompt_offers_resume_and_archive() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(Some("gpt-5")).await;
+    let teams = vec![
+        DormantTeam {
+            name: "alpha".to_string(),
+            rollout_path: Some(PathBuf::from("/tmp/rollout-alpha.jsonl")),
+        },
+        DormantTeam {
+            name: "beta".to_string(),
+            rollout_path: None,
+        },
+    ];
+
+    chat.open_dormant_teams_prompt(teams.clone());
+    let popup = render_bottom_popup(&chat, 80);
+    assert_snapshot!("dormant_teams_prompt", popup);
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert_matches!(rx.try_recv(), Ok(AppEvent::ArchiveTeam(name)) if name == "beta");
+
+    chat.open_dormant_teams_prompt(teams);
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert_matches!(
+        rx.try_recv(),
+        Ok(AppEvent::ResumeSession(path)) if path == PathBuf::from("/tmp/rollout-alpha.jsonl")
+    );
+}
+
+#[tokio::test]
+async fn at_mentioned_teammate_message_goes_to_their_inbox() {
+    let (mut chat, _rx, mut op_rx) = make_chatwidget_manual(Some("gpt-5")).await;
+    chat.thread_id = Some(ThreadId::new());
+    chat.replay_initial_messages(vec![
+        team_created(TeamDisplayMode::Inline),
+        team_member_added("alice"),
+    ]);
+
+    chat.bottom_pane
+        .set_composer_text("@alice please review".to_string(), Vec::new(), Vec::new());
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    let ops: Vec<Op> = std::iter::from_fn(|| op_rx.try_recv().ok()).collect();
+    assert!(
+        ops.contains(&Op::SendTeammateMessage {
+            team_name: "zeta".to_string(),
+            to: "alice".to_string(),
+            content: "please review".to_string(),
+        }),
+        "{ops:?}"
+    );
+    assert!(
+        !ops.iter().any(|op| matches!(op, Op::UserTurn { .. })),
+        "{ops:?}"
+    );
+}
+
+#[tokio::test]
+async fn compact_team_stays_out_of_the_transcript() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(Some("gpt-5")).await;
+    chat.config.tui_status_line = Some(Vec::new());
+    chat.replay_initial_messages(vec![
+        team_created(TeamDisplayMode::Compact),
+        team_member_added("alice"),
+        EventMsg::TeamError(TeamErrorEvent {
+            tool_name: "assign_task".to_string(),
+            team_name: Some("zeta".to_string()),
+            code: TeamErrorCode::TaskNotFound,
+            message: "task 'task-9' not found".to_string(),
+        }),
+    ]);
+
+    assert_eq!(chat.team_state.members.len(), 1);
+    assert!(chat.active_cell.is_none());
+    assert!(drain_insert_history(&mut rx).is_empty());
+    let popup = render_bottom_popup(&chat, 80);
+    assert_snapshot!("compact_team_status_line", popup);
+
+    chat.replay_initial_messages(vec![EventMsg::TeamCleanup(TeamCleanupEvent {
+        team_name: "zeta".to_string(),
+        leader_thread_id: ThreadId::new(),
+    })]);
+    assert!(drain_insert_history(&mut rx).is_empty());
+    assert!(!chat.team_state.is_active());
+}
+
+#[tokio::test]
+async fn team_escalation_opens_answer_prompt() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(Some("gpt-5")).await;
+    let escalation = TeamEscalationEvent {
+        team_name: "zeta".to_string(),
+        escalation_id: "escalation-1".to_string(),
+        from: "alice".to_string(),
+        question: "Drop the legacy API?".to_string(),
+        options: vec!["yes".to_string(), "no".to_string()],
+    };
+
+    chat.handle_codex_event(Event {
+        id: "escalation".into(),
+        msg: EventMsg::TeamEscalationRequested(escalation.clone()),
+    });
+    let popup = render_bottom_popup(&chat, 80);
+    assert_snapshot!("team_escalation_prompt", popup);
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let events: Vec<AppEvent> = std::iter::from_fn(|| rx.try_recv().ok()).collect();
+    assert!(
+        events.iter().any(|event| matches!(
+            event,
+            AppEvent::CodexOp(Op::AnswerTeamEscalation {
+                team_name,
+                escalation_id,
+                answer,
+            }) if team_name == "zeta" && escalation_id == "escalation-1" && answer == "no"
+        )),
+        "{events:?}"
+    );
+
+    chat.show_team_escalation_answer_prompt(escalation);
+    chat.handle_paste("  only after the 2.0 release  ".to_string());
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert_matches!(
+        rx.try_recv(),
+        Ok(AppEvent::CodexOp(Op::AnswerTeamEscalation { answer, .. }))
+            if answer == "only after the 2.0 release"
+    );
+}
+
 #[tokio::test]
 async fn plan_implementation_popup_skips_when_messages_queued() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(Some("gpt-5")).await;
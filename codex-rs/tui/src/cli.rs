@@ -49,6 +49,16 @@ pub struct Cli {
     #[clap(skip)]
     pub fork_show_all: bool,
 
+    /// Internal: follow a team led from another terminal instead of starting
+    /// a session. Set by the top-level `codex team attach <NAME>` wrapper.
+    #[clap(skip)]
+    pub attach_team: Option<String>,
+
+    /// Internal: step through a team trace instead of starting a session.
+    /// Set by the top-level `codex team replay --tui` wrapper.
+    #[clap(skip)]
+    pub replay_trace: Option<PathBuf>,
+
     /// Model the agent should use.
     #[arg(long, short = 'm')]
     pub model: Option<String>,
@@ -87,7 +87,10 @@ mod markdown_stream;
 mod mention_codec;
 mod model_migration;
 mod multi_agents;
+mod team_attach;
 mod team_events;
+mod team_task_overlay;
+mod teammate_panes;
 mod notifications;
 pub mod onboarding;
 mod oss_selection;
@@ -458,6 +461,29 @@ async fn run_ratatui_app(
 
     let mut tui = Tui::new(terminal);
 
+    let team_view = if let Some(team_name) = cli.attach_team.as_deref() {
+        Some(team_attach::run_team_attach(&mut tui, &initial_config, team_name).await)
+    } else if let Some(trace) = cli.replay_trace.as_deref() {
+        Some(team_attach::run_team_replay(&mut tui, &initial_config, trace).await)
+    } else {
+        None
+    };
+    if let Some(result) = team_view {
+        restore();
+        let _ = tui.terminal.clear();
+        let exit_reason = match result {
+            Ok(()) => ExitReason::UserRequested,
+            Err(err) => ExitReason::Fatal(err.to_string()),
+        };
+        return Ok(AppExitInfo {
+            token_usage: codex_core::protocol::TokenUsage::default(),
+            thread_id: None,
+            thread_name: None,
+            update_action: None,
+            exit_reason,
+        });
+    }
+
     #[cfg(not(debug_assertions))]
     {
         use crate::update_prompt::UpdatePromptOutcome;
@@ -27,6 +27,8 @@ use crate::render::Insets;
 use crate::render::renderable::InsetRenderable;
 use crate::render::renderable::Renderable;
 use crate::style::user_message_style;
+use crate::team_events::TeamState;
+use crate::team_task_overlay::TeamTaskOverlay;
 use crate::tui;
 use crate::tui::TuiEvent;
 use crossterm::event::KeyCode;
@@ -48,6 +50,7 @@ use ratatui::widgets::Wrap;
 pub(crate) enum Overlay {
     Transcript(TranscriptOverlay),
     Static(StaticOverlay),
+    TeamTasks(TeamTaskOverlay),
 }
 
 impl Overlay {
@@ -59,6 +62,66 @@ impl Overlay {
         Self::Static(StaticOverlay::with_title(lines, title))
     }
 
+    /// Also close a static overlay on `key`, so the key that opened it
+    /// toggles it.
+    pub(crate) fn closed_by(mut self, key: KeyBinding) -> Self {
+        match &mut self {
+            Overlay::Static(overlay) => overlay.close_key = Some(key),
+            Overlay::TeamTasks(overlay) => overlay.overlay.close_key = Some(key),
+            Overlay::Transcript(_) => {}
+        }
+        self
+    }
+
+    /// Switch a static overlay to `other` on `key`, and back again on the same
+    /// key. `hint` and `back_hint` describe the switch in the key hints.
+    pub(crate) fn linked_to(
+        mut self,
+        key: KeyBinding,
+        hint: &'static str,
+        back_hint: &'static str,
+        other: Overlay,
+    ) -> Self {
+        if let (Overlay::Static(overlay), Overlay::Static(other)) = (&mut self, other) {
+            overlay.linked = Some(LinkedOverlay {
+                key,
+                hint,
+                back_hint,
+                overlay: Box::new(other),
+            });
+        }
+        self
+    }
+
+    /// A static overlay of `lines` in which Up/Down select one of `rows`
+    /// (line index and id) instead of scrolling. The key of each of `actions`
+    /// closes the overlay and runs the action on the id of the selected row.
+    pub(crate) fn new_static_with_selection(
+        lines: Vec<Line<'static>>,
+        title: String,
+        rows: Vec<(usize, String)>,
+        actions: Vec<OverlayAction>,
+    ) -> Self {
+        if rows.is_empty() {
+            return Self::new_static_with_lines(lines, title);
+        }
+        let mut overlay = StaticOverlay::with_renderables(Vec::new(), title);
+        overlay.selection = Some(OverlaySelection {
+            lines,
+            rows,
+            selected: 0,
+            actions,
+        });
+        overlay.select(0);
+        Self::Static(overlay)
+    }
+
+    /// The task overlay of a team in `state`, whose tasks can be searched and
+    /// filtered; see [`Overlay::new_static_with_selection`] for `actions`.
+    pub(crate) fn new_team_tasks(state: TeamState, actions: Vec<OverlayAction>) -> Self {
+        Self::TeamTasks(TeamTaskOverlay::new(state, actions))
+    }
+
     pub(crate) fn new_static_with_renderables(
         renderables: Vec<Box<dyn Renderable>>,
         title: String,
@@ -70,6 +133,7 @@ impl Overlay {
         match self {
             Overlay::Transcript(o) => o.handle_event(tui, event),
             Overlay::Static(o) => o.handle_event(tui, event),
+            Overlay::TeamTasks(o) => o.handle_event(tui, event),
         }
     }
 
@@ -77,6 +141,7 @@ impl Overlay {
         match self {
             Overlay::Transcript(o) => o.is_done(),
             Overlay::Static(o) => o.is_done(),
+            Overlay::TeamTasks(o) => o.is_done(),
         }
     }
 }
@@ -111,7 +176,7 @@ const PAGER_KEY_HINTS: &[(&[KeyBinding], &str)] = &[
 ];
 
 // Render a single line of key hints from (key(s), description) pairs.
-fn render_key_hints(area: Rect, buf: &mut Buffer, pairs: &[(&[KeyBinding], &str)]) {
+pub(crate) fn render_key_hints(area: Rect, buf: &mut Buffer, pairs: &[(&[KeyBinding], &str)]) {
     let mut spans: Vec<Span<'static>> = vec![" ".into()];
     let mut first = true;
     for (keys, desc) in pairs {
@@ -710,6 +775,49 @@ impl TranscriptOverlay {
 pub(crate) struct StaticOverlay {
     view: PagerView,
     is_done: bool,
+    close_key: Option<KeyBinding>,
+    linked: Option<LinkedOverlay>,
+    selection: Option<OverlaySelection>,
+}
+
+/// Selectable rows of a static overlay; see
+/// [`Overlay::new_static_with_selection`].
+struct OverlaySelection {
+    lines: Vec<Line<'static>>,
+    /// Index into `lines` of each selectable row, with its id.
+    rows: Vec<(usize, String)>,
+    selected: usize,
+    actions: Vec<OverlayAction>,
+}
+
+/// What a key does to the selected row of a static overlay; `hint`
+/// describes it in the key hints.
+pub(crate) struct OverlayAction {
+    key: KeyBinding,
+    hint: &'static str,
+    on_choose: Box<dyn Fn(&str) + Send + Sync>,
+}
+
+impl OverlayAction {
+    pub(crate) fn new(
+        key: KeyBinding,
+        hint: &'static str,
+        on_choose: impl Fn(&str) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            key,
+            hint,
+            on_choose: Box::new(on_choose),
+        }
+    }
+}
+
+/// A static overlay that another one switches to; see [`Overlay::linked_to`].
+struct LinkedOverlay {
+    key: KeyBinding,
+    hint: &'static str,
+    back_hint: &'static str,
+    overlay: Box<StaticOverlay>,
 }
 
 impl StaticOverlay {
@@ -722,14 +830,89 @@ impl StaticOverlay {
         Self {
             view: PagerView::new(renderables, title, 0),
             is_done: false,
+            close_key: None,
+            linked: None,
+            selection: None,
+        }
+    }
+
+    /// A static overlay whose content is set with [`Self::set_selection`];
+    /// see [`Overlay::new_static_with_selection`].
+    pub(crate) fn with_actions(title: String, actions: Vec<OverlayAction>) -> Self {
+        let mut overlay = Self::with_renderables(Vec::new(), title);
+        overlay.selection = Some(OverlaySelection {
+            lines: Vec::new(),
+            rows: Vec::new(),
+            selected: 0,
+            actions,
+        });
+        overlay
+    }
+
+    /// Replace the content of an overlay made with [`Self::with_actions`],
+    /// selecting the first of `rows` and scrolling back to the top.
+    pub(crate) fn set_selection(&mut self, lines: Vec<Line<'static>>, rows: Vec<(usize, String)>) {
+        if let Some(selection) = &mut self.selection {
+            selection.lines = lines;
+            selection.rows = rows;
+        }
+        self.view.scroll_offset = 0;
+        self.select(0);
+    }
+
+    /// Select row `index` of the selection, rendering it highlighted and
+    /// scrolling it into view. Without rows, nothing is highlighted.
+    fn select(&mut self, index: usize) {
+        let Some(selection) = &mut self.selection else {
+            return;
+        };
+        selection.selected = index.min(selection.rows.len().saturating_sub(1));
+        let highlighted = selection
+            .rows
+            .get(selection.selected)
+            .map_or(usize::MAX, |(line, _)| *line);
+        self.view.renderables = selection
+            .lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let line = if i == highlighted {
+                    line.clone().reversed()
+                } else {
+                    line.clone()
+                };
+                let paragraph = Paragraph::new(line).wrap(Wrap { trim: false });
+                Box::new(CachedRenderable::new(paragraph)) as Box<dyn Renderable>
+            })
+            .collect();
+        if highlighted != usize::MAX {
+            self.view.scroll_chunk_into_view(highlighted);
         }
     }
 
     fn render_hints(&self, area: Rect, buf: &mut Buffer) {
         let line1 = Rect::new(area.x, area.y, area.width, 1);
         let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
-        render_key_hints(line1, buf, PAGER_KEY_HINTS);
-        let pairs: Vec<(&[KeyBinding], &str)> = vec![(&[KEY_Q], "to quit")];
+        let mut pairs: Vec<(&[KeyBinding], &str)> = vec![(&[KEY_Q], "to quit")];
+        if let Some(selection) = &self.selection {
+            render_key_hints(
+                line1,
+                buf,
+                &[
+                    (&[KEY_UP, KEY_DOWN], "to select"),
+                    (&[KEY_PAGE_UP, KEY_PAGE_DOWN], "to page"),
+                    (&[KEY_HOME, KEY_END], "to jump"),
+                ],
+            );
+            for action in &selection.actions {
+                pairs.push((std::slice::from_ref(&action.key), action.hint));
+            }
+        } else {
+            render_key_hints(line1, buf, PAGER_KEY_HINTS);
+        }
+        if let Some(linked) = &self.linked {
+            pairs.push((std::slice::from_ref(&linked.key), linked.hint));
+        }
         render_key_hints(line2, buf, &pairs);
     }
 
@@ -746,7 +929,64 @@ impl StaticOverlay {
     pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
         match event {
             TuiEvent::Key(key_event) => match key_event {
-                e if KEY_Q.is_press(e) || KEY_CTRL_C.is_press(e) => {
+                e if KEY_Q.is_press(e)
+                    || KEY_ESC.is_press(e)
+                    || KEY_CTRL_C.is_press(e)
+                    || self.close_key.is_some_and(|key| key.is_press(e)) =>
+                {
+                    self.is_done = true;
+                    Ok(())
+                }
+                e if self
+                    .linked
+                    .as_ref()
+                    .is_some_and(|linked| linked.key.is_press(e)) =>
+                {
+                    if let Some(linked) = self.linked.take() {
+                        let previous = std::mem::replace(self, *linked.overlay);
+                        self.linked = Some(LinkedOverlay {
+                            key: linked.key,
+                            hint: linked.back_hint,
+                            back_hint: linked.hint,
+                            overlay: Box::new(previous),
+                        });
+                        tui.frame_requester().schedule_frame();
+                    }
+                    Ok(())
+                }
+                e if self.selection.is_some()
+                    && (KEY_UP.is_press(e)
+                        || KEY_K.is_press(e)
+                        || KEY_DOWN.is_press(e)
+                        || KEY_J.is_press(e)) =>
+                {
+                    if let Some(selection) = &self.selection {
+                        let index = if KEY_UP.is_press(e) || KEY_K.is_press(e) {
+                            selection.selected.saturating_sub(1)
+                        } else {
+                            selection.selected + 1
+                        };
+                        self.select(index);
+                        tui.frame_requester().schedule_frame();
+                    }
+                    Ok(())
+                }
+                e if self.selection.as_ref().is_some_and(|selection| {
+                    !selection.rows.is_empty()
+                        && selection
+                            .actions
+                            .iter()
+                            .any(|action| action.key.is_press(e))
+                }) =>
+                {
+                    if let Some(selection) = &self.selection
+                        && let Some(action) = selection
+                            .actions
+                            .iter()
+                            .find(|action| action.key.is_press(e))
+                    {
+                        (action.on_choose)(&selection.rows[selection.selected].1);
+                    }
                     self.is_done = true;
                     Ok(())
                 }
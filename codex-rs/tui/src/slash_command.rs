@@ -31,6 +31,8 @@ pub enum SlashCommand {
     Plan,
     Collab,
     Agent,
+    Team,
+    Task,
     // Undo,
     Diff,
     Mention,
@@ -84,6 +86,8 @@ impl SlashCommand {
             SlashCommand::Plan => "switch to Plan mode",
             SlashCommand::Collab => "change collaboration mode (experimental)",
             SlashCommand::Agent => "switch the active agent thread",
+            SlashCommand::Team => "show the agent team dashboard, or its messages: /team messages",
+            SlashCommand::Task => "add a task to the agent team: /task new",
             SlashCommand::Approvals => "choose what Codex is allowed to do",
             SlashCommand::Permissions => "choose what Codex is allowed to do",
             SlashCommand::ElevateSandbox => "set up elevated agent sandbox",
@@ -113,6 +117,8 @@ impl SlashCommand {
                 | SlashCommand::Rename
                 | SlashCommand::Plan
                 | SlashCommand::SandboxReadRoot
+                | SlashCommand::Team
+                | SlashCommand::Task
         )
     }
 
@@ -154,6 +160,8 @@ impl SlashCommand {
             SlashCommand::TestApproval => true,
             SlashCommand::Collab => true,
             SlashCommand::Agent => true,
+            SlashCommand::Team => true,
+            SlashCommand::Task => true,
             SlashCommand::Statusline => false,
         }
     }
@@ -0,0 +1,373 @@
+//! `codex team attach`: a read-only view of a team led from another terminal.
+//!
+//! The team's `events.jsonl` is replayed into a [`TeamState`] and then polled
+//! for new events, so the dashboard and the task list stay current while the
+//! team runs elsewhere. Nothing is ever sent to the team.
+//!
+//! `codex team replay --tui` shows the same views for a finished run, moving
+//! through its trace one entry at a time instead of following it.
+
+use crate::key_hint;
+use crate::team_events::TaskFilter;
+use crate::team_events::TeamState;
+use crate::tui::Tui;
+use crate::tui::TuiEvent;
+use codex_core::config::Config;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::TeamCreatedEvent;
+use codex_core::teams::default_teams_root;
+use codex_core::teams::event_log::TeamEventLog;
+use codex_core::teams::event_log::TeamTraceEntry;
+use codex_core::teams::replay::describe;
+use codex_core::teams::team_manager::TeamManager;
+use color_eyre::eyre::Result;
+use color_eyre::eyre::eyre;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use ratatui::layout::Constraint;
+use ratatui::layout::Layout;
+use ratatui::style::Stylize as _;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget as _;
+use ratatui::widgets::Wrap;
+use std::path::Path;
+use std::time::Duration;
+use tokio_stream::StreamExt;
+
+/// How often the event log is checked for new events.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum View {
+    Dashboard,
+    Tasks,
+}
+
+impl View {
+    fn toggle(self) -> Self {
+        match self {
+            View::Dashboard => View::Tasks,
+            View::Tasks => View::Dashboard,
+        }
+    }
+
+    fn lines(self, state: &TeamState, config: &Config) -> Vec<Line<'static>> {
+        match self {
+            View::Dashboard => state.dashboard_lines(&config.teams),
+            View::Tasks => state.task_overlay_lines(&TaskFilter::default()),
+        }
+    }
+}
+
+/// Follow `team_name` until the user quits.
+pub(crate) async fn run_team_attach(tui: &mut Tui, config: &Config, team_name: &str) -> Result<()> {
+    let manager = TeamManager::new(default_teams_root());
+    if !manager.team_exists(team_name).await {
+        return Err(eyre!("No team named '{team_name}' found."));
+    }
+    let team = manager.load_config(team_name).await?;
+    let mut state = TeamState::default();
+    state.on_team_created(&TeamCreatedEvent {
+        team_name: team.name,
+        leader_thread_id: team.leader_thread_id,
+        description: team.description,
+        display_mode: team.display_mode,
+    });
+    let log = TeamEventLog::new(manager.events_path(team_name));
+    let mut offset = 0;
+    let mut gone = false;
+    let mut view = View::Dashboard;
+    let mut scroll: u16 = 0;
+
+    let _ = tui.enter_alt_screen();
+    let requester = tui.frame_requester();
+    requester.schedule_frame();
+    let mut tui_events = tui.event_stream().fuse();
+    let mut poll = tokio::time::interval(POLL_INTERVAL);
+
+    loop {
+        tokio::select! {
+            Some(ev) = tui_events.next() => match ev {
+                TuiEvent::Key(key) if key.kind != KeyEventKind::Release => {
+                    match key.code {
+                        KeyCode::Char('q') | KeyCode::Esc => break,
+                        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            break;
+                        }
+                        KeyCode::Tab => {
+                            view = view.toggle();
+                            scroll = 0;
+                        }
+                        KeyCode::Up => scroll = scroll.saturating_sub(1),
+                        KeyCode::Down => scroll = scroll.saturating_add(1),
+                        _ => continue,
+                    }
+                    requester.schedule_frame();
+                }
+                TuiEvent::Draw => {
+                    let mut hint = vec![
+                        key_hint::plain(KeyCode::Tab).into(),
+                        " dashboard/tasks".dim(),
+                        "    ".dim(),
+                        key_hint::plain(KeyCode::Up).into(),
+                        "/".dim(),
+                        key_hint::plain(KeyCode::Down).into(),
+                        " scroll".dim(),
+                        "    ".dim(),
+                        key_hint::plain(KeyCode::Char('q')).into(),
+                        " quit".dim(),
+                        "    ".dim(),
+                    ];
+                    hint.push(if gone {
+                        "team no longer exists".red()
+                    } else {
+                        "read-only".dim()
+                    });
+                    draw(tui, view.lines(&state, config), scroll, hint)?;
+                }
+                _ => {}
+            },
+            _ = poll.tick(), if !gone => {
+                match log.read_from(offset).await {
+                    Ok((records, next)) => {
+                        offset = next;
+                        if !records.is_empty() {
+                            for record in &records {
+                                apply_event(&mut state, &record.msg);
+                            }
+                            requester.schedule_frame();
+                        }
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                        // The log appears with the team's first event; until
+                        // then only a missing team directory means it is gone.
+                        if !manager.team_exists(team_name).await {
+                            gone = true;
+                            requester.schedule_frame();
+                        }
+                    }
+                    Err(e) => tracing::debug!("failed to read events of team {team_name}: {e}"),
+                }
+            }
+        }
+    }
+
+    let _ = tui.leave_alt_screen();
+    Ok(())
+}
+
+/// Step through the trace at `path` until the user quits.
+pub(crate) async fn run_team_replay(tui: &mut Tui, config: &Config, path: &Path) -> Result<()> {
+    let (entries, _) = TeamEventLog::new(path.to_path_buf())
+        .read_trace_from(0)
+        .await
+        .map_err(|e| eyre!("failed to read the team trace {}: {e}", path.display()))?;
+    if entries.is_empty() {
+        return Err(eyre!("The team trace {} is empty.", path.display()));
+    }
+    let mut position = 0;
+    let mut state = TeamState::default();
+    let mut view = View::Dashboard;
+    let mut scroll: u16 = 0;
+
+    let _ = tui.enter_alt_screen();
+    let requester = tui.frame_requester();
+    requester.schedule_frame();
+    let mut tui_events = tui.event_stream().fuse();
+
+    while let Some(ev) = tui_events.next().await {
+        match ev {
+            TuiEvent::Key(key) if key.kind != KeyEventKind::Release => {
+                let target = match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => break,
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break,
+                    KeyCode::Right | KeyCode::Char(' ') => (position + 1).min(entries.len()),
+                    KeyCode::Left => position.saturating_sub(1),
+                    KeyCode::Home => 0,
+                    KeyCode::End => entries.len(),
+                    KeyCode::Tab => {
+                        view = view.toggle();
+                        scroll = 0;
+                        position
+                    }
+                    KeyCode::Up => {
+                        scroll = scroll.saturating_sub(1);
+                        position
+                    }
+                    KeyCode::Down => {
+                        scroll = scroll.saturating_add(1);
+                        position
+                    }
+                    _ => continue,
+                };
+                if target < position {
+                    // Events only move forward; going back replays from the start.
+                    state = TeamState::default();
+                    apply_entries(&mut state, &entries[..target]);
+                } else {
+                    apply_entries(&mut state, &entries[position..target]);
+                }
+                position = target;
+                requester.schedule_frame();
+            }
+            TuiEvent::Draw => {
+                let step = match position.checked_sub(1).map(|i| &entries[i]) {
+                    Some(entry) => {
+                        format!("step {position}/{}: {}", entries.len(), describe(entry))
+                    }
+                    None => format!("step 0/{}", entries.len()),
+                };
+                let hint = vec![
+                    key_hint::plain(KeyCode::Left).into(),
+                    "/".dim(),
+                    key_hint::plain(KeyCode::Right).into(),
+                    " step".dim(),
+                    "    ".dim(),
+                    key_hint::plain(KeyCode::Tab).into(),
+                    " dashboard/tasks".dim(),
+                    "    ".dim(),
+                    key_hint::plain(KeyCode::Char('q')).into(),
+                    " quit".dim(),
+                    "    ".dim(),
+                    step.into(),
+                ];
+                draw(tui, view.lines(&state, config), scroll, hint)?;
+            }
+            _ => {}
+        }
+    }
+
+    let _ = tui.leave_alt_screen();
+    Ok(())
+}
+
+fn draw(
+    tui: &mut Tui,
+    lines: Vec<Line<'static>>,
+    scroll: u16,
+    hint: Vec<Span<'static>>,
+) -> Result<()> {
+    let height = tui.terminal.size()?.height;
+    tui.draw(height, |frame| {
+        let [body, footer] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .scroll((scroll, 0))
+            .render(body, frame.buffer_mut());
+        frame.render_widget_ref(Line::from(hint), footer);
+    })?;
+    Ok(())
+}
+
+fn apply_entries(state: &mut TeamState, entries: &[TeamTraceEntry]) {
+    for entry in entries {
+        if let TeamTraceEntry::Event(record) = entry {
+            apply_event(state, &record.msg);
+        }
+    }
+}
+
+/// Update `state` with a logged team event, as the leader's chat widget
+/// does for the events it receives.
+fn apply_event(state: &mut TeamState, msg: &EventMsg) {
+    match msg {
+        EventMsg::TeamCreated(ev) => state.on_team_created(ev),
+        EventMsg::TeamMemberAdded(ev) => state.on_member_added(ev),
+        EventMsg::TeamMemberRemoved(ev) => state.on_member_removed(ev),
+        EventMsg::TeamMemberStatusChanged(ev) => state.on_member_status_changed(ev),
+        EventMsg::TeamTaskCreated(ev) => state.on_task_created(ev),
+        EventMsg::TeamTaskUpdated(ev) => state.on_task_updated(ev),
+        EventMsg::TeamMessageSent(ev) => state.on_message_sent(ev),
+        EventMsg::TeamRenamed(ev) => state.on_team_renamed(ev),
+        EventMsg::TeamLeaderChanged(ev) => state.on_leader_changed(ev),
+        EventMsg::TeamDiffSummary(ev) => state.on_diff_summary(ev),
+        EventMsg::TeamMemberTokenUsage(ev) => state.on_member_token_usage(ev),
+        EventMsg::TeamProgress(ev) => state.on_progress(ev),
+        EventMsg::TeamCleanup(_) => state.on_cleanup(),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::protocol::AgentStatus;
+    use codex_core::protocol::TeamDisplayMode;
+    use codex_core::protocol::TeamMemberEvent;
+    use codex_core::protocol::TeamMemberInfo;
+    use codex_core::protocol::TeamTaskEvent;
+    use codex_core::protocol::TeamTaskInfo;
+    use codex_core::protocol::TeamTaskStatus;
+    use codex_protocol::ThreadId;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn replaying_the_log_rebuilds_members_and_tasks() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = TeamEventLog::new(dir.path().join("events.jsonl"));
+        let leader = ThreadId::new();
+        let member = TeamMemberInfo {
+            name: "writer".to_string(),
+            thread_id: ThreadId::new(),
+            role: None,
+            status: AgentStatus::Running,
+        };
+        let task = TeamTaskInfo {
+            id: "1".to_string(),
+            title: "Outline".to_string(),
+            description: None,
+            status: TeamTaskStatus::Pending,
+            assigned_to: Some("writer".to_string()),
+            depends_on: Vec::new(),
+            result: None,
+            created_at: None,
+        };
+        let events = [
+            EventMsg::TeamCreated(TeamCreatedEvent {
+                team_name: "docs".to_string(),
+                leader_thread_id: leader,
+                description: None,
+                display_mode: TeamDisplayMode::default(),
+            }),
+            EventMsg::TeamMemberAdded(TeamMemberEvent {
+                team_name: "docs".to_string(),
+                member: member.clone(),
+                previous_status: None,
+            }),
+            EventMsg::TeamTaskCreated(TeamTaskEvent {
+                team_name: "docs".to_string(),
+                task: task.clone(),
+            }),
+            EventMsg::TeamTaskUpdated(TeamTaskEvent {
+                team_name: "docs".to_string(),
+                task: TeamTaskInfo {
+                    status: TeamTaskStatus::Completed,
+                    ..task.clone()
+                },
+            }),
+        ];
+        for msg in &events {
+            log.append(leader, msg).await.unwrap();
+        }
+
+        let mut state = TeamState::default();
+        let (records, _) = log.read_from(0).await.unwrap();
+        for record in &records {
+            apply_event(&mut state, &record.msg);
+        }
+
+        assert_eq!(state.team_name.as_deref(), Some("docs"));
+        assert_eq!(state.members, vec![member]);
+        assert_eq!(
+            state.tasks,
+            vec![TeamTaskInfo {
+                status: TeamTaskStatus::Completed,
+                ..task
+            }]
+        );
+    }
+}
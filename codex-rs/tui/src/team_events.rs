@@ -2,21 +2,36 @@
 //!
 //! Follows the same pattern as `multi_agents.rs`: each handler function
 //! returns a `PlainHistoryCell` that is inserted into the chat history.
+//! Consecutive routine updates (membership, task and message events) are
+//! folded into one [`TeamUpdatesCell`].
 
+use crate::exec_command::strip_bash_lc_and_escape;
+use crate::history_cell::HistoryCell;
 use crate::history_cell::PlainHistoryCell;
 use crate::render::line_utils::prefix_lines;
+use crate::status_indicator_widget::fmt_elapsed_compact;
+use crate::text_formatting::truncate_text;
 use codex_core::protocol::{
-    TeamCleanupEvent, TeamCreatedEvent, TeamMemberEvent, TeamMessageEvent, TeamTaskEvent,
+    TeamBudgetExhaustedEvent, TeamCleanupEvent, TeamCreatedEvent, TeamEditConflictEvent,
+    TeamErrorCode, TeamErrorEvent, TeamEscalationEvent, TeamLeaderChangedEvent, TeamMemberEvent,
+    TeamMemberLivenessLostEvent, TeamMemberOutput, TeamMemberOutputEvent, TeamMemberRestartedEvent,
+    TeamMessageEvent, TeamRenamedEvent, TeamTaskEvent, TeamTaskUnblockedEvent,
 };
+use ratatui::style::Color;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
 use ratatui::style::Stylize;
 use ratatui::text::Line;
 use ratatui::text::Span;
 
 pub(crate) fn team_created(ev: TeamCreatedEvent) -> PlainHistoryCell {
-    let details = vec![
+    let mut details = vec![
         detail_line("team", ev.team_name),
         detail_line("leader", ev.leader_thread_id.to_string()),
     ];
+    if let Some(description) = ev.description {
+        details.push(detail_line("goal", description));
+    }
     team_event("🏗  Team created", details)
 }
 
@@ -46,6 +61,141 @@ pub(crate) fn team_member_removed(ev: TeamMemberEvent) -> PlainHistoryCell {
     team_event("🚪  Teammate left", details)
 }
 
+pub(crate) fn team_member_status_changed(ev: TeamMemberEvent) -> PlainHistoryCell {
+    let title = match ev.member.status {
+        codex_core::protocol::AgentStatus::Running => "▶️  Teammate started",
+        codex_core::protocol::AgentStatus::Shutdown => "⏹️  Teammate shut down",
+        _ => "🔔  Teammate finished",
+    };
+    let mut details = vec![
+        detail_line("team", ev.team_name),
+        detail_line("teammate", ev.member.name),
+        detail_line("status", status_span(&ev.member.status)),
+    ];
+    if let Some(previous_status) = &ev.previous_status {
+        details.push(detail_line("was", status_span(previous_status)));
+    }
+    match ev.member.status {
+        codex_core::protocol::AgentStatus::Completed(Some(message)) => {
+            details.push(detail_line("last message", Span::from(message).dim()));
+        }
+        codex_core::protocol::AgentStatus::Errored(error) => {
+            details.push(detail_line("error", Span::from(error).red()));
+        }
+        _ => {}
+    }
+    team_event(title, details)
+}
+
+pub(crate) fn team_budget_exhausted(ev: TeamBudgetExhaustedEvent) -> PlainHistoryCell {
+    let details = vec![
+        detail_line("team", ev.team_name),
+        detail_line("teammate", ev.member_name),
+        detail_line("thread", ev.thread_id.to_string()),
+        detail_line("reason", Span::from(ev.reason).red()),
+    ];
+    team_event("⛽  Teammate out of budget", details)
+}
+
+pub(crate) fn team_member_liveness_lost(ev: TeamMemberLivenessLostEvent) -> PlainHistoryCell {
+    let details = vec![
+        detail_line("team", ev.team_name),
+        detail_line("teammate", ev.member_name),
+        detail_line("thread", ev.thread_id.to_string()),
+        detail_line(
+            "silent for",
+            Span::from(fmt_elapsed_compact(ev.silent_secs)).red(),
+        ),
+    ];
+    team_event("💤  Teammate unresponsive", details)
+}
+
+pub(crate) fn team_error(ev: TeamErrorEvent) -> PlainHistoryCell {
+    let code = match ev.code {
+        TeamErrorCode::InvalidArguments => "invalid_arguments",
+        TeamErrorCode::TeamNotFound => "team_not_found",
+        TeamErrorCode::MemberNotFound => "member_not_found",
+        TeamErrorCode::TaskNotFound => "task_not_found",
+        TeamErrorCode::PatchNotFound => "patch_not_found",
+        TeamErrorCode::NotLeader => "not_leader",
+        TeamErrorCode::BudgetExceeded => "budget_exceeded",
+        TeamErrorCode::LimitReached => "limit_reached",
+        TeamErrorCode::SpawnFailed => "spawn_failed",
+        TeamErrorCode::StorageCorrupt => "storage_corrupt",
+        TeamErrorCode::StorageError => "storage_error",
+        TeamErrorCode::Other => "other",
+    };
+    let mut details = vec![detail_line("tool", ev.tool_name)];
+    if let Some(team_name) = ev.team_name {
+        details.push(detail_line("team", team_name));
+    }
+    details.push(detail_line("code", Span::from(code).dim()));
+    details.push(detail_line("error", Span::from(ev.message).red()));
+    team_event("⚠️  Team tool failed", details)
+}
+
+pub(crate) fn team_member_restarted(ev: TeamMemberRestartedEvent) -> PlainHistoryCell {
+    let details = vec![
+        detail_line("team", ev.team_name),
+        detail_line("teammate", ev.member_name),
+        detail_line("thread", ev.thread_id.to_string()),
+        detail_line("attempt", format!("{} of {}", ev.attempt, ev.max_restarts)),
+        detail_line("error", Span::from(ev.error).red()),
+    ];
+    team_event("🔁  Teammate restarted", details)
+}
+
+pub(crate) fn team_edit_conflict(ev: TeamEditConflictEvent) -> PlainHistoryCell {
+    let mut details = vec![detail_line("team", ev.team_name)];
+    details.extend(ev.conflicts.into_iter().map(|conflict| {
+        detail_line(
+            &conflict.path,
+            Span::from(conflict.members.join(", ")).red(),
+        )
+    }));
+    team_event("⚠️  Teammates edited the same files", details)
+}
+
+pub(crate) fn team_escalation_requested(ev: TeamEscalationEvent) -> PlainHistoryCell {
+    let mut details = vec![
+        detail_line("team", ev.team_name),
+        detail_line("teammate", ev.from),
+        detail_line("id", ev.escalation_id),
+        detail_line("question", Span::from(ev.question).bold()),
+    ];
+    if !ev.options.is_empty() {
+        details.push(detail_line("options", ev.options.join(", ")));
+    }
+    team_event("🙋  Teammate needs your answer", details)
+}
+
+/// Cell for forwarded teammate output, titled in the teammate's `accent`
+/// style, or `None` for streamed chunks and finished commands, which the
+/// transcript does not show.
+pub(crate) fn team_member_output(
+    ev: TeamMemberOutputEvent,
+    accent: Option<Style>,
+) -> Option<PlainHistoryCell> {
+    match ev.output {
+        TeamMemberOutput::AgentMessage { message } => Some(accented_team_event(
+            format!("💬  {} says", ev.member_name),
+            accent,
+            message
+                .lines()
+                .map(|line| Line::from(line.to_string()))
+                .collect(),
+        )),
+        TeamMemberOutput::CommandBegin { command, .. } => Some(accented_team_event(
+            format!("⚙️  {} runs", ev.member_name),
+            accent,
+            vec![Line::from(strip_bash_lc_and_escape(&command)).dim()],
+        )),
+        TeamMemberOutput::AgentMessageDelta { .. }
+        | TeamMemberOutput::CommandOutputDelta { .. }
+        | TeamMemberOutput::CommandEnd { .. } => None,
+    }
+}
+
 pub(crate) fn team_task_created(ev: TeamTaskEvent) -> PlainHistoryCell {
     let mut details = vec![
         detail_line("team", ev.team_name),
@@ -69,17 +219,35 @@ pub(crate) fn team_task_updated(ev: TeamTaskEvent) -> PlainHistoryCell {
     if let Some(assignee) = &ev.task.assigned_to {
         details.push(detail_line("assigned_to", assignee.clone()));
     }
+    if let Some(result) = ev.task.result {
+        details.push(detail_line("result", Span::from(result).dim()));
+    }
     team_event("📝  Task updated", details)
 }
 
-pub(crate) fn team_message_sent(ev: TeamMessageEvent) -> PlainHistoryCell {
+pub(crate) fn team_task_unblocked(ev: TeamTaskUnblockedEvent) -> PlainHistoryCell {
+    let mut details = vec![
+        detail_line("team", ev.team_name),
+        detail_line("task", ev.task.title),
+        detail_line("id", ev.task.id),
+        detail_line("unblocked by", ev.unblocked_by),
+    ];
+    if let Some(assignee) = &ev.task.assigned_to {
+        details.push(detail_line("assigned_to", assignee.clone()));
+    }
+    team_event("🔓  Task ready", details)
+}
+
+/// Cell for a team message, titled in the `accent` style of the teammate
+/// that sent it.
+pub(crate) fn team_message_sent(ev: TeamMessageEvent, accent: Option<Style>) -> PlainHistoryCell {
     let details = vec![
         detail_line("team", ev.team_name),
         detail_line("from", ev.from),
         detail_line("to", ev.to),
         detail_line("content", Span::from(ev.content).dim()),
     ];
-    team_event("💬  Team message", details)
+    accented_team_event("💬  Team message", accent, details)
 }
 
 pub(crate) fn team_cleanup(ev: TeamCleanupEvent) -> PlainHistoryCell {
@@ -90,12 +258,107 @@ pub(crate) fn team_cleanup(ev: TeamCleanupEvent) -> PlainHistoryCell {
     team_event("🧹  Team cleaned up", details)
 }
 
+pub(crate) fn team_renamed(ev: TeamRenamedEvent) -> PlainHistoryCell {
+    let details = vec![
+        detail_line("from", ev.old_name),
+        detail_line("to", ev.new_name),
+    ];
+    team_event("✏️  Team renamed", details)
+}
+
+pub(crate) fn team_leader_changed(ev: TeamLeaderChangedEvent) -> PlainHistoryCell {
+    let details = vec![
+        detail_line("team", ev.team_name),
+        detail_line("from", ev.previous_leader_thread_id.to_string()),
+        detail_line("to", ev.leader_thread_id.to_string()),
+    ];
+    team_event("👑  Leadership handed off", details)
+}
+
+/// How many titles a folded [`TeamUpdatesCell`] lists; older updates are
+/// counted.
+const FOLDED_UPDATE_TITLES: usize = 3;
+
+/// Consecutive routine team updates shown as one cell: a count and the titles
+/// of the latest updates in the chat history, every update in full in the
+/// transcript overlay.
+#[derive(Debug)]
+pub(crate) struct TeamUpdatesCell {
+    updates: Vec<PlainHistoryCell>,
+}
+
+impl TeamUpdatesCell {
+    pub(crate) fn new(update: PlainHistoryCell) -> Self {
+        Self {
+            updates: vec![update],
+        }
+    }
+
+    pub(crate) fn push(&mut self, update: PlainHistoryCell) {
+        self.updates.push(update);
+    }
+}
+
+impl HistoryCell for TeamUpdatesCell {
+    fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
+        if let [update] = self.updates.as_slice() {
+            return update.display_lines(width);
+        }
+        let mut lines: Vec<Line<'static>> = vec![
+            vec![
+                Span::from("• ").dim(),
+                Span::from(format!("{} team updates", self.updates.len()))
+                    .bold()
+                    .cyan(),
+                Span::from("  ctrl + t to view").dim(),
+            ]
+            .into(),
+        ];
+        let skip = self.updates.len().saturating_sub(FOLDED_UPDATE_TITLES);
+        let mut titles = Vec::new();
+        if skip > 0 {
+            titles.push(Line::from(format!("… {skip} earlier")).dim());
+        }
+        // The first line of a team event cell is its bulleted title.
+        for update in &self.updates[skip..] {
+            if let Some(mut title) = update.display_lines(width).into_iter().next() {
+                title.spans.remove(0);
+                titles.push(title);
+            }
+        }
+        lines.extend(prefix_lines(titles, "  └ ".dim(), "    ".into()));
+        lines
+    }
+
+    fn transcript_lines(&self, width: u16) -> Vec<Line<'static>> {
+        self.updates
+            .iter()
+            .flat_map(|update| update.display_lines(width))
+            .collect()
+    }
+}
+
 // ── helpers ─────────────────────────────────────────────────────────────
 
 fn team_event(title: impl Into<String>, details: Vec<Line<'static>>) -> PlainHistoryCell {
+    accented_team_event(title, None, details)
+}
+
+/// A team event cell whose title is drawn in `accent`, or in bold cyan like
+/// other team events.
+fn accented_team_event(
+    title: impl Into<String>,
+    accent: Option<Style>,
+    details: Vec<Line<'static>>,
+) -> PlainHistoryCell {
     let title = title.into();
-    let mut lines: Vec<Line<'static>> =
-        vec![vec![Span::from("• ").dim(), Span::from(title).bold().cyan()].into()];
+    let mut lines: Vec<Line<'static>> = vec![
+        vec![
+            Span::from("• ").dim(),
+            Span::styled(title, accent.unwrap_or(TEAM_EVENT_TITLE)),
+        ]
+        .into(),
+    ];
     if !details.is_empty() {
         lines.extend(prefix_lines(details, "  └ ".dim(), "    ".into()));
     }
@@ -117,27 +380,179 @@ fn status_span(status: &codex_core::protocol::AgentStatus) -> Span<'static> {
     }
 }
 
+fn task_status_icon(status: &TeamTaskStatus) -> Span<'static> {
+    match status {
+        TeamTaskStatus::Pending => Span::from("○").dim(),
+        TeamTaskStatus::InProgress => Span::from("◑").cyan().bold(),
+        TeamTaskStatus::Completed => Span::from("●").green(),
+    }
+}
+
+fn task_status_label(status: &TeamTaskStatus) -> &'static str {
+    match status {
+        TeamTaskStatus::Pending => "pending",
+        TeamTaskStatus::InProgress => "in progress",
+        TeamTaskStatus::Completed => "completed",
+    }
+}
+
+/// Estimated cost of `usage` in US dollars at the token prices of `teams`,
+/// or `None` if no prices are set. Cached input is priced as regular input.
+fn estimated_cost_usd(teams: &TeamsConfig, usage: &TokenUsage) -> Option<f64> {
+    if teams.cents_per_million_input_tokens.is_none()
+        && teams.cents_per_million_output_tokens.is_none()
+    {
+        return None;
+    }
+    let cents = |tokens: i64, price: Option<u64>| {
+        tokens.max(0) as f64 * price.unwrap_or(0) as f64 / 1_000_000.0
+    };
+    Some(
+        (cents(usage.input_tokens, teams.cents_per_million_input_tokens)
+            + cents(usage.output_tokens, teams.cents_per_million_output_tokens))
+            / 100.0,
+    )
+}
+
 // ── TeamState ──────────────────────────────────────────────────────────
 
-use codex_core::protocol::{TeamMemberInfo, TeamTaskInfo, TeamTaskStatus};
+use codex_core::config::types::TeamsConfig;
+use codex_core::protocol::AgentStatus;
+use codex_core::protocol::{
+    TeamDiffSummaryEvent, TeamDisplayMode, TeamFileDiff, TeamMemberInfo, TeamMemberTokenUsageEvent,
+    TeamProgressEvent, TeamTaskInfo, TeamTaskStatus, TokenUsage,
+};
 use codex_protocol::ThreadId;
+use codex_protocol::num_format::format_with_separators;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::time::Duration;
+use std::time::Instant;
 
 /// In-memory snapshot of team state, updated as Team* events arrive.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub(crate) struct TeamState {
     pub team_name: Option<String>,
+    pub description: Option<String>,
+    pub display_mode: TeamDisplayMode,
     pub leader_thread_id: Option<ThreadId>,
     pub members: Vec<TeamMemberInfo>,
     pub tasks: Vec<TeamTaskInfo>,
+    /// Latest changes of each teammate, by name.
+    pub diffs: BTreeMap<String, Vec<TeamFileDiff>>,
+    /// Latest token usage of each teammate, by name.
+    pub usage: BTreeMap<String, TokenUsage>,
+    /// Latest progress report of the team.
+    pub progress: Option<TeamProgressEvent>,
+    /// The last [`MESSAGE_LOG_LIMIT`] messages sent within the team, oldest
+    /// first.
+    pub messages: VecDeque<TeamMessageEvent>,
+    /// Messages to the leader that arrived since the `/team` dashboard was
+    /// last opened.
+    pub unread_messages: usize,
+    /// Changes of each task seen in this session, oldest first, by task id.
+    task_history: BTreeMap<String, Vec<String>>,
+    /// When each task was seen to start, by task id.
+    task_started: BTreeMap<String, Instant>,
+    /// How long each task seen both starting and completing took, by task
+    /// id.
+    task_durations: BTreeMap<String, Duration>,
+    /// Accent style of each teammate, by name. Styles are handed out in the
+    /// order teammates are first seen and kept when they leave, so a
+    /// teammate's cells keep their style for the life of the team.
+    accents: BTreeMap<String, Style>,
+}
+
+/// Which tasks the task overlay lists.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct TaskFilter {
+    pub status: Option<TeamTaskStatus>,
+    pub assignee: Option<String>,
+    /// Case-insensitive text to find in a task's id, title, description or
+    /// assignee.
+    pub query: String,
+    /// Whether `query` is being typed.
+    pub searching: bool,
+}
+
+impl TaskFilter {
+    fn matches(&self, task: &TeamTaskInfo) -> bool {
+        if self
+            .status
+            .as_ref()
+            .is_some_and(|status| status != &task.status)
+            || self
+                .assignee
+                .as_ref()
+                .is_some_and(|assignee| task.assigned_to.as_ref() != Some(assignee))
+        {
+            return false;
+        }
+        let query = self.query.to_lowercase();
+        [
+            Some(&task.id),
+            Some(&task.title),
+            task.description.as_ref(),
+            task.assigned_to.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        .any(|text| text.to_lowercase().contains(&query))
+    }
+
+    /// Whether the overlay shows this filter, because it narrows the tasks
+    /// or is being typed.
+    fn is_shown(&self) -> bool {
+        self.status.is_some() || self.assignee.is_some() || !self.query.is_empty() || self.searching
+    }
 }
 
+/// Name under which the leader sends and receives team messages.
+const LEADER_NAME: &str = "leader";
+
+/// Whether `ev` is addressed to the leader, and so to the user watching the
+/// leader's session.
+pub(crate) fn is_for_leader(ev: &TeamMessageEvent) -> bool {
+    ev.to == LEADER_NAME || ev.to == "user"
+}
+
+/// Title style of team events that are not about one teammate, and of the
+/// leader's.
+const TEAM_EVENT_TITLE: Style = Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+
+/// Title styles of teammates, in the order they are handed out. They only
+/// combine cyan, bold and dim (see `tui/styles.md`), so they are few; the
+/// teammate's name tells apart teammates that share one.
+const TEAMMATE_ACCENTS: [Style; 4] = [
+    Style::new().add_modifier(Modifier::BOLD),
+    Style::new().fg(Color::Cyan),
+    Style::new().fg(Color::Cyan).add_modifier(Modifier::DIM),
+    Style::new().add_modifier(Modifier::DIM),
+];
+
+/// How many team messages the `/team` dashboard shows.
+const RECENT_MESSAGES: usize = 10;
+
+/// How many team messages are kept for the message feed.
+const MESSAGE_LOG_LIMIT: usize = 1000;
+
+/// Width of the task overlay's progress bar, in cells.
+const PROGRESS_BAR_WIDTH: usize = 20;
+
+/// Width of each column of the task board, in cells.
+const BOARD_COLUMN_WIDTH: usize = 32;
+
 impl TeamState {
     pub(crate) fn on_team_created(&mut self, ev: &TeamCreatedEvent) {
         self.team_name = Some(ev.team_name.clone());
+        self.description = ev.description.clone();
+        self.display_mode = ev.display_mode;
         self.leader_thread_id = Some(ev.leader_thread_id);
     }
 
     pub(crate) fn on_member_added(&mut self, ev: &TeamMemberEvent) {
+        self.teammate_accent(&ev.member.name);
         // Replace if already present, else push.
         if let Some(m) = self.members.iter_mut().find(|m| m.thread_id == ev.member.thread_id) {
             *m = ev.member.clone();
@@ -146,63 +561,180 @@ impl TeamState {
         }
     }
 
+    pub(crate) fn on_member_status_changed(&mut self, ev: &TeamMemberEvent) {
+        if let Some(m) = self.members.iter_mut().find(|m| m.thread_id == ev.member.thread_id) {
+            m.status = ev.member.status.clone();
+        }
+    }
+
     pub(crate) fn on_member_removed(&mut self, ev: &TeamMemberEvent) {
         self.members.retain(|m| m.thread_id != ev.member.thread_id);
     }
 
     pub(crate) fn on_task_created(&mut self, ev: &TeamTaskEvent) {
+        let entry = match &ev.task.assigned_to {
+            Some(assignee) => format!("created for {assignee}"),
+            None => "created".to_string(),
+        };
+        self.task_history
+            .entry(ev.task.id.clone())
+            .or_default()
+            .push(entry);
+        if ev.task.status == TeamTaskStatus::InProgress {
+            self.task_started.insert(ev.task.id.clone(), Instant::now());
+        }
         self.tasks.push(ev.task.clone());
     }
 
     pub(crate) fn on_task_updated(&mut self, ev: &TeamTaskEvent) {
-        if let Some(t) = self.tasks.iter_mut().find(|t| t.id == ev.task.id) {
-            *t = ev.task.clone();
+        let Some(t) = self.tasks.iter_mut().find(|t| t.id == ev.task.id) else {
+            return;
+        };
+        let history = self.task_history.entry(ev.task.id.clone()).or_default();
+        if t.assigned_to != ev.task.assigned_to {
+            history.push(match &ev.task.assigned_to {
+                Some(assignee) => format!("assigned to {assignee}"),
+                None => "unassigned".to_string(),
+            });
+        }
+        if t.status != ev.task.status {
+            history.push(format!("marked {}", task_status_label(&ev.task.status)));
+            match ev.task.status {
+                TeamTaskStatus::InProgress => {
+                    self.task_started.insert(ev.task.id.clone(), Instant::now());
+                }
+                TeamTaskStatus::Completed => {
+                    if let Some(started) = self.task_started.remove(&ev.task.id) {
+                        self.task_durations
+                            .insert(ev.task.id.clone(), started.elapsed());
+                    }
+                }
+                TeamTaskStatus::Pending => {
+                    self.task_started.remove(&ev.task.id);
+                }
+            }
+        }
+        if t.result != ev.task.result && ev.task.result.is_some() {
+            history.push("result recorded".to_string());
+        }
+        *t = ev.task.clone();
+    }
+
+    pub(crate) fn on_diff_summary(&mut self, ev: &TeamDiffSummaryEvent) {
+        if self.team_name.as_deref() == Some(ev.team_name.as_str()) {
+            self.diffs.insert(ev.member_name.clone(), ev.files.clone());
+        }
+    }
+
+    pub(crate) fn on_member_token_usage(&mut self, ev: &TeamMemberTokenUsageEvent) {
+        if self.team_name.as_deref() == Some(ev.team_name.as_str()) {
+            self.usage
+                .insert(ev.member_name.clone(), ev.total_token_usage.clone());
+        }
+    }
+
+    pub(crate) fn on_progress(&mut self, ev: &TeamProgressEvent) {
+        if self.team_name.as_deref() == Some(ev.team_name.as_str()) {
+            self.progress = Some(ev.clone());
+        }
+    }
+
+    pub(crate) fn on_message_sent(&mut self, ev: &TeamMessageEvent) {
+        if self.team_name.as_deref() == Some(ev.team_name.as_str()) {
+            if self.messages.len() == MESSAGE_LOG_LIMIT {
+                self.messages.pop_front();
+            }
+            self.messages.push_back(ev.clone());
+        }
+    }
+
+    pub(crate) fn on_team_renamed(&mut self, ev: &TeamRenamedEvent) {
+        if self.team_name.as_deref() == Some(ev.old_name.as_str()) {
+            self.team_name = Some(ev.new_name.clone());
+        }
+    }
+
+    pub(crate) fn on_leader_changed(&mut self, ev: &TeamLeaderChangedEvent) {
+        if self.team_name.as_deref() == Some(ev.team_name.as_str()) {
+            self.leader_thread_id = Some(ev.leader_thread_id);
         }
     }
 
     pub(crate) fn on_cleanup(&mut self) {
         self.team_name = None;
+        self.description = None;
+        self.display_mode = TeamDisplayMode::default();
         self.leader_thread_id = None;
         self.members.clear();
         self.tasks.clear();
+        self.diffs.clear();
+        self.usage.clear();
+        self.progress = None;
+        self.messages.clear();
+        self.unread_messages = 0;
+        self.accents.clear();
+        self.task_history.clear();
+        self.task_started.clear();
+        self.task_durations.clear();
     }
 
-    /// Render the task list as styled lines for use in a `StaticOverlay`.
-    pub(crate) fn task_overlay_lines(&self) -> Vec<Line<'static>> {
-        let mut lines = Vec::new();
+    /// Accent style of teammate `name`, handing it the next style if it has
+    /// none yet; `None` for the leader and the user.
+    pub(crate) fn teammate_accent(&mut self, name: &str) -> Option<Style> {
+        if name == LEADER_NAME || name == "user" {
+            return None;
+        }
+        let next = TEAMMATE_ACCENTS[self.accents.len() % TEAMMATE_ACCENTS.len()];
+        Some(*self.accents.entry(name.to_string()).or_insert(next))
+    }
 
-        let team = self
-            .team_name
-            .as_deref()
-            .unwrap_or("<no team>");
-        lines.push(
-            vec![
-                Span::from("Team: ").dim(),
-                Span::from(team.to_string()).bold().cyan(),
-            ]
-            .into(),
-        );
+    /// Render the task list, keeping the tasks that match `filter`, as styled
+    /// lines for use in a `StaticOverlay`.
+    pub(crate) fn task_overlay_lines(&self, filter: &TaskFilter) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        self.push_header_lines(&mut lines);
         lines.push(Line::from(""));
 
         if self.tasks.is_empty() {
             lines.push(Line::from("  No tasks.").dim());
+            self.push_diff_lines(&mut lines);
+            self.push_usage_lines(&mut lines);
             return lines;
         }
 
-        for task in &self.tasks {
-            let icon = match task.status {
-                TeamTaskStatus::Pending => "○",
-                TeamTaskStatus::InProgress => "◑",
-                TeamTaskStatus::Completed => "●",
-            };
-            let status_style: Span<'static> = match task.status {
-                TeamTaskStatus::Pending => Span::from(icon).dim(),
-                TeamTaskStatus::InProgress => Span::from(icon).cyan().bold(),
-                TeamTaskStatus::Completed => Span::from(icon).green(),
-            };
+        let tasks: Vec<&TeamTaskInfo> = self.tasks.iter().filter(|t| filter.matches(t)).collect();
+        if filter.is_shown() {
+            let mut spans = vec![Span::from("Filter: ").dim()];
+            let mut parts = Vec::new();
+            if let Some(status) = &filter.status {
+                parts.push(task_status_label(status).to_string());
+            }
+            if let Some(assignee) = &filter.assignee {
+                parts.push(format!("assigned to {assignee}"));
+            }
+            if !filter.query.is_empty() && !filter.searching {
+                parts.push(format!("\"{}\"", filter.query));
+            }
+            spans.push(Span::from(parts.join(" · ")));
+            if filter.searching {
+                if !parts.is_empty() {
+                    spans.push(Span::from(" · "));
+                }
+                spans.push(Span::from(format!("/{}▏", filter.query)).cyan());
+            }
+            spans.push(
+                Span::from(format!("  ({} of {} tasks)", tasks.len(), self.tasks.len())).dim(),
+            );
+            lines.push(Line::from(spans));
+        }
+        if tasks.is_empty() {
+            lines.push(Line::from("  No matching tasks.").dim());
+        }
+
+        for task in tasks {
             let mut spans = vec![
                 Span::from("  "),
-                status_style,
+                task_status_icon(&task.status),
                 Span::from(format!(" {} ", task.title)),
             ];
             spans.push(Span::from(format!("[{}]", task.id)).dim());
@@ -218,21 +750,620 @@ impl TeamState {
             .iter()
             .filter(|t| matches!(t.status, TeamTaskStatus::Completed))
             .count();
+        let total = self.tasks.len();
+        let filled = done * PROGRESS_BAR_WIDTH / total;
+        let mut spans = vec![
+            Span::from("  "),
+            Span::from("█".repeat(filled)).green(),
+            Span::from("░".repeat(PROGRESS_BAR_WIDTH - filled)).dim(),
+            Span::from(format!("  {done}/{total} completed")).dim(),
+        ];
+        // Naive estimate: the average duration of the tasks seen start and
+        // complete, for each task left, with the teammates working in
+        // parallel.
+        if done < total && !self.task_durations.is_empty() {
+            let average = self.task_durations.values().sum::<Duration>()
+                / u32::try_from(self.task_durations.len()).unwrap_or(u32::MAX);
+            let workers = self.members.len().max(1);
+            let left = (total - done).div_ceil(workers);
+            let eta = average
+                .as_secs()
+                .saturating_mul(u64::try_from(left).unwrap_or(u64::MAX));
+            spans.push(Span::from(format!(" · ~{} left", fmt_elapsed_compact(eta))).dim());
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(spans));
+
+        let mut assignees: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+        for task in &self.tasks {
+            if let Some(assignee) = &task.assigned_to {
+                let (done, total) = assignees.entry(assignee).or_default();
+                *total += 1;
+                if task.status == TeamTaskStatus::Completed {
+                    *done += 1;
+                }
+            }
+        }
+        for (assignee, (done, total)) in assignees {
+            lines.push(Line::from(vec![
+                Span::from(format!("  {assignee}  ")),
+                Span::from(format!("{done}/{total} completed")).dim(),
+            ]));
+        }
+        self.push_diff_lines(&mut lines);
+        self.push_usage_lines(&mut lines);
+
+        lines
+    }
+
+    /// Render the details of task `task_id`: its description, dependencies
+    /// with their statuses, result, and the changes seen in this session.
+    /// `None` if the team has no such task.
+    pub(crate) fn task_detail_lines(&self, task_id: &str) -> Option<Vec<Line<'static>>> {
+        let task = self.tasks.iter().find(|task| task.id == task_id)?;
+        let mut lines = Vec::new();
+        self.push_header_lines(&mut lines);
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            task_status_icon(&task.status),
+            Span::from(format!(" {} ", task.title)).bold(),
+            Span::from(format!("[{}]", task.id)).dim(),
+        ]));
+        lines.push(Line::from(vec![
+            Span::from("Status: ").dim(),
+            Span::from(task_status_label(&task.status)),
+        ]));
+        lines.push(Line::from(vec![
+            Span::from("Assignee: ").dim(),
+            Span::from(
+                task.assigned_to
+                    .clone()
+                    .unwrap_or_else(|| "unassigned".to_string()),
+            ),
+        ]));
+
+        if let Some(description) = &task.description {
+            lines.push(Line::from(""));
+            lines.push(Line::from("Description").bold());
+            for line in description.lines() {
+                lines.push(Line::from(format!("  {line}")));
+            }
+        }
+
+        if !task.depends_on.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from("Dependencies").bold());
+            for id in &task.depends_on {
+                let line = match self.tasks.iter().find(|dep| &dep.id == id) {
+                    Some(dep) => Line::from(vec![
+                        Span::from("  "),
+                        task_status_icon(&dep.status),
+                        Span::from(format!(" {} ", dep.title)),
+                        Span::from(format!("[{id}]  {}", task_status_label(&dep.status))).dim(),
+                    ]),
+                    None => Line::from(format!("  ? [{id}]  unknown")).dim(),
+                };
+                lines.push(line);
+            }
+        }
+
+        if let Some(result) = &task.result {
+            lines.push(Line::from(""));
+            lines.push(Line::from("Result").bold());
+            for line in result.lines() {
+                lines.push(Line::from(format!("  {line}")));
+            }
+        }
+
+        if let Some(history) = self.task_history.get(&task.id) {
+            lines.push(Line::from(""));
+            lines.push(Line::from("History").bold());
+            for entry in history {
+                lines.push(Line::from(format!("  {entry}")).dim());
+            }
+        }
+
+        Some(lines)
+    }
+
+    /// Line index in [`Self::task_overlay_lines`] of each task matching
+    /// `filter`, with the task's id.
+    pub(crate) fn task_overlay_rows(&self, filter: &TaskFilter) -> Vec<(usize, String)> {
+        let mut header = Vec::new();
+        self.push_header_lines(&mut header);
+        let first = header.len() + 1 + usize::from(filter.is_shown());
+        self.tasks
+            .iter()
+            .filter(|task| filter.matches(task))
+            .enumerate()
+            .map(|(i, task)| (first + i, task.id.clone()))
+            .collect()
+    }
+
+    /// Line index in [`Self::dashboard_lines`] of each teammate, with the
+    /// teammate's name.
+    pub(crate) fn dashboard_member_rows(&self) -> Vec<(usize, String)> {
+        let mut header = Vec::new();
+        self.push_header_lines(&mut header);
+        let first = header.len() + 2;
+        self.members
+            .iter()
+            .enumerate()
+            .map(|(i, member)| (first + i, member.name.clone()))
+            .collect()
+    }
+
+    /// Render the `/team` dashboard: teammates with their statuses, a task
+    /// summary, the most recent team messages, and each teammate's tokens
+    /// with a cost estimate if `teams` sets token prices.
+    pub(crate) fn dashboard_lines(&self, teams: &TeamsConfig) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        self.push_header_lines(&mut lines);
+
+        lines.push(Line::from(""));
+        lines.push(Line::from("Members").bold());
+        if self.members.is_empty() {
+            lines.push(Line::from("  No teammates.").dim());
+        }
+        for member in &self.members {
+            let mut spans = vec![
+                Span::from(format!("  {}  ", member.name)),
+                status_span(&member.status),
+            ];
+            if let Some(role) = &member.role {
+                spans.push(Span::from(format!("  {role}")).dim());
+            }
+            lines.push(Line::from(spans));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from("Tasks").bold());
+        let count =
+            |status: TeamTaskStatus| self.tasks.iter().filter(|t| t.status == status).count();
+        lines.push(Line::from(format!(
+            "  {} total · {} pending · {} in progress · {} completed",
+            self.tasks.len(),
+            count(TeamTaskStatus::Pending),
+            count(TeamTaskStatus::InProgress),
+            count(TeamTaskStatus::Completed),
+        )));
+
+        if !self.messages.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from("Recent messages").bold());
+            let skip = self.messages.len().saturating_sub(RECENT_MESSAGES);
+            for message in self.messages.iter().skip(skip) {
+                lines.push(Line::from(vec![
+                    Span::from(format!("  {} → {}  ", message.from, message.to)).dim(),
+                    Span::from(message.content.clone()),
+                ]));
+            }
+        }
+        self.push_diff_lines(&mut lines);
+        self.push_usage_table(&mut lines, teams);
+
+        lines
+    }
+
+    /// Render the message feed: every kept team message, oldest first, or only
+    /// those sent by or to `participant`.
+    pub(crate) fn message_feed_lines(&self, participant: Option<&str>) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        self.push_header_lines(&mut lines);
+
+        lines.push(Line::from(""));
+        lines.push(match participant {
+            Some(name) => Line::from(format!("Messages from or to {name}")).bold(),
+            None => Line::from("Messages").bold(),
+        });
+        let mut shown = 0;
+        for message in self
+            .messages
+            .iter()
+            .filter(|m| participant.is_none_or(|name| m.from == name || m.to == name))
+        {
+            shown += 1;
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![
+                Span::from(format!("  {}", message.from)).bold(),
+                Span::from(" → ").dim(),
+                Span::from(message.to.clone()).bold(),
+            ]));
+            for line in message.content.lines() {
+                lines.push(Line::from(format!("    {line}")));
+            }
+        }
+        if shown == 0 {
+            lines.push(Line::from("  No messages.").dim());
+        }
+
+        lines
+    }
+
+    /// Render a Markdown report of the team run: the teammates with their
+    /// usage, each task with its outcome and how long it took, and the kept
+    /// team messages.
+    pub(crate) fn export_markdown(&self) -> String {
+        let mut out = format!("# Team {}\n", self.team_name.as_deref().unwrap_or_default());
+        if let Some(description) = &self.description {
+            out.push_str(&format!("\n{description}\n"));
+        }
+        if let Some(progress) = &self.progress {
+            out.push_str(&format!(
+                "\n{} of {} tasks completed after {}.\n",
+                progress.tasks_completed,
+                progress.tasks_total,
+                fmt_elapsed_compact(progress.elapsed_secs)
+            ));
+        }
+
+        out.push_str("\n## Members\n\n");
+        if self.members.is_empty() {
+            out.push_str("No teammates.\n");
+        }
+        for member in &self.members {
+            out.push_str(&format!("- **{}**", member.name));
+            if let Some(role) = &member.role {
+                out.push_str(&format!(" ({role})"));
+            }
+            out.push_str(&format!(": {}", status_span(&member.status).content));
+            if let Some(usage) = self.usage.get(&member.name) {
+                out.push_str(&format!(
+                    " · {} tokens in, {} out",
+                    format_with_separators(usage.input_tokens),
+                    format_with_separators(usage.output_tokens)
+                ));
+            }
+            out.push('\n');
+        }
+
+        out.push_str("\n## Tasks\n\n");
+        if self.tasks.is_empty() {
+            out.push_str("No tasks.\n");
+        }
+        for task in &self.tasks {
+            let done = if task.status == TeamTaskStatus::Completed {
+                "x"
+            } else {
+                " "
+            };
+            out.push_str(&format!(
+                "- [{done}] **{}** (`{}`) · {}",
+                task.title,
+                task.id,
+                task_status_label(&task.status)
+            ));
+            if let Some(assignee) = &task.assigned_to {
+                out.push_str(&format!(" · {assignee}"));
+            }
+            if let Some(duration) = self.task_durations.get(&task.id) {
+                out.push_str(&format!(
+                    " · took {}",
+                    fmt_elapsed_compact(duration.as_secs())
+                ));
+            }
+            out.push('\n');
+            if !task.depends_on.is_empty() {
+                out.push_str(&format!("  - Depends on: {}\n", task.depends_on.join(", ")));
+            }
+            if let Some(result) = &task.result {
+                out.push_str(&format!("  - Result: {}\n", result.replace('\n', "\n    ")));
+            }
+        }
+
+        if !self.messages.is_empty() {
+            out.push_str("\n## Messages\n\n");
+            for message in &self.messages {
+                out.push_str(&format!(
+                    "- **{} → {}**: {}\n",
+                    message.from,
+                    message.to,
+                    message.content.replace('\n', "\n  ")
+                ));
+            }
+        }
+        out
+    }
+
+    /// The same report as [`Self::export_markdown`], as JSON.
+    pub(crate) fn export_json(&self) -> serde_json::Value {
+        let members: Vec<serde_json::Value> = self
+            .members
+            .iter()
+            .map(|member| {
+                json!({
+                    "name": member.name,
+                    "role": member.role,
+                    "thread_id": member.thread_id.to_string(),
+                    "status": status_span(&member.status).content,
+                    "usage": self.usage.get(&member.name),
+                })
+            })
+            .collect();
+        let tasks: Vec<serde_json::Value> = self
+            .tasks
+            .iter()
+            .map(|task| {
+                json!({
+                    "id": task.id,
+                    "title": task.title,
+                    "description": task.description,
+                    "status": task_status_label(&task.status),
+                    "assigned_to": task.assigned_to,
+                    "depends_on": task.depends_on,
+                    "result": task.result,
+                    "duration_secs": self.task_durations.get(&task.id).map(Duration::as_secs),
+                    "history": self.task_history.get(&task.id),
+                })
+            })
+            .collect();
+        json!({
+            "team_name": self.team_name,
+            "description": self.description,
+            "elapsed_secs": self.progress.as_ref().map(|progress| progress.elapsed_secs),
+            "members": members,
+            "tasks": tasks,
+            "messages": self.messages,
+        })
+    }
+
+    /// Render the task board: one column per task status, with the tasks in
+    /// each column grouped by assignee.
+    pub(crate) fn task_board_lines(&self) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        self.push_header_lines(&mut lines);
         lines.push(Line::from(""));
+
+        let columns: Vec<Vec<Line<'static>>> = [
+            (TeamTaskStatus::Pending, "Pending"),
+            (TeamTaskStatus::InProgress, "In progress"),
+            (TeamTaskStatus::Completed, "Completed"),
+        ]
+        .into_iter()
+        .map(|(status, label)| {
+            let mut by_assignee: BTreeMap<Option<&str>, Vec<&TeamTaskInfo>> = BTreeMap::new();
+            for task in self.tasks.iter().filter(|task| task.status == status) {
+                by_assignee
+                    .entry(task.assigned_to.as_deref())
+                    .or_default()
+                    .push(task);
+            }
+            let count: usize = by_assignee.values().map(Vec::len).sum();
+            let mut column = vec![Line::from(format!("{label} ({count})")).bold()];
+            if by_assignee.is_empty() {
+                column.push(Line::from("  —").dim());
+            }
+            for (assignee, tasks) in by_assignee {
+                column.push(Line::from(format!(" {}", assignee.unwrap_or("unassigned"))).cyan());
+                for task in tasks {
+                    let title_width = BOARD_COLUMN_WIDTH.saturating_sub(task.id.len() + 6);
+                    column.push(Line::from(vec![
+                        Span::from("   "),
+                        Span::from(task.id.clone()).dim(),
+                        Span::from(" "),
+                        Span::from(truncate_text(&task.title, title_width)),
+                    ]));
+                }
+            }
+            column
+        })
+        .collect();
+
+        let rows = columns.iter().map(Vec::len).max().unwrap_or(0);
+        for row in 0..rows {
+            let cells: Vec<Line<'static>> = columns
+                .iter()
+                .map(|column| column.get(row).cloned().unwrap_or_default())
+                .collect();
+            // Pad each cell to the column width, up to the last non-empty one.
+            let last = cells.iter().rposition(|cell| cell.width() > 0).unwrap_or(0);
+            let mut spans = Vec::new();
+            for (i, cell) in cells.into_iter().take(last + 1).enumerate() {
+                let padding = BOARD_COLUMN_WIDTH.saturating_sub(cell.width());
+                let style = cell.style;
+                spans.extend(cell.spans.into_iter().map(|span| span.patch_style(style)));
+                if i < last {
+                    spans.push(Span::from(" ".repeat(padding)));
+                }
+            }
+            lines.push(Line::from(spans));
+        }
+
+        lines
+    }
+
+    /// Compact summary of the active team for the status line: teammates
+    /// running, idle and errored, tasks completed, and unread messages to the
+    /// leader, e.g. `team: zeta  3▶ 1✔ 0✖  5/9 tasks  ✉ 2`.
+    pub(crate) fn status_line_summary(&self) -> Option<String> {
+        let team = self.team_name.as_deref()?;
+        // Progress reports count teammates from their live agent status;
+        // until one arrives, fall back to the statuses of membership events.
+        let (running, idle, errored) = match &self.progress {
+            Some(progress) => (
+                progress.members_running as usize,
+                progress.members_idle as usize,
+                progress.members_errored as usize,
+            ),
+            None => {
+                let count = |matches: fn(&AgentStatus) -> bool| {
+                    self.members.iter().filter(|m| matches(&m.status)).count()
+                };
+                (
+                    count(|s| matches!(s, AgentStatus::PendingInit | AgentStatus::Running)),
+                    count(|s| matches!(s, AgentStatus::Completed(_) | AgentStatus::Shutdown)),
+                    count(|s| matches!(s, AgentStatus::Errored(_) | AgentStatus::NotFound)),
+                )
+            }
+        };
+        let done = self
+            .tasks
+            .iter()
+            .filter(|t| t.status == TeamTaskStatus::Completed)
+            .count();
+        let mut summary = format!(
+            "team: {team}  {running}▶ {idle}✔ {errored}✖  {done}/{} tasks",
+            self.tasks.len()
+        );
+        if self.unread_messages > 0 {
+            summary.push_str(&format!("  ✉ {}", self.unread_messages));
+        }
+        Some(summary)
+    }
+
+    /// Append the team name, its description and the latest progress report.
+    fn push_header_lines(&self, lines: &mut Vec<Line<'static>>) {
+        let team = self.team_name.as_deref().unwrap_or("<no team>");
         lines.push(
-            Line::from(format!(
-                "  {done}/{} completed",
-                self.tasks.len()
-            ))
-            .dim(),
+            vec![
+                Span::from("Team: ").dim(),
+                Span::from(team.to_string()).bold().cyan(),
+            ]
+            .into(),
         );
+        if let Some(description) = &self.description {
+            lines.push(Line::from(description.clone()).dim().italic());
+        }
+        if let Some(progress) = &self.progress {
+            lines.push(
+                Line::from(format!(
+                    "Teammates: {} running, {} idle, {} errored · {}",
+                    progress.members_running,
+                    progress.members_idle,
+                    progress.members_errored,
+                    fmt_elapsed_compact(progress.elapsed_secs)
+                ))
+                .dim(),
+            );
+        }
+    }
 
-        lines
+    /// Append each teammate's latest changes, if any were reported.
+    fn push_diff_lines(&self, lines: &mut Vec<Line<'static>>) {
+        if self.diffs.is_empty() {
+            return;
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from("Changes").bold());
+        for (name, files) in &self.diffs {
+            let mut spans = vec![Span::from(format!("  {name}  "))];
+            if files.is_empty() {
+                spans.push(Span::from("no changes").dim());
+            } else {
+                let added: u64 = files.iter().map(|file| file.added).sum();
+                let removed: u64 = files.iter().map(|file| file.removed).sum();
+                let noun = if files.len() == 1 { "file" } else { "files" };
+                spans.push(Span::from(format!("{} {noun} ", files.len())));
+                spans.push(Span::from(format!("+{added}")).green());
+                spans.push(Span::from(" "));
+                spans.push(Span::from(format!("-{removed}")).red());
+            }
+            lines.push(Line::from(spans));
+        }
     }
 
-    /// Return thread IDs of active teammates for cycling.
-    pub(crate) fn teammate_thread_ids(&self) -> Vec<ThreadId> {
-        self.members.iter().map(|m| m.thread_id).collect()
+    /// Append each teammate's token usage so far, if any was reported.
+    fn push_usage_lines(&self, lines: &mut Vec<Line<'static>>) {
+        if self.usage.is_empty() {
+            return;
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from("Usage").bold());
+        for (name, usage) in &self.usage {
+            lines.push(Line::from(vec![
+                Span::from(format!("  {name}  ")),
+                Span::from(format_with_separators(usage.blended_total())),
+                Span::from(" tokens").dim(),
+            ]));
+        }
+    }
+
+    /// Append a table of each teammate's input and output tokens, and their
+    /// estimated cost if `teams` sets token prices, ending in a team total.
+    fn push_usage_table(&self, lines: &mut Vec<Line<'static>>, teams: &TeamsConfig) {
+        if self.usage.is_empty() {
+            return;
+        }
+        let total = self
+            .usage
+            .values()
+            .fold(TokenUsage::default(), |mut total, usage| {
+                total.input_tokens += usage.input_tokens;
+                total.output_tokens += usage.output_tokens;
+                total
+            });
+        let rows: Vec<(&str, &TokenUsage)> = self
+            .usage
+            .iter()
+            .map(|(name, usage)| (name.as_str(), usage))
+            .chain(std::iter::once(("total", &total)))
+            .collect();
+        let cells: Vec<[String; 3]> = rows
+            .iter()
+            .map(|(name, usage)| {
+                [
+                    (*name).to_string(),
+                    format_with_separators(usage.input_tokens),
+                    format_with_separators(usage.output_tokens),
+                ]
+            })
+            .collect();
+        let width = |column: usize, header: &str| {
+            cells
+                .iter()
+                .map(|row| row[column].chars().count())
+                .chain(std::iter::once(header.chars().count()))
+                .max()
+                .unwrap_or(0)
+        };
+        let (name_width, in_width, out_width) = (width(0, ""), width(1, "in"), width(2, "out"));
+
+        lines.push(Line::from(""));
+        lines.push(Line::from("Usage").bold());
+        let mut header = format!(
+            "  {:name_width$}  {:>in_width$}  {:>out_width$}",
+            "", "in", "out"
+        );
+        if estimated_cost_usd(teams, &total).is_some() {
+            header.push_str("  cost");
+        }
+        lines.push(Line::from(header).dim());
+        for ((name, usage), [_, input, output]) in rows.iter().zip(&cells) {
+            let mut row = format!("  {name:name_width$}  {input:>in_width$}  {output:>out_width$}");
+            if let Some(cost) = estimated_cost_usd(teams, usage) {
+                row.push_str(&format!("  ~${cost:.2}"));
+            }
+            let row = Line::from(row);
+            lines.push(if *name == "total" { row.bold() } else { row });
+        }
+    }
+
+    /// The leader and each teammate, with the name to show for each, in the
+    /// order focus cycles through them.
+    pub(crate) fn focus_threads(&self) -> Vec<(ThreadId, String)> {
+        self.leader_thread_id
+            .map(|id| (id, LEADER_NAME.to_string()))
+            .into_iter()
+            .chain(self.members.iter().map(|m| (m.thread_id, m.name.clone())))
+            .collect()
+    }
+
+    /// Whether teammate activity (membership, task, and message events)
+    /// should be rendered in the transcript. Split-pane teams render it
+    /// inline too, and show teammate output in panes instead; quiet and
+    /// compact teams leave it to the task overlay.
+    pub(crate) fn shows_activity_in_transcript(&self) -> bool {
+        matches!(
+            self.display_mode,
+            TeamDisplayMode::Inline | TeamDisplayMode::SplitPane
+        )
+    }
+
+    /// Whether any team event, lifecycle events included, should be
+    /// rendered in the transcript. Compact teams only update the status line.
+    pub(crate) fn shows_team_in_transcript(&self) -> bool {
+        self.display_mode != TeamDisplayMode::Compact
     }
 
     /// Whether a team is currently active.
@@ -240,3 +1371,892 @@ impl TeamState {
         self.team_name.is_some()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn render_lines(lines: &[Line<'static>]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn status_change_shows_previous_status() {
+        let cell = team_member_status_changed(TeamMemberEvent {
+            team_name: "zeta".to_string(),
+            member: TeamMemberInfo {
+                name: "alice".to_string(),
+                thread_id: ThreadId::new(),
+                role: None,
+                status: AgentStatus::Shutdown,
+            },
+            previous_status: Some(AgentStatus::Running),
+        });
+
+        assert_eq!(
+            render_lines(&cell.display_lines(80)),
+            vec![
+                "• ⏹️  Teammate shut down".to_string(),
+                "  └ team: zeta".to_string(),
+                "    teammate: alice".to_string(),
+                "    status: shutdown".to_string(),
+                "    was: running".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn team_error_shows_code_and_message() {
+        let cell = team_error(TeamErrorEvent {
+            tool_name: "spawn_teammate".to_string(),
+            team_name: Some("zeta".to_string()),
+            code: TeamErrorCode::BudgetExceeded,
+            message: "team 'zeta' has used 10 of its 5 token budget".to_string(),
+        });
+
+        assert_eq!(
+            render_lines(&cell.display_lines(80)),
+            vec![
+                "• ⚠️  Team tool failed".to_string(),
+                "  └ tool: spawn_teammate".to_string(),
+                "    team: zeta".to_string(),
+                "    code: budget_exceeded".to_string(),
+                "    error: team 'zeta' has used 10 of its 5 token budget".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn task_unblocked_names_the_completed_dependency() {
+        let cell = team_task_unblocked(TeamTaskUnblockedEvent {
+            team_name: "zeta".to_string(),
+            task: TeamTaskInfo {
+                id: "task-2".to_string(),
+                title: "Write docs".to_string(),
+                description: None,
+                status: TeamTaskStatus::Pending,
+                assigned_to: None,
+                depends_on: vec!["task-1".to_string()],
+                result: None,
+                created_at: None,
+            },
+            unblocked_by: "task-1".to_string(),
+        });
+
+        assert_eq!(
+            render_lines(&cell.display_lines(80)),
+            vec![
+                "• 🔓  Task ready".to_string(),
+                "  └ team: zeta".to_string(),
+                "    task: Write docs".to_string(),
+                "    id: task-2".to_string(),
+                "    unblocked by: task-1".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn liveness_lost_shows_silence() {
+        let thread_id = ThreadId::new();
+        let cell = team_member_liveness_lost(TeamMemberLivenessLostEvent {
+            team_name: "zeta".to_string(),
+            member_name: "alice".to_string(),
+            thread_id,
+            silent_secs: 330,
+        });
+
+        assert_eq!(
+            render_lines(&cell.display_lines(80)),
+            vec![
+                "• 💤  Teammate unresponsive".to_string(),
+                "  └ team: zeta".to_string(),
+                "    teammate: alice".to_string(),
+                format!("    thread: {thread_id}"),
+                "    silent for: 5m 30s".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn teammate_messages_and_commands_get_cells() {
+        let output = |output| TeamMemberOutputEvent {
+            team_name: "zeta".to_string(),
+            member_name: "alice".to_string(),
+            thread_id: ThreadId::new(),
+            output,
+        };
+
+        let cell = team_member_output(
+            output(TeamMemberOutput::AgentMessage {
+                message: "Found it.\nFixing now.".to_string(),
+            }),
+            None,
+        )
+        .expect("agent messages are shown");
+        assert_eq!(
+            render_lines(&cell.display_lines(80)),
+            vec![
+                "• 💬  alice says".to_string(),
+                "  └ Found it.".to_string(),
+                "    Fixing now.".to_string(),
+            ]
+        );
+        let cell = team_member_output(
+            output(TeamMemberOutput::CommandBegin {
+                call_id: "call-1".to_string(),
+                command: vec!["cargo".to_string(), "test".to_string()],
+            }),
+            None,
+        )
+        .expect("commands are shown");
+        assert_eq!(
+            render_lines(&cell.display_lines(80)),
+            vec!["• ⚙️  alice runs".to_string(), "  └ cargo test".to_string()]
+        );
+        assert!(
+            team_member_output(
+                output(TeamMemberOutput::AgentMessageDelta {
+                    delta: "Fo".to_string(),
+                }),
+                None,
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn consecutive_updates_fold_into_one_cell() {
+        let task = |id: &str| TeamTaskEvent {
+            team_name: "zeta".to_string(),
+            task: TeamTaskInfo {
+                id: id.to_string(),
+                title: format!("Work on {id}"),
+                description: None,
+                status: TeamTaskStatus::Pending,
+                assigned_to: None,
+                depends_on: Vec::new(),
+                result: None,
+                created_at: None,
+            },
+        };
+        let mut cell = TeamUpdatesCell::new(team_task_created(task("task-1")));
+        assert_eq!(
+            render_lines(&cell.display_lines(80))[0],
+            "• 📋  Task created".to_string()
+        );
+
+        for id in ["task-2", "task-3", "task-4"] {
+            cell.push(team_task_created(task(id)));
+        }
+        cell.push(team_task_updated(task("task-1")));
+        assert_eq!(
+            render_lines(&cell.display_lines(80)),
+            vec![
+                "• 5 team updates  ctrl + t to view".to_string(),
+                "  └ … 2 earlier".to_string(),
+                "    📋  Task created".to_string(),
+                "    📋  Task created".to_string(),
+                "    📝  Task updated".to_string(),
+            ]
+        );
+        assert_eq!(
+            cell.transcript_lines(80).len(),
+            4 * team_task_created(task("task-1")).display_lines(80).len()
+                + team_task_updated(task("task-1")).display_lines(80).len()
+        );
+    }
+
+    #[test]
+    fn teammates_keep_their_accent_style() {
+        let mut state = TeamState::default();
+        assert_eq!(state.teammate_accent("leader"), None);
+        assert_eq!(state.teammate_accent("alice"), Some(TEAMMATE_ACCENTS[0]));
+        assert_eq!(state.teammate_accent("bob"), Some(TEAMMATE_ACCENTS[1]));
+        assert_eq!(state.teammate_accent("alice"), Some(TEAMMATE_ACCENTS[0]));
+
+        let cell = team_message_sent(
+            TeamMessageEvent {
+                team_name: "zeta".to_string(),
+                from: "bob".to_string(),
+                to: "leader".to_string(),
+                content: "Done.".to_string(),
+            },
+            state.teammate_accent("bob"),
+        );
+        let title = &cell.display_lines(80)[0];
+        assert_eq!(title.spans[1].style, TEAMMATE_ACCENTS[1]);
+    }
+
+    #[test]
+    fn task_overlay_header_shows_description() {
+        let mut state = TeamState::default();
+        state.on_team_created(&TeamCreatedEvent {
+            team_name: "zeta".to_string(),
+            leader_thread_id: ThreadId::new(),
+            description: Some("Refactor the auth module".to_string()),
+            display_mode: TeamDisplayMode::Inline,
+        });
+
+        assert_eq!(
+            render_lines(&state.task_overlay_lines(&TaskFilter::default())),
+            vec![
+                "Team: zeta".to_string(),
+                "Refactor the auth module".to_string(),
+                String::new(),
+                "  No tasks.".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn dashboard_shows_members_tasks_and_recent_messages() {
+        let mut state = TeamState::default();
+        state.on_team_created(&TeamCreatedEvent {
+            team_name: "zeta".to_string(),
+            leader_thread_id: ThreadId::new(),
+            description: None,
+            display_mode: TeamDisplayMode::Inline,
+        });
+        for (name, role, status) in [
+            ("alice", Some("reviewer"), AgentStatus::Running),
+            ("bob", None, AgentStatus::Completed(None)),
+        ] {
+            state.on_member_added(&TeamMemberEvent {
+                team_name: "zeta".to_string(),
+                member: TeamMemberInfo {
+                    name: name.to_string(),
+                    thread_id: ThreadId::new(),
+                    role: role.map(str::to_string),
+                    status,
+                },
+                previous_status: None,
+            });
+        }
+        for (id, status) in [
+            ("task-1", TeamTaskStatus::Completed),
+            ("task-2", TeamTaskStatus::InProgress),
+            ("task-3", TeamTaskStatus::Pending),
+        ] {
+            state.on_task_created(&TeamTaskEvent {
+                team_name: "zeta".to_string(),
+                task: TeamTaskInfo {
+                    id: id.to_string(),
+                    title: id.to_string(),
+                    description: None,
+                    status,
+                    assigned_to: None,
+                    depends_on: Vec::new(),
+                    result: None,
+                    created_at: None,
+                },
+            });
+        }
+        for n in 0..=RECENT_MESSAGES {
+            state.on_message_sent(&TeamMessageEvent {
+                team_name: "zeta".to_string(),
+                from: "leader".to_string(),
+                to: "alice".to_string(),
+                content: format!("message {n}"),
+            });
+        }
+
+        let rendered = render_lines(&state.dashboard_lines(&TeamsConfig::default())).join("\n");
+        insta::assert_snapshot!(rendered);
+        assert_eq!(
+            state.dashboard_member_rows(),
+            vec![(3, "alice".to_string()), (4, "bob".to_string())]
+        );
+    }
+
+    #[test]
+    fn export_reports_members_tasks_and_messages() {
+        let mut state = TeamState::default();
+        state.on_team_created(&TeamCreatedEvent {
+            team_name: "zeta".to_string(),
+            leader_thread_id: ThreadId::new(),
+            description: Some("Port the parser".to_string()),
+            display_mode: TeamDisplayMode::Inline,
+        });
+        state.on_member_added(&TeamMemberEvent {
+            team_name: "zeta".to_string(),
+            member: TeamMemberInfo {
+                name: "alice".to_string(),
+                thread_id: ThreadId::new(),
+                role: Some("reviewer".to_string()),
+                status: AgentStatus::Running,
+            },
+            previous_status: None,
+        });
+        for (id, status, result) in [
+            ("task-1", TeamTaskStatus::Completed, Some("Done.")),
+            ("task-2", TeamTaskStatus::Pending, None),
+        ] {
+            state.on_task_created(&TeamTaskEvent {
+                team_name: "zeta".to_string(),
+                task: TeamTaskInfo {
+                    id: id.to_string(),
+                    title: format!("Title of {id}"),
+                    description: None,
+                    status,
+                    assigned_to: Some("alice".to_string()),
+                    depends_on: if id == "task-2" {
+                        vec!["task-1".to_string()]
+                    } else {
+                        Vec::new()
+                    },
+                    result: result.map(str::to_string),
+                    created_at: None,
+                },
+            });
+        }
+        state
+            .task_durations
+            .insert("task-1".to_string(), Duration::from_secs(90));
+        state.on_message_sent(&TeamMessageEvent {
+            team_name: "zeta".to_string(),
+            from: "alice".to_string(),
+            to: "leader".to_string(),
+            content: "Parser ported.\nTests pass.".to_string(),
+        });
+
+        assert_eq!(
+            state.export_markdown(),
+            "# Team zeta
+
+Port the parser
+
+## Members
+
+- **alice** (reviewer): running
+
+## Tasks
+
+- [x] **Title of task-1** (`task-1`) · completed · alice · took 1m 30s
+  - Result: Done.
+- [ ] **Title of task-2** (`task-2`) · pending · alice
+  - Depends on: task-1
+
+## Messages
+
+- **alice → leader**: Parser ported.
+  Tests pass.
+"
+        );
+
+        let report = state.export_json();
+        assert_eq!(report["team_name"], "zeta");
+        assert_eq!(report["members"][0]["role"], "reviewer");
+        assert_eq!(report["tasks"][0]["duration_secs"], 90);
+        assert_eq!(report["tasks"][1]["depends_on"], json!(["task-1"]));
+        assert_eq!(
+            report["messages"][0]["content"],
+            "Parser ported.\nTests pass."
+        );
+    }
+
+    #[test]
+    fn message_feed_filters_by_participant() {
+        let mut state = TeamState::default();
+        state.on_team_created(&TeamCreatedEvent {
+            team_name: "zeta".to_string(),
+            leader_thread_id: ThreadId::new(),
+            description: None,
+            display_mode: TeamDisplayMode::Inline,
+        });
+        for (from, to, content) in [
+            ("leader", "alice", "Take the parser.\nStart with the lexer."),
+            ("bob", "leader", "Docs are done."),
+            ("alice", "leader", "On it."),
+        ] {
+            state.on_message_sent(&TeamMessageEvent {
+                team_name: "zeta".to_string(),
+                from: from.to_string(),
+                to: to.to_string(),
+                content: content.to_string(),
+            });
+        }
+
+        let rendered = render_lines(&state.message_feed_lines(Some("alice"))).join("\n");
+        insta::assert_snapshot!(rendered);
+        assert_eq!(state.message_feed_lines(None).len(), 13);
+        assert_eq!(
+            render_lines(&state.message_feed_lines(Some("carol")))
+                .last()
+                .map(String::as_str),
+            Some("  No messages.")
+        );
+    }
+
+    #[test]
+    fn status_line_summary_counts_teammates_and_tasks() {
+        let mut state = TeamState::default();
+        assert_eq!(state.status_line_summary(), None);
+        state.on_team_created(&TeamCreatedEvent {
+            team_name: "zeta".to_string(),
+            leader_thread_id: ThreadId::new(),
+            description: None,
+            display_mode: TeamDisplayMode::Inline,
+        });
+        for (name, status) in [
+            ("alice", AgentStatus::Running),
+            ("bob", AgentStatus::Completed(None)),
+        ] {
+            state.on_member_added(&TeamMemberEvent {
+                team_name: "zeta".to_string(),
+                member: TeamMemberInfo {
+                    name: name.to_string(),
+                    thread_id: ThreadId::new(),
+                    role: None,
+                    status,
+                },
+                previous_status: None,
+            });
+        }
+        for (id, status) in [
+            ("task-1", TeamTaskStatus::Completed),
+            ("task-2", TeamTaskStatus::InProgress),
+        ] {
+            state.on_task_created(&TeamTaskEvent {
+                team_name: "zeta".to_string(),
+                task: TeamTaskInfo {
+                    id: id.to_string(),
+                    title: id.to_string(),
+                    description: None,
+                    status,
+                    assigned_to: None,
+                    depends_on: Vec::new(),
+                    result: None,
+                    created_at: None,
+                },
+            });
+        }
+        assert_eq!(
+            state.status_line_summary().as_deref(),
+            Some("team: zeta  1▶ 1✔ 0✖  1/2 tasks")
+        );
+
+        state.on_progress(&TeamProgressEvent {
+            team_name: "zeta".to_string(),
+            tasks_completed: 1,
+            tasks_total: 2,
+            members_running: 0,
+            members_idle: 1,
+            members_errored: 1,
+            elapsed_secs: 30,
+        });
+        assert_eq!(
+            state.status_line_summary().as_deref(),
+            Some("team: zeta  0▶ 1✔ 1✖  1/2 tasks")
+        );
+
+        state.unread_messages = 2;
+        assert_eq!(
+            state.status_line_summary().as_deref(),
+            Some("team: zeta  0▶ 1✔ 1✖  1/2 tasks  ✉ 2")
+        );
+    }
+
+    #[test]
+    fn focus_cycles_from_the_leader_through_teammates() {
+        let leader = ThreadId::new();
+        let alice = ThreadId::new();
+        let mut state = TeamState::default();
+        state.on_team_created(&TeamCreatedEvent {
+            team_name: "zeta".to_string(),
+            leader_thread_id: leader,
+            description: None,
+            display_mode: TeamDisplayMode::Inline,
+        });
+        state.on_member_added(&TeamMemberEvent {
+            team_name: "zeta".to_string(),
+            member: TeamMemberInfo {
+                name: "alice".to_string(),
+                thread_id: alice,
+                role: None,
+                status: AgentStatus::Running,
+            },
+            previous_status: None,
+        });
+
+        assert_eq!(
+            state.focus_threads(),
+            vec![(leader, "leader".to_string()), (alice, "alice".to_string())]
+        );
+    }
+
+    #[test]
+    fn task_board_groups_tasks_by_status_and_assignee() {
+        let mut state = TeamState::default();
+        state.on_team_created(&TeamCreatedEvent {
+            team_name: "zeta".to_string(),
+            leader_thread_id: ThreadId::new(),
+            description: None,
+            display_mode: TeamDisplayMode::Inline,
+        });
+        for (id, title, status, assignee) in [
+            ("task-1", "Plan", TeamTaskStatus::Completed, Some("alice")),
+            (
+                "task-2",
+                "Review the parser changes",
+                TeamTaskStatus::InProgress,
+                Some("alice"),
+            ),
+            ("task-3", "Write docs", TeamTaskStatus::Pending, None),
+            ("task-4", "Fix lint", TeamTaskStatus::Pending, Some("bob")),
+        ] {
+            state.on_task_created(&TeamTaskEvent {
+                team_name: "zeta".to_string(),
+                task: TeamTaskInfo {
+                    id: id.to_string(),
+                    title: title.to_string(),
+                    description: None,
+                    status,
+                    assigned_to: assignee.map(str::to_string),
+                    depends_on: Vec::new(),
+                    result: None,
+                    created_at: None,
+                },
+            });
+        }
+
+        let rendered = render_lines(&state.task_board_lines()).join("\n");
+        insta::assert_snapshot!(rendered);
+    }
+
+    #[test]
+    fn task_overlay_rows_point_at_tasks() {
+        let mut state = TeamState::default();
+        state.on_team_created(&TeamCreatedEvent {
+            team_name: "zeta".to_string(),
+            leader_thread_id: ThreadId::new(),
+            description: None,
+            display_mode: TeamDisplayMode::Inline,
+        });
+        for (id, status) in [
+            ("task-1", TeamTaskStatus::Completed),
+            ("task-2", TeamTaskStatus::InProgress),
+            ("task-3", TeamTaskStatus::Pending),
+        ] {
+            state.on_task_created(&TeamTaskEvent {
+                team_name: "zeta".to_string(),
+                task: TeamTaskInfo {
+                    id: id.to_string(),
+                    title: format!("Work on {id}"),
+                    description: None,
+                    status,
+                    assigned_to: None,
+                    depends_on: Vec::new(),
+                    result: None,
+                    created_at: None,
+                },
+            });
+        }
+
+        let rows = state.task_overlay_rows(&TaskFilter::default());
+        assert_eq!(
+            rows.iter().map(|(_, id)| id.as_str()).collect::<Vec<_>>(),
+            vec!["task-1", "task-2", "task-3"]
+        );
+        let lines = render_lines(&state.task_overlay_lines(&TaskFilter::default()));
+        for (index, id) in &rows {
+            assert!(lines[*index].contains(&format!("[{id}]")), "{lines:?}");
+        }
+    }
+
+    #[test]
+    fn task_detail_shows_dependencies_result_and_history() {
+        let mut state = TeamState::default();
+        state.on_team_created(&TeamCreatedEvent {
+            team_name: "zeta".to_string(),
+            leader_thread_id: ThreadId::new(),
+            description: None,
+            display_mode: TeamDisplayMode::Inline,
+        });
+        let task = |id: &str, status, assigned_to: Option<&str>| TeamTaskInfo {
+            id: id.to_string(),
+            title: format!("Work on {id}"),
+            description: None,
+            status,
+            assigned_to: assigned_to.map(str::to_string),
+            depends_on: Vec::new(),
+            result: None,
+            created_at: None,
+        };
+        state.on_task_created(&TeamTaskEvent {
+            team_name: "zeta".to_string(),
+            task: task("task-1", TeamTaskStatus::Completed, Some("alice")),
+        });
+        state.on_task_created(&TeamTaskEvent {
+            team_name: "zeta".to_string(),
+            task: TeamTaskInfo {
+                description: Some("Cover the lexer.".to_string()),
+                depends_on: vec!["task-1".to_string(), "task-9".to_string()],
+                ..task("task-2", TeamTaskStatus::Pending, None)
+            },
+        });
+        state.on_task_updated(&TeamTaskEvent {
+            team_name: "zeta".to_string(),
+            task: TeamTaskInfo {
+                description: Some("Cover the lexer.".to_string()),
+                depends_on: vec!["task-1".to_string(), "task-9".to_string()],
+                result: Some("12 tests added.".to_string()),
+                ..task("task-2", TeamTaskStatus::Completed, Some("bob"))
+            },
+        });
+
+        assert_eq!(state.task_detail_lines("task-3"), None);
+        let rendered =
+            render_lines(&state.task_detail_lines("task-2").expect("task exists")).join("\n");
+        insta::assert_snapshot!(rendered);
+    }
+
+    #[test]
+    fn task_overlay_shows_progress_per_assignee_and_eta() {
+        let mut state = TeamState::default();
+        state.on_team_created(&TeamCreatedEvent {
+            team_name: "zeta".to_string(),
+            leader_thread_id: ThreadId::new(),
+            description: None,
+            display_mode: TeamDisplayMode::Inline,
+        });
+        for (id, status, assignee) in [
+            ("task-1", TeamTaskStatus::Completed, Some("alice")),
+            ("task-2", TeamTaskStatus::InProgress, Some("alice")),
+            ("task-3", TeamTaskStatus::Pending, Some("bob")),
+            ("task-4", TeamTaskStatus::Pending, None),
+        ] {
+            state.on_task_created(&TeamTaskEvent {
+                team_name: "zeta".to_string(),
+                task: TeamTaskInfo {
+                    id: id.to_string(),
+                    title: id.to_string(),
+                    description: None,
+                    status,
+                    assigned_to: assignee.map(str::to_string),
+                    depends_on: Vec::new(),
+                    result: None,
+                    created_at: None,
+                },
+            });
+        }
+
+        assert_eq!(
+            render_lines(&state.task_overlay_lines(&TaskFilter::default()))[7..],
+            [
+                format!("  {}{}  1/4 completed", "█".repeat(5), "░".repeat(15)),
+                "  alice  1/2 completed".to_string(),
+                "  bob  0/1 completed".to_string(),
+            ]
+        );
+
+        state.task_durations = BTreeMap::from([
+            ("task-1".to_string(), Duration::from_secs(60)),
+            ("task-5".to_string(), Duration::from_secs(180)),
+        ]);
+        let rendered = render_lines(&state.task_overlay_lines(&TaskFilter::default())).join("\n");
+        insta::assert_snapshot!(rendered);
+    }
+
+    #[test]
+    fn task_overlay_shows_teammate_changes() {
+        let mut state = TeamState::default();
+        state.on_team_created(&TeamCreatedEvent {
+            team_name: "zeta".to_string(),
+            leader_thread_id: ThreadId::new(),
+            description: None,
+            display_mode: TeamDisplayMode::Inline,
+        });
+        let file = |path: &str, added, removed| TeamFileDiff {
+            path: path.to_string(),
+            added,
+            removed,
+        };
+        state.on_diff_summary(&TeamDiffSummaryEvent {
+            team_name: "zeta".to_string(),
+            member_name: "bob".to_string(),
+            files: Vec::new(),
+        });
+        state.on_diff_summary(&TeamDiffSummaryEvent {
+            team_name: "zeta".to_string(),
+            member_name: "alice".to_string(),
+            files: vec![file("src/lib.rs", 40, 7), file("src/new.rs", 2, 0)],
+        });
+
+        assert_eq!(
+            render_lines(&state.task_overlay_lines(&TaskFilter::default())),
+            vec![
+                "Team: zeta".to_string(),
+                String::new(),
+                "  No tasks.".to_string(),
+                String::new(),
+                "Changes".to_string(),
+                "  alice  2 files +42 -7".to_string(),
+                "  bob  no changes".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn task_overlay_shows_latest_progress() {
+        let mut state = TeamState::default();
+        state.on_team_created(&TeamCreatedEvent {
+            team_name: "zeta".to_string(),
+            leader_thread_id: ThreadId::new(),
+            description: None,
+            display_mode: TeamDisplayMode::Inline,
+        });
+        let progress = TeamProgressEvent {
+            team_name: "zeta".to_string(),
+            tasks_completed: 0,
+            tasks_total: 0,
+            members_running: 2,
+            members_idle: 1,
+            members_errored: 0,
+            elapsed_secs: 200,
+        };
+        state.on_progress(&progress);
+        state.on_progress(&TeamProgressEvent {
+            team_name: "other".to_string(),
+            members_errored: 3,
+            ..progress
+        });
+
+        assert_eq!(
+            render_lines(&state.task_overlay_lines(&TaskFilter::default())),
+            vec![
+                "Team: zeta".to_string(),
+                "Teammates: 2 running, 1 idle, 0 errored · 3m 20s".to_string(),
+                String::new(),
+                "  No tasks.".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn task_overlay_shows_teammate_usage() {
+        let mut state = TeamState::default();
+        state.on_team_created(&TeamCreatedEvent {
+            team_name: "zeta".to_string(),
+            leader_thread_id: ThreadId::new(),
+            description: None,
+            display_mode: TeamDisplayMode::Inline,
+        });
+        let usage = |member_name: &str, total_tokens| TeamMemberTokenUsageEvent {
+            team_name: "zeta".to_string(),
+            member_name: member_name.to_string(),
+            thread_id: ThreadId::new(),
+            total_token_usage: TokenUsage {
+                input_tokens: total_tokens,
+                output_tokens: 0,
+                cached_input_tokens: 0,
+                reasoning_output_tokens: 0,
+                total_tokens,
+            },
+            last_token_usage: TokenUsage::default(),
+        };
+        state.on_member_token_usage(&usage("alice", 900));
+        state.on_member_token_usage(&usage("alice", 12_345));
+        state.on_member_token_usage(&TeamMemberTokenUsageEvent {
+            team_name: "other".to_string(),
+            ..usage("bob", 50)
+        });
+
+        assert_eq!(
+            render_lines(&state.task_overlay_lines(&TaskFilter::default())),
+            vec![
+                "Team: zeta".to_string(),
+                String::new(),
+                "  No tasks.".to_string(),
+                String::new(),
+                "Usage".to_string(),
+                "  alice  12,345 tokens".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn dashboard_lists_tokens_and_cost_per_teammate() {
+        let mut state = TeamState::default();
+        state.on_team_created(&TeamCreatedEvent {
+            team_name: "zeta".to_string(),
+            leader_thread_id: ThreadId::new(),
+            description: None,
+            display_mode: TeamDisplayMode::Inline,
+        });
+        for (member_name, input_tokens, output_tokens) in
+            [("alice", 1_200_000, 30_000), ("bob", 400_000, 5_000)]
+        {
+            state.on_member_token_usage(&TeamMemberTokenUsageEvent {
+                team_name: "zeta".to_string(),
+                member_name: member_name.to_string(),
+                thread_id: ThreadId::new(),
+                total_token_usage: TokenUsage {
+                    input_tokens,
+                    output_tokens,
+                    cached_input_tokens: 0,
+                    reasoning_output_tokens: 0,
+                    total_tokens: input_tokens + output_tokens,
+                },
+                last_token_usage: TokenUsage::default(),
+            });
+        }
+
+        let unpriced = render_lines(&state.dashboard_lines(&TeamsConfig::default())).join("\n");
+        insta::assert_snapshot!("dashboard_usage_without_prices", unpriced);
+        let priced = render_lines(&state.dashboard_lines(&TeamsConfig {
+            cents_per_million_input_tokens: Some(125),
+            cents_per_million_output_tokens: Some(1000),
+            ..TeamsConfig::default()
+        }))
+        .join("\n");
+        insta::assert_snapshot!("dashboard_usage_with_prices", priced);
+    }
+
+    #[test]
+    fn quiet_display_mode_hides_activity_until_cleanup() {
+        let mut state = TeamState::default();
+        assert!(state.shows_activity_in_transcript());
+
+        state.on_team_created(&TeamCreatedEvent {
+            team_name: "zeta".to_string(),
+            leader_thread_id: ThreadId::new(),
+            description: None,
+            display_mode: TeamDisplayMode::Quiet,
+        });
+        assert!(!state.shows_activity_in_transcript());
+
+        state.on_cleanup();
+        assert!(state.shows_activity_in_transcript());
+    }
+
+    #[test]
+    fn compact_display_mode_hides_lifecycle_events_too() {
+        let mut state = TeamState::default();
+        state.on_team_created(&TeamCreatedEvent {
+            team_name: "zeta".to_string(),
+            leader_thread_id: ThreadId::new(),
+            description: None,
+            display_mode: TeamDisplayMode::Quiet,
+        });
+        assert!(state.shows_team_in_transcript());
+
+        state.on_cleanup();
+        state.on_team_created(&TeamCreatedEvent {
+            team_name: "zeta".to_string(),
+            leader_thread_id: ThreadId::new(),
+            description: None,
+            display_mode: TeamDisplayMode::Compact,
+        });
+        assert!(!state.shows_activity_in_transcript());
+        assert!(!state.shows_team_in_transcript());
+    }
+}
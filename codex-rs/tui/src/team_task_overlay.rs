@@ -0,0 +1,302 @@
+//! Searchable, filterable team task overlay.
+//!
+//! Wraps the selectable [`StaticOverlay`] of the team's tasks (see
+//! [`Overlay::new_team_tasks`](crate::pager_overlay::Overlay::new_team_tasks))
+//! and re-renders it from a snapshot of the [`TeamState`] whenever the
+//! search text or the status and teammate filters change, so large teams
+//! stay navigable without reading anything from disk.
+
+use std::io::Result;
+
+use crate::key_hint;
+use crate::key_hint::KeyBinding;
+use crate::pager_overlay::OverlayAction;
+use crate::pager_overlay::StaticOverlay;
+use crate::pager_overlay::render_key_hints;
+use crate::team_events::TaskFilter;
+use crate::team_events::TeamState;
+use crate::tui;
+use crate::tui::TuiEvent;
+use codex_core::protocol::TeamTaskStatus;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+
+const KEY_SEARCH: KeyBinding = key_hint::plain(KeyCode::Char('/'));
+const KEY_STATUS: KeyBinding = key_hint::plain(KeyCode::Char('s'));
+const KEY_MEMBER: KeyBinding = key_hint::plain(KeyCode::Char('m'));
+const KEY_ENTER: KeyBinding = key_hint::plain(KeyCode::Enter);
+const KEY_ESC: KeyBinding = key_hint::plain(KeyCode::Esc);
+
+pub(crate) struct TeamTaskOverlay {
+    pub(crate) overlay: StaticOverlay,
+    state: TeamState,
+    filter: TaskFilter,
+}
+
+impl TeamTaskOverlay {
+    pub(crate) fn new(state: TeamState, actions: Vec<OverlayAction>) -> Self {
+        let mut overlay = Self {
+            overlay: StaticOverlay::with_actions("T E A M   T A S K S".to_string(), actions),
+            state,
+            filter: TaskFilter::default(),
+        };
+        overlay.refresh();
+        overlay
+    }
+
+    fn refresh(&mut self) {
+        self.overlay.set_selection(
+            self.state.task_overlay_lines(&self.filter),
+            self.state.task_overlay_rows(&self.filter),
+        );
+    }
+
+    /// Show the next task status, after the last one all statuses again.
+    fn cycle_status(&mut self) {
+        self.filter.status = match self.filter.status {
+            None => Some(TeamTaskStatus::Pending),
+            Some(TeamTaskStatus::Pending) => Some(TeamTaskStatus::InProgress),
+            Some(TeamTaskStatus::InProgress) => Some(TeamTaskStatus::Completed),
+            Some(TeamTaskStatus::Completed) => None,
+        };
+    }
+
+    /// Show the tasks of the next assignee in name order, after the last one
+    /// the tasks of everyone again.
+    fn cycle_assignee(&mut self) {
+        let mut assignees: Vec<&String> = self
+            .state
+            .tasks
+            .iter()
+            .filter_map(|task| task.assigned_to.as_ref())
+            .collect();
+        assignees.sort();
+        assignees.dedup();
+        self.filter.assignee = match &self.filter.assignee {
+            None => assignees.first().map(|name| (*name).clone()),
+            Some(current) => assignees.into_iter().find(|name| *name > current).cloned(),
+        };
+    }
+
+    /// Apply a key typed while the search text is being edited. Returns
+    /// whether the key was used.
+    fn handle_search_key(&mut self, key_event: crossterm::event::KeyEvent) -> bool {
+        if KEY_ENTER.is_press(key_event) {
+            self.filter.searching = false;
+        } else if KEY_ESC.is_press(key_event) {
+            self.filter.query.clear();
+            self.filter.searching = false;
+        } else if !matches!(key_event.kind, KeyEventKind::Press | KeyEventKind::Repeat) {
+            return false;
+        } else {
+            match key_event.code {
+                KeyCode::Backspace => {
+                    self.filter.query.pop();
+                }
+                KeyCode::Char(c)
+                    if key_event.modifiers == KeyModifiers::NONE
+                        || key_event.modifiers == KeyModifiers::SHIFT =>
+                {
+                    self.filter.query.push(c);
+                }
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
+        match event {
+            TuiEvent::Key(key_event) => {
+                let handled = if self.filter.searching {
+                    self.handle_search_key(key_event)
+                } else if KEY_SEARCH.is_press(key_event) {
+                    self.filter.searching = true;
+                    true
+                } else if KEY_STATUS.is_press(key_event) {
+                    self.cycle_status();
+                    true
+                } else if KEY_MEMBER.is_press(key_event) {
+                    self.cycle_assignee();
+                    true
+                } else {
+                    false
+                };
+                if handled {
+                    self.refresh();
+                    tui.frame_requester().schedule_frame();
+                    Ok(())
+                } else {
+                    self.overlay.handle_event(tui, TuiEvent::Key(key_event))
+                }
+            }
+            TuiEvent::Draw => {
+                tui.draw(u16::MAX, |frame| {
+                    self.render(frame.area(), frame.buffer);
+                })?;
+                Ok(())
+            }
+            other => self.overlay.handle_event(tui, other),
+        }
+    }
+
+    pub(crate) fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        self.overlay.render(area, buf);
+        let hints = Rect::new(
+            area.x,
+            area.y + area.height.saturating_sub(1),
+            area.width,
+            area.height.min(1),
+        );
+        if self.filter.searching {
+            render_key_hints(
+                hints,
+                buf,
+                &[
+                    (&[KEY_ENTER], "to apply"),
+                    (&[KEY_ESC], "to clear the search"),
+                ],
+            );
+        } else {
+            render_key_hints(
+                hints,
+                buf,
+                &[
+                    (&[KEY_SEARCH], "to search"),
+                    (&[KEY_STATUS], "to filter by status"),
+                    (&[KEY_MEMBER], "to filter by teammate"),
+                ],
+            );
+        }
+    }
+
+    pub(crate) fn is_done(&self) -> bool {
+        self.overlay.is_done()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::protocol::TeamCreatedEvent;
+    use codex_core::protocol::TeamDisplayMode;
+    use codex_core::protocol::TeamTaskEvent;
+    use codex_core::protocol::TeamTaskInfo;
+    use codex_protocol::ThreadId;
+    use crossterm::event::KeyEvent;
+    use pretty_assertions::assert_eq;
+
+    fn overlay() -> TeamTaskOverlay {
+        let mut state = TeamState::default();
+        state.on_team_created(&TeamCreatedEvent {
+            team_name: "zeta".to_string(),
+            leader_thread_id: ThreadId::new(),
+            description: None,
+            display_mode: TeamDisplayMode::Inline,
+        });
+        for (id, title, status, assignee) in [
+            ("task-1", "Plan", TeamTaskStatus::Completed, Some("bob")),
+            (
+                "task-2",
+                "Fix the parser",
+                TeamTaskStatus::InProgress,
+                Some("alice"),
+            ),
+            (
+                "task-3",
+                "Parser docs",
+                TeamTaskStatus::Pending,
+                Some("bob"),
+            ),
+            ("task-4", "Fix lint", TeamTaskStatus::Pending, None),
+        ] {
+            state.on_task_created(&TeamTaskEvent {
+                team_name: "zeta".to_string(),
+                task: TeamTaskInfo {
+                    id: id.to_string(),
+                    title: title.to_string(),
+                    description: None,
+                    status,
+                    assigned_to: assignee.map(str::to_string),
+                    depends_on: Vec::new(),
+                    result: None,
+                    created_at: None,
+                },
+            });
+        }
+        TeamTaskOverlay::new(state, Vec::new())
+    }
+
+    fn shown_tasks(overlay: &TeamTaskOverlay) -> Vec<String> {
+        overlay
+            .state
+            .task_overlay_rows(&overlay.filter)
+            .into_iter()
+            .map(|(_, id)| id)
+            .collect()
+    }
+
+    fn overlay_text(overlay: &TeamTaskOverlay) -> Vec<String> {
+        overlay
+            .state
+            .task_overlay_lines(&overlay.filter)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn filters_cycle_through_statuses_and_assignees() {
+        let mut overlay = overlay();
+        overlay.cycle_status();
+        assert_eq!(shown_tasks(&overlay), vec!["task-3", "task-4"]);
+        overlay.cycle_assignee();
+        assert_eq!(overlay.filter.assignee.as_deref(), Some("alice"));
+        assert_eq!(shown_tasks(&overlay), Vec::<String>::new());
+        overlay.cycle_assignee();
+        assert_eq!(shown_tasks(&overlay), vec!["task-3"]);
+        let lines = overlay_text(&overlay);
+        insta::assert_snapshot!(
+            "task_overlay_filtered_by_status_and_assignee",
+            lines.join("\n")
+        );
+        let (row, _) = overlay.state.task_overlay_rows(&overlay.filter)[0];
+        assert!(lines[row].contains("[task-3]"), "{lines:?}");
+        overlay.cycle_assignee();
+        assert_eq!(overlay.filter.assignee, None);
+        for _ in 0..3 {
+            overlay.cycle_status();
+        }
+        assert_eq!(overlay.filter.status, None);
+        assert_eq!(shown_tasks(&overlay).len(), 4);
+    }
+
+    #[test]
+    fn search_matches_titles_case_insensitively() {
+        let mut overlay = overlay();
+        overlay.filter.searching = true;
+        for c in "PARSER".chars() {
+            assert!(
+                overlay.handle_search_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::SHIFT))
+            );
+        }
+        assert_eq!(shown_tasks(&overlay), vec!["task-2", "task-3"]);
+        insta::assert_snapshot!("task_overlay_searching", overlay_text(&overlay).join("\n"));
+
+        assert!(overlay.handle_search_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)));
+        assert!(!overlay.filter.searching);
+        assert_eq!(overlay.filter.query, "PARSER");
+
+        overlay.filter.searching = true;
+        assert!(overlay.handle_search_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)));
+        assert_eq!(overlay.filter, TaskFilter::default());
+    }
+}
@@ -0,0 +1,361 @@
+//! Split-pane view of teammate output.
+//!
+//! For a team in split-pane display mode, the chat widget shows panes above
+//! the composer, each streaming the forwarded output of one teammate
+//! (`TeamMemberOutput` events) while the leader's transcript keeps scrolling
+//! above them. Alt+1, Alt+2, ... switch the teammate shown in a pane.
+
+use crate::exec_command::strip_bash_lc_and_escape;
+use crate::render::renderable::Renderable;
+use codex_core::protocol::TeamMemberOutput;
+use codex_core::protocol::TeamMemberOutputEvent;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Constraint;
+use ratatui::layout::Layout;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Block;
+use ratatui::widgets::Borders;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget;
+use std::collections::VecDeque;
+
+/// How many panes are shown side by side, at most.
+pub(crate) const MAX_PANES: usize = 2;
+
+/// Height of the panes, borders included.
+const PANE_HEIGHT: u16 = 10;
+
+/// How many lines of output are kept per teammate.
+const MAX_OUTPUT_LINES: usize = 500;
+
+/// Output received from one teammate.
+#[derive(Debug, Default)]
+struct TeammateOutput {
+    name: String,
+    lines: VecDeque<Line<'static>>,
+    /// Whether streamed text is still being appended to the last line.
+    open: bool,
+    /// Whether the agent message being received was streamed as deltas, so
+    /// the complete message that follows is already shown.
+    streamed_message: bool,
+}
+
+impl TeammateOutput {
+    fn push_line(&mut self, line: Line<'static>) {
+        if self.lines.len() == MAX_OUTPUT_LINES {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+    }
+
+    /// Append streamed `text`, starting a new line at each newline.
+    fn append_text(&mut self, text: &str, style: Style) {
+        for (i, piece) in text.split('\n').enumerate() {
+            let span = Span::styled(piece.trim_end_matches('\r').to_string(), style);
+            match self.lines.back_mut() {
+                Some(line) if i == 0 && self.open => line.push_span(span),
+                _ => self.push_line(Line::from(span)),
+            }
+        }
+        self.open = true;
+    }
+
+    /// End the streamed line, if any, dropping it when nothing was written
+    /// after its newline.
+    fn close_line(&mut self) {
+        if self.open && self.lines.back().is_some_and(|line| line.width() == 0) {
+            self.lines.pop_back();
+        }
+        self.open = false;
+    }
+
+    fn on_output(&mut self, output: &TeamMemberOutput) {
+        match output {
+            TeamMemberOutput::AgentMessageDelta { delta } => {
+                self.append_text(delta, Style::default());
+                self.streamed_message = true;
+            }
+            TeamMemberOutput::AgentMessage { message } => {
+                self.close_line();
+                if !self.streamed_message {
+                    for line in message.lines() {
+                        self.push_line(Line::from(line.to_string()));
+                    }
+                }
+                self.streamed_message = false;
+            }
+            TeamMemberOutput::CommandBegin { command, .. } => {
+                self.close_line();
+                self.push_line(Line::from(vec![
+                    "$ ".dim(),
+                    strip_bash_lc_and_escape(command).cyan(),
+                ]));
+            }
+            TeamMemberOutput::CommandOutputDelta { chunk, .. } => {
+                self.append_text(chunk, Style::default().dim());
+            }
+            TeamMemberOutput::CommandEnd { exit_code, .. } => {
+                self.close_line();
+                let status = if *exit_code == 0 {
+                    "✓ exited 0".green()
+                } else {
+                    format!("✗ exited {exit_code}").red()
+                };
+                self.push_line(Line::from(status));
+            }
+        }
+    }
+}
+
+/// Teammate output in the leader's session and the teammates shown in panes.
+#[derive(Debug, Default)]
+pub(crate) struct TeammatePanes {
+    /// Output of each teammate, in the order the teammates joined.
+    outputs: Vec<TeammateOutput>,
+    /// Name of the teammate shown in each pane.
+    panes: Vec<String>,
+}
+
+impl TeammatePanes {
+    /// Track teammate `name`, giving it a pane if one is free.
+    pub(crate) fn on_member_added(&mut self, name: &str) {
+        if self.outputs.iter().any(|output| output.name == name) {
+            return;
+        }
+        self.outputs.push(TeammateOutput {
+            name: name.to_string(),
+            ..Default::default()
+        });
+        if self.panes.len() < MAX_PANES {
+            self.panes.push(name.to_string());
+        }
+    }
+
+    /// Forget teammate `name`; its pane shows the next teammate not shown in
+    /// another pane, or is closed.
+    pub(crate) fn on_member_removed(&mut self, name: &str) {
+        self.outputs.retain(|output| output.name != name);
+        let Some(index) = self.panes.iter().position(|pane| pane == name) else {
+            return;
+        };
+        match self.next_teammate(name) {
+            Some(next) => self.panes[index] = next,
+            None => {
+                self.panes.remove(index);
+            }
+        }
+    }
+
+    pub(crate) fn on_member_output(&mut self, ev: &TeamMemberOutputEvent) {
+        self.on_member_added(&ev.member_name);
+        if let Some(output) = self
+            .outputs
+            .iter_mut()
+            .find(|output| output.name == ev.member_name)
+        {
+            output.on_output(&ev.output);
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.outputs.clear();
+        self.panes.clear();
+    }
+
+    /// Show the next teammate in pane `index`. Returns whether the pane
+    /// exists.
+    pub(crate) fn cycle_pane(&mut self, index: usize) -> bool {
+        let Some(current) = self.panes.get(index) else {
+            return false;
+        };
+        if let Some(next) = self.next_teammate(current) {
+            self.panes[index] = next;
+        }
+        true
+    }
+
+    /// The teammate after `name` in join order that no pane shows.
+    fn next_teammate(&self, name: &str) -> Option<String> {
+        let start = self
+            .outputs
+            .iter()
+            .position(|output| output.name == name)
+            .map_or(0, |i| i + 1);
+        (0..self.outputs.len())
+            .map(|offset| &self.outputs[(start + offset) % self.outputs.len()].name)
+            .find(|candidate| !self.panes.contains(*candidate))
+            .cloned()
+    }
+}
+
+impl Renderable for TeammatePanes {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if self.panes.is_empty() || area.is_empty() {
+            return;
+        }
+        let areas = Layout::horizontal(vec![Constraint::Fill(1); self.panes.len()]).split(area);
+        for (index, (name, pane_area)) in self.panes.iter().zip(areas.iter()).enumerate() {
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().dim())
+                .title(Line::from(vec![
+                    format!(" {name} ").bold(),
+                    format!("alt+{} ", index + 1).dim(),
+                ]));
+            let inner = block.inner(*pane_area);
+            block.render(*pane_area, buf);
+            let Some(output) = self.outputs.iter().find(|output| &output.name == name) else {
+                continue;
+            };
+            let skip = output.lines.len().saturating_sub(usize::from(inner.height));
+            let lines: Vec<Line<'static>> = output.lines.iter().skip(skip).cloned().collect();
+            if lines.is_empty() {
+                Paragraph::new(Line::from("No output yet.").dim()).render(inner, buf);
+            } else {
+                Paragraph::new(lines).render(inner, buf);
+            }
+        }
+    }
+
+    fn desired_height(&self, _width: u16) -> u16 {
+        if self.panes.is_empty() {
+            0
+        } else {
+            PANE_HEIGHT
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::ThreadId;
+    use insta::assert_snapshot;
+    use pretty_assertions::assert_eq;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    fn output_event(member_name: &str, output: TeamMemberOutput) -> TeamMemberOutputEvent {
+        TeamMemberOutputEvent {
+            team_name: "zeta".to_string(),
+            member_name: member_name.to_string(),
+            thread_id: ThreadId::new(),
+            output,
+        }
+    }
+
+    fn output_text(panes: &TeammatePanes, name: &str) -> Vec<String> {
+        let output = panes
+            .outputs
+            .iter()
+            .find(|output| output.name == name)
+            .expect("teammate output");
+        output
+            .lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn streamed_output_is_joined_into_lines() {
+        let mut panes = TeammatePanes::default();
+        for output in [
+            TeamMemberOutput::AgentMessageDelta {
+                delta: "Looking at ".to_string(),
+            },
+            TeamMemberOutput::AgentMessageDelta {
+                delta: "the parser\nnow".to_string(),
+            },
+            TeamMemberOutput::AgentMessage {
+                message: "Looking at the parser\nnow".to_string(),
+            },
+            TeamMemberOutput::CommandBegin {
+                call_id: "call-1".to_string(),
+                command: vec!["cargo".to_string(), "test".to_string()],
+            },
+            TeamMemberOutput::CommandOutputDelta {
+                call_id: "call-1".to_string(),
+                chunk: "running 2 tests\r\nok\n".to_string(),
+            },
+            TeamMemberOutput::CommandEnd {
+                call_id: "call-1".to_string(),
+                exit_code: 101,
+            },
+            TeamMemberOutput::AgentMessage {
+                message: "Tests fail.".to_string(),
+            },
+        ] {
+            panes.on_member_output(&output_event("alice", output));
+        }
+
+        assert_eq!(
+            output_text(&panes, "alice"),
+            vec![
+                "Looking at the parser".to_string(),
+                "now".to_string(),
+                "$ cargo test".to_string(),
+                "running 2 tests".to_string(),
+                "ok".to_string(),
+                "✗ exited 101".to_string(),
+                "Tests fail.".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn panes_show_each_teammate_side_by_side() {
+        let mut panes = TeammatePanes::default();
+        panes.on_member_added("alice");
+        panes.on_member_added("bob");
+        for output in [
+            TeamMemberOutput::CommandBegin {
+                call_id: "call-1".to_string(),
+                command: vec!["cargo".to_string(), "test".to_string()],
+            },
+            TeamMemberOutput::CommandEnd {
+                call_id: "call-1".to_string(),
+                exit_code: 0,
+            },
+            TeamMemberOutput::AgentMessage {
+                message: "Tests pass.".to_string(),
+            },
+        ] {
+            panes.on_member_output(&output_event("alice", output));
+        }
+
+        let mut term = Terminal::new(TestBackend::new(60, PANE_HEIGHT)).expect("term");
+        term.draw(|f| panes.render(f.area(), f.buffer_mut()))
+            .expect("draw");
+        assert_snapshot!(term.backend());
+    }
+
+    #[test]
+    fn panes_cycle_through_teammates_not_shown_elsewhere() {
+        let mut panes = TeammatePanes::default();
+        for name in ["alice", "bob", "carol"] {
+            panes.on_member_added(name);
+        }
+        assert_eq!(panes.panes, vec!["alice".to_string(), "bob".to_string()]);
+
+        assert!(panes.cycle_pane(0));
+        assert_eq!(panes.panes, vec!["carol".to_string(), "bob".to_string()]);
+        assert!(panes.cycle_pane(0));
+        assert_eq!(panes.panes, vec!["alice".to_string(), "bob".to_string()]);
+        assert!(!panes.cycle_pane(MAX_PANES));
+
+        panes.on_member_removed("bob");
+        assert_eq!(panes.panes, vec!["alice".to_string(), "carol".to_string()]);
+        panes.on_member_removed("carol");
+        assert_eq!(panes.panes, vec!["alice".to_string()]);
+    }
+}